@@ -49,6 +49,7 @@ use lenient_bool::LenientBool;
 use std::collections::{BTreeSet, HashMap};
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Golem Command Line Interface
 #[derive(Debug, Parser)]
@@ -903,6 +904,7 @@ pub mod component {
     use crate::model::worker::AgentUpdateMode;
     use clap::Subcommand;
     use golem_common::model::component::{ComponentName, ComponentRevision};
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum ComponentSubcommand {
@@ -953,6 +955,19 @@ pub mod component {
             #[command(flatten)]
             component_name: OptionalComponentNames,
         },
+        /// Build a self-contained bundle of the component, including its pre-compiled binary, for installing it without the compilation service
+        Bundle {
+            #[command(flatten)]
+            component_name: OptionalComponentName,
+            /// Path of the created bundle, defaults to <COMPONENT_NAME>.bundle.zip in the current directory
+            #[arg(long, short)]
+            output: Option<PathBuf>,
+        },
+        /// Import a component bundle into the current environment
+        ImportBundle {
+            /// Path of the component bundle
+            bundle: PathBuf,
+        },
     }
 
     pub mod plugin {
@@ -1016,15 +1031,21 @@ pub mod component {
 
 pub mod worker {
     use crate::command::parse_cursor;
+    use crate::command::parse_instant;
+    use crate::command::parse_interval;
     use crate::command::parse_key_val;
     use crate::command::shared_args::{
         AgentIdArgs, DeployArgs, StreamArgs, WorkerFunctionArgument, WorkerFunctionName,
     };
-    use crate::model::worker::AgentUpdateMode;
+    use crate::command::worker::bulk::AgentBulkSubcommand;
+    use crate::command::worker::oplog::AgentOplogSubcommand;
+    use crate::model::worker::{AgentUpdateMode, WorkerName};
+    use chrono::{DateTime, Utc};
     use clap::Subcommand;
     use golem_client::model::ScanCursor;
     use golem_common::model::component::{ComponentName, ComponentRevision};
     use golem_common::model::IdempotencyKey;
+    use std::time::Duration;
 
     #[derive(Debug, Subcommand)]
     pub enum AgentSubcommand {
@@ -1081,7 +1102,7 @@ pub mod worker {
 
             /// Filter for agent metadata in form of `property op value`.
             ///
-            /// Filter examples: `name = my-agent(1, 2, 3)`, `version >= 0`, `status = Running`, `env.var1 = value`.
+            /// Filter examples: `name = my-agent(1, 2, 3)`, `version >= 0`, `status = Running`, `env.var1 = value`, `label.tenant = acme`, `consumed_fuel > 1000000`.
             /// Can be used multiple times (AND condition is applied between them)
             #[arg(long)]
             filter: Vec<String>,
@@ -1136,10 +1157,19 @@ pub mod worker {
             #[command(flatten)]
             agent_id: AgentIdArgs,
         },
-        /// Queries and dumps an agent's full oplog
+        /// Queries and dumps an agent's full oplog, or exports and imports it as a portable archive
+        #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
         Oplog {
-            #[command(flatten)]
-            agent_id: AgentIdArgs,
+            #[command(subcommand)]
+            subcommand: Option<AgentOplogSubcommand>,
+            // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
+            /// Agent ID, accepted formats:
+            ///   - <AGENT_TYPE>(<AGENT_PARAMETERS>)
+            ///   - <COMPONENT>/<AGENT_TYPE>(<AGENT_PARAMETERS>)
+            ///   - <PROJECT>/<COMPONENT>/<AGENT_TYPE>(<AGENT_PARAMETERS>)
+            ///   - <ACCOUNT>/<PROJECT>/<COMPONENT>/<AGENT_TYPE>(<AGENT_PARAMETERS>)
+            #[arg(verbatim_doc_comment, required = true)]
+            agent_id: Option<WorkerName>,
             /// Index of the first oplog entry to get. If missing, the whole oplog is returned
             #[arg(long, conflicts_with = "query")]
             from: Option<u64>,
@@ -1158,6 +1188,22 @@ pub mod worker {
             #[arg(long, conflicts_with = "last_oplog_index")]
             number_of_invocations: Option<u64>,
         },
+        /// Sets and removes labels of an agent
+        Label {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+            /// Label to set, replacing its previous value; can be defined multiple times
+            #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL", required_unless_present = "remove")]
+            set: Vec<(String, String)>,
+            /// Key of a label to remove; can be defined multiple times
+            #[arg(long, value_name = "KEY")]
+            remove: Vec<String>,
+        },
+        /// Applies an operation to all agents matching the given filters, executed in the background by the server
+        Bulk {
+            #[command(subcommand)]
+            subcommand: AgentBulkSubcommand,
+        },
         /// Cancels an enqueued invocation if it has not started yet
         CancelInvocation {
             #[command(flatten)]
@@ -1165,6 +1211,42 @@ pub mod worker {
             /// Idempotency key of the invocation to be cancelled
             idempotency_key: IdempotencyKey,
         },
+        /// Schedule a recurring invocation of an agent function, repeated with a fixed interval or by a cron expression
+        ScheduleRecurring {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+            /// Agent function name to invoke on each occurrence
+            function_name: WorkerFunctionName,
+            /// Agent function arguments in WAVE format
+            arguments: Vec<WorkerFunctionArgument>,
+            /// Repeat the invocation with a fixed interval, e.g. `90s`, `15m`, `1h` or `1d`
+            #[arg(long, value_parser = parse_interval, conflicts_with = "cron", required_unless_present = "cron")]
+            every: Option<Duration>,
+            /// Repeat the invocation according to a cron expression (with seconds), evaluated in UTC, e.g. `0 0 * * * *`
+            #[arg(long, conflicts_with = "every")]
+            cron: Option<String>,
+            /// Time of the first occurrence in RFC 3339 format, defaults to the current time
+            #[arg(long, value_parser = parse_instant)]
+            start_at: Option<DateTime<Utc>>,
+            /// No occurrences are scheduled after this time, in RFC 3339 format
+            #[arg(long, value_parser = parse_instant)]
+            until: Option<DateTime<Utc>>,
+            /// Set idempotency key for the recurring invocation, use "-" for an auto-generated key
+            #[clap(long, short)]
+            idempotency_key: Option<IdempotencyKey>,
+        },
+        /// List the recurring invocations of an agent
+        ListRecurring {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+        },
+        /// Cancels a recurring invocation, occurrences already enqueued are not affected
+        CancelRecurring {
+            #[command(flatten)]
+            agent_id: AgentIdArgs,
+            /// Idempotency key of the recurring invocation to be cancelled
+            idempotency_key: IdempotencyKey,
+        },
         /// List files in a worker's directory
         Files {
             #[command(flatten)]
@@ -1184,6 +1266,141 @@ pub mod worker {
             output: Option<String>,
         },
     }
+
+    pub mod oplog {
+        use crate::command::shared_args::AgentIdArgs;
+        use clap::Subcommand;
+        use std::path::PathBuf;
+
+        #[derive(Debug, Subcommand)]
+        pub enum AgentOplogSubcommand {
+            /// Export an agent's oplog, including its external payloads and initial files, into a portable archive
+            Export {
+                #[command(flatten)]
+                agent_id: AgentIdArgs,
+                /// Local path of the created archive
+                #[arg(long, short)]
+                output: PathBuf,
+            },
+            /// Create a new agent from a previously exported oplog archive
+            Import {
+                #[command(flatten)]
+                agent_id: AgentIdArgs,
+                /// Local path of the archive to import
+                archive: PathBuf,
+            },
+        }
+    }
+
+    pub mod bulk {
+        use crate::model::worker::AgentUpdateMode;
+        use clap::{Args, Subcommand};
+        use golem_common::model::component::{ComponentName, ComponentRevision};
+        use golem_common::model::worker::BulkWorkerOperationId;
+
+        #[derive(Debug, Args)]
+        pub struct AgentBulkTargetArgs {
+            /// Optional filter for a specific agent type
+            #[arg(conflicts_with = "component_name")]
+            pub agent_type_name: Option<String>,
+            /// Optional filter for a specific component
+            #[arg(long, conflicts_with = "agent_type_name")]
+            pub component_name: Option<ComponentName>,
+            /// Filter for agent metadata in form of `property op value`, same as for `agent list`.
+            ///
+            /// Can be used multiple times (AND condition is applied between them)
+            #[arg(long)]
+            pub filter: Vec<String>,
+            /// Maximum number of agents processed concurrently, limited by the server configuration
+            #[arg(long)]
+            pub max_concurrency: Option<u32>,
+            /// Maximum number of agents processed per second, limited by the server configuration
+            #[arg(long)]
+            pub max_rate: Option<u32>,
+            /// Await the operation to be finished
+            #[arg(long, default_value_t = false)]
+            pub r#await: bool,
+        }
+
+        #[derive(Debug, Subcommand)]
+        pub enum AgentBulkSubcommand {
+            /// Interrupts all matching agents
+            Interrupt {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+                /// Start recovering the interrupted agents immediately, simulating a crash
+                #[arg(long, default_value_t = false)]
+                recover_immediately: bool,
+            },
+            /// Resumes all matching agents
+            Resume {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+            },
+            /// Updates all matching agents
+            Update {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+                /// Update mode - auto or manual (default is auto)
+                #[arg(long)]
+                mode: Option<AgentUpdateMode>,
+                /// The new revision of the updated agents (default is the latest revision)
+                #[arg(long)]
+                target_revision: Option<ComponentRevision>,
+            },
+            /// Deletes all matching agents
+            Delete {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+            },
+            /// Reverts all matching agents by undoing their last recorded operations
+            Revert {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+                /// Revert by oplog index
+                #[arg(long, conflicts_with = "number_of_invocations")]
+                last_oplog_index: Option<u64>,
+                /// Revert by number of invocations
+                #[arg(long, conflicts_with = "last_oplog_index")]
+                number_of_invocations: Option<u64>,
+            },
+            /// Activates a plugin installation on all matching agents
+            ActivatePlugin {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+                /// Priority of the plugin installation to activate
+                #[arg(long)]
+                plugin_priority: i32,
+            },
+            /// Deactivates a plugin installation on all matching agents
+            DeactivatePlugin {
+                #[command(flatten)]
+                targets: AgentBulkTargetArgs,
+                /// Priority of the plugin installation to deactivate
+                #[arg(long)]
+                plugin_priority: i32,
+            },
+            /// Shows the progress of a bulk operation
+            Status {
+                /// Component the bulk operation was started for
+                #[arg(long)]
+                component_name: Option<ComponentName>,
+                /// ID of the bulk operation
+                operation_id: BulkWorkerOperationId,
+                /// Await the operation to be finished
+                #[arg(long, default_value_t = false)]
+                r#await: bool,
+            },
+            /// Cancels a bulk operation, agents already processed are not affected
+            Cancel {
+                /// Component the bulk operation was started for
+                #[arg(long)]
+                component_name: Option<ComponentName>,
+                /// ID of the bulk operation
+                operation_id: BulkWorkerOperationId,
+            },
+        }
+    }
 }
 
 pub mod api {
@@ -1263,7 +1480,9 @@ pub mod api {
     }
 
     pub mod deployment {
+        use crate::model::OpenApiDefinitionOutputFormat;
         use clap::Subcommand;
+        use url::Url;
 
         #[derive(Debug, Subcommand)]
         pub enum ApiDeploymentSubcommand {
@@ -1274,12 +1493,28 @@ pub mod api {
             },
             /// List API deployment for API definition
             List,
+            /// Export the OpenAPI spec served by an API deployment
+            OpenApi {
+                /// Deployment domain
+                domain: String,
+                /// Output format (json or yaml)
+                #[arg(long = "def-format", default_value = "yaml", name = "def-format")]
+                format: OpenApiDefinitionOutputFormat,
+                /// Custom output file name (without extension)
+                #[arg(short, long)]
+                output_name: Option<String>,
+                /// Base URL to fetch the spec from instead of the domain itself, e.g. http://localhost:9006
+                #[arg(long)]
+                base_url: Option<Url>,
+            },
         }
     }
 
     pub mod security_scheme {
         use clap::Subcommand;
-        use golem_common::model::security_scheme::{Provider, SecuritySchemeName};
+        use golem_common::model::security_scheme::{
+            Provider, SecuritySchemeMode, SecuritySchemeName,
+        };
 
         #[derive(Debug, Subcommand)]
         pub enum ApiSecuritySchemeSubcommand {
@@ -1287,9 +1522,12 @@ pub mod api {
             Create {
                 /// Security Scheme name
                 security_scheme_name: SecuritySchemeName,
-                /// Security Scheme provider (Google, Facebook, Gitlab, Microsoft)
+                /// Security Scheme provider (Google, Facebook, Gitlab, Microsoft, Custom)
                 #[arg(long)]
                 provider_type: Provider,
+                /// OpenID Connect issuer URL, required for the Custom provider
+                #[arg(long, required_if_eq("provider_type", "custom"))]
+                issuer_url: Option<String>,
                 /// Security Scheme client ID
                 #[arg(long)]
                 client_id: String,
@@ -1302,6 +1540,12 @@ pub mod api {
                 #[arg(long)]
                 /// Security Scheme redirect URL
                 redirect_url: String,
+                /// How callers authenticate: authorization-code (default) or bearer-token
+                #[arg(long)]
+                mode: Option<SecuritySchemeMode>,
+                /// Audience expected in bearer tokens, defaults to the client ID
+                #[arg(long)]
+                audience: Option<String>,
             },
 
             /// Get HTTP API Security Scheme
@@ -1632,6 +1876,38 @@ fn parse_cursor(cursor: &str) -> anyhow::Result<ScanCursor> {
     })
 }
 
+fn parse_interval(interval: &str) -> anyhow::Result<Duration> {
+    let interval = interval.trim();
+    let split_at = interval
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(interval.len());
+    let (amount, unit) = interval.split_at(split_at);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| anyhow!("Invalid interval: {}", interval.log_color_error_highlight()))?;
+    let unit_millis: u64 = match unit {
+        "ms" => 1,
+        "s" | "" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        _ => bail!(
+            "Invalid interval unit in {}, expected one of ms, s, m, h, d",
+            interval.log_color_error_highlight()
+        ),
+    };
+    let millis = amount.checked_mul(unit_millis).ok_or_else(|| {
+        anyhow!(
+            "Interval is too long: {}",
+            interval.log_color_error_highlight()
+        )
+    })?;
+    if millis == 0 {
+        bail!("Interval must be greater than zero");
+    }
+    Ok(Duration::from_millis(millis))
+}
+
 fn parse_instant(
     s: &str,
 ) -> Result<DateTime<Utc>, Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -1644,7 +1920,7 @@ fn parse_instant(
 #[cfg(test)]
 mod test {
     use crate::command::{
-        builtin_exec_subcommands, help_target_to_subcommand_names, GolemCliCommand,
+        builtin_exec_subcommands, help_target_to_subcommand_names, parse_interval, GolemCliCommand,
     };
     use crate::error::ShowClapHelpTarget;
     use assert2::assert;
@@ -1652,6 +1928,7 @@ mod test {
     use clap::{Command, CommandFactory};
     use itertools::Itertools;
     use std::collections::{BTreeMap, BTreeSet};
+    use std::time::Duration;
     use strum::IntoEnumIterator;
     use test_r::test;
 
@@ -1885,4 +2162,18 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn parse_interval_units() {
+        assert!(parse_interval("250ms").unwrap() == Duration::from_millis(250));
+        assert!(parse_interval("90").unwrap() == Duration::from_secs(90));
+        assert!(parse_interval("90s").unwrap() == Duration::from_secs(90));
+        assert!(parse_interval("15m").unwrap() == Duration::from_secs(15 * 60));
+        assert!(parse_interval("2h").unwrap() == Duration::from_secs(2 * 60 * 60));
+        assert!(parse_interval("1d").unwrap() == Duration::from_secs(24 * 60 * 60));
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("5w").is_err());
+        assert!(parse_interval("m").is_err());
+        assert!(parse_interval("18446744073709551615d").is_err());
+    }
 }
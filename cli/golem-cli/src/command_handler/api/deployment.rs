@@ -19,6 +19,7 @@ use crate::error::service::{AnyhowMapServiceError, ServiceError};
 use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
 use crate::model::environment::{EnvironmentResolveMode, ResolvedEnvironmentIdentity};
 use crate::model::text::http_api_deployment::HttpApiDeploymentGetView;
+use crate::model::OpenApiDefinitionOutputFormat;
 use anyhow::{anyhow, bail};
 use golem_client::api::ApiDeploymentClient;
 use golem_common::cache::SimpleCache;
@@ -33,6 +34,10 @@ use golem_common::model::http_api_deployment_legacy::{
 };
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use url::Url;
+
+/// Well-known path the worker service serves the OpenAPI spec of a deployment on
+const OPENAPI_SPEC_PATH: [&str; 2] = [".well-known", "openapi.json"];
 
 pub struct ApiDeploymentCommandHandler {
     ctx: Arc<Context>,
@@ -47,6 +52,15 @@ impl ApiDeploymentCommandHandler {
         match command {
             ApiDeploymentSubcommand::Get { domain } => self.cmd_get(Domain(domain)).await,
             ApiDeploymentSubcommand::List => self.cmd_list().await,
+            ApiDeploymentSubcommand::OpenApi {
+                domain,
+                format,
+                output_name,
+                base_url,
+            } => {
+                self.cmd_openapi(Domain(domain), format, output_name, base_url)
+                    .await
+            }
         }
     }
 
@@ -102,6 +116,66 @@ impl ApiDeploymentCommandHandler {
         Ok(())
     }
 
+    async fn cmd_openapi(
+        &self,
+        domain: Domain,
+        format: OpenApiDefinitionOutputFormat,
+        output_name: Option<String>,
+        base_url: Option<Url>,
+    ) -> anyhow::Result<()> {
+        let base_url = match base_url {
+            Some(base_url) => base_url,
+            None => Url::parse(&format!("http://{}", domain.0))
+                .map_err(|err| anyhow!("Invalid domain {}: {err}", domain.0))?,
+        };
+        let mut spec_url = base_url.clone();
+        spec_url
+            .path_segments_mut()
+            .map_err(|_| anyhow!("Invalid base URL: {base_url}"))?
+            .pop_if_empty()
+            .extend(OPENAPI_SPEC_PATH);
+
+        let response = self
+            .ctx
+            .file_download_client()
+            .get(spec_url.clone())
+            .header(reqwest::header::HOST, &domain.0)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| anyhow!("Failed to fetch OpenAPI spec from {spec_url}: {err}"))?;
+        let openapi_spec: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|err| anyhow!("Failed to parse OpenAPI spec from {spec_url}: {err}"))?;
+
+        let file_name = output_name.unwrap_or_else(|| domain.0.replace(':', "_"));
+        let file_path = match format {
+            OpenApiDefinitionOutputFormat::Json => format!("{file_name}.json"),
+            OpenApiDefinitionOutputFormat::Yaml => format!("{file_name}.yaml"),
+        };
+
+        match format {
+            OpenApiDefinitionOutputFormat::Json => {
+                std::fs::write(&file_path, serde_json::to_string_pretty(&openapi_spec)?)?;
+            }
+            OpenApiDefinitionOutputFormat::Yaml => {
+                std::fs::write(&file_path, serde_yaml::to_string(&openapi_spec)?)?;
+            }
+        }
+
+        log_action(
+            "Exported",
+            format!(
+                "OpenAPI spec for {} to {}",
+                domain.0.log_color_highlight(),
+                file_path.log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
     async fn resolve_http_api_deployment(
         &self,
         environment: &ResolvedEnvironmentIdentity,
@@ -25,7 +25,7 @@ use crate::model::text::http_api_security::{
 use anyhow::bail;
 use golem_client::api::ApiSecurityClient;
 use golem_client::model::SecuritySchemeCreation;
-use golem_common::model::security_scheme::{Provider, SecuritySchemeName};
+use golem_common::model::security_scheme::{Provider, SecuritySchemeMode, SecuritySchemeName};
 use std::sync::Arc;
 
 pub struct ApiSecuritySchemeCommandHandler {
@@ -42,18 +42,24 @@ impl ApiSecuritySchemeCommandHandler {
             ApiSecuritySchemeSubcommand::Create {
                 security_scheme_name,
                 provider_type,
+                issuer_url,
                 client_id,
                 client_secret,
                 scope,
                 redirect_url,
+                mode,
+                audience,
             } => {
                 self.cmd_create(
                     security_scheme_name,
                     provider_type,
+                    issuer_url,
                     client_id,
                     client_secret,
                     scope,
                     redirect_url,
+                    mode,
+                    audience,
                 )
                 .await
             }
@@ -68,10 +74,13 @@ impl ApiSecuritySchemeCommandHandler {
         &self,
         security_scheme_name: SecuritySchemeName,
         provider_type: Provider,
+        issuer_url: Option<String>,
         client_id: String,
         client_secret: String,
         scopes: Vec<String>,
         redirect_url: String,
+        mode: Option<SecuritySchemeMode>,
+        audience: Option<String>,
     ) -> anyhow::Result<()> {
         let environment = self
             .ctx
@@ -92,6 +101,9 @@ impl ApiSecuritySchemeCommandHandler {
                     client_secret,
                     redirect_url,
                     scopes,
+                    issuer_url,
+                    mode,
+                    audience,
                 },
             )
             .await
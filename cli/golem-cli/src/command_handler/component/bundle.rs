@@ -0,0 +1,97 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command_handler::component::ifs::ComponentFilesArchive;
+use crate::log::{log_action, LogColorize};
+use anyhow::{anyhow, Context};
+use async_zip::tokio::write::ZipFileWriter;
+use async_zip::{Compression, ZipEntryBuilder};
+use golem_common::model::component_bundle::{
+    wasmtime_config, ComponentBundleManifest, EngineFingerprint, COMPONENT_BUNDLE_COMPILED_PATH,
+    COMPONENT_BUNDLE_FILES_PATH, COMPONENT_BUNDLE_MANIFEST_PATH, COMPONENT_BUNDLE_WASM_PATH,
+};
+use std::path::Path;
+use tokio::fs::File;
+use wasmtime::Engine;
+
+/// Compiles the component with the same engine settings the worker executors are using
+pub async fn precompile_component(wasm: Vec<u8>) -> anyhow::Result<(Vec<u8>, EngineFingerprint)> {
+    log_action("Compiling", "component for the bundle");
+
+    tokio::task::spawn_blocking(move || {
+        let engine = Engine::new(&wasmtime_config())
+            .map_err(|err| anyhow!("Failed to create wasmtime engine: {err}"))?;
+        let compiled = engine
+            .precompile_component(&wasm)
+            .map_err(|err| anyhow!("Failed to compile component: {err}"))?;
+        Ok((compiled, EngineFingerprint::of(&engine)))
+    })
+    .await?
+}
+
+pub async fn write_component_bundle(
+    path: &Path,
+    manifest: &ComponentBundleManifest,
+    wasm: &[u8],
+    compiled: &[u8],
+    files: Option<&ComponentFilesArchive>,
+) -> anyhow::Result<()> {
+    let zip_file = File::create(path)
+        .await
+        .with_context(|| anyhow!("Error creating component bundle {}", path.display()))?;
+    let mut zip_writer = ZipFileWriter::with_tokio(zip_file);
+
+    let manifest = serde_json::to_vec_pretty(manifest)?;
+    let files = match files {
+        Some(files) => Some(
+            tokio::fs::read(&files.archive_path)
+                .await
+                .with_context(|| anyhow!("Error reading IFS archive"))?,
+        ),
+        None => None,
+    };
+
+    let entries = [
+        (COMPONENT_BUNDLE_MANIFEST_PATH, Some(manifest.as_slice())),
+        (COMPONENT_BUNDLE_WASM_PATH, Some(wasm)),
+        (COMPONENT_BUNDLE_COMPILED_PATH, Some(compiled)),
+        (COMPONENT_BUNDLE_FILES_PATH, files.as_deref()),
+    ];
+
+    for (name, content) in entries {
+        let Some(content) = content else {
+            continue;
+        };
+
+        log_action(
+            "Adding",
+            format!("entry {} to component bundle", name.log_color_highlight()),
+        );
+
+        zip_writer
+            .write_entry_whole(
+                ZipEntryBuilder::new(name.to_string().into(), Compression::Deflate),
+                content,
+            )
+            .await
+            .with_context(|| anyhow!("Error writing component bundle entry {name}"))?;
+    }
+
+    zip_writer
+        .close()
+        .await
+        .with_context(|| anyhow!("Error closing component bundle {}", path.display()))?;
+
+    Ok(())
+}
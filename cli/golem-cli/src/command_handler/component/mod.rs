@@ -50,6 +50,9 @@ use golem_common::model::application::ApplicationName;
 use golem_common::model::component::{
     ComponentId, ComponentName, ComponentRevision, ComponentUpdate,
 };
+use golem_common::model::component_bundle::{
+    ComponentBundleManifest, COMPONENT_BUNDLE_FORMAT_VERSION,
+};
 use golem_common::model::component_metadata::{
     dynamic_linking_to_diffable, DynamicLinkedInstance, DynamicLinkedWasmRpc, WasmRpcTarget,
 };
@@ -66,6 +69,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 
+mod bundle;
 pub mod ifs;
 mod staging;
 
@@ -112,6 +116,11 @@ impl ComponentCommandHandler {
             ComponentSubcommand::ManifestTrace { component_name } => {
                 self.cmd_manifest_trace(component_name).await
             }
+            ComponentSubcommand::Bundle {
+                component_name,
+                output,
+            } => self.cmd_bundle(component_name.component_name, output).await,
+            ComponentSubcommand::ImportBundle { bundle } => self.cmd_import_bundle(bundle).await,
         }
     }
 
@@ -462,6 +471,138 @@ impl ComponentCommandHandler {
         Ok(())
     }
 
+    async fn cmd_bundle(
+        &self,
+        component_name: Option<ComponentName>,
+        output: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let selected_components = self
+            .must_select_components_by_app_dir_or_name(component_name.as_ref())
+            .await?;
+        let [component_name] = selected_components.component_names.as_slice() else {
+            log_error("Component bundles can only be created for a single component, please specify the component name");
+            bail!(NonSuccessfulExit);
+        };
+
+        self.ctx
+            .app_handler()
+            .build(
+                vec![component_name.clone()],
+                None,
+                &ApplicationComponentSelectMode::CurrentDir,
+            )
+            .await?;
+
+        let properties = self.component_deploy_properties(component_name).await?;
+        if !properties.plugins.is_empty() {
+            log_error(format!(
+                "Component {} has plugins installed, which is not supported for component bundles",
+                component_name.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let output = output.unwrap_or_else(|| {
+            PathBuf::from(format!("{}.bundle.zip", component_name.0.replace(':', "-")))
+        });
+
+        log_action(
+            "Bundling",
+            format!(
+                "component {} to {}",
+                component_name.0.log_color_highlight(),
+                output.display().to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let wasm = tokio::fs::read(&properties.linked_wasm_path)
+            .await
+            .with_context(|| {
+                anyhow!(
+                    "Failed to read component linked WASM at {}",
+                    properties.linked_wasm_path.display()
+                )
+            })?;
+        let (compiled, engine) = bundle::precompile_component(wasm.clone()).await?;
+
+        let files = if properties.files.is_empty() {
+            None
+        } else {
+            Some(
+                IfsFileManager::new(self.ctx.file_download_client().clone())
+                    .build_files_archive(properties.files.as_slice())
+                    .await?,
+            )
+        };
+
+        let manifest = ComponentBundleManifest {
+            format_version: COMPONENT_BUNDLE_FORMAT_VERSION,
+            component_name: component_name.clone(),
+            engine,
+            file_options: files
+                .as_ref()
+                .map(|files| files.file_options.clone())
+                .unwrap_or_default(),
+            dynamic_linking: properties.dynamic_linking,
+            env: properties.env,
+            agent_types: properties.agent_types,
+        };
+
+        bundle::write_component_bundle(&output, &manifest, &wasm, &compiled, files.as_ref())
+            .await?;
+
+        log_action(
+            "Created",
+            format!(
+                "component bundle {}",
+                output.display().to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_import_bundle(&self, bundle: PathBuf) -> anyhow::Result<()> {
+        let environment = self
+            .ctx
+            .environment_handler()
+            .resolve_environment(EnvironmentResolveMode::Any)
+            .await?;
+
+        log_action(
+            "Importing",
+            format!(
+                "component bundle {}",
+                bundle.display().to_string().log_color_highlight()
+            ),
+        );
+
+        let bundle_file = tokio::fs::File::open(&bundle)
+            .await
+            .with_context(|| anyhow!("Failed to open component bundle at {}", bundle.display()))?;
+
+        let component = self
+            .ctx
+            .golem_clients()
+            .await?
+            .component
+            .import_component_bundle(&environment.environment_id.0, bundle_file)
+            .await
+            .map_service_error()?;
+
+        log_action(
+            "Imported",
+            format!(
+                "component revision: {} {}",
+                component.component_name.0.log_color_highlight(),
+                component.revision.to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
     pub async fn update_workers_by_components(
         &self,
         components: &[ComponentDto],
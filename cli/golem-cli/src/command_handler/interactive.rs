@@ -173,6 +173,23 @@ impl InteractiveHandler {
         )
     }
 
+    pub fn confirm_bulk_delete_agents(
+        &self,
+        component_name: &ComponentName,
+        filtered: bool,
+    ) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "{} {} agents of component {}, do you want to continue?",
+                "Deleting".log_color_warn(),
+                if filtered { "the matching" } else { "all" }.log_color_highlight(),
+                component_name.0.log_color_highlight()
+            ),
+            None,
+        )
+    }
+
     pub fn confirm_update_to_current(
         &self,
         component_name: &ComponentName,
@@ -18,12 +18,15 @@ mod stream_output;
 use crate::command::shared_args::{
     AgentIdArgs, DeployArgs, StreamArgs, WorkerFunctionArgument, WorkerFunctionName,
 };
+use crate::command::worker::bulk::{AgentBulkSubcommand, AgentBulkTargetArgs};
+use crate::command::worker::oplog::AgentOplogSubcommand;
 use crate::command::worker::AgentSubcommand;
 use crate::command_handler::worker::stream::WorkerConnection;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::service::{AnyhowMapServiceError, ServiceError};
 use crate::error::NonSuccessfulExit;
+use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
 use crate::log::{log_action, log_error_action, log_warn_action, logln, LogColorize, LogIndent};
 use crate::model::app::ApplicationComponentSelectMode;
@@ -39,10 +42,11 @@ use crate::model::text::help::{
     AvailableFunctionNamesHelp, ParameterErrorTableView, WorkerNameHelp,
 };
 use crate::model::text::worker::{
-    format_timestamp, format_worker_name_match, FileNodeView, WorkerCreateView, WorkerFilesView,
-    WorkerGetView,
+    format_timestamp, format_worker_name_match, FileNodeView, RecurringInvocationsView,
+    WorkerCreateView, WorkerFilesView, WorkerGetView,
 };
 use anyhow::{anyhow, bail};
+use chrono::{DateTime, Utc};
 use colored::Colorize;
 
 use crate::model::environment::{EnvironmentReference, EnvironmentResolveMode};
@@ -52,19 +56,25 @@ use crate::model::worker::{
 };
 use golem_client::api::{ComponentClient, EnvironmentClient, WorkerClient};
 use golem_client::model::{
-    ComponentDto, InvokeParameters, RevertWorkerTarget, UpdateWorkerRequest, WorkerCreationRequest,
+    ComponentDto, InvokeParameters, RevertWorkerTarget, ScheduleRecurringInvocationRequest,
+    UpdateWorkerRequest, WorkerCreationRequest,
 };
 use golem_client::model::{InvokeResult, ScanCursor};
 use golem_common::model::agent::AgentId;
 use golem_common::model::application::ApplicationName;
 use golem_common::model::component::ComponentName;
-use golem_common::model::component::{ComponentId, ComponentRevision};
+use golem_common::model::component::{ComponentId, ComponentRevision, PluginPriority};
 use golem_common::model::environment::EnvironmentName;
 use golem_common::model::oplog::{OplogCursor, PublicOplogEntry};
 use golem_common::model::worker::{
-    RevertLastInvocations, RevertToOplogIndex, UpdateRecord, WasiConfigVars,
+    BulkActivatePlugin, BulkDeactivatePlugin, BulkDeleteWorkers, BulkInterruptWorkers,
+    BulkResumeWorkers, BulkRevertWorkers, BulkUpdateWorkers, BulkWorkerOperation,
+    BulkWorkerOperationId, BulkWorkerOperationJob, BulkWorkerOperationRequest,
+    BulkWorkerOperationStatus, ChangeWorkerLabelsRequest, CronRecurrence, IntervalRecurrence,
+    RevertLastInvocations, RevertToOplogIndex, ScheduleRecurrence, UpdateRecord, WasiConfigVars,
+    WorkerUpdateMode,
 };
-use golem_common::model::{IdempotencyKey, OplogIndex};
+use golem_common::model::{IdempotencyKey, OplogIndex, Timestamp, WorkerFilter};
 use golem_wasm::analysis::AnalysedType;
 use golem_wasm::json::OptionallyValueAndTypeJson;
 use golem_wasm::{parse_value_and_type, ValueAndType};
@@ -74,7 +84,7 @@ use rib::ParsedFunctionSite;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -171,10 +181,27 @@ impl WorkerCommandHandler {
                 agent_id: worker_name,
             } => self.cmd_simulate_crash(worker_name).await,
             AgentSubcommand::Oplog {
-                agent_id: worker_name,
+                subcommand: Some(subcommand),
+                ..
+            } => match subcommand {
+                AgentOplogSubcommand::Export { agent_id, output } => {
+                    self.cmd_oplog_export(agent_id, output).await
+                }
+                AgentOplogSubcommand::Import { agent_id, archive } => {
+                    self.cmd_oplog_import(agent_id, archive).await
+                }
+            },
+            AgentSubcommand::Oplog {
+                subcommand: None,
+                agent_id,
                 from,
                 query,
-            } => self.cmd_oplog(worker_name, from, query).await,
+            } => {
+                let Some(agent_id) = agent_id else {
+                    bail!("Missing agent ID")
+                };
+                self.cmd_oplog(AgentIdArgs { agent_id }, from, query).await
+            }
             AgentSubcommand::Revert {
                 agent_id: worker_name,
                 last_oplog_index,
@@ -183,6 +210,12 @@ impl WorkerCommandHandler {
                 self.cmd_revert(worker_name, last_oplog_index, number_of_invocations)
                     .await
             }
+            AgentSubcommand::Label {
+                agent_id: worker_name,
+                set,
+                remove,
+            } => self.cmd_label(worker_name, set, remove).await,
+            AgentSubcommand::Bulk { subcommand } => self.cmd_bulk(subcommand).await,
             AgentSubcommand::CancelInvocation {
                 agent_id: worker_name,
                 idempotency_key,
@@ -190,6 +223,38 @@ impl WorkerCommandHandler {
                 self.cmd_cancel_invocation(worker_name, idempotency_key)
                     .await
             }
+            AgentSubcommand::ScheduleRecurring {
+                agent_id: worker_name,
+                function_name,
+                arguments,
+                every,
+                cron,
+                start_at,
+                until,
+                idempotency_key,
+            } => {
+                self.cmd_schedule_recurring(
+                    worker_name,
+                    &function_name,
+                    arguments,
+                    every,
+                    cron,
+                    start_at,
+                    until,
+                    idempotency_key,
+                )
+                .await
+            }
+            AgentSubcommand::ListRecurring {
+                agent_id: worker_name,
+            } => self.cmd_list_recurring(worker_name).await,
+            AgentSubcommand::CancelRecurring {
+                agent_id: worker_name,
+                idempotency_key,
+            } => {
+                self.cmd_cancel_recurring(worker_name, idempotency_key)
+                    .await
+            }
             AgentSubcommand::Files { worker_name, path } => self.cmd_files(worker_name, path).await,
             AgentSubcommand::FileContents {
                 worker_name,
@@ -256,32 +321,7 @@ impl WorkerCommandHandler {
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
-        fn new_idempotency_key() -> IdempotencyKey {
-            let key = IdempotencyKey::fresh();
-            log_action(
-                "Using",
-                format!(
-                    "generated idempotency key: {}",
-                    key.value.log_color_highlight()
-                ),
-            );
-            key
-        }
-
-        let idempotency_key = match idempotency_key {
-            Some(idempotency_key) if idempotency_key.value == "-" => new_idempotency_key(),
-            Some(idempotency_key) => {
-                log_action(
-                    "Using",
-                    format!(
-                        "requested idempotency key: {}",
-                        idempotency_key.value.log_color_highlight()
-                    ),
-                );
-                idempotency_key
-            }
-            None => new_idempotency_key(),
-        };
+        let idempotency_key = resolve_idempotency_key(idempotency_key);
 
         let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
 
@@ -297,9 +337,75 @@ impl WorkerCommandHandler {
             )
             .await?;
 
+        let (worker_name_match, function_name) =
+            self.resolve_function_name(&component, worker_name_match, function_name)?;
+
+        if trigger {
+            log_action(
+                "Triggering",
+                format!(
+                    "invocation for agent {}/{}",
+                    format_worker_name_match(&worker_name_match),
+                    format_export(&function_name)
+                ),
+            );
+        } else {
+            log_action(
+                "Invoking",
+                format!(
+                    "agent {}/{} ",
+                    format_worker_name_match(&worker_name_match),
+                    format_export(&function_name)
+                ),
+            );
+        }
+
+        let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
+
+        let result = self
+            .invoke_worker(
+                &component,
+                &worker_name_match.worker_name,
+                &function_name,
+                arguments,
+                idempotency_key.clone(),
+                trigger,
+                stream.then_some(stream_args),
+            )
+            .await?;
+
+        match result {
+            Some(result) => {
+                logln("");
+                self.ctx
+                    .log_handler()
+                    .log_view(&InvokeResultView::new_invoke(
+                        idempotency_key,
+                        result,
+                        &component,
+                        &function_name,
+                    ));
+            }
+            None => {
+                log_action("Triggered", "invocation");
+                self.ctx
+                    .log_handler()
+                    .log_view(&InvokeResultView::new_trigger(idempotency_key));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_function_name(
+        &self,
+        component: &ComponentDto,
+        worker_name_match: WorkerNameMatch,
+        function_name: &WorkerFunctionName,
+    ) -> anyhow::Result<(WorkerNameMatch, String)> {
         // First, validate without the function name
         let agent_id_and_type = self.validate_worker_and_function_names(
-            &component,
+            component,
             &worker_name_match.worker_name,
             None,
         )?;
@@ -309,7 +415,7 @@ impl WorkerCommandHandler {
             component.metadata.exports(),
             agent_id_and_type
                 .as_ref()
-                .and_then(|a| agent_interface_name(&component, a.wrapper_agent_type()))
+                .and_then(|a| agent_interface_name(component, a.wrapper_agent_type()))
                 .as_deref(),
         );
         let function_name = match matched_function_name {
@@ -335,7 +441,7 @@ impl WorkerCommandHandler {
                     logln("?");
                     logln("");
                     log_text_view(&AvailableFunctionNamesHelp::new(
-                        &component,
+                        component,
                         agent_id_and_type.as_ref(),
                     ));
 
@@ -349,7 +455,7 @@ impl WorkerCommandHandler {
                     ));
                     logln("");
                     log_text_view(&AvailableFunctionNamesHelp::new(
-                        &component,
+                        component,
                         agent_id_and_type.as_ref(),
                     ));
 
@@ -360,7 +466,7 @@ impl WorkerCommandHandler {
 
         // Re-validate with function-name
         let agent_id = self.validate_worker_and_function_names(
-            &component,
+            component,
             &worker_name_match.worker_name,
             Some(&function_name),
         )?;
@@ -377,61 +483,7 @@ impl WorkerCommandHandler {
             }
         };
 
-        if trigger {
-            log_action(
-                "Triggering",
-                format!(
-                    "invocation for agent {}/{}",
-                    format_worker_name_match(&worker_name_match),
-                    format_export(&function_name)
-                ),
-            );
-        } else {
-            log_action(
-                "Invoking",
-                format!(
-                    "agent {}/{} ",
-                    format_worker_name_match(&worker_name_match),
-                    format_export(&function_name)
-                ),
-            );
-        }
-
-        let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
-
-        let result = self
-            .invoke_worker(
-                &component,
-                &worker_name_match.worker_name,
-                &function_name,
-                arguments,
-                idempotency_key.clone(),
-                trigger,
-                stream.then_some(stream_args),
-            )
-            .await?;
-
-        match result {
-            Some(result) => {
-                logln("");
-                self.ctx
-                    .log_handler()
-                    .log_view(&InvokeResultView::new_invoke(
-                        idempotency_key,
-                        result,
-                        &component,
-                        &function_name,
-                    ));
-            }
-            None => {
-                log_action("Triggered", "invocation");
-                self.ctx
-                    .log_handler()
-                    .log_view(&InvokeResultView::new_trigger(idempotency_key));
-            }
-        }
-
-        Ok(())
+        Ok((worker_name_match, function_name))
     }
 
     async fn cmd_stream(
@@ -549,6 +601,93 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    async fn cmd_oplog_export(
+        &self,
+        worker_name: AgentIdArgs,
+        output: PathBuf,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        if output.exists() && !self.confirm_file_overwrite(&output.display().to_string())? {
+            log_action(
+                "Oplog export cancelled",
+                format!(
+                    "by user for file {}",
+                    output.as_path().log_color_highlight()
+                ),
+            );
+            return Ok(());
+        }
+
+        log_action(
+            "Exporting",
+            format!(
+                "oplog of agent {}",
+                format_worker_name_match(&worker_name_match)
+            ),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+        let archive = clients
+            .worker
+            .export_oplog(&component.id.0, &worker_name.0)
+            .await
+            .map_service_error()?;
+
+        fs::write(&output, archive)?;
+
+        log_action(
+            "Exported",
+            format!("oplog to {}", output.as_path().log_color_highlight()),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_oplog_import(
+        &self,
+        worker_name: AgentIdArgs,
+        archive: PathBuf,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let archive = fs::read(&archive)?;
+
+        log_action(
+            "Importing",
+            format!(
+                "oplog as agent {}",
+                format_worker_name_match(&worker_name_match)
+            ),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+        let result = clients
+            .worker
+            .import_oplog(&component.id.0, &worker_name.0, archive)
+            .await
+            .map_service_error()?;
+
+        log_action(
+            "Imported",
+            format!(
+                "agent {} with {} oplog entries",
+                format_worker_name_match(&worker_name_match),
+                result.last_index.to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
     async fn cmd_revert(
         &self,
         worker_name: AgentIdArgs,
@@ -592,23 +731,546 @@ impl WorkerCommandHandler {
                 }
             };
 
-            clients
-                .worker
-                .revert_worker(&component.id.0, &worker_name.0, &target)
-                .await
-                .map(|_| ())
-                .map_service_error()?
-        }
+            clients
+                .worker
+                .revert_worker(&component.id.0, &worker_name.0, &target)
+                .await
+                .map(|_| ())
+                .map_service_error()?
+        }
+
+        log_action(
+            "Reverted",
+            format!("agent {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_label(
+        &self,
+        worker_name: AgentIdArgs,
+        set: Vec<(String, String)>,
+        remove: Vec<String>,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        log_action(
+            "Changing labels",
+            format!("of agent {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+
+        clients
+            .worker
+            .change_worker_labels(
+                &component.id.0,
+                &worker_name.0,
+                &ChangeWorkerLabelsRequest {
+                    set: set.into_iter().collect(),
+                    removed: remove.into_iter().collect(),
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_service_error()?;
+
+        log_action(
+            "Changed labels",
+            format!("of agent {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_bulk(&self, subcommand: AgentBulkSubcommand) -> anyhow::Result<()> {
+        let (targets, operation) = match subcommand {
+            AgentBulkSubcommand::Interrupt {
+                targets,
+                recover_immediately,
+            } => (
+                targets,
+                BulkWorkerOperation::Interrupt(BulkInterruptWorkers {
+                    recover_immediately,
+                }),
+            ),
+            AgentBulkSubcommand::Resume { targets } => {
+                (targets, BulkWorkerOperation::Resume(BulkResumeWorkers {}))
+            }
+            AgentBulkSubcommand::Update {
+                targets,
+                mode,
+                target_revision,
+            } => {
+                return self
+                    .cmd_bulk_update(
+                        targets,
+                        mode.unwrap_or(AgentUpdateMode::Automatic),
+                        target_revision,
+                    )
+                    .await
+            }
+            AgentBulkSubcommand::Delete { targets } => {
+                (targets, BulkWorkerOperation::Delete(BulkDeleteWorkers {}))
+            }
+            AgentBulkSubcommand::Revert {
+                targets,
+                last_oplog_index,
+                number_of_invocations,
+            } => {
+                let target = if let Some(last_oplog_index) = last_oplog_index {
+                    RevertWorkerTarget::RevertToOplogIndex(RevertToOplogIndex {
+                        last_oplog_index: OplogIndex::from_u64(last_oplog_index),
+                    })
+                } else if let Some(number_of_invocations) = number_of_invocations {
+                    RevertWorkerTarget::RevertLastInvocations(RevertLastInvocations {
+                        number_of_invocations,
+                    })
+                } else {
+                    log_error(format!(
+                        "One of [{}, {}] must be specified",
+                        "last-oplog-index".log_color_highlight(),
+                        "number of invocations".log_color_highlight()
+                    ));
+                    bail!(NonSuccessfulExit)
+                };
+                (
+                    targets,
+                    BulkWorkerOperation::Revert(BulkRevertWorkers { target }),
+                )
+            }
+            AgentBulkSubcommand::ActivatePlugin {
+                targets,
+                plugin_priority,
+            } => (
+                targets,
+                BulkWorkerOperation::ActivatePlugin(BulkActivatePlugin {
+                    plugin_priority: PluginPriority(plugin_priority),
+                }),
+            ),
+            AgentBulkSubcommand::DeactivatePlugin {
+                targets,
+                plugin_priority,
+            } => (
+                targets,
+                BulkWorkerOperation::DeactivatePlugin(BulkDeactivatePlugin {
+                    plugin_priority: PluginPriority(plugin_priority),
+                }),
+            ),
+            AgentBulkSubcommand::Status {
+                component_name,
+                operation_id,
+                r#await,
+            } => {
+                return self
+                    .cmd_bulk_status(component_name, operation_id, r#await)
+                    .await
+            }
+            AgentBulkSubcommand::Cancel {
+                component_name,
+                operation_id,
+            } => return self.cmd_bulk_cancel(component_name, operation_id).await,
+        };
+
+        let (components, filters) = self
+            .select_components_with_filters(
+                targets.agent_type_name.clone(),
+                targets.component_name.clone(),
+                targets.filter.clone(),
+            )
+            .await?;
+
+        self.start_bulk_operations(&targets, &components, &filters, |_| operation.clone())
+            .await
+    }
+
+    async fn cmd_bulk_update(
+        &self,
+        targets: AgentBulkTargetArgs,
+        mode: AgentUpdateMode,
+        target_revision: Option<ComponentRevision>,
+    ) -> anyhow::Result<()> {
+        let (components, filters) = self
+            .select_components_with_filters(
+                targets.agent_type_name.clone(),
+                targets.component_name.clone(),
+                targets.filter.clone(),
+            )
+            .await?;
+
+        if target_revision.is_some() && components.len() != 1 {
+            log_error("Target revision cannot be used with multiple components selected!");
+            bail!(NonSuccessfulExit);
+        }
+
+        let mode = match mode {
+            AgentUpdateMode::Automatic => WorkerUpdateMode::Automatic,
+            AgentUpdateMode::Manual => WorkerUpdateMode::Manual,
+        };
+
+        // Without an explicit target revision each component's agents are updated to the
+        // revision of the component in the current deployment
+        self.start_bulk_operations(&targets, &components, &filters, |component| {
+            BulkWorkerOperation::Update(BulkUpdateWorkers {
+                mode,
+                target_revision: target_revision.unwrap_or(component.revision),
+            })
+        })
+        .await
+    }
+
+    async fn start_bulk_operations(
+        &self,
+        targets: &AgentBulkTargetArgs,
+        components: &[ComponentDto],
+        filters: &[String],
+        operation: impl Fn(&ComponentDto) -> BulkWorkerOperation,
+    ) -> anyhow::Result<()> {
+        let filter = if filters.is_empty() {
+            None
+        } else {
+            match WorkerFilter::from(filters.to_vec()) {
+                Ok(filter) => Some(filter),
+                Err(error) => {
+                    log_error(format!("Invalid agent filter: {error}"));
+                    bail!(NonSuccessfulExit)
+                }
+            }
+        };
+
+        let clients = self.ctx.golem_clients().await?;
+
+        let mut jobs = Vec::with_capacity(components.len());
+        for component in components {
+            let operation = operation(component);
+
+            if matches!(operation, BulkWorkerOperation::Delete(_))
+                && !self
+                    .ctx
+                    .interactive_handler()
+                    .confirm_bulk_delete_agents(&component.component_name, filter.is_some())?
+            {
+                bail!(NonSuccessfulExit)
+            }
+
+            log_warn_action(
+                "Starting",
+                format!(
+                    "bulk {} for agents of component {}",
+                    operation.to_string().log_color_highlight(),
+                    component.component_name.0.log_color_highlight()
+                ),
+            );
+
+            let job = clients
+                .worker
+                .start_bulk_worker_operation(
+                    &component.id.0,
+                    &BulkWorkerOperationRequest {
+                        filter: filter.clone(),
+                        operation,
+                        max_concurrency: targets.max_concurrency,
+                        max_rate: targets.max_rate,
+                    },
+                )
+                .await
+                .map_service_error()?;
+
+            log_action(
+                "Started",
+                format!(
+                    "bulk operation {}",
+                    job.id.to_string().log_color_highlight()
+                ),
+            );
+
+            jobs.push((component.component_name.clone(), job));
+        }
+
+        if targets.r#await {
+            for (component_name, job) in jobs {
+                self.await_bulk_operation(&component_name, job).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_bulk_status(
+        &self,
+        component_name: Option<ComponentName>,
+        operation_id: BulkWorkerOperationId,
+        await_operation: bool,
+    ) -> anyhow::Result<()> {
+        let component = self.select_bulk_operation_component(component_name).await?;
+
+        let clients = self.ctx.golem_clients().await?;
+        let job = clients
+            .worker
+            .get_bulk_worker_operation(&component.id.0, &operation_id.0)
+            .await
+            .map_service_error()?;
+
+        if await_operation {
+            self.await_bulk_operation(&component.component_name, job)
+                .await
+        } else {
+            log_bulk_operation_job(&component.component_name, &job);
+            Ok(())
+        }
+    }
+
+    async fn cmd_bulk_cancel(
+        &self,
+        component_name: Option<ComponentName>,
+        operation_id: BulkWorkerOperationId,
+    ) -> anyhow::Result<()> {
+        let component = self.select_bulk_operation_component(component_name).await?;
+
+        log_warn_action(
+            "Cancelling",
+            format!(
+                "bulk operation {}",
+                operation_id.to_string().log_color_highlight()
+            ),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+        let job = clients
+            .worker
+            .cancel_bulk_worker_operation(&component.id.0, &operation_id.0)
+            .await
+            .map_service_error()?;
+
+        log_action(
+            "Cancelled",
+            format!(
+                "bulk operation {}, agents already processed are not affected",
+                job.id.to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn select_bulk_operation_component(
+        &self,
+        component_name: Option<ComponentName>,
+    ) -> anyhow::Result<ComponentDto> {
+        let (mut components, _) = self
+            .select_components_with_filters(None, component_name, vec![])
+            .await?;
+
+        if components.len() != 1 {
+            log_error(format!(
+                "Bulk operations belong to a single component, but multiple components are selected! ({})",
+                components
+                    .iter()
+                    .map(|component| component.component_name.0.log_color_highlight())
+                    .join(", ")
+            ));
+            logln("");
+            logln("Switch to an application directory with only one component or explicitly specify the requested component name.");
+            logln("");
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(components.remove(0))
+    }
+
+    async fn await_bulk_operation(
+        &self,
+        component_name: &ComponentName,
+        mut job: BulkWorkerOperationJob,
+    ) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients().await?;
+
+        let mut last_processed = None;
+        while job.status == BulkWorkerOperationStatus::Running {
+            let processed = job.succeeded + job.failed;
+            if last_processed != Some(processed) {
+                log_action(
+                    "Bulk operation",
+                    format!(
+                        "{} is running, processed {}/{} agents",
+                        job.id.to_string().log_color_highlight(),
+                        processed.to_string().log_color_highlight(),
+                        job.matched.to_string().log_color_highlight()
+                    ),
+                );
+                last_processed = Some(processed);
+            }
+
+            tokio::time::sleep(Duration::from_secs(2)).await;
+
+            job = clients
+                .worker
+                .get_bulk_worker_operation(&job.component_id.0, &job.id.0)
+                .await
+                .map_service_error()?;
+        }
+
+        log_bulk_operation_job(component_name, &job);
+
+        if job.status == BulkWorkerOperationStatus::Failed || job.failed > 0 {
+            bail!(NonSuccessfulExit)
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_cancel_invocation(
+        &self,
+        worker_name: AgentIdArgs,
+        idempotency_key: IdempotencyKey,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        log_warn_action(
+            "Canceling invocation",
+            format!(
+                "for agent {} using idempotency key: {}",
+                format_worker_name_match(&worker_name_match),
+                idempotency_key.value.log_color_highlight()
+            ),
+        );
+
+        let clients = self.ctx.golem_clients().await?;
+
+        let canceled = clients
+            .worker
+            .cancel_invocation(&component.id.0, &worker_name.0, &idempotency_key.value)
+            .await
+            .map(|result| result.canceled)
+            .map_service_error()?;
+
+        // TODO: json / yaml response?
+        if canceled {
+            log_action("Canceled", "");
+        } else {
+            log_warn_action("Failed", "to cancel, invocation already started");
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_schedule_recurring(
+        &self,
+        worker_name: AgentIdArgs,
+        function_name: &WorkerFunctionName,
+        arguments: Vec<WorkerFunctionArgument>,
+        every: Option<Duration>,
+        cron: Option<String>,
+        start_at: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        idempotency_key: Option<IdempotencyKey>,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+
+        let until = until.map(|until| Timestamp::from(until.timestamp_millis() as u64));
+        let recurrence = match (every, cron) {
+            (Some(every), None) => ScheduleRecurrence::Interval(IntervalRecurrence {
+                interval_millis: every.as_millis() as u64,
+                until,
+            }),
+            (None, Some(expression)) => {
+                ScheduleRecurrence::Cron(CronRecurrence { expression, until })
+            }
+            _ => {
+                log_error(format!(
+                    "Exactly one of [{}, {}] must be specified",
+                    "every".log_color_highlight(),
+                    "cron".log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit)
+            }
+        };
+        if let Err(error) = recurrence.validate() {
+            log_error(error);
+            bail!(NonSuccessfulExit)
+        }
+
+        let idempotency_key = resolve_idempotency_key(idempotency_key);
+
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, _) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let (worker_name_match, function_name) =
+            self.resolve_function_name(&component, worker_name_match, function_name)?;
+
+        log_action(
+            "Scheduling",
+            format!(
+                "recurring invocation for agent {}/{} ({})",
+                format_worker_name_match(&worker_name_match),
+                format_export(&function_name),
+                recurrence.to_string().log_color_highlight()
+            ),
+        );
+
+        let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
+
+        let clients = self.ctx.golem_clients().await?;
+
+        let recurring_invocation = clients
+            .worker
+            .schedule_recurring_invocation(
+                &component.id.0,
+                &worker_name_match.worker_name.0,
+                Some(&idempotency_key.value),
+                &ScheduleRecurringInvocationRequest {
+                    function: function_name,
+                    params: arguments,
+                    recurrence,
+                    start_at,
+                },
+            )
+            .await
+            .map_service_error()?;
+
+        log_action("Scheduled", "recurring invocation");
+        self.ctx.log_handler().log_view(&RecurringInvocationsView {
+            invocations: vec![recurring_invocation],
+        });
+
+        Ok(())
+    }
+
+    async fn cmd_list_recurring(&self, worker_name: AgentIdArgs) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.agent_id).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let clients = self.ctx.golem_clients().await?;
 
-        log_action(
-            "Reverted",
-            format!("agent {}", format_worker_name_match(&worker_name_match)),
-        );
+        let invocations = clients
+            .worker
+            .get_recurring_invocations(&component.id.0, &worker_name.0)
+            .await
+            .map(|result| result.invocations)
+            .map_service_error()?;
+
+        self.ctx
+            .log_handler()
+            .log_view(&RecurringInvocationsView { invocations });
 
         Ok(())
     }
 
-    async fn cmd_cancel_invocation(
+    async fn cmd_cancel_recurring(
         &self,
         worker_name: AgentIdArgs,
         idempotency_key: IdempotencyKey,
@@ -620,7 +1282,7 @@ impl WorkerCommandHandler {
             .await?;
 
         log_warn_action(
-            "Canceling invocation",
+            "Canceling recurring invocation",
             format!(
                 "for agent {} using idempotency key: {}",
                 format_worker_name_match(&worker_name_match),
@@ -632,16 +1294,15 @@ impl WorkerCommandHandler {
 
         let canceled = clients
             .worker
-            .cancel_invocation(&component.id.0, &worker_name.0, &idempotency_key.value)
+            .cancel_recurring_invocation(&component.id.0, &worker_name.0, &idempotency_key.value)
             .await
             .map(|result| result.canceled)
             .map_service_error()?;
 
-        // TODO: json / yaml response?
         if canceled {
             log_action("Canceled", "");
         } else {
-            log_warn_action("Failed", "to cancel, invocation already started");
+            log_warn_action("Failed", "to cancel, no such recurring invocation");
         }
 
         Ok(())
@@ -656,105 +1317,9 @@ impl WorkerCommandHandler {
         max_count: Option<u64>,
         precise: bool,
     ) -> anyhow::Result<()> {
-        let clients = self.ctx.golem_clients().await?;
-        let component_handler = self.ctx.component_handler();
-
-        let (components, filters) = match agent_type_name {
-            Some(agent_type_name) => {
-                let environment = self
-                    .ctx
-                    .environment_handler()
-                    .resolve_environment(EnvironmentResolveMode::Any)
-                    .await?;
-                environment
-                    .with_current_deployment_revision_or_default_warn(
-                        |current_deployment_revision| async move {
-                            debug!("Finding agent type {}", agent_type_name);
-                            let Some(agent_type) = clients
-                                .environment
-                                .get_deployment_agent_type(
-                                    &environment.environment_id.0,
-                                    current_deployment_revision.into(),
-                                    &agent_type_name,
-                                )
-                                .await
-                                .map_service_error_not_found_as_opt()?
-                            else {
-                                log_error(format!(
-                                    "Agent type {} not found",
-                                    agent_type_name.log_color_highlight()
-                                ));
-                                bail!(NonSuccessfulExit)
-                            };
-
-                            let mut filters = filters;
-                            filters.insert(
-                                0,
-                                format!(
-                                    "name startswith {}(",
-                                    agent_type.agent_type.wrapper_type_name()
-                                ),
-                            );
-
-                            Ok((
-                                vec![
-                                    component_handler
-                                        .get_component_revision_by_id(
-                                            &agent_type.implemented_by.component_id,
-                                            agent_type.implemented_by.component_revision,
-                                        )
-                                        .await?,
-                                ],
-                                filters,
-                            ))
-                        },
-                    )
-                    .await?
-            }
-            None => {
-                let selected_components = self
-                    .ctx
-                    .component_handler()
-                    .must_select_components_by_app_dir_or_name(component_name.as_ref())
-                    .await?;
-
-                let environment = &selected_components.environment;
-
-                environment
-                    .with_current_deployment_revision_or_default_warn(
-                        |current_deployment_revision| async move {
-                            let mut components =
-                                Vec::with_capacity(selected_components.component_names.len());
-                            for component_name in selected_components.component_names {
-                                match clients
-                                    .component
-                                    .get_deployment_component(
-                                        &environment.environment_id.0,
-                                        current_deployment_revision.into(),
-                                        component_name.as_str(),
-                                    )
-                                    .await
-                                    .map_service_error_not_found_as_opt()?
-                                {
-                                    Some(component) => {
-                                        components.push(component);
-                                    }
-                                    None => {
-                                        log_error(format!(
-                                            "Component not found: {}",
-                                            component_name.0.log_color_error_highlight()
-                                        ));
-                                        bail!(NonSuccessfulExit)
-                                    }
-                                }
-                            }
-
-                            Ok((components, filters))
-                        },
-                    )
-                    .await?
-            }
-        };
+        let (components, filters) = self
+            .select_components_with_filters(agent_type_name, component_name, filters)
+            .await?;
 
         if scan_cursor.is_some() && components.len() != 1 {
             log_error(format!(
@@ -1609,6 +2174,117 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    /// Selects the components of the given agent type or component name (or of the current
+    /// application), extending the filters to only match agents of the agent type
+    async fn select_components_with_filters(
+        &self,
+        agent_type_name: Option<String>,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+    ) -> anyhow::Result<(Vec<ComponentDto>, Vec<String>)> {
+        let clients = self.ctx.golem_clients().await?;
+        let component_handler = self.ctx.component_handler();
+
+        let result = match agent_type_name {
+            Some(agent_type_name) => {
+                let environment = self
+                    .ctx
+                    .environment_handler()
+                    .resolve_environment(EnvironmentResolveMode::Any)
+                    .await?;
+                environment
+                    .with_current_deployment_revision_or_default_warn(
+                        |current_deployment_revision| async move {
+                            debug!("Finding agent type {}", agent_type_name);
+                            let Some(agent_type) = clients
+                                .environment
+                                .get_deployment_agent_type(
+                                    &environment.environment_id.0,
+                                    current_deployment_revision.into(),
+                                    &agent_type_name,
+                                )
+                                .await
+                                .map_service_error_not_found_as_opt()?
+                            else {
+                                log_error(format!(
+                                    "Agent type {} not found",
+                                    agent_type_name.log_color_highlight()
+                                ));
+                                bail!(NonSuccessfulExit)
+                            };
+
+                            let mut filters = filters;
+                            filters.insert(
+                                0,
+                                format!(
+                                    "name startswith {}(",
+                                    agent_type.agent_type.wrapper_type_name()
+                                ),
+                            );
+
+                            Ok((
+                                vec![
+                                    component_handler
+                                        .get_component_revision_by_id(
+                                            &agent_type.implemented_by.component_id,
+                                            agent_type.implemented_by.component_revision,
+                                        )
+                                        .await?,
+                                ],
+                                filters,
+                            ))
+                        },
+                    )
+                    .await?
+            }
+            None => {
+                let selected_components = self
+                    .ctx
+                    .component_handler()
+                    .must_select_components_by_app_dir_or_name(component_name.as_ref())
+                    .await?;
+
+                let environment = &selected_components.environment;
+
+                environment
+                    .with_current_deployment_revision_or_default_warn(
+                        |current_deployment_revision| async move {
+                            let mut components =
+                                Vec::with_capacity(selected_components.component_names.len());
+                            for component_name in selected_components.component_names {
+                                match clients
+                                    .component
+                                    .get_deployment_component(
+                                        &environment.environment_id.0,
+                                        current_deployment_revision.into(),
+                                        component_name.as_str(),
+                                    )
+                                    .await
+                                    .map_service_error_not_found_as_opt()?
+                                {
+                                    Some(component) => {
+                                        components.push(component);
+                                    }
+                                    None => {
+                                        log_error(format!(
+                                            "Component not found: {}",
+                                            component_name.0.log_color_error_highlight()
+                                        ));
+                                        bail!(NonSuccessfulExit)
+                                    }
+                                }
+                            }
+
+                            Ok((components, filters))
+                        },
+                    )
+                    .await?
+            }
+        };
+
+        Ok(result)
+    }
+
     pub async fn list_component_workers(
         &self,
         component_name: &ComponentName,
@@ -2062,6 +2738,35 @@ impl WorkerCommandHandler {
     }
 }
 
+fn resolve_idempotency_key(idempotency_key: Option<IdempotencyKey>) -> IdempotencyKey {
+    fn new_idempotency_key() -> IdempotencyKey {
+        let key = IdempotencyKey::fresh();
+        log_action(
+            "Using",
+            format!(
+                "generated idempotency key: {}",
+                key.value.log_color_highlight()
+            ),
+        );
+        key
+    }
+
+    match idempotency_key {
+        Some(idempotency_key) if idempotency_key.value == "-" => new_idempotency_key(),
+        Some(idempotency_key) => {
+            log_action(
+                "Using",
+                format!(
+                    "requested idempotency key: {}",
+                    idempotency_key.value.log_color_highlight()
+                ),
+            );
+            idempotency_key
+        }
+        None => new_idempotency_key(),
+    }
+}
+
 fn wave_args_to_invoke_args(
     component: &ComponentDto,
     function_name: &str,
@@ -2160,6 +2865,48 @@ pub fn lenient_parse_type_annotated_value(
     parse_value_and_type(analysed_type, input)
 }
 
+fn log_bulk_operation_job(component_name: &ComponentName, job: &BulkWorkerOperationJob) {
+    let summary = format!(
+        "{} of component {}: {}, matched {}, succeeded {}, failed {} agents",
+        job.operation.to_string().log_color_highlight(),
+        component_name.0.log_color_highlight(),
+        job.status.to_string().log_color_highlight(),
+        job.matched.to_string().log_color_highlight(),
+        job.succeeded.to_string().log_color_ok_highlight(),
+        job.failed.to_string().log_color_error_highlight(),
+    );
+
+    if job.status == BulkWorkerOperationStatus::Failed || job.failed > 0 {
+        log_warn_action(
+            "Bulk operation",
+            format!("{} {summary}", job.id.to_string().log_color_highlight()),
+        );
+    } else {
+        log_action(
+            "Bulk operation",
+            format!("{} {summary}", job.id.to_string().log_color_highlight()),
+        );
+    }
+
+    let _indent = LogIndent::new();
+    if let Some(error) = &job.error {
+        log_error(error);
+    }
+    for failure in &job.failures {
+        logln(format!(
+            "{}: {}",
+            failure.worker_id.worker_name.log_color_error_highlight(),
+            failure.error
+        ));
+    }
+    if job.failed > job.failures.len() as u64 {
+        logln(format!(
+            "... and {} more failures",
+            job.failed - job.failures.len() as u64
+        ));
+    }
+}
+
 fn scan_cursor_to_string(cursor: &ScanCursor) -> String {
     format!("{}/{}", cursor.layer, cursor.cursor)
 }
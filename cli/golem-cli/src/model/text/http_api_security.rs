@@ -57,9 +57,12 @@ fn security_scheme_view_fields(view: &SecuritySchemeDto) -> Vec<(String, String)
         .fmt_field("ID", &view.id, format_id)
         .fmt_field("Revision", &view.revision.get(), format_id)
         .field("Provider", &view.provider_type)
+        .fmt_field_option("Issuer URL", &view.issuer_url, |url| url.clone())
         .field("Client ID", &view.client_id)
         .field("Redirect URL", &view.redirect_url)
-        .field("Scopes", &view.scopes.join("\n"));
+        .field("Scopes", &view.scopes.join("\n"))
+        .field("Mode", &view.mode)
+        .fmt_field_option("Audience", &view.audience, |audience| audience.clone());
 
     fields.build()
 }
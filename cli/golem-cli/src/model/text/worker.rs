@@ -31,7 +31,7 @@ use golem_common::model::oplog::{
     PluginInstallationDescription, PublicAttributeValue, PublicOplogEntry, PublicUpdateDescription,
     PublicWorkerInvocation, StringAttributeValue,
 };
-use golem_common::model::worker::UpdateRecord;
+use golem_common::model::worker::{RecurringInvocation, UpdateRecord};
 use golem_common::model::Timestamp;
 use golem_wasm::{print_value_and_type, ValueAndType};
 use indoc::indoc;
@@ -171,6 +171,21 @@ impl MessageWithFields for WorkerGetView {
                 &self.metadata.total_linear_memory_size,
                 format_binary_size,
             )
+            .fmt_field(
+                "Peak linear memory size",
+                &self.metadata.resource_usage.peak_linear_memory_size,
+                format_binary_size,
+            )
+            .field("Consumed fuel", &self.metadata.resource_usage.consumed_fuel)
+            .field(
+                "Invocation count",
+                &self.metadata.resource_usage.invocation_count,
+            )
+            .fmt_field(
+                "Invocation time",
+                &self.metadata.resource_usage.invocation_time_millis,
+                |millis| format!("{millis} ms"),
+            )
             .fmt_field_optional(
                 "Environment variables",
                 &self.metadata.env,
@@ -181,6 +196,17 @@ impl MessageWithFields for WorkerGetView {
                         .join(";")
                 },
             )
+            .fmt_field_optional(
+                "Labels",
+                &self.metadata.labels,
+                !self.metadata.labels.is_empty(),
+                |labels| {
+                    labels
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v.bold()))
+                        .join(";")
+                },
+            )
             .fmt_field_optional("Status", &self.metadata.status, self.precise, format_status)
             .fmt_field_optional(
                 "Retry count",
@@ -835,6 +861,32 @@ impl TextView for PublicOplogEntry {
                     format_id(&params.begin_index)
                 ));
             }
+            PublicOplogEntry::Snapshot(params) => {
+                logln(format_message_highlight("SNAPSHOT"));
+                logln(format!(
+                    "{pad}at:                {}",
+                    format_id(&params.timestamp)
+                ));
+                logln(format!(
+                    "{pad}snapshot:          {}",
+                    BASE64_STANDARD.encode(&params.data),
+                ));
+            }
+            PublicOplogEntry::ChangeLabels(params) => {
+                logln(format_message_highlight("CHANGE LABELS"));
+                logln(format!(
+                    "{pad}at:                {}",
+                    format_id(&params.timestamp)
+                ));
+                logln(format!("{pad}set:"));
+                for (k, v) in &params.set {
+                    logln(format!("{pad}  - {}: {}", k, format_id(&v)));
+                }
+                logln(format!("{pad}removed:"));
+                for k in &params.removed {
+                    logln(format!("{pad}  - {k}"));
+                }
+            }
         }
     }
 }
@@ -972,6 +1024,44 @@ impl TextView for WorkerFilesView {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringInvocationsView {
+    pub invocations: Vec<RecurringInvocation>,
+}
+
+#[derive(Table)]
+pub struct RecurringInvocationTableView {
+    #[table(title = "Idempotency key")]
+    pub idempotency_key: String,
+    #[table(title = "Function")]
+    pub function_name: String,
+    #[table(title = "Recurrence")]
+    pub recurrence: String,
+    #[table(title = "Next occurrence", justify = "Justify::Right")]
+    pub next_occurrence: String,
+}
+
+impl From<&RecurringInvocation> for RecurringInvocationTableView {
+    fn from(value: &RecurringInvocation) -> Self {
+        Self {
+            idempotency_key: value.idempotency_key.value.clone(),
+            function_name: value.function_name.clone(),
+            recurrence: value.recurrence.to_string(),
+            next_occurrence: value.next_occurrence.to_string(),
+        }
+    }
+}
+
+impl TextView for RecurringInvocationsView {
+    fn log(&self) {
+        if self.invocations.is_empty() {
+            logln("No recurring invocations found.");
+        } else {
+            log_table::<_, RecurringInvocationTableView>(&self.invocations);
+        }
+    }
+}
+
 // Helper function to convert Unix timestamp to human-readable format
 pub fn format_timestamp(timestamp: u64) -> String {
     if let Some(datetime) = DateTime::from_timestamp(timestamp as i64, 0) {
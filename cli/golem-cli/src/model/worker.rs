@@ -24,7 +24,7 @@ use golem_common::model::account::AccountId;
 use golem_common::model::component::{ComponentName, ComponentRevision};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::trim_date::TrimDateTime;
-use golem_common::model::worker::UpdateRecord;
+use golem_common::model::worker::{UpdateRecord, WorkerResourceUsage};
 use golem_common::model::{Timestamp, WorkerId, WorkerResourceDescription, WorkerStatus};
 use golem_wasm::analysis::AnalysedExport;
 use rib::{ParsedFunctionName, ParsedFunctionReference};
@@ -105,6 +105,8 @@ pub struct WorkerMetadataView {
     pub last_error: Option<String>,
     pub component_size: u64,
     pub total_linear_memory_size: u64,
+    pub resource_usage: WorkerResourceUsage,
+    pub labels: BTreeMap<String, String>,
     pub exported_resource_instances: HashMap<String, WorkerResourceDescription>,
 }
 
@@ -131,6 +133,8 @@ impl From<WorkerMetadata> for WorkerMetadataView {
             last_error: value.last_error,
             component_size: value.component_size,
             total_linear_memory_size: value.total_linear_memory_size,
+            resource_usage: value.resource_usage,
+            labels: value.labels,
             exported_resource_instances: value.exported_resource_instances,
         }
     }
@@ -152,6 +156,8 @@ pub struct WorkerMetadata {
     pub last_error: Option<String>,
     pub component_size: u64,
     pub total_linear_memory_size: u64,
+    pub resource_usage: WorkerResourceUsage,
+    pub labels: BTreeMap<String, String>,
     pub exported_resource_instances: HashMap<String, WorkerResourceDescription>,
 }
 
@@ -175,6 +181,8 @@ impl WorkerMetadata {
             last_error: value.last_error,
             component_size: value.component_size,
             total_linear_memory_size: value.total_linear_memory_size,
+            resource_usage: value.resource_usage,
+            labels: value.labels,
             exported_resource_instances: HashMap::from_iter(
                 value
                     .exported_resource_instances
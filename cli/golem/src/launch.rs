@@ -41,7 +41,8 @@ use golem_worker_executor::services::golem_config::{
     ShardManagerServiceConfig, ShardManagerServiceGrpcConfig, WorkerServiceGrpcConfig,
 };
 use golem_worker_service::config::{
-    RouteResolverConfig, SqliteSessionStoreConfig, WorkerServiceConfig,
+    ApiKeyLookupConfig, RouteResolverConfig, SqliteBulkOperationStoreConfig,
+    SqliteSessionStoreConfig, WorkerServiceConfig,
 };
 use golem_worker_service::WorkerService;
 use opentelemetry::global;
@@ -352,11 +353,33 @@ fn worker_service_config(
             port: registry_service_run_details.grpc_port,
             ..Default::default()
         },
+        bulk_operations: golem_worker_service::config::BulkOperationsConfig {
+            storage: golem_worker_service::config::BulkOperationStoreConfig::Sqlite(
+                SqliteBulkOperationStoreConfig {
+                    cleanup_interval: Duration::from_mins(10),
+                    sqlite_config: DbSqliteConfig {
+                        database: args
+                            .data_dir
+                            .join("bulk-operations.db")
+                            .to_string_lossy()
+                            .to_string(),
+                        max_connections: 4,
+                        foreign_keys: false,
+                    },
+                },
+            ),
+            ..Default::default()
+        },
         route_resolver: RouteResolverConfig {
             router_cache_max_capacity: 0,
             router_cache_ttl: Default::default(),
             router_cache_eviction_period: Default::default(),
         },
+        api_key_lookup: ApiKeyLookupConfig {
+            api_key_cache_max_capacity: 0,
+            api_key_cache_ttl: Default::default(),
+            api_key_cache_eviction_period: Default::default(),
+        },
         ..Default::default()
     }
 }
@@ -95,6 +95,14 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "golem_common::model::auth::TokenWithSecret",
             ),
             // component
+            (
+                "ComponentCompilationState",
+                "golem_common::model::component::ComponentCompilationState",
+            ),
+            (
+                "ComponentCompilationStatus",
+                "golem_common::model::component::ComponentCompilationStatus",
+            ),
             (
                 "ComponentCreation",
                 "golem_common::model::component::ComponentCreation",
@@ -231,6 +239,23 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "golem_common::model::reports::AccountSummaryReport",
             ),
             // security_scheme
+            ("ApiKey", "golem_common::model::security_scheme::ApiKey"),
+            (
+                "ApiKeyCreation",
+                "golem_common::model::security_scheme::ApiKeyCreation",
+            ),
+            (
+                "ApiKeySecuritySchemeCreation",
+                "golem_common::model::security_scheme::ApiKeySecuritySchemeCreation",
+            ),
+            (
+                "ApiKeySecuritySchemeDto",
+                "golem_common::model::security_scheme::ApiKeySecuritySchemeDto",
+            ),
+            (
+                "ApiKeyWithSecret",
+                "golem_common::model::security_scheme::ApiKeyWithSecret",
+            ),
             (
                 "SecuritySchemeCreation",
                 "golem_common::model::security_scheme::SecuritySchemeCreation",
@@ -244,14 +269,50 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
                 "golem_common::model::security_scheme::SecuritySchemeUpdate",
             ),
             // worker
+            (
+                "BulkWorkerOperation",
+                "golem_common::model::worker::BulkWorkerOperation",
+            ),
+            (
+                "BulkWorkerOperationJob",
+                "golem_common::model::worker::BulkWorkerOperationJob",
+            ),
+            (
+                "BulkWorkerOperationRequest",
+                "golem_common::model::worker::BulkWorkerOperationRequest",
+            ),
+            (
+                "BulkWorkerOperationStatus",
+                "golem_common::model::worker::BulkWorkerOperationStatus",
+            ),
             (
                 "FlatComponentFileSystemNode",
                 "golem_common::model::worker::FlatComponentFileSystemNode",
             ),
+            (
+                "CronRecurrence",
+                "golem_common::model::worker::CronRecurrence",
+            ),
+            (
+                "IntervalRecurrence",
+                "golem_common::model::worker::IntervalRecurrence",
+            ),
+            (
+                "RecurringInvocation",
+                "golem_common::model::worker::RecurringInvocation",
+            ),
             (
                 "RevertWorkerTarget",
                 "golem_common::model::worker::RevertWorkerTarget",
             ),
+            (
+                "ScheduleRecurrence",
+                "golem_common::model::worker::ScheduleRecurrence",
+            ),
+            (
+                "ChangeWorkerLabelsRequest",
+                "golem_common::model::worker::ChangeWorkerLabelsRequest",
+            ),
             (
                 "WasiConfigVars",
                 "golem_common::model::worker::WasiConfigVars",
@@ -274,6 +335,10 @@ fn generate(yaml_path: PathBuf, out_dir: OsString) {
             ),
             // oplog
             ("OplogCursor", "golem_common::model::oplog::OplogCursor"),
+            (
+                "OplogSearchHit",
+                "golem_common::model::oplog::OplogSearchHit",
+            ),
             (
                 "PublicOplogEntry",
                 "golem_common::model::oplog::PublicOplogEntry",
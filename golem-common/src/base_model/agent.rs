@@ -920,11 +920,15 @@ pub struct QueryVariable {
     feature = "full",
     derive(desert_rust::BinaryCodec, poem_openapi::Object)
 )]
-#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", desert(evolution(FieldAdded("scopes", Vec::new()))))]
 #[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
 #[serde(rename_all = "camelCase")]
 pub struct AgentHttpAuthDetails {
     pub required: bool,
+    /// Scopes bearer tokens have to grant to call the endpoint
+    #[serde(default)]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub scopes: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, IntoValue, FromValue)]
@@ -940,6 +944,7 @@ pub enum Principal {
     GolemUser(GolemUserPrincipal),
     #[unit_case]
     Anonymous(Empty),
+    ApiKey(ApiKeyPrincipal),
 }
 
 impl Principal {
@@ -994,6 +999,22 @@ pub struct GolemUserPrincipal {
     pub account_id: AccountId,
 }
 
+/// Caller authenticated with an API key issued for an api-key security scheme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, IntoValue, FromValue)]
+#[cfg_attr(
+    feature = "full",
+    derive(desert_rust::BinaryCodec, poem_openapi::Object)
+)]
+#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyPrincipal {
+    pub key_id: String,
+    pub name: String,
+    pub security_scheme: String,
+    pub scopes: Vec<String>,
+}
+
 pub trait UnstructuredTextExtensions {
     fn into_text_reference(self) -> TextReference;
     fn from_text_reference(text_ref: TextReference) -> Result<Self, String>
@@ -208,6 +208,15 @@ declare_structs! {
         pub path: ComponentFilePath,
         pub permissions: ComponentFilePermissions,
     }
+
+    /// Progress of compiling a component revision to native code
+    pub struct ComponentCompilationStatus {
+        pub component_id: ComponentId,
+        pub revision: ComponentRevision,
+        pub state: ComponentCompilationState,
+        /// Error of the last compilation attempt, if it failed
+        pub error: Option<String>,
+    }
 }
 
 declare_unions! {
@@ -225,6 +234,15 @@ declare_enums! {
         ReadOnly,
         ReadWrite,
     }
+
+    pub enum ComponentCompilationState {
+        /// The compilation service has no information about the component revision
+        Unknown,
+        Queued,
+        Compiling,
+        Ready,
+        Failed,
+    }
 }
 
 impl ComponentFilePermissions {
@@ -17,7 +17,7 @@ use crate::base_model::agent::AgentTypeName;
 use crate::base_model::diff;
 use crate::base_model::domain_registration::Domain;
 use crate::base_model::environment::EnvironmentId;
-use crate::{declare_revision, declare_structs, newtype_uuid};
+use crate::{declare_enums, declare_revision, declare_structs, newtype_uuid};
 use chrono::DateTime;
 use std::collections::BTreeMap;
 
@@ -27,13 +27,28 @@ declare_revision!(HttpApiDeploymentRevision);
 
 declare_structs! {
     #[derive(Default)]
-    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
-    #[cfg_attr(feature = "full", desert(transparent))]
     pub struct HttpApiDeploymentAgentOptions {
         /// Security scheme to use for all agent methods that require auth.
         /// Failure to provide a security scheme for an agent that requires one will lead to a deployment failure.
         /// If the requested security scheme does not exist in the environment, the route will be disabled at runtime.
-        pub security_scheme: Option<SecuritySchemeName>
+        pub security_scheme: Option<SecuritySchemeName>,
+        /// Rate limits applied to every HTTP route of the agent. A request is rejected with
+        /// 429 Too Many Requests as soon as any of the limits is exceeded.
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub rate_limits: Vec<HttpRateLimit>
+    }
+
+    /// Token bucket holding up to `burst` requests, refilled with `requests` tokens every `interval_seconds`.
+    /// Long intervals can be used to express quotas, e.g. 10000 requests per day.
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(feature = "full", desert(evolution()))]
+    pub struct HttpRateLimit {
+        pub scope: HttpRateLimitScope,
+        pub requests: u32,
+        pub interval_seconds: u32,
+        /// Defaults to `requests`
+        pub burst: Option<u32>
     }
 
     pub struct HttpApiDeploymentCreation {
@@ -56,3 +71,15 @@ declare_structs! {
         pub created_at: DateTime<chrono::Utc>,
     }
 }
+
+declare_enums! {
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    pub enum HttpRateLimitScope {
+        /// One bucket per route, shared by all callers
+        Route,
+        /// One bucket per route and authenticated caller, unauthenticated callers are told apart by their address
+        Principal,
+        /// One bucket per route and client address
+        ClientIp,
+    }
+}
@@ -534,12 +534,30 @@ impl IdempotencyKey {
     /// we generate a UUIDv5 in the ROOT_NS namespace and use that a unique namespace for generating
     /// the new idempotency key.
     pub fn derived(base: &IdempotencyKey, oplog_index: OplogIndex) -> Self {
+        let name = format!("oplog-index-{oplog_index}");
+        Self::derived_from_name(base, &name)
+    }
+
+    /// Generates a deterministic idempotency key for one occurrence of a recurring scheduled invocation,
+    /// identified by the base idempotency key of the schedule and the occurrence's scheduled time.
+    pub fn derived_for_occurrence(base: &IdempotencyKey, timestamp_millis: i64) -> Self {
+        let name = format!("occurrence-{timestamp_millis}");
+        Self::derived_from_name(base, &name)
+    }
+
+    /// Generates a deterministic idempotency key for one of the key-value change notifications
+    /// sent by the durable host function at the given oplog index.
+    pub fn derived_for_change(base: &IdempotencyKey, oplog_index: OplogIndex, idx: usize) -> Self {
+        let name = format!("oplog-index-{oplog_index}-change-{idx}");
+        Self::derived_from_name(base, &name)
+    }
+
+    fn derived_from_name(base: &IdempotencyKey, name: &str) -> Self {
         let namespace = if let Ok(base_uuid) = Uuid::parse_str(&base.value) {
             base_uuid
         } else {
             Uuid::new_v5(&Self::ROOT_NS, base.value.as_bytes())
         };
-        let name = format!("oplog-index-{oplog_index}");
         Self::from_uuid(Uuid::new_v5(&namespace, name.as_bytes()))
     }
 }
@@ -491,4 +491,29 @@ oplog_entry! {
             begin_index: OplogIndex,
         }
     },
+    /// A snapshot of the worker's state taken automatically by the executor
+    ///
+    /// Recovery loads the latest snapshot and replays only the entries following it.
+    Snapshot {
+        hint: true
+        raw {
+            data: payload::OplogPayload<Vec<u8>>,
+        }
+        public {
+            data: Vec<u8>,
+        }
+    },
+    /// Sets and removes labels of the worker. Labels are metadata only, they are not visible
+    /// for the running worker and do not affect its execution.
+    ChangeLabels {
+        hint: true
+        raw {
+            set: BTreeMap<String, String>,
+            removed: BTreeSet<String>,
+        }
+        public {
+            set: BTreeMap<String, String>,
+            removed: BTreeSet<String>,
+        }
+    },
 }
@@ -98,13 +98,25 @@ macro_rules! oplog_entry {
                 }
             )),*
         }
+
+        impl PublicOplogEntry {
+            pub fn timestamp(&self) -> Timestamp {
+                match self {
+                    $(Self::$case(params) => params.timestamp),*
+                }
+            }
+        }
     }
 }
 
+/// Defines a payload enum of host requests or responses, with a struct for each case.
+///
+/// The payloads are stored in the oplog, so new cases must be appended at the end, and fields
+/// added to an existing case must be declared with `evolution(FieldAdded(..))` after its name.
 #[macro_export]
 macro_rules! oplog_payload {
         ( $typename:ident => {
-                $($(#[$casemeta:meta])* $case:ident {
+                $($(#[$casemeta:meta])* $case:ident $(evolution($($evolution:tt)*))? {
                     $($(#[$meta:meta])* $field:ident: $typ:ty),* $(,)?
                 }),* $(,)?
         }) => {
@@ -149,6 +161,7 @@ macro_rules! oplog_payload {
             inner_name = $typename + $case =>
 
             #[derive(Clone, Debug, PartialEq, desert_rust::BinaryCodec, IntoValue, FromValue)]
+            $(#[desert(evolution($($evolution)*))])?
             pub struct $inner_name {
                 $( $(#[$meta])* pub $field: $typ ),*
             }
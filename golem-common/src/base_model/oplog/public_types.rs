@@ -16,7 +16,7 @@ use crate::base_model::component::{ComponentRevision, PluginPriority};
 use crate::base_model::invocation_context::{SpanId, TraceId};
 use crate::base_model::oplog::public_oplog_entry::{Deserialize, Serialize};
 use crate::base_model::oplog::PublicOplogEntry;
-use crate::base_model::{Empty, IdempotencyKey, OplogIndex, Timestamp};
+use crate::base_model::{Empty, IdempotencyKey, OplogIndex, Timestamp, WorkerId};
 use crate::declare_structs;
 use golem_wasm::ValueAndType;
 use golem_wasm_derive::{FromValue, IntoValue};
@@ -49,6 +49,13 @@ declare_structs! {
         pub oplog_index: OplogIndex,
         pub entry: PublicOplogEntry,
     }
+
+    /// An oplog entry found by searching the oplogs of all the workers of a component
+    pub struct OplogSearchHit {
+        pub worker_id: WorkerId,
+        pub oplog_index: OplogIndex,
+        pub entry: PublicOplogEntry,
+    }
 }
 
 #[derive(
@@ -27,6 +27,8 @@ newtype_uuid!(
 
 declare_revision!(SecuritySchemeRevision);
 
+newtype_uuid!(ApiKeyId);
+
 declare_transparent_newtypes! {
     #[derive(Display, Eq, Hash, PartialOrd, Ord)]
     #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
@@ -59,6 +61,12 @@ declare_structs! {
         pub client_secret: String,
         pub redirect_url: String,
         pub scopes: Vec<String>,
+        /// Issuer used for OpenID Connect discovery, required for custom providers
+        pub issuer_url: Option<String>,
+        /// Defaults to the authorization code flow
+        pub mode: Option<SecuritySchemeMode>,
+        /// Audience expected in bearer tokens, defaults to the client id
+        pub audience: Option<String>,
     }
 
     pub struct SecuritySchemeUpdate {
@@ -68,6 +76,9 @@ declare_structs! {
         pub client_secret: Option<String>,
         pub redirect_url: Option<String>,
         pub scopes: Option<Vec<String>>,
+        pub issuer_url: Option<String>,
+        pub mode: Option<SecuritySchemeMode>,
+        pub audience: Option<String>,
     }
 
     pub struct SecuritySchemeDto {
@@ -79,6 +90,64 @@ declare_structs! {
         pub client_id: String,
         pub redirect_url: String,
         pub scopes: Vec<String>,
+        pub issuer_url: Option<String>,
+        pub mode: SecuritySchemeMode,
+        pub audience: Option<String>,
+    }
+
+    /// Security scheme accepting api keys issued by the registry, without OpenID Connect settings
+    pub struct ApiKeySecuritySchemeCreation {
+        pub name: SecuritySchemeName,
+    }
+
+    pub struct ApiKeySecuritySchemeDto {
+        pub id: SecuritySchemeId,
+        pub revision: SecuritySchemeRevision,
+        pub name: SecuritySchemeName,
+        pub environment_id: EnvironmentId,
+    }
+
+    pub struct ApiKey {
+        pub id: ApiKeyId,
+        pub security_scheme_id: SecuritySchemeId,
+        pub name: String,
+        pub scopes: Vec<String>,
+        pub created_at: chrono::DateTime<chrono::Utc>,
+        pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    pub struct ApiKeyCreation {
+        pub name: String,
+        /// Scopes granted to the key, routes require all the scopes they declare
+        #[serde(default)]
+        #[cfg_attr(feature = "full", oai(default))]
+        pub scopes: Vec<String>,
+        /// The key never expires if not set
+        pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+
+    /// Newly created API key. The secret is only returned once, the registry only stores its hash
+    pub struct ApiKeyWithSecret {
+        pub id: ApiKeyId,
+        pub secret: String,
+        pub security_scheme_id: SecuritySchemeId,
+        pub name: String,
+        pub scopes: Vec<String>,
+        pub created_at: chrono::DateTime<chrono::Utc>,
+        pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    }
+}
+
+impl ApiKeyWithSecret {
+    pub fn without_secret(self) -> ApiKey {
+        ApiKey {
+            id: self.id,
+            security_scheme_id: self.security_scheme_id,
+            name: self.name,
+            scopes: self.scopes,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+        }
     }
 }
 
@@ -88,5 +157,17 @@ declare_enums! {
         Facebook,
         Microsoft,
         Gitlab,
+        /// Any OpenID Connect compliant provider, discovered through its issuer url
+        Custom,
+    }
+
+    /// How callers of the routes protected by an OpenID Connect security scheme authenticate
+    #[derive(Default)]
+    pub enum SecuritySchemeMode {
+        /// Browser based authorization code flow, keeping the authenticated user in a session
+        #[default]
+        AuthorizationCode,
+        /// Stateless JWTs sent in the `Authorization: Bearer` header, for machine-to-machine callers
+        BearerToken,
     }
 }
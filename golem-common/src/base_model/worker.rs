@@ -13,15 +13,20 @@
 // limitations under the License.
 
 use crate::base_model::account::AccountId;
-use crate::base_model::component::{ComponentFilePermissions, ComponentRevision, PluginPriority};
+use crate::base_model::component::{
+    ComponentFilePermissions, ComponentId, ComponentRevision, PluginPriority,
+};
 use crate::base_model::environment::EnvironmentId;
 use crate::base_model::oplog::WorkerResourceId;
 use crate::base_model::regions::OplogRegion;
-use crate::base_model::{OplogIndex, Timestamp, WorkerId, WorkerResourceDescription, WorkerStatus};
-use crate::{declare_enums, declare_structs, declare_unions};
+use crate::base_model::{
+    IdempotencyKey, OplogIndex, Timestamp, WorkerFilter, WorkerId, WorkerResourceDescription,
+    WorkerStatus,
+};
+use crate::{declare_enums, declare_structs, declare_unions, newtype_uuid};
 use golem_wasm::{FromValue, IntoValue, Value};
 use golem_wasm_derive::{FromValue, IntoValue};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -35,6 +40,40 @@ pub struct WorkerCreationRequest {
     pub config_vars: WasiConfigVars,
 }
 
+/// Changes the labels of a worker. Removals are applied before the new values are set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "full", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+pub struct ChangeWorkerLabelsRequest {
+    /// Labels to set, replacing the previous values of the same keys
+    #[serde(default)]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub set: BTreeMap<String, String>,
+    /// Keys of the labels to remove
+    #[serde(default)]
+    #[cfg_attr(feature = "full", oai(default))]
+    pub removed: BTreeSet<String>,
+}
+
+impl ChangeWorkerLabelsRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        self.set
+            .keys()
+            .chain(self.removed.iter())
+            .try_for_each(|key| validate_label_key(key))
+    }
+}
+
+/// Label keys are checked the same way when changed through the API and by the agent itself
+pub fn validate_label_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        Err("Label names must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "full", derive(poem_openapi::Object))]
@@ -90,6 +129,8 @@ impl IntoValue for WasiConfigVars {
     }
 }
 
+newtype_uuid!(BulkWorkerOperationId);
+
 declare_enums! {
     pub enum FlatComponentFileSystemNodeKind {
         Directory,
@@ -120,6 +161,15 @@ declare_unions! {
         RevertToOplogIndex(RevertToOplogIndex),
         RevertLastInvocations(RevertLastInvocations),
     }
+
+    /// Describes how a recurring scheduled invocation is repeated after each occurrence
+    #[derive(IntoValue, FromValue)]
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(feature = "full", desert(evolution()))]
+    pub enum ScheduleRecurrence {
+        Interval(IntervalRecurrence),
+        Cron(CronRecurrence),
+    }
 }
 
 declare_structs! {
@@ -144,6 +194,19 @@ declare_structs! {
         pub description: WorkerResourceDescription,
     }
 
+    /// Resources consumed by an agent over its whole lifetime
+    pub struct WorkerResourceUsage {
+        /// Total fuel consumed by the completed invocations
+        pub consumed_fuel: u64,
+        /// The highest total linear memory size the agent reached. Memory grown again after a jump
+        /// or revert is added on top of it, so in that case it is an upper bound.
+        pub peak_linear_memory_size: u64,
+        /// Number of completed invocations
+        pub invocation_count: u64,
+        /// Total wall-clock time spent in completed invocations, in milliseconds
+        pub invocation_time_millis: u64,
+    }
+
     pub struct WorkerMetadataDto {
         pub worker_id: WorkerId,
         pub environment_id: EnvironmentId,
@@ -159,6 +222,9 @@ declare_structs! {
         pub last_error: Option<String>,
         pub component_size: u64,
         pub total_linear_memory_size: u64,
+        pub resource_usage: WorkerResourceUsage,
+        /// Mutable key-value labels attached to the worker
+        pub labels: BTreeMap<String, String>,
         pub exported_resource_instances: Vec<ExportedResourceMetadata>,
         pub active_plugins: HashSet<PluginPriority>,
         /// Oplog regions that are skipped during the worker's state recovery, but describe
@@ -183,6 +249,43 @@ declare_structs! {
         pub number_of_invocations: u64,
     }
 
+    /// Repeats the invocation with a fixed interval, aligned to the first scheduled occurrence
+    #[derive(IntoValue, FromValue)]
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(feature = "full", desert(evolution()))]
+    pub struct IntervalRecurrence {
+        pub interval_millis: u64,
+        /// No occurrences are scheduled after this point in time
+        pub until: Option<Timestamp>,
+    }
+
+    /// Repeats the invocation according to a cron expression, evaluated in UTC
+    #[derive(IntoValue, FromValue)]
+    #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec))]
+    #[cfg_attr(feature = "full", desert(evolution()))]
+    pub struct CronRecurrence {
+        pub expression: String,
+        /// No occurrences are scheduled after this point in time
+        pub until: Option<Timestamp>,
+    }
+
+    /// A recurring invocation registered for a worker, identified by its base idempotency key
+    pub struct RecurringInvocation {
+        pub idempotency_key: IdempotencyKey,
+        pub function_name: String,
+        pub recurrence: ScheduleRecurrence,
+        pub next_occurrence: Timestamp,
+    }
+
+    /// An agent which is expired according to the retention policy of its environment
+    pub struct RetentionCandidate {
+        pub worker_id: WorkerId,
+        pub status: WorkerStatus,
+        pub reason: RetentionReason,
+        /// Time of the agent's last oplog entry
+        pub last_activity: Timestamp,
+    }
+
     pub struct FlatComponentFileSystemNode {
         pub name: String,
         pub last_modified: u64,
@@ -192,11 +295,159 @@ declare_structs! {
     }
 }
 
+declare_structs! {
+    pub struct BulkInterruptWorkers {
+        pub recover_immediately: bool,
+    }
+
+    pub struct BulkResumeWorkers {}
+
+    pub struct BulkUpdateWorkers {
+        pub mode: WorkerUpdateMode,
+        pub target_revision: ComponentRevision,
+    }
+
+    pub struct BulkDeleteWorkers {}
+
+    pub struct BulkRevertWorkers {
+        pub target: RevertWorkerTarget,
+    }
+
+    pub struct BulkActivatePlugin {
+        pub plugin_priority: PluginPriority,
+    }
+
+    pub struct BulkDeactivatePlugin {
+        pub plugin_priority: PluginPriority,
+    }
+
+    /// Starts applying an operation to every worker of a component matching the filter
+    pub struct BulkWorkerOperationRequest {
+        /// Workers to apply the operation to, all workers of the component if missing
+        pub filter: Option<WorkerFilter>,
+        pub operation: BulkWorkerOperation,
+        /// Maximum number of workers processed at the same time, capped by the server configuration
+        pub max_concurrency: Option<u32>,
+        /// Maximum number of workers processed per second, capped by the server configuration
+        pub max_rate: Option<u32>,
+    }
+
+    pub struct BulkWorkerOperationFailure {
+        pub worker_id: WorkerId,
+        pub error: String,
+    }
+
+    /// Progress of a bulk worker operation
+    pub struct BulkWorkerOperationJob {
+        pub id: BulkWorkerOperationId,
+        pub component_id: ComponentId,
+        pub filter: Option<WorkerFilter>,
+        pub operation: BulkWorkerOperation,
+        pub status: BulkWorkerOperationStatus,
+        pub started_at: Timestamp,
+        pub finished_at: Option<Timestamp>,
+        /// Number of workers matched by the filter so far
+        pub matched: u64,
+        pub succeeded: u64,
+        pub failed: u64,
+        /// The first failures of the operation, the rest is only counted in `failed`
+        pub failures: Vec<BulkWorkerOperationFailure>,
+        /// Reason of the whole operation failing, if it could not enumerate the workers
+        pub error: Option<String>,
+    }
+}
+
+declare_unions! {
+    /// Operation applied to each worker matched by a bulk worker operation
+    pub enum BulkWorkerOperation {
+        Interrupt(BulkInterruptWorkers),
+        Resume(BulkResumeWorkers),
+        Update(BulkUpdateWorkers),
+        Delete(BulkDeleteWorkers),
+        Revert(BulkRevertWorkers),
+        ActivatePlugin(BulkActivatePlugin),
+        DeactivatePlugin(BulkDeactivatePlugin),
+    }
+}
+
 declare_enums! {
     pub enum WorkerUpdateMode {
         Automatic,
         Manual,
     }
+
+    pub enum BulkWorkerOperationStatus {
+        Running,
+        Completed,
+        Cancelled,
+        Failed,
+    }
+
+    /// The retention rule an agent is expired by
+    pub enum RetentionReason {
+        Exited,
+        Failed,
+        Idle,
+    }
+}
+
+impl ScheduleRecurrence {
+    pub fn until(&self) -> Option<Timestamp> {
+        match self {
+            ScheduleRecurrence::Interval(interval) => interval.until,
+            ScheduleRecurrence::Cron(cron) => cron.until,
+        }
+    }
+}
+
+impl Display for ScheduleRecurrence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduleRecurrence::Interval(interval) => {
+                write!(f, "every {}ms", interval.interval_millis)?
+            }
+            ScheduleRecurrence::Cron(cron) => write!(f, "cron '{}'", cron.expression)?,
+        }
+        if let Some(until) = self.until() {
+            write!(f, " until {until}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for BulkWorkerOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkWorkerOperation::Interrupt(_) => write!(f, "interrupt"),
+            BulkWorkerOperation::Resume(_) => write!(f, "resume"),
+            BulkWorkerOperation::Update(_) => write!(f, "update"),
+            BulkWorkerOperation::Delete(_) => write!(f, "delete"),
+            BulkWorkerOperation::Revert(_) => write!(f, "revert"),
+            BulkWorkerOperation::ActivatePlugin(_) => write!(f, "activate-plugin"),
+            BulkWorkerOperation::DeactivatePlugin(_) => write!(f, "deactivate-plugin"),
+        }
+    }
+}
+
+impl Display for BulkWorkerOperationStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkWorkerOperationStatus::Running => write!(f, "running"),
+            BulkWorkerOperationStatus::Completed => write!(f, "completed"),
+            BulkWorkerOperationStatus::Cancelled => write!(f, "cancelled"),
+            BulkWorkerOperationStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl Display for RetentionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetentionReason::Exited => write!(f, "exited"),
+            RetentionReason::Failed => write!(f, "failed"),
+            RetentionReason::Idle => write!(f, "idle"),
+        }
+    }
 }
 
 impl FromValue for WasiConfigVars {
@@ -182,6 +182,70 @@ impl Display for WorkerWasiConfigVarsFilter {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "full",
+    derive(desert_rust::BinaryCodec, poem_openapi::Object)
+)]
+#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerLabelFilter {
+    pub name: String,
+    pub comparator: StringFilterComparator,
+    pub value: String,
+}
+
+impl WorkerLabelFilter {
+    pub fn new(name: String, comparator: StringFilterComparator, value: String) -> Self {
+        Self {
+            name,
+            comparator,
+            value,
+        }
+    }
+}
+
+impl Display for WorkerLabelFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "label.{} {} {}", self.name, self.comparator, self.value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "full",
+    derive(desert_rust::BinaryCodec, poem_openapi::Object)
+)]
+#[cfg_attr(feature = "full", desert(evolution()))]
+#[cfg_attr(feature = "full", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerResourceUsageFilter {
+    pub metric: WorkerResourceUsageMetric,
+    pub comparator: FilterComparator,
+    pub value: u64,
+}
+
+impl WorkerResourceUsageFilter {
+    pub fn new(
+        metric: WorkerResourceUsageMetric,
+        comparator: FilterComparator,
+        value: u64,
+    ) -> Self {
+        Self {
+            metric,
+            comparator,
+            value,
+        }
+    }
+}
+
+impl Display for WorkerResourceUsageFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.metric, self.comparator, self.value)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "full",
@@ -290,6 +354,8 @@ pub enum WorkerFilter {
     Or(WorkerOrFilter),
     Not(WorkerNotFilter),
     WasiConfigVars(WorkerWasiConfigVarsFilter),
+    ResourceUsage(WorkerResourceUsageFilter),
+    Label(WorkerLabelFilter),
 }
 
 impl WorkerFilter {
@@ -343,6 +409,10 @@ impl WorkerFilter {
         WorkerFilter::WasiConfigVars(WorkerWasiConfigVarsFilter::new(name, comparator, value))
     }
 
+    pub fn new_label(name: String, comparator: StringFilterComparator, value: String) -> Self {
+        WorkerFilter::Label(WorkerLabelFilter::new(name, comparator, value))
+    }
+
     pub fn new_revision(comparator: FilterComparator, value: ComponentRevision) -> Self {
         WorkerFilter::Revision(WorkerRevisionFilter::new(comparator, value))
     }
@@ -355,6 +425,14 @@ impl WorkerFilter {
         WorkerFilter::CreatedAt(WorkerCreatedAtFilter::new(comparator, value))
     }
 
+    pub fn new_resource_usage(
+        metric: WorkerResourceUsageMetric,
+        comparator: FilterComparator,
+        value: u64,
+    ) -> Self {
+        WorkerFilter::ResourceUsage(WorkerResourceUsageFilter::new(metric, comparator, value))
+    }
+
     pub fn from(filters: Vec<String>) -> Result<WorkerFilter, String> {
         let mut fs = Vec::new();
         for f in filters {
@@ -385,6 +463,12 @@ impl Display for WorkerFilter {
             WorkerFilter::WasiConfigVars(filter) => {
                 write!(f, "{filter}")
             }
+            WorkerFilter::ResourceUsage(filter) => {
+                write!(f, "{filter}")
+            }
+            WorkerFilter::Label(filter) => {
+                write!(f, "{filter}")
+            }
             WorkerFilter::Not(filter) => {
                 write!(f, "{filter}")
             }
@@ -427,6 +511,15 @@ impl FromStr for WorkerFilter {
                     comparator.parse()?,
                     value.parse()?,
                 )),
+                _ if arg.parse::<WorkerResourceUsageMetric>().is_ok() => {
+                    Ok(WorkerFilter::new_resource_usage(
+                        arg.parse()?,
+                        comparator.parse()?,
+                        value
+                            .parse()
+                            .map_err(|e| format!("Invalid filter value: {e}"))?,
+                    ))
+                }
                 _ if arg.starts_with("env.") => {
                     let name = &arg[4..];
                     Ok(WorkerFilter::new_env(
@@ -435,6 +528,14 @@ impl FromStr for WorkerFilter {
                         value.to_string(),
                     ))
                 }
+                _ if arg.starts_with("label.") => {
+                    let name = &arg[6..];
+                    Ok(WorkerFilter::new_label(
+                        name.to_string(),
+                        comparator.parse()?,
+                        value.to_string(),
+                    ))
+                }
                 _ => Err(format!("Invalid filter: {s}")),
             }
         } else {
@@ -527,6 +628,74 @@ impl Display for StringFilterComparator {
     }
 }
 
+/// Resource usage metrics of an agent which can be used in filters
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec, poem_openapi::Enum))]
+#[cfg_attr(feature = "full", desert(evolution()))]
+pub enum WorkerResourceUsageMetric {
+    ConsumedFuel,
+    PeakLinearMemorySize,
+    InvocationCount,
+    InvocationTimeMillis,
+}
+
+impl Display for WorkerResourceUsageMetric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            WorkerResourceUsageMetric::ConsumedFuel => "consumed_fuel",
+            WorkerResourceUsageMetric::PeakLinearMemorySize => "peak_linear_memory_size",
+            WorkerResourceUsageMetric::InvocationCount => "invocation_count",
+            WorkerResourceUsageMetric::InvocationTimeMillis => "invocation_time_millis",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for WorkerResourceUsageMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "consumed_fuel" | "consumedFuel" => Ok(WorkerResourceUsageMetric::ConsumedFuel),
+            "peak_linear_memory_size" | "peakLinearMemorySize" => {
+                Ok(WorkerResourceUsageMetric::PeakLinearMemorySize)
+            }
+            "invocation_count" | "invocationCount" => {
+                Ok(WorkerResourceUsageMetric::InvocationCount)
+            }
+            "invocation_time_millis" | "invocationTimeMillis" => {
+                Ok(WorkerResourceUsageMetric::InvocationTimeMillis)
+            }
+            _ => Err(format!("Unknown Resource Usage Metric: {s}")),
+        }
+    }
+}
+
+impl TryFrom<i32> for WorkerResourceUsageMetric {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WorkerResourceUsageMetric::ConsumedFuel),
+            1 => Ok(WorkerResourceUsageMetric::PeakLinearMemorySize),
+            2 => Ok(WorkerResourceUsageMetric::InvocationCount),
+            3 => Ok(WorkerResourceUsageMetric::InvocationTimeMillis),
+            _ => Err(format!("Unknown Resource Usage Metric: {value}")),
+        }
+    }
+}
+
+impl From<WorkerResourceUsageMetric> for i32 {
+    fn from(value: WorkerResourceUsageMetric) -> Self {
+        match value {
+            WorkerResourceUsageMetric::ConsumedFuel => 0,
+            WorkerResourceUsageMetric::PeakLinearMemorySize => 1,
+            WorkerResourceUsageMetric::InvocationCount => 2,
+            WorkerResourceUsageMetric::InvocationTimeMillis => 3,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "full", derive(desert_rust::BinaryCodec, poem_openapi::Enum))]
 #[cfg_attr(feature = "full", desert(evolution()))]
@@ -17,7 +17,7 @@ use super::{
     HttpEndpointDetails, HttpMethod, HttpMountDetails, LiteralSegment, PathSegment, PathVariable,
     QueryVariable, SystemVariable, SystemVariableSegment,
 };
-use crate::base_model::agent::{GolemUserPrincipal, OidcPrincipal, Principal};
+use crate::base_model::agent::{ApiKeyPrincipal, GolemUserPrincipal, OidcPrincipal, Principal};
 use crate::model::agent::bindings::golem::agent::host;
 use crate::model::agent::{
     AgentConstructor, AgentDependency, AgentError, AgentMethod, AgentMode, AgentType,
@@ -843,6 +843,7 @@ impl From<AgentHttpAuthDetails> for super::bindings::golem::agent::common::AuthD
     fn from(value: AgentHttpAuthDetails) -> Self {
         Self {
             required: value.required,
+            scopes: value.scopes,
         }
     }
 }
@@ -851,6 +852,7 @@ impl From<super::bindings::golem::agent::common::AuthDetails> for AgentHttpAuthD
     fn from(value: super::bindings::golem::agent::common::AuthDetails) -> Self {
         Self {
             required: value.required,
+            scopes: value.scopes,
         }
     }
 }
@@ -862,6 +864,7 @@ impl From<Principal> for super::bindings::golem::agent::common::Principal {
             Principal::Agent(inner) => Self::Agent(inner.into()),
             Principal::GolemUser(inner) => Self::GolemUser(inner.into()),
             Principal::Anonymous(_) => Self::Anonymous,
+            Principal::ApiKey(inner) => Self::ApiKey(inner.into()),
         }
     }
 }
@@ -875,6 +878,7 @@ impl From<super::bindings::golem::agent::common::Principal> for Principal {
             Value::Agent(inner) => Self::Agent(inner.into()),
             Value::GolemUser(inner) => Self::GolemUser(inner.into()),
             Value::Anonymous => Self::Anonymous(Empty {}),
+            Value::ApiKey(inner) => Self::ApiKey(inner.into()),
         }
     }
 }
@@ -944,3 +948,25 @@ impl From<super::bindings::golem::agent::common::GolemUserPrincipal> for GolemUs
         }
     }
 }
+
+impl From<ApiKeyPrincipal> for super::bindings::golem::agent::common::ApiKeyPrincipal {
+    fn from(value: ApiKeyPrincipal) -> Self {
+        Self {
+            key_id: value.key_id,
+            name: value.name,
+            security_scheme: value.security_scheme,
+            scopes: value.scopes,
+        }
+    }
+}
+
+impl From<super::bindings::golem::agent::common::ApiKeyPrincipal> for ApiKeyPrincipal {
+    fn from(value: super::bindings::golem::agent::common::ApiKeyPrincipal) -> Self {
+        Self {
+            key_id: value.key_id,
+            name: value.name,
+            security_scheme: value.security_scheme,
+            scopes: value.scopes,
+        }
+    }
+}
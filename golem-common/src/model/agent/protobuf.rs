@@ -1,13 +1,13 @@
 use super::{
     AgentConstructor, AgentDependency, AgentHttpAuthDetails, AgentMethod, AgentMode,
-    AgentPrincipal, AgentType, AgentTypeName, BinaryDescriptor, BinaryReference, BinarySource,
-    BinaryType, ComponentModelElementSchema, CorsOptions, CustomHttpMethod, DataSchema, DataValue,
-    ElementSchema, ElementValue, ElementValues, GolemUserPrincipal, HeaderVariable,
-    HttpEndpointDetails, HttpMethod, HttpMountDetails, LiteralSegment, NamedElementSchema,
-    NamedElementSchemas, NamedElementValue, NamedElementValues, OidcPrincipal, PathSegment,
-    PathVariable, Principal, QueryVariable, RegisteredAgentType, RegisteredAgentTypeImplementer,
-    SystemVariable, SystemVariableSegment, TextDescriptor, TextReference, TextSource, TextType,
-    Url,
+    AgentPrincipal, AgentType, AgentTypeName, ApiKeyPrincipal, BinaryDescriptor, BinaryReference,
+    BinarySource, BinaryType, ComponentModelElementSchema, CorsOptions, CustomHttpMethod,
+    DataSchema, DataValue, ElementSchema, ElementValue, ElementValues, GolemUserPrincipal,
+    HeaderVariable, HttpEndpointDetails, HttpMethod, HttpMountDetails, LiteralSegment,
+    NamedElementSchema, NamedElementSchemas, NamedElementValue, NamedElementValues, OidcPrincipal,
+    PathSegment, PathVariable, Principal, QueryVariable, RegisteredAgentType,
+    RegisteredAgentTypeImplementer, SystemVariable, SystemVariableSegment, TextDescriptor,
+    TextReference, TextSource, TextType, Url,
 };
 use crate::model::Empty;
 use golem_api_grpc::proto::golem::component::data_schema;
@@ -1075,6 +1075,7 @@ impl TryFrom<golem_api_grpc::proto::golem::component::AgentHttpAuthDetails>
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             required: value.required,
+            scopes: value.scopes,
         })
     }
 }
@@ -1083,6 +1084,7 @@ impl From<AgentHttpAuthDetails> for golem_api_grpc::proto::golem::component::Age
     fn from(value: AgentHttpAuthDetails) -> Self {
         Self {
             required: value.required,
+            scopes: value.scopes,
         }
     }
 }
@@ -1103,6 +1105,7 @@ impl TryFrom<golem_api_grpc::proto::golem::component::Principal> for Principal {
             Value::Agent(v) => Ok(Self::Agent(v.try_into()?)),
             Value::GolemUser(v) => Ok(Self::GolemUser(v.try_into()?)),
             Value::Anonymous(_) => Ok(Self::Anonymous(Empty {})),
+            Value::ApiKey(v) => Ok(Self::ApiKey(v.into())),
         }
     }
 }
@@ -1119,6 +1122,7 @@ impl From<Principal> for golem_api_grpc::proto::golem::component::Principal {
                 Principal::Anonymous(_) => {
                     Value::Anonymous(golem_api_grpc::proto::golem::common::Empty {})
                 }
+                Principal::ApiKey(v) => Value::ApiKey(v.into()),
             }),
         }
     }
@@ -1207,3 +1211,25 @@ impl From<GolemUserPrincipal> for golem_api_grpc::proto::golem::component::Golem
         }
     }
 }
+
+impl From<golem_api_grpc::proto::golem::component::ApiKeyPrincipal> for ApiKeyPrincipal {
+    fn from(value: golem_api_grpc::proto::golem::component::ApiKeyPrincipal) -> Self {
+        Self {
+            key_id: value.key_id,
+            name: value.name,
+            security_scheme: value.security_scheme,
+            scopes: value.scopes,
+        }
+    }
+}
+
+impl From<ApiKeyPrincipal> for golem_api_grpc::proto::golem::component::ApiKeyPrincipal {
+    fn from(value: ApiKeyPrincipal) -> Self {
+        Self {
+            key_id: value.key_id,
+            name: value.name,
+            security_scheme: value.security_scheme,
+            scopes: value.scopes,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::agent::AgentType;
+use crate::model::component::{
+    ComponentCreation, ComponentFileOptions, ComponentFilePath, ComponentName,
+};
+use crate::model::component_metadata::DynamicLinkedInstance;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use wasmtime::{Config, Engine, WasmBacktraceDetails};
+
+/// Version of the component bundle layout, bumped on incompatible changes
+pub const COMPONENT_BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Path of the JSON encoded [`ComponentBundleManifest`] in the bundle archive
+pub const COMPONENT_BUNDLE_MANIFEST_PATH: &str = "manifest.json";
+/// Path of the component's WASM binary in the bundle archive
+pub const COMPONENT_BUNDLE_WASM_PATH: &str = "component.wasm";
+/// Path of the pre-compiled component in the bundle archive
+pub const COMPONENT_BUNDLE_COMPILED_PATH: &str = "component.cwasm";
+/// Path of the initial file system archive in the bundle archive, in the same format as
+/// the files archive accepted when creating a component
+pub const COMPONENT_BUNDLE_FILES_PATH: &str = "files.zip";
+
+/// Metadata of a component bundle, a zip archive containing everything needed to install a
+/// component without running the compilation service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentBundleManifest {
+    pub format_version: u32,
+    pub component_name: ComponentName,
+    pub engine: EngineFingerprint,
+    #[serde(default)]
+    pub file_options: BTreeMap<ComponentFilePath, ComponentFileOptions>,
+    #[serde(default)]
+    pub dynamic_linking: HashMap<String, DynamicLinkedInstance>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub agent_types: Vec<AgentType>,
+}
+
+impl ComponentBundleManifest {
+    /// Bundles cannot contain plugin installations, as plugins transform the component binary
+    /// after the bundled artifact was compiled.
+    pub fn to_component_creation(&self) -> ComponentCreation {
+        ComponentCreation {
+            component_name: self.component_name.clone(),
+            file_options: self.file_options.clone(),
+            dynamic_linking: self.dynamic_linking.clone(),
+            env: self.env.clone(),
+            agent_types: self.agent_types.clone(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+/// Identifies the wasmtime engine settings a component was compiled with. Pre-compiled
+/// components can only be loaded by an engine with the same fingerprint, which is checked by
+/// the worker executors when loading them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineFingerprint {
+    pub target: String,
+    pub compatibility_hash: String,
+}
+
+impl EngineFingerprint {
+    pub fn of(engine: &Engine) -> Self {
+        Self {
+            target: format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            compatibility_hash: engine_compatibility_hash(engine).to_hex().to_string(),
+        }
+    }
+}
+
+/// Hash of the settings of an engine which affect the generated native code.
+///
+/// Computed with blake3 as it gets persisted, while the standard library hashers are not
+/// guaranteed to be stable between releases.
+pub fn engine_compatibility_hash(engine: &Engine) -> blake3::Hash {
+    let mut hasher = Blake3Hasher(blake3::Hasher::new());
+    engine.precompile_compatibility_hash().hash(&mut hasher);
+    hasher.0.finalize()
+}
+
+/// Feeds the data of a `Hash` implementation to blake3
+struct Blake3Hasher(blake3::Hasher);
+
+impl Hasher for Blake3Hasher {
+    fn finish(&self) -> u64 {
+        let hash = self.0.finalize();
+        u64::from_le_bytes(hash.as_bytes()[..8].try_into().unwrap())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+impl Display for EngineFingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.target, self.compatibility_hash)
+    }
+}
+
+/// The wasmtime settings used for compiling components for the worker executors. Shared by the
+/// executor, the compilation service and the CLI so that their compiled components are
+/// interchangeable.
+pub fn wasmtime_config() -> Config {
+    let mut config = Config::default();
+
+    config.wasm_multi_value(true);
+    config.async_support(true);
+    config.wasm_component_model(true);
+    config.epoch_interruption(true);
+    config.consume_fuel(true);
+    config.wasm_backtrace_details(WasmBacktraceDetails::Enable);
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wasmtime_config, EngineFingerprint};
+    use test_r::test;
+    use wasmtime::Engine;
+
+    #[test]
+    fn engine_fingerprint_only_depends_on_the_settings() {
+        let engine1 = Engine::new(&wasmtime_config()).unwrap();
+        let engine2 = Engine::new(&wasmtime_config()).unwrap();
+
+        let mut other_config = wasmtime_config();
+        other_config.consume_fuel(false);
+        let other_engine = Engine::new(&other_config).unwrap();
+
+        assert_eq!(
+            EngineFingerprint::of(&engine1),
+            EngineFingerprint::of(&engine2)
+        );
+        assert_ne!(
+            EngineFingerprint::of(&engine1).compatibility_hash,
+            EngineFingerprint::of(&other_engine).compatibility_hash
+        );
+    }
+}
@@ -14,6 +14,7 @@
 
 use super::BTreeMapDiff;
 use crate::model::diff::{hash_from_serialized_value, BTreeSetDiff, Diffable, Hash, Hashable};
+use crate::model::http_api_deployment::HttpRateLimit;
 use serde::Serialize;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -21,12 +22,16 @@ use std::collections::{BTreeMap, BTreeSet};
 #[serde(rename_all = "camelCase")]
 pub struct HttpApiDeploymentAgentOptions {
     pub security_scheme: Option<String>,
+    // Skipped when empty to keep the hashes of deployments without rate limits unchanged
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rate_limits: Vec<HttpRateLimit>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HttpApiDeploymentAgentOptionsDiff {
     pub security_scheme_changed: bool,
+    pub rate_limits_changed: bool,
 }
 
 impl Diffable for HttpApiDeploymentAgentOptions {
@@ -34,10 +39,12 @@ impl Diffable for HttpApiDeploymentAgentOptions {
 
     fn diff(new: &Self, current: &Self) -> Option<Self::DiffResult> {
         let security_scheme_changed = new.security_scheme != current.security_scheme;
+        let rate_limits_changed = new.rate_limits != current.rate_limits;
 
-        if security_scheme_changed {
+        if security_scheme_changed || rate_limits_changed {
             Some(HttpApiDeploymentAgentOptionsDiff {
                 security_scheme_changed,
+                rate_limits_changed,
             })
         } else {
             None
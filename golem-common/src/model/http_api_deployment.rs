@@ -27,6 +27,7 @@ impl HttpApiDeployment {
                         k.0.clone(),
                         diff::HttpApiDeploymentAgentOptions {
                             security_scheme: v.security_scheme.as_ref().map(|v| v.0.clone()),
+                            rate_limits: v.rate_limits.clone(),
                         },
                     )
                 })
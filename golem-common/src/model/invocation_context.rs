@@ -24,6 +24,7 @@ use lazy_static::lazy_static;
 use nonempty_collections::NEVec;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
 pub use crate::base_model::invocation_context::*;
@@ -675,6 +676,12 @@ impl BinaryDeserializer for InvocationContextSpan {
     }
 }
 
+/// Span attribute holding the deadline of an RPC invocation as an ISO 8601 timestamp.
+///
+/// As span attributes are inherited by the remote invocation context, it also limits the RPC calls
+/// made by the callee while serving the invocation.
+pub const DEADLINE_ATTRIBUTE: &str = "deadline";
+
 #[derive(Clone, PartialEq, BinaryCodec)]
 #[desert(evolution())]
 pub struct InvocationContextStack {
@@ -777,6 +784,18 @@ impl InvocationContextStack {
         self.spans.insert(0, span);
     }
 
+    /// Returns the earliest deadline defined by the spans of this stack, including the inherited ones
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.spans
+            .first()
+            .get_attribute_chain(DEADLINE_ATTRIBUTE)?
+            .into_iter()
+            .filter_map(|value| match value {
+                AttributeValue::String(value) => Timestamp::from_str(&value).ok(),
+            })
+            .min()
+    }
+
     /// Returns the span IDs in this stack, partitioned by local and inherited ones
     /// Return value is (local, inherited)
     ///
@@ -1049,6 +1068,7 @@ mod protobuf {
 mod tests {
     use crate::model::invocation_context::{
         AttributeValue, InvocationContextSpan, InvocationContextStack, SpanId, TraceId,
+        DEADLINE_ATTRIBUTE,
     };
     use crate::model::Timestamp;
     use crate::serialization::{deserialize, serialize};
@@ -1152,6 +1172,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deadline_is_the_earliest_in_the_stack() {
+        let stack = example_stack_1();
+        assert_eq!(stack.deadline(), None);
+
+        let earlier = Timestamp::from(1724701990000);
+        let later = Timestamp::from(1724702000000);
+        stack.spans[2].set_attribute(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(earlier.to_string()),
+        );
+        stack.spans[0].set_attribute(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(later.to_string()),
+        );
+
+        assert_eq!(stack.deadline(), Some(earlier));
+    }
+
     #[test]
     fn binary_serialization() {
         let stack = example_stack_1();
@@ -12,26 +12,50 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use chrono::{DateTime, NaiveDate};
 use combine::parser::char::{char, space, spaces, string};
 use combine::{
     any, attempt, between, choice, eof, many, none_of, one_of, optional, parser, sep_by,
     skip_many1, token, EasyParser, ParseError, Parser, Stream,
 };
 use regex::Regex;
+use std::cmp::Ordering;
 use std::error::Error;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::ops::Bound;
 
 /// A simplified version of the Lucene query language
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Query {
-    Or { queries: Vec<Query> },
-    And { queries: Vec<Query> },
-    Not { query: Box<Query> },
-    Regex { pattern: String },
-    Term { value: String },
-    Phrase { value: String },
-    Field { field: String, query: Box<Query> },
+    Or {
+        queries: Vec<Query>,
+    },
+    And {
+        queries: Vec<Query>,
+    },
+    Not {
+        query: Box<Query>,
+    },
+    Regex {
+        pattern: String,
+    },
+    Term {
+        value: String,
+    },
+    Phrase {
+        value: String,
+    },
+    /// Matches values between the given bounds, written as `[from TO to]` (`{` and `}` for
+    /// exclusive bounds, `*` for an unbounded side) or as a comparison like `>=value`
+    Range {
+        lower: Bound<String>,
+        upper: Bound<String>,
+    },
+    Field {
+        field: String,
+        query: Box<Query>,
+    },
 }
 
 impl Query {
@@ -46,9 +70,19 @@ impl Query {
 
 #[derive(Debug, Clone)]
 pub enum LeafQuery {
-    Term { value: String },
-    Phrase { value: String },
-    Regex { pattern: Regex },
+    Term {
+        value: String,
+    },
+    Phrase {
+        value: String,
+    },
+    Regex {
+        pattern: Regex,
+    },
+    Range {
+        lower: Bound<RangeValue>,
+        upper: Bound<RangeValue>,
+    },
 }
 
 impl LeafQuery {
@@ -57,8 +91,91 @@ impl LeafQuery {
             LeafQuery::Term { value } => s.to_lowercase().contains(&value.to_lowercase()),
             LeafQuery::Phrase { value } => s.contains(value),
             LeafQuery::Regex { pattern } => pattern.is_match(s),
+            LeafQuery::Range { lower, upper } => {
+                let above_lower = match lower {
+                    Bound::Included(bound) => bound
+                        .compare(s)
+                        .is_some_and(|ordering| ordering != Ordering::Less),
+                    Bound::Excluded(bound) => bound
+                        .compare(s)
+                        .is_some_and(|ordering| ordering == Ordering::Greater),
+                    Bound::Unbounded => true,
+                };
+                let below_upper = match upper {
+                    Bound::Included(bound) => bound
+                        .compare(s)
+                        .is_some_and(|ordering| ordering != Ordering::Greater),
+                    Bound::Excluded(bound) => bound
+                        .compare(s)
+                        .is_some_and(|ordering| ordering == Ordering::Less),
+                    Bound::Unbounded => true,
+                };
+                above_lower && below_upper
+            }
+        }
+    }
+}
+
+/// A bound of a range query.
+///
+/// Numeric bounds only match numeric values and date bounds only match dates, otherwise values
+/// are compared as strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeValue {
+    Number(f64),
+    /// Milliseconds since the Unix epoch
+    Instant(i64),
+    String(String),
+}
+
+impl RangeValue {
+    pub fn parse(s: &str) -> Self {
+        if let Some(number) = parse_number(s) {
+            Self::Number(number)
+        } else if let Some(instant) = parse_instant(s) {
+            Self::Instant(instant)
+        } else {
+            Self::String(s.to_string())
         }
     }
+
+    /// Compares the given value to this bound, returns `None` if they are not comparable
+    pub fn compare(&self, value: &str) -> Option<Ordering> {
+        match self {
+            RangeValue::Number(bound) => parse_number(value)?.partial_cmp(bound),
+            RangeValue::Instant(bound) => Some(parse_instant(value)?.cmp(bound)),
+            RangeValue::String(bound) => Some(value.cmp(bound.as_str())),
+        }
+    }
+}
+
+/// Parses a finite number, as used by range queries
+pub fn parse_number(s: &str) -> Option<f64> {
+    s.trim().parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// Parses an RFC 3339 timestamp or a `YYYY-MM-DD` date (as midnight UTC) into milliseconds
+/// since the Unix epoch, as used by range queries
+pub fn parse_instant(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(s) {
+        Some(timestamp.timestamp_millis())
+    } else {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|timestamp| timestamp.and_utc().timestamp_millis())
+    }
+}
+
+fn range_bound(value: String, inclusive: bool) -> Bound<String> {
+    if value == "*" {
+        Bound::Unbounded
+    } else if inclusive {
+        Bound::Included(value)
+    } else {
+        Bound::Excluded(value)
+    }
 }
 
 impl TryFrom<Query> for LeafQuery {
@@ -73,6 +190,10 @@ impl TryFrom<Query> for LeafQuery {
             }
             Query::Term { value } => Ok(Self::Term { value }),
             Query::Phrase { value } => Ok(Self::Phrase { value }),
+            Query::Range { lower, upper } => Ok(Self::Range {
+                lower: lower.map(|value| RangeValue::parse(&value)),
+                upper: upper.map(|value| RangeValue::parse(&value)),
+            }),
             _ => Err("Not a leaf query".to_string()),
         }
     }
@@ -171,7 +292,7 @@ where
     .map(|pattern| Query::Regex { pattern })
 }
 
-fn quoted_term_query<Input>() -> impl Parser<Input, Output = Query>
+fn quoted_string<Input>() -> impl Parser<Input, Output = String>
 where
     Input: Stream<Token = char>,
 {
@@ -180,7 +301,82 @@ where
         token('"'),
         many(string("\\\"").map(|_| '"').or(none_of("\"".chars()))),
     )
-    .map(|value| Query::Phrase { value })
+}
+
+fn quoted_term_query<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+{
+    quoted_string().map(|value| Query::Phrase { value })
+}
+
+fn range_value<Input>() -> impl Parser<Input, Output = String>
+where
+    Input: Stream<Token = char>,
+    LuceneParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+{
+    quoted_string().or(
+        (optional(token('-')), term()).map(|(sign, value)| match sign {
+            Some(_) => format!("-{value}"),
+            None => value,
+        }),
+    )
+}
+
+fn range_query<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+    LuceneParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+{
+    (
+        one_of("[{".chars()).skip(spaces()),
+        range_value().skip(skip_many1(space())),
+        string("TO").skip(skip_many1(space())),
+        range_value().skip(spaces()),
+        one_of("]}".chars()),
+    )
+        .map(|(open, from, _, to, close)| Query::Range {
+            lower: range_bound(from, open == '['),
+            upper: range_bound(to, close == ']'),
+        })
+}
+
+fn comparison_query<Input>() -> impl Parser<Input, Output = Query>
+where
+    Input: Stream<Token = char>,
+    LuceneParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+{
+    let operator = choice!(
+        attempt(string(">=")),
+        attempt(string("<=")),
+        string(">"),
+        string("<")
+    );
+
+    (operator.skip(spaces()), range_value()).map(|(operator, value)| match operator {
+        ">=" => Query::Range {
+            lower: Bound::Included(value),
+            upper: Bound::Unbounded,
+        },
+        ">" => Query::Range {
+            lower: Bound::Excluded(value),
+            upper: Bound::Unbounded,
+        },
+        "<=" => Query::Range {
+            lower: Bound::Unbounded,
+            upper: Bound::Included(value),
+        },
+        _ => Query::Range {
+            lower: Bound::Unbounded,
+            upper: Bound::Excluded(value),
+        },
+    })
 }
 
 fn field_term_query<Input>() -> impl Parser<Input, Output = Query>
@@ -200,7 +396,13 @@ where
         <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
     >,
 {
-    choice!(regexp_term_query(), quoted_term_query(), field_term_query())
+    choice!(
+        range_query(),
+        comparison_query(),
+        regexp_term_query(),
+        quoted_term_query(),
+        field_term_query()
+    )
 }
 
 fn grouping<Input>() -> impl Parser<Input, Output = Query>
@@ -710,4 +912,101 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn range_field() -> Result<(), String> {
+        let query = "count:[10 TO 20}";
+        let parsed = Query::parse(query)?;
+
+        assert_eq!(
+            parsed,
+            Query::Field {
+                field: "count".to_string(),
+                query: Box::new(Query::Range {
+                    lower: Bound::Included("10".to_string()),
+                    upper: Bound::Excluded("20".to_string())
+                })
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unbounded_range() -> Result<(), String> {
+        let query = "{2024-01-01 TO *]";
+        let parsed = Query::parse(query)?;
+
+        assert_eq!(
+            parsed,
+            Query::Range {
+                lower: Bound::Excluded("2024-01-01".to_string()),
+                upper: Bound::Unbounded
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn comparisons() -> Result<(), String> {
+        let query = "amount:>=-1.5 AND timestamp:<\"2024-01-01T10:00:00Z\"";
+        let parsed = Query::parse(query)?;
+
+        assert_eq!(
+            parsed,
+            Query::And {
+                queries: vec![
+                    Query::Field {
+                        field: "amount".to_string(),
+                        query: Box::new(Query::Range {
+                            lower: Bound::Included("-1.5".to_string()),
+                            upper: Bound::Unbounded
+                        })
+                    },
+                    Query::Field {
+                        field: "timestamp".to_string(),
+                        query: Box::new(Query::Range {
+                            lower: Bound::Unbounded,
+                            upper: Bound::Excluded("2024-01-01T10:00:00Z".to_string())
+                        })
+                    }
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn numeric_range_matching() -> Result<(), String> {
+        let query: LeafQuery = Query::parse("[9 TO 100}")?.try_into()?;
+
+        assert!(query.matches("9"));
+        assert!(query.matches("10.5"));
+        assert!(!query.matches("100"));
+        assert!(!query.matches("8"));
+        assert!(!query.matches("abc"));
+        Ok(())
+    }
+
+    #[test]
+    fn date_range_matching() -> Result<(), String> {
+        let query: LeafQuery = Query::parse(">2024-03-01")?.try_into()?;
+
+        assert!(query.matches("2024-03-01T00:00:00.001Z"));
+        assert!(query.matches("2024-03-02"));
+        assert!(!query.matches("2024-03-01"));
+        assert!(!query.matches("2024-02-28T23:00:00+00:00"));
+        assert!(!query.matches("20240302"));
+        Ok(())
+    }
+
+    #[test]
+    fn string_range_matching() -> Result<(), String> {
+        let query: LeafQuery = Query::parse("[b TO d]")?.try_into()?;
+
+        assert!(query.matches("b"));
+        assert!(query.matches("cat"));
+        assert!(!query.matches("dog"));
+        assert!(!query.matches("apple"));
+        Ok(())
+    }
 }
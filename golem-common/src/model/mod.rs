@@ -19,6 +19,7 @@ pub mod auth;
 pub mod base64;
 pub mod certificate;
 pub mod component;
+pub mod component_bundle;
 pub mod component_constraint;
 pub mod component_metadata;
 pub mod deployment;
@@ -59,6 +60,7 @@ use crate::model::agent::AgentTypeResolver;
 use crate::model::invocation_context::InvocationContextStack;
 use crate::model::oplog::{TimestampedUpdateDescription, WorkerResourceId};
 use crate::model::regions::DeletedRegions;
+use crate::model::worker::{ScheduleRecurrence, WorkerResourceUsage};
 use crate::{grpc_uri, SafeDisplay};
 use desert_rust::{
     BinaryCodec, BinaryDeserializer, BinaryOutput, BinarySerializer, DeserializationContext,
@@ -277,6 +279,24 @@ pub enum ScheduledAction {
         function_input: Vec<Value>,
         invocation_context: InvocationContextStack,
     },
+    /// Invoke the given action on the worker, and schedule the next occurrence
+    /// according to the recurrence. Each occurrence gets its own idempotency key
+    /// derived from the base `idempotency_key`, which identifies the whole schedule.
+    RecurringInvoke {
+        account_id: AccountId,
+        owned_worker_id: OwnedWorkerId,
+        idempotency_key: IdempotencyKey,
+        full_function_name: String,
+        function_input: Vec<Value>,
+        invocation_context: InvocationContextStack,
+        recurrence: ScheduleRecurrence,
+    },
+    /// Expires a given promise if it was not completed or rejected before
+    ExpirePromise {
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+        promise_id: PromiseId,
+    },
 }
 
 impl ScheduledAction {
@@ -293,6 +313,14 @@ impl ScheduledAction {
             ScheduledAction::Invoke {
                 owned_worker_id, ..
             } => owned_worker_id.clone(),
+            ScheduledAction::RecurringInvoke {
+                owned_worker_id, ..
+            } => owned_worker_id.clone(),
+            ScheduledAction::ExpirePromise {
+                environment_id,
+                promise_id,
+                ..
+            } => OwnedWorkerId::new(*environment_id, &promise_id.worker_id),
         }
     }
 }
@@ -311,10 +339,30 @@ impl Display for ScheduledAction {
             ScheduledAction::Invoke {
                 owned_worker_id, ..
             } => write!(f, "invoke[{owned_worker_id}]"),
+            ScheduledAction::RecurringInvoke {
+                owned_worker_id,
+                recurrence,
+                ..
+            } => write!(f, "recurring[{owned_worker_id}, {recurrence}]"),
+            ScheduledAction::ExpirePromise { promise_id, .. } => {
+                write!(f, "expire[{promise_id}]")
+            }
         }
     }
 }
 
+/// The final state of a promise
+#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec, IntoValue, FromValue)]
+#[desert(evolution())]
+pub enum PromiseOutcome {
+    /// The promise was completed with the given payload
+    Completed(Vec<u8>),
+    /// The promise was rejected with the given error
+    Rejected(String),
+    /// The promise was neither completed nor rejected before its timeout
+    Expired,
+}
+
 #[derive(Debug, Clone, BinaryCodec)]
 #[desert(evolution())]
 pub struct ScheduleId {
@@ -515,12 +563,38 @@ impl WorkerFilter {
                     .map(|ev| comparator.matches(ev, &value))
                     .unwrap_or(false)
             }
+            WorkerFilter::Label(WorkerLabelFilter {
+                name,
+                comparator,
+                value,
+            }) => metadata
+                .last_known_status
+                .labels
+                .get(&name)
+                .map(|label_value| comparator.matches(label_value, &value))
+                .unwrap_or(false),
             WorkerFilter::CreatedAt(WorkerCreatedAtFilter { comparator, value }) => {
                 comparator.matches(&metadata.created_at, &value)
             }
             WorkerFilter::Status(WorkerStatusFilter { comparator, value }) => {
                 comparator.matches(&metadata.last_known_status.status, &value)
             }
+            WorkerFilter::ResourceUsage(WorkerResourceUsageFilter {
+                metric,
+                comparator,
+                value,
+            }) => {
+                let usage = &metadata.last_known_status.resource_usage;
+                let actual = match metric {
+                    WorkerResourceUsageMetric::ConsumedFuel => usage.consumed_fuel,
+                    WorkerResourceUsageMetric::PeakLinearMemorySize => {
+                        usage.peak_linear_memory_size
+                    }
+                    WorkerResourceUsageMetric::InvocationCount => usage.invocation_count,
+                    WorkerResourceUsageMetric::InvocationTimeMillis => usage.invocation_time_millis,
+                };
+                comparator.matches(&actual, &value)
+            }
             WorkerFilter::Not(WorkerNotFilter { filter }) => !filter.matches(metadata),
             WorkerFilter::And(WorkerAndFilter { filters }) => {
                 let mut result = true;
@@ -583,7 +657,11 @@ impl SafeDisplay for RetryConfig {
 /// By having an associated oplog_idx, the cached information can be used together with the
 /// tail of the oplog to determine the actual status of the worker.
 #[derive(Clone, Debug, PartialEq, BinaryCodec)]
-#[desert(evolution())]
+#[desert(evolution(
+    FieldAdded("last_snapshot", None),
+    FieldAdded("resource_usage", WorkerResourceUsageRecord::default()),
+    FieldAdded("labels", BTreeMap::new())
+))]
 pub struct WorkerStatusRecord {
     pub status: WorkerStatus,
     pub skipped_regions: DeletedRegions,
@@ -607,6 +685,13 @@ pub struct WorkerStatusRecord {
     /// The number of encountered error entries grouped by their 'retry_from' index, calculated from
     /// the last invocation boundary.
     pub current_retry_count: HashMap<OplogIndex, u32>,
+    /// The latest automatic snapshot usable as the starting point of the replay
+    pub last_snapshot: Option<SnapshotRecord>,
+    /// Cumulative resource usage of the worker. Unlike the other fields, it also accounts for
+    /// entries in skipped and deleted regions, as those resources were consumed as well.
+    pub resource_usage: WorkerResourceUsageRecord,
+    /// Mutable key-value labels attached to the worker
+    pub labels: BTreeMap<String, String>,
 }
 
 impl Default for WorkerStatusRecord {
@@ -630,6 +715,9 @@ impl Default for WorkerStatusRecord {
             deleted_regions: DeletedRegions::new(),
             component_revision_for_replay: ComponentRevision::INITIAL,
             current_retry_count: HashMap::new(),
+            last_snapshot: None,
+            resource_usage: WorkerResourceUsageRecord::default(),
+            labels: BTreeMap::new(),
         }
     }
 }
@@ -649,6 +737,35 @@ pub struct SuccessfulUpdateRecord {
     pub target_revision: ComponentRevision,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct WorkerResourceUsageRecord {
+    pub consumed_fuel: u64,
+    pub peak_linear_memory_size: u64,
+    pub invocation_count: u64,
+    pub invocation_time_millis: u64,
+    /// Start of the currently running invocation, if any
+    pub current_invocation_started_at: Option<Timestamp>,
+}
+
+impl From<WorkerResourceUsageRecord> for WorkerResourceUsage {
+    fn from(value: WorkerResourceUsageRecord) -> Self {
+        Self {
+            consumed_fuel: value.consumed_fuel,
+            peak_linear_memory_size: value.peak_linear_memory_size,
+            invocation_count: value.invocation_count,
+            invocation_time_millis: value.invocation_time_millis,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub struct SnapshotRecord {
+    pub timestamp: Timestamp,
+    pub oplog_index: OplogIndex,
+}
+
 #[derive(Clone, Debug, PartialEq, BinaryCodec)]
 #[desert(evolution())]
 pub enum WorkerInvocation {
@@ -0,0 +1,123 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::component::ComponentRevision;
+use crate::model::oplog::{OplogEntry, PayloadId};
+use crate::model::{Timestamp, WorkerId};
+use crate::serialization::{deserialize_with_version, serialize, SERIALIZATION_VERSION_V3};
+use desert_rust::BinaryCodec;
+
+/// A self-contained, portable archive of an agent's oplog, streamed as a sequence of parts.
+///
+/// Every externally stored payload referenced by the entries, and every initial component file
+/// belonging to a component revision used by the agent, is embedded in the archive so it can be
+/// imported into a different environment or cluster.
+///
+/// An archive starts with a single [`ExportedOplogPart::Header`], followed by the initial files,
+/// the payloads and the oplog entries in order, starting from `OplogIndex::INITIAL`, and is closed
+/// by [`ExportedOplogPart::End`] so a truncated archive is never mistaken for a complete one. Each
+/// part is written with [`encode_exported_oplog_part`] so the archive can be produced and consumed
+/// without ever holding all of it in memory.
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub enum ExportedOplogPart {
+    Header(ExportedOplogHeader),
+    InitialFile(ExportedInitialFile),
+    Payload(ExportedOplogPayload),
+    Entries(Vec<OplogEntry>),
+    End,
+}
+
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct ExportedOplogHeader {
+    /// The agent the oplog was exported from
+    pub worker_id: WorkerId,
+    pub exported_at: Timestamp,
+    /// All component revisions the exported entries refer to
+    pub component_revisions: Vec<ComponentRevision>,
+}
+
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct ExportedOplogPayload {
+    pub payload_id: PayloadId,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+pub struct ExportedInitialFile {
+    pub component_revision: ComponentRevision,
+    pub path: String,
+    pub data: Vec<u8>,
+}
+
+/// Serializes a part of an exported oplog archive, prefixed with its length
+pub fn encode_exported_oplog_part(part: &ExportedOplogPart) -> Result<Vec<u8>, String> {
+    let data = serialize(part)?;
+    let length = u32::try_from(data.len())
+        .map_err(|_| format!("Exported oplog part is too large ({} bytes)", data.len()))?;
+    let mut result = Vec::with_capacity(data.len() + 4);
+    result.extend_from_slice(&length.to_be_bytes());
+    result.extend(data);
+    Ok(result)
+}
+
+/// Incrementally decodes the parts of an exported oplog archive from arbitrarily sized chunks
+#[derive(Debug, Default)]
+pub struct ExportedOplogDecoder {
+    buffer: Vec<u8>,
+}
+
+impl ExportedOplogDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete part, or `None` if more data has to be pushed first
+    pub fn next_part(&mut self) -> Result<Option<ExportedOplogPart>, String> {
+        if self.buffer.len() < 4 {
+            return Ok(None);
+        }
+        let length = u32::from_be_bytes(self.buffer[0..4].try_into().unwrap()) as usize;
+        if self.buffer.len() < 4 + length {
+            return Ok(None);
+        }
+        // The archive comes from the outside, so unknown serialization versions are rejected
+        // here instead of reaching the panicking branches of the deserializer
+        let part = match self.buffer[4..4 + length].split_first() {
+            Some((&SERIALIZATION_VERSION_V3, data)) => {
+                deserialize_with_version(data, SERIALIZATION_VERSION_V3)
+                    .map_err(|err| format!("Invalid oplog archive part: {err}"))?
+            }
+            _ => return Err("Invalid oplog archive part".to_string()),
+        };
+        self.buffer.drain(0..4 + length);
+        Ok(Some(part))
+    }
+
+    /// Checks that the archive did not end in the middle of a part
+    pub fn finish(&self) -> Result<(), String> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err("Oplog archive ends with an incomplete part".to_string())
+        }
+    }
+}
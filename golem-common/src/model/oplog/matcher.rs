@@ -40,6 +40,9 @@ impl PublicOplogEntry {
                 Query::Phrase { .. } => {
                     entry.matches_leaf_query(field_stack, &query.clone().try_into().unwrap())
                 }
+                Query::Range { .. } => {
+                    entry.matches_leaf_query(field_stack, &query.clone().try_into().unwrap())
+                }
                 Query::Field { field, query } => {
                     let mut new_stack: Vec<String> = field_stack.to_vec();
                     let parts: Vec<String> = field.split(".").map(|s| s.to_string()).collect();
@@ -68,11 +71,10 @@ impl PublicOplogEntry {
         }
     }
 
-    fn span_attribute_match(
+    fn visit_span_attributes(
         attributes: &Vec<PublicAttribute>,
         path_stack: &[String],
-        query_path: &[String],
-        query: &LeafQuery,
+        f: &mut dyn FnMut(&[String], &str) -> bool,
     ) -> bool {
         for attr in attributes {
             let key = &attr.key;
@@ -86,7 +88,7 @@ impl PublicOplogEntry {
                 }
             };
 
-            if Self::match_value(&vnt, &new_path, query_path, query) {
+            if Self::visit_value(&vnt, &new_path, f) {
                 return true;
             }
         }
@@ -94,299 +96,237 @@ impl PublicOplogEntry {
     }
 
     fn matches_leaf_query(&self, query_path: &[String], query: &LeafQuery) -> bool {
+        (Self::is_timestamp_path(query_path) && query.matches(&self.timestamp().to_string()))
+            || self
+                .visit_fields(&mut |path, value| Self::string_match(value, path, query_path, query))
+    }
+
+    /// True if the query path refers to the timestamp of the oplog entry
+    pub fn is_timestamp_path(query_path: &[String]) -> bool {
+        query_path.len() == 1 && query_path[0].eq_ignore_ascii_case("timestamp")
+    }
+
+    /// Collects the textual representation of all the fields of the entry which are considered
+    /// by `matches`, together with their paths
+    pub fn searchable_fields(&self) -> Vec<(Vec<String>, String)> {
+        let mut fields = Vec::new();
+        self.visit_fields(&mut |path, value| {
+            fields.push((path.to_vec(), value.to_string()));
+            false
+        });
+        fields
+    }
+
+    /// Calls `f` with the path and textual value of each field of the entry, stopping when it
+    /// returns true
+    fn visit_fields(&self, f: &mut dyn FnMut(&[String], &str) -> bool) -> bool {
         match self {
-            PublicOplogEntry::Create(_params) => {
-                Self::string_match("create", &[], query_path, query)
-            }
+            PublicOplogEntry::Create(_params) => f(&[], "create"),
             PublicOplogEntry::ImportedFunctionInvoked(params) => {
-                Self::string_match("importedfunctioninvoked", &[], query_path, query)
-                    || Self::string_match("imported-function-invoked", &[], query_path, query)
-                    || Self::string_match("imported-function", &[], query_path, query)
-                    || Self::string_match(&params.function_name, &[], query_path, query)
-                    || Self::match_value(&params.request, &[], query_path, query)
-                    || Self::match_value(&params.response, &[], query_path, query)
+                f(&[], "importedfunctioninvoked")
+                    || f(&[], "imported-function-invoked")
+                    || f(&[], "imported-function")
+                    || f(&[], &params.function_name)
+                    || Self::visit_value(&params.request, &[], f)
+                    || Self::visit_value(&params.response, &[], f)
             }
             PublicOplogEntry::ExportedFunctionInvoked(params) => {
-                Self::string_match("exportedfunctioninvoked", &[], query_path, query)
-                    || Self::string_match("exported-function-invoked", &[], query_path, query)
-                    || Self::string_match("exported-function", &[], query_path, query)
-                    || Self::string_match(&params.function_name, &[], query_path, query)
-                    || params
-                        .request
-                        .iter()
-                        .any(|v| Self::match_value(v, &[], query_path, query))
-                    || Self::string_match(&params.idempotency_key.value, &[], query_path, query)
+                f(&[], "exportedfunctioninvoked")
+                    || f(&[], "exported-function-invoked")
+                    || f(&[], "exported-function")
+                    || f(&[], &params.function_name)
+                    || params.request.iter().any(|v| Self::visit_value(v, &[], f))
+                    || f(&[], &params.idempotency_key.value)
             }
             PublicOplogEntry::ExportedFunctionCompleted(params) => {
-                Self::string_match("exportedfunctioncompleted", &[], query_path, query)
-                    || Self::string_match("exported-function-completed", &[], query_path, query)
-                    || Self::string_match("exported-function", &[], query_path, query)
+                f(&[], "exportedfunctioncompleted")
+                    || f(&[], "exported-function-completed")
+                    || f(&[], "exported-function")
                     || match &params.response {
-                        Some(response) => Self::match_value(response, &[], query_path, query),
+                        Some(response) => Self::visit_value(response, &[], f),
                         None => false,
                     }
                 // TODO: should we store function name and idempotency key in ExportedFunctionCompleted?
             }
-            PublicOplogEntry::Suspend(_params) => {
-                Self::string_match("suspend", &[], query_path, query)
-            }
-            PublicOplogEntry::Error(params) => {
-                Self::string_match("error", &[], query_path, query)
-                    || Self::string_match(&params.error, &[], query_path, query)
-            }
-            PublicOplogEntry::NoOp(_params) => Self::string_match("noop", &[], query_path, query),
-            PublicOplogEntry::Jump(_params) => Self::string_match("jump", &[], query_path, query),
-            PublicOplogEntry::Interrupted(_params) => {
-                Self::string_match("interrupted", &[], query_path, query)
-            }
-            PublicOplogEntry::Exited(_params) => {
-                Self::string_match("exited", &[], query_path, query)
-            }
+            PublicOplogEntry::Suspend(_params) => f(&[], "suspend"),
+            PublicOplogEntry::Error(params) => f(&[], "error") || f(&[], &params.error),
+            PublicOplogEntry::NoOp(_params) => f(&[], "noop"),
+            PublicOplogEntry::Jump(_params) => f(&[], "jump"),
+            PublicOplogEntry::Interrupted(_params) => f(&[], "interrupted"),
+            PublicOplogEntry::Exited(_params) => f(&[], "exited"),
             PublicOplogEntry::ChangeRetryPolicy(_params) => {
-                Self::string_match("changeretrypolicy", &[], query_path, query)
-                    || Self::string_match("change-retry-policy", &[], query_path, query)
+                f(&[], "changeretrypolicy") || f(&[], "change-retry-policy")
             }
             PublicOplogEntry::BeginAtomicRegion(_params) => {
-                Self::string_match("beginatomicregion", &[], query_path, query)
-                    || Self::string_match("begin-atomic-region", &[], query_path, query)
+                f(&[], "beginatomicregion") || f(&[], "begin-atomic-region")
             }
             PublicOplogEntry::EndAtomicRegion(_params) => {
-                Self::string_match("endatomicregion", &[], query_path, query)
-                    || Self::string_match("end-atomic-region", &[], query_path, query)
+                f(&[], "endatomicregion") || f(&[], "end-atomic-region")
             }
             PublicOplogEntry::BeginRemoteWrite(_params) => {
-                Self::string_match("beginremotewrite", &[], query_path, query)
-                    || Self::string_match("begin-remote-write", &[], query_path, query)
+                f(&[], "beginremotewrite") || f(&[], "begin-remote-write")
             }
             PublicOplogEntry::EndRemoteWrite(_params) => {
-                Self::string_match("endremotewrite", &[], query_path, query)
-                    || Self::string_match("end-remote-write", &[], query_path, query)
+                f(&[], "endremotewrite") || f(&[], "end-remote-write")
             }
             PublicOplogEntry::PendingWorkerInvocation(params) => {
-                Self::string_match("pendingworkerinvocation", &[], query_path, query)
-                    || Self::string_match("pending-worker-invocation", &[], query_path, query)
+                f(&[], "pendingworkerinvocation")
+                    || f(&[], "pending-worker-invocation")
                     || match &params.invocation {
                         PublicWorkerInvocation::ExportedFunction(params) => {
-                            Self::string_match(&params.full_function_name, &[], query_path, query)
-                                || Self::string_match(
-                                    &params.idempotency_key.value,
-                                    &[],
-                                    query_path,
-                                    query,
-                                )
+                            f(&[], &params.full_function_name)
+                                || f(&[], &params.idempotency_key.value)
                                 || params
                                     .function_input
                                     .as_ref()
                                     .map(|params| {
-                                        params
-                                            .iter()
-                                            .any(|v| Self::match_value(v, &[], query_path, query))
+                                        params.iter().any(|v| Self::visit_value(v, &[], f))
                                     })
                                     .unwrap_or(false)
                         }
-                        PublicWorkerInvocation::ManualUpdate(params) => Self::string_match(
-                            &params.target_revision.to_string(),
-                            &[],
-                            query_path,
-                            query,
-                        ),
+                        PublicWorkerInvocation::ManualUpdate(params) => {
+                            f(&[], &params.target_revision.to_string())
+                        }
                     }
             }
             PublicOplogEntry::PendingUpdate(params) => {
-                Self::string_match("pendingupdate", &[], query_path, query)
-                    || Self::string_match("pending-update", &[], query_path, query)
-                    || Self::string_match("update", &[], query_path, query)
-                    || Self::string_match(
-                        &params.target_revision.to_string(),
-                        &[],
-                        query_path,
-                        query,
-                    )
+                f(&[], "pendingupdate")
+                    || f(&[], "pending-update")
+                    || f(&[], "update")
+                    || f(&[], &params.target_revision.to_string())
             }
             PublicOplogEntry::SuccessfulUpdate(params) => {
-                Self::string_match("successfulupdate", &[], query_path, query)
-                    || Self::string_match("successful-update", &[], query_path, query)
-                    || Self::string_match("update", &[], query_path, query)
-                    || Self::string_match(
-                        &params.target_revision.to_string(),
-                        &[],
-                        query_path,
-                        query,
-                    )
+                f(&[], "successfulupdate")
+                    || f(&[], "successful-update")
+                    || f(&[], "update")
+                    || f(&[], &params.target_revision.to_string())
             }
             PublicOplogEntry::FailedUpdate(params) => {
-                Self::string_match("failedupdate", &[], query_path, query)
-                    || Self::string_match("failed-update", &[], query_path, query)
-                    || Self::string_match("update", &[], query_path, query)
-                    || Self::string_match(
-                        &params.target_revision.to_string(),
-                        &[],
-                        query_path,
-                        query,
-                    )
+                f(&[], "failedupdate")
+                    || f(&[], "failed-update")
+                    || f(&[], "update")
+                    || f(&[], &params.target_revision.to_string())
                     || params
                         .details
                         .as_ref()
-                        .map(|details| Self::string_match(details, &[], query_path, query))
+                        .map(|details| f(&[], details))
                         .unwrap_or(false)
             }
-            PublicOplogEntry::GrowMemory(_params) => {
-                Self::string_match("growmemory", &[], query_path, query)
-                    || Self::string_match("grow-memory", &[], query_path, query)
-            }
+            PublicOplogEntry::GrowMemory(_params) => f(&[], "growmemory") || f(&[], "grow-memory"),
             PublicOplogEntry::CreateResource(_params) => {
-                Self::string_match("createresource", &[], query_path, query)
-                    || Self::string_match("create-resource", &[], query_path, query)
+                f(&[], "createresource") || f(&[], "create-resource")
             }
             PublicOplogEntry::DropResource(_params) => {
-                Self::string_match("dropresource", &[], query_path, query)
-                    || Self::string_match("drop-resource", &[], query_path, query)
+                f(&[], "dropresource") || f(&[], "drop-resource")
             }
             PublicOplogEntry::Log(params) => {
-                Self::string_match("log", &[], query_path, query)
-                    || Self::string_match(&params.context, &[], query_path, query)
-                    || Self::string_match(&params.message, &[], query_path, query)
-            }
-            PublicOplogEntry::Restart(_params) => {
-                Self::string_match("restart", &[], query_path, query)
+                f(&[], "log") || f(&[], &params.context) || f(&[], &params.message)
             }
+            PublicOplogEntry::Restart(_params) => f(&[], "restart"),
             PublicOplogEntry::ActivatePlugin(_params) => {
-                Self::string_match("activateplugin", &[], query_path, query)
-                    || Self::string_match("activate-plugin", &[], query_path, query)
+                f(&[], "activateplugin") || f(&[], "activate-plugin")
             }
             PublicOplogEntry::DeactivatePlugin(_params) => {
-                Self::string_match("deactivateplugin", &[], query_path, query)
-                    || Self::string_match("deactivate-plugin", &[], query_path, query)
-            }
-            PublicOplogEntry::Revert(_params) => {
-                Self::string_match("revert", &[], query_path, query)
+                f(&[], "deactivateplugin") || f(&[], "deactivate-plugin")
             }
+            PublicOplogEntry::Revert(_params) => f(&[], "revert"),
             PublicOplogEntry::CancelPendingInvocation(params) => {
-                Self::string_match("cancel", &[], query_path, query)
-                    || Self::string_match("cancel-invocation", &[], query_path, query)
-                    || Self::string_match(&params.idempotency_key.value, &[], query_path, query)
+                f(&[], "cancel")
+                    || f(&[], "cancel-invocation")
+                    || f(&[], &params.idempotency_key.value)
             }
             PublicOplogEntry::StartSpan(params) => {
-                Self::string_match("startspan", &[], query_path, query)
-                    || Self::string_match("start-span", &[], query_path, query)
-                    || Self::string_match(&params.span_id.to_string(), &[], query_path, query)
-                    || Self::string_match(
+                f(&[], "startspan")
+                    || f(&[], "start-span")
+                    || f(&[], &params.span_id.to_string())
+                    || f(
+                        &[],
                         &params
                             .parent_id
                             .as_ref()
                             .map(|id| id.to_string())
                             .unwrap_or_default(),
-                        &[],
-                        query_path,
-                        query,
                     )
-                    || Self::string_match(
+                    || f(
+                        &[],
                         &params
                             .linked_context
                             .as_ref()
                             .map(|id| id.to_string())
                             .unwrap_or_default(),
-                        &[],
-                        query_path,
-                        query,
                     )
-                    || Self::span_attribute_match(&params.attributes, &[], query_path, query)
+                    || Self::visit_span_attributes(&params.attributes, &[], f)
             }
             PublicOplogEntry::FinishSpan(params) => {
-                Self::string_match("finishspan", &[], query_path, query)
-                    || Self::string_match("finish-span", &[], query_path, query)
-                    || Self::string_match(&params.span_id.to_string(), &[], query_path, query)
+                f(&[], "finishspan") || f(&[], "finish-span") || f(&[], &params.span_id.to_string())
             }
             PublicOplogEntry::SetSpanAttribute(params) => {
                 let attributes = vec![PublicAttribute {
                     key: params.key.clone(),
                     value: params.value.clone(),
                 }];
-                Self::string_match("setspanattribute", &[], query_path, query)
-                    || Self::string_match("set-span-attribute", &[], query_path, query)
-                    || Self::string_match(&params.key, &[], query_path, query)
-                    || Self::span_attribute_match(&attributes, &[], query_path, query)
+                f(&[], "setspanattribute")
+                    || f(&[], "set-span-attribute")
+                    || f(&[], &params.key)
+                    || Self::visit_span_attributes(&attributes, &[], f)
             }
             PublicOplogEntry::ChangePersistenceLevel(_params) => {
-                Self::string_match("changepersistencelevel", &[], query_path, query)
-                    || Self::string_match("change-persistence-level", &[], query_path, query)
-                    || Self::string_match("persistence-level", &[], query_path, query)
+                f(&[], "changepersistencelevel")
+                    || f(&[], "change-persistence-level")
+                    || f(&[], "persistence-level")
             }
             PublicOplogEntry::BeginRemoteTransaction(_params) => {
-                Self::string_match("beginremotetransaction", &[], query_path, query)
-                    || Self::string_match("begin-remote-transaction", &[], query_path, query)
+                f(&[], "beginremotetransaction") || f(&[], "begin-remote-transaction")
             }
             PublicOplogEntry::PreCommitRemoteTransaction(_params) => {
-                Self::string_match("precommitremotetransaction", &[], query_path, query)
-                    || Self::string_match("pre-commit-remote-transaction", &[], query_path, query)
+                f(&[], "precommitremotetransaction") || f(&[], "pre-commit-remote-transaction")
             }
             PublicOplogEntry::PreRollbackRemoteTransaction(_params) => {
-                Self::string_match("prerollbackremotetransaction", &[], query_path, query)
-                    || Self::string_match("pre-rollback-remote-transaction", &[], query_path, query)
+                f(&[], "prerollbackremotetransaction") || f(&[], "pre-rollback-remote-transaction")
             }
             PublicOplogEntry::CommittedRemoteTransaction(_params) => {
-                Self::string_match("committedremotetransaction", &[], query_path, query)
-                    || Self::string_match("committed-remote-transaction", &[], query_path, query)
+                f(&[], "committedremotetransaction") || f(&[], "committed-remote-transaction")
             }
             PublicOplogEntry::RolledBackRemoteTransaction(_params) => {
-                Self::string_match("rolledbackremotetransaction", &[], query_path, query)
-                    || Self::string_match("rolled-back-remote-transaction", &[], query_path, query)
+                f(&[], "rolledbackremotetransaction") || f(&[], "rolled-back-remote-transaction")
+            }
+            PublicOplogEntry::Snapshot(_params) => f(&[], "snapshot"),
+            PublicOplogEntry::ChangeLabels(params) => {
+                f(&[], "changelabels")
+                    || f(&[], "change-labels")
+                    || params
+                        .set
+                        .iter()
+                        .any(|(key, value)| f(&[], key) || f(&[key.clone()], value))
+                    || params.removed.iter().any(|key| f(&[], key))
             }
         }
     }
 
-    fn match_value(
+    fn visit_value(
         value: &ValueAndType,
         path_stack: &[String],
-        query_path: &[String],
-        query: &LeafQuery,
+        f: &mut dyn FnMut(&[String], &str) -> bool,
     ) -> bool {
         match (&value.value, &value.typ) {
-            (Value::Bool(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::U8(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::U16(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::U32(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::U64(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::S8(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::S16(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::S32(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::S64(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::F32(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::F64(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::Char(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
-            (Value::String(value), _) => {
-                Self::string_match(&value.to_string(), path_stack, query_path, query)
-            }
+            (Value::Bool(value), _) => f(path_stack, &value.to_string()),
+            (Value::U8(value), _) => f(path_stack, &value.to_string()),
+            (Value::U16(value), _) => f(path_stack, &value.to_string()),
+            (Value::U32(value), _) => f(path_stack, &value.to_string()),
+            (Value::U64(value), _) => f(path_stack, &value.to_string()),
+            (Value::S8(value), _) => f(path_stack, &value.to_string()),
+            (Value::S16(value), _) => f(path_stack, &value.to_string()),
+            (Value::S32(value), _) => f(path_stack, &value.to_string()),
+            (Value::S64(value), _) => f(path_stack, &value.to_string()),
+            (Value::F32(value), _) => f(path_stack, &value.to_string()),
+            (Value::F64(value), _) => f(path_stack, &value.to_string()),
+            (Value::Char(value), _) => f(path_stack, &value.to_string()),
+            (Value::String(value), _) => f(path_stack, &value.to_string()),
             (Value::List(elems), AnalysedType::List(list)) => elems.iter().any(|v| {
-                Self::match_value(
+                Self::visit_value(
                     &ValueAndType::new(v.clone(), (*list.inner).clone()),
                     path_stack,
-                    query_path,
-                    query,
+                    f,
                 )
             }),
             (Value::Tuple(elems), AnalysedType::Tuple(tuple)) => {
@@ -400,11 +340,10 @@ impl PublicOplogEntry {
                         .any(|(idx, (v, t))| {
                             let mut new_path: Vec<String> = path_stack.to_vec();
                             new_path.push(idx.to_string());
-                            Self::match_value(
+                            Self::visit_value(
                                 &ValueAndType::new(v.clone(), t.clone()),
                                 &new_path,
-                                query_path,
-                                query,
+                                f,
                             )
                         })
                 }
@@ -416,11 +355,10 @@ impl PublicOplogEntry {
                     fields.iter().zip(record.fields.iter()).any(|(v, t)| {
                         let mut new_path: Vec<String> = path_stack.to_vec();
                         new_path.push(t.name.clone());
-                        Self::match_value(
+                        Self::visit_value(
                             &ValueAndType::new(v.clone(), t.typ.clone()),
                             &new_path,
-                            path_stack,
-                            query,
+                            f,
                         )
                     })
                 }
@@ -443,11 +381,10 @@ impl PublicOplogEntry {
                     ) => {
                         let mut new_path: Vec<String> = path_stack.to_vec();
                         new_path.push(name.clone());
-                        Self::match_value(
+                        Self::visit_value(
                             &ValueAndType::new((**value).clone(), typ.clone()),
                             &new_path,
-                            query_path,
-                            query,
+                            f,
                         )
                     }
                     _ => false,
@@ -455,7 +392,7 @@ impl PublicOplogEntry {
             }
             (Value::Enum(value), AnalysedType::Enum(typ)) => {
                 if let Some(case) = typ.cases.get(*value as usize) {
-                    Self::string_match(case, path_stack, query_path, query)
+                    f(path_stack, case)
                 } else {
                     false
                 }
@@ -466,41 +403,36 @@ impl PublicOplogEntry {
                     .enumerate()
                     .filter_map(|(idx, set)| if *set { flags.names.get(idx) } else { None })
                     .collect::<Vec<_>>();
-                names
-                    .iter()
-                    .any(|name| Self::string_match(name, path_stack, query_path, query))
+                names.iter().any(|name| f(path_stack, name))
             }
-            (Value::Option(Some(value)), AnalysedType::Option(typ)) => Self::match_value(
+            (Value::Option(Some(value)), AnalysedType::Option(typ)) => Self::visit_value(
                 &ValueAndType::new((**value).clone(), (*typ.inner).clone()),
                 path_stack,
-                query_path,
-                query,
+                f,
             ),
             (Value::Result(value), AnalysedType::Result(typ)) => match value {
                 Ok(Some(value)) if typ.ok.is_some() => {
                     let mut new_path = path_stack.to_vec();
                     new_path.push("ok".to_string());
-                    Self::match_value(
+                    Self::visit_value(
                         &ValueAndType::new(
                             (**value).clone(),
                             (**(typ.ok.as_ref().unwrap())).clone(),
                         ),
                         &new_path,
-                        query_path,
-                        query,
+                        f,
                     )
                 }
                 Err(Some(value)) if typ.err.is_some() => {
                     let mut new_path = path_stack.to_vec();
                     new_path.push("err".to_string());
-                    Self::match_value(
+                    Self::visit_value(
                         &ValueAndType::new(
                             (**value).clone(),
                             (**(typ.err.as_ref().unwrap())).clone(),
                         ),
                         &new_path,
-                        query_path,
-                        query,
+                        f,
                     )
                 }
                 _ => false,
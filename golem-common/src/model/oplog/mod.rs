@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod export;
 pub mod matcher;
 pub mod payload;
 mod protobuf;
@@ -25,11 +26,13 @@ pub use crate::base_model::oplog::{
     public_oplog_entry, OplogEntry, PublicOplogEntry, PublicOplogEntryWithIndex,
 };
 pub use crate::base_model::OplogIndex;
+pub use export::*;
 pub use payload::*;
 pub use public_types::*;
 pub use raw_types::*;
 
 use crate::model::component::ComponentRevision;
+use std::collections::HashMap;
 
 impl OplogEntry {
     pub fn is_end_atomic_region(&self, idx: OplogIndex) -> bool {
@@ -132,6 +135,56 @@ impl OplogEntry {
         }
     }
 
+    /// Returns the references of all the payloads of this entry which are stored outside the oplog
+    pub fn external_payloads(&self) -> Vec<(&PayloadId, &Vec<u8>)> {
+        match self {
+            OplogEntry::ImportedFunctionInvoked {
+                request, response, ..
+            } => request
+                .external_reference()
+                .into_iter()
+                .chain(response.external_reference())
+                .collect(),
+            OplogEntry::ExportedFunctionInvoked { request, .. } => {
+                request.external_reference().into_iter().collect()
+            }
+            OplogEntry::ExportedFunctionCompleted { response, .. } => {
+                response.external_reference().into_iter().collect()
+            }
+            OplogEntry::PendingUpdate {
+                description: UpdateDescription::SnapshotBased { payload, .. },
+                ..
+            } => payload.external_reference().into_iter().collect(),
+            OplogEntry::Snapshot { data, .. } => data.external_reference().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Replaces the references of all the externally stored payloads of this entry using the given mapping
+    pub fn remap_external_payloads(
+        &mut self,
+        mapping: &HashMap<PayloadId, RawOplogPayload>,
+    ) -> Result<(), String> {
+        match self {
+            OplogEntry::ImportedFunctionInvoked {
+                request, response, ..
+            } => {
+                request.remap_external(mapping)?;
+                response.remap_external(mapping)
+            }
+            OplogEntry::ExportedFunctionInvoked { request, .. } => request.remap_external(mapping),
+            OplogEntry::ExportedFunctionCompleted { response, .. } => {
+                response.remap_external(mapping)
+            }
+            OplogEntry::PendingUpdate {
+                description: UpdateDescription::SnapshotBased { payload, .. },
+                ..
+            } => payload.remap_external(mapping),
+            OplogEntry::Snapshot { data, .. } => data.remap_external(mapping),
+            _ => Ok(()),
+        }
+    }
+
     pub fn specifies_component_revision(&self) -> Option<ComponentRevision> {
         match self {
             OplogEntry::Create {
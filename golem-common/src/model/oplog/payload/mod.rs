@@ -31,8 +31,11 @@ use crate::model::oplog::types::{
     SerializableStreamError,
 };
 use crate::model::oplog::PayloadId;
-use crate::model::worker::RevertWorkerTarget;
-use crate::model::{ComponentId, ForkResult, IdempotencyKey, OplogIndex, PromiseId, WorkerId};
+use crate::model::worker::{RevertWorkerTarget, ScheduleRecurrence};
+use crate::model::{
+    ComponentId, ForkResult, IdempotencyKey, OplogIndex, PromiseId, PromiseOutcome, Timestamp,
+    WorkerId,
+};
 use crate::oplog_payload;
 use crate::serialization::serialize;
 use desert_rust::{
@@ -40,6 +43,7 @@ use desert_rust::{
     DeserializationContext, SerializationContext,
 };
 use golem_api_grpc::proto::golem::worker::UpdateMode;
+use golem_wasm::analysis::AnalysedType;
 use golem_wasm::{IntoValueAndType, ValueAndType};
 use golem_wasm_derive::{FromValue, IntoValue};
 use std::collections::HashMap;
@@ -116,7 +120,7 @@ oplog_payload! {
         GolemRdbmsRequest {
             request: Option<SerializableRdbmsRequest>
         },
-        GolemRpcInvoke {
+        GolemRpcInvoke evolution(FieldAdded("deadline", None)) {
             remote_worker_id: WorkerId,
             idempotency_key: IdempotencyKey,
             function_name: String,
@@ -127,6 +131,7 @@ oplog_payload! {
             #[transient(None::<DataValue>)]
             #[from_value(skip)]
             remote_agent_parameters: Option<DataValue>, // enriched field, only filled when exposed as public oplog entry
+            deadline: Option<Timestamp>, // absolute deadline, reused when the invocation is resumed after a replay
         },
         GolemRpcScheduledInvocation {
             remote_worker_id: WorkerId,
@@ -178,6 +183,29 @@ oplog_payload! {
         SocketsResolveName {
             name: String
         },
+        GolemRpcRecurringInvocation {
+            remote_worker_id: WorkerId,
+            idempotency_key: IdempotencyKey,
+            function_name: String,
+            function_params: Vec<ValueAndType>,
+            datetime: SerializableDateTime,
+            recurrence: ScheduleRecurrence,
+            #[from_value(skip)]
+            #[transient(None::<AgentTypeName>)]
+            remote_agent_type: Option<AgentTypeName>, // enriched field, only filled when exposed as public oplog entry
+            #[from_value(skip)]
+            #[transient(None::<DataValue>)]
+            remote_agent_parameters: Option<DataValue>, // enriched field, only filled when exposed as public oplog entry
+        },
+        GolemApiPromiseOptions {
+            timeout_in_nanos: Option<u64>,
+            #[from_value(skip)]
+            payload_type: Option<AnalysedType>
+        },
+        GolemApiPromiseRejection {
+            promise_id: PromiseId,
+            error: String
+        },
     }
 }
 
@@ -321,6 +349,9 @@ oplog_payload! {
         },
         StreamSkip {
             result: Result<u64, SerializableStreamError>
+        },
+        GolemApiPromiseOutcome {
+            result: Option<PromiseOutcome>
         }
     }
 }
@@ -417,7 +448,11 @@ pub mod host_functions {
         (GolemApiRevertWorker => "golem::api", "revert_worker", GolemApiRevertAgent, GolemApiUnit),
         (GolemApiResolveComponentId => "golem::api", "resolve_component_id", GolemApiComponentSlug, GolemApiComponentId),
         (GolemApiResolveWorkerIdStrict => "golem::api", "resolve_worker_id_strict", GolemApiComponentSlugAndAgentName, GolemApiAgentId),
-        (GolemApiFork => "golem::api", "fork", NoInput, GolemApiFork)
+        (GolemApiFork => "golem::api", "fork", NoInput, GolemApiFork),
+        (GolemRpcWasmRpcScheduleRecurringInvocation => "golem::rpc::wasm-rpc", "schedule_recurring_invocation", GolemRpcRecurringInvocation, GolemRpcScheduledInvocation),
+        (GolemApiCreatePromiseWithOptions => "golem::api", "create_promise_with_options", GolemApiPromiseOptions, GolemApiPromiseId),
+        (GolemApiRejectPromise => "golem::api", "reject_promise", GolemApiPromiseRejection, GolemApiPromiseCompletion),
+        (GolemApiGetPromiseOutcome => "golem::api", "get_promise_outcome", NoInput, GolemApiPromiseOutcome)
     }
 }
 
@@ -448,6 +483,34 @@ impl<T: BinaryCodec + Debug + Clone + PartialEq> OplogPayload<T> {
             }),
         }
     }
+
+    /// Returns the reference of the payload if it is stored outside the oplog
+    pub fn external_reference(&self) -> Option<(&PayloadId, &Vec<u8>)> {
+        match self {
+            OplogPayload::External {
+                payload_id,
+                md5_hash,
+            } => Some((payload_id, md5_hash)),
+            _ => None,
+        }
+    }
+
+    /// Replaces the reference of an externally stored payload using the given mapping.
+    ///
+    /// Inline payloads are kept as they are. Fails if an external payload has no replacement.
+    pub fn remap_external(
+        &mut self,
+        mapping: &HashMap<PayloadId, RawOplogPayload>,
+    ) -> Result<(), String> {
+        if let OplogPayload::External { payload_id, .. } = self {
+            let replacement = mapping
+                .get(payload_id)
+                .cloned()
+                .ok_or_else(|| format!("No replacement for external oplog payload {payload_id}"))?;
+            *self = replacement.into_payload()?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: BinaryCodec + Debug + Clone + PartialEq> BinarySerializer for OplogPayload<T> {
@@ -501,6 +564,7 @@ impl<T: BinaryCodec + Debug + Clone + PartialEq> BinaryDeserializer for OplogPay
 }
 
 /// Untyped version of OplogPayload
+#[derive(Debug, Clone)]
 pub enum RawOplogPayload {
     SerializedInline(Vec<u8>),
     External {
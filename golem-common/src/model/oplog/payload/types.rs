@@ -19,6 +19,7 @@ use crate::model::invocation_context::{AttributeValue, InvocationContextStack, T
 use crate::model::oplog::{
     PublicAttribute, PublicExternalSpanData, PublicLocalSpanData, PublicSpanData, SpanData,
 };
+use crate::model::worker::ScheduleRecurrence;
 use crate::model::{
     AccountId, IdempotencyKey, OwnedWorkerId, RdbmsPoolKey, ScheduleId, ScheduledAction, WorkerId,
     WorkerMetadata, WorkerStatus,
@@ -1275,10 +1276,11 @@ pub enum SerializableRpcError {
     Denied { details: String },
     NotFound { details: String },
     RemoteInternalError { details: String },
+    Timeout { details: String },
 }
 
 #[derive(Debug, Clone, PartialEq, BinaryCodec, IntoValue, FromValue)]
-#[desert(evolution())]
+#[desert(evolution(FieldAdded("recurrence", None)))]
 #[wit_transparent]
 pub struct SerializableScheduledInvocation {
     pub timestamp: i64,
@@ -1291,6 +1293,8 @@ pub struct SerializableScheduledInvocation {
     pub trace_id: TraceId,
     pub trace_states: Vec<String>,
     pub spans: Vec<Vec<PublicSpanData>>,
+    /// Set for recurring invocations, in which case `idempotency_key` is the base key of the schedule
+    pub recurrence: Option<ScheduleRecurrence>,
 }
 
 impl SerializableScheduledInvocation {
@@ -1314,29 +1318,65 @@ impl SerializableScheduledInvocation {
                 spans: encode_span_data(&invocation_context.to_oplog_data()),
                 trace_id: invocation_context.trace_id,
                 trace_states: invocation_context.trace_states,
+                recurrence: None,
+            }),
+            ScheduledAction::RecurringInvoke {
+                account_id,
+                owned_worker_id,
+                idempotency_key,
+                full_function_name,
+                function_input,
+                invocation_context,
+                recurrence,
+            } => Ok(Self {
+                timestamp: schedule_id.timestamp,
+                account_id,
+                environment_id: owned_worker_id.environment_id,
+                worker_id: owned_worker_id.worker_id,
+                idempotency_key,
+                full_function_name,
+                function_input,
+                spans: encode_span_data(&invocation_context.to_oplog_data()),
+                trace_id: invocation_context.trace_id,
+                trace_states: invocation_context.trace_states,
+                recurrence: Some(recurrence),
             }),
             _ => Err("ScheduleId does not describe an invocation".to_string()),
         }
     }
 
     pub fn into_domain(self) -> ScheduleId {
-        ScheduleId {
-            timestamp: self.timestamp,
-            action: ScheduledAction::Invoke {
+        let owned_worker_id = OwnedWorkerId {
+            environment_id: self.environment_id,
+            worker_id: self.worker_id,
+        };
+        let invocation_context = InvocationContextStack::from_oplog_data(
+            self.trace_id,
+            self.trace_states,
+            decode_span_data(self.spans),
+        );
+        let action = match self.recurrence {
+            None => ScheduledAction::Invoke {
                 account_id: self.account_id,
-                owned_worker_id: OwnedWorkerId {
-                    environment_id: self.environment_id,
-                    worker_id: self.worker_id,
-                },
+                owned_worker_id,
                 idempotency_key: self.idempotency_key,
                 full_function_name: self.full_function_name,
                 function_input: self.function_input,
-                invocation_context: InvocationContextStack::from_oplog_data(
-                    self.trace_id,
-                    self.trace_states,
-                    decode_span_data(self.spans),
-                ),
+                invocation_context,
             },
+            Some(recurrence) => ScheduledAction::RecurringInvoke {
+                account_id: self.account_id,
+                owned_worker_id,
+                idempotency_key: self.idempotency_key,
+                full_function_name: self.full_function_name,
+                function_input: self.function_input,
+                invocation_context,
+                recurrence,
+            },
+        };
+        ScheduleId {
+            timestamp: self.timestamp,
+            action,
         }
     }
 }
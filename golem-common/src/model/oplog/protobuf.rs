@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::{
-    ExportedFunctionParameters, LogLevel, ManualUpdateParameters, OplogCursor,
+    ExportedFunctionParameters, LogLevel, ManualUpdateParameters, OplogCursor, OplogSearchHit,
     PluginInstallationDescription, PublicAttribute, PublicAttributeValue,
     PublicDurableFunctionType, PublicExternalSpanData, PublicLocalSpanData, PublicOplogEntry,
     PublicOplogEntryWithIndex, PublicRetryConfig, PublicSpanData, PublicUpdateDescription,
@@ -25,16 +25,16 @@ use crate::model::component::PluginPriority;
 use crate::model::invocation_context::{SpanId, TraceId};
 use crate::model::oplog::public_oplog_entry::{
     ActivatePluginParams, BeginAtomicRegionParams, BeginRemoteTransactionParams,
-    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangePersistenceLevelParams,
-    ChangeRetryPolicyParams, CommittedRemoteTransactionParams, CreateParams, CreateResourceParams,
-    DeactivatePluginParams, DropResourceParams, EndAtomicRegionParams, EndRemoteWriteParams,
-    ErrorParams, ExitedParams, ExportedFunctionCompletedParams, ExportedFunctionInvokedParams,
-    FailedUpdateParams, FinishSpanParams, GrowMemoryParams, ImportedFunctionInvokedParams,
-    InterruptedParams, JumpParams, LogParams, NoOpParams, PendingUpdateParams,
-    PendingWorkerInvocationParams, PreCommitRemoteTransactionParams,
-    PreRollbackRemoteTransactionParams, RestartParams, RevertParams,
-    RolledBackRemoteTransactionParams, SetSpanAttributeParams, StartSpanParams,
-    SuccessfulUpdateParams, SuspendParams,
+    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangeLabelsParams,
+    ChangePersistenceLevelParams, ChangeRetryPolicyParams, CommittedRemoteTransactionParams,
+    CreateParams, CreateResourceParams, DeactivatePluginParams, DropResourceParams,
+    EndAtomicRegionParams, EndRemoteWriteParams, ErrorParams, ExitedParams,
+    ExportedFunctionCompletedParams, ExportedFunctionInvokedParams, FailedUpdateParams,
+    FinishSpanParams, GrowMemoryParams, ImportedFunctionInvokedParams, InterruptedParams,
+    JumpParams, LogParams, NoOpParams, PendingUpdateParams, PendingWorkerInvocationParams,
+    PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams, RestartParams,
+    RevertParams, RolledBackRemoteTransactionParams, SetSpanAttributeParams, SnapshotParams,
+    StartSpanParams, SuccessfulUpdateParams, SuspendParams,
 };
 use crate::model::oplog::PersistenceLevel;
 use crate::model::regions::OplogRegion;
@@ -94,6 +94,7 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerError> for WorkerError
             Error::InvalidRequest(inner) => Ok(Self::InvalidRequest(inner.details)),
             Error::UnknownError(inner) => Ok(Self::Unknown(inner.details)),
             Error::ExceededMemoryLimit(_) => Ok(Self::ExceededMemoryLimit),
+            Error::QuotaExceeded(inner) => Ok(Self::QuotaExceeded(inner.details)),
         }
     }
 }
@@ -114,6 +115,9 @@ impl From<WorkerError> for golem_api_grpc::proto::golem::worker::WorkerError {
             WorkerError::ExceededMemoryLimit => {
                 Error::ExceededMemoryLimit(grpc_worker::ExceededMemoryLimit {})
             }
+            WorkerError::QuotaExceeded(details) => {
+                Error::QuotaExceeded(grpc_worker::QuotaExceeded { details })
+            }
         };
         Self { error: Some(error) }
     }
@@ -589,6 +593,17 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::OplogEntry> for PublicOplogEn
                     begin_index: OplogIndex::from_u64(value.begin_index),
                 }),
             ),
+            oplog_entry::Entry::Snapshot(value) => Ok(PublicOplogEntry::Snapshot(SnapshotParams {
+                timestamp: value.timestamp.ok_or("Missing timestamp field")?.into(),
+                data: value.data,
+            })),
+            oplog_entry::Entry::ChangeLabels(value) => {
+                Ok(PublicOplogEntry::ChangeLabels(ChangeLabelsParams {
+                    timestamp: value.timestamp.ok_or("Missing timestamp field")?.into(),
+                    set: value.set.into_iter().collect(),
+                    removed: value.removed.into_iter().collect(),
+                }))
+            }
         }
     }
 }
@@ -1020,6 +1035,27 @@ impl TryFrom<PublicOplogEntry> for golem_api_grpc::proto::golem::worker::OplogEn
                     )),
                 }
             }
+            PublicOplogEntry::Snapshot(snapshot) => {
+                golem_api_grpc::proto::golem::worker::OplogEntry {
+                    entry: Some(oplog_entry::Entry::Snapshot(
+                        golem_api_grpc::proto::golem::worker::SnapshotParameters {
+                            timestamp: Some(snapshot.timestamp.into()),
+                            data: snapshot.data,
+                        },
+                    )),
+                }
+            }
+            PublicOplogEntry::ChangeLabels(change) => {
+                golem_api_grpc::proto::golem::worker::OplogEntry {
+                    entry: Some(oplog_entry::Entry::ChangeLabels(
+                        golem_api_grpc::proto::golem::worker::ChangeLabelsParameters {
+                            timestamp: Some(change.timestamp.into()),
+                            set: change.set.into_iter().collect(),
+                            removed: change.removed.into_iter().collect(),
+                        },
+                    )),
+                }
+            }
         })
     }
 }
@@ -1410,3 +1446,32 @@ impl TryFrom<PublicOplogEntryWithIndex>
         })
     }
 }
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::OplogSearchHit> for OplogSearchHit {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::OplogSearchHit,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            worker_id: value
+                .worker_id
+                .ok_or("Missing field: worker_id")?
+                .try_into()?,
+            oplog_index: OplogIndex::from_u64(value.oplog_index),
+            entry: value.entry.ok_or("Missing field: entry")?.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<OplogSearchHit> for golem_api_grpc::proto::golem::worker::OplogSearchHit {
+    type Error = String;
+
+    fn try_from(value: OplogSearchHit) -> Result<Self, Self::Error> {
+        Ok(Self {
+            worker_id: Some(value.worker_id.into()),
+            oplog_index: value.oplog_index.into(),
+            entry: Some(value.entry.try_into()?),
+        })
+    }
+}
@@ -115,7 +115,7 @@ impl From<AtomicOplogIndex> for OplogIndex {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, BinaryCodec)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
 #[desert(transparent)]
 pub struct PayloadId(pub Uuid);
 
@@ -262,6 +262,8 @@ pub enum WorkerError {
     OutOfMemory,
     // The worker tried to grow its memory beyond the limits of the plan
     ExceededMemoryLimit,
+    // The agent violated one of its per-agent quotas
+    QuotaExceeded(String),
 }
 
 impl WorkerError {
@@ -272,6 +274,7 @@ impl WorkerError {
             Self::StackOverflow => "Stack overflow",
             Self::OutOfMemory => "Out of memory",
             Self::ExceededMemoryLimit => "Exceeded plan memory limit",
+            Self::QuotaExceeded(message) => message,
         }
     }
 
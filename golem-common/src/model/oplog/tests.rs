@@ -454,3 +454,99 @@ fn restart_serialization_poem_serde_equivalence() {
     let deserialized: PublicOplogEntry = serde_json::from_str(&serialized).unwrap();
     assert_eq!(entry, deserialized);
 }
+
+#[test]
+fn exported_oplog_roundtrip_and_remap() {
+    use crate::model::oplog::{
+        encode_exported_oplog_part, ExportedOplogDecoder, ExportedOplogHeader, ExportedOplogPart,
+        ExportedOplogPayload, OplogEntry, OplogPayload, PayloadId, RawOplogPayload,
+    };
+    use std::collections::HashMap;
+
+    let payload_id = PayloadId::new();
+    let mut entry = OplogEntry::ExportedFunctionCompleted {
+        timestamp: Timestamp::now_utc().rounded(),
+        response: OplogPayload::External {
+            payload_id: payload_id.clone(),
+            md5_hash: vec![1, 2, 3],
+        },
+        consumed_fuel: 100,
+    };
+
+    let parts = vec![
+        ExportedOplogPart::Header(ExportedOplogHeader {
+            worker_id: WorkerId {
+                component_id: ComponentId(Uuid::new_v4()),
+                worker_name: "test".to_string(),
+            },
+            exported_at: Timestamp::now_utc().rounded(),
+            component_revisions: vec![],
+        }),
+        ExportedOplogPart::Payload(ExportedOplogPayload {
+            payload_id: payload_id.clone(),
+            data: vec![4, 5, 6],
+        }),
+        ExportedOplogPart::Entries(vec![entry.clone(), OplogEntry::suspend().rounded()]),
+        ExportedOplogPart::End,
+    ];
+    let mut archive = Vec::new();
+    for part in &parts {
+        archive.extend(encode_exported_oplog_part(part).unwrap());
+    }
+
+    // Chunk boundaries are independent of the part boundaries
+    let mut decoder = ExportedOplogDecoder::new();
+    let mut decoded = Vec::new();
+    for chunk in archive.chunks(7) {
+        decoder.push(chunk);
+        while let Some(part) = decoder.next_part().unwrap() {
+            decoded.push(part);
+        }
+    }
+    decoder.finish().unwrap();
+    assert_eq!(parts, decoded);
+
+    assert_eq!(
+        entry.external_payloads(),
+        vec![(&payload_id, &vec![1u8, 2, 3])]
+    );
+    assert!(entry.remap_external_payloads(&HashMap::new()).is_err());
+
+    let new_payload_id = PayloadId::new();
+    let mapping = HashMap::from_iter(vec![(
+        payload_id,
+        RawOplogPayload::External {
+            payload_id: new_payload_id.clone(),
+            md5_hash: vec![7, 8, 9],
+        },
+    )]);
+    entry.remap_external_payloads(&mapping).unwrap();
+    assert_eq!(
+        entry.external_payloads(),
+        vec![(&new_payload_id, &vec![7u8, 8, 9])]
+    );
+}
+
+#[test]
+fn exported_oplog_decoder_rejects_malformed_archives() {
+    use crate::model::oplog::{
+        encode_exported_oplog_part, ExportedOplogDecoder, ExportedOplogPart,
+    };
+
+    let encoded = encode_exported_oplog_part(&ExportedOplogPart::Entries(vec![])).unwrap();
+
+    let mut truncated = ExportedOplogDecoder::new();
+    truncated.push(&encoded[..encoded.len() - 1]);
+    assert_eq!(truncated.next_part().unwrap(), None);
+    assert!(truncated.finish().is_err());
+
+    let mut unknown_version = ExportedOplogDecoder::new();
+    let mut tampered = encoded.clone();
+    tampered[4] = 1;
+    unknown_version.push(&tampered);
+    assert!(unknown_version.next_part().is_err());
+
+    let mut empty_part = ExportedOplogDecoder::new();
+    empty_part.push(&[0, 0, 0, 0]);
+    assert!(empty_part.next_part().is_err());
+}
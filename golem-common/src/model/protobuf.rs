@@ -19,7 +19,8 @@ use crate::model::{
     ComponentFilePermissions, FilterComparator, IdempotencyKey, LogLevel, NumberOfShards, Pod,
     PromiseId, RoutingTable, RoutingTableEntry, ScanCursor, ShardId, StringFilterComparator,
     Timestamp, WorkerCreatedAtFilter, WorkerEnvFilter, WorkerEvent, WorkerFilter, WorkerId,
-    WorkerNameFilter, WorkerNotFilter, WorkerRevisionFilter, WorkerStatus, WorkerStatusFilter,
+    WorkerLabelFilter, WorkerNameFilter, WorkerNotFilter, WorkerResourceUsageFilter,
+    WorkerRevisionFilter, WorkerStatus, WorkerStatusFilter,
 };
 use applying::Apply;
 use golem_api_grpc::proto::golem;
@@ -236,6 +237,18 @@ impl TryFrom<golem::worker::WorkerFilter> for WorkerFilter {
                         filter.value,
                     ))
                 }
+                golem::worker::worker_filter::Filter::ResourceUsage(filter) => {
+                    Ok(WorkerFilter::new_resource_usage(
+                        filter.metric.try_into()?,
+                        filter.comparator.try_into()?,
+                        filter.value,
+                    ))
+                }
+                golem::worker::worker_filter::Filter::Label(filter) => Ok(WorkerFilter::new_label(
+                    filter.name,
+                    filter.comparator.try_into()?,
+                    filter.value,
+                )),
                 golem::worker::worker_filter::Filter::Not(filter) => {
                     let filter = *filter.filter.ok_or_else(|| "Missing filter".to_string())?;
                     Ok(WorkerFilter::new_not(filter.try_into()?))
@@ -305,6 +318,15 @@ impl From<WorkerFilter> for golem::worker::WorkerFilter {
                     value,
                 },
             ),
+            WorkerFilter::Label(WorkerLabelFilter {
+                name,
+                comparator,
+                value,
+            }) => golem::worker::worker_filter::Filter::Label(golem::worker::WorkerLabelFilter {
+                name,
+                comparator: comparator.into(),
+                value,
+            }),
             WorkerFilter::Status(WorkerStatusFilter { comparator, value }) => {
                 golem::worker::worker_filter::Filter::Status(golem::worker::WorkerStatusFilter {
                     comparator: comparator.into(),
@@ -319,6 +341,17 @@ impl From<WorkerFilter> for golem::worker::WorkerFilter {
                     },
                 )
             }
+            WorkerFilter::ResourceUsage(WorkerResourceUsageFilter {
+                metric,
+                comparator,
+                value,
+            }) => golem::worker::worker_filter::Filter::ResourceUsage(
+                golem::worker::WorkerResourceUsageFilter {
+                    metric: metric.into(),
+                    comparator: comparator.into(),
+                    value,
+                },
+            ),
             WorkerFilter::Not(WorkerNotFilter { filter }) => {
                 let f: golem::worker::WorkerFilter = (*filter).into();
                 golem::worker::worker_filter::Filter::Not(Box::new(
@@ -12,21 +12,66 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use base64::Engine;
 use openidconnect::IssuerUrl;
+use rand::rngs::OsRng;
+use rand::TryRngCore;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub use crate::base_model::security_scheme::*;
 
+/// Prefix of generated API key secrets, making leaked keys easy to recognize
+pub const API_KEY_SECRET_PREFIX: &str = "golem_ak_";
+
+/// Generates a new random API key secret
+pub fn new_api_key_secret() -> String {
+    let mut secret = [0u8; 32]; // 32 bytes = 256 bits
+    OsRng
+        .try_fill_bytes(&mut secret)
+        .expect("Failed to generate random bytes");
+    format!(
+        "{API_KEY_SECRET_PREFIX}{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(secret)
+    )
+}
+
+/// Hash under which API key secrets are stored and looked up. The secrets are random with enough
+/// entropy that a fast unsalted hash is sufficient.
+pub fn hash_api_key_secret(secret: &str) -> String {
+    blake3::hash(secret.as_bytes()).to_hex().to_string()
+}
+
 impl Provider {
-    pub fn issuer_url(&self) -> IssuerUrl {
+    /// Well-known issuer of the provider, None for custom providers which have to be configured
+    /// with an explicit issuer url.
+    pub fn issuer_url(&self) -> Option<IssuerUrl> {
         match self {
-            Provider::Google => IssuerUrl::new("https://accounts.google.com".to_string()).unwrap(),
-            Provider::Facebook => IssuerUrl::new("https://www.facebook.com".to_string()).unwrap(),
+            Provider::Google => {
+                Some(IssuerUrl::new("https://accounts.google.com".to_string()).unwrap())
+            }
+            Provider::Facebook => {
+                Some(IssuerUrl::new("https://www.facebook.com".to_string()).unwrap())
+            }
             Provider::Microsoft => {
-                IssuerUrl::new("https://login.microsoftonline.com".to_string()).unwrap()
+                Some(IssuerUrl::new("https://login.microsoftonline.com".to_string()).unwrap())
             }
-            Provider::Gitlab => IssuerUrl::new("https://gitlab.com".to_string()).unwrap(),
+            Provider::Gitlab => Some(IssuerUrl::new("https://gitlab.com".to_string()).unwrap()),
+            Provider::Custom => None,
+        }
+    }
+
+    /// Resolves the issuer used for discovery, validating that custom providers have an explicit
+    /// issuer url and well-known providers do not.
+    pub fn resolve_issuer_url(&self, issuer_url: Option<&str>) -> Result<IssuerUrl, String> {
+        match (self.issuer_url(), issuer_url) {
+            (Some(well_known), None) => Ok(well_known),
+            (Some(_), Some(_)) => Err(format!(
+                "Issuer url can only be provided for custom providers, not for {self}"
+            )),
+            (None, Some(issuer_url)) => IssuerUrl::new(issuer_url.to_string())
+                .map_err(|err| format!("Invalid issuer url {issuer_url}: {err}")),
+            (None, None) => Err(format!("Issuer url is required for {self} providers")),
         }
     }
 }
@@ -38,6 +83,7 @@ impl Display for Provider {
             Provider::Facebook => write!(f, "facebook"),
             Provider::Microsoft => write!(f, "microsoft"),
             Provider::Gitlab => write!(f, "gitlab"),
+            Provider::Custom => write!(f, "custom"),
         }
     }
 }
@@ -51,13 +97,35 @@ impl FromStr for Provider {
             "facebook" => Ok(Provider::Facebook),
             "microsoft" => Ok(Provider::Microsoft),
             "gitlab" => Ok(Provider::Gitlab),
+            "custom" => Ok(Provider::Custom),
             _ => Err(format!("Invalid provider: {s}")),
         }
     }
 }
 
+impl Display for SecuritySchemeMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecuritySchemeMode::AuthorizationCode => write!(f, "authorization-code"),
+            SecuritySchemeMode::BearerToken => write!(f, "bearer-token"),
+        }
+    }
+}
+
+impl FromStr for SecuritySchemeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "authorization-code" => Ok(SecuritySchemeMode::AuthorizationCode),
+            "bearer-token" => Ok(SecuritySchemeMode::BearerToken),
+            _ => Err(format!("Invalid security scheme mode: {s}")),
+        }
+    }
+}
+
 mod protobuf {
-    use super::Provider;
+    use super::{Provider, SecuritySchemeMode};
 
     impl From<Provider> for golem_api_grpc::proto::golem::registry::SecuritySchemeProvider {
         fn from(value: Provider) -> Self {
@@ -66,6 +134,7 @@ mod protobuf {
                 Provider::Facebook => Self::Facebook,
                 Provider::Gitlab => Self::Gitlab,
                 Provider::Microsoft => Self::Microsoft,
+                Provider::Custom => Self::Custom,
             }
         }
     }
@@ -81,8 +150,94 @@ mod protobuf {
                 GrpcProvider::Gitlab => Ok(Self::Gitlab),
                 GrpcProvider::Google => Ok(Self::Google),
                 GrpcProvider::Microsoft => Ok(Self::Microsoft),
+                GrpcProvider::Custom => Ok(Self::Custom),
                 GrpcProvider::Unspecified => Err("Unknown provider".to_string()),
             }
         }
     }
+
+    impl From<SecuritySchemeMode> for golem_api_grpc::proto::golem::registry::SecuritySchemeMode {
+        fn from(value: SecuritySchemeMode) -> Self {
+            match value {
+                SecuritySchemeMode::AuthorizationCode => Self::AuthorizationCode,
+                SecuritySchemeMode::BearerToken => Self::BearerToken,
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::registry::SecuritySchemeMode> for SecuritySchemeMode {
+        fn from(value: golem_api_grpc::proto::golem::registry::SecuritySchemeMode) -> Self {
+            use golem_api_grpc::proto::golem::registry::SecuritySchemeMode as GrpcMode;
+            match value {
+                // senders predating the mode field only supported the authorization code flow
+                GrpcMode::Unspecified | GrpcMode::AuthorizationCode => Self::AuthorizationCode,
+                GrpcMode::BearerToken => Self::BearerToken,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        hash_api_key_secret, new_api_key_secret, Provider, SecuritySchemeMode,
+        API_KEY_SECRET_PREFIX,
+    };
+    use test_r::test;
+
+    #[test]
+    fn well_known_providers_use_their_own_issuer() {
+        let issuer_url = Provider::Google.resolve_issuer_url(None).unwrap();
+        assert_eq!(issuer_url.as_str(), "https://accounts.google.com");
+
+        assert!(Provider::Google
+            .resolve_issuer_url(Some("https://idp.example.com"))
+            .is_err());
+    }
+
+    #[test]
+    fn custom_provider_requires_valid_issuer() {
+        let issuer_url = Provider::Custom
+            .resolve_issuer_url(Some("https://idp.example.com/realms/golem"))
+            .unwrap();
+        assert_eq!(issuer_url.as_str(), "https://idp.example.com/realms/golem");
+
+        assert!(Provider::Custom.resolve_issuer_url(None).is_err());
+        assert!(Provider::Custom
+            .resolve_issuer_url(Some("not a url"))
+            .is_err());
+    }
+
+    #[test]
+    fn custom_provider_roundtrips_through_string() {
+        assert_eq!(
+            Provider::Custom.to_string().parse::<Provider>(),
+            Ok(Provider::Custom)
+        );
+    }
+
+    #[test]
+    fn security_scheme_mode_roundtrips_through_string() {
+        for mode in [
+            SecuritySchemeMode::AuthorizationCode,
+            SecuritySchemeMode::BearerToken,
+        ] {
+            assert_eq!(mode.to_string().parse::<SecuritySchemeMode>(), Ok(mode));
+        }
+        assert_eq!(
+            serde_json::to_string(&SecuritySchemeMode::BearerToken).unwrap(),
+            "\"bearer-token\""
+        );
+    }
+
+    #[test]
+    fn api_key_secrets_are_random_and_hashed_deterministically() {
+        let secret = new_api_key_secret();
+        let other = new_api_key_secret();
+
+        assert!(secret.starts_with(API_KEY_SECRET_PREFIX));
+        assert_ne!(secret, other);
+        assert_eq!(hash_api_key_secret(&secret), hash_api_key_secret(&secret));
+        assert_ne!(hash_api_key_secret(&secret), hash_api_key_secret(&other));
+    }
 }
@@ -15,13 +15,18 @@
 use crate::model::component::{ComponentFilePath, ComponentRevision};
 use crate::model::environment::EnvironmentId;
 use crate::model::oplog::OplogIndex;
+use crate::model::worker::{
+    validate_label_key, ChangeWorkerLabelsRequest, CronRecurrence, IntervalRecurrence,
+    RetentionCandidate, RetentionReason, ScheduleRecurrence,
+};
 use crate::model::{
     AccountId, ComponentId, FilterComparator, IdempotencyKey, StringFilterComparator, Timestamp,
-    WorkerFilter, WorkerId, WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+    WorkerFilter, WorkerId, WorkerMetadata, WorkerResourceUsageMetric, WorkerResourceUsageRecord,
+    WorkerStatus, WorkerStatusRecord,
 };
 use desert_rust::BinaryCodec;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 use std::vec;
 use test_r::test;
@@ -95,6 +100,33 @@ fn worker_filter_parse() {
             "abc".to_string(),
         )
     );
+
+    assert_eq!(
+        WorkerFilter::from_str("consumed_fuel > 1000").unwrap(),
+        WorkerFilter::new_resource_usage(
+            WorkerResourceUsageMetric::ConsumedFuel,
+            FilterComparator::Greater,
+            1000
+        )
+    );
+
+    assert_eq!(
+        WorkerFilter::from_str("peakLinearMemorySize <= 65536").unwrap(),
+        WorkerFilter::new_resource_usage(
+            WorkerResourceUsageMetric::PeakLinearMemorySize,
+            FilterComparator::LessEqual,
+            65536
+        )
+    );
+
+    assert_eq!(
+        WorkerFilter::from_str("label.tenant startswith acme").unwrap(),
+        WorkerFilter::new_label(
+            "tenant".to_string(),
+            StringFilterComparator::StartsWith,
+            "acme".to_string(),
+        )
+    );
 }
 
 #[test]
@@ -219,6 +251,12 @@ fn worker_filter_matches() {
         parent: None,
         last_known_status: WorkerStatusRecord {
             component_revision: ComponentRevision::new(1).unwrap(),
+            resource_usage: WorkerResourceUsageRecord {
+                consumed_fuel: 5000,
+                invocation_count: 3,
+                ..WorkerResourceUsageRecord::default()
+            },
+            labels: BTreeMap::from([("tenant".to_string(), "acme".to_string())]),
             ..WorkerStatusRecord::default()
         },
         original_phantom_id: None,
@@ -302,6 +340,39 @@ fn worker_filter_matches() {
         "value2".to_string(),
     )
     .matches(&worker_metadata));
+
+    assert!(WorkerFilter::new_resource_usage(
+        WorkerResourceUsageMetric::ConsumedFuel,
+        FilterComparator::GreaterEqual,
+        5000
+    )
+    .and(WorkerFilter::new_resource_usage(
+        WorkerResourceUsageMetric::InvocationCount,
+        FilterComparator::Less,
+        4
+    ))
+    .matches(&worker_metadata));
+
+    assert!(!WorkerFilter::new_resource_usage(
+        WorkerResourceUsageMetric::ConsumedFuel,
+        FilterComparator::Greater,
+        5000
+    )
+    .matches(&worker_metadata));
+
+    assert!(WorkerFilter::new_label(
+        "tenant".to_string(),
+        StringFilterComparator::Equal,
+        "acme".to_string(),
+    )
+    .matches(&worker_metadata));
+
+    assert!(!WorkerFilter::new_label(
+        "region".to_string(),
+        StringFilterComparator::NotEqual,
+        "eu".to_string(),
+    )
+    .matches(&worker_metadata));
 }
 
 #[test]
@@ -360,3 +431,117 @@ fn initial_component_file_path_from_relative() {
     let path = ComponentFilePath::from_abs_str("a/b/c");
     assert!(path.is_err());
 }
+
+#[test]
+fn derived_occurrence_idempotency_keys_are_deterministic() {
+    let base = IdempotencyKey::fresh();
+
+    let first_a = IdempotencyKey::derived_for_occurrence(&base, 1000);
+    let first_b = IdempotencyKey::derived_for_occurrence(&base, 1000);
+    let second = IdempotencyKey::derived_for_occurrence(&base, 2000);
+
+    assert_eq!(first_a, first_b);
+    assert_ne!(first_a, second);
+    assert_ne!(
+        first_a,
+        IdempotencyKey::derived(&base, OplogIndex::from_u64(1000))
+    );
+}
+
+#[test]
+fn interval_recurrence_is_aligned_to_anchor() {
+    let recurrence = ScheduleRecurrence::Interval(IntervalRecurrence {
+        interval_millis: 1000,
+        until: None,
+    });
+
+    assert_eq!(recurrence.next_occurrence(5000, 3000), Ok(Some(5000)));
+    assert_eq!(recurrence.next_occurrence(5000, 5000), Ok(Some(5000)));
+    assert_eq!(recurrence.next_occurrence(5000, 5001), Ok(Some(6000)));
+    assert_eq!(recurrence.next_occurrence(5000, 9500), Ok(Some(10000)));
+}
+
+#[test]
+fn interval_recurrence_respects_until() {
+    let recurrence = ScheduleRecurrence::Interval(IntervalRecurrence {
+        interval_millis: 1000,
+        until: Some(Timestamp::from(7000)),
+    });
+
+    assert_eq!(recurrence.next_occurrence(5000, 6500), Ok(Some(7000)));
+    assert_eq!(recurrence.next_occurrence(5000, 7001), Ok(None));
+}
+
+#[test]
+fn cron_recurrence_next_occurrence() {
+    let recurrence = ScheduleRecurrence::Cron(CronRecurrence {
+        expression: "0 */5 * * * *".to_string(),
+        until: None,
+    });
+
+    // 2025-01-01T00:00:00Z
+    let start = 1735689600000;
+    assert_eq!(recurrence.next_occurrence(start, start), Ok(Some(start)));
+    assert_eq!(
+        recurrence.next_occurrence(start, start + 1),
+        Ok(Some(start + 5 * 60 * 1000))
+    );
+}
+
+#[test]
+fn recurrence_validation() {
+    assert!(ScheduleRecurrence::Interval(IntervalRecurrence {
+        interval_millis: 0,
+        until: None,
+    })
+    .validate()
+    .is_err());
+    assert!(ScheduleRecurrence::Cron(CronRecurrence {
+        expression: "not a cron expression".to_string(),
+        until: None,
+    })
+    .validate()
+    .is_err());
+    assert!(ScheduleRecurrence::Cron(CronRecurrence {
+        expression: "0 0 * * * *".to_string(),
+        until: None,
+    })
+    .validate()
+    .is_ok());
+}
+
+#[test]
+fn retention_candidate_protobuf_roundtrip() {
+    let candidate = RetentionCandidate {
+        worker_id: WorkerId {
+            component_id: ComponentId::new(),
+            worker_name: "inactive-worker".to_string(),
+        },
+        status: WorkerStatus::Suspended,
+        reason: RetentionReason::Idle,
+        last_activity: Timestamp::from(1735689600000),
+    };
+
+    let proto: golem_api_grpc::proto::golem::worker::RetentionCandidate = candidate.clone().into();
+    let roundtrip: RetentionCandidate = proto.try_into().unwrap();
+
+    assert_eq!(roundtrip, candidate);
+}
+
+#[test]
+fn empty_label_keys_are_rejected() {
+    assert!(validate_label_key("tenant").is_ok());
+    assert!(validate_label_key("").is_err());
+
+    let request = ChangeWorkerLabelsRequest {
+        set: BTreeMap::from([("tenant".to_string(), "acme".to_string())]),
+        removed: BTreeSet::from([String::new()]),
+    };
+    assert!(request.validate().is_err());
+
+    let request = ChangeWorkerLabelsRequest {
+        set: BTreeMap::from([(String::new(), "acme".to_string())]),
+        removed: BTreeSet::new(),
+    };
+    assert!(request.validate().is_err());
+}
@@ -14,16 +14,84 @@
 
 pub use crate::base_model::worker::*;
 
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+impl ScheduleRecurrence {
+    /// Checks that the recurrence can be used to compute occurrences
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ScheduleRecurrence::Interval(interval) => {
+                if interval.interval_millis == 0 {
+                    Err("Recurrence interval must be greater than zero".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            ScheduleRecurrence::Cron(cron) => {
+                cron::Schedule::from_str(&cron.expression).map_err(|err| {
+                    format!("Invalid cron expression '{}': {err}", cron.expression)
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Calculates the first occurrence at or after `from_millis`.
+    ///
+    /// Interval based occurrences are aligned to `anchor_millis`, the time of a previous (or the first) occurrence.
+    /// Returns `None` if there are no more occurrences before the recurrence's `until` limit.
+    pub fn next_occurrence(
+        &self,
+        anchor_millis: i64,
+        from_millis: i64,
+    ) -> Result<Option<i64>, String> {
+        let next = match self {
+            ScheduleRecurrence::Interval(interval) => {
+                let interval_millis = interval.interval_millis as i64;
+                if interval_millis <= 0 {
+                    return Err("Recurrence interval must be greater than zero".to_string());
+                }
+                if from_millis <= anchor_millis {
+                    anchor_millis
+                } else {
+                    let elapsed = from_millis - anchor_millis;
+                    let steps = (elapsed + interval_millis - 1) / interval_millis;
+                    anchor_millis + steps * interval_millis
+                }
+            }
+            ScheduleRecurrence::Cron(cron) => {
+                let schedule = cron::Schedule::from_str(&cron.expression).map_err(|err| {
+                    format!("Invalid cron expression '{}': {err}", cron.expression)
+                })?;
+                let after: DateTime<Utc> = DateTime::from_timestamp_millis(from_millis - 1)
+                    .ok_or_else(|| format!("Timestamp out of range: {from_millis}"))?;
+                match schedule.after(&after).next() {
+                    Some(next) => next.timestamp_millis(),
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        match self.until() {
+            Some(until) if next > until.to_millis() as i64 => Ok(None),
+            _ => Ok(Some(next)),
+        }
+    }
+}
+
 mod protobuf {
-    use super::WorkerMetadataDto;
+    use super::{CronRecurrence, IntervalRecurrence, RecurringInvocation, ScheduleRecurrence};
     use super::{
         ExportedResourceMetadata, FailedUpdate, PendingUpdate, SuccessfulUpdate, UpdateRecord,
         WasiConfigVars,
     };
+    use super::{RetentionCandidate, RetentionReason};
     use super::{
         RevertLastInvocations, RevertToOplogIndex, RevertWorkerTarget, WasiConfigVarsEntry,
         WorkerUpdateMode,
     };
+    use super::{WorkerMetadataDto, WorkerResourceUsage};
     use crate::model::component::PluginPriority;
     use crate::model::oplog::WorkerResourceId;
     use crate::model::regions::OplogRegion;
@@ -105,6 +173,8 @@ mod protobuf {
                 last_error: value.last_error,
                 component_size: value.component_size,
                 total_linear_memory_size: value.total_linear_memory_size,
+                resource_usage: value.resource_usage.ok_or("Missing resource_usage")?.into(),
+                labels: value.labels.into_iter().collect(),
                 exported_resource_instances,
                 active_plugins: value
                     .active_plugins
@@ -152,6 +222,8 @@ mod protobuf {
                 last_error: value.last_error,
                 component_size: value.component_size,
                 total_linear_memory_size: value.total_linear_memory_size,
+                resource_usage: Some(value.resource_usage.into()),
+                labels: value.labels.into_iter().collect(),
                 owned_resources,
                 active_plugins: value.active_plugins.into_iter().map(|id| id.0).collect(),
                 skipped_regions: value
@@ -168,6 +240,28 @@ mod protobuf {
         }
     }
 
+    impl From<golem_api_grpc::proto::golem::worker::WorkerResourceUsage> for WorkerResourceUsage {
+        fn from(value: golem_api_grpc::proto::golem::worker::WorkerResourceUsage) -> Self {
+            Self {
+                consumed_fuel: value.consumed_fuel,
+                peak_linear_memory_size: value.peak_linear_memory_size,
+                invocation_count: value.invocation_count,
+                invocation_time_millis: value.invocation_time_millis,
+            }
+        }
+    }
+
+    impl From<WorkerResourceUsage> for golem_api_grpc::proto::golem::worker::WorkerResourceUsage {
+        fn from(value: WorkerResourceUsage) -> Self {
+            Self {
+                consumed_fuel: value.consumed_fuel,
+                peak_linear_memory_size: value.peak_linear_memory_size,
+                invocation_count: value.invocation_count,
+                invocation_time_millis: value.invocation_time_millis,
+            }
+        }
+    }
+
     impl TryFrom<golem_api_grpc::proto::golem::worker::UpdateRecord> for UpdateRecord {
         type Error = String;
 
@@ -330,4 +424,144 @@ mod protobuf {
             }
         }
     }
+
+    impl TryFrom<golem_api_grpc::proto::golem::worker::ScheduleRecurrence> for ScheduleRecurrence {
+        type Error = String;
+
+        fn try_from(
+            value: golem_api_grpc::proto::golem::worker::ScheduleRecurrence,
+        ) -> Result<Self, Self::Error> {
+            match value.recurrence {
+                Some(
+                    golem_api_grpc::proto::golem::worker::schedule_recurrence::Recurrence::Interval(
+                        interval,
+                    ),
+                ) => Ok(ScheduleRecurrence::Interval(IntervalRecurrence {
+                    interval_millis: interval.interval_millis,
+                    until: interval.until.map(|until| until.into()),
+                })),
+                Some(
+                    golem_api_grpc::proto::golem::worker::schedule_recurrence::Recurrence::Cron(
+                        cron,
+                    ),
+                ) => Ok(ScheduleRecurrence::Cron(CronRecurrence {
+                    expression: cron.expression,
+                    until: cron.until.map(|until| until.into()),
+                })),
+                None => Err("Missing field: recurrence".to_string()),
+            }
+        }
+    }
+
+    impl From<ScheduleRecurrence> for golem_api_grpc::proto::golem::worker::ScheduleRecurrence {
+        fn from(value: ScheduleRecurrence) -> Self {
+            match value {
+                ScheduleRecurrence::Interval(interval) => Self {
+                    recurrence: Some(golem_api_grpc::proto::golem::worker::schedule_recurrence::Recurrence::Interval(
+                        golem_api_grpc::proto::golem::worker::IntervalRecurrence {
+                            interval_millis: interval.interval_millis,
+                            until: interval.until.map(|until| until.into()),
+                        },
+                    )),
+                },
+                ScheduleRecurrence::Cron(cron) => Self {
+                    recurrence: Some(golem_api_grpc::proto::golem::worker::schedule_recurrence::Recurrence::Cron(
+                        golem_api_grpc::proto::golem::worker::CronRecurrence {
+                            expression: cron.expression,
+                            until: cron.until.map(|until| until.into()),
+                        },
+                    )),
+                },
+            }
+        }
+    }
+
+    impl TryFrom<golem_api_grpc::proto::golem::worker::RecurringInvocation> for RecurringInvocation {
+        type Error = String;
+
+        fn try_from(
+            value: golem_api_grpc::proto::golem::worker::RecurringInvocation,
+        ) -> Result<Self, Self::Error> {
+            Ok(Self {
+                idempotency_key: value
+                    .idempotency_key
+                    .ok_or("Missing field: idempotency_key")?
+                    .into(),
+                function_name: value.function_name,
+                recurrence: value
+                    .recurrence
+                    .ok_or("Missing field: recurrence")?
+                    .try_into()?,
+                next_occurrence: value
+                    .next_occurrence
+                    .ok_or("Missing field: next_occurrence")?
+                    .into(),
+            })
+        }
+    }
+
+    impl From<RecurringInvocation> for golem_api_grpc::proto::golem::worker::RecurringInvocation {
+        fn from(value: RecurringInvocation) -> Self {
+            Self {
+                idempotency_key: Some(value.idempotency_key.into()),
+                function_name: value.function_name,
+                recurrence: Some(value.recurrence.into()),
+                next_occurrence: Some(value.next_occurrence.into()),
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::worker::RetentionReason> for RetentionReason {
+        fn from(value: golem_api_grpc::proto::golem::worker::RetentionReason) -> Self {
+            match value {
+                golem_api_grpc::proto::golem::worker::RetentionReason::Exited => Self::Exited,
+                golem_api_grpc::proto::golem::worker::RetentionReason::Failed => Self::Failed,
+                golem_api_grpc::proto::golem::worker::RetentionReason::Idle => Self::Idle,
+            }
+        }
+    }
+
+    impl From<RetentionReason> for golem_api_grpc::proto::golem::worker::RetentionReason {
+        fn from(value: RetentionReason) -> Self {
+            match value {
+                RetentionReason::Exited => Self::Exited,
+                RetentionReason::Failed => Self::Failed,
+                RetentionReason::Idle => Self::Idle,
+            }
+        }
+    }
+
+    impl TryFrom<golem_api_grpc::proto::golem::worker::RetentionCandidate> for RetentionCandidate {
+        type Error = String;
+
+        fn try_from(
+            value: golem_api_grpc::proto::golem::worker::RetentionCandidate,
+        ) -> Result<Self, Self::Error> {
+            Ok(Self {
+                reason: value.reason().into(),
+                worker_id: value
+                    .worker_id
+                    .ok_or("Missing field: worker_id")?
+                    .try_into()?,
+                status: value.status.try_into()?,
+                last_activity: value
+                    .last_activity
+                    .ok_or("Missing field: last_activity")?
+                    .into(),
+            })
+        }
+    }
+
+    impl From<RetentionCandidate> for golem_api_grpc::proto::golem::worker::RetentionCandidate {
+        fn from(value: RetentionCandidate) -> Self {
+            let status: golem_api_grpc::proto::golem::worker::WorkerStatus = value.status.into();
+            let reason: golem_api_grpc::proto::golem::worker::RetentionReason = value.reason.into();
+            Self {
+                worker_id: Some(value.worker_id.into()),
+                status: status.into(),
+                reason: reason.into(),
+                last_activity: Some(value.last_activity.into()),
+            }
+        }
+    }
 }
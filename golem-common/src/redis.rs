@@ -21,7 +21,7 @@ use desert_rust::{BinaryDeserializer, BinarySerializer};
 use fred::clients::Transaction;
 use fred::cmd;
 use fred::prelude::{Pool as FredRedisPool, *};
-use fred::types::{InfoKind, Limit, Map, MultipleKeys, MultipleValues};
+use fred::types::{InfoKind, Limit, Map, MultipleKeys, MultipleStrings, MultipleValues};
 use tracing::{debug, Level};
 
 use crate::metrics::redis::{record_redis_failure, record_redis_success};
@@ -593,6 +593,21 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn xdel<R, K, S>(&self, key: K, ids: S) -> RedisResult<R>
+    where
+        R: FromValue,
+        K: AsRef<str>,
+        S: Into<MultipleStrings> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "XDEL",
+            self.pool.xdel(self.prefixed_key(key), ids).await,
+        )
+    }
+
     pub async fn xtrim<R, K, C>(&self, key: K, cap: C) -> RedisResult<R>
     where
         R: FromValue,
@@ -826,6 +841,31 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn eval<R, K>(&self, script: &str, keys: Vec<K>, args: Vec<String>) -> RedisResult<R>
+    where
+        R: FromValue,
+        K: AsRef<str>,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+
+        //https://redis.io/commands/eval/
+        let mut eval_args: Vec<String> = vec![script.to_string(), keys.len().to_string()];
+        eval_args.extend(keys.iter().map(|key| self.prefixed_key(key)));
+        eval_args.extend(args);
+
+        self.record(
+            start,
+            "EVAL",
+            self.pool
+                .next()
+                .custom_raw(cmd!("EVAL"), eval_args)
+                .await
+                .and_then(|f| f.try_into())
+                .and_then(|v: Value| v.convert::<R>()),
+        )
+    }
+
     fn parse_key_scan_frame(&self, frame: Resp3Frame) -> RedisResult<(u64, Vec<String>)> {
         use fred::prelude::*;
         if let Resp3Frame::Array { mut data, .. } = frame {
@@ -199,6 +199,11 @@ impl SafeDisplay for StaticRegistryServiceConfig {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompileWorkerConfig {
     pub max_message_size: usize,
+    /// Number of components compiled in parallel
+    pub concurrency: usize,
+    /// How long the status of a finished compilation is kept for status queries
+    #[serde(with = "humantime_serde")]
+    pub status_retention: Duration,
     #[serde(flatten)]
     pub client_config: GrpcClientConfig,
 }
@@ -207,6 +212,8 @@ impl SafeDisplay for CompileWorkerConfig {
     fn to_safe_string(&self) -> String {
         let mut result = String::new();
         let _ = writeln!(&mut result, "max_message_size: {}", self.max_message_size);
+        let _ = writeln!(&mut result, "concurrency: {}", self.concurrency);
+        let _ = writeln!(&mut result, "status_retention: {:?}", self.status_retention);
         let _ = writeln!(&mut result, "{}", self.client_config.to_safe_string());
         result
     }
@@ -231,6 +238,8 @@ impl Default for CompileWorkerConfig {
     fn default() -> Self {
         Self {
             max_message_size: 1000000,
+            concurrency: 4,
+            status_retention: Duration::from_secs(60 * 60),
             client_config: GrpcClientConfig {
                 retries_on_unavailable: RetryConfig::max_attempts_3(),
                 connect_timeout: Duration::from_secs(10),
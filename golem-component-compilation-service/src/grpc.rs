@@ -19,8 +19,10 @@ use golem_api_grpc::proto::golem::common::{Empty, ErrorBody, ErrorsBody};
 use golem_api_grpc::proto::golem::component;
 use golem_api_grpc::proto::golem::componentcompilation::v1::component_compilation_service_server::ComponentCompilationService as GrpcCompilationServer;
 use golem_api_grpc::proto::golem::componentcompilation::v1::{
-    component_compilation_error, component_compilation_response, ComponentCompilationError,
-    ComponentCompilationRequest, ComponentCompilationResponse,
+    component_compilation_error, component_compilation_response, get_compilation_status_response,
+    CompilationPriority, CompilationState, CompilationStatus, ComponentCompilationError,
+    ComponentCompilationRequest, ComponentCompilationResponse, GetCompilationStatusRequest,
+    GetCompilationStatusResponse,
 };
 use golem_common::metrics::api::ApiErrorDetails;
 use golem_common::model::component::ComponentId;
@@ -95,6 +97,33 @@ impl GrpcCompilationServer for CompileGrpcService {
             result: Some(response),
         }))
     }
+
+    async fn get_compilation_status(
+        &self,
+        request: Request<GetCompilationStatusRequest>,
+    ) -> Result<Response<GetCompilationStatusResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_compilation_status",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let response = match self
+            .get_compilation_status_impl(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(status) => record.succeed(get_compilation_status_response::Result::Success(status)),
+            Err(error) => record.fail(
+                get_compilation_status_response::Result::Failure(error.clone()),
+                &mut ComponentCompilationTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(GetCompilationStatusResponse {
+            result: Some(response),
+        }))
+    }
 }
 
 impl CompileGrpcService {
@@ -109,11 +138,53 @@ impl CompileGrpcService {
             .try_into()
             .map_err(|e| bad_request_error(format!("missing component version: {e}")))?;
         let environment_id = make_environment_id(request.environment_id)?;
+        let priority = match request.priority() {
+            CompilationPriority::Low => crate::model::CompilationPriority::Low,
+            CompilationPriority::Normal => crate::model::CompilationPriority::Normal,
+            CompilationPriority::High => crate::model::CompilationPriority::High,
+        };
         self.service
-            .enqueue_compilation(component_id, component_revision, environment_id, sender)
+            .enqueue_compilation(
+                component_id,
+                component_revision,
+                environment_id,
+                priority,
+                sender,
+            )
             .await?;
         Ok(())
     }
+
+    async fn get_compilation_status_impl(
+        &self,
+        request: GetCompilationStatusRequest,
+    ) -> Result<CompilationStatus, ComponentCompilationError> {
+        let component_id = make_component_id(request.component_id)?;
+        let component_revision = request
+            .component_revision
+            .try_into()
+            .map_err(|e| bad_request_error(format!("missing component version: {e}")))?;
+        let environment_id = make_environment_id(request.environment_id)?;
+
+        let status =
+            self.service
+                .get_compilation_status(component_id, component_revision, environment_id);
+
+        let (state, error) = match status {
+            None => (CompilationState::Unknown, None),
+            Some(crate::model::CompilationStatus::Queued) => (CompilationState::Queued, None),
+            Some(crate::model::CompilationStatus::Compiling) => (CompilationState::Compiling, None),
+            Some(crate::model::CompilationStatus::Ready) => (CompilationState::Ready, None),
+            Some(crate::model::CompilationStatus::Failed(error)) => {
+                (CompilationState::Failed, Some(error))
+            }
+        };
+
+        Ok(CompilationStatus {
+            state: state as i32,
+            error,
+        })
+    }
 }
 
 impl From<crate::model::CompilationError> for ComponentCompilationError {
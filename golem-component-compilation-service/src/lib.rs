@@ -17,10 +17,12 @@ use crate::config::RegistryServiceConfig;
 use config::ServerConfig;
 use futures::TryFutureExt;
 use golem_api_grpc::proto::golem::componentcompilation::v1::component_compilation_service_server::ComponentCompilationServiceServer;
+use golem_common::model::component_bundle::wasmtime_config;
 use golem_service_base::config::BlobStorageConfig;
 use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::grpc::server::GrpcServerTlsConfig;
 use golem_service_base::service::compiled_component;
+use golem_service_base::service::compiled_component::CompiledComponentServiceConfig;
 use golem_service_base::storage::blob::s3::S3BlobStorage;
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
@@ -37,7 +39,6 @@ use tonic_tracing_opentelemetry::middleware::filters;
 use tracing::{info, Instrument};
 use wasmtime::component::__internal::anyhow;
 use wasmtime::component::__internal::anyhow::anyhow;
-use wasmtime::WasmBacktraceDetails;
 
 pub mod config;
 mod grpc;
@@ -89,7 +90,13 @@ pub async fn run(
     let compiled_component =
         compiled_component::configured(&config.compiled_component_service, blob_storage.clone());
 
-    let engine = wasmtime::Engine::new(&create_wasmtime_config()).expect("Failed to create engine");
+    // Compiled artifacts are shared between environments only if storing compiled components is enabled
+    let artifact_storage: Option<Arc<dyn BlobStorage>> = match &config.compiled_component_service {
+        CompiledComponentServiceConfig::Enabled(_) => Some(blob_storage.clone()),
+        CompiledComponentServiceConfig::Disabled(_) => None,
+    };
+
+    let engine = wasmtime::Engine::new(&wasmtime_config()).expect("Failed to create engine");
 
     // Start metrics and healthcheck server.
     let address = config.http_addr().expect("Invalid HTTP address");
@@ -106,6 +113,7 @@ pub async fn run(
         config.registry_service.clone(),
         engine,
         compiled_component,
+        artifact_storage,
     )
     .await;
 
@@ -169,16 +177,3 @@ async fn start_grpc_server(
 
     Ok(grpc_port)
 }
-
-fn create_wasmtime_config() -> wasmtime::Config {
-    let mut config = wasmtime::Config::default();
-
-    config.wasm_multi_value(true);
-    config.async_support(true);
-    config.wasm_component_model(true);
-    config.epoch_interruption(true);
-    config.consume_fuel(true);
-    config.wasm_backtrace_details(WasmBacktraceDetails::Enable);
-
-    config
-}
@@ -46,6 +46,27 @@ pub fn record_compilation_time(duration: Duration) {
     COMPILATION_TIME_SECONDS.observe(duration.as_secs_f64());
 }
 
+lazy_static! {
+    static ref ARTIFACT_CACHE_LOOKUPS_TOTAL: CounterVec = register_counter_vec!(
+        "component_compilation_artifact_cache_lookups_total",
+        "Number of lookups in the content-addressed cache of compiled components",
+        &["result"]
+    )
+    .unwrap();
+}
+
+pub fn record_artifact_cache_hit() {
+    ARTIFACT_CACHE_LOOKUPS_TOTAL
+        .with_label_values(&["hit"])
+        .inc();
+}
+
+pub fn record_artifact_cache_miss() {
+    ARTIFACT_CACHE_LOOKUPS_TOTAL
+        .with_label_values(&["miss"])
+        .inc();
+}
+
 pub fn register_all() -> Registry {
     default_registry().clone()
 }
@@ -13,14 +13,17 @@
 // limitations under the License.
 
 use std::fmt::Display;
+use std::path::PathBuf;
 
 use crate::config::StaticRegistryServiceConfig;
 use golem_common::model::component::{ComponentId, ComponentRevision};
+use golem_common::model::component_bundle::engine_compatibility_hash;
 use golem_common::model::environment::EnvironmentId;
 use tokio::sync::mpsc;
 use wasmtime::component::Component;
+use wasmtime::Engine;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct ComponentIdAndRevision {
     pub id: ComponentId,
     pub revision: ComponentRevision,
@@ -32,13 +35,70 @@ impl Display for ComponentIdAndRevision {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompilationPriority {
+    Low,
+    Normal,
+    High,
+}
+
 #[derive(Debug)]
 pub struct CompilationRequest {
     pub component: ComponentIdAndRevision,
     pub environment_id: EnvironmentId,
+    pub priority: CompilationPriority,
     pub sender: Option<StaticRegistryServiceConfig>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompilationStatus {
+    Queued,
+    Compiling,
+    Ready,
+    Failed(String),
+}
+
+impl CompilationStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            CompilationStatus::Ready | CompilationStatus::Failed(_)
+        )
+    }
+}
+
+/// Content address of a compiled component: the hash of the WASM binary together with
+/// the hash of the wasmtime settings affecting the generated native code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArtifactKey {
+    pub binary_hash: blake3::Hash,
+    pub engine_hash: blake3::Hash,
+}
+
+impl ArtifactKey {
+    pub fn new(engine: &Engine, binary: &[u8]) -> Self {
+        Self {
+            binary_hash: blake3::hash(binary),
+            engine_hash: engine_compatibility_hash(engine),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from(format!("{self}.cwasm"))
+    }
+}
+
+impl Display for ArtifactKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.binary_hash.to_hex(),
+            self.engine_hash.to_hex()
+        )
+    }
+}
+
 pub struct CompiledComponent {
     pub component_and_revision: ComponentIdAndRevision,
     pub environment_id: EnvironmentId,
@@ -0,0 +1,219 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::*;
+use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+type InFlightLocks = std::sync::Mutex<HashMap<ArtifactKey, Arc<Mutex<()>>>>;
+
+// Content-addressed cache of compiled components, shared by all environments. Identical WASM
+// binaries are compiled only once, even if they are requested by multiple workers at the same time.
+pub struct CompiledArtifactCache {
+    blob_storage: Option<Arc<dyn BlobStorage>>,
+    in_flight: InFlightLocks,
+}
+
+impl CompiledArtifactCache {
+    pub fn new(blob_storage: Option<Arc<dyn BlobStorage>>) -> Self {
+        Self {
+            blob_storage,
+            in_flight: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get_or_compile<F, Fut>(
+        &self,
+        key: &ArtifactKey,
+        engine: &Engine,
+        compile: F,
+    ) -> Result<Component, CompilationError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Component, CompilationError>>,
+    {
+        let in_flight = InFlight::acquire(&self.in_flight, key);
+        let _guard = in_flight.lock.lock().await;
+
+        match self.get(key, engine).await {
+            Some(component) => {
+                crate::metrics::record_artifact_cache_hit();
+                Ok(component)
+            }
+            None => {
+                crate::metrics::record_artifact_cache_miss();
+                let component = compile().await?;
+                self.put(key, &component).await;
+                Ok(component)
+            }
+        }
+    }
+
+    async fn get(&self, key: &ArtifactKey, engine: &Engine) -> Option<Component> {
+        let blob_storage = self.blob_storage.as_ref()?;
+        let bytes = match blob_storage
+            .get_raw(
+                "compiled_artifact_cache",
+                "get",
+                BlobStorageNamespace::CompiledArtifacts,
+                &key.path(),
+            )
+            .await
+        {
+            Ok(bytes) => bytes?,
+            Err(err) => {
+                warn!("Failed to download compiled artifact {key}: {err}");
+                return None;
+            }
+        };
+
+        // The key contains the hash of the engine settings, so the artifact was produced by a compatible engine
+        match unsafe { Component::deserialize(engine, &bytes) } {
+            Ok(component) => Some(component),
+            Err(err) => {
+                warn!("Failed to deserialize compiled artifact {key}: {err}");
+                None
+            }
+        }
+    }
+
+    async fn put(&self, key: &ArtifactKey, component: &Component) {
+        let Some(blob_storage) = &self.blob_storage else {
+            return;
+        };
+
+        let bytes = match component.serialize() {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!("Failed to serialize compiled artifact {key}: {err}");
+                return;
+            }
+        };
+
+        if let Err(err) = blob_storage
+            .put_raw(
+                "compiled_artifact_cache",
+                "put",
+                BlobStorageNamespace::CompiledArtifacts,
+                &key.path(),
+                &bytes,
+            )
+            .await
+        {
+            warn!("Failed to upload compiled artifact {key}: {err}");
+        }
+    }
+}
+
+// Lock of an artifact being compiled, removed from the map when the last user drops it, no
+// matter whether the compilation succeeded, failed or got cancelled
+struct InFlight<'a> {
+    in_flight: &'a InFlightLocks,
+    key: &'a ArtifactKey,
+    lock: Arc<Mutex<()>>,
+}
+
+impl<'a> InFlight<'a> {
+    fn acquire(in_flight: &'a InFlightLocks, key: &'a ArtifactKey) -> Self {
+        let lock = in_flight
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        Self {
+            in_flight,
+            key,
+            lock,
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        // Only the map and this user are holding the lock if nobody else is waiting for it
+        let unused = Arc::strong_count(&self.lock) == 2
+            && in_flight
+                .get(self.key)
+                .is_some_and(|lock| Arc::ptr_eq(lock, &self.lock));
+        if unused {
+            in_flight.remove(self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::CompiledArtifactCache;
+    use crate::model::*;
+    use wasmtime::{Config, Engine};
+
+    fn engine() -> Engine {
+        Engine::new(&Config::default()).unwrap()
+    }
+
+    #[test]
+    fn artifact_key_is_stable() {
+        let engine = engine();
+        let key1 = ArtifactKey::new(&engine, b"binary");
+        let key2 = ArtifactKey::new(&engine, b"binary");
+        let other = ArtifactKey::new(&engine, b"other binary");
+
+        assert_eq!(key1, key2);
+        assert_eq!(key1.path(), key2.path());
+        assert_eq!(key1.engine_hash, other.engine_hash);
+        assert_ne!(key1.binary_hash, other.binary_hash);
+    }
+
+    #[test]
+    async fn failed_compilation_releases_the_in_flight_lock() {
+        let engine = engine();
+        let cache = CompiledArtifactCache::new(None);
+        let key = ArtifactKey::new(&engine, b"binary");
+
+        let result = cache
+            .get_or_compile(&key, &engine, || async {
+                Err(CompilationError::CompileFailure("failed".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    async fn cancelled_compilation_releases_the_in_flight_lock() {
+        let engine = engine();
+        let cache = CompiledArtifactCache::new(None);
+        let key = ArtifactKey::new(&engine, b"binary");
+
+        let result = tokio::time::timeout(
+            std::time::Duration::from_millis(10),
+            cache.get_or_compile(&key, &engine, || std::future::pending()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+    }
+}
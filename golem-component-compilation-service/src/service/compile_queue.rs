@@ -0,0 +1,141 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+// Queue of compilation requests shared by the compile workers. Requests with higher priority
+// are taken first, requests with the same priority in the order they were enqueued.
+pub struct CompilationQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+impl CompilationQueue {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                heap: BinaryHeap::new(),
+                next_sequence: 0,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn push(&self, request: CompilationRequest) {
+        {
+            let mut state = self.state.lock().unwrap();
+            let sequence = state.next_sequence;
+            state.next_sequence += 1;
+            state.heap.push(QueuedRequest { sequence, request });
+        }
+        crate::metrics::increment_queue_length();
+        self.notify.notify_one();
+    }
+
+    pub async fn pop(&self) -> CompilationRequest {
+        loop {
+            if let Some(request) = self.try_pop() {
+                crate::metrics::decrement_queue_length();
+                return request;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn try_pop(&self) -> Option<CompilationRequest> {
+        let mut state = self.state.lock().unwrap();
+        let request = state.heap.pop().map(|queued| queued.request);
+        if request.is_some() && !state.heap.is_empty() {
+            // Wake up another worker for the remaining requests
+            self.notify.notify_one();
+        }
+        request
+    }
+}
+
+struct QueueState {
+    heap: BinaryHeap<QueuedRequest>,
+    next_sequence: u64,
+}
+
+struct QueuedRequest {
+    sequence: u64,
+    request: CompilationRequest,
+}
+
+impl PartialEq for QueuedRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedRequest {}
+
+impl PartialOrd for QueuedRequest {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedRequest {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.request
+            .priority
+            .cmp(&other.request.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::CompilationQueue;
+    use crate::model::*;
+    use golem_common::model::component::{ComponentId, ComponentRevision};
+    use golem_common::model::environment::EnvironmentId;
+    use uuid::Uuid;
+
+    fn request(revision: u64, priority: CompilationPriority) -> CompilationRequest {
+        CompilationRequest {
+            component: ComponentIdAndRevision {
+                id: ComponentId(Uuid::nil()),
+                revision: ComponentRevision::new(revision).unwrap(),
+            },
+            environment_id: EnvironmentId(Uuid::nil()),
+            priority,
+            sender: None,
+        }
+    }
+
+    #[test]
+    async fn higher_priority_requests_are_taken_first() {
+        let queue = CompilationQueue::new();
+        queue.push(request(0, CompilationPriority::Normal));
+        queue.push(request(1, CompilationPriority::Low));
+        queue.push(request(2, CompilationPriority::High));
+        queue.push(request(3, CompilationPriority::Normal));
+
+        let mut revisions = Vec::new();
+        for _ in 0..4 {
+            revisions.push(queue.pop().await.component.revision.get());
+        }
+
+        assert_eq!(revisions, vec![2, 0, 3, 1]);
+    }
+}
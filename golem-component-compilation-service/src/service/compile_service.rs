@@ -15,16 +15,21 @@
 use super::*;
 use crate::config::{CompileWorkerConfig, RegistryServiceConfig, StaticRegistryServiceConfig};
 use crate::model::*;
+use crate::service::artifact_cache::CompiledArtifactCache;
+use crate::service::compile_queue::CompilationQueue;
+use crate::service::compile_status::CompilationStatusTracker;
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::environment::EnvironmentId;
 use golem_service_base::service::compiled_component::CompiledComponentService;
+use golem_service_base::storage::blob::BlobStorage;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use wasmtime::Engine;
 
 #[derive(Clone)]
 pub struct ComponentCompilationService {
-    queue: mpsc::Sender<CompilationRequest>,
+    queue: Arc<CompilationQueue>,
+    status: Arc<CompilationStatusTracker>,
 }
 
 impl ComponentCompilationService {
@@ -33,8 +38,13 @@ impl ComponentCompilationService {
         registry_service: RegistryServiceConfig,
         engine: Engine,
         compiled_component_service: Arc<dyn CompiledComponentService>,
+        artifact_storage: Option<Arc<dyn BlobStorage>>,
     ) -> Self {
-        let (compile_tx, compile_rx) = mpsc::channel(100);
+        let queue = Arc::new(CompilationQueue::new());
+        let status = Arc::new(CompilationStatusTracker::new(
+            compile_worker.status_retention,
+        ));
+        let artifact_cache = Arc::new(CompiledArtifactCache::new(artifact_storage));
         let (upload_tx, upload_rx) = mpsc::channel(100);
 
         CompileWorker::start(
@@ -42,14 +52,20 @@ impl ComponentCompilationService {
             compile_worker,
             engine.clone(),
             compiled_component_service.clone(),
+            artifact_cache,
+            status.clone(),
             upload_tx,
-            compile_rx,
+            queue.clone(),
         )
         .await;
 
-        UploadWorker::start(compiled_component_service.clone(), upload_rx);
+        UploadWorker::start(
+            compiled_component_service.clone(),
+            status.clone(),
+            upload_rx,
+        );
 
-        Self { queue: compile_tx }
+        Self { queue, status }
     }
 
     pub async fn enqueue_compilation(
@@ -57,23 +73,50 @@ impl ComponentCompilationService {
         component_id: ComponentId,
         component_revision: ComponentRevision,
         environment_id: EnvironmentId,
+        priority: CompilationPriority,
         sender: Option<StaticRegistryServiceConfig>,
     ) -> Result<(), CompilationError> {
+        let component = ComponentIdAndRevision {
+            id: component_id,
+            revision: component_revision,
+        };
+
+        if !self.status.try_enqueue(environment_id, component) {
+            tracing::info!(
+                component_id = component_id.to_string(),
+                component_revision = component_revision.to_string(),
+                "Compilation of component is already pending",
+            );
+            return Ok(());
+        }
+
         tracing::info!(
             component_id = component_id.to_string(),
             component_revision = component_revision.to_string(),
+            priority = ?priority,
             "Enqueueing compilation for component",
         );
-        let request = CompilationRequest {
-            component: ComponentIdAndRevision {
-                id: component_id,
-                revision: component_revision,
-            },
+        self.queue.push(CompilationRequest {
+            component,
             environment_id,
+            priority,
             sender,
-        };
-        self.queue.send(request).await?;
-        crate::metrics::increment_queue_length();
+        });
         Ok(())
     }
+
+    pub fn get_compilation_status(
+        &self,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+        environment_id: EnvironmentId,
+    ) -> Option<CompilationStatus> {
+        self.status.get(
+            environment_id,
+            ComponentIdAndRevision {
+                id: component_id,
+                revision: component_revision,
+            },
+        )
+    }
 }
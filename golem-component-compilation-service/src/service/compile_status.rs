@@ -0,0 +1,108 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::*;
+use golem_common::model::environment::EnvironmentId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Keeps track of the compilation status of component revisions. Finished compilations are
+// forgotten after the configured retention period.
+pub struct CompilationStatusTracker {
+    statuses: Mutex<HashMap<(EnvironmentId, ComponentIdAndRevision), TrackedStatus>>,
+    retention: Duration,
+}
+
+struct TrackedStatus {
+    status: CompilationStatus,
+    updated_at: Instant,
+}
+
+impl CompilationStatusTracker {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            statuses: Mutex::new(HashMap::new()),
+            retention,
+        }
+    }
+
+    pub fn get(
+        &self,
+        environment_id: EnvironmentId,
+        component: ComponentIdAndRevision,
+    ) -> Option<CompilationStatus> {
+        self.statuses
+            .lock()
+            .unwrap()
+            .get(&(environment_id, component))
+            .map(|tracked| tracked.status.clone())
+    }
+
+    pub fn set(
+        &self,
+        environment_id: EnvironmentId,
+        component: ComponentIdAndRevision,
+        status: CompilationStatus,
+    ) {
+        let mut statuses = self.statuses.lock().unwrap();
+        self.insert(&mut statuses, environment_id, component, status);
+    }
+
+    /// Marks the component revision as queued, unless it is already queued or being compiled.
+    /// Returns whether the status was changed.
+    pub fn try_enqueue(
+        &self,
+        environment_id: EnvironmentId,
+        component: ComponentIdAndRevision,
+    ) -> bool {
+        let mut statuses = self.statuses.lock().unwrap();
+        let pending = matches!(
+            statuses.get(&(environment_id, component)),
+            Some(TrackedStatus {
+                status: CompilationStatus::Queued | CompilationStatus::Compiling,
+                ..
+            })
+        );
+        if !pending {
+            self.insert(
+                &mut statuses,
+                environment_id,
+                component,
+                CompilationStatus::Queued,
+            );
+        }
+        !pending
+    }
+
+    fn insert(
+        &self,
+        statuses: &mut HashMap<(EnvironmentId, ComponentIdAndRevision), TrackedStatus>,
+        environment_id: EnvironmentId,
+        component: ComponentIdAndRevision,
+        status: CompilationStatus,
+    ) {
+        let now = Instant::now();
+        statuses.retain(|_, tracked| {
+            !tracked.status.is_finished() || now.duration_since(tracked.updated_at) < self.retention
+        });
+        statuses.insert(
+            (environment_id, component),
+            TrackedStatus {
+                status,
+                updated_at: now,
+            },
+        );
+    }
+}
@@ -15,6 +15,9 @@
 use crate::config::{CompileWorkerConfig, StaticRegistryServiceConfig};
 use crate::metrics::record_compilation_time;
 use crate::model::*;
+use crate::service::artifact_cache::CompiledArtifactCache;
+use crate::service::compile_queue::CompilationQueue;
+use crate::service::compile_status::CompilationStatusTracker;
 use golem_common::model::environment::EnvironmentId;
 use golem_service_base::clients::registry::GrpcRegistryServiceConfig;
 use golem_service_base::clients::registry::{GrpcRegistryService, RegistryService};
@@ -27,7 +30,7 @@ use tracing::{info, warn, Instrument};
 use wasmtime::component::Component;
 use wasmtime::Engine;
 
-// Single worker that compiles WASM components.
+// Pool of workers compiling WASM components, taking requests from a shared priority queue.
 #[derive(Clone)]
 pub struct CompileWorker {
     // Config
@@ -36,24 +39,32 @@ pub struct CompileWorker {
     // Resources
     engine: Engine,
     compiled_component_service: Arc<dyn CompiledComponentService>,
+    artifact_cache: Arc<CompiledArtifactCache>,
+    status: Arc<CompilationStatusTracker>,
     client: Arc<Mutex<Option<GrpcRegistryService>>>,
 }
 
 impl CompileWorker {
+    #[allow(clippy::too_many_arguments)]
     pub async fn start(
         component_service_config: Option<StaticRegistryServiceConfig>,
         config: CompileWorkerConfig,
 
         engine: Engine,
         compiled_component_service: Arc<dyn CompiledComponentService>,
+        artifact_cache: Arc<CompiledArtifactCache>,
+        status: Arc<CompilationStatusTracker>,
 
         sender: mpsc::Sender<CompiledComponent>,
-        mut recv: mpsc::Receiver<CompilationRequest>,
+        queue: Arc<CompilationQueue>,
     ) {
+        let concurrency = config.concurrency.max(1);
         let worker = Self {
             config,
             engine,
             compiled_component_service,
+            artifact_cache,
+            status,
             client: Arc::new(Mutex::new(None)),
         };
 
@@ -61,48 +72,83 @@ impl CompileWorker {
             worker.set_client(component_service_config).await;
         }
 
-        tokio::spawn(
-            async move {
-                while let Some(request) = recv.recv().await {
-                    crate::metrics::decrement_queue_length();
+        for _ in 0..concurrency {
+            let worker = worker.clone();
+            let sender = sender.clone();
+            let queue = queue.clone();
 
-                    if let Some(sender) = request.sender {
-                        if worker.client.lock().await.is_none() {
-                            worker.set_client(sender).await;
+            tokio::spawn(
+                async move {
+                    loop {
+                        let request = queue.pop().await;
+                        if !worker.process_request(request, &sender).await {
+                            break;
                         }
                     }
-
-                    let result = worker
-                        .compile_component(request.component, request.environment_id)
-                        .await;
-                    match result {
-                        Err(error) => {
-                            warn!(
-                                component_id = request.component.id.to_string(),
-                                component_revision = request.component.revision.to_string(),
-                                error = error.to_string(),
-                                "Failed to compile component"
-                            );
-                        }
-                        Ok(component) => {
-                            let send_result = sender
-                                .send(CompiledComponent {
-                                    component_and_revision: request.component,
-                                    component,
-                                    environment_id: request.environment_id,
-                                })
-                                .await;
-
-                            if send_result.is_err() {
-                                tracing::error!("Failed to send compiled component");
-                                break;
-                            }
-                        }
-                    };
                 }
+                .in_current_span(),
+            );
+        }
+    }
+
+    // Returns false if the compiled components can no longer be passed to the upload worker
+    async fn process_request(
+        &self,
+        request: CompilationRequest,
+        sender: &mpsc::Sender<CompiledComponent>,
+    ) -> bool {
+        if let Some(registry_service) = request.sender {
+            if self.client.lock().await.is_none() {
+                self.set_client(registry_service).await;
             }
-            .in_current_span(),
+        }
+
+        self.status.set(
+            request.environment_id,
+            request.component,
+            CompilationStatus::Compiling,
         );
+
+        let result = self
+            .compile_component(request.component, request.environment_id)
+            .await;
+        match result {
+            Err(error) => {
+                warn!(
+                    component_id = request.component.id.to_string(),
+                    component_revision = request.component.revision.to_string(),
+                    error = error.to_string(),
+                    "Failed to compile component"
+                );
+                self.status.set(
+                    request.environment_id,
+                    request.component,
+                    CompilationStatus::Failed(error.to_string()),
+                );
+                true
+            }
+            Ok(component) => {
+                let send_result = sender
+                    .send(CompiledComponent {
+                        component_and_revision: request.component,
+                        component,
+                        environment_id: request.environment_id,
+                    })
+                    .await;
+
+                if send_result.is_err() {
+                    tracing::error!("Failed to send compiled component");
+                    self.status.set(
+                        request.environment_id,
+                        request.component,
+                        CompilationStatus::Failed("Failed to send compiled component".to_string()),
+                    );
+                    false
+                } else {
+                    true
+                }
+            }
+        }
     }
 
     async fn set_client(&self, config: StaticRegistryServiceConfig) {
@@ -150,44 +196,61 @@ impl CompileWorker {
             }
         };
 
+        // The client is cloned, so the lock is not held while downloading and compiling
+        let client = self.client.lock().await.clone();
+
         // TODO: we should download directly from blob store here.
-        if let Some(client) = &*self.client.lock().await {
+        if let Some(client) = client {
             let bytes = client
                 .download_component(component_with_revision.id, component_with_revision.revision)
                 .await
                 .map_err(|e| CompilationError::ComponentDownloadFailed(e.to_string()))?;
 
-            let start = Instant::now();
-            let component = spawn_blocking({
-                move || {
-                    Component::from_binary(&engine, &bytes).map_err(|e| {
-                        CompilationError::CompileFailure(format!(
-                            "Failed to compile component {component_with_revision:?}: {e}"
-                        ))
-                    })
-                }
-            })
-            .instrument(tracing::Span::current())
-            .await
-            .map_err(|join_err| CompilationError::Unexpected(join_err.to_string()))??;
-            let end = Instant::now();
-
-            let compilation_time = end.duration_since(start);
+            let key = ArtifactKey::new(&engine, &bytes);
 
-            record_compilation_time(compilation_time);
-
-            tracing::info!(
-                component_id = component_with_revision.id.to_string(),
-                component_revision = component_with_revision.revision.to_string(),
-                compilation_time_ms = compilation_time.as_millis(),
-                "Compiled component"
-            );
-
-            Ok(component)
+            self.artifact_cache
+                .get_or_compile(&key, &engine, || {
+                    Self::compile_binary(engine.clone(), bytes, component_with_revision)
+                })
+                .await
         } else {
             Err(CompilationError::Unexpected(
                 "Component service is not configured".to_string(),
             ))
         }
     }
+
+    async fn compile_binary(
+        engine: Engine,
+        bytes: Vec<u8>,
+        component_with_revision: ComponentIdAndRevision,
+    ) -> Result<Component, CompilationError> {
+        let start = Instant::now();
+        let component = spawn_blocking({
+            move || {
+                Component::from_binary(&engine, &bytes).map_err(|e| {
+                    CompilationError::CompileFailure(format!(
+                        "Failed to compile component {component_with_revision:?}: {e}"
+                    ))
+                })
+            }
+        })
+        .instrument(tracing::Span::current())
+        .await
+        .map_err(|join_err| CompilationError::Unexpected(join_err.to_string()))??;
+        let end = Instant::now();
+
+        let compilation_time = end.duration_since(start);
+
+        record_compilation_time(compilation_time);
+
+        tracing::info!(
+            component_id = component_with_revision.id.to_string(),
+            component_revision = component_with_revision.revision.to_string(),
+            compilation_time_ms = compilation_time.as_millis(),
+            "Compiled component"
+        );
+
+        Ok(component)
+    }
 }
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod artifact_cache;
+mod compile_queue;
 pub mod compile_service;
+mod compile_status;
 mod compile_worker;
 mod upload_worker;
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::model::*;
+use crate::service::compile_status::CompilationStatusTracker;
 use golem_service_base::service::compiled_component::CompiledComponentService;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -22,15 +23,18 @@ use tracing::Instrument;
 #[derive(Clone)]
 pub struct UploadWorker {
     compiled_component_service: Arc<dyn CompiledComponentService>,
+    status: Arc<CompilationStatusTracker>,
 }
 
 impl UploadWorker {
     pub fn start(
         compiled_component_service: Arc<dyn CompiledComponentService>,
+        status: Arc<CompilationStatusTracker>,
         mut recv: mpsc::Receiver<CompiledComponent>,
     ) {
         let worker = Self {
             compiled_component_service,
+            status,
         };
 
         tokio::spawn(
@@ -71,12 +75,22 @@ impl UploadWorker {
                 error = err.to_string(),
                 "Failed to upload compiled component"
             );
+            self.status.set(
+                environment_id,
+                component_and_revision,
+                CompilationStatus::Failed(err.to_string()),
+            );
         } else {
             tracing::info!(
                 component_id = component_and_revision.id.to_string(),
                 component_revision = component_and_revision.revision.to_string(),
                 "Successfully uploaded compiled component"
             );
+            self.status.set(
+                environment_id,
+                component_and_revision,
+                CompilationStatus::Ready,
+            );
         }
     }
 }
@@ -20,9 +20,10 @@ use golem_service_base::clients::registry::GrpcRegistryServiceConfig;
 use golem_service_base::config::BlobStorageConfig;
 use golem_service_base::service::compiled_component::CompiledComponentServiceConfig;
 use golem_worker_executor::services::golem_config::{
-    ActiveWorkersConfig, AgentTypesServiceConfig, ComponentCacheConfig, EngineConfig, GolemConfig,
-    GrpcApiConfig, IndexedStorageConfig, KeyValueStorageConfig, Limits, MemoryConfig, OplogConfig,
-    RdbmsConfig, ResourceLimitsConfig, SchedulerConfig, ShardManagerServiceConfig,
+    ActiveWorkersConfig, AgentQuotasConfig, AgentTypesServiceConfig, ComponentCacheConfig,
+    EngineConfig, GolemConfig, GrpcApiConfig, IndexedStorageConfig, KeyValueStorageConfig, Limits,
+    MemoryConfig, OplogConfig, OplogSearchIndexConfig, RdbmsConfig, ResourceLimitsConfig,
+    RetentionConfig, SchedulerConfig, ShardManagerServiceConfig,
     ShardManagerServiceSingleShardConfig, SuspendConfig, WorkerServiceGrpcConfig,
 };
 use serde::{Deserialize, Serialize};
@@ -69,9 +70,15 @@ impl DebugConfig {
             retry: self.retry,
             compiled_component_service: self.compiled_component_service,
             oplog: self.oplog,
+            // the debugged agents' oplogs are not indexed again
+            oplog_search_index: OplogSearchIndexConfig::default(),
             suspend: self.suspend,
             active_workers: self.active_workers,
             scheduler: self.scheduler,
+            // the debugging service never deletes agents
+            retention: RetentionConfig::default(),
+            // quotas are enforced when the agents run live, not while debugging them
+            agent_quotas: AgentQuotasConfig::default(),
             public_worker_api: self.public_worker_api,
             memory: self.memory,
             rdbms: self.rdbms,
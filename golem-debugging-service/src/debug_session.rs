@@ -381,6 +381,9 @@ fn get_oplog_entry_from_public_oplog_entry(
         PublicOplogEntry::RolledBackRemoteTransaction(_) => {
             Err("Cannot override an oplog with a rolled back remote transaction".to_string())?
         }
+        PublicOplogEntry::Snapshot(_) => {
+            Err("Cannot override an oplog with a snapshot".to_string())?
+        }
         PublicOplogEntry::SuccessfulUpdate(successful_update_params) => {
             let new_active_plugins: HashSet<PluginPriority> = successful_update_params
                 .new_active_plugins
@@ -453,6 +456,11 @@ fn get_oplog_entry_from_public_oplog_entry(
                 plugin_priority: deactivate_plugin_params.plugin.plugin_priority,
             })
         }
+        PublicOplogEntry::ChangeLabels(change_labels_params) => Ok(OplogEntry::ChangeLabels {
+            timestamp: change_labels_params.timestamp,
+            set: change_labels_params.set,
+            removed: change_labels_params.removed,
+        }),
         PublicOplogEntry::Revert(revert_params) => Ok(OplogEntry::Revert {
             timestamp: revert_params.timestamp,
             dropped_region: revert_params.dropped_region,
@@ -33,7 +33,9 @@ use async_trait::async_trait;
 use golem_service_base::clients::registry::RegistryService;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_worker_executor::durable_host::DurableWorkerCtx;
-use golem_worker_executor::preview2::{golem_agent, golem_api_1_x, golem_durability};
+use golem_worker_executor::preview2::{
+    golem_agent, golem_api_1_4, golem_api_1_x, golem_durability,
+};
 use golem_worker_executor::services::active_workers::ActiveWorkers;
 use golem_worker_executor::services::agent_types::AgentTypesService;
 use golem_worker_executor::services::blob_store::BlobStoreService;
@@ -329,11 +331,13 @@ fn get_durable_ctx(ctx: &mut DebugContext) -> &mut DurableWorkerCtx<DebugContext
 pub fn create_debug_wasmtime_linker(engine: &Engine) -> anyhow::Result<Linker<DebugContext>> {
     let mut linker = create_linker(engine, get_durable_ctx)?;
     golem_api_1_x::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
+    golem_api_1_4::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     golem_api_1_x::oplog::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     golem_api_1_x::context::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     golem_durability::durability::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     golem_agent::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     golem_wasm::golem_rpc_0_2_x::types::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
+    golem_wasm::golem_rpc_0_2_2::types::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
     Ok(linker)
 }
 
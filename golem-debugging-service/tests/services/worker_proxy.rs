@@ -10,7 +10,7 @@ use golem_common::model::account::AccountId;
 use golem_common::model::component::ComponentRevision;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::worker::RevertWorkerTarget;
-use golem_common::model::{IdempotencyKey, OwnedWorkerId, PromiseId, WorkerId};
+use golem_common::model::{IdempotencyKey, OwnedWorkerId, PromiseId, PromiseOutcome, WorkerId};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::model::auth::{AuthCtx, UserAuthCtx};
 use golem_wasm::{ValueAndType, WitValue};
@@ -276,7 +276,7 @@ impl WorkerProxy for TestWorkerProxy {
     async fn complete_promise(
         &self,
         _promise_id: PromiseId,
-        _data: Vec<u8>,
+        _outcome: PromiseOutcome,
         _caller_account_id: AccountId,
     ) -> Result<bool, WorkerProxyError> {
         unimplemented!()
@@ -0,0 +1 @@
+ALTER TABLE security_scheme_revisions ADD COLUMN issuer_url TEXT;
@@ -0,0 +1,33 @@
+-- api-key security schemes are stored as security scheme revisions with mode 'api-key',
+-- their OpenID Connect columns (provider_type, client_id, client_secret, redirect_url, scopes) are left empty
+
+CREATE TABLE security_scheme_api_keys
+(
+    api_key_id         UUID      NOT NULL,
+    security_scheme_id UUID      NOT NULL,
+    name               TEXT      NOT NULL,
+    -- hex encoded blake3 hash of the secret, the secret itself is never stored
+    secret_hash        TEXT      NOT NULL,
+    -- string containing a json array
+    scopes             TEXT      NOT NULL,
+    expires_at         TIMESTAMP,
+
+    created_at         TIMESTAMP NOT NULL,
+    created_by         UUID      NOT NULL,
+    revoked_at         TIMESTAMP,
+
+    CONSTRAINT security_scheme_api_keys_pk
+        PRIMARY KEY (api_key_id),
+    CONSTRAINT security_scheme_api_keys_security_schemes_fk
+        FOREIGN KEY (security_scheme_id) REFERENCES security_schemes
+);
+
+CREATE UNIQUE INDEX security_scheme_api_keys_secret_hash_uk
+    ON security_scheme_api_keys (secret_hash);
+
+CREATE UNIQUE INDEX security_scheme_api_keys_name_uk
+    ON security_scheme_api_keys (security_scheme_id, name)
+    WHERE revoked_at IS NULL;
+
+CREATE INDEX security_scheme_api_keys_security_scheme_idx
+    ON security_scheme_api_keys (security_scheme_id);
@@ -0,0 +1,2 @@
+ALTER TABLE security_scheme_revisions ADD COLUMN mode TEXT NOT NULL DEFAULT 'authorization-code';
+ALTER TABLE security_scheme_revisions ADD COLUMN audience TEXT;
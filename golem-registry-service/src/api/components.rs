@@ -17,6 +17,7 @@ use crate::services::auth::AuthService;
 use crate::services::component::{ComponentService, ComponentWriteService};
 use futures::TryStreamExt;
 use golem_common::model::Page;
+use golem_common::model::component::ComponentCompilationStatus;
 use golem_common::model::component::ComponentId;
 use golem_common::model::component::ComponentRevision;
 use golem_common::model::component::ComponentUpdate;
@@ -115,6 +116,52 @@ impl ComponentsApi {
         Ok(Json(component))
     }
 
+    /// Import a component bundle into the environment
+    ///
+    /// The request body is encoded as multipart/form-data containing a component bundle created by the CLI.
+    /// The bundle's pre-compiled component is stored directly, without running the compilation service.
+    #[oai(
+        path = "/envs/:environment_id/component-bundles",
+        method = "post",
+        operation_id = "import_component_bundle",
+        tag = ApiTags::Environment
+    )]
+    async fn import_component_bundle(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        payload: ImportComponentBundleRequest,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ComponentDto>> {
+        let record = recorded_http_api_request!(
+            "import_component_bundle",
+            environment_id = environment_id.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .import_component_bundle_internal(environment_id.0, payload, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn import_component_bundle_internal(
+        &self,
+        environment_id: EnvironmentId,
+        payload: ImportComponentBundleRequest,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ComponentDto>> {
+        let component: ComponentDto = self
+            .component_write_service
+            .import_bundle(environment_id, payload.bundle.into_file(), &auth)
+            .await?
+            .into();
+
+        Ok(Json(component))
+    }
+
     /// Get all components in the environment
     #[oai(
         path = "/envs/:environment_id/components",
@@ -430,6 +477,51 @@ impl ComponentsApi {
         Ok(Binary(body))
     }
 
+    /// Get the compilation status of a specific revision of a component
+    ///
+    /// Reports whether the revision is queued for compilation to native code, being compiled, ready or failed to compile.
+    /// The status of compilations finished a long time ago is no longer tracked and reported as unknown.
+    #[oai(
+        path = "/components/:component_id/revisions/:revision/compilation-status",
+        method = "get",
+        operation_id = "get_component_compilation_status"
+    )]
+    async fn get_component_compilation_status(
+        &self,
+        component_id: Path<ComponentId>,
+        revision: Path<ComponentRevision>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ComponentCompilationStatus>> {
+        let record = recorded_http_api_request!(
+            "get_component_compilation_status",
+            component_id = component_id.0.to_string(),
+            revision = revision.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_component_compilation_status_internal(component_id.0, revision.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_component_compilation_status_internal(
+        &self,
+        component_id: ComponentId,
+        revision: ComponentRevision,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ComponentCompilationStatus>> {
+        let status = self
+            .component_service
+            .get_compilation_status(component_id, revision, &auth)
+            .await?;
+
+        Ok(Json(status))
+    }
+
     /// Update a component
     ///
     /// The request body is encoded as multipart/form-data containing metadata and the WASM binary.
@@ -537,6 +629,12 @@ struct CreateComponentRequest {
     files: Option<TempFileUpload>,
 }
 
+#[derive(Multipart)]
+#[oai(rename_all = "camelCase")]
+struct ImportComponentBundleRequest {
+    bundle: TempFileUpload,
+}
+
 #[derive(Multipart)]
 #[oai(rename_all = "camelCase")]
 struct UpdateComponentRequest {
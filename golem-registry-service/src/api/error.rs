@@ -263,6 +263,7 @@ impl From<ComponentError> for ApiError {
             | ComponentError::InvalidOplogProcessorPlugin
             | ComponentError::InvalidPluginScope { .. }
             | ComponentError::MalformedComponentArchive { .. }
+            | ComponentError::InvalidComponentBundle { .. }
             | ComponentError::PluginInstallationNotFound { .. }
             | ComponentError::EnvironmentPluginNotFound(_)
             | ComponentError::ComponentTransformerPluginFailed { .. } => {
@@ -550,16 +551,20 @@ impl From<SecuritySchemeError> for ApiError {
         match value {
             SecuritySchemeError::ParentEnvironmentNotFound(_)
             | SecuritySchemeError::SecuritySchemeNotFound(_)
-            | SecuritySchemeError::SecuritySchemeForNameNotFound(_) => {
+            | SecuritySchemeError::SecuritySchemeForNameNotFound(_)
+            | SecuritySchemeError::ApiKeyNotFound(_) => {
                 Self::NotFound(Json(ErrorBody { error, cause: None }))
             }
 
-            SecuritySchemeError::InvalidRedirectUrl => Self::BadRequest(Json(ErrorsBody {
+            SecuritySchemeError::InvalidRedirectUrl
+            | SecuritySchemeError::InvalidIssuerUrl(_)
+            | SecuritySchemeError::InvalidApiKey(_) => Self::BadRequest(Json(ErrorsBody {
                 errors: vec![error],
                 cause: None,
             })),
 
             SecuritySchemeError::SecuritySchemeWithNameAlreadyExists(_)
+            | SecuritySchemeError::ApiKeyWithNameAlreadyExists(_)
             | SecuritySchemeError::ConcurrentUpdateAttempt => {
                 Self::Conflict(Json(ErrorBody { error, cause: None }))
             }
@@ -584,6 +589,11 @@ impl From<HttpApiDeploymentError> for ApiError {
                 Self::NotFound(Json(ErrorBody { error, cause: None }))
             }
 
+            HttpApiDeploymentError::InvalidRateLimit(_, _) => Self::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+                cause: None,
+            })),
+
             HttpApiDeploymentError::DomainNotRegistered(_)
             | HttpApiDeploymentError::HttpApiDeploymentForDomainAlreadyExists(_)
             | HttpApiDeploymentError::ConcurrentUpdate => {
@@ -18,8 +18,9 @@ use crate::services::security_scheme::SecuritySchemeService;
 use golem_common::model::Page;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::{
-    SecuritySchemeCreation, SecuritySchemeDto, SecuritySchemeId, SecuritySchemeRevision,
-    SecuritySchemeUpdate,
+    ApiKey, ApiKeyCreation, ApiKeyId, ApiKeySecuritySchemeCreation, ApiKeySecuritySchemeDto,
+    ApiKeyWithSecret, SecuritySchemeCreation, SecuritySchemeDto, SecuritySchemeId,
+    SecuritySchemeRevision, SecuritySchemeUpdate,
 };
 use golem_common::recorded_http_api_request;
 use golem_service_base::api_tags::ApiTags;
@@ -253,4 +254,285 @@ impl SecuritySchemesApi {
             .await?;
         Ok(Json(security_scheme.into()))
     }
+
+    /// Create a new api-key security scheme
+    #[oai(
+        path = "/envs/:environment_id/api-key-security-schemes",
+        method = "post",
+        operation_id = "create_api_key_security_scheme",
+        tag = ApiTags::Environment
+    )]
+    async fn create_api_key_security_scheme(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        payload: Json<ApiKeySecuritySchemeCreation>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let record = recorded_http_api_request!(
+            "create_api_key_security_scheme",
+            environment_id = environment_id.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .create_api_key_security_scheme_internal(environment_id.0, payload.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn create_api_key_security_scheme_internal(
+        &self,
+        environment_id: EnvironmentId,
+        payload: ApiKeySecuritySchemeCreation,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let result = self
+            .security_scheme_service
+            .create_api_key_scheme(environment_id, payload, &auth)
+            .await?;
+
+        Ok(Json(result.into()))
+    }
+
+    /// Get all api-key security schemes of the environment
+    #[oai(
+        path = "/envs/:environment_id/api-key-security-schemes",
+        method = "get",
+        operation_id = "get_environment_api_key_security_schemes",
+        tag = ApiTags::Environment
+    )]
+    async fn get_environment_api_key_security_schemes(
+        &self,
+        environment_id: Path<EnvironmentId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<Page<ApiKeySecuritySchemeDto>>> {
+        let record = recorded_http_api_request!(
+            "get_environment_api_key_security_schemes",
+            environment_id = environment_id.0.to_string(),
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_environment_api_key_security_schemes_internal(environment_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_environment_api_key_security_schemes_internal(
+        &self,
+        environment_id: EnvironmentId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<Page<ApiKeySecuritySchemeDto>>> {
+        let result = self
+            .security_scheme_service
+            .get_api_key_schemes_in_environment(environment_id, &auth)
+            .await?;
+
+        Ok(Json(Page {
+            values: result.into_iter().map(|ss| ss.into()).collect(),
+        }))
+    }
+
+    /// Get api-key security scheme
+    #[oai(
+        path = "/api-key-security-schemes/:security_scheme_id",
+        method = "get",
+        operation_id = "get_api_key_security_scheme"
+    )]
+    pub async fn get_api_key_security_scheme(
+        &self,
+        security_scheme_id: Path<SecuritySchemeId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let record = recorded_http_api_request!(
+            "get_api_key_security_scheme",
+            security_scheme_id = security_scheme_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_api_key_security_scheme_internal(security_scheme_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_api_key_security_scheme_internal(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let security_scheme = self
+            .security_scheme_service
+            .get_api_key_scheme(security_scheme_id, &auth)
+            .await?;
+        Ok(Json(security_scheme.into()))
+    }
+
+    /// Delete api-key security scheme
+    #[oai(
+        path = "/api-key-security-schemes/:security_scheme_id",
+        method = "delete",
+        operation_id = "delete_api_key_security_scheme"
+    )]
+    pub async fn delete_api_key_security_scheme(
+        &self,
+        security_scheme_id: Path<SecuritySchemeId>,
+        current_revision: Query<SecuritySchemeRevision>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let record = recorded_http_api_request!(
+            "delete_api_key_security_scheme",
+            security_scheme_id = security_scheme_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .delete_api_key_security_scheme_internal(security_scheme_id.0, current_revision.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn delete_api_key_security_scheme_internal(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        current_revision: SecuritySchemeRevision,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ApiKeySecuritySchemeDto>> {
+        let security_scheme = self
+            .security_scheme_service
+            .delete_api_key_scheme(security_scheme_id, current_revision, &auth)
+            .await?;
+        Ok(Json(security_scheme.into()))
+    }
+
+    /// Create a new api key for an api-key security scheme. The secret is only returned by this call.
+    #[oai(
+        path = "/api-key-security-schemes/:security_scheme_id/api-keys",
+        method = "post",
+        operation_id = "create_security_scheme_api_key"
+    )]
+    pub async fn create_security_scheme_api_key(
+        &self,
+        security_scheme_id: Path<SecuritySchemeId>,
+        payload: Json<ApiKeyCreation>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ApiKeyWithSecret>> {
+        let record = recorded_http_api_request!(
+            "create_security_scheme_api_key",
+            security_scheme_id = security_scheme_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .create_security_scheme_api_key_internal(security_scheme_id.0, payload.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn create_security_scheme_api_key_internal(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        payload: ApiKeyCreation,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ApiKeyWithSecret>> {
+        let result = self
+            .security_scheme_service
+            .create_api_key(security_scheme_id, payload, &auth)
+            .await?;
+        Ok(Json(result))
+    }
+
+    /// Get the active api keys of a security scheme
+    #[oai(
+        path = "/api-key-security-schemes/:security_scheme_id/api-keys",
+        method = "get",
+        operation_id = "get_security_scheme_api_keys"
+    )]
+    pub async fn get_security_scheme_api_keys(
+        &self,
+        security_scheme_id: Path<SecuritySchemeId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<Page<ApiKey>>> {
+        let record = recorded_http_api_request!(
+            "get_security_scheme_api_keys",
+            security_scheme_id = security_scheme_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_security_scheme_api_keys_internal(security_scheme_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_security_scheme_api_keys_internal(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<Page<ApiKey>>> {
+        let result = self
+            .security_scheme_service
+            .get_api_keys(security_scheme_id, &auth)
+            .await?;
+        Ok(Json(Page { values: result }))
+    }
+
+    /// Revoke an api key of a security scheme
+    #[oai(
+        path = "/api-key-security-schemes/:security_scheme_id/api-keys/:api_key_id",
+        method = "delete",
+        operation_id = "revoke_security_scheme_api_key"
+    )]
+    pub async fn revoke_security_scheme_api_key(
+        &self,
+        security_scheme_id: Path<SecuritySchemeId>,
+        api_key_id: Path<ApiKeyId>,
+        token: GolemSecurityScheme,
+    ) -> ApiResult<Json<ApiKey>> {
+        let record = recorded_http_api_request!(
+            "revoke_security_scheme_api_key",
+            security_scheme_id = security_scheme_id.0.to_string(),
+            api_key_id = api_key_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .revoke_security_scheme_api_key_internal(security_scheme_id.0, api_key_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn revoke_security_scheme_api_key_internal(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        api_key_id: ApiKeyId,
+        auth: AuthCtx,
+    ) -> ApiResult<Json<ApiKey>> {
+        let result = self
+            .security_scheme_service
+            .revoke_api_key(security_scheme_id, api_key_id, &auth)
+            .await?;
+        Ok(Json(result))
+    }
 }
@@ -65,6 +65,7 @@ use golem_service_base::db;
 use golem_service_base::db::postgres::PostgresPool;
 use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use golem_service_base::service::compiled_component_files::CompiledComponentFilesService;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use golem_service_base::service::plugin_wasm_files::PluginWasmFilesService;
 use golem_service_base::storage::blob::BlobStorage;
@@ -129,6 +130,8 @@ impl Services {
         let initial_component_files =
             Arc::new(InitialComponentFilesService::new(blob_storage.clone()));
         let plugin_wasm_files = Arc::new(PluginWasmFilesService::new(blob_storage.clone()));
+        let compiled_component_files =
+            Arc::new(CompiledComponentFilesService::new(blob_storage.clone()));
         let component_object_store = Arc::new(ComponentObjectStore::new(blob_storage));
 
         let component_compilation_service =
@@ -199,6 +202,7 @@ impl Services {
         let component_service = Arc::new(ComponentService::new(
             repos.component_repo.clone(),
             component_object_store.clone(),
+            component_compilation_service.clone(),
             environment_service.clone(),
             deployment_service.clone(),
         ));
@@ -225,6 +229,7 @@ impl Services {
             repos.component_repo,
             component_object_store,
             component_compilation_service.clone(),
+            compiled_component_files,
             initial_component_files,
             plugin_wasm_files.clone(),
             account_usage_service.clone(),
@@ -267,6 +272,7 @@ impl Services {
         let security_scheme_service = Arc::new(SecuritySchemeService::new(
             repos.security_scheme_repo.clone(),
             environment_service.clone(),
+            config.security_schemes.clone(),
         ));
 
         let http_api_deployment_service = Arc::new(HttpApiDeploymentService::new(
@@ -285,6 +291,7 @@ impl Services {
 
         let deployed_routes_service = Arc::new(DeployedRoutesService::new(
             repos.deployment_repo.clone(),
+            repos.security_scheme_repo.clone(),
             http_api_deployment_service.clone(),
         ));
 
@@ -45,6 +45,7 @@ pub struct RegistryServiceConfig {
     pub cors_origin_regex: String,
     pub domain_provisioner: DomainProvisionerConfig,
     pub component_compilation: ComponentCompilationConfig,
+    pub security_schemes: SecuritySchemesConfig,
     pub initial_accounts: HashMap<String, PrecreatedAccount>,
     pub initial_plans: HashMap<String, PrecreatedPlan>,
 }
@@ -98,6 +99,13 @@ impl SafeDisplay for RegistryServiceConfig {
             self.component_compilation.to_safe_string_indented()
         );
 
+        let _ = writeln!(&mut result, "security schemes:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.security_schemes.to_safe_string_indented()
+        );
+
         result
     }
 }
@@ -163,6 +171,7 @@ impl Default for RegistryServiceConfig {
             component_compilation: ComponentCompilationConfig::default(),
             blob_storage: BlobStorageConfig::default(),
             domain_provisioner: DomainProvisionerConfig::default(),
+            security_schemes: SecuritySchemesConfig::default(),
             initial_accounts,
             initial_plans,
         }
@@ -304,6 +313,25 @@ impl Default for GitHubOAuth2Config {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct SecuritySchemesConfig {
+    /// Hosts of custom OpenID Connect issuers which may be used with plain http instead of https,
+    /// for example identity providers running next to a local deployment
+    pub http_issuer_hosts: Vec<String>,
+}
+
+impl SafeDisplay for SecuritySchemesConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "HTTP issuer hosts: {}",
+            self.http_issuer_hosts.join(", ")
+        );
+        result
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ComponentTransformerPluginCallerConfig {
     pub retries: RetryConfig,
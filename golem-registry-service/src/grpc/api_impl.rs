@@ -37,18 +37,20 @@ use golem_api_grpc::proto::golem::registry::v1::{
     GetComponentMetadataRequest, GetComponentMetadataResponse, GetComponentMetadataSuccessResponse,
     GetDeployedComponentMetadataRequest, GetDeployedComponentMetadataResponse,
     GetDeployedComponentMetadataSuccessResponse, GetResourceLimitsRequest,
-    GetResourceLimitsResponse, GetResourceLimitsSuccessResponse, RegistryServiceError,
-    ResolveComponentRequest, ResolveComponentResponse, ResolveComponentSuccessResponse,
-    ResolveLatestAgentTypeByNamesRequest, ResolveLatestAgentTypeByNamesResponse,
-    ResolveLatestAgentTypeByNamesSuccessResponse, UpdateWorkerConnectionLimitRequest,
-    UpdateWorkerConnectionLimitResponse, UpdateWorkerLimitRequest, UpdateWorkerLimitResponse,
-    authenticate_token_response, batch_update_fuel_usage_response, download_component_response,
+    GetResourceLimitsResponse, GetResourceLimitsSuccessResponse, GetSecuritySchemeApiKeysRequest,
+    GetSecuritySchemeApiKeysResponse, GetSecuritySchemeApiKeysSuccessResponse,
+    RegistryServiceError, ResolveComponentRequest, ResolveComponentResponse,
+    ResolveComponentSuccessResponse, ResolveLatestAgentTypeByNamesRequest,
+    ResolveLatestAgentTypeByNamesResponse, ResolveLatestAgentTypeByNamesSuccessResponse,
+    UpdateWorkerConnectionLimitRequest, UpdateWorkerConnectionLimitResponse,
+    UpdateWorkerLimitRequest, UpdateWorkerLimitResponse, authenticate_token_response,
+    batch_update_fuel_usage_response, download_component_response,
     get_active_routes_for_domain_response, get_agent_type_response, get_all_agent_types_response,
     get_all_deployed_component_revisions_response, get_auth_details_for_environment_response,
     get_component_metadata_response, get_deployed_component_metadata_response,
-    get_resource_limits_response, registry_service_error, resolve_component_response,
-    resolve_latest_agent_type_by_names_response, update_worker_connection_limit_response,
-    update_worker_limit_response,
+    get_resource_limits_response, get_security_scheme_api_keys_response, registry_service_error,
+    resolve_component_response, resolve_latest_agent_type_by_names_response,
+    update_worker_connection_limit_response, update_worker_limit_response,
 };
 use golem_common::model::account::AccountId;
 use golem_common::model::agent::AgentTypeName;
@@ -57,10 +59,11 @@ use golem_common::model::auth::TokenSecret;
 use golem_common::model::component::{ComponentDto, ComponentId, ComponentRevision};
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+use golem_common::model::security_scheme::SecuritySchemeId;
 use golem_common::recorded_grpc_api_request;
 use golem_service_base::grpc::{
     proto_account_id_string, proto_application_id_string, proto_component_id_string,
-    proto_environment_id_string,
+    proto_environment_id_string, proto_security_scheme_id_string,
 };
 use golem_service_base::model::auth::{AuthCtx, AuthDetailsForEnvironment};
 use std::collections::HashMap;
@@ -394,6 +397,25 @@ impl RegistryServiceGrpcApi {
         })
     }
 
+    async fn get_security_scheme_api_keys_internal(
+        &self,
+        request: GetSecuritySchemeApiKeysRequest,
+    ) -> Result<GetSecuritySchemeApiKeysSuccessResponse, GrpcApiError> {
+        let security_scheme_id: SecuritySchemeId = request
+            .security_scheme_id
+            .ok_or("missing security_scheme_id field")?
+            .try_into()?;
+
+        let api_keys = self
+            .deployed_routes_service
+            .get_security_scheme_api_keys(security_scheme_id)
+            .await?;
+
+        Ok(GetSecuritySchemeApiKeysSuccessResponse {
+            api_keys: api_keys.into_iter().map(|key| key.into()).collect(),
+        })
+    }
+
     async fn resolve_latest_agent_type_by_names_internal(
         &self,
         request: ResolveLatestAgentTypeByNamesRequest,
@@ -822,6 +844,31 @@ impl golem_api_grpc::proto::golem::registry::v1::registry_service_server::Regist
             result: Some(response),
         }))
     }
+
+    async fn get_security_scheme_api_keys(
+        &self,
+        request: Request<GetSecuritySchemeApiKeysRequest>,
+    ) -> Result<Response<GetSecuritySchemeApiKeysResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_security_scheme_api_keys",
+            security_scheme_id = proto_security_scheme_id_string(&request.security_scheme_id)
+        );
+
+        let response = match self
+            .get_security_scheme_api_keys_internal(request)
+            .instrument(record.span.clone())
+            .await
+            .apply(|r| record.result(r))
+        {
+            Ok(result) => get_security_scheme_api_keys_response::Result::Success(result),
+            Err(error) => get_security_scheme_api_keys_response::Result::Error(error.into()),
+        };
+
+        Ok(Response::new(GetSecuritySchemeApiKeysResponse {
+            result: Some(response),
+        }))
+    }
 }
 
 fn internal_error(error: &str) -> RegistryServiceError {
@@ -20,12 +20,16 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::{SecuritySchemeId, SecuritySchemeName};
 use golem_service_base::custom_api::{
-    CorsOptions, PathSegment, RequestBodySchema, RouteBehaviour, RouteId, SecuritySchemeDetails,
+    CorsOptions, PathSegment, RateLimit, RequestBodySchema, RouteBehaviour, RouteId,
+    SecuritySchemeDetails,
 };
 use std::collections::HashMap;
 
 #[derive(BinaryCodec)]
-#[desert(evolution())]
+#[desert(evolution(
+    FieldAdded("rate_limits", Vec::new()),
+    FieldAdded("required_scopes", Vec::new())
+))]
 // Flattened version of golem_service_base::custom_api::CompiledRoute with late-bound references still unresolved
 pub struct UnboundCompiledRoute {
     pub domain: Domain,
@@ -36,6 +40,8 @@ pub struct UnboundCompiledRoute {
     pub behaviour: RouteBehaviour,
     pub security_scheme: Option<SecuritySchemeName>,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RateLimit>,
+    pub required_scopes: Vec<String>,
 }
 
 pub struct BoundCompiledRoute {
@@ -59,7 +65,9 @@ pub struct MaybeDisabledCompiledRoute {
     pub behavior: RouteBehaviour,
     pub security_scheme_missing: bool,
     pub security_scheme: Option<SecuritySchemeId>,
+    pub required_scopes: Vec<String>,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RateLimit>,
 }
 
 // impl golem_service_base::custom_api::openapi::HttpApiRoute for MaybeDisabledCompiledRoute {
@@ -14,9 +14,10 @@
 
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::{
-    Provider, SecuritySchemeDto, SecuritySchemeId, SecuritySchemeName, SecuritySchemeRevision,
+    ApiKeySecuritySchemeDto, Provider, SecuritySchemeDto, SecuritySchemeId, SecuritySchemeMode,
+    SecuritySchemeName, SecuritySchemeRevision,
 };
-use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
+use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl, Scope};
 
 #[derive(Debug, Clone)]
 pub struct SecurityScheme {
@@ -25,10 +26,15 @@ pub struct SecurityScheme {
     pub name: SecuritySchemeName,
     pub environment_id: EnvironmentId,
     pub provider_type: Provider,
+    /// Only set for custom providers
+    pub issuer_url: Option<IssuerUrl>,
     pub client_id: ClientId,
     pub client_secret: ClientSecret,
     pub redirect_url: RedirectUrl,
     pub scopes: Vec<Scope>,
+    pub mode: SecuritySchemeMode,
+    /// Audience expected in bearer tokens if it differs from the client id
+    pub audience: Option<Audience>,
 }
 
 impl PartialEq for SecurityScheme {
@@ -38,10 +44,13 @@ impl PartialEq for SecurityScheme {
             && self.name == other.name
             && self.environment_id == other.environment_id
             && self.provider_type == other.provider_type
+            && self.issuer_url == other.issuer_url
             && self.client_id == other.client_id
             && self.client_secret.secret() == other.client_secret.secret()
             && self.redirect_url == other.redirect_url
             && self.scopes == other.scopes
+            && self.mode == other.mode
+            && self.audience == other.audience
     }
 }
 
@@ -56,6 +65,45 @@ impl From<SecurityScheme> for SecuritySchemeDto {
             client_id: value.client_id.into(),
             redirect_url: (*value.redirect_url).clone(),
             scopes: value.scopes.into_iter().map(|s| (*s).clone()).collect(),
+            issuer_url: value.issuer_url.map(|url| (*url).clone()),
+            mode: value.mode,
+            audience: value.audience.map(|audience| (*audience).clone()),
+        }
+    }
+}
+
+/// Security scheme accepting the api keys issued for it, it has no OpenID Connect settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKeySecurityScheme {
+    pub id: SecuritySchemeId,
+    pub revision: SecuritySchemeRevision,
+    pub name: SecuritySchemeName,
+    pub environment_id: EnvironmentId,
+}
+
+impl From<ApiKeySecurityScheme> for ApiKeySecuritySchemeDto {
+    fn from(value: ApiKeySecurityScheme) -> Self {
+        Self {
+            id: value.id,
+            revision: value.revision,
+            name: value.name,
+            environment_id: value.environment_id,
+        }
+    }
+}
+
+/// Security scheme of either kind, as stored in the repo
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnySecurityScheme {
+    OpenIdConnect(SecurityScheme),
+    ApiKey(ApiKeySecurityScheme),
+}
+
+impl AnySecurityScheme {
+    pub fn environment_id(&self) -> EnvironmentId {
+        match self {
+            Self::OpenIdConnect(scheme) => scheme.environment_id,
+            Self::ApiKey(scheme) => scheme.environment_id,
         }
     }
 }
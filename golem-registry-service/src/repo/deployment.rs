@@ -724,10 +724,13 @@ impl DeploymentRepo for DbDeploymentRepo<PostgresPool> {
                         s.security_scheme_id,
                         s.name AS security_scheme_name,
                         sr.provider_type AS security_scheme_provider_type,
+                        sr.issuer_url AS security_scheme_issuer_url,
                         sr.client_id AS security_scheme_client_id,
                         sr.client_secret AS security_scheme_client_secret,
                         sr.redirect_url AS security_scheme_redirect_url,
                         sr.scopes AS security_scheme_scopes,
+                        sr.mode AS security_scheme_mode,
+                        sr.audience AS security_scheme_audience,
                         r.compiled_route
 
                     FROM deployment_compiled_routes r
@@ -792,10 +795,13 @@ impl DeploymentRepo for DbDeploymentRepo<PostgresPool> {
                         s.security_scheme_id,
                         s.name AS security_scheme_name,
                         sr.provider_type AS security_scheme_provider_type,
+                        sr.issuer_url AS security_scheme_issuer_url,
                         sr.client_id AS security_scheme_client_id,
                         sr.client_secret AS security_scheme_client_secret,
                         sr.redirect_url AS security_scheme_redirect_url,
                         sr.scopes AS security_scheme_scopes,
+                        sr.mode AS security_scheme_mode,
+                        sr.audience AS security_scheme_audience,
                         r.compiled_route
 
                     FROM deployment_compiled_routes r
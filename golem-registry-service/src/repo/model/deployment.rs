@@ -18,6 +18,7 @@ use crate::repo::model::audit::RevisionAuditFields;
 use crate::repo::model::component::ComponentRevisionIdentityRecord;
 use crate::repo::model::hash::SqlBlake3Hash;
 use crate::repo::model::http_api_deployment::HttpApiDeploymentRevisionIdentityRecord;
+use crate::repo::model::security_scheme::API_KEY_SECURITY_SCHEME_MODE;
 use anyhow::anyhow;
 use golem_common::error_forwarding;
 use golem_common::model::account::AccountId;
@@ -29,8 +30,12 @@ use golem_common::model::deployment::{
 use golem_common::model::diff::{self, Hash, Hashable};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::http_api_deployment::HttpApiDeployment;
-use golem_common::model::security_scheme::{Provider, SecuritySchemeId, SecuritySchemeName};
-use golem_service_base::custom_api::SecuritySchemeDetails;
+use golem_common::model::security_scheme::{
+    Provider, SecuritySchemeId, SecuritySchemeMode, SecuritySchemeName,
+};
+use golem_service_base::custom_api::{
+    ApiKeySecuritySchemeDetails, OidcSecuritySchemeDetails, SecuritySchemeDetails,
+};
 use golem_service_base::repo::RepoError;
 use golem_service_base::repo::blob::Blob;
 use sqlx::FromRow;
@@ -425,10 +430,13 @@ pub struct DeploymentCompiledRouteWithSecuritySchemeRecord {
     pub security_scheme_id: Option<Uuid>,
     pub security_scheme_name: Option<String>,
     pub security_scheme_provider_type: Option<String>,
+    pub security_scheme_issuer_url: Option<String>,
     pub security_scheme_client_id: Option<String>,
     pub security_scheme_client_secret: Option<String>,
     pub security_scheme_redirect_url: Option<String>,
     pub security_scheme_scopes: Option<String>,
+    pub security_scheme_mode: Option<String>,
+    pub security_scheme_audience: Option<String>,
 
     pub compiled_route: Blob<UnboundCompiledRoute>,
 }
@@ -439,49 +447,85 @@ impl TryFrom<DeploymentCompiledRouteWithSecuritySchemeRecord> for BoundCompiledR
     fn try_from(
         value: DeploymentCompiledRouteWithSecuritySchemeRecord,
     ) -> Result<Self, Self::Error> {
-        use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
-
-        let security_scheme = match (
-            value.security_scheme_id,
-            value.security_scheme_name,
-            value.security_scheme_provider_type,
-            value.security_scheme_client_id,
-            value.security_scheme_client_secret,
-            value.security_scheme_redirect_url,
-            value.security_scheme_scopes,
-        ) {
-            (
-                Some(security_scheme_id),
-                Some(security_scheme_name),
-                Some(provider_type),
-                Some(client_id),
-                Some(client_secret),
-                Some(redirect_url),
-                Some(scopes),
-            ) => {
-                let id = SecuritySchemeId(security_scheme_id);
-                let name = SecuritySchemeName(security_scheme_name);
-                let scopes: Vec<Scope> = serde_json::from_str(&scopes)
-                    .map_err(|e| anyhow::Error::from(e).context("Failed parsing scopes"))?;
-                let redirect_url: RedirectUrl = serde_json::from_str(&redirect_url)
-                    .map_err(|e| anyhow::Error::from(e).context("Failed parsing redirect_url"))?;
-                let provider_type = Provider::from_str(&provider_type)
-                    .map_err(|e| anyhow!("Failed parsing provider type: {e}"))?;
-                let client_id = ClientId::new(client_id);
-                let client_secret = ClientSecret::new(client_secret);
-
-                Some(SecuritySchemeDetails {
-                    id,
-                    name,
-                    scopes,
-                    redirect_url,
-                    provider_type,
-                    client_id,
-                    client_secret,
-                })
-            }
-            _ => None,
-        };
+        use openidconnect::{Audience, ClientId, ClientSecret, RedirectUrl, Scope};
+
+        let security_scheme =
+            if value.security_scheme_mode.as_deref() == Some(API_KEY_SECURITY_SCHEME_MODE) {
+                value
+                    .security_scheme_id
+                    .zip(value.security_scheme_name)
+                    .map(|(id, name)| {
+                        SecuritySchemeDetails::ApiKey(ApiKeySecuritySchemeDetails {
+                            id: SecuritySchemeId(id),
+                            name: SecuritySchemeName(name),
+                        })
+                    })
+            } else {
+                match (
+                    value.security_scheme_id,
+                    value.security_scheme_name,
+                    value.security_scheme_provider_type,
+                    value.security_scheme_client_id,
+                    value.security_scheme_client_secret,
+                    value.security_scheme_redirect_url,
+                    value.security_scheme_scopes,
+                ) {
+                    (
+                        Some(security_scheme_id),
+                        Some(security_scheme_name),
+                        Some(provider_type),
+                        Some(client_id),
+                        Some(client_secret),
+                        Some(redirect_url),
+                        Some(scopes),
+                    ) => {
+                        let id = SecuritySchemeId(security_scheme_id);
+                        let name = SecuritySchemeName(security_scheme_name);
+                        let scopes: Vec<Scope> = serde_json::from_str(&scopes)
+                            .map_err(|e| anyhow::Error::from(e).context("Failed parsing scopes"))?;
+                        let redirect_url: RedirectUrl = serde_json::from_str(&redirect_url)
+                            .map_err(|e| {
+                                anyhow::Error::from(e).context("Failed parsing redirect_url")
+                            })?;
+                        let provider_type = Provider::from_str(&provider_type)
+                            .map_err(|e| anyhow!("Failed parsing provider type: {e}"))?;
+                        let issuer_url = provider_type
+                            .resolve_issuer_url(value.security_scheme_issuer_url.as_deref())
+                            .map_err(|e| anyhow!("Failed resolving issuer url: {e}"))?;
+                        let mode = value
+                            .security_scheme_mode
+                            .as_deref()
+                            .map(SecuritySchemeMode::from_str)
+                            .transpose()
+                            .map_err(|e| anyhow!("Failed parsing mode: {e}"))?
+                            .unwrap_or_default();
+                        // bearer tokens are expected to be issued for the client unless configured otherwise
+                        let audience = Audience::new(
+                            value
+                                .security_scheme_audience
+                                .unwrap_or_else(|| client_id.clone()),
+                        );
+                        let client_id = ClientId::new(client_id);
+                        let client_secret = ClientSecret::new(client_secret);
+
+                        Some(SecuritySchemeDetails::OpenIdConnect(
+                            OidcSecuritySchemeDetails {
+                                id,
+                                name,
+                                scopes,
+                                redirect_url,
+                                provider_type,
+                                issuer_url,
+                                client_id,
+                                client_secret,
+                                mode,
+                                audience,
+                            },
+                        ))
+                    }
+                    _ => None,
+                }
+            };
 
         Ok(Self {
             account_id: AccountId(value.account_id),
@@ -26,8 +26,9 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::http_api_deployment::{
     HttpApiDeployment, HttpApiDeploymentAgentOptions, HttpApiDeploymentId,
-    HttpApiDeploymentRevision,
+    HttpApiDeploymentRevision, HttpRateLimit,
 };
+use golem_common::model::security_scheme::SecuritySchemeName;
 use golem_service_base::repo::RepoError;
 use golem_service_base::repo::blob::Blob;
 use sqlx::FromRow;
@@ -47,9 +48,48 @@ pub enum HttpApiDeploymentRepoError {
 error_forwarding!(HttpApiDeploymentRepoError, RepoError);
 
 #[derive(Debug, Clone, PartialEq, BinaryCodec)]
-#[desert(evolution())]
+#[desert(evolution(FieldAdded("rate_limits", BTreeMap::new())))]
 pub struct HttpApiDeploymentData {
-    pub agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+    // Security schemes keep the encoding of the original agent options, the rate limits added
+    // later are stored separately so existing revisions remain readable.
+    pub agents: BTreeMap<AgentTypeName, Option<SecuritySchemeName>>,
+    pub rate_limits: BTreeMap<AgentTypeName, Vec<HttpRateLimit>>,
+}
+
+impl HttpApiDeploymentData {
+    pub fn new(agents: BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>) -> Self {
+        let mut data = Self {
+            agents: BTreeMap::new(),
+            rate_limits: BTreeMap::new(),
+        };
+        for (agent_type, options) in agents {
+            if !options.rate_limits.is_empty() {
+                data.rate_limits
+                    .insert(agent_type.clone(), options.rate_limits);
+            }
+            data.agents.insert(agent_type, options.security_scheme);
+        }
+        data
+    }
+
+    pub fn agent_options(&self) -> BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions> {
+        self.agents
+            .iter()
+            .map(|(agent_type, security_scheme)| {
+                (
+                    agent_type.clone(),
+                    HttpApiDeploymentAgentOptions {
+                        security_scheme: security_scheme.clone(),
+                        rate_limits: self
+                            .rate_limits
+                            .get(agent_type)
+                            .cloned()
+                            .unwrap_or_default(),
+                    },
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, FromRow, PartialEq)]
@@ -98,7 +138,7 @@ impl HttpApiDeploymentRevisionRecord {
             revision_id: HttpApiDeploymentRevision::INITIAL.into(),
             hash: SqlBlake3Hash::empty(),
             audit: DeletableRevisionAuditFields::new(actor.0),
-            data: Blob::new(HttpApiDeploymentData { agents }),
+            data: Blob::new(HttpApiDeploymentData::new(agents)),
         };
         value.update_hash();
         value
@@ -110,9 +150,7 @@ impl HttpApiDeploymentRevisionRecord {
             revision_id: value.revision.into(),
             hash: SqlBlake3Hash::empty(),
             audit,
-            data: Blob::new(HttpApiDeploymentData {
-                agents: value.agents,
-            }),
+            data: Blob::new(HttpApiDeploymentData::new(value.agents)),
         };
         value.update_hash();
         value
@@ -128,9 +166,7 @@ impl HttpApiDeploymentRevisionRecord {
             revision_id: current_revision_id,
             hash: SqlBlake3Hash::empty(),
             audit: DeletableRevisionAuditFields::deletion(created_by),
-            data: Blob::new(HttpApiDeploymentData {
-                agents: BTreeMap::new(),
-            }),
+            data: Blob::new(HttpApiDeploymentData::new(BTreeMap::new())),
         }
     }
 
@@ -139,13 +175,14 @@ impl HttpApiDeploymentRevisionRecord {
             agents: self
                 .data
                 .value()
-                .agents
-                .iter()
+                .agent_options()
+                .into_iter()
                 .map(|(k, v)| {
                     (
-                        k.0.clone(),
+                        k.0,
                         diff::HttpApiDeploymentAgentOptions {
-                            security_scheme: v.security_scheme.as_ref().map(|v| v.0.clone()),
+                            security_scheme: v.security_scheme.map(|v| v.0),
+                            rate_limits: v.rate_limits,
                         },
                     )
                 })
@@ -183,7 +220,7 @@ impl TryFrom<HttpApiDeploymentExtRevisionRecord> for HttpApiDeployment {
             environment_id: EnvironmentId(value.environment_id),
             domain: Domain(value.domain),
             hash: value.revision.hash.into(),
-            agents: value.revision.data.into_value().agents,
+            agents: value.revision.data.value().agent_options(),
             created_at: value.entity_created_at.into(),
         })
     }
@@ -13,17 +13,19 @@
 // limitations under the License.
 
 use super::datetime::SqlDateTime;
-use crate::model::security_scheme::SecurityScheme;
+use crate::model::security_scheme::{AnySecurityScheme, ApiKeySecurityScheme, SecurityScheme};
 use crate::repo::model::audit::{AuditFields, DeletableRevisionAuditFields};
 use anyhow::anyhow;
 use golem_common::error_forwarding;
 use golem_common::model::account::AccountId;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::security_scheme::{
-    Provider, SecuritySchemeId, SecuritySchemeName, SecuritySchemeRevision,
+    ApiKey, ApiKeyId, Provider, SecuritySchemeId, SecuritySchemeMode, SecuritySchemeName,
+    SecuritySchemeRevision,
 };
+use golem_service_base::custom_api::SecuritySchemeApiKey;
 use golem_service_base::repo::RepoError;
-use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
+use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl, Scope};
 use sqlx::FromRow;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -34,12 +36,17 @@ pub enum SecuritySchemeRepoError {
     SecuritySchemeViolatesUniqueness,
     #[error("Concurrent modification")]
     ConcurrentModification,
+    #[error("There is an active API key with this name for the security scheme")]
+    ApiKeyViolatesUniqueness,
     #[error(transparent)]
     InternalError(#[from] anyhow::Error),
 }
 
 error_forwarding!(SecuritySchemeRepoError, RepoError);
 
+/// Mode stored for api-key security schemes. Their revisions leave the OpenID Connect columns empty.
+pub const API_KEY_SECURITY_SCHEME_MODE: &str = "api-key";
+
 #[derive(FromRow, Debug, Clone, PartialEq)]
 pub struct SecuritySchemeRecord {
     pub security_scheme_id: Uuid,
@@ -58,10 +65,13 @@ pub struct SecuritySchemeRevisionRecord {
     pub revision_id: i64,
 
     pub provider_type: String,
+    pub issuer_url: Option<String>,
     pub client_id: String,
     pub client_secret: String,
     pub redirect_url: String,
     pub scopes: String,
+    pub mode: String,
+    pub audience: Option<String>,
 
     #[sqlx(flatten)]
     pub audit: DeletableRevisionAuditFields,
@@ -71,10 +81,13 @@ impl SecuritySchemeRevisionRecord {
     pub fn creation(
         security_scheme_id: SecuritySchemeId,
         provider_type: Provider,
+        issuer_url: Option<&IssuerUrl>,
         client_id: String,
         client_secret: String,
         redirect_url: &RedirectUrl,
         scopes: &[Scope],
+        mode: SecuritySchemeMode,
+        audience: Option<&Audience>,
         actor: AccountId,
     ) -> Self {
         let redirect_url: String = serde_json::to_string(&redirect_url).unwrap();
@@ -84,14 +97,52 @@ impl SecuritySchemeRevisionRecord {
             security_scheme_id: security_scheme_id.0,
             revision_id: SecuritySchemeRevision::INITIAL.into(),
             provider_type: provider_type.to_string(),
+            issuer_url: issuer_url.map(|url| (**url).clone()),
             client_id,
             client_secret,
             redirect_url,
             scopes,
+            mode: mode.to_string(),
+            audience: audience.map(|audience| (**audience).clone()),
             audit: DeletableRevisionAuditFields::new(actor.0),
         }
     }
 
+    pub fn api_key_creation(security_scheme_id: SecuritySchemeId, actor: AccountId) -> Self {
+        Self::api_key_revision(
+            security_scheme_id,
+            SecuritySchemeRevision::INITIAL,
+            DeletableRevisionAuditFields::new(actor.0),
+        )
+    }
+
+    pub fn from_api_key_model(
+        value: ApiKeySecurityScheme,
+        audit: DeletableRevisionAuditFields,
+    ) -> Self {
+        Self::api_key_revision(value.id, value.revision, audit)
+    }
+
+    fn api_key_revision(
+        security_scheme_id: SecuritySchemeId,
+        revision: SecuritySchemeRevision,
+        audit: DeletableRevisionAuditFields,
+    ) -> Self {
+        Self {
+            security_scheme_id: security_scheme_id.0,
+            revision_id: revision.into(),
+            provider_type: String::new(),
+            issuer_url: None,
+            client_id: String::new(),
+            client_secret: String::new(),
+            redirect_url: String::new(),
+            scopes: "[]".to_string(),
+            mode: API_KEY_SECURITY_SCHEME_MODE.to_string(),
+            audience: None,
+            audit,
+        }
+    }
+
     pub fn from_model(value: SecurityScheme, audit: DeletableRevisionAuditFields) -> Self {
         let redirect_url: String = serde_json::to_string(&value.redirect_url).unwrap();
         let scopes: String = serde_json::to_string(&value.scopes).unwrap();
@@ -100,10 +151,13 @@ impl SecuritySchemeRevisionRecord {
             security_scheme_id: value.id.0,
             revision_id: value.revision.into(),
             provider_type: value.provider_type.to_string(),
+            issuer_url: value.issuer_url.map(|url| (*url).clone()),
             client_id: value.client_id.into(),
             client_secret: value.client_secret.secret().clone(),
             redirect_url,
             scopes,
+            mode: value.mode.to_string(),
+            audience: value.audience.map(|audience| (*audience).clone()),
             audit,
         }
     }
@@ -120,15 +174,61 @@ pub struct SecuritySchemeExtRevisionRecord {
     pub revision: SecuritySchemeRevisionRecord,
 }
 
+impl SecuritySchemeExtRevisionRecord {
+    pub fn is_api_key(&self) -> bool {
+        self.revision.mode == API_KEY_SECURITY_SCHEME_MODE
+    }
+}
+
+impl TryFrom<SecuritySchemeExtRevisionRecord> for AnySecurityScheme {
+    type Error = SecuritySchemeRepoError;
+    fn try_from(value: SecuritySchemeExtRevisionRecord) -> Result<Self, Self::Error> {
+        if value.is_api_key() {
+            Ok(Self::ApiKey(value.try_into()?))
+        } else {
+            Ok(Self::OpenIdConnect(value.try_into()?))
+        }
+    }
+}
+
+impl TryFrom<SecuritySchemeExtRevisionRecord> for ApiKeySecurityScheme {
+    type Error = SecuritySchemeRepoError;
+    fn try_from(value: SecuritySchemeExtRevisionRecord) -> Result<Self, Self::Error> {
+        if !value.is_api_key() {
+            return Err(anyhow!("Security scheme is not an api-key security scheme").into());
+        }
+
+        Ok(Self {
+            id: SecuritySchemeId(value.revision.security_scheme_id),
+            revision: value.revision.revision_id.try_into()?,
+            environment_id: EnvironmentId(value.environment_id),
+            name: SecuritySchemeName(value.name),
+        })
+    }
+}
+
 impl TryFrom<SecuritySchemeExtRevisionRecord> for SecurityScheme {
     type Error = SecuritySchemeRepoError;
     fn try_from(value: SecuritySchemeExtRevisionRecord) -> Result<Self, Self::Error> {
+        if value.is_api_key() {
+            return Err(anyhow!("Security scheme is not an OpenID Connect security scheme").into());
+        }
+
         let scopes: Vec<Scope> = serde_json::from_str(&value.revision.scopes)
             .map_err(|e| anyhow::Error::from(e).context("Failed parsing scopes"))?;
         let redirect_url: RedirectUrl = serde_json::from_str(&value.revision.redirect_url)
             .map_err(|e| anyhow::Error::from(e).context("Failed parsing redirect_url"))?;
         let provider_type = Provider::from_str(&value.revision.provider_type)
             .map_err(|e| anyhow!("Failed parsing provider type: {e}"))?;
+        let issuer_url = value
+            .revision
+            .issuer_url
+            .map(IssuerUrl::new)
+            .transpose()
+            .map_err(|e| anyhow::Error::from(e).context("Failed parsing issuer_url"))?;
+        let mode = SecuritySchemeMode::from_str(&value.revision.mode)
+            .map_err(|e| anyhow!("Failed parsing mode: {e}"))?;
+        let audience = value.revision.audience.map(Audience::new);
         let client_id = ClientId::new(value.revision.client_id);
         let client_secret = ClientSecret::new(value.revision.client_secret);
 
@@ -138,10 +238,90 @@ impl TryFrom<SecuritySchemeExtRevisionRecord> for SecurityScheme {
             environment_id: EnvironmentId(value.environment_id),
             name: SecuritySchemeName(value.name),
             provider_type,
+            issuer_url,
             client_id,
             client_secret,
             redirect_url,
             scopes,
+            mode,
+            audience,
+        })
+    }
+}
+
+#[derive(FromRow, Debug, Clone, PartialEq)]
+pub struct SecuritySchemeApiKeyRecord {
+    pub api_key_id: Uuid,
+    pub security_scheme_id: Uuid,
+    pub name: String,
+    pub secret_hash: String,
+    pub scopes: String,
+    pub expires_at: Option<SqlDateTime>,
+
+    pub created_at: SqlDateTime,
+    pub created_by: Uuid,
+    pub revoked_at: Option<SqlDateTime>,
+}
+
+impl SecuritySchemeApiKeyRecord {
+    pub fn creation(
+        api_key_id: ApiKeyId,
+        security_scheme_id: SecuritySchemeId,
+        name: String,
+        secret_hash: String,
+        scopes: &[Scope],
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        actor: AccountId,
+    ) -> Self {
+        let scopes: String = serde_json::to_string(&scopes).unwrap();
+
+        Self {
+            api_key_id: api_key_id.0,
+            security_scheme_id: security_scheme_id.0,
+            name,
+            secret_hash,
+            scopes,
+            expires_at: expires_at.map(SqlDateTime::new),
+            created_at: SqlDateTime::now(),
+            created_by: actor.0,
+            revoked_at: None,
+        }
+    }
+
+    fn parsed_scopes(&self) -> Result<Vec<Scope>, SecuritySchemeRepoError> {
+        let scopes = serde_json::from_str(&self.scopes)
+            .map_err(|e| anyhow::Error::from(e).context("Failed parsing scopes"))?;
+        Ok(scopes)
+    }
+}
+
+impl TryFrom<SecuritySchemeApiKeyRecord> for ApiKey {
+    type Error = SecuritySchemeRepoError;
+    fn try_from(value: SecuritySchemeApiKeyRecord) -> Result<Self, Self::Error> {
+        let scopes = value.parsed_scopes()?;
+
+        Ok(Self {
+            id: ApiKeyId(value.api_key_id),
+            security_scheme_id: SecuritySchemeId(value.security_scheme_id),
+            name: value.name,
+            scopes: scopes.into_iter().map(|scope| (*scope).clone()).collect(),
+            created_at: value.created_at.into(),
+            expires_at: value.expires_at.map(|expires_at| expires_at.into()),
+        })
+    }
+}
+
+impl TryFrom<SecuritySchemeApiKeyRecord> for SecuritySchemeApiKey {
+    type Error = SecuritySchemeRepoError;
+    fn try_from(value: SecuritySchemeApiKeyRecord) -> Result<Self, Self::Error> {
+        let scopes = value.parsed_scopes()?;
+
+        Ok(Self {
+            id: ApiKeyId(value.api_key_id),
+            name: value.name,
+            secret_hash: value.secret_hash,
+            scopes,
+            expires_at: value.expires_at.map(|expires_at| expires_at.into()),
         })
     }
 }
@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use super::model::security_scheme::{
-    SecuritySchemeExtRevisionRecord, SecuritySchemeRepoError, SecuritySchemeRevisionRecord,
+    SecuritySchemeApiKeyRecord, SecuritySchemeExtRevisionRecord, SecuritySchemeRepoError,
+    SecuritySchemeRevisionRecord,
 };
 use crate::repo::model::BindFields;
 pub use crate::repo::model::account::AccountRecord;
+use crate::repo::model::datetime::SqlDateTime;
 use crate::repo::model::security_scheme::SecuritySchemeRecord;
 use async_trait::async_trait;
 use conditional_trait_gen::trait_gen;
@@ -63,6 +65,23 @@ pub trait SecuritySchemeRepo: Send + Sync {
         environment_id: Uuid,
         name: &str,
     ) -> Result<Option<SecuritySchemeExtRevisionRecord>, SecuritySchemeRepoError>;
+
+    async fn create_api_key(
+        &self,
+        api_key: SecuritySchemeApiKeyRecord,
+    ) -> Result<SecuritySchemeApiKeyRecord, SecuritySchemeRepoError>;
+
+    /// Returns the api keys of the security scheme that were not revoked, including expired ones
+    async fn get_api_keys(
+        &self,
+        security_scheme_id: Uuid,
+    ) -> Result<Vec<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError>;
+
+    async fn revoke_api_key(
+        &self,
+        security_scheme_id: Uuid,
+        api_key_id: Uuid,
+    ) -> Result<Option<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError>;
 }
 
 pub struct LoggedSecuritySchemeRepo<Repo: SecuritySchemeRepo> {
@@ -146,6 +165,35 @@ impl<Repo: SecuritySchemeRepo> SecuritySchemeRepo for LoggedSecuritySchemeRepo<R
             .instrument(Self::span_environment_id(environment_id))
             .await
     }
+
+    async fn create_api_key(
+        &self,
+        api_key: SecuritySchemeApiKeyRecord,
+    ) -> Result<SecuritySchemeApiKeyRecord, SecuritySchemeRepoError> {
+        let span = Self::span_security_scheme_id(api_key.security_scheme_id);
+        self.repo.create_api_key(api_key).instrument(span).await
+    }
+
+    async fn get_api_keys(
+        &self,
+        security_scheme_id: Uuid,
+    ) -> Result<Vec<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError> {
+        self.repo
+            .get_api_keys(security_scheme_id)
+            .instrument(Self::span_security_scheme_id(security_scheme_id))
+            .await
+    }
+
+    async fn revoke_api_key(
+        &self,
+        security_scheme_id: Uuid,
+        api_key_id: Uuid,
+    ) -> Result<Option<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError> {
+        self.repo
+            .revoke_api_key(security_scheme_id, api_key_id)
+            .instrument(Self::span_security_scheme_id(security_scheme_id))
+            .await
+    }
 }
 
 pub struct DbSecuritySchemeRepo<DBP: Pool> {
@@ -169,6 +217,10 @@ impl<DBP: Pool> DbSecuritySchemeRepo<DBP> {
     fn with_ro(&self, api_name: &'static str) -> DBP::LabelledApi {
         self.db_pool.with_ro(METRICS_SVC_NAME, api_name)
     }
+
+    fn with_rw(&self, api_name: &'static str) -> DBP::LabelledApi {
+        self.db_pool.with_rw(METRICS_SVC_NAME, api_name)
+    }
 }
 
 #[trait_gen(PostgresPool -> PostgresPool, SqlitePool)]
@@ -181,17 +233,20 @@ impl DbSecuritySchemeRepo<PostgresPool> {
             .fetch_one_as(
                 sqlx::query_as(indoc! { r#"
                     INSERT INTO security_scheme_revisions
-                    (security_scheme_id, revision_id, provider_type, client_id, client_secret, redirect_url, scopes, created_at, created_by, deleted)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-                    RETURNING security_scheme_id, revision_id, provider_type, client_id, client_secret, redirect_url, scopes, created_at, created_by, deleted
+                    (security_scheme_id, revision_id, provider_type, issuer_url, client_id, client_secret, redirect_url, scopes, mode, audience, created_at, created_by, deleted)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                    RETURNING security_scheme_id, revision_id, provider_type, issuer_url, client_id, client_secret, redirect_url, scopes, mode, audience, created_at, created_by, deleted
                 "# })
                 .bind(revision.security_scheme_id)
                 .bind(revision.revision_id)
                 .bind(revision.provider_type)
+                .bind(revision.issuer_url)
                 .bind(revision.client_id)
                 .bind(revision.client_secret)
                 .bind(revision.redirect_url)
                 .bind(revision.scopes)
+                .bind(revision.mode)
+                .bind(revision.audience)
                 .bind_deletable_revision_audit(revision.audit),
             )
             .await
@@ -314,7 +369,7 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<PostgresPool> {
         let result: Option<SecuritySchemeExtRevisionRecord> = self.with_ro("get_by_id")
             .fetch_optional_as(
                 sqlx::query_as(indoc! {r#"
-                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.created_at, ssr.created_by, ssr.deleted
+                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.issuer_url, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.mode, ssr.audience, ssr.created_at, ssr.created_by, ssr.deleted
                     FROM security_schemes ss
                     JOIN security_scheme_revisions ssr ON ssr.security_scheme_id = ss.security_scheme_id AND ssr.revision_id = ss.current_revision_id
                     WHERE ss.security_scheme_id = $1 AND ss.deleted_at IS NULL
@@ -333,7 +388,7 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<PostgresPool> {
         let results: Vec<SecuritySchemeExtRevisionRecord> = self.with_ro("get_for_environment")
             .fetch_all_as(
                 sqlx::query_as(indoc! {r#"
-                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.created_at, ssr.created_by, ssr.deleted
+                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.issuer_url, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.mode, ssr.audience, ssr.created_at, ssr.created_by, ssr.deleted
                     FROM security_schemes ss
                     JOIN security_scheme_revisions ssr ON ssr.security_scheme_id = ss.security_scheme_id AND ssr.revision_id = ss.current_revision_id
                     WHERE ss.environment_id = $1 AND ss.deleted_at IS NULL
@@ -353,7 +408,7 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<PostgresPool> {
         let result: Option<SecuritySchemeExtRevisionRecord> = self.with_ro("get_for_environment_and_name")
             .fetch_optional_as(
                 sqlx::query_as(indoc! {r#"
-                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.created_at, ssr.created_by, ssr.deleted
+                    SELECT ss.environment_id, ss.name, ss.created_at AS entity_created_at, ssr.security_scheme_id, ssr.revision_id, ssr.provider_type, ssr.issuer_url, ssr.client_id, ssr.client_secret, ssr.redirect_url, ssr.scopes, ssr.mode, ssr.audience, ssr.created_at, ssr.created_by, ssr.deleted
                     FROM security_schemes ss
                     JOIN security_scheme_revisions ssr ON ssr.security_scheme_id = ss.security_scheme_id AND ssr.revision_id = ss.current_revision_id
                     WHERE ss.environment_id = $1 AND ss.name = $2 AND ss.deleted_at IS NULL
@@ -365,4 +420,71 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<PostgresPool> {
 
         Ok(result)
     }
+
+    async fn create_api_key(
+        &self,
+        api_key: SecuritySchemeApiKeyRecord,
+    ) -> Result<SecuritySchemeApiKeyRecord, SecuritySchemeRepoError> {
+        let result: SecuritySchemeApiKeyRecord = self.with_rw("create_api_key")
+            .fetch_one_as(
+                sqlx::query_as(indoc! {r#"
+                    INSERT INTO security_scheme_api_keys (api_key_id, security_scheme_id, name, secret_hash, scopes, expires_at, created_at, created_by, revoked_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NULL)
+                    RETURNING api_key_id, security_scheme_id, name, secret_hash, scopes, expires_at, created_at, created_by, revoked_at
+                "#})
+                    .bind(api_key.api_key_id)
+                    .bind(api_key.security_scheme_id)
+                    .bind(api_key.name)
+                    .bind(api_key.secret_hash)
+                    .bind(api_key.scopes)
+                    .bind(api_key.expires_at)
+                    .bind(api_key.created_at)
+                    .bind(api_key.created_by)
+            )
+            .await
+            .to_error_on_unique_violation(SecuritySchemeRepoError::ApiKeyViolatesUniqueness)?;
+
+        Ok(result)
+    }
+
+    async fn get_api_keys(
+        &self,
+        security_scheme_id: Uuid,
+    ) -> Result<Vec<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError> {
+        let results: Vec<SecuritySchemeApiKeyRecord> = self.with_ro("get_api_keys")
+            .fetch_all_as(
+                sqlx::query_as(indoc! {r#"
+                    SELECT api_key_id, security_scheme_id, name, secret_hash, scopes, expires_at, created_at, created_by, revoked_at
+                    FROM security_scheme_api_keys
+                    WHERE security_scheme_id = $1 AND revoked_at IS NULL
+                    ORDER BY name
+                "#})
+                    .bind(security_scheme_id),
+            )
+            .await?;
+
+        Ok(results)
+    }
+
+    async fn revoke_api_key(
+        &self,
+        security_scheme_id: Uuid,
+        api_key_id: Uuid,
+    ) -> Result<Option<SecuritySchemeApiKeyRecord>, SecuritySchemeRepoError> {
+        let result: Option<SecuritySchemeApiKeyRecord> = self.with_rw("revoke_api_key")
+            .fetch_optional_as(
+                sqlx::query_as(indoc! {r#"
+                    UPDATE security_scheme_api_keys
+                    SET revoked_at = $1
+                    WHERE security_scheme_id = $2 AND api_key_id = $3 AND revoked_at IS NULL
+                    RETURNING api_key_id, security_scheme_id, name, secret_hash, scopes, expires_at, created_at, created_by, revoked_at
+                "#})
+                    .bind(SqlDateTime::now())
+                    .bind(security_scheme_id)
+                    .bind(api_key_id)
+            )
+            .await?;
+
+        Ok(result)
+    }
 }
@@ -41,6 +41,8 @@ pub enum ComponentError {
     ComponentProcessingError(#[from] ComponentProcessingError),
     #[error("Malformed component archive: {message}")]
     MalformedComponentArchive { message: String },
+    #[error("Invalid component bundle: {message}")]
+    InvalidComponentBundle { message: String },
     #[error("Provided component file not found: {path} (key: {key})")]
     InitialComponentFileNotFound {
         path: ComponentFilePath,
@@ -105,6 +107,7 @@ impl SafeDisplay for ComponentError {
             Self::ComponentVersionAlreadyExists(_) => self.to_string(),
             Self::ComponentProcessingError(inner) => inner.to_safe_string(),
             Self::MalformedComponentArchive { .. } => self.to_string(),
+            Self::InvalidComponentBundle { .. } => self.to_string(),
             Self::InitialComponentFileNotFound { .. } => self.to_string(),
             Self::InvalidFilePath(_) => self.to_string(),
             Self::InvalidComponentName { .. } => self.to_string(),
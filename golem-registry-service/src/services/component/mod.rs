@@ -18,6 +18,7 @@ mod write;
 
 pub use self::error::ComponentError;
 pub use self::write::ComponentWriteService;
+use super::component_compilation::ComponentCompilationService;
 use super::component_object_store::ComponentObjectStore;
 use super::component_transformer_plugin_caller::{self};
 use super::deployment::DeploymentService;
@@ -28,7 +29,9 @@ use crate::services::deployment::DeploymentError;
 use crate::services::environment::EnvironmentError;
 use futures::stream::BoxStream;
 use golem_common::model::component::ComponentId;
-use golem_common::model::component::{ComponentName, ComponentRevision};
+use golem_common::model::component::{
+    ComponentCompilationStatus, ComponentName, ComponentRevision,
+};
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::environment::{Environment, EnvironmentId};
 use golem_service_base::model::auth::AuthCtx;
@@ -39,6 +42,7 @@ use tracing::info;
 pub struct ComponentService {
     component_repo: Arc<dyn ComponentRepo>,
     object_store: Arc<ComponentObjectStore>,
+    component_compilation: Arc<dyn ComponentCompilationService>,
     environment_service: Arc<EnvironmentService>,
     deployment_service: Arc<DeploymentService>,
 }
@@ -47,12 +51,14 @@ impl ComponentService {
     pub fn new(
         component_repo: Arc<dyn ComponentRepo>,
         object_store: Arc<ComponentObjectStore>,
+        component_compilation: Arc<dyn ComponentCompilationService>,
         environment_service: Arc<EnvironmentService>,
         deployment_service: Arc<DeploymentService>,
     ) -> Self {
         Self {
             component_repo,
             object_store,
+            component_compilation,
             environment_service,
             deployment_service,
         }
@@ -210,6 +216,26 @@ impl ComponentService {
         Ok(record.try_into_model(environment.application_id, environment.owner_account_id)?)
     }
 
+    pub async fn get_compilation_status(
+        &self,
+        component_id: ComponentId,
+        revision: ComponentRevision,
+        auth: &AuthCtx,
+    ) -> Result<ComponentCompilationStatus, ComponentError> {
+        info!(component_id = %component_id, "Get component compilation status");
+
+        let component = self
+            .get_component_revision(component_id, revision, false, auth)
+            .await?;
+
+        let status = self
+            .component_compilation
+            .get_compilation_status(component.environment_id, component_id, revision)
+            .await?;
+
+        Ok(status)
+    }
+
     pub async fn list_staged_components(
         &self,
         environment_id: EnvironmentId,
@@ -21,6 +21,10 @@ use async_zip::ZipEntry;
 use async_zip::tokio::read::seek::ZipFileReader;
 use futures::TryStreamExt;
 use golem_common::model::component::ComponentFilePath;
+use golem_common::model::component_bundle::{
+    COMPONENT_BUNDLE_COMPILED_PATH, COMPONENT_BUNDLE_FILES_PATH, COMPONENT_BUNDLE_MANIFEST_PATH,
+    COMPONENT_BUNDLE_WASM_PATH, ComponentBundleManifest,
+};
 use golem_common::model::component_constraint::FunctionConstraints;
 use golem_service_base::replayable_stream::ReplayableStream;
 use golem_wasm::analysis::AnalysedType;
@@ -147,6 +151,96 @@ fn initial_component_file_path_from_zip_entry(
     })
 }
 
+pub struct ComponentBundle {
+    pub manifest: ComponentBundleManifest,
+    pub wasm: Vec<u8>,
+    pub compiled: Vec<u8>,
+    pub files_archive: Option<NamedTempFile>,
+}
+
+pub async fn read_component_bundle(
+    bundle: NamedTempFile,
+) -> Result<ComponentBundle, ComponentError> {
+    fn malformed(message: impl Into<String>) -> ComponentError {
+        ComponentError::InvalidComponentBundle {
+            message: message.into(),
+        }
+    }
+
+    let reopened = tokio::task::spawn_blocking(move || bundle.reopen())
+        .await
+        .map_err(anyhow::Error::from)?
+        .map_err(anyhow::Error::from)?;
+
+    let mut buf_reader = BufReader::new(tokio::fs::File::from_std(reopened));
+
+    let mut zip_archive = ZipFileReader::with_tokio(&mut buf_reader)
+        .await
+        .map_err(|e| malformed(format!("Failed to open bundle archive: {e}")))?;
+
+    let mut manifest = None;
+    let mut wasm = None;
+    let mut compiled = None;
+    let mut files_archive = None;
+
+    for i in 0..zip_archive.file().entries().len() {
+        let mut entry_reader = zip_archive
+            .reader_with_entry(i)
+            .await
+            .map_err(|e| malformed(format!("Failed to read bundle entry: {e}")))?;
+
+        let file_name = entry_reader
+            .entry()
+            .filename()
+            .as_str()
+            .map_err(|e| malformed(format!("Failed to convert filename to string: {e}")))?
+            .to_string();
+
+        if ![
+            COMPONENT_BUNDLE_MANIFEST_PATH,
+            COMPONENT_BUNDLE_WASM_PATH,
+            COMPONENT_BUNDLE_COMPILED_PATH,
+            COMPONENT_BUNDLE_FILES_PATH,
+        ]
+        .contains(&file_name.as_str())
+        {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        entry_reader
+            .read_to_end_checked(&mut data)
+            .await
+            .map_err(|e| malformed(format!("Failed to read bundle entry {file_name}: {e}")))?;
+
+        match file_name.as_str() {
+            COMPONENT_BUNDLE_MANIFEST_PATH => {
+                let parsed: ComponentBundleManifest = serde_json::from_slice(&data)
+                    .map_err(|e| malformed(format!("Invalid bundle manifest: {e}")))?;
+                manifest = Some(parsed);
+            }
+            COMPONENT_BUNDLE_WASM_PATH => wasm = Some(data),
+            COMPONENT_BUNDLE_COMPILED_PATH => compiled = Some(data),
+            _ => {
+                let file = NamedTempFile::new().map_err(anyhow::Error::from)?;
+                tokio::fs::write(file.path(), &data)
+                    .await
+                    .map_err(anyhow::Error::from)?;
+                files_archive = Some(file);
+            }
+        }
+    }
+
+    Ok(ComponentBundle {
+        manifest: manifest
+            .ok_or_else(|| malformed(format!("Missing {COMPONENT_BUNDLE_MANIFEST_PATH}")))?,
+        wasm: wasm.ok_or_else(|| malformed(format!("Missing {COMPONENT_BUNDLE_WASM_PATH}")))?,
+        compiled: compiled
+            .ok_or_else(|| malformed(format!("Missing {COMPONENT_BUNDLE_COMPILED_PATH}")))?,
+        files_archive,
+    })
+}
+
 pub fn _find_component_metadata_conflicts(
     function_constraints: &FunctionConstraints,
     new_type_registry: &FunctionDictionary,
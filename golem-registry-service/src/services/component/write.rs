@@ -37,6 +37,7 @@ use golem_common::model::component::{
 };
 use golem_common::model::component::{ComponentId, PluginInstallation};
 use golem_common::model::component::{ComponentRevision, PluginPriority};
+use golem_common::model::component_bundle::COMPONENT_BUNDLE_FORMAT_VERSION;
 use golem_common::model::diff::Hash;
 use golem_common::model::environment::{Environment, EnvironmentId};
 use golem_common::widen_infallible;
@@ -46,6 +47,7 @@ use golem_service_base::model::plugin_registration::{
     AppPluginSpec, LibraryPluginSpec, PluginSpec,
 };
 use golem_service_base::replayable_stream::ReplayableStream;
+use golem_service_base::service::compiled_component_files::CompiledComponentFilesService;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use golem_service_base::service::plugin_wasm_files::PluginWasmFilesService;
 use itertools::Itertools;
@@ -53,12 +55,13 @@ use std::collections::HashSet;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tempfile::NamedTempFile;
-use tracing::{Instrument, debug, info, info_span};
+use tracing::{Instrument, debug, info, info_span, warn};
 
 pub struct ComponentWriteService {
     component_repo: Arc<dyn ComponentRepo>,
     object_store: Arc<ComponentObjectStore>,
     component_compilation: Arc<dyn ComponentCompilationService>,
+    compiled_component_files: Arc<CompiledComponentFilesService>,
     initial_component_files_service: Arc<InitialComponentFilesService>,
     plugin_wasm_files_service: Arc<PluginWasmFilesService>,
     account_usage_service: Arc<AccountUsageService>,
@@ -73,6 +76,7 @@ impl ComponentWriteService {
         component_repo: Arc<dyn ComponentRepo>,
         object_store: Arc<ComponentObjectStore>,
         component_compilation: Arc<dyn ComponentCompilationService>,
+        compiled_component_files: Arc<CompiledComponentFilesService>,
         initial_component_files_service: Arc<InitialComponentFilesService>,
         plugin_wasm_files_service: Arc<PluginWasmFilesService>,
         account_usage_service: Arc<AccountUsageService>,
@@ -85,6 +89,7 @@ impl ComponentWriteService {
             component_repo,
             object_store,
             component_compilation,
+            compiled_component_files,
             initial_component_files_service,
             plugin_wasm_files_service,
             account_usage_service,
@@ -105,6 +110,62 @@ impl ComponentWriteService {
     ) -> Result<Component, ComponentError> {
         info!(environment_id = %environment_id, "Create component");
 
+        self.create_internal(
+            environment_id,
+            component_creation,
+            wasm,
+            files_archive,
+            None,
+            auth,
+        )
+        .await
+    }
+
+    /// Creates a new component from a component bundle, storing the bundled pre-compiled
+    /// component instead of scheduling its compilation.
+    ///
+    /// The compatibility of the pre-compiled component is not checked here, as only the worker
+    /// executors know their engine settings. They check it when loading the component, and
+    /// compile the bundled WASM binary instead if it is not compatible.
+    pub async fn import_bundle(
+        &self,
+        environment_id: EnvironmentId,
+        bundle: NamedTempFile,
+        auth: &AuthCtx,
+    ) -> Result<Component, ComponentError> {
+        info!(environment_id = %environment_id, "Import component bundle");
+
+        let bundle = super::utils::read_component_bundle(bundle).await?;
+
+        if bundle.manifest.format_version != COMPONENT_BUNDLE_FORMAT_VERSION {
+            Err(ComponentError::InvalidComponentBundle {
+                message: format!(
+                    "Unsupported bundle format version {}, expected {COMPONENT_BUNDLE_FORMAT_VERSION}",
+                    bundle.manifest.format_version
+                ),
+            })?;
+        }
+
+        self.create_internal(
+            environment_id,
+            bundle.manifest.to_component_creation(),
+            bundle.wasm,
+            bundle.files_archive,
+            Some(bundle.compiled),
+            auth,
+        )
+        .await
+    }
+
+    async fn create_internal(
+        &self,
+        environment_id: EnvironmentId,
+        component_creation: ComponentCreation,
+        wasm: Vec<u8>,
+        files_archive: Option<NamedTempFile>,
+        precompiled: Option<Vec<u8>>,
+        auth: &AuthCtx,
+    ) -> Result<Component, ComponentError> {
         let wasm: Arc<[u8]> = Arc::from(wasm);
 
         let environment = self
@@ -203,13 +264,49 @@ impl ComponentWriteService {
             })?
             .try_into_model(environment.application_id, environment.owner_account_id)?;
 
-        self.component_compilation
-            .enqueue_compilation(environment_id, component_id, stored_component.revision)
-            .await;
+        match precompiled {
+            Some(precompiled) => {
+                self.store_precompiled_component(
+                    environment_id,
+                    component_id,
+                    stored_component.revision,
+                    &precompiled,
+                )
+                .await
+            }
+            None => {
+                self.component_compilation
+                    .enqueue_compilation(environment_id, component_id, stored_component.revision)
+                    .await
+            }
+        }
 
         Ok(stored_component)
     }
 
+    async fn store_precompiled_component(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        revision: ComponentRevision,
+        precompiled: &[u8],
+    ) {
+        if let Err(err) = self
+            .compiled_component_files
+            .put(environment_id, component_id, revision, precompiled)
+            .await
+        {
+            // The component is already created at this point, fall back to compiling it
+            warn!(
+                component_id = %component_id,
+                "Failed to store pre-compiled component: {err:#}"
+            );
+            self.component_compilation
+                .enqueue_compilation(environment_id, component_id, revision)
+                .await;
+        }
+    }
+
     pub async fn update(
         &self,
         component_id: ComponentId,
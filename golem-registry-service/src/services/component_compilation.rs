@@ -13,13 +13,18 @@
 // limitations under the License.
 
 use crate::config::{ComponentCompilationConfig, ComponentCompilationEnabledConfig};
+use anyhow::anyhow;
 use async_trait::async_trait;
 use golem_api_grpc::proto::golem::componentcompilation::v1::{
-    ComponentCompilationRequest,
+    CompilationPriority, CompilationState, ComponentCompilationRequest,
+    GetCompilationStatusRequest,
     component_compilation_service_client::ComponentCompilationServiceClient,
+    get_compilation_status_response,
 };
-use golem_common::model::component::ComponentId;
 use golem_common::model::component::ComponentRevision;
+use golem_common::model::component::{
+    ComponentCompilationState, ComponentCompilationStatus, ComponentId,
+};
 use golem_common::model::environment::EnvironmentId;
 use golem_service_base::grpc::client::GrpcClient;
 use std::fmt::{Debug, Formatter};
@@ -38,6 +43,13 @@ pub trait ComponentCompilationService: Debug + Send + Sync {
         component_revision: ComponentRevision,
     );
 
+    async fn get_compilation_status(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+    ) -> anyhow::Result<ComponentCompilationStatus>;
+
     fn set_own_grpc_port(&self, grpc_port: u16);
 }
 
@@ -103,6 +115,7 @@ impl ComponentCompilationService for GrpcComponentCompilationService {
                         component_revision: component_revision.into(),
                         component_service_port,
                         environment_id: Some(environment_id.into()),
+                        priority: CompilationPriority::Normal.into(),
                     };
 
                     client.enqueue_compilation(request).await
@@ -123,6 +136,51 @@ impl ComponentCompilationService for GrpcComponentCompilationService {
         }
     }
 
+    async fn get_compilation_status(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+    ) -> anyhow::Result<ComponentCompilationStatus> {
+        let response = self
+            .client
+            .call("get-compilation-status", move |client| {
+                Box::pin(async move {
+                    let request = GetCompilationStatusRequest {
+                        component_id: Some(component_id.into()),
+                        component_revision: component_revision.into(),
+                        environment_id: Some(environment_id.into()),
+                    };
+
+                    client.get_compilation_status(request).await
+                })
+            })
+            .await?
+            .into_inner();
+
+        match response.result {
+            Some(get_compilation_status_response::Result::Success(status)) => {
+                let state = match status.state() {
+                    CompilationState::Unknown => ComponentCompilationState::Unknown,
+                    CompilationState::Queued => ComponentCompilationState::Queued,
+                    CompilationState::Compiling => ComponentCompilationState::Compiling,
+                    CompilationState::Ready => ComponentCompilationState::Ready,
+                    CompilationState::Failed => ComponentCompilationState::Failed,
+                };
+                Ok(ComponentCompilationStatus {
+                    component_id,
+                    revision: component_revision,
+                    state,
+                    error: status.error,
+                })
+            }
+            Some(get_compilation_status_response::Result::Failure(error)) => {
+                Err(anyhow!("Failed to get compilation status: {error:?}"))
+            }
+            None => Err(anyhow!("Empty response from component compilation service")),
+        }
+    }
+
     fn set_own_grpc_port(&self, grpc_port: u16) {
         self.own_grpc_port.store(grpc_port, Ordering::Release);
     }
@@ -146,5 +204,20 @@ impl ComponentCompilationService for DisabledComponentCompilationService {
         _component_revision: ComponentRevision,
     ) {
     }
+
+    async fn get_compilation_status(
+        &self,
+        _environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+    ) -> anyhow::Result<ComponentCompilationStatus> {
+        Ok(ComponentCompilationStatus {
+            component_id,
+            revision: component_revision,
+            state: ComponentCompilationState::Unknown,
+            error: None,
+        })
+    }
+
     fn set_own_grpc_port(&self, _grpc_port: u16) {}
 }
@@ -32,7 +32,7 @@ use golem_common::model::domain_registration::Domain;
 use golem_common::model::http_api_deployment::{HttpApiDeployment, HttpApiDeploymentAgentOptions};
 use golem_service_base::custom_api::{
     CallAgentBehaviour, ConstructorParameter, CorsOptions, CorsPreflightBehaviour, OriginPattern,
-    PathSegment, RequestBodySchema, RouteBehaviour,
+    PathSegment, RateLimit, RequestBodySchema, RouteBehaviour,
 };
 use itertools::Itertools;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
@@ -291,11 +291,14 @@ impl DeploymentContext {
                 }
 
                 let mut auth_required = false;
+                let mut required_scopes = BTreeSet::new();
                 if let Some(auth_details) = &http_mount.auth_details {
                     auth_required = auth_details.required;
+                    required_scopes.extend(auth_details.scopes.iter().cloned());
                 }
                 if let Some(auth_details) = &http_endpoint.auth_details {
                     auth_required = auth_details.required;
+                    required_scopes.extend(auth_details.scopes.iter().cloned());
                 }
 
                 let security_scheme = if auth_required {
@@ -333,7 +336,14 @@ impl DeploymentContext {
                         expected_agent_response: agent_method.output_schema.clone(),
                     }),
                     security_scheme,
+                    required_scopes: required_scopes.into_iter().collect(),
                     cors,
+                    rate_limits: agent_options
+                        .rate_limits
+                        .iter()
+                        .cloned()
+                        .map(RateLimit::from)
+                        .collect(),
                 };
 
                 {
@@ -382,9 +392,12 @@ impl DeploymentContext {
                         allowed_methods,
                     }),
                     security_scheme: None,
+                    required_scopes: vec![],
                     cors: CorsOptions {
                         allowed_patterns: vec![],
                     },
+                    // preflight requests are answered without reaching the agent
+                    rate_limits: vec![],
                 },
             );
         }
@@ -17,12 +17,15 @@ use crate::model::api_definition::{
 };
 use crate::repo::deployment::DeploymentRepo;
 use crate::repo::model::deployment::DeployRepoError;
+use crate::repo::model::security_scheme::SecuritySchemeRepoError;
+use crate::repo::security_scheme::SecuritySchemeRepo;
 use crate::services::http_api_deployment::{HttpApiDeploymentError, HttpApiDeploymentService};
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::EnvironmentId;
+use golem_common::model::security_scheme::SecuritySchemeId;
 use golem_common::{SafeDisplay, error_forwarding};
-use golem_service_base::custom_api::{CompiledRoute, CompiledRoutes};
+use golem_service_base::custom_api::{CompiledRoute, CompiledRoutes, SecuritySchemeApiKey};
 use golem_service_base::model::auth::AuthCtx;
 use golem_service_base::repo::RepoError;
 use std::collections::HashMap;
@@ -58,21 +61,25 @@ error_forwarding!(
     DeployedRoutesError,
     RepoError,
     DeployRepoError,
+    SecuritySchemeRepoError,
     HttpApiDeploymentError
 );
 
 pub struct DeployedRoutesService {
     deployment_repo: Arc<dyn DeploymentRepo>,
+    security_scheme_repo: Arc<dyn SecuritySchemeRepo>,
     http_api_deployment_service: Arc<HttpApiDeploymentService>,
 }
 
 impl DeployedRoutesService {
     pub fn new(
         deployment_repo: Arc<dyn DeploymentRepo>,
+        security_scheme_repo: Arc<dyn SecuritySchemeRepo>,
         http_api_deployment_service: Arc<HttpApiDeploymentService>,
     ) -> Self {
         Self {
             deployment_repo,
+            security_scheme_repo,
             http_api_deployment_service,
         }
     }
@@ -146,8 +153,8 @@ impl DeployedRoutesService {
         for route in routes {
             let mut security_scheme_id = None;
             if let Some(security_scheme) = route.security_scheme {
-                let _ = security_scheme_id.insert(security_scheme.id);
-                security_schemes.insert(security_scheme.id, security_scheme);
+                let _ = security_scheme_id.insert(security_scheme.id());
+                security_schemes.insert(security_scheme.id(), security_scheme);
             }
             let converted = MaybeDisabledCompiledRoute {
                 method: route.route.method,
@@ -156,7 +163,9 @@ impl DeployedRoutesService {
                 body: route.route.body,
                 security_scheme_missing: route.security_scheme_missing,
                 security_scheme: security_scheme_id,
+                required_scopes: route.route.required_scopes,
                 cors: route.route.cors,
+                rate_limits: route.route.rate_limits,
             };
             converted_routes.push(converted);
         }
@@ -197,8 +206,8 @@ impl DeployedRoutesService {
 
             let mut security_scheme_id = None;
             if let Some(security_scheme) = route.security_scheme {
-                let _ = security_scheme_id.insert(security_scheme.id);
-                security_schemes.insert(security_scheme.id, security_scheme);
+                let _ = security_scheme_id.insert(security_scheme.id());
+                security_schemes.insert(security_scheme.id(), security_scheme);
             }
             let converted = CompiledRoute {
                 route_id: route.route.route_id,
@@ -207,7 +216,9 @@ impl DeployedRoutesService {
                 body: route.route.body,
                 behavior: route.route.behaviour,
                 security_scheme: security_scheme_id,
+                required_scopes: route.route.required_scopes,
                 cors: route.route.cors,
+                rate_limits: route.route.rate_limits,
             };
             converted_routes.push(converted);
         }
@@ -229,4 +240,22 @@ impl DeployedRoutesService {
             security_schemes,
         })
     }
+
+    /// Hashes of the keys of a security scheme that were not revoked. Served separately from the
+    /// compiled routes so that the worker service can pick up revocations without rebuilding its
+    /// routers.
+    pub async fn get_security_scheme_api_keys(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+    ) -> Result<Vec<SecuritySchemeApiKey>, DeployedRoutesError> {
+        let api_keys = self
+            .security_scheme_repo
+            .get_api_keys(security_scheme_id.0)
+            .await?
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<_, _>>()?;
+
+        Ok(api_keys)
+    }
 }
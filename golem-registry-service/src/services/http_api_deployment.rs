@@ -20,16 +20,18 @@ use crate::repo::model::audit::DeletableRevisionAuditFields;
 use crate::repo::model::http_api_deployment::{
     HttpApiDeploymentRepoError, HttpApiDeploymentRevisionRecord,
 };
+use golem_common::model::agent::AgentTypeName;
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::{Environment, EnvironmentId};
 use golem_common::model::http_api_deployment::{
-    HttpApiDeployment, HttpApiDeploymentCreation, HttpApiDeploymentId, HttpApiDeploymentRevision,
-    HttpApiDeploymentUpdate,
+    HttpApiDeployment, HttpApiDeploymentAgentOptions, HttpApiDeploymentCreation,
+    HttpApiDeploymentId, HttpApiDeploymentRevision, HttpApiDeploymentUpdate,
 };
 use golem_common::{SafeDisplay, error_forwarding};
 use golem_service_base::model::auth::{AuthCtx, AuthorizationError, EnvironmentAction};
 use golem_service_base::repo::RepoError;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 #[derive(Debug, thiserror::Error)]
@@ -46,6 +48,8 @@ pub enum HttpApiDeploymentError {
     HttpApiDeploymentForDomainAlreadyExists(Domain),
     #[error("Domain {0} is not registered")]
     DomainNotRegistered(Domain),
+    #[error("Invalid rate limit for agent {0}: {1}")]
+    InvalidRateLimit(AgentTypeName, String),
     #[error("Concurrent update attempt")]
     ConcurrentUpdate,
     #[error(transparent)]
@@ -63,6 +67,7 @@ impl SafeDisplay for HttpApiDeploymentError {
             Self::ParentEnvironmentNotFound(_) => self.to_string(),
             Self::HttpApiDeploymentForDomainAlreadyExists(_) => self.to_string(),
             Self::DomainNotRegistered(_) => self.to_string(),
+            Self::InvalidRateLimit(_, _) => self.to_string(),
             Self::ConcurrentUpdate => self.to_string(),
             Self::Unauthorized(inner) => inner.to_safe_string(),
             Self::InternalError(_) => "Internal error".to_string(),
@@ -134,6 +139,8 @@ impl HttpApiDeploymentService {
                 other => other.into(),
             })?;
 
+        validate_rate_limits(&data.agents)?;
+
         let id = HttpApiDeploymentId::new();
         let record = HttpApiDeploymentRevisionRecord::creation(id, data.agents, auth.account_id());
 
@@ -200,6 +207,7 @@ impl HttpApiDeploymentService {
 
         http_api_deployment.revision = http_api_deployment.revision.next()?;
         if let Some(api_definitions) = update.agents {
+            validate_rate_limits(&api_definitions)?;
             http_api_deployment.agents = api_definitions;
         };
 
@@ -505,3 +513,25 @@ impl HttpApiDeploymentService {
         Ok(http_api_deployment)
     }
 }
+
+fn validate_rate_limits(
+    agents: &BTreeMap<AgentTypeName, HttpApiDeploymentAgentOptions>,
+) -> Result<(), HttpApiDeploymentError> {
+    for (agent_type, options) in agents {
+        for rate_limit in &options.rate_limits {
+            let invalid = |message: &str| {
+                HttpApiDeploymentError::InvalidRateLimit(agent_type.clone(), message.to_string())
+            };
+            if rate_limit.requests == 0 {
+                return Err(invalid("requests must be positive"));
+            }
+            if rate_limit.interval_seconds == 0 {
+                return Err(invalid("intervalSeconds must be positive"));
+            }
+            if rate_limit.burst == Some(0) {
+                return Err(invalid("burst must be positive"));
+            }
+        }
+    }
+    Ok(())
+}
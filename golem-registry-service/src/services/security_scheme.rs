@@ -13,19 +13,23 @@
 // limitations under the License.
 
 use super::environment::{EnvironmentError, EnvironmentService};
-use crate::model::security_scheme::SecurityScheme;
+use crate::config::SecuritySchemesConfig;
+use crate::model::security_scheme::{AnySecurityScheme, ApiKeySecurityScheme, SecurityScheme};
 use crate::repo::model::audit::DeletableRevisionAuditFields;
-use crate::repo::model::security_scheme::{SecuritySchemeRepoError, SecuritySchemeRevisionRecord};
+use crate::repo::model::security_scheme::{
+    SecuritySchemeApiKeyRecord, SecuritySchemeRepoError, SecuritySchemeRevisionRecord,
+};
 use crate::repo::security_scheme::SecuritySchemeRepo;
 use golem_common::model::environment::{Environment, EnvironmentId};
 use golem_common::model::security_scheme::{
+    ApiKey, ApiKeyCreation, ApiKeyId, ApiKeySecuritySchemeCreation, ApiKeyWithSecret, Provider,
     SecuritySchemeCreation, SecuritySchemeId, SecuritySchemeName, SecuritySchemeRevision,
-    SecuritySchemeUpdate,
+    SecuritySchemeUpdate, hash_api_key_secret, new_api_key_secret,
 };
 use golem_common::{SafeDisplay, error_forwarding};
 use golem_service_base::model::auth::EnvironmentAction;
 use golem_service_base::model::auth::{AuthCtx, AuthorizationError};
-use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
+use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl, Scope};
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -35,6 +39,8 @@ pub enum SecuritySchemeError {
     SecuritySchemeWithNameAlreadyExists(SecuritySchemeName),
     #[error("Invalid redirect url provided")]
     InvalidRedirectUrl,
+    #[error("Invalid issuer url: {0}")]
+    InvalidIssuerUrl(String),
     #[error("Environment {0} not found")]
     ParentEnvironmentNotFound(EnvironmentId),
     #[error("Security scheme {0} not found")]
@@ -43,6 +49,12 @@ pub enum SecuritySchemeError {
     SecuritySchemeForNameNotFound(SecuritySchemeName),
     #[error("Concurrent update attempt")]
     ConcurrentUpdateAttempt,
+    #[error("There is already an active api key with name {0}")]
+    ApiKeyWithNameAlreadyExists(String),
+    #[error("Invalid api key: {0}")]
+    InvalidApiKey(String),
+    #[error("Api key {0} not found")]
+    ApiKeyNotFound(ApiKeyId),
     #[error(transparent)]
     Unauthorized(#[from] AuthorizationError),
     #[error(transparent)]
@@ -53,11 +65,15 @@ impl SafeDisplay for SecuritySchemeError {
     fn to_safe_string(&self) -> String {
         match self {
             Self::InvalidRedirectUrl => self.to_string(),
+            Self::InvalidIssuerUrl(_) => self.to_string(),
             Self::SecuritySchemeWithNameAlreadyExists(_) => self.to_string(),
             Self::SecuritySchemeForNameNotFound(_) => self.to_string(),
             Self::ParentEnvironmentNotFound(_) => self.to_string(),
             Self::SecuritySchemeNotFound(_) => self.to_string(),
             Self::ConcurrentUpdateAttempt => self.to_string(),
+            Self::ApiKeyWithNameAlreadyExists(_) => self.to_string(),
+            Self::InvalidApiKey(_) => self.to_string(),
+            Self::ApiKeyNotFound(_) => self.to_string(),
             Self::Unauthorized(inner) => inner.to_safe_string(),
             Self::InternalError(_) => "Internal error".to_string(),
         }
@@ -73,16 +89,19 @@ error_forwarding!(
 pub struct SecuritySchemeService {
     security_scheme_repo: Arc<dyn SecuritySchemeRepo>,
     environment_service: Arc<EnvironmentService>,
+    config: SecuritySchemesConfig,
 }
 
 impl SecuritySchemeService {
     pub fn new(
         security_scheme_repo: Arc<dyn SecuritySchemeRepo>,
         environment_service: Arc<EnvironmentService>,
+        config: SecuritySchemesConfig,
     ) -> Self {
         Self {
             security_scheme_repo,
             environment_service,
+            config,
         }
     }
 
@@ -92,16 +111,7 @@ impl SecuritySchemeService {
         data: SecuritySchemeCreation,
         auth: &AuthCtx,
     ) -> Result<SecurityScheme, SecuritySchemeError> {
-        let environment = self
-            .environment_service
-            .get(environment_id, false, auth)
-            .await
-            .map_err(|err| match err {
-                EnvironmentError::EnvironmentNotFound(_) => {
-                    SecuritySchemeError::ParentEnvironmentNotFound(environment_id)
-                }
-                other => other.into(),
-            })?;
+        let environment = self.get_parent_environment(environment_id, auth).await?;
 
         auth.authorize_environment_action(
             environment.owner_account_id,
@@ -114,14 +124,26 @@ impl SecuritySchemeService {
         let redirect_url: RedirectUrl = RedirectUrl::new(data.redirect_url)
             .map_err(|_| SecuritySchemeError::InvalidRedirectUrl)?;
         let scopes: Vec<Scope> = data.scopes.into_iter().map(Scope::new).collect();
+        let issuer_url = custom_issuer_url(
+            &data.provider_type,
+            data.issuer_url,
+            &self.config.http_issuer_hosts,
+        )?;
+        let audience = data
+            .audience
+            .filter(|audience| !audience.is_empty())
+            .map(Audience::new);
 
         let record = SecuritySchemeRevisionRecord::creation(
             id,
             data.provider_type,
+            issuer_url.as_ref(),
             data.client_id,
             data.client_secret,
             &redirect_url,
             &scopes,
+            data.mode.unwrap_or_default(),
+            audience.as_ref(),
             auth.account_id(),
         );
 
@@ -159,7 +181,20 @@ impl SecuritySchemeService {
         };
 
         security_scheme.revision = security_scheme.revision.next()?;
-        if let Some(provider_type) = update.provider_type {
+        if update.provider_type.is_some() || update.issuer_url.is_some() {
+            let provider_type = update
+                .provider_type
+                .unwrap_or(security_scheme.provider_type);
+            // keep the configured issuer when only other fields of a custom provider change
+            let issuer_url = update.issuer_url.or_else(|| {
+                security_scheme
+                    .issuer_url
+                    .as_ref()
+                    .filter(|_| provider_type == Provider::Custom)
+                    .map(|url| (**url).clone())
+            });
+            security_scheme.issuer_url =
+                custom_issuer_url(&provider_type, issuer_url, &self.config.http_issuer_hosts)?;
             security_scheme.provider_type = provider_type;
         };
         if let Some(client_id) = update.client_id {
@@ -177,6 +212,15 @@ impl SecuritySchemeService {
             let scopes: Vec<Scope> = scopes.into_iter().map(Scope::new).collect();
             security_scheme.scopes = scopes;
         };
+        if let Some(mode) = update.mode {
+            security_scheme.mode = mode;
+        };
+        if let Some(audience) = update.audience {
+            // an empty audience resets it to the client id
+            security_scheme.audience = Some(audience)
+                .filter(|audience| !audience.is_empty())
+                .map(Audience::new);
+        };
 
         let audit = DeletableRevisionAuditFields::new(auth.account_id().0);
 
@@ -251,16 +295,7 @@ impl SecuritySchemeService {
         environment_id: EnvironmentId,
         auth: &AuthCtx,
     ) -> Result<Vec<SecurityScheme>, SecuritySchemeError> {
-        let environment = self
-            .environment_service
-            .get(environment_id, false, auth)
-            .await
-            .map_err(|err| match err {
-                EnvironmentError::EnvironmentNotFound(_) => {
-                    SecuritySchemeError::ParentEnvironmentNotFound(environment_id)
-                }
-                other => other.into(),
-            })?;
+        let environment = self.get_parent_environment(environment_id, auth).await?;
 
         auth.authorize_environment_action(
             environment.owner_account_id,
@@ -273,6 +308,7 @@ impl SecuritySchemeService {
             .get_for_environment(environment_id.0)
             .await?
             .into_iter()
+            .filter(|r| !r.is_api_key())
             .map(|r| r.try_into())
             .collect::<Result<_, _>>()?;
 
@@ -295,6 +331,7 @@ impl SecuritySchemeService {
             .security_scheme_repo
             .get_for_environment_and_name(environment.id.0, &name.0)
             .await?
+            .filter(|r| !r.is_api_key())
             .ok_or(SecuritySchemeError::SecuritySchemeForNameNotFound(
                 name.clone(),
             ))?
@@ -303,12 +340,288 @@ impl SecuritySchemeService {
         Ok(result)
     }
 
+    pub async fn create_api_key_scheme(
+        &self,
+        environment_id: EnvironmentId,
+        data: ApiKeySecuritySchemeCreation,
+        auth: &AuthCtx,
+    ) -> Result<ApiKeySecurityScheme, SecuritySchemeError> {
+        let environment = self.get_parent_environment(environment_id, auth).await?;
+
+        auth.authorize_environment_action(
+            environment.owner_account_id,
+            &environment.roles_from_active_shares,
+            EnvironmentAction::CreateSecurityScheme,
+        )?;
+
+        let record = SecuritySchemeRevisionRecord::api_key_creation(
+            SecuritySchemeId::new(),
+            auth.account_id(),
+        );
+
+        let result = self
+            .security_scheme_repo
+            .create(environment_id.0, data.name.0.clone(), record)
+            .await;
+
+        match result {
+            Ok(record) => Ok(record.try_into()?),
+            Err(SecuritySchemeRepoError::SecuritySchemeViolatesUniqueness) => Err(
+                SecuritySchemeError::SecuritySchemeWithNameAlreadyExists(data.name),
+            ),
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn delete_api_key_scheme(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        current_revision: SecuritySchemeRevision,
+        auth: &AuthCtx,
+    ) -> Result<ApiKeySecurityScheme, SecuritySchemeError> {
+        let (mut security_scheme, environment) = self
+            .get_api_key_scheme_with_environment(security_scheme_id, auth)
+            .await?;
+
+        auth.authorize_environment_action(
+            environment.owner_account_id,
+            &environment.roles_from_active_shares,
+            EnvironmentAction::DeleteSecurityScheme,
+        )?;
+
+        if current_revision != security_scheme.revision {
+            return Err(SecuritySchemeError::ConcurrentUpdateAttempt);
+        };
+
+        security_scheme.revision = security_scheme.revision.next()?;
+
+        let audit = DeletableRevisionAuditFields::deletion(auth.account_id().0);
+
+        let result = self
+            .security_scheme_repo
+            .delete(SecuritySchemeRevisionRecord::from_api_key_model(
+                security_scheme,
+                audit,
+            ))
+            .await;
+
+        match result {
+            Ok(record) => Ok(record.try_into()?),
+            Err(SecuritySchemeRepoError::ConcurrentModification) => {
+                Err(SecuritySchemeError::ConcurrentUpdateAttempt)
+            }
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn get_api_key_scheme(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: &AuthCtx,
+    ) -> Result<ApiKeySecurityScheme, SecuritySchemeError> {
+        let (security_scheme, _) = self
+            .get_api_key_scheme_with_environment(security_scheme_id, auth)
+            .await?;
+        Ok(security_scheme)
+    }
+
+    pub async fn get_api_key_schemes_in_environment(
+        &self,
+        environment_id: EnvironmentId,
+        auth: &AuthCtx,
+    ) -> Result<Vec<ApiKeySecurityScheme>, SecuritySchemeError> {
+        let environment = self.get_parent_environment(environment_id, auth).await?;
+
+        auth.authorize_environment_action(
+            environment.owner_account_id,
+            &environment.roles_from_active_shares,
+            EnvironmentAction::ViewSecurityScheme,
+        )?;
+
+        let result = self
+            .security_scheme_repo
+            .get_for_environment(environment_id.0)
+            .await?
+            .into_iter()
+            .filter(|r| r.is_api_key())
+            .map(|r| r.try_into())
+            .collect::<Result<_, _>>()?;
+
+        Ok(result)
+    }
+
+    /// Creates a new key for an api-key security scheme. The secret is only part of this response.
+    pub async fn create_api_key(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        data: ApiKeyCreation,
+        auth: &AuthCtx,
+    ) -> Result<ApiKeyWithSecret, SecuritySchemeError> {
+        let (_, environment) = self
+            .get_api_key_scheme_with_environment(security_scheme_id, auth)
+            .await?;
+
+        auth.authorize_environment_action(
+            environment.owner_account_id,
+            &environment.roles_from_active_shares,
+            EnvironmentAction::UpdateSecurityScheme,
+        )?;
+
+        if data.name.trim().is_empty() {
+            return Err(SecuritySchemeError::InvalidApiKey(
+                "name cannot be empty".to_string(),
+            ));
+        };
+        if data
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now())
+        {
+            return Err(SecuritySchemeError::InvalidApiKey(
+                "expiry has to be in the future".to_string(),
+            ));
+        };
+
+        let secret = new_api_key_secret();
+        let scopes: Vec<Scope> = data.scopes.into_iter().map(Scope::new).collect();
+
+        let record = SecuritySchemeApiKeyRecord::creation(
+            ApiKeyId::new(),
+            security_scheme_id,
+            data.name.clone(),
+            hash_api_key_secret(&secret),
+            &scopes,
+            data.expires_at,
+            auth.account_id(),
+        );
+
+        let result = self.security_scheme_repo.create_api_key(record).await;
+
+        match result {
+            Ok(record) => {
+                let api_key: ApiKey = record.try_into()?;
+                Ok(ApiKeyWithSecret {
+                    id: api_key.id,
+                    secret,
+                    security_scheme_id: api_key.security_scheme_id,
+                    name: api_key.name,
+                    scopes: api_key.scopes,
+                    created_at: api_key.created_at,
+                    expires_at: api_key.expires_at,
+                })
+            }
+            Err(SecuritySchemeRepoError::ApiKeyViolatesUniqueness) => {
+                Err(SecuritySchemeError::ApiKeyWithNameAlreadyExists(data.name))
+            }
+            Err(other) => Err(other.into()),
+        }
+    }
+
+    pub async fn get_api_keys(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: &AuthCtx,
+    ) -> Result<Vec<ApiKey>, SecuritySchemeError> {
+        let _ = self
+            .get_api_key_scheme_with_environment(security_scheme_id, auth)
+            .await?;
+
+        let result = self
+            .security_scheme_repo
+            .get_api_keys(security_scheme_id.0)
+            .await?
+            .into_iter()
+            .map(|r| r.try_into())
+            .collect::<Result<_, _>>()?;
+
+        Ok(result)
+    }
+
+    pub async fn revoke_api_key(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        api_key_id: ApiKeyId,
+        auth: &AuthCtx,
+    ) -> Result<ApiKey, SecuritySchemeError> {
+        let (_, environment) = self
+            .get_api_key_scheme_with_environment(security_scheme_id, auth)
+            .await?;
+
+        auth.authorize_environment_action(
+            environment.owner_account_id,
+            &environment.roles_from_active_shares,
+            EnvironmentAction::UpdateSecurityScheme,
+        )?;
+
+        let result = self
+            .security_scheme_repo
+            .revoke_api_key(security_scheme_id.0, api_key_id.0)
+            .await?
+            .ok_or(SecuritySchemeError::ApiKeyNotFound(api_key_id))?
+            .try_into()?;
+
+        Ok(result)
+    }
+
+    async fn get_parent_environment(
+        &self,
+        environment_id: EnvironmentId,
+        auth: &AuthCtx,
+    ) -> Result<Environment, SecuritySchemeError> {
+        self.environment_service
+            .get(environment_id, false, auth)
+            .await
+            .map_err(|err| match err {
+                EnvironmentError::EnvironmentNotFound(_) => {
+                    SecuritySchemeError::ParentEnvironmentNotFound(environment_id)
+                }
+                other => other.into(),
+            })
+    }
+
+    /// Schemes of the other kind are reported as not found, so OpenID Connect and api-key
+    /// schemes can only be managed through their own endpoints.
     async fn get_with_environment(
         &self,
         security_scheme_id: SecuritySchemeId,
         auth: &AuthCtx,
     ) -> Result<(SecurityScheme, Environment), SecuritySchemeError> {
-        let security_scheme: SecurityScheme = self
+        match self
+            .get_any_with_environment(security_scheme_id, auth)
+            .await?
+        {
+            (AnySecurityScheme::OpenIdConnect(security_scheme), environment) => {
+                Ok((security_scheme, environment))
+            }
+            (AnySecurityScheme::ApiKey(_), _) => Err(SecuritySchemeError::SecuritySchemeNotFound(
+                security_scheme_id,
+            )),
+        }
+    }
+
+    async fn get_api_key_scheme_with_environment(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: &AuthCtx,
+    ) -> Result<(ApiKeySecurityScheme, Environment), SecuritySchemeError> {
+        match self
+            .get_any_with_environment(security_scheme_id, auth)
+            .await?
+        {
+            (AnySecurityScheme::ApiKey(security_scheme), environment) => {
+                Ok((security_scheme, environment))
+            }
+            (AnySecurityScheme::OpenIdConnect(_), _) => Err(
+                SecuritySchemeError::SecuritySchemeNotFound(security_scheme_id),
+            ),
+        }
+    }
+
+    async fn get_any_with_environment(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+        auth: &AuthCtx,
+    ) -> Result<(AnySecurityScheme, Environment), SecuritySchemeError> {
+        let security_scheme: AnySecurityScheme = self
             .security_scheme_repo
             .get_by_id(security_scheme_id.0)
             .await?
@@ -319,7 +632,7 @@ impl SecuritySchemeService {
 
         let environment = self
             .environment_service
-            .get(security_scheme.environment_id, false, auth)
+            .get(security_scheme.environment_id(), false, auth)
             .await
             .map_err(|err| match err {
                 EnvironmentError::EnvironmentNotFound(_) => {
@@ -338,3 +651,38 @@ impl SecuritySchemeService {
         Ok((security_scheme, environment))
     }
 }
+
+/// Validates the issuer url against the provider, returning it if it has to be stored.
+///
+/// Custom issuers are trusted for discovering the token signing keys, so they must use https,
+/// unless their host is explicitly allowed in `http_issuer_hosts`.
+fn custom_issuer_url(
+    provider_type: &Provider,
+    issuer_url: Option<String>,
+    http_issuer_hosts: &[String],
+) -> Result<Option<IssuerUrl>, SecuritySchemeError> {
+    let resolved = provider_type
+        .resolve_issuer_url(issuer_url.as_deref())
+        .map_err(SecuritySchemeError::InvalidIssuerUrl)?;
+
+    if issuer_url.is_none() {
+        return Ok(None);
+    }
+
+    let url = resolved.url();
+    let allowed = match url.scheme() {
+        "https" => true,
+        "http" => url
+            .host_str()
+            .is_some_and(|host| http_issuer_hosts.iter().any(|allowed| allowed == host)),
+        _ => false,
+    };
+    if !allowed {
+        return Err(SecuritySchemeError::InvalidIssuerUrl(format!(
+            "Issuer url {} must use https",
+            resolved.as_str()
+        )));
+    }
+
+    Ok(Some(resolved))
+}
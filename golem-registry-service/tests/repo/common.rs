@@ -14,12 +14,14 @@
 
 use crate::repo::Deps;
 use assert2::{assert, check, let_assert};
-use chrono::Utc;
+use chrono::{TimeDelta, Utc};
 use futures::future::join_all;
+use golem_common::model::account::AccountId;
 use golem_common::model::agent::AgentTypeName;
 use golem_common::model::component::ComponentFilePermissions;
 use golem_common::model::component_metadata::ComponentMetadata;
 use golem_common::model::http_api_deployment::HttpApiDeploymentAgentOptions;
+use golem_common::model::security_scheme::{ApiKeyId, SecuritySchemeId};
 use golem_registry_service::repo::environment::EnvironmentRevisionRecord;
 use golem_registry_service::repo::model::account::{
     AccountExtRevisionRecord, AccountRepoError, AccountRevisionRecord,
@@ -42,7 +44,11 @@ use golem_registry_service::repo::model::http_api_deployment::{
 };
 use golem_registry_service::repo::model::new_repo_uuid;
 use golem_registry_service::repo::model::plugin::PluginRecord;
+use golem_registry_service::repo::model::security_scheme::{
+    SecuritySchemeApiKeyRecord, SecuritySchemeRepoError, SecuritySchemeRevisionRecord,
+};
 use golem_service_base::repo::blob::Blob;
+use openidconnect::Scope;
 use std::collections::{BTreeMap, HashMap};
 use std::default::Default;
 use strum::IntoEnumIterator;
@@ -817,12 +823,10 @@ pub async fn test_http_api_deployment_stage(deps: &Deps) {
         revision_id: 0,
         hash: SqlBlake3Hash::empty(),
         audit: DeletableRevisionAuditFields::new(user.revision.account_id),
-        data: Blob::new(HttpApiDeploymentData {
-            agents: BTreeMap::from_iter([(
-                AgentTypeName("test-agent".to_string()),
-                HttpApiDeploymentAgentOptions::default(),
-            )]),
-        }),
+        data: Blob::new(HttpApiDeploymentData::new(BTreeMap::from_iter([(
+            AgentTypeName("test-agent".to_string()),
+            HttpApiDeploymentAgentOptions::default(),
+        )]))),
     }
     .with_updated_hash();
 
@@ -977,6 +981,109 @@ pub async fn test_http_api_deployment_stage(deps: &Deps) {
     assert!(created_after_delete.revision == revision_after_delete);
 }
 
+pub async fn test_security_scheme_api_keys(deps: &Deps) {
+    let user = deps.create_account().await;
+    let app = deps.create_application(user.revision.account_id).await;
+    let env = deps.create_env(app.revision.application_id).await;
+    let actor = AccountId(user.revision.account_id);
+
+    let security_scheme = deps
+        .security_scheme_repo
+        .create(
+            env.revision.environment_id,
+            "partner-keys".to_string(),
+            SecuritySchemeRevisionRecord::api_key_creation(SecuritySchemeId::new(), actor),
+        )
+        .await
+        .unwrap();
+    let security_scheme_id = SecuritySchemeId(security_scheme.revision.security_scheme_id);
+
+    let api_key = |name: &str, secret_hash: &str| {
+        SecuritySchemeApiKeyRecord::creation(
+            ApiKeyId::new(),
+            security_scheme_id,
+            name.to_string(),
+            secret_hash.to_string(),
+            &[Scope::new("counters:read".to_string())],
+            Some(Utc::now() + TimeDelta::hours(1)),
+            actor,
+        )
+    };
+
+    let partner = api_key("partner", "partner-hash");
+    let created_partner = deps
+        .security_scheme_repo
+        .create_api_key(partner.clone())
+        .await
+        .unwrap();
+    assert!(created_partner == partner);
+
+    let other = api_key("other", "other-hash");
+    let created_other = deps
+        .security_scheme_repo
+        .create_api_key(other.clone())
+        .await
+        .unwrap();
+    assert!(created_other == other);
+
+    let duplicate_name = deps
+        .security_scheme_repo
+        .create_api_key(api_key("partner", "another-hash"))
+        .await;
+    let_assert!(Err(SecuritySchemeRepoError::ApiKeyViolatesUniqueness) = duplicate_name);
+
+    let api_keys = deps
+        .security_scheme_repo
+        .get_api_keys(security_scheme_id.0)
+        .await
+        .unwrap();
+    assert!(api_keys == vec![other.clone(), partner.clone()]);
+
+    let revoked = deps
+        .security_scheme_repo
+        .revoke_api_key(security_scheme_id.0, partner.api_key_id)
+        .await
+        .unwrap();
+    let_assert!(Some(revoked) = revoked);
+    check!(revoked.api_key_id == partner.api_key_id);
+    check!(revoked.revoked_at.is_some());
+
+    let revoked_again = deps
+        .security_scheme_repo
+        .revoke_api_key(security_scheme_id.0, partner.api_key_id)
+        .await
+        .unwrap();
+    check!(revoked_again.is_none());
+
+    let revoked_with_other_scheme = deps
+        .security_scheme_repo
+        .revoke_api_key(new_repo_uuid(), other.api_key_id)
+        .await
+        .unwrap();
+    check!(revoked_with_other_scheme.is_none());
+
+    let api_keys = deps
+        .security_scheme_repo
+        .get_api_keys(security_scheme_id.0)
+        .await
+        .unwrap();
+    assert!(api_keys == vec![other.clone()]);
+
+    // the name of a revoked key can be reused
+    let reissued_partner = api_key("partner", "reissued-partner-hash");
+    deps.security_scheme_repo
+        .create_api_key(reissued_partner.clone())
+        .await
+        .unwrap();
+
+    let api_keys = deps
+        .security_scheme_repo
+        .get_api_keys(security_scheme_id.0)
+        .await
+        .unwrap();
+    assert!(api_keys == vec![other, reissued_partner]);
+}
+
 pub async fn test_account_usage(deps: &Deps) {
     let user = deps.create_account().await;
     let now = SqlDateTime::now();
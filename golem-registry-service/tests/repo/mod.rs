@@ -33,6 +33,7 @@ use golem_registry_service::repo::model::new_repo_uuid;
 use golem_registry_service::repo::model::plan::PlanRecord;
 use golem_registry_service::repo::plan::PlanRepo;
 use golem_registry_service::repo::plugin::PluginRepo;
+use golem_registry_service::repo::security_scheme::SecuritySchemeRepo;
 use std::str::FromStr;
 use test_r::{inherit_test_dep, sequential_suite};
 use uuid::Uuid;
@@ -56,6 +57,7 @@ pub struct Deps {
     pub http_api_deployment_repo: Box<dyn HttpApiDeploymentRepo>,
     pub deployment_repo: Box<dyn HttpApiDeploymentRepo>,
     pub plugin_repo: Box<dyn PluginRepo>,
+    pub security_scheme_repo: Box<dyn SecuritySchemeRepo>,
 }
 
 impl Deps {
@@ -23,6 +23,7 @@ use golem_registry_service::repo::environment::DbEnvironmentRepo;
 use golem_registry_service::repo::http_api_deployment::DbHttpApiDeploymentRepo;
 use golem_registry_service::repo::plan::DbPlanRepo;
 use golem_registry_service::repo::plugin::DbPluginRepo;
+use golem_registry_service::repo::security_scheme::DbSecuritySchemeRepo;
 use golem_service_base::db;
 use golem_service_base::db::postgres::PostgresPool;
 use golem_service_base::migration::{Migrations, MigrationsDir};
@@ -158,6 +159,7 @@ async fn deps(db: &PostgresDb) -> Deps {
         http_api_deployment_repo: Box::new(DbHttpApiDeploymentRepo::logged(db.pool.clone())),
         deployment_repo: Box::new(DbHttpApiDeploymentRepo::logged(db.pool.clone())),
         plugin_repo: Box::new(DbPluginRepo::logged(db.pool.clone())),
+        security_scheme_repo: Box::new(DbSecuritySchemeRepo::logged(db.pool.clone())),
     };
     deps.setup().await;
     deps
@@ -215,6 +217,11 @@ async fn test_http_api_deployment_stage(deps: &Deps) {
     crate::repo::common::test_http_api_deployment_stage(deps).await;
 }
 
+#[test]
+async fn test_security_scheme_api_keys(deps: &Deps) {
+    crate::repo::common::test_security_scheme_api_keys(deps).await;
+}
+
 #[test]
 async fn test_account_usage(deps: &Deps) {
     crate::repo::common::test_account_usage(deps).await;
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::custom_api::CompiledRoutes;
+use crate::custom_api::{CompiledRoutes, SecuritySchemeApiKey};
 use crate::grpc::client::{GrpcClient, GrpcClientConfig};
 use crate::model::auth::{AuthCtx, AuthDetailsForEnvironment, UserAuthCtx};
 use crate::model::{AccountResourceLimits, ResourceLimits};
@@ -24,12 +24,13 @@ use golem_api_grpc::proto::golem::registry::v1::{
     GetActiveRoutesForDomainRequest, GetAgentTypeRequest, GetAllAgentTypesRequest,
     GetAllDeployedComponentRevisionsRequest, GetAuthDetailsForEnvironmentRequest,
     GetComponentMetadataRequest, GetDeployedComponentMetadataRequest, GetResourceLimitsRequest,
-    ResolveComponentRequest, UpdateWorkerConnectionLimitRequest, UpdateWorkerLimitRequest,
-    authenticate_token_response, batch_update_fuel_usage_response, download_component_response,
-    get_active_routes_for_domain_response, get_agent_type_response, get_all_agent_types_response,
-    get_all_deployed_component_revisions_response, get_auth_details_for_environment_response,
-    get_component_metadata_response, get_deployed_component_metadata_response,
-    get_resource_limits_response, resolve_component_response,
+    GetSecuritySchemeApiKeysRequest, ResolveComponentRequest, UpdateWorkerConnectionLimitRequest,
+    UpdateWorkerLimitRequest, authenticate_token_response, batch_update_fuel_usage_response,
+    download_component_response, get_active_routes_for_domain_response, get_agent_type_response,
+    get_all_agent_types_response, get_all_deployed_component_revisions_response,
+    get_auth_details_for_environment_response, get_component_metadata_response,
+    get_deployed_component_metadata_response, get_resource_limits_response,
+    get_security_scheme_api_keys_response, resolve_component_response,
     resolve_latest_agent_type_by_names_response, update_worker_connection_limit_response,
     update_worker_limit_response,
 };
@@ -43,6 +44,7 @@ use golem_common::model::component::ComponentDto;
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::domain_registration::Domain;
 use golem_common::model::environment::{EnvironmentId, EnvironmentName};
+use golem_common::model::security_scheme::SecuritySchemeId;
 use golem_common::{IntoAnyhow, SafeDisplay, grpc_uri};
 use http::Uri;
 use serde::{Deserialize, Serialize};
@@ -152,6 +154,12 @@ pub trait RegistryService: Send + Sync {
         domain: &Domain,
     ) -> Result<CompiledRoutes, RegistryServiceError>;
 
+    /// Keys of an api-key security scheme that were not revoked, including expired ones
+    async fn get_security_scheme_api_keys(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+    ) -> Result<Vec<SecuritySchemeApiKey>, RegistryServiceError>;
+
     async fn resolve_latest_agent_type_by_names(
         &self,
         account_id: &AccountId,
@@ -666,6 +674,35 @@ impl RegistryService for GrpcRegistryService {
         }
     }
 
+    async fn get_security_scheme_api_keys(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+    ) -> Result<Vec<SecuritySchemeApiKey>, RegistryServiceError> {
+        let response = self
+            .client
+            .call("get_security_scheme_api_keys", move |client| {
+                let request = GetSecuritySchemeApiKeysRequest {
+                    security_scheme_id: Some(security_scheme_id.into()),
+                };
+                Box::pin(client.get_security_scheme_api_keys(request))
+            })
+            .await?
+            .into_inner();
+
+        match response.result {
+            None => Err(RegistryServiceError::empty_response()),
+            Some(get_security_scheme_api_keys_response::Result::Success(payload)) => {
+                let converted = payload
+                    .api_keys
+                    .into_iter()
+                    .map(SecuritySchemeApiKey::try_from)
+                    .collect::<Result<_, _>>()?;
+                Ok(converted)
+            }
+            Some(get_security_scheme_api_keys_response::Result::Error(error)) => Err(error.into()),
+        }
+    }
+
     async fn resolve_latest_agent_type_by_names(
         &self,
         account_id: &AccountId,
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// pub mod openapi;
+pub mod openapi;
 mod protobuf;
 
 use crate::model::SafeIndex;
@@ -22,15 +22,23 @@ use golem_common::model::agent::{AgentTypeName, DataSchema, HttpMethod};
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::deployment::DeploymentRevision;
 use golem_common::model::environment::EnvironmentId;
-use golem_common::model::security_scheme::{Provider, SecuritySchemeId, SecuritySchemeName};
+use golem_common::model::http_api_deployment::{HttpRateLimit, HttpRateLimitScope};
+use golem_common::model::security_scheme::{
+    ApiKeyId, Provider, SecuritySchemeId, SecuritySchemeMode, SecuritySchemeName,
+};
 use golem_wasm::analysis::analysed_type;
 use golem_wasm::analysis::{AnalysedType, TypeList, TypeOption};
-use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
+use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl, Scope};
 use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 
 pub type RouteId = i32;
 
+/// Header carrying the key for api-key security schemes
+pub const API_KEY_HEADER: &str = "X-API-Key";
+/// Query parameter accepted instead of the header, for clients that cannot set headers
+pub const API_KEY_QUERY_PARAM: &str = "api_key";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
 #[desert(evolution())]
 pub enum PathSegment {
@@ -233,7 +241,10 @@ pub struct CompiledRoute {
     pub body: RequestBodySchema,
     pub behavior: RouteBehaviour,
     pub security_scheme: Option<SecuritySchemeId>,
+    /// Scopes bearer tokens have to grant, declared by the endpoint and its mount
+    pub required_scopes: Vec<String>,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RateLimit>,
 }
 
 #[derive(Debug, BinaryCodec)]
@@ -264,14 +275,59 @@ pub struct CorsPreflightBehaviour {
 }
 
 #[derive(Debug, Clone)]
-pub struct SecuritySchemeDetails {
+pub enum SecuritySchemeDetails {
+    OpenIdConnect(OidcSecuritySchemeDetails),
+    ApiKey(ApiKeySecuritySchemeDetails),
+}
+
+impl SecuritySchemeDetails {
+    pub fn id(&self) -> SecuritySchemeId {
+        match self {
+            Self::OpenIdConnect(details) => details.id,
+            Self::ApiKey(details) => details.id,
+        }
+    }
+
+    pub fn name(&self) -> &SecuritySchemeName {
+        match self {
+            Self::OpenIdConnect(details) => &details.name,
+            Self::ApiKey(details) => &details.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcSecuritySchemeDetails {
     pub id: SecuritySchemeId,
     pub name: SecuritySchemeName,
     pub provider_type: Provider,
+    pub issuer_url: IssuerUrl,
     pub client_id: ClientId,
     pub client_secret: ClientSecret,
     pub redirect_url: RedirectUrl,
     pub scopes: Vec<Scope>,
+    pub mode: SecuritySchemeMode,
+    /// Audience bearer tokens have to be issued for
+    pub audience: Audience,
+}
+
+/// Security scheme accepting the api keys issued for it by the registry
+#[derive(Debug, Clone)]
+pub struct ApiKeySecuritySchemeDetails {
+    pub id: SecuritySchemeId,
+    pub name: SecuritySchemeName,
+}
+
+/// Key accepted by an api-key security scheme. Looked up separately from the compiled routes,
+/// so revoking a key does not have to wait for the routes of the domain to be refreshed.
+#[derive(Debug, Clone)]
+pub struct SecuritySchemeApiKey {
+    pub id: ApiKeyId,
+    pub name: String,
+    /// Only the hash of the secret is known outside of the response creating the key
+    pub secret_hash: String,
+    pub scopes: Vec<Scope>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, BinaryCodec)]
@@ -280,6 +336,51 @@ pub struct CorsOptions {
     pub allowed_patterns: Vec<OriginPattern>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, BinaryCodec)]
+#[desert(evolution())]
+pub struct RateLimit {
+    pub scope: RateLimitScope,
+    pub requests: u32,
+    pub interval_seconds: u32,
+    /// Capacity of the token bucket
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn refill_per_second(&self) -> f64 {
+        self.requests as f64 / self.interval_seconds as f64
+    }
+}
+
+impl From<HttpRateLimit> for RateLimit {
+    fn from(value: HttpRateLimit) -> Self {
+        Self {
+            scope: value.scope.into(),
+            requests: value.requests,
+            interval_seconds: value.interval_seconds,
+            burst: value.burst.unwrap_or(value.requests),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, BinaryCodec)]
+#[desert(evolution())]
+pub enum RateLimitScope {
+    Route,
+    Principal,
+    ClientIp,
+}
+
+impl From<HttpRateLimitScope> for RateLimitScope {
+    fn from(value: HttpRateLimitScope) -> Self {
+        match value {
+            HttpRateLimitScope::Route => Self::Route,
+            HttpRateLimitScope::Principal => Self::Principal,
+            HttpRateLimitScope::ClientIp => Self::ClientIp,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, BinaryCodec)]
 #[desert(transparent)]
 // Note: Wildcards are only considered during matching. When setting the allow-origin header
@@ -1,7 +1,7 @@
 // Copyright 2024-2025 Golem Cloud
 //
 // Licensed under the Golem Source License v1.0 (the "License");
-// You may not use this file except in compliance with the License.
+// you may not use this file except in compliance with the License.
 // You may obtain a copy of the License at
 //
 //     http://license.golem.cloud/LICENSE
@@ -9,1006 +9,1121 @@
 // Unless required by applicable law or agreed to in writing, software
 // distributed under the License is distributed on an "AS IS" BASIS,
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
 
-use super::RouteBehaviour;
-use super::path_pattern::AllPathPatterns;
-use super::{CompiledRoute, SecuritySchemeDetails};
-use golem_common::model::http_api_definition::{
-    HttpApiDefinitionName, HttpApiDefinitionVersion, RouteMethod,
+use super::{
+    API_KEY_HEADER, API_KEY_QUERY_PARAM, CallAgentBehaviour, CompiledRoute, CompiledRoutes,
+    ConstructorParameter, MethodParameter, OidcSecuritySchemeDetails, PathSegment, PathSegmentType,
+    QueryOrHeaderType, RequestBodySchema, RouteBehaviour, SecuritySchemeDetails,
 };
-use golem_common::model::security_scheme::SecuritySchemeId;
-use golem_wasm::analysis::{AnalysedType, NameTypePair};
-use indexmap::IndexMap;
-use rib::RibInputTypeInfo;
-use std::collections::{BTreeMap, HashMap};
-
-// Constants for OpenAPI extensions
-const GOLEM_DISABLED_EXTENSION: &str = "x-golem-disabled";
-
-/// Trait that any route type must implement to be used for OpenAPI generation
-pub trait HttpApiRoute {
-    fn security_scheme_missing(&self) -> bool;
-    fn security_scheme(&self) -> Option<SecuritySchemeId>;
-    fn method(&self) -> &RouteMethod;
-    fn path(&self) -> &AllPathPatterns;
-    fn binding(&self) -> &RouteBehaviour;
-}
-
-impl HttpApiRoute for CompiledRoute {
-    fn security_scheme_missing(&self) -> bool {
-        false
-    }
-    fn security_scheme(&self) -> Option<SecuritySchemeId> {
-        self.security_scheme
-    }
-    fn method(&self) -> &RouteMethod {
-        &self.method
-    }
-    fn path(&self) -> &AllPathPatterns {
-        &self.path
-    }
-    fn binding(&self) -> &RouteBehaviour {
-        &self.binding
-    }
-}
-
-/// Main wrapper type
-pub struct HttpApiDefinitionOpenApiSpec(pub openapiv3::OpenAPI);
-
-impl HttpApiDefinitionOpenApiSpec {
-    pub async fn from_routes<'a, T, I>(
-        http_api_definition_name: &HttpApiDefinitionName,
-        http_api_definition_version: &HttpApiDefinitionVersion,
-        routes: I,
-        security_schemes: &HashMap<SecuritySchemeId, SecuritySchemeDetails>,
-    ) -> Result<Self, String>
-    where
-        T: 'a + HttpApiRoute + ?Sized,
-        I: IntoIterator<Item = &'a T>,
-    {
-        let mut open_api = create_base_openapi(
-            http_api_definition_name,
-            http_api_definition_version,
-            security_schemes,
-        );
+use golem_common::model::agent::{
+    BinaryDescriptor, ComponentModelElementSchema, DataSchema, ElementSchema, HttpMethod,
+    NamedElementSchemas,
+};
+use golem_common::model::security_scheme::{SecuritySchemeId, SecuritySchemeMode};
+use golem_wasm::analysis::AnalysedType;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Path segments of the well-known path the OpenAPI document of a domain is served on
+pub const OPENAPI_SPEC_PATH: [&str; 2] = [".well-known", "openapi.json"];
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// OpenAPI 3.1 document describing the agent HTTP routes deployed to a domain.
+///
+/// The document is built as plain JSON, because OpenAPI 3.1 schema objects are JSON Schema
+/// 2020-12 schemas, which the OpenAPI 3.0 models available for Rust cannot express.
+pub struct AgentHttpApiOpenApiSpec(pub Value);
+
+impl AgentHttpApiOpenApiSpec {
+    /// Builds the document from the compiled routes of a deployment. CORS preflight routes are
+    /// not part of the contract, and routes with methods OpenAPI cannot describe are skipped.
+    pub fn from_compiled_routes(domain: &str, compiled_routes: &CompiledRoutes) -> Self {
+        let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+        let mut operation_ids = HashSet::new();
+
+        for route in &compiled_routes.routes {
+            let RouteBehaviour::CallAgent(behaviour) = &route.behavior else {
+                continue;
+            };
+            let Some(method) = path_item_method(&route.method) else {
+                continue;
+            };
 
-        let mut paths = BTreeMap::new();
-        for route in routes {
-            add_route_to_paths(route, &mut paths, security_schemes).await?;
+            let operation = create_operation(
+                route,
+                behaviour,
+                &compiled_routes.security_schemes,
+                &mut operation_ids,
+            );
+            paths
+                .entry(path_template(&route.path))
+                .or_default()
+                .insert(method.to_string(), operation);
         }
 
-        open_api.paths.paths = paths
-            .into_iter()
-            .map(|(k, v)| (k, openapiv3::ReferenceOr::Item(v)))
-            .collect();
-
-        Ok(HttpApiDefinitionOpenApiSpec(open_api))
+        AgentHttpApiOpenApiSpec(json!({
+            "openapi": OPENAPI_VERSION,
+            "info": {
+                "title": domain,
+                "version": compiled_routes.deployment_revision.to_string(),
+            },
+            "paths": paths,
+            "components": create_components(&compiled_routes.security_schemes),
+        }))
     }
 }
 
 // --------------------- Base OpenAPI structure ---------------------
 
-fn create_base_openapi(
-    http_api_definition_name: &HttpApiDefinitionName,
-    http_api_definition_version: &HttpApiDefinitionVersion,
-    security_schemes: &HashMap<SecuritySchemeId, SecuritySchemeDetails>,
-) -> openapiv3::OpenAPI {
-    let mut open_api = openapiv3::OpenAPI {
-        openapi: "3.0.0".to_string(),
-        info: openapiv3::Info {
-            title: http_api_definition_name.0.to_string(),
-            description: None,
-            terms_of_service: None,
-            contact: None,
-            license: None,
-            version: http_api_definition_version.0.to_string(),
-            extensions: Default::default(),
-        },
-        ..Default::default()
-    };
+fn create_components(security_schemes: &HashMap<SecuritySchemeId, SecuritySchemeDetails>) -> Value {
+    let mut sorted_schemes = security_schemes.values().collect::<Vec<_>>();
+    sorted_schemes.sort_by(|a, b| a.name().0.cmp(&b.name().0));
 
-    let components = openapiv3::Components::default();
-    let mut spec_security_schemes = components.security_schemes;
-    for security_scheme in security_schemes.values() {
-        let openid_config_url = format!(
-            "{}/.well-known/openid-configuration",
-            security_scheme.provider_type.issuer_url().url()
-        );
+    let security_schemes = sorted_schemes
+        .into_iter()
+        .map(|security_scheme| {
+            let scheme = match security_scheme {
+                SecuritySchemeDetails::OpenIdConnect(security_scheme) => {
+                    oidc_security_scheme(security_scheme)
+                }
+                SecuritySchemeDetails::ApiKey(security_scheme) => json!({
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": API_KEY_HEADER,
+                    "description": format!(
+                        "API key issued for {}, alternatively sent in the {API_KEY_QUERY_PARAM} query parameter",
+                        security_scheme.name
+                    ),
+                }),
+            };
+            (security_scheme.name().0.clone(), scheme)
+        })
+        .collect::<Map<_, _>>();
 
-        let scheme = openapiv3::SecurityScheme::OpenIDConnect {
-            open_id_connect_url: openid_config_url,
-            description: Some(format!(
+    json!({ "securitySchemes": security_schemes })
+}
+
+fn oidc_security_scheme(security_scheme: &OidcSecuritySchemeDetails) -> Value {
+    match security_scheme.mode {
+        SecuritySchemeMode::AuthorizationCode => json!({
+            "type": "openIdConnect",
+            "openIdConnectUrl": format!(
+                "{}/.well-known/openid-configuration",
+                security_scheme
+                    .issuer_url
+                    .url()
+                    .as_str()
+                    .trim_end_matches('/')
+            ),
+            "description": format!(
                 "OpenID Connect provider for {}",
                 security_scheme.name
-            )),
-            extensions: Default::default(),
-        };
-
-        spec_security_schemes.insert(
-            security_scheme.name.0.clone(),
-            openapiv3::ReferenceOr::Item(scheme),
-        );
+            ),
+        }),
+        SecuritySchemeMode::BearerToken => json!({
+            "type": "http",
+            "scheme": "bearer",
+            "bearerFormat": "JWT",
+            "description": format!(
+                "JWT issued by {} for audience {}",
+                security_scheme.issuer_url.as_str(),
+                security_scheme.audience.as_str()
+            ),
+        }),
     }
-
-    open_api.components = Some(openapiv3::Components::default());
-    open_api
 }
 
 // --------------------- Process routes ---------------------
 
-async fn add_route_to_paths<T: HttpApiRoute + ?Sized>(
-    route: &T,
-    paths: &mut BTreeMap<String, openapiv3::PathItem>,
+fn create_operation(
+    route: &CompiledRoute,
+    behaviour: &CallAgentBehaviour,
     security_schemes: &HashMap<SecuritySchemeId, SecuritySchemeDetails>,
-) -> Result<(), String> {
-    let path_str = route.path().to_string();
-    let path_item = paths.entry(path_str.clone()).or_default();
+    operation_ids: &mut HashSet<String>,
+) -> Value {
+    let mut operation_id = format!("{}-{}", behaviour.agent_type, behaviour.method_name);
+    if operation_ids.contains(&operation_id) {
+        operation_id = format!("{operation_id}-{}", route.route_id);
+    }
+    operation_ids.insert(operation_id.clone());
+
+    let mut operation = Map::new();
+    operation.insert("tags".to_string(), json!([behaviour.agent_type.0]));
+    operation.insert(
+        "summary".to_string(),
+        json!(format!(
+            "Invokes {} of agent {}",
+            behaviour.method_name, behaviour.agent_type
+        )),
+    );
+    operation.insert("operationId".to_string(), json!(operation_id));
+    operation.insert(
+        "parameters".to_string(),
+        Value::Array(create_parameters(route, behaviour)),
+    );
+    if let Some(request_body) = create_request_body(&route.body) {
+        operation.insert("requestBody".to_string(), request_body);
+    }
 
-    let mut operation = openapiv3::Operation::default();
+    let mut responses = create_responses(&behaviour.expected_agent_response);
+    if !route.rate_limits.is_empty() {
+        responses.insert(
+            "429".to_string(),
+            response("Rate limit exceeded, retry after Retry-After seconds", None),
+        );
+    }
+    operation.insert("responses".to_string(), Value::Object(responses));
 
-    add_parameters(&mut operation, route);
-    add_request_body(&mut operation, route);
-    add_responses(&mut operation, route);
     add_security(&mut operation, route, security_schemes);
 
-    add_operation_to_path_item(path_item, route.method(), operation)?;
+    Value::Object(operation)
+}
 
-    Ok(())
+/// Path variables are not named in compiled routes, they are referred to by their position
+fn path_variable_name(variable_index: usize) -> String {
+    format!("param{variable_index}")
 }
 
-// --------------------- Parameters ---------------------
+fn path_template(path: &[PathSegment]) -> String {
+    let mut variable_index = 0;
+    let segments = path
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Literal { value } => value.clone(),
+            PathSegment::Variable | PathSegment::CatchAll => {
+                let name = path_variable_name(variable_index);
+                variable_index += 1;
+                format!("{{{name}}}")
+            }
+        })
+        .collect::<Vec<_>>();
 
-type ParameterTuple = (String, openapiv3::Schema);
+    format!("/{}", segments.join("/"))
+}
 
-fn add_parameters<T: HttpApiRoute + ?Sized>(operation: &mut openapiv3::Operation, route: &T) {
-    let (path_params, query_params, header_params) = get_parameters(route);
+// --------------------- Parameters ---------------------
 
-    for (name, schema) in path_params {
-        operation
-            .parameters
-            .push(openapiv3::ReferenceOr::Item(create_path_parameter(
-                &name, schema,
-            )));
-    }
-    for (name, schema) in query_params {
-        operation
-            .parameters
-            .push(openapiv3::ReferenceOr::Item(create_query_parameter(
-                &name, schema,
-            )));
-    }
-    for (name, schema) in header_params {
-        operation
-            .parameters
-            .push(openapiv3::ReferenceOr::Item(create_header_parameter(
-                &name, schema,
-            )));
+fn create_parameters(route: &CompiledRoute, behaviour: &CallAgentBehaviour) -> Vec<Value> {
+    let mut path_variable_types: HashMap<usize, &PathSegmentType> = HashMap::new();
+    for param in &behaviour.constructor_parameters {
+        match param {
+            ConstructorParameter::Path {
+                path_segment_index,
+                parameter_type,
+            } => {
+                path_variable_types.insert(usize::from(*path_segment_index), parameter_type);
+            }
+        }
     }
-}
 
-fn get_parameters<T: HttpApiRoute + ?Sized>(
-    route: &T,
-) -> (
-    Vec<ParameterTuple>,
-    Vec<ParameterTuple>,
-    Vec<ParameterTuple>,
-) {
-    let mut path_params = Vec::new();
     let mut query_params = Vec::new();
     let mut header_params = Vec::new();
-
-    match route.binding() {
-        RouteBehaviour::Worker(worker) => {
-            if let Some(request) = worker
-                .idempotency_key_compiled
-                .as_ref()
-                .and_then(|c| c.rib_input.types.get("request"))
-            {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-            if let Some(request) = worker
-                .invocation_context_compiled
-                .as_ref()
-                .and_then(|c| c.rib_input.types.get("request"))
-            {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-            if let Some(request) = worker.response_compiled.rib_input.types.get("request") {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-        }
-        RouteBehaviour::FileServer(file_server) => {
-            if let Some(request) = file_server
-                .worker_name_compiled
-                .rib_input
-                .types
-                .get("request")
-            {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-            if let Some(request) = file_server.response_compiled.rib_input.types.get("request") {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-        }
-        RouteBehaviour::HttpHandler(handler) => {
-            if let Some(request) = handler.worker_name_compiled.rib_input.types.get("request") {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-            if let Some(request) = handler
-                .idempotency_key_compiled
-                .as_ref()
-                .and_then(|c| c.rib_input.types.get("request"))
-            {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
-            }
-            if let Some(request) = handler
-                .invocation_context_compiled
-                .as_ref()
-                .and_then(|c| c.rib_input.types.get("request"))
-            {
-                extract_parameters_from_record(
-                    request,
-                    &mut path_params,
-                    &mut query_params,
-                    &mut header_params,
-                );
+    for param in &behaviour.method_parameters {
+        match param {
+            MethodParameter::Path {
+                path_segment_index,
+                parameter_type,
+            } => {
+                path_variable_types.insert(usize::from(*path_segment_index), parameter_type);
             }
+            MethodParameter::Query {
+                query_parameter_name,
+                parameter_type,
+            } => query_params.push(create_query_parameter(query_parameter_name, parameter_type)),
+            MethodParameter::Header {
+                header_name,
+                parameter_type,
+            } => header_params.push(create_header_parameter(header_name, parameter_type)),
+            MethodParameter::JsonObjectBodyField { .. }
+            | MethodParameter::UnstructuredBinaryBody => {}
         }
-        RouteBehaviour::HttpCorsPreflight(_) => {}
-        RouteBehaviour::SwaggerUi(_) => {}
     }
 
-    (path_params, query_params, header_params)
+    let path_params = route
+        .path
+        .iter()
+        .filter(|segment| !matches!(segment, PathSegment::Literal { .. }))
+        .enumerate()
+        .map(|(variable_index, segment)| {
+            create_path_parameter(
+                variable_index,
+                matches!(segment, PathSegment::CatchAll),
+                path_variable_types.get(&variable_index).copied(),
+            )
+        });
+
+    path_params
+        .chain(query_params)
+        .chain(header_params)
+        .collect()
 }
 
-fn extract_parameters_from_record(
-    record: &AnalysedType,
-    path_parameters: &mut Vec<ParameterTuple>,
-    query_parameters: &mut Vec<ParameterTuple>,
-    header_parameters: &mut Vec<ParameterTuple>,
-) {
-    if let AnalysedType::Record(rec) = record {
-        for field_name in ["path", "query", "headers"] {
-            if let Some(field) = rec.fields.iter().find(|f| f.name == field_name)
-                && let AnalysedType::Record(sub_rec) = &field.typ
-            {
-                for sub_field in &sub_rec.fields {
-                    let schema = create_schema_from_analysed_type(&sub_field.typ);
-                    match field_name {
-                        "path" => path_parameters.push((sub_field.name.clone(), schema)),
-                        "query" => query_parameters.push((sub_field.name.clone(), schema)),
-                        "headers" => header_parameters.push((sub_field.name.clone(), schema)),
-                        _ => {}
-                    }
-                }
-            }
-        }
-    }
+fn create_path_parameter(
+    variable_index: usize,
+    catch_all: bool,
+    parameter_type: Option<&PathSegmentType>,
+) -> Value {
+    let schema = parameter_type
+        .map(path_segment_schema)
+        .unwrap_or_else(string_schema);
+    let description = if catch_all {
+        "Remaining path, may contain multiple segments"
+    } else {
+        "Path segment"
+    };
+
+    json!({
+        "name": path_variable_name(variable_index),
+        "in": "path",
+        "description": description,
+        "required": true,
+        "style": "simple",
+        "schema": schema,
+    })
 }
 
-fn create_path_parameter(name: &str, schema: openapiv3::Schema) -> openapiv3::Parameter {
-    openapiv3::Parameter::Path {
-        parameter_data: openapiv3::ParameterData {
-            name: name.to_string(),
-            description: Some(format!("Path parameter: {name}")),
-            required: true,
-            deprecated: None,
-            explode: Some(false),
-            format: openapiv3::ParameterSchemaOrContent::Schema(openapiv3::ReferenceOr::Item(
-                schema,
-            )),
-            example: None,
-            examples: Default::default(),
-            extensions: Default::default(),
-        },
-        style: openapiv3::PathStyle::Simple,
-    }
+fn create_query_parameter(name: &str, parameter_type: &QueryOrHeaderType) -> Value {
+    let (required, schema) = query_or_header_schema(parameter_type);
+
+    json!({
+        "name": name,
+        "in": "query",
+        "description": "Query parameter",
+        "required": required,
+        "style": "form",
+        // lists are passed as repeated query parameters
+        "explode": true,
+        "schema": schema,
+    })
 }
 
-fn create_query_parameter(name: &str, schema: openapiv3::Schema) -> openapiv3::Parameter {
-    openapiv3::Parameter::Query {
-        parameter_data: openapiv3::ParameterData {
-            name: name.to_string(),
-            description: Some(format!("Query parameter: {name}")),
-            required: true,
-            deprecated: None,
-            explode: Some(false),
-            format: openapiv3::ParameterSchemaOrContent::Schema(openapiv3::ReferenceOr::Item(
-                schema,
-            )),
-            example: None,
-            examples: Default::default(),
-            extensions: Default::default(),
-        },
-        style: openapiv3::QueryStyle::Form,
-        allow_empty_value: Some(false),
-        allow_reserved: false,
+fn create_header_parameter(name: &str, parameter_type: &QueryOrHeaderType) -> Value {
+    let (required, schema) = query_or_header_schema(parameter_type);
+
+    json!({
+        "name": name,
+        "in": "header",
+        "description": "Header parameter",
+        "required": required,
+        "style": "simple",
+        "schema": schema,
+    })
+}
+
+/// Returns whether the parameter is required, and the schema of its value
+fn query_or_header_schema(parameter_type: &QueryOrHeaderType) -> (bool, Value) {
+    match parameter_type {
+        QueryOrHeaderType::Primitive(inner) => (true, path_segment_schema(inner)),
+        QueryOrHeaderType::Option { inner, .. } => (false, path_segment_schema(inner)),
+        QueryOrHeaderType::List { inner, .. } => (false, array_schema(path_segment_schema(inner))),
     }
 }
 
-fn create_header_parameter(name: &str, schema: openapiv3::Schema) -> openapiv3::Parameter {
-    openapiv3::Parameter::Header {
-        parameter_data: openapiv3::ParameterData {
-            name: name.to_string(),
-            description: Some(format!("Header parameter: {name}")),
-            required: true,
-            deprecated: None,
-            explode: Some(false),
-            format: openapiv3::ParameterSchemaOrContent::Schema(openapiv3::ReferenceOr::Item(
-                schema,
-            )),
-            example: None,
-            examples: Default::default(),
-            extensions: Default::default(),
-        },
-        style: openapiv3::HeaderStyle::Simple,
+/// Schema of a value parsed from its textual representation in a path, query or header
+fn path_segment_schema(parameter_type: &PathSegmentType) -> Value {
+    match parameter_type {
+        PathSegmentType::Chr => json!({
+            "type": "string",
+            "description": "Unicode character",
+            "minLength": 1,
+            "maxLength": 1,
+        }),
+        other => json_schema(&AnalysedType::from(other.clone())),
     }
 }
 
 // --------------------- Request body ---------------------
 
-fn add_request_body<T: HttpApiRoute + ?Sized>(operation: &mut openapiv3::Operation, route: &T) {
-    match route.binding() {
-        RouteBehaviour::Worker(worker) => {
-            if let Some(rb) = create_request_body(&worker.response_compiled.rib_input) {
-                operation.request_body = Some(openapiv3::ReferenceOr::Item(rb));
-            }
-        }
-        RouteBehaviour::FileServer(file_server) => {
-            if let Some(rb) = create_request_body(&file_server.response_compiled.rib_input) {
-                operation.request_body = Some(openapiv3::ReferenceOr::Item(rb));
-            }
+fn create_request_body(body: &RequestBodySchema) -> Option<Value> {
+    let content = match body {
+        RequestBodySchema::Unused => return None,
+        RequestBodySchema::JsonBody { expected_type } => json_content(expected_type),
+        RequestBodySchema::UnrestrictedBinary => binary_content(["*/*"]),
+        RequestBodySchema::RestrictedBinary { allowed_mime_types } => {
+            binary_content(allowed_mime_types.iter().map(String::as_str))
         }
-        RouteBehaviour::HttpHandler(_) => {}
-        RouteBehaviour::HttpCorsPreflight(_) => {}
-        RouteBehaviour::SwaggerUi(_) => {}
-    }
-}
-
-fn create_request_body(rib: &RibInputTypeInfo) -> Option<openapiv3::RequestBody> {
-    if let Some(schema) = determine_request_body_schema(rib) {
-        let mut content = IndexMap::new();
-        content.insert(
-            "application/json".to_string(),
-            openapiv3::MediaType {
-                schema: Some(openapiv3::ReferenceOr::Item(schema)),
-                ..Default::default()
-            },
-        );
-        Some(openapiv3::RequestBody {
-            description: Some("Request payload".to_string()),
-            content,
-            required: true,
-            extensions: Default::default(),
-        })
-    } else {
-        None
-    }
-}
+    };
 
-fn determine_request_body_schema(rib: &RibInputTypeInfo) -> Option<openapiv3::Schema> {
-    if let Some(AnalysedType::Record(req)) = rib.types.get("request")
-        && let Some(body) = req.fields.iter().find(|f| f.name == "body")
-    {
-        return Some(create_schema_from_analysed_type(&body.typ));
-    }
-    None
+    Some(json!({
+        "description": "Request payload",
+        "content": content,
+        "required": true,
+    }))
 }
 
 // --------------------- Responses ---------------------
 
-fn add_responses<T: HttpApiRoute + ?Sized>(operation: &mut openapiv3::Operation, route: &T) {
-    let (response_schema, headers, explicit_status) =
-        determine_response_schema_content_type_headers(route);
-
-    let mut response = openapiv3::Response {
-        description: "Response".to_string(),
-        ..Default::default()
-    };
+/// Describes the responses the agent's result is mapped to when the method returns
+fn create_responses(expected_response: &DataSchema) -> Map<String, Value> {
+    let mut responses = Map::new();
 
-    match response_schema {
-        DeterminedResponseBodySchema::Known {
-            schema,
-            content_type,
-        } => {
-            let mut content = IndexMap::new();
-            content.insert(
-                content_type.clone(),
-                openapiv3::MediaType {
-                    schema: Some(openapiv3::ReferenceOr::Item(*schema)),
-                    ..Default::default()
-                },
-            );
-            response.content = content;
-        }
-        DeterminedResponseBodySchema::Unknown => {
-            let mut content = IndexMap::new();
-            content.insert(
-                "*/*".to_string(),
-                openapiv3::MediaType {
-                    ..Default::default()
-                },
-            );
-            response.content = content;
-        }
-        DeterminedResponseBodySchema::NoBody => {
-            response.content = IndexMap::new(); // explicitly no body
+    match expected_response {
+        DataSchema::Tuple(NamedElementSchemas { elements }) if elements.is_empty() => {
+            responses.insert("204".to_string(), response("No content", None));
         }
-    }
-
-    // Add headers if any
-    if let Some(headers_map) = headers {
-        let mut response_headers = IndexMap::new();
-        for (name, schema) in headers_map {
-            let description = format!("Response header: {}", name);
-            response_headers.insert(
-                name.clone(),
-                openapiv3::ReferenceOr::Item(openapiv3::Header {
-                    description: Some(description),
-                    required: true,
-                    deprecated: None,
-                    format: openapiv3::ParameterSchemaOrContent::Schema(
-                        openapiv3::ReferenceOr::Item(schema),
-                    ),
-                    example: None,
-                    examples: Default::default(),
-                    extensions: Default::default(),
-                    style: openapiv3::HeaderStyle::Simple,
-                }),
-            );
+        DataSchema::Tuple(NamedElementSchemas { elements }) if elements.len() == 1 => {
+            match &elements[0].schema {
+                ElementSchema::ComponentModel(ComponentModelElementSchema { element_type }) => {
+                    add_component_model_responses(&mut responses, element_type)
+                }
+                ElementSchema::UnstructuredBinary(BinaryDescriptor { restrictions }) => {
+                    let content = match restrictions {
+                        Some(binary_types) => binary_content(
+                            binary_types
+                                .iter()
+                                .map(|binary_type| binary_type.mime_type.as_str()),
+                        ),
+                        None => binary_content(["*/*"]),
+                    };
+                    responses.insert(
+                        "200".to_string(),
+                        response("Binary response", Some(content)),
+                    );
+                }
+                ElementSchema::UnstructuredText(_) => {}
+            }
         }
-        response.headers = response_headers;
+        _ => {}
     }
 
-    // Insert response: explicit status if given, otherwise default
-    if let Some(status) = explicit_status {
-        operation.responses.responses.insert(
-            openapiv3::StatusCode::Code(status),
-            openapiv3::ReferenceOr::Item(response),
-        );
-    } else {
-        operation.responses.default = Some(openapiv3::ReferenceOr::Item(response));
-    }
-}
+    // Custom agent errors
+    responses
+        .entry("500")
+        .or_insert_with(|| response("Agent error", Some(any_json_content())));
 
-enum DeterminedResponseBodySchema {
-    Unknown,
-    Known {
-        schema: Box<openapiv3::Schema>,
-        content_type: String,
-    },
-    NoBody,
+    responses
 }
 
-fn determine_response_schema_content_type_headers<T: HttpApiRoute + ?Sized>(
-    route: &T,
-) -> (
-    DeterminedResponseBodySchema,
-    Option<IndexMap<String, openapiv3::Schema>>,
-    Option<u16>,
-) {
-    match route.binding() {
-        RouteBehaviour::Worker(worker) => {
-            if let Some(output_info) = &worker.response_compiled.rib_output
-                && let AnalysedType::Record(record) = &output_info.analysed_type
-            {
-                let (headers, body) = extract_response_header_and_body_types(&record.fields);
-                let headers_schema = headers.map(|h| {
-                    let mut map = IndexMap::new();
-                    if let AnalysedType::Record(r) = h {
-                        for f in &r.fields {
-                            map.insert(f.name.clone(), create_schema_from_analysed_type(&f.typ));
-                        }
-                    }
-                    map
-                });
-
-                let response_schema = body
-                    .map(|b| DeterminedResponseBodySchema::Known {
-                        schema: Box::new(create_schema_from_analysed_type(&b)),
-                        content_type: "application/json".to_string(),
-                    })
-                    .unwrap_or(DeterminedResponseBodySchema::NoBody);
-
-                return (response_schema, headers_schema, None);
-            }
-            (DeterminedResponseBodySchema::Unknown, None, None)
-        }
-        RouteBehaviour::SwaggerUi(_) => {
-            let schema = create_schema_from_analysed_type(&AnalysedType::Str(
-                golem_wasm::analysis::TypeStr {},
-            ));
-            (
-                DeterminedResponseBodySchema::Known {
-                    schema: Box::new(schema),
-                    content_type: "text/html".to_string(),
-                },
-                None,
-                Some(200),
-            )
+fn add_component_model_responses(responses: &mut Map<String, Value>, element_type: &AnalysedType) {
+    match element_type {
+        AnalysedType::Option(option) => {
+            responses.insert(
+                "200".to_string(),
+                response("Successful response", Some(json_content(&option.inner))),
+            );
+            responses.insert("404".to_string(), response("Not found", None));
         }
-        RouteBehaviour::HttpCorsPreflight(_) => {
-            let mut headers = IndexMap::new();
-            let string_schema = openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                    openapiv3::StringType {
-                        format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                        pattern: None,
-                        enumeration: vec![],
-                        min_length: None,
-                        max_length: None,
-                    },
-                )),
+        AnalysedType::Result(result) => {
+            match &result.ok {
+                Some(ok) => responses.insert(
+                    "200".to_string(),
+                    response("Successful response", Some(json_content(ok))),
+                ),
+                None => responses.insert("204".to_string(), response("No content", None)),
             };
-            for h in [
-                "Access-Control-Allow-Origin",
-                "Access-Control-Allow-Headers",
-                "Access-Control-Allow-Methods",
-            ] {
-                headers.insert(h.to_string(), string_schema.clone());
-            }
-            (
-                DeterminedResponseBodySchema::NoBody,
-                Some(headers),
-                Some(200),
-            )
+            let err_content = result.err.as_ref().map(|err| json_content(err));
+            responses.insert("500".to_string(), response("Error response", err_content));
+        }
+        other => {
+            responses.insert(
+                "200".to_string(),
+                response("Successful response", Some(json_content(other))),
+            );
         }
-        _ => (DeterminedResponseBodySchema::Unknown, None, None),
     }
 }
 
-fn extract_response_header_and_body_types(
-    fields: &[NameTypePair],
-) -> (Option<AnalysedType>, Option<AnalysedType>) {
-    let mut headers = None;
-    let mut body = None;
-
-    for f in fields {
-        match f.name.as_str() {
-            "headers" => headers = Some(f.typ.clone()),
-            "body" => body = Some(f.typ.clone()),
-            _ => {}
-        }
+fn response(description: &str, content: Option<Value>) -> Value {
+    match content {
+        Some(content) => json!({ "description": description, "content": content }),
+        None => json!({ "description": description }),
     }
+}
+
+fn json_content(typ: &AnalysedType) -> Value {
+    json!({ "application/json": { "schema": json_schema(typ) } })
+}
 
-    (headers, body)
+fn any_json_content() -> Value {
+    json!({ "application/json": {} })
+}
+
+fn binary_content<'a>(mime_types: impl IntoIterator<Item = &'a str>) -> Value {
+    Value::Object(
+        mime_types
+            .into_iter()
+            .map(|mime_type| (mime_type.to_string(), json!({})))
+            .collect(),
+    )
 }
 
 // --------------------- Security ---------------------
 
-fn add_security<T: HttpApiRoute + ?Sized>(
-    operation: &mut openapiv3::Operation,
-    route: &T,
-    security_schemes_map: &HashMap<SecuritySchemeId, SecuritySchemeDetails>,
+fn add_security(
+    operation: &mut Map<String, Value>,
+    route: &CompiledRoute,
+    security_schemes: &HashMap<SecuritySchemeId, SecuritySchemeDetails>,
 ) {
-    if route.security_scheme_missing() {
-        operation.extensions.insert(
-            GOLEM_DISABLED_EXTENSION.to_string(),
-            serde_json::json!({ "reason": "security_scheme_missing" }),
+    if let Some(details) = route
+        .security_scheme
+        .and_then(|security_scheme_id| security_schemes.get(&security_scheme_id))
+    {
+        let scopes = match details {
+            SecuritySchemeDetails::OpenIdConnect(OidcSecuritySchemeDetails {
+                mode: SecuritySchemeMode::AuthorizationCode,
+                scopes,
+                ..
+            }) => scopes.iter().map(|s| s.to_string()).collect(),
+            SecuritySchemeDetails::OpenIdConnect(OidcSecuritySchemeDetails {
+                mode: SecuritySchemeMode::BearerToken,
+                ..
+            })
+            | SecuritySchemeDetails::ApiKey(_) => route.required_scopes.clone(),
+        };
+        operation.insert(
+            "security".to_string(),
+            json!([{ details.name().0.clone(): scopes }]),
         );
-        return;
     }
+}
 
-    // If the route references a scheme
-    if let Some(security_scheme_id) = route.security_scheme() {
-        let details = security_schemes_map
-            .get(&security_scheme_id)
-            .expect("Failed to find security scheme even though it's not marked as missing");
-        let scopes_vec: Vec<String> = details.scopes.iter().map(|s| s.to_string()).collect();
-
-        let mut requirement: indexmap::IndexMap<String, Vec<String>> = indexmap::IndexMap::new();
-        requirement.insert(details.name.0.clone(), scopes_vec);
+// --------------------- Helpers ---------------------
 
-        operation.security = Some(vec![requirement]);
+/// Name of the path item field describing the operation of the method, if OpenAPI has one
+fn path_item_method(method: &HttpMethod) -> Option<&'static str> {
+    match method {
+        HttpMethod::Get(_) => Some("get"),
+        HttpMethod::Head(_) => Some("head"),
+        HttpMethod::Post(_) => Some("post"),
+        HttpMethod::Put(_) => Some("put"),
+        HttpMethod::Delete(_) => Some("delete"),
+        HttpMethod::Options(_) => Some("options"),
+        HttpMethod::Trace(_) => Some("trace"),
+        HttpMethod::Patch(_) => Some("patch"),
+        HttpMethod::Connect(_) | HttpMethod::Custom(_) => None,
     }
 }
 
-// --------------------- Helpers ---------------------
+fn null_schema() -> Value {
+    json!({ "type": "null" })
+}
 
-fn add_operation_to_path_item(
-    path_item: &mut openapiv3::PathItem,
-    method: &RouteMethod,
-    operation: openapiv3::Operation,
-) -> Result<(), String> {
-    match method {
-        RouteMethod::Get => path_item.get = Some(operation),
-        RouteMethod::Post => path_item.post = Some(operation),
-        RouteMethod::Put => path_item.put = Some(operation),
-        RouteMethod::Delete => path_item.delete = Some(operation),
-        RouteMethod::Patch => path_item.patch = Some(operation),
-        RouteMethod::Options => path_item.options = Some(operation),
-        RouteMethod::Head => path_item.head = Some(operation),
-        RouteMethod::Trace => path_item.trace = Some(operation),
-        RouteMethod::Connect => return Err("CONNECT not supported".to_string()),
+/// Schema accepting `null` in addition to the values of `inner`. Single typed schemas get `null`
+/// added to their list of types, other schemas are combined with the null schema.
+fn nullable_schema(mut inner: Value) -> Value {
+    if let Some(Value::String(typ)) = inner.get("type")
+        && typ != "null"
+        && inner.get("enum").is_none()
+    {
+        let typ = typ.clone();
+        inner["type"] = json!([typ, "null"]);
+        inner
+    } else {
+        json!({ "anyOf": [inner, null_schema()] })
     }
-    Ok(())
 }
 
-fn create_integer_schema(
-    format: openapiv3::IntegerFormat,
-    min: Option<i64>,
-    max: Option<i64>,
-) -> openapiv3::Schema {
-    let schema_data = openapiv3::SchemaData::default();
-    openapiv3::Schema {
-        schema_data,
-        schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Integer(
-            openapiv3::IntegerType {
-                format: openapiv3::VariantOrUnknownOrEmpty::Item(format),
-                minimum: min,
-                maximum: max,
-                multiple_of: None,
-                exclusive_minimum: false,
-                exclusive_maximum: false,
-                enumeration: vec![],
-            },
-        )),
+fn string_schema() -> Value {
+    json!({ "type": "string" })
+}
+
+fn string_enum_schema(cases: &[String]) -> Value {
+    json!({ "type": "string", "enum": cases })
+}
+
+fn integer_schema(format: &str, min: Option<i64>, max: Option<i64>) -> Value {
+    let mut schema = json!({ "type": "integer", "format": format });
+    if let Some(min) = min {
+        schema["minimum"] = json!(min);
     }
+    if let Some(max) = max {
+        schema["maximum"] = json!(max);
+    }
+    schema
 }
 
-fn create_schema_from_analysed_type(analysed_type: &AnalysedType) -> openapiv3::Schema {
-    use golem_wasm::analysis::AnalysedType;
+fn array_schema(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
 
-    match analysed_type {
-        AnalysedType::Bool(_) => openapiv3::Schema {
-            schema_data: Default::default(),
-            schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Boolean(
-                openapiv3::BooleanType::default(),
-            )),
-        },
-        AnalysedType::U8(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int32, Some(0), Some(255))
-        }
-        AnalysedType::U16(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int32, Some(0), Some(65535))
-        }
-        AnalysedType::U32(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int32, Some(0), None)
-        }
-        AnalysedType::U64(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int64, Some(0), None)
-        }
-        AnalysedType::S8(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int32, Some(-128), Some(127))
-        }
-        AnalysedType::S16(_) => {
-            create_integer_schema(openapiv3::IntegerFormat::Int32, Some(-32768), Some(32767))
-        }
-        AnalysedType::S32(_) => create_integer_schema(openapiv3::IntegerFormat::Int32, None, None),
-        AnalysedType::S64(_) => create_integer_schema(openapiv3::IntegerFormat::Int64, None, None),
-
-        AnalysedType::F32(_) => {
-            let sd = openapiv3::SchemaData::default();
-            openapiv3::Schema {
-                schema_data: sd,
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Number(
-                    openapiv3::NumberType {
-                        format: openapiv3::VariantOrUnknownOrEmpty::Item(
-                            openapiv3::NumberFormat::Float,
-                        ),
-                        multiple_of: None,
-                        exclusive_minimum: false,
-                        exclusive_maximum: false,
-                        minimum: None,
-                        maximum: None,
-                        enumeration: vec![],
-                    },
-                )),
-            }
-        }
-        AnalysedType::F64(_) => {
-            let sd = openapiv3::SchemaData::default();
-            openapiv3::Schema {
-                schema_data: sd,
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Number(
-                    openapiv3::NumberType {
-                        format: openapiv3::VariantOrUnknownOrEmpty::Item(
-                            openapiv3::NumberFormat::Double,
-                        ),
-                        multiple_of: None,
-                        exclusive_minimum: false,
-                        exclusive_maximum: false,
-                        minimum: None,
-                        maximum: None,
-                        enumeration: vec![],
-                    },
-                )),
-            }
-        }
-        AnalysedType::Str(_) => openapiv3::Schema {
-            schema_data: Default::default(),
-            schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                openapiv3::StringType {
-                    format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                    pattern: None,
-                    enumeration: vec![],
-                    min_length: None,
-                    max_length: None,
-                },
-            )),
-        },
-        AnalysedType::List(type_list) => {
-            let items = openapiv3::ReferenceOr::Item(Box::new(create_schema_from_analysed_type(
-                &type_list.inner,
-            )));
-            openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Array(
-                    openapiv3::ArrayType {
-                        items: Some(items),
-                        min_items: None,
-                        max_items: None,
-                        unique_items: false,
-                    },
-                )),
-            }
-        }
-        AnalysedType::Tuple(type_tuple) => {
-            let min_items = Some(type_tuple.items.len());
-            let max_items = Some(type_tuple.items.len());
-
-            let items = openapiv3::ReferenceOr::Item(Box::new(openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Object(
-                    Default::default(),
-                )),
-            }));
-            let array_schema = openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Array(
-                    openapiv3::ArrayType {
-                        items: Some(items),
-                        min_items,
-                        max_items,
-                        unique_items: false,
-                    },
-                )),
-            };
+/// Object with a single required property, used for variant cases and results
+fn single_property_object_schema(name: &str, property: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": { name: property },
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
 
-            let schema_data = openapiv3::SchemaData {
-                description: Some("Tuple type".to_string()),
-                ..Default::default()
-            };
-            openapiv3::Schema {
-                schema_data,
-                schema_kind: array_schema.schema_kind,
-            }
+/// Alternatives of a value, `oneOf` must not be empty so a type without cases matches nothing
+fn one_of_schema(schemas: Vec<Value>) -> Value {
+    if schemas.is_empty() {
+        json!(false)
+    } else {
+        json!({ "oneOf": schemas })
+    }
+}
+
+/// Schema of the JSON representation of component model values, as used for request and
+/// response bodies
+fn json_schema(analysed_type: &AnalysedType) -> Value {
+    match analysed_type {
+        AnalysedType::Bool(_) => json!({ "type": "boolean" }),
+        AnalysedType::U8(_) => integer_schema("int32", Some(0), Some(255)),
+        AnalysedType::U16(_) => integer_schema("int32", Some(0), Some(65535)),
+        AnalysedType::U32(_) => integer_schema("int64", Some(0), None),
+        AnalysedType::U64(_) => integer_schema("int64", Some(0), None),
+        AnalysedType::S8(_) => integer_schema("int32", Some(-128), Some(127)),
+        AnalysedType::S16(_) => integer_schema("int32", Some(-32768), Some(32767)),
+        AnalysedType::S32(_) => integer_schema("int32", None, None),
+        AnalysedType::S64(_) => integer_schema("int64", None, None),
+        AnalysedType::F32(_) => json!({ "type": "number", "format": "float" }),
+        AnalysedType::F64(_) => json!({ "type": "number", "format": "double" }),
+        AnalysedType::Chr(_) => {
+            let mut schema = integer_schema("int32", Some(0), Some(char::MAX as i64));
+            schema["description"] = json!("Unicode code point");
+            schema
         }
+        AnalysedType::Str(_) => string_schema(),
+        AnalysedType::List(type_list) => array_schema(json_schema(&type_list.inner)),
+        AnalysedType::Tuple(type_tuple) => json!({
+            "type": "array",
+            "prefixItems": type_tuple.items.iter().map(json_schema).collect::<Vec<_>>(),
+            "items": false,
+            "minItems": type_tuple.items.len(),
+            "maxItems": type_tuple.items.len(),
+        }),
         AnalysedType::Record(type_record) => {
-            let mut properties = IndexMap::new();
+            let mut properties = Map::new();
             let mut required = Vec::new();
             for field in &type_record.fields {
-                let field_schema = create_schema_from_analysed_type(&field.typ);
-                let is_nullable = field_schema.schema_data.nullable;
-                properties.insert(
-                    field.name.clone(),
-                    openapiv3::ReferenceOr::Item(Box::new(field_schema)),
-                );
-                if !is_nullable {
+                // optional fields can be omitted
+                if !matches!(field.typ, AnalysedType::Option(_)) {
                     required.push(field.name.clone());
                 }
+                properties.insert(field.name.clone(), json_schema(&field.typ));
             }
-            openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Object(
-                    openapiv3::ObjectType {
-                        properties,
-                        required,
-                        additional_properties: None,
-                        min_properties: None,
-                        max_properties: None,
-                    },
-                )),
-            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            })
         }
-        AnalysedType::Variant(type_variant) => {
-            let mut one_of = Vec::new();
-            for case in &type_variant.cases {
-                let case_name = &case.name;
-                if let Some(case_type) = &case.typ {
-                    let case_schema = create_schema_from_analysed_type(case_type);
-                    let mut properties = IndexMap::new();
-                    properties.insert(
-                        case_name.clone(),
-                        openapiv3::ReferenceOr::Item(Box::new(case_schema)),
-                    );
-                    let required = vec![case_name.clone()];
-                    let schema = openapiv3::Schema {
-                        schema_data: Default::default(),
-                        schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Object(
-                            openapiv3::ObjectType {
-                                properties,
-                                required,
-                                additional_properties: None,
-                                min_properties: None,
-                                max_properties: None,
-                            },
-                        )),
-                    };
-                    one_of.push(openapiv3::ReferenceOr::Item(schema));
-                } else {
-                    let schema = openapiv3::Schema {
-                        schema_data: Default::default(),
-                        schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                            openapiv3::StringType {
-                                format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                                pattern: None,
-                                enumeration: vec![],
-                                min_length: None,
-                                max_length: None,
-                            },
-                        )),
-                    };
-                    one_of.push(openapiv3::ReferenceOr::Item(schema));
+        AnalysedType::Variant(type_variant) => one_of_schema(
+            type_variant
+                .cases
+                .iter()
+                .map(|case| {
+                    let case_schema = case
+                        .typ
+                        .as_ref()
+                        .map(json_schema)
+                        .unwrap_or_else(null_schema);
+                    single_property_object_schema(&case.name, case_schema)
+                })
+                .collect(),
+        ),
+        AnalysedType::Enum(type_enum) => string_enum_schema(&type_enum.cases),
+        AnalysedType::Flags(type_flags) => json!({
+            "type": "array",
+            "items": string_enum_schema(&type_flags.names),
+            "maxItems": type_flags.names.len(),
+            "uniqueItems": true,
+        }),
+        AnalysedType::Option(type_option) => nullable_schema(json_schema(&type_option.inner)),
+        AnalysedType::Result(type_result) => {
+            let ok_schema = type_result
+                .ok
+                .as_ref()
+                .map(|ok| json_schema(ok))
+                .unwrap_or_else(null_schema);
+            let err_schema = type_result
+                .err
+                .as_ref()
+                .map(|err| json_schema(err))
+                .unwrap_or_else(null_schema);
+            one_of_schema(vec![
+                single_property_object_schema("ok", ok_schema),
+                single_property_object_schema("err", err_schema),
+            ])
+        }
+        // Resource handles cannot be passed through HTTP
+        AnalysedType::Handle(_) => json!({}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_api::{ApiKeySecuritySchemeDetails, CorsOptions, RateLimit, RateLimitScope};
+    use crate::model::SafeIndex;
+    use golem_common::model::Empty;
+    use golem_common::model::account::AccountId;
+    use golem_common::model::agent::{AgentTypeName, NamedElementSchema};
+    use golem_common::model::component::{ComponentId, ComponentRevision};
+    use golem_common::model::deployment::DeploymentRevision;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::security_scheme::{Provider, SecuritySchemeName};
+    use golem_wasm::analysis::analysed_type;
+    use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl};
+    use serde_json::json;
+    use std::sync::LazyLock;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn call_agent_route(
+        route_id: i32,
+        method: HttpMethod,
+        path: Vec<PathSegment>,
+        body: RequestBodySchema,
+        method_parameters: Vec<MethodParameter>,
+        expected_agent_response: DataSchema,
+    ) -> CompiledRoute {
+        CompiledRoute {
+            route_id,
+            method,
+            path,
+            body,
+            behavior: RouteBehaviour::CallAgent(CallAgentBehaviour {
+                component_id: ComponentId(Uuid::new_v4()),
+                component_revision: ComponentRevision::INITIAL,
+                agent_type: AgentTypeName("counter".to_string()),
+                constructor_parameters: vec![ConstructorParameter::Path {
+                    path_segment_index: SafeIndex::new(0),
+                    parameter_type: PathSegmentType::Str,
+                }],
+                phantom: false,
+                method_name: "increment".to_string(),
+                method_parameters,
+                expected_agent_response,
+            }),
+            security_scheme: None,
+            required_scopes: vec![],
+            cors: CorsOptions {
+                allowed_patterns: vec![],
+            },
+            rate_limits: vec![],
+        }
+    }
+
+    fn single_element_schema(element_type: AnalysedType) -> DataSchema {
+        DataSchema::Tuple(NamedElementSchemas {
+            elements: vec![NamedElementSchema {
+                name: "result".to_string(),
+                schema: ElementSchema::ComponentModel(ComponentModelElementSchema { element_type }),
+            }],
+        })
+    }
+
+    fn spec_json(routes: Vec<CompiledRoute>) -> serde_json::Value {
+        spec_json_with_security_schemes(routes, HashMap::new())
+    }
+
+    fn spec_json_with_security_schemes(
+        routes: Vec<CompiledRoute>,
+        security_schemes: HashMap<SecuritySchemeId, SecuritySchemeDetails>,
+    ) -> serde_json::Value {
+        let compiled_routes = CompiledRoutes {
+            account_id: AccountId(Uuid::new_v4()),
+            environment_id: EnvironmentId(Uuid::new_v4()),
+            deployment_revision: DeploymentRevision::INITIAL,
+            security_schemes,
+            routes,
+        };
+        AgentHttpApiOpenApiSpec::from_compiled_routes("example.com", &compiled_routes).0
+    }
+
+    /// Official JSON Schema of OpenAPI 3.1 documents, without the validation of schema objects
+    static OPENAPI_3_1_SCHEMA: LazyLock<jsonschema::Validator> = LazyLock::new(|| {
+        let schema = serde_json::from_str::<serde_json::Value>(include_str!(
+            "../../tests/openapi/openapi-3.1-schema-2022-10-07.json"
+        ))
+        .expect("Invalid OpenAPI 3.1 schema: cannot parse as JSON");
+        jsonschema::validator_for(&schema).expect("Invalid OpenAPI 3.1 schema")
+    });
+
+    /// Validates the document, and each of its schema objects as a JSON Schema 2020-12 schema
+    fn assert_valid_openapi_3_1(spec: &serde_json::Value) {
+        let errors = OPENAPI_3_1_SCHEMA
+            .iter_errors(spec)
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert!(
+            errors.is_empty(),
+            "Invalid OpenAPI 3.1 document: {errors:#?}"
+        );
+
+        let mut schemas = Vec::new();
+        for path_item in spec["paths"].as_object().unwrap().values() {
+            for operation in path_item.as_object().unwrap().values() {
+                for parameter in operation["parameters"].as_array().unwrap() {
+                    schemas.push(&parameter["schema"]);
                 }
-            }
-            openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::OneOf { one_of },
+                let contents = operation["responses"]
+                    .as_object()
+                    .unwrap()
+                    .values()
+                    .chain(std::iter::once(&operation["requestBody"]))
+                    .filter_map(|item| item.get("content"))
+                    .flat_map(|content| content.as_object().unwrap().values());
+                schemas.extend(contents.filter_map(|media_type| media_type.get("schema")));
             }
         }
-        AnalysedType::Enum(type_enum) => {
-            let enum_values: Vec<Option<String>> =
-                type_enum.cases.iter().map(|c| Some(c.clone())).collect();
-            openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                    openapiv3::StringType {
-                        format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                        pattern: None,
-                        enumeration: enum_values,
-                        min_length: None,
-                        max_length: None,
-                    },
-                )),
+        assert!(!schemas.is_empty());
+        for schema in schemas {
+            if let Err(error) = jsonschema::draft202012::meta::validate(schema) {
+                panic!("Invalid JSON Schema 2020-12 schema {schema}: {error}");
             }
         }
-        AnalysedType::Option(type_option) => {
-            let mut schema = create_schema_from_analysed_type(&type_option.inner);
-            schema.schema_data.nullable = true;
-            schema
-        }
-        AnalysedType::Result(type_result) => {
-            let ok_type = match &type_result.ok {
-                Some(b) => &**b,
-                None => &AnalysedType::Str(golem_wasm::analysis::TypeStr {}),
-            };
-            let err_type = match &type_result.err {
-                Some(b) => &**b,
-                None => &AnalysedType::Str(golem_wasm::analysis::TypeStr {}),
-            };
-            let ok_schema = create_schema_from_analysed_type(ok_type);
-            let err_schema = create_schema_from_analysed_type(err_type);
+    }
 
-            let mut ok_properties = IndexMap::new();
-            ok_properties.insert(
-                "ok".to_string(),
-                openapiv3::ReferenceOr::Item(Box::new(ok_schema)),
-            );
-            let ok_required = vec!["ok".to_string()];
-            let ok_object_schema = openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Object(
-                    openapiv3::ObjectType {
-                        properties: ok_properties,
-                        required: ok_required,
-                        additional_properties: None,
-                        min_properties: None,
-                        max_properties: None,
+    #[test]
+    fn path_query_and_header_parameters() {
+        let spec = spec_json(vec![call_agent_route(
+            0,
+            HttpMethod::Post(Empty {}),
+            vec![
+                PathSegment::Literal {
+                    value: "counters".to_string(),
+                },
+                PathSegment::Variable,
+                PathSegment::Variable,
+            ],
+            RequestBodySchema::Unused,
+            vec![
+                MethodParameter::Path {
+                    path_segment_index: SafeIndex::new(1),
+                    parameter_type: PathSegmentType::U32,
+                },
+                MethodParameter::Query {
+                    query_parameter_name: "tag".to_string(),
+                    parameter_type: QueryOrHeaderType::List {
+                        name: None,
+                        owner: None,
+                        inner: Box::new(PathSegmentType::Str),
                     },
-                )),
-            };
+                },
+                MethodParameter::Header {
+                    header_name: "x-request-id".to_string(),
+                    parameter_type: QueryOrHeaderType::Primitive(PathSegmentType::Str),
+                },
+            ],
+            DataSchema::Tuple(NamedElementSchemas { elements: vec![] }),
+        )]);
+
+        assert_eq!(spec["openapi"], json!("3.1.0"));
+
+        let operation = &spec["paths"]["/counters/{param0}/{param1}"]["post"];
+        assert_eq!(operation["operationId"], json!("counter-increment"));
+
+        let parameters = operation["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 4);
+        assert_eq!(parameters[0]["in"], json!("path"));
+        assert_eq!(parameters[0]["schema"]["type"], json!("string"));
+        assert_eq!(parameters[1]["name"], json!("param1"));
+        assert_eq!(parameters[1]["schema"]["type"], json!("integer"));
+        assert_eq!(parameters[2]["in"], json!("query"));
+        assert_eq!(parameters[2]["required"], json!(false));
+        assert_eq!(parameters[2]["schema"]["type"], json!("array"));
+        assert_eq!(parameters[3]["in"], json!("header"));
+        assert_eq!(parameters[3]["required"], json!(true));
+
+        assert!(operation["responses"]["204"].is_object());
+    }
 
-            let mut err_properties = IndexMap::new();
-            err_properties.insert(
-                "err".to_string(),
-                openapiv3::ReferenceOr::Item(Box::new(err_schema)),
-            );
-            let err_required = vec!["err".to_string()];
-            let err_object_schema = openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Object(
-                    openapiv3::ObjectType {
-                        properties: err_properties,
-                        required: err_required,
-                        additional_properties: None,
-                        min_properties: None,
-                        max_properties: None,
-                    },
-                )),
-            };
+    #[test]
+    fn json_body_and_option_response() {
+        let spec = spec_json(vec![call_agent_route(
+            0,
+            HttpMethod::Put(Empty {}),
+            vec![PathSegment::Variable],
+            RequestBodySchema::JsonBody {
+                expected_type: analysed_type::record(vec![
+                    analysed_type::field("amount", analysed_type::s64()),
+                    analysed_type::field("note", analysed_type::option(analysed_type::str())),
+                ]),
+            },
+            vec![
+                MethodParameter::JsonObjectBodyField {
+                    field_index: SafeIndex::new(0),
+                },
+                MethodParameter::JsonObjectBodyField {
+                    field_index: SafeIndex::new(1),
+                },
+            ],
+            single_element_schema(analysed_type::option(analysed_type::u64())),
+        )]);
+
+        let operation = &spec["paths"]["/{param0}"]["put"];
+
+        let body_schema = &operation["requestBody"]["content"]["application/json"]["schema"];
+        assert_eq!(body_schema["type"], json!("object"));
+        assert_eq!(body_schema["required"], json!(["amount"]));
+        assert_eq!(
+            body_schema["properties"]["note"]["type"],
+            json!(["string", "null"])
+        );
+
+        let responses = &operation["responses"];
+        assert_eq!(
+            responses["200"]["content"]["application/json"]["schema"]["type"],
+            json!("integer")
+        );
+        assert!(responses["404"].is_object());
+        assert!(responses["500"].is_object());
+    }
+
+    #[test]
+    fn schemas_use_json_schema_2020_12_constructs() {
+        let schema_json = |typ: AnalysedType| json_schema(&typ);
+
+        let empty_tuple = schema_json(analysed_type::tuple(vec![]));
+        assert_eq!(empty_tuple["type"], json!("array"));
+        assert_eq!(empty_tuple["prefixItems"], json!([]));
+        assert_eq!(empty_tuple["items"], json!(false));
+        assert_eq!(empty_tuple["maxItems"], json!(0));
+
+        let tuple = schema_json(analysed_type::tuple(vec![
+            analysed_type::str(),
+            analysed_type::u32(),
+        ]));
+        assert_eq!(tuple["prefixItems"][0]["type"], json!("string"));
+        assert_eq!(tuple["prefixItems"][1]["type"], json!("integer"));
+
+        let result = schema_json(analysed_type::result_err(analysed_type::str()));
+        assert_eq!(
+            result["oneOf"][0]["properties"]["ok"],
+            json!({ "type": "null" })
+        );
+
+        let option = schema_json(analysed_type::option(analysed_type::u8()));
+        assert_eq!(option["type"], json!(["integer", "null"]));
+
+        let option_of_variant = schema_json(analysed_type::option(analysed_type::variant(vec![
+            analysed_type::case("some", analysed_type::u8()),
+            analysed_type::unit_case("none"),
+        ])));
+        assert_eq!(option_of_variant["anyOf"][1], json!({ "type": "null" }));
+
+        let option_of_enum = schema_json(analysed_type::option(analysed_type::r#enum(&[
+            "red", "green",
+        ])));
+        assert_eq!(option_of_enum["anyOf"][1], json!({ "type": "null" }));
+    }
 
-            openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::OneOf {
-                    one_of: vec![
-                        openapiv3::ReferenceOr::Item(ok_object_schema),
-                        openapiv3::ReferenceOr::Item(err_object_schema),
-                    ],
+    #[test]
+    fn document_is_valid_openapi_3_1() {
+        let security_scheme = SecuritySchemeDetails::OpenIdConnect(OidcSecuritySchemeDetails {
+            id: SecuritySchemeId::new(),
+            name: SecuritySchemeName("google".to_string()),
+            provider_type: Provider::Google,
+            issuer_url: Provider::Google.issuer_url().unwrap(),
+            client_id: ClientId::new("golem-client".to_string()),
+            client_secret: ClientSecret::new("golem-secret".to_string()),
+            redirect_url: RedirectUrl::new("https://example.com/callback".to_string()).unwrap(),
+            scopes: vec![],
+            mode: SecuritySchemeMode::AuthorizationCode,
+            audience: Audience::new("golem-client".to_string()),
+        });
+
+        let mut create_route = call_agent_route(
+            0,
+            HttpMethod::Post(Empty {}),
+            vec![
+                PathSegment::Variable,
+                PathSegment::Literal {
+                    value: "items".to_string(),
                 },
-            }
-        }
-        AnalysedType::Flags(type_flags) => {
-            let enum_values: Vec<Option<String>> =
-                type_flags.names.iter().map(|n| Some(n.clone())).collect();
-            let items_schema = openapiv3::Schema {
-                schema_data: Default::default(),
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                    openapiv3::StringType {
-                        format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                        pattern: None,
-                        enumeration: enum_values,
-                        min_length: None,
-                        max_length: None,
+                PathSegment::CatchAll,
+            ],
+            RequestBodySchema::JsonBody {
+                expected_type: analysed_type::record(vec![
+                    analysed_type::field("name", analysed_type::str()),
+                    analysed_type::field("note", analysed_type::option(analysed_type::str())),
+                    analysed_type::field(
+                        "position",
+                        analysed_type::tuple(vec![analysed_type::f64(), analysed_type::f64()]),
+                    ),
+                    analysed_type::field(
+                        "color",
+                        analysed_type::option(analysed_type::r#enum(&["red", "green"])),
+                    ),
+                    analysed_type::field("permissions", analysed_type::flags(&["read", "write"])),
+                    analysed_type::field("initial", analysed_type::chr()),
+                ]),
+            },
+            vec![
+                MethodParameter::Path {
+                    path_segment_index: SafeIndex::new(1),
+                    parameter_type: PathSegmentType::Chr,
+                },
+                MethodParameter::Query {
+                    query_parameter_name: "tag".to_string(),
+                    parameter_type: QueryOrHeaderType::List {
+                        name: None,
+                        owner: None,
+                        inner: Box::new(PathSegmentType::Str),
                     },
-                )),
-            };
-            openapiv3::Schema {
-                schema_data: openapiv3::SchemaData {
-                    description: Some("Flags type - array of flag names".to_string()),
-                    ..Default::default()
                 },
-                schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::Array(
-                    openapiv3::ArrayType {
-                        items: Some(openapiv3::ReferenceOr::Item(Box::new(items_schema))),
-                        min_items: Some(0),
-                        max_items: Some(type_flags.names.len()),
-                        unique_items: true,
+                MethodParameter::Header {
+                    header_name: "x-limit".to_string(),
+                    parameter_type: QueryOrHeaderType::Option {
+                        name: None,
+                        owner: None,
+                        inner: Box::new(PathSegmentType::U32),
                     },
-                )),
-            }
-        }
-        AnalysedType::Chr(_) => openapiv3::Schema {
-            schema_data: openapiv3::SchemaData {
-                description: Some("Unicode character".to_string()),
-                ..Default::default()
-            },
-            schema_kind: openapiv3::SchemaKind::Type(openapiv3::Type::String(
-                openapiv3::StringType {
-                    format: openapiv3::VariantOrUnknownOrEmpty::Empty,
-                    pattern: Some("^.{1}$".to_string()),
-                    enumeration: vec![],
-                    min_length: Some(1),
-                    max_length: Some(1),
                 },
+            ],
+            single_element_schema(analysed_type::result(
+                analysed_type::option(analysed_type::variant(vec![
+                    analysed_type::case("created", analysed_type::u64()),
+                    analysed_type::unit_case("unchanged"),
+                ])),
+                analysed_type::str(),
             )),
-        },
-        AnalysedType::Handle(_) => unimplemented!(),
+        );
+        create_route.security_scheme = Some(security_scheme.id());
+        create_route.rate_limits = vec![RateLimit {
+            scope: RateLimitScope::ClientIp,
+            requests: 10,
+            interval_seconds: 60,
+            burst: 10,
+        }];
+
+        let upload_route = call_agent_route(
+            1,
+            HttpMethod::Put(Empty {}),
+            vec![PathSegment::Variable],
+            RequestBodySchema::RestrictedBinary {
+                allowed_mime_types: vec!["image/png".to_string()],
+            },
+            vec![MethodParameter::UnstructuredBinaryBody],
+            DataSchema::Tuple(NamedElementSchemas { elements: vec![] }),
+        );
+
+        let get_route = call_agent_route(
+            2,
+            HttpMethod::Get(Empty {}),
+            vec![PathSegment::Variable],
+            RequestBodySchema::Unused,
+            vec![],
+            single_element_schema(analysed_type::option(analysed_type::list(
+                analysed_type::s32(),
+            ))),
+        );
+
+        let spec = spec_json_with_security_schemes(
+            vec![create_route, upload_route, get_route],
+            HashMap::from([(security_scheme.id(), security_scheme)]),
+        );
+
+        assert_valid_openapi_3_1(&spec);
+    }
+
+    #[test]
+    fn duplicate_operation_ids_are_disambiguated() {
+        let spec = spec_json(vec![
+            call_agent_route(
+                1,
+                HttpMethod::Get(Empty {}),
+                vec![PathSegment::Variable],
+                RequestBodySchema::Unused,
+                vec![],
+                single_element_schema(analysed_type::u64()),
+            ),
+            call_agent_route(
+                2,
+                HttpMethod::Get(Empty {}),
+                vec![
+                    PathSegment::Variable,
+                    PathSegment::Literal {
+                        value: "value".to_string(),
+                    },
+                ],
+                RequestBodySchema::Unused,
+                vec![],
+                single_element_schema(analysed_type::u64()),
+            ),
+        ]);
+
+        assert_eq!(
+            spec["paths"]["/{param0}"]["get"]["operationId"],
+            json!("counter-increment")
+        );
+        assert_eq!(
+            spec["paths"]["/{param0}/value"]["get"]["operationId"],
+            json!("counter-increment-2")
+        );
+    }
+
+    #[test]
+    fn bearer_token_security_scheme() {
+        let security_scheme = SecuritySchemeDetails::OpenIdConnect(OidcSecuritySchemeDetails {
+            id: SecuritySchemeId::new(),
+            name: SecuritySchemeName("service-tokens".to_string()),
+            provider_type: Provider::Custom,
+            issuer_url: IssuerUrl::new("https://idp.example.com/realms/golem".to_string()).unwrap(),
+            client_id: ClientId::new("golem-client".to_string()),
+            client_secret: ClientSecret::new("golem-secret".to_string()),
+            redirect_url: RedirectUrl::new("https://example.com/callback".to_string()).unwrap(),
+            scopes: vec![],
+            mode: SecuritySchemeMode::BearerToken,
+            audience: Audience::new("counter-api".to_string()),
+        });
+
+        let mut route = call_agent_route(
+            0,
+            HttpMethod::Post(Empty {}),
+            vec![PathSegment::Variable],
+            RequestBodySchema::Unused,
+            vec![],
+            DataSchema::Tuple(NamedElementSchemas { elements: vec![] }),
+        );
+        route.security_scheme = Some(security_scheme.id());
+        route.required_scopes = vec!["counters:write".to_string()];
+
+        let spec = spec_json_with_security_schemes(
+            vec![route],
+            HashMap::from([(security_scheme.id(), security_scheme)]),
+        );
+
+        let scheme = &spec["components"]["securitySchemes"]["service-tokens"];
+        assert_eq!(scheme["type"], json!("http"));
+        assert_eq!(scheme["scheme"], json!("bearer"));
+        assert_eq!(scheme["bearerFormat"], json!("JWT"));
+
+        assert_eq!(
+            spec["paths"]["/{param0}"]["post"]["security"],
+            json!([{ "service-tokens": ["counters:write"] }])
+        );
+        assert_valid_openapi_3_1(&spec);
+    }
+
+    #[test]
+    fn api_key_security_scheme() {
+        let security_scheme = SecuritySchemeDetails::ApiKey(ApiKeySecuritySchemeDetails {
+            id: SecuritySchemeId::new(),
+            name: SecuritySchemeName("partner-keys".to_string()),
+        });
+
+        let mut route = call_agent_route(
+            0,
+            HttpMethod::Get(Empty {}),
+            vec![PathSegment::Variable],
+            RequestBodySchema::Unused,
+            vec![],
+            DataSchema::Tuple(NamedElementSchemas { elements: vec![] }),
+        );
+        route.security_scheme = Some(security_scheme.id());
+
+        let spec = spec_json_with_security_schemes(
+            vec![route],
+            HashMap::from([(security_scheme.id(), security_scheme)]),
+        );
+
+        let scheme = &spec["components"]["securitySchemes"]["partner-keys"];
+        assert_eq!(scheme["type"], json!("apiKey"));
+        assert_eq!(scheme["in"], json!("header"));
+        assert_eq!(scheme["name"], json!("X-API-Key"));
+
+        assert_eq!(
+            spec["paths"]["/{param0}"]["get"]["security"],
+            json!([{ "partner-keys": [] }])
+        );
+        assert_valid_openapi_3_1(&spec);
     }
 }
@@ -12,20 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::{
+    ApiKeySecuritySchemeDetails, CorsOptions, OidcSecuritySchemeDetails, SecuritySchemeApiKey,
+    SecuritySchemeDetails,
+};
 use super::{CompiledRoute, CompiledRoutes};
-use super::{CorsOptions, SecuritySchemeDetails};
 use super::{PathSegment, PathSegmentType, RequestBodySchema, RouteBehaviour};
 use crate::custom_api::{
     CallAgentBehaviour, ConstructorParameter, CorsPreflightBehaviour, MethodParameter,
-    OriginPattern, QueryOrHeaderType,
+    OriginPattern, QueryOrHeaderType, RateLimit, RateLimitScope,
 };
 use golem_api_grpc::proto;
 use golem_common::model::agent::{AgentTypeName, HttpMethod};
-use golem_common::model::security_scheme::SecuritySchemeName;
+use golem_common::model::security_scheme::{
+    ApiKeyId, Provider, SecuritySchemeMode, SecuritySchemeName,
+};
 use golem_wasm::analysis::TypeEnum;
-use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
+use openidconnect::{Audience, ClientId, ClientSecret, IssuerUrl, RedirectUrl, Scope};
 use std::collections::{BTreeSet, HashMap};
 use std::ops::Deref;
+use std::time::SystemTime;
 
 impl TryFrom<proto::golem::customapi::SecuritySchemeDetails> for SecuritySchemeDetails {
     type Error = String;
@@ -34,22 +40,49 @@ impl TryFrom<proto::golem::customapi::SecuritySchemeDetails> for SecuritySchemeD
         value: proto::golem::customapi::SecuritySchemeDetails,
     ) -> Result<Self, Self::Error> {
         let id = value.id.ok_or("id field missing")?.try_into()?;
+        let name = SecuritySchemeName(value.name);
+
+        if value.api_key {
+            return Ok(Self::ApiKey(ApiKeySecuritySchemeDetails { id, name }));
+        }
 
-        let provider_type = value
+        let provider_type: Provider = value
             .provider()
             .try_into()
             .map_err(|e| format!("invalid provider: {e}"))?;
 
-        Ok(Self {
+        // fall back to the well-known issuer if the sender did not include it
+        let issuer_url = if value.issuer_url.is_empty() {
+            provider_type
+                .issuer_url()
+                .ok_or("issuer_url field missing for custom provider")?
+        } else {
+            IssuerUrl::new(value.issuer_url)
+                .map_err(|e| format!("Failed parsing issuer url: {e}"))?
+        };
+
+        let mode = SecuritySchemeMode::from(value.mode());
+
+        // tokens are expected to be issued for the client unless configured otherwise
+        let audience = if value.audience.is_empty() {
+            Audience::new(value.client_id.clone())
+        } else {
+            Audience::new(value.audience)
+        };
+
+        Ok(Self::OpenIdConnect(OidcSecuritySchemeDetails {
             id,
-            name: SecuritySchemeName(value.name),
+            name,
             provider_type,
+            issuer_url,
             client_id: ClientId::new(value.client_id),
             client_secret: ClientSecret::new(value.client_secret),
             redirect_url: RedirectUrl::new(value.redirect_url)
                 .map_err(|e| format!("Failed parsing redirect url: {e}"))?,
             scopes: value.scopes.into_iter().map(Scope::new).collect(),
-        })
+            mode,
+            audience,
+        }))
     }
 }
 
@@ -57,17 +90,69 @@ impl From<SecuritySchemeDetails>
     for golem_api_grpc::proto::golem::customapi::SecuritySchemeDetails
 {
     fn from(value: SecuritySchemeDetails) -> Self {
+        match value {
+            SecuritySchemeDetails::OpenIdConnect(value) => Self {
+                id: Some(value.id.into()),
+                name: value.name.0,
+                provider: golem_api_grpc::proto::golem::registry::SecuritySchemeProvider::from(
+                    value.provider_type,
+                )
+                .into(),
+                issuer_url: value.issuer_url.deref().clone(),
+                client_id: value.client_id.deref().clone(),
+                client_secret: value.client_secret.secret().clone(),
+                redirect_url: value.redirect_url.deref().clone(),
+                scopes: value.scopes.iter().map(|s| s.deref().clone()).collect(),
+                mode: golem_api_grpc::proto::golem::registry::SecuritySchemeMode::from(value.mode)
+                    .into(),
+                audience: value.audience.deref().clone(),
+                api_key: false,
+            },
+            SecuritySchemeDetails::ApiKey(value) => Self {
+                id: Some(value.id.into()),
+                name: value.name.0,
+                api_key: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl TryFrom<proto::golem::customapi::SecuritySchemeApiKey> for SecuritySchemeApiKey {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::SecuritySchemeApiKey) -> Result<Self, Self::Error> {
+        let expires_at = value
+            .expires_at
+            .map(SystemTime::try_from)
+            .transpose()
+            .map_err(|e| format!("Failed parsing expires_at: {e}"))?
+            .map(chrono::DateTime::from);
+
+        Ok(Self {
+            id: ApiKeyId(value.id.ok_or("id field missing")?.into()),
+            name: value.name,
+            secret_hash: value.secret_hash,
+            scopes: value.scopes.into_iter().map(Scope::new).collect(),
+            expires_at,
+        })
+    }
+}
+
+impl From<SecuritySchemeApiKey> for proto::golem::customapi::SecuritySchemeApiKey {
+    fn from(value: SecuritySchemeApiKey) -> Self {
         Self {
-            id: Some(value.id.into()),
-            name: value.name.0,
-            provider: golem_api_grpc::proto::golem::registry::SecuritySchemeProvider::from(
-                value.provider_type,
-            )
-            .into(),
-            client_id: value.client_id.deref().clone(),
-            client_secret: value.client_secret.secret().clone(),
-            redirect_url: value.redirect_url.deref().clone(),
-            scopes: value.scopes.iter().map(|s| s.deref().clone()).collect(),
+            id: Some(value.id.0.into()),
+            name: value.name,
+            secret_hash: value.secret_hash,
+            scopes: value
+                .scopes
+                .into_iter()
+                .map(|s| s.deref().clone())
+                .collect(),
+            expires_at: value
+                .expires_at
+                .map(|expires_at| prost_types::Timestamp::from(SystemTime::from(expires_at))),
         }
     }
 }
@@ -85,7 +170,7 @@ impl TryFrom<proto::golem::customapi::CompiledRoutes> for CompiledRoutes {
         let mut security_schemes = HashMap::new();
         for scheme in value.security_schemes {
             let scheme: SecuritySchemeDetails = scheme.try_into()?;
-            security_schemes.insert(scheme.id, scheme);
+            security_schemes.insert(scheme.id(), scheme);
         }
 
         let routes = value
@@ -135,7 +220,13 @@ impl TryFrom<proto::golem::customapi::CompiledRoute> for CompiledRoute {
             body: value.body.ok_or("Missing body")?.try_into()?,
             behavior: value.behavior.ok_or("Missing behavior")?.try_into()?,
             security_scheme: value.security_scheme.map(TryInto::try_into).transpose()?,
+            required_scopes: value.required_scopes,
             cors: value.cors.ok_or("Missing cors")?.try_into()?,
+            rate_limits: value
+                .rate_limits
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
@@ -150,6 +241,8 @@ impl From<CompiledRoute> for proto::golem::customapi::CompiledRoute {
             behavior: Some(value.behavior.into()),
             security_scheme: value.security_scheme.map(Into::into),
             cors: Some(value.cors.into()),
+            rate_limits: value.rate_limits.into_iter().map(Into::into).collect(),
+            required_scopes: value.required_scopes,
         }
     }
 }
@@ -633,3 +726,42 @@ impl From<CorsOptions> for golem_api_grpc::proto::golem::customapi::CorsOptions
         }
     }
 }
+
+impl TryFrom<proto::golem::customapi::RateLimit> for RateLimit {
+    type Error = String;
+
+    fn try_from(value: proto::golem::customapi::RateLimit) -> Result<Self, Self::Error> {
+        let scope = match value.scope() {
+            proto::golem::customapi::RateLimitScope::Unspecified => {
+                return Err("Missing rate limit scope".to_string());
+            }
+            proto::golem::customapi::RateLimitScope::Route => RateLimitScope::Route,
+            proto::golem::customapi::RateLimitScope::Principal => RateLimitScope::Principal,
+            proto::golem::customapi::RateLimitScope::ClientIp => RateLimitScope::ClientIp,
+        };
+
+        Ok(Self {
+            scope,
+            requests: value.requests,
+            interval_seconds: value.interval_seconds,
+            burst: value.burst,
+        })
+    }
+}
+
+impl From<RateLimit> for proto::golem::customapi::RateLimit {
+    fn from(value: RateLimit) -> Self {
+        let scope = match value.scope {
+            RateLimitScope::Route => proto::golem::customapi::RateLimitScope::Route,
+            RateLimitScope::Principal => proto::golem::customapi::RateLimitScope::Principal,
+            RateLimitScope::ClientIp => proto::golem::customapi::RateLimitScope::ClientIp,
+        };
+
+        Self {
+            scope: scope.into(),
+            requests: value.requests,
+            interval_seconds: value.interval_seconds,
+            burst: value.burst,
+        }
+    }
+}
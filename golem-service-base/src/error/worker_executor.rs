@@ -116,6 +116,10 @@ pub enum WorkerExecutorError {
         error: WorkerError,
         stderr: String,
     },
+    /// The agent was stopped because it violated one of its per-agent quotas
+    AgentQuotaExceeded {
+        details: String,
+    },
 }
 
 impl WorkerExecutorError {
@@ -300,6 +304,9 @@ impl Display for WorkerExecutorError {
             Self::InvocationFailed { error, stderr } => {
                 write!(f, "Component trapped: {}", error.to_string(stderr))
             }
+            Self::AgentQuotaExceeded { details } => {
+                write!(f, "Agent quota exceeded: {details}")
+            }
         }
     }
 }
@@ -341,6 +348,7 @@ impl Error for WorkerExecutorError {
             Self::Unknown { .. } => "Unknown error",
             Self::ShardingNotReady => "Sharding not ready",
             Self::FileSystemError { .. } => "File system error",
+            Self::AgentQuotaExceeded { .. } => "Agent quota exceeded",
         }
     }
 }
@@ -374,6 +382,7 @@ impl ApiErrorDetails for WorkerExecutorError {
             Self::Unknown { .. } => "Unknown",
             Self::ShardingNotReady => "ShardingNotReady",
             Self::FileSystemError { .. } => "FileSystemError",
+            Self::AgentQuotaExceeded { .. } => "AgentQuotaExceeded",
         }
     }
 
@@ -385,7 +394,8 @@ impl ApiErrorDetails for WorkerExecutorError {
             | Self::PromiseDropped { .. }
             | Self::PromiseAlreadyCompleted { .. }
             | Self::Interrupted { .. }
-            | Self::InvalidShardId { .. } => true,
+            | Self::InvalidShardId { .. }
+            | Self::AgentQuotaExceeded { .. } => true,
             Self::InvalidRequest { .. }
             | Self::WorkerCreationFailed { .. }
             | Self::FailedToResumeWorker { .. }
@@ -459,6 +469,9 @@ impl From<WorkerExecutorError> for Status {
             WorkerExecutorError::PreviousInvocationFailed { .. } => {
                 Self::failed_precondition(format!("{value}"))
             }
+            WorkerExecutorError::AgentQuotaExceeded { .. } => {
+                Self::resource_exhausted(format!("{value}"))
+            }
             _ => Self::internal(format!("{value}")),
         }
     }
@@ -693,6 +706,13 @@ impl From<WorkerExecutorError> for golem::worker::v1::WorkerExecutionError {
                     }
                 ))
             },
+            WorkerExecutorError::AgentQuotaExceeded { details } => Self {
+                error: Some(
+                    golem::worker::v1::worker_execution_error::Error::AgentQuotaExceeded(
+                        golem::worker::v1::AgentQuotaExceeded { details },
+                    ),
+                ),
+            },
         }
     }
 }
@@ -880,6 +900,11 @@ impl TryFrom<golem::worker::v1::WorkerExecutionError> for WorkerExecutorError {
                 error: inner.error.ok_or("no trap_cause field")?.try_into()?,
                 stderr: inner.stderr
              }),
+            Some(golem::worker::v1::worker_execution_error::Error::AgentQuotaExceeded(inner)) => {
+                Ok(Self::AgentQuotaExceeded {
+                    details: inner.details,
+                })
+            }
         }
     }
 }
@@ -20,6 +20,7 @@ use golem_common::model::application::ApplicationId;
 use golem_common::model::component::ComponentId;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::plugin_registration::PluginRegistrationId;
+use golem_common::model::security_scheme::SecuritySchemeId;
 use golem_common::model::{IdempotencyKey, PromiseId, WorkerId};
 use std::fmt::{Debug, Display, Formatter};
 
@@ -133,6 +134,14 @@ pub fn proto_component_id_string(
         .map(|v| v.to_string())
 }
 
+pub fn proto_security_scheme_id_string(
+    security_scheme_id: &Option<golem_api_grpc::proto::golem::registry::SecuritySchemeId>,
+) -> Option<String> {
+    (*security_scheme_id)
+        .and_then(|v| TryInto::<SecuritySchemeId>::try_into(v).ok())
+        .map(|v| v.to_string())
+}
+
 pub fn proto_worker_id_string(
     worker_id: &Option<golem_api_grpc::proto::golem::worker::WorkerId>,
 ) -> Option<String> {
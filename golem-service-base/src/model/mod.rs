@@ -21,11 +21,12 @@ use golem_common::model::account::AccountId;
 use golem_common::model::component::{
     ComponentFilePermissions, ComponentRevision, PluginInstallationAction,
 };
-use golem_common::model::oplog::{OplogCursor, PublicOplogEntryWithIndex};
+use golem_common::model::oplog::{OplogCursor, OplogSearchHit, PublicOplogEntryWithIndex};
 use golem_common::model::worker::{
-    FlatComponentFileSystemNode, FlatComponentFileSystemNodeKind, WorkerUpdateMode,
+    FlatComponentFileSystemNode, FlatComponentFileSystemNodeKind, RecurringInvocation,
+    RetentionCandidate, ScheduleRecurrence, WorkerUpdateMode,
 };
-use golem_common::model::{OplogIndex, ScanCursor, WorkerFilter, WorkerId};
+use golem_common::model::{OplogIndex, ScanCursor, Timestamp, WorkerFilter, WorkerId};
 use golem_wasm::ValueAndType;
 use golem_wasm::json::OptionallyValueAndTypeJson;
 use poem_openapi::Object;
@@ -41,19 +42,36 @@ pub struct WorkerCreationResponse {
     pub component_revision: ComponentRevision,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
 pub struct CompleteParameters {
     pub oplog_idx: u64,
+    #[serde(default)]
+    #[oai(default)]
     pub data: Vec<u8>,
+    /// JSON payload used instead of `data`, required by promises created with a payload type
+    pub json: Option<serde_json::Value>,
+    /// If set, the promise is rejected with this error instead of being completed
+    pub rejection: Option<String>,
+}
+
+impl CompleteParameters {
+    /// The payload to complete the promise with
+    pub fn payload(&self) -> Vec<u8> {
+        match &self.json {
+            Some(json) => json.to_string().into_bytes(),
+            None => self.data.clone(),
+        }
+    }
 }
 
 impl From<CompleteParameters> for golem_api_grpc::proto::golem::worker::CompleteParameters {
     fn from(value: CompleteParameters) -> Self {
         Self {
             oplog_idx: value.oplog_idx,
-            data: value.data,
+            data: value.payload(),
+            rejection: value.rejection,
         }
     }
 }
@@ -89,6 +107,9 @@ pub struct ActivatePluginResponse {}
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct DeactivatePluginResponse {}
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct ChangeWorkerLabelsResponse {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct RevertWorkerResponse {}
 
@@ -100,6 +121,37 @@ pub struct CancelInvocationResponse {
     pub canceled: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleRecurringInvocationRequest {
+    /// Name of the exported function to be invoked on each occurrence
+    pub function: String,
+    pub params: Vec<OptionallyValueAndTypeJson>,
+    pub recurrence: ScheduleRecurrence,
+    /// Time of the first occurrence, defaults to the current time
+    pub start_at: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct GetRecurringInvocationsResponse {
+    pub invocations: Vec<RecurringInvocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct CancelRecurringInvocationResponse {
+    pub canceled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[oai(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct GetRetentionReportResponse {
+    pub candidates: Vec<RetentionCandidate>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
@@ -110,6 +162,23 @@ pub struct GetOplogResponse {
     pub last_index: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct SearchComponentOplogResponse {
+    /// Matching oplog entries of the component's workers, newest first
+    pub hits: Vec<OplogSearchHit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ImportOplogResponse {
+    pub worker_id: WorkerId,
+    /// Index of the last entry in the imported oplog
+    pub last_index: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
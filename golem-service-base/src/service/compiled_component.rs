@@ -13,13 +13,13 @@
 // limitations under the License.
 
 use crate::error::worker_executor::WorkerExecutorError;
-use crate::storage::blob::{BlobStorage, BlobStorageNamespace};
+use crate::service::compiled_component_files::CompiledComponentFilesService;
+use crate::storage::blob::BlobStorage;
 use async_trait::async_trait;
 use golem_common::SafeDisplay;
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::environment::EnvironmentId;
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::time::Instant;
 use tracing::{debug, info_span};
@@ -98,16 +98,118 @@ pub fn configured(
 }
 
 pub struct DefaultCompiledComponentService {
-    blob_storage: Arc<dyn BlobStorage>,
+    files: CompiledComponentFilesService,
 }
 
 impl DefaultCompiledComponentService {
     pub fn new(blob_storage: Arc<dyn BlobStorage>) -> Self {
-        Self { blob_storage }
+        Self {
+            files: CompiledComponentFilesService::new(blob_storage),
+        }
     }
+}
+
+#[async_trait]
+impl CompiledComponentService for DefaultCompiledComponentService {
+    async fn get(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+        engine: &Engine,
+    ) -> Result<Option<Component>, WorkerExecutorError> {
+        match self
+            .files
+            .get(environment_id, component_id, component_revision)
+            .await
+        {
+            Ok(None) => Ok(None),
+            Ok(Some(bytes)) => {
+                let start = Instant::now();
+                let component = {
+                    let span = info_span!("Loading precompiled WASM component");
+                    let _enter = span.enter();
+
+                    // Fails if the stored component was compiled by an engine with incompatible
+                    // settings, for example when it was imported from a component bundle
+                    let component = unsafe {
+                        Component::deserialize(engine, &bytes).map_err(|err| {
+                            WorkerExecutorError::component_download_failed(
+                                component_id,
+                                component_revision,
+                                format!("Could not deserialize compiled component: {err}"),
+                            )
+                        })?
+                    };
+                    let end = Instant::now();
 
-    fn key(component_id: ComponentId, component_revision: ComponentRevision) -> PathBuf {
-        Path::new(&component_id.to_string()).join(format!("{component_revision}.cwasm"))
+                    let load_time = end.duration_since(start);
+                    debug!(
+                        "Loaded precompiled image for {} in {}ms",
+                        component_id,
+                        load_time.as_millis(),
+                    );
+                    component
+                };
+
+                Ok(Some(component))
+            }
+            Err(err) => Err(WorkerExecutorError::component_download_failed(
+                component_id,
+                component_revision,
+                format!("Could not download compiled component: {err}"),
+            )),
+        }
+    }
+
+    async fn put(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+        component: &Component,
+    ) -> Result<(), WorkerExecutorError> {
+        let bytes = component
+            .serialize()
+            .expect("Could not serialize component");
+        self.files
+            .put(environment_id, component_id, component_revision, &bytes)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::component_download_failed(
+                    component_id,
+                    component_revision,
+                    format!("Could not store compiled component: {err}"),
+                )
+            })
+    }
+}
+
+pub struct CompiledComponentServiceDisabledConfig {}
+
+pub fn configured(
+    config: &CompiledComponentServiceConfig,
+    blob_storage: Arc<dyn BlobStorage>,
+) -> Arc<dyn CompiledComponentService> {
+    match config {
+        CompiledComponentServiceConfig::Enabled(_) => {
+            Arc::new(DefaultCompiledComponentService::new(blob_storage))
+        }
+        CompiledComponentServiceConfig::Disabled(_) => {
+            Arc::new(CompiledComponentServiceDisabled::new())
+        }
+    }
+}
+
+pub struct DefaultCompiledComponentService {
+    files: CompiledComponentFilesService,
+}
+
+impl DefaultCompiledComponentService {
+    pub fn new(blob_storage: Arc<dyn BlobStorage>) -> Self {
+        Self {
+            files: CompiledComponentFilesService::new(blob_storage),
+        }
     }
 }
 
@@ -121,13 +223,8 @@ impl CompiledComponentService for DefaultCompiledComponentService {
         engine: &Engine,
     ) -> Result<Option<Component>, WorkerExecutorError> {
         match self
-            .blob_storage
-            .get_raw(
-                "compiled_component",
-                "get",
-                BlobStorageNamespace::CompilationCache { environment_id },
-                &Self::key(component_id, component_revision),
-            )
+            .files
+            .get(environment_id, component_id, component_revision)
             .await
         {
             Ok(None) => Ok(None),
@@ -137,6 +234,8 @@ impl CompiledComponentService for DefaultCompiledComponentService {
                     let span = info_span!("Loading precompiled WASM component");
                     let _enter = span.enter();
 
+                    // Fails if the stored component was compiled by an engine with incompatible
+                    // settings, for example when it was imported from a component bundle
                     let component = unsafe {
                         Component::deserialize(engine, &bytes).map_err(|err| {
                             WorkerExecutorError::component_download_failed(
@@ -177,13 +276,24 @@ impl CompiledComponentService for DefaultCompiledComponentService {
         let bytes = component
             .serialize()
             .expect("Could not serialize component");
+        self.put_serialized(environment_id, component_id, component_revision, &bytes)
+            .await
+    }
+
+    async fn put_serialized(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+        bytes: &[u8],
+    ) -> Result<(), WorkerExecutorError> {
         self.blob_storage
             .put_raw(
                 "compiled_component",
                 "put",
                 BlobStorageNamespace::CompilationCache { environment_id },
                 &Self::key(component_id, component_revision),
-                &bytes,
+                bytes,
             )
             .await
             .map_err(|err| {
@@ -0,0 +1,75 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::blob::{BlobStorage, BlobStorageNamespace};
+use anyhow::Error;
+use golem_common::model::component::{ComponentId, ComponentRevision};
+use golem_common::model::environment::EnvironmentId;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const COMPILED_COMPONENT_LABEL: &str = "compiled_component";
+
+/// Service for storing the serialized compiled native binaries of WebAssembly components.
+///
+/// Does not depend on wasmtime, so it can be used by the services which only store pre-compiled
+/// components, without loading them.
+#[derive(Debug)]
+pub struct CompiledComponentFilesService {
+    blob_storage: Arc<dyn BlobStorage>,
+}
+
+impl CompiledComponentFilesService {
+    pub fn new(blob_storage: Arc<dyn BlobStorage>) -> Self {
+        Self { blob_storage }
+    }
+
+    pub async fn get(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.blob_storage
+            .get_raw(
+                COMPILED_COMPONENT_LABEL,
+                "get",
+                BlobStorageNamespace::CompilationCache { environment_id },
+                &Self::key(component_id, component_revision),
+            )
+            .await
+    }
+
+    pub async fn put(
+        &self,
+        environment_id: EnvironmentId,
+        component_id: ComponentId,
+        component_revision: ComponentRevision,
+        bytes: &[u8],
+    ) -> Result<(), Error> {
+        self.blob_storage
+            .put_raw(
+                COMPILED_COMPONENT_LABEL,
+                "put",
+                BlobStorageNamespace::CompilationCache { environment_id },
+                &Self::key(component_id, component_revision),
+                bytes,
+            )
+            .await
+    }
+
+    fn key(component_id: ComponentId, component_revision: ComponentRevision) -> PathBuf {
+        Path::new(&component_id.to_string()).join(format!("{component_revision}.cwasm"))
+    }
+}
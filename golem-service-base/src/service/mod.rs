@@ -14,6 +14,7 @@
 
 #[cfg(feature = "worker-executor")]
 pub mod compiled_component;
+pub mod compiled_component_files;
 pub mod initial_component_files;
 pub mod plugin_wasm_files;
 pub mod routing_table;
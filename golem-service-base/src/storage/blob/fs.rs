@@ -66,6 +66,9 @@ impl FileSystemBlobStorage {
                 result.push("compilation_cache");
                 result.push(environment_id.to_string());
             }
+            BlobStorageNamespace::CompiledArtifacts => {
+                result.push("compiled_artifacts");
+            }
             BlobStorageNamespace::CustomStorage { environment_id } => {
                 result.push("custom_data");
                 result.push(environment_id.to_string());
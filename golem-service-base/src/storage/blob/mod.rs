@@ -363,6 +363,8 @@ pub enum BlobStorageNamespace {
     CompilationCache {
         environment_id: EnvironmentId,
     },
+    /// Compiled components addressed by the hash of their WASM binary, shared by all environments
+    CompiledArtifacts,
     InitialComponentFiles {
         environment_id: EnvironmentId,
     },
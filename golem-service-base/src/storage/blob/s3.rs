@@ -73,7 +73,8 @@ impl S3BlobStorage {
 
     fn bucket_of(&self, namespace: &BlobStorageNamespace) -> &String {
         match namespace {
-            BlobStorageNamespace::CompilationCache { .. } => &self.config.compilation_cache_bucket,
+            BlobStorageNamespace::CompilationCache { .. }
+            | BlobStorageNamespace::CompiledArtifacts => &self.config.compilation_cache_bucket,
             BlobStorageNamespace::CustomStorage { .. } => &self.config.custom_data_bucket,
             BlobStorageNamespace::OplogPayload { .. } => &self.config.oplog_payload_bucket,
             BlobStorageNamespace::CompressedOplog { level, .. } => {
@@ -137,6 +138,15 @@ impl S3BlobStorage {
                         .to_path_buf()
                 }
             }
+            BlobStorageNamespace::CompiledArtifacts => {
+                if self.config.object_prefix.is_empty() {
+                    PathBuf::from("compiled_artifacts")
+                } else {
+                    Path::new(&self.config.object_prefix)
+                        .join("compiled_artifacts")
+                        .to_path_buf()
+                }
+            }
             BlobStorageNamespace::PluginWasmFiles { account_id } => {
                 let account_id_string = account_id.to_string();
                 if self.config.object_prefix.is_empty() {
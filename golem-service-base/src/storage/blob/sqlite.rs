@@ -59,6 +59,7 @@ impl SqliteBlobStorage {
             BlobStorageNamespace::CompilationCache { environment_id } => {
                 format!("compilation_cache-{environment_id}")
             }
+            BlobStorageNamespace::CompiledArtifacts => "compiled_artifacts".to_string(),
             BlobStorageNamespace::CustomStorage { environment_id } => {
                 format!("custom_data-{environment_id}")
             }
@@ -413,6 +413,11 @@ fn compilation_cache() -> BlobStorageNamespace {
     }
 }
 
+#[test_dep(tagged_as = "ca")]
+fn compiled_artifacts() -> BlobStorageNamespace {
+    BlobStorageNamespace::CompiledArtifacts
+}
+
 #[test_dep(tagged_as = "co")]
 fn compressed_oplog() -> BlobStorageNamespace {
     BlobStorageNamespace::CompressedOplog {
@@ -425,7 +430,7 @@ fn compressed_oplog() -> BlobStorageNamespace {
 }
 
 define_matrix_dimension!(storage: Arc<dyn GetBlobStorage + Send + Sync> -> "in_memory", "fs", "s3", "s3_prefixed", "sqlite");
-define_matrix_dimension!(ns: BlobStorageNamespace -> "cc", "co");
+define_matrix_dimension!(ns: BlobStorageNamespace -> "cc", "ca", "co");
 
 #[test]
 #[tracing::instrument]
@@ -0,0 +1,20 @@
+CREATE TABLE shard_manager_routing_table
+(
+    name    TEXT   NOT NULL,
+    version BIGINT NOT NULL,
+    state   BYTEA  NOT NULL,
+
+    CONSTRAINT shard_manager_routing_table_pk
+        PRIMARY KEY (name)
+);
+
+-- Lease of the shard manager replica performing the shard management
+CREATE TABLE shard_manager_leader
+(
+    name       TEXT   NOT NULL,
+    holder     TEXT   NOT NULL,
+    expires_at BIGINT NOT NULL,
+
+    CONSTRAINT shard_manager_leader_pk
+        PRIMARY KEY (name)
+);
@@ -0,0 +1,18 @@
+CREATE TABLE shard_manager_routing_table
+(
+    name    TEXT    NOT NULL,
+    version INTEGER NOT NULL,
+    state   BLOB    NOT NULL,
+
+    PRIMARY KEY (name)
+);
+
+-- Lease of the shard manager replica performing the shard management
+CREATE TABLE shard_manager_leader
+(
+    name       TEXT    NOT NULL,
+    holder     TEXT    NOT NULL,
+    expires_at INTEGER NOT NULL,
+
+    PRIMARY KEY (name)
+);
@@ -40,6 +40,12 @@ pub enum ShardManagerError {
     RedisError(#[from] golem_common::redis::RedisError),
     #[error("IO error {0}")]
     IoError(#[from] std::io::Error),
+    #[error("Database error {0}")]
+    DbError(#[from] golem_service_base::repo::RepoError),
+    #[error("Routing table was concurrently modified by another shard manager")]
+    ConcurrentModification,
+    #[error("Shard manager leadership was lost")]
+    LostLeadership,
 }
 
 impl IsRetriableError for ShardManagerError {
@@ -54,6 +60,9 @@ impl IsRetriableError for ShardManagerError {
             ShardManagerError::SerializationError(_) => false,
             ShardManagerError::RedisError(_) => false,
             ShardManagerError::IoError(_) => false,
+            ShardManagerError::DbError(_) => false,
+            ShardManagerError::ConcurrentModification => false,
+            ShardManagerError::LostLeadership => false,
         }
     }
 
@@ -101,6 +110,17 @@ impl From<ShardManagerError> for golem::shardmanager::v1::ShardManagerError {
             ShardManagerError::IoError(err) => {
                 error(shard_manager_error::Error::Unknown, err.to_string())
             }
+            ShardManagerError::DbError(err) => {
+                error(shard_manager_error::Error::Unknown, err.to_string())
+            }
+            ShardManagerError::ConcurrentModification => error(
+                shard_manager_error::Error::Unknown,
+                "ConcurrentModification".to_string(),
+            ),
+            ShardManagerError::LostLeadership => error(
+                shard_manager_error::Error::Unknown,
+                "LostLeadership".to_string(),
+            ),
         }
     }
 }
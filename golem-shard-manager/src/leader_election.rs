@@ -0,0 +1,268 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::ShardManagerError;
+use crate::shard_manager_config::LeaderElectionConfig;
+use async_trait::async_trait;
+use conditional_trait_gen::trait_gen;
+use golem_service_base::db::postgres::PostgresPool;
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::db::{Pool, PoolApi};
+use indoc::indoc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// Lease based leader election among shard manager replicas sharing the same persistence.
+#[async_trait]
+pub trait LeaderElection {
+    /// Acquires or renews the lease for `holder`. Returns false if another holder has a valid lease.
+    async fn try_acquire(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+    ) -> Result<bool, ShardManagerError>;
+}
+
+pub struct DbLeaderElection<DBP: Pool> {
+    pool: DBP,
+}
+
+static LEADER_NAME: &str = "shard_manager";
+static METRICS_SVC_NAME: &str = "shard_manager_leader_election";
+
+impl<DBP: Pool> DbLeaderElection<DBP> {
+    pub fn new(pool: DBP) -> Self {
+        Self { pool }
+    }
+}
+
+#[trait_gen(PostgresPool -> PostgresPool, SqlitePool)]
+#[async_trait]
+impl LeaderElection for DbLeaderElection<PostgresPool> {
+    async fn try_acquire(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+    ) -> Result<bool, ShardManagerError> {
+        let now = now_millis();
+        let result = self
+            .pool
+            .with_rw(METRICS_SVC_NAME, "try_acquire")
+            .execute(
+                sqlx::query(indoc! { r#"
+                    INSERT INTO shard_manager_leader (name, holder, expires_at)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (name) DO UPDATE
+                    SET holder = excluded.holder, expires_at = excluded.expires_at
+                    WHERE shard_manager_leader.holder = excluded.holder
+                       OR shard_manager_leader.expires_at < $4
+                "#})
+                .bind(LEADER_NAME)
+                .bind(holder.to_string())
+                .bind(now + lease_duration.as_millis() as i64)
+                .bind(now),
+            )
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time is before the UNIX epoch")
+        .as_millis() as i64
+}
+
+/// Whether this replica is still the leader, based on the expiry of its last acquired lease
+#[derive(Clone, Default)]
+pub struct Leadership {
+    // Not set if there is no leader election, in which case the leadership is always held
+    lease_expires_at: Option<Arc<Mutex<Instant>>>,
+}
+
+impl Leadership {
+    fn acquired(lease_expires_at: Instant) -> Self {
+        Self {
+            lease_expires_at: Some(Arc::new(Mutex::new(lease_expires_at))),
+        }
+    }
+
+    fn renewed(&self, lease_expires_at: Instant) {
+        if let Some(current) = &self.lease_expires_at {
+            *current.lock().unwrap() = lease_expires_at;
+        }
+    }
+
+    fn lost(&self) {
+        self.renewed(Instant::now());
+    }
+
+    pub fn is_held(&self) -> bool {
+        match &self.lease_expires_at {
+            Some(lease_expires_at) => Instant::now() < *lease_expires_at.lock().unwrap(),
+            None => true,
+        }
+    }
+}
+
+/// Blocks until `holder` becomes the leader
+pub async fn await_leadership(
+    leader_election: &Arc<dyn LeaderElection + Send + Sync>,
+    holder: &str,
+    config: &LeaderElectionConfig,
+) -> Leadership {
+    loop {
+        // The lease is counted from before the request, so it never outlives the stored one
+        let requested_at = Instant::now();
+        match leader_election
+            .try_acquire(holder, config.lease_duration)
+            .await
+        {
+            Ok(true) => {
+                info!(holder = %holder, "Acquired shard manager leadership");
+                break Leadership::acquired(requested_at + config.lease_duration);
+            }
+            Ok(false) => {
+                info!(holder = %holder, "Another shard manager is the leader, waiting");
+            }
+            Err(err) => {
+                warn!(holder = %holder, "Failed to acquire shard manager leadership: {err}");
+            }
+        }
+        tokio::time::sleep(config.renew_interval).await;
+    }
+}
+
+/// Periodically renews the lease of `holder`. Fails if the leadership was taken over by another
+/// replica, or if the lease could not be renewed before it expired.
+pub async fn keep_leadership(
+    leader_election: Arc<dyn LeaderElection + Send + Sync>,
+    holder: String,
+    leadership: Leadership,
+    config: LeaderElectionConfig,
+) -> anyhow::Result<()> {
+    loop {
+        tokio::time::sleep(config.renew_interval).await;
+        let requested_at = Instant::now();
+        match leader_election
+            .try_acquire(&holder, config.lease_duration)
+            .await
+        {
+            Ok(true) => {
+                leadership.renewed(requested_at + config.lease_duration);
+            }
+            Ok(false) => {
+                leadership.lost();
+                return Err(anyhow::anyhow!("Lost shard manager leadership"));
+            }
+            Err(err) => {
+                warn!(holder = %holder, "Failed to renew shard manager leadership: {err}");
+                if !leadership.is_held() {
+                    return Err(anyhow::anyhow!(
+                        "Shard manager leadership lease expired: {err}"
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::leader_election::{await_leadership, DbLeaderElection, LeaderElection};
+    use crate::persistence::migrate_sqlite;
+    use crate::shard_manager_config::LeaderElectionConfig;
+    use golem_common::config::DbSqliteConfig;
+    use golem_service_base::db::sqlite::SqlitePool;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    async fn leader_election(dir: &TempDir) -> Arc<dyn LeaderElection + Send + Sync> {
+        let config = DbSqliteConfig {
+            database: dir
+                .path()
+                .join("shard-manager.db")
+                .to_string_lossy()
+                .to_string(),
+            max_connections: 4,
+            foreign_keys: false,
+        };
+        migrate_sqlite(&config).await.unwrap();
+        Arc::new(DbLeaderElection::new(
+            SqlitePool::configured(&config).await.unwrap(),
+        ))
+    }
+
+    #[test]
+    async fn only_one_holder_has_a_valid_lease() {
+        let dir = TempDir::new().unwrap();
+        let leader_election = leader_election(&dir).await;
+        let lease_duration = Duration::from_secs(60);
+
+        assert!(leader_election
+            .try_acquire("first", lease_duration)
+            .await
+            .unwrap());
+        assert!(!leader_election
+            .try_acquire("second", lease_duration)
+            .await
+            .unwrap());
+        // The holder can renew its own lease
+        assert!(leader_election
+            .try_acquire("first", lease_duration)
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    async fn expired_lease_can_be_taken_over() {
+        let dir = TempDir::new().unwrap();
+        let leader_election = leader_election(&dir).await;
+
+        assert!(leader_election
+            .try_acquire("first", Duration::from_millis(100))
+            .await
+            .unwrap());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(leader_election
+            .try_acquire("second", Duration::from_secs(60))
+            .await
+            .unwrap());
+        assert!(!leader_election
+            .try_acquire("first", Duration::from_secs(60))
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    async fn leadership_expires_with_the_lease() {
+        let dir = TempDir::new().unwrap();
+        let leader_election = leader_election(&dir).await;
+        let config = LeaderElectionConfig {
+            lease_duration: Duration::from_millis(200),
+            renew_interval: Duration::from_millis(50),
+        };
+
+        let leadership = await_leadership(&leader_election, "first", &config).await;
+        assert!(leadership.is_held());
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!leadership.is_held());
+    }
+}
@@ -14,6 +14,7 @@
 
 mod error;
 mod healthcheck;
+mod leader_election;
 mod model;
 mod persistence;
 mod rebalancing;
@@ -23,7 +24,10 @@ mod worker_executor;
 
 use self::error::ShardManagerTraceErrorKind;
 use crate::healthcheck::{get_unhealthy_pods, GrpcHealthCheck, HealthCheck};
-use crate::persistence::RoutingTableFileSystemPersistence;
+use crate::leader_election::{
+    await_leadership, keep_leadership, DbLeaderElection, LeaderElection, Leadership,
+};
+use crate::persistence::{RoutingTableDbPersistence, RoutingTableFileSystemPersistence};
 use crate::shard_manager_config::{HealthCheckK8sConfig, HealthCheckMode, PersistenceConfig};
 use error::ShardManagerError;
 use futures::TryFutureExt;
@@ -37,6 +41,7 @@ use golem_service_base::grpc::server::GrpcServerTlsConfig;
 use model::{Pod, RoutingTable};
 use persistence::{RoutingTablePersistence, RoutingTableRedisPersistence};
 use prometheus::Registry;
+use rebalancing::Rebalance;
 use shard_management::ShardManagement;
 use shard_manager_config::ShardManagerConfig;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
@@ -51,7 +56,7 @@ use tonic_tracing_opentelemetry::middleware;
 use tonic_tracing_opentelemetry::middleware::filters;
 use tracing::Instrument;
 use tracing::{debug, info, warn};
-use worker_executor::{WorkerExecutorService, WorkerExecutorServiceDefault};
+use worker_executor::{get_pod_capacities, WorkerExecutorService, WorkerExecutorServiceDefault};
 
 #[cfg(test)]
 test_r::enable!();
@@ -65,6 +70,7 @@ pub struct ShardManagerServiceImpl {
     shard_management: ShardManagement,
     shard_manager_config: Arc<ShardManagerConfig>,
     health_check: Arc<dyn HealthCheck + Send + Sync>,
+    worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
 }
 
 impl ShardManagerServiceImpl {
@@ -73,12 +79,17 @@ impl ShardManagerServiceImpl {
         worker_executor_service: Arc<dyn WorkerExecutorService + Send + Sync>,
         shard_manager_config: Arc<ShardManagerConfig>,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
+        leadership: Leadership,
+        join_set: &mut JoinSet<anyhow::Result<()>>,
     ) -> Result<ShardManagerServiceImpl, ShardManagerError> {
         let shard_management = ShardManagement::new(
             persistence_service.clone(),
-            worker_executor_service,
+            worker_executor_service.clone(),
             health_check.clone(),
             shard_manager_config.rebalance_threshold,
+            shard_manager_config.rebalancing.clone(),
+            leadership,
+            join_set,
         )
         .await?;
 
@@ -86,6 +97,7 @@ impl ShardManagerServiceImpl {
             shard_management,
             shard_manager_config,
             health_check,
+            worker_executors: worker_executor_service,
         };
 
         shard_manager_service.start_health_check();
@@ -112,22 +124,48 @@ impl ShardManagerServiceImpl {
         Ok(())
     }
 
+    async fn preview_rebalance_internal(&self) -> Rebalance {
+        let rebalance = self.shard_management.preview_rebalance().await;
+        debug!("Providing rebalance preview: {}", rebalance);
+        rebalance
+    }
+
     fn start_health_check(&self) {
         let delay = self.shard_manager_config.health_check.delay;
         let shard_management = self.shard_management.clone();
         let health_check = self.health_check.clone();
+        let worker_executors = self.worker_executors.clone();
+        let collect_capacities = self.shard_manager_config.rebalancing.capacity_weighting
+            || self.shard_manager_config.rebalancing.load_weighting;
 
         tokio::spawn(
             async move {
                 loop {
                     tokio::time::sleep(delay).await;
-                    Self::health_check(shard_management.clone(), health_check.clone()).await
+                    Self::health_check(shard_management.clone(), health_check.clone()).await;
+                    if collect_capacities {
+                        Self::collect_capacities(
+                            shard_management.clone(),
+                            worker_executors.clone(),
+                        )
+                        .await;
+                    }
                 }
             }
             .in_current_span(),
         );
     }
 
+    async fn collect_capacities(
+        shard_management: ShardManagement,
+        worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+    ) {
+        let routing_table = shard_management.current_snapshot().await;
+        let capacities = get_pod_capacities(worker_executors, &routing_table.get_pods()).await;
+        debug!("Collected capacities of {} pods", capacities.len());
+        shard_management.update_capacities(capacities).await;
+    }
+
     async fn health_check(
         shard_management: ShardManagement,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
@@ -213,6 +251,28 @@ impl ShardManagerService for ShardManagerServiceImpl {
             result: Some(result),
         }))
     }
+
+    async fn preview_rebalance(
+        &self,
+        _request: tonic::Request<golem::shardmanager::v1::PreviewRebalanceRequest>,
+    ) -> Result<Response<golem::shardmanager::v1::PreviewRebalanceResponse>, tonic::Status> {
+        let record = recorded_grpc_api_request!("preview_rebalance",);
+
+        let response = self
+            .preview_rebalance_internal()
+            .instrument(record.span.clone())
+            .await;
+
+        Ok(Response::new(
+            golem::shardmanager::v1::PreviewRebalanceResponse {
+                result: Some(
+                    golem::shardmanager::v1::preview_rebalance_response::Result::Success(
+                        response.into(),
+                    ),
+                ),
+            },
+        ))
+    }
 }
 
 pub async fn run(
@@ -241,23 +301,72 @@ pub async fn run(
 
     let shard_manager_config = Arc::new(shard_manager_config.clone());
 
-    let persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync> =
-        match &shard_manager_config.persistence {
-            PersistenceConfig::Redis(redis) => {
-                let pool = golem_common::redis::RedisPool::configured(redis).await?;
-                Arc::new(RoutingTableRedisPersistence::new(
-                    &pool,
-                    shard_manager_config.number_of_shards,
-                ))
-            }
-            PersistenceConfig::FileSystem(fs) => Arc::new(
+    // Leader election is only used by the SQL persistence backends, which can be shared by multiple replicas
+    let (persistence_service, leader_election): (
+        Arc<dyn RoutingTablePersistence + Send + Sync>,
+        Option<Arc<dyn LeaderElection + Send + Sync>>,
+    ) = match &shard_manager_config.persistence {
+        PersistenceConfig::Redis(redis) => {
+            let pool = golem_common::redis::RedisPool::configured(redis).await?;
+            let persistence = Arc::new(RoutingTableRedisPersistence::new(
+                &pool,
+                shard_manager_config.number_of_shards,
+            ));
+            (persistence, None)
+        }
+        PersistenceConfig::FileSystem(fs) => {
+            let persistence = Arc::new(
                 RoutingTableFileSystemPersistence::new(
                     &fs.path,
                     shard_manager_config.number_of_shards,
                 )
                 .await?,
-            ),
-        };
+            );
+            (persistence, None)
+        }
+        PersistenceConfig::Sqlite(sqlite) => {
+            persistence::migrate_sqlite(sqlite).await?;
+            let pool = golem_service_base::db::sqlite::SqlitePool::configured(sqlite).await?;
+            let persistence = Arc::new(RoutingTableDbPersistence::new(
+                pool.clone(),
+                shard_manager_config.number_of_shards,
+            ));
+            (persistence, Some(Arc::new(DbLeaderElection::new(pool))))
+        }
+        PersistenceConfig::Postgres(postgres) => {
+            persistence::migrate_postgres(postgres).await?;
+            let pool = golem_service_base::db::postgres::PostgresPool::configured(postgres).await?;
+            let persistence = Arc::new(RoutingTableDbPersistence::new(
+                pool.clone(),
+                shard_manager_config.number_of_shards,
+            ));
+            (persistence, Some(Arc::new(DbLeaderElection::new(pool))))
+        }
+    };
+
+    let leadership = match leader_election {
+        Some(leader_election) => {
+            let holder = uuid::Uuid::new_v4().to_string();
+            let leadership = await_leadership(
+                &leader_election,
+                &holder,
+                &shard_manager_config.leader_election,
+            )
+            .await;
+            join_set.spawn(
+                keep_leadership(
+                    leader_election,
+                    holder,
+                    leadership.clone(),
+                    shard_manager_config.leader_election.clone(),
+                )
+                .in_current_span(),
+            );
+            leadership
+        }
+        None => Leadership::default(),
+    };
+
     let worker_executors = Arc::new(WorkerExecutorServiceDefault::new(
         shard_manager_config.worker_executors.clone(),
     ));
@@ -286,6 +395,8 @@ pub async fn run(
         worker_executors,
         shard_manager_config.clone(),
         health_check,
+        leadership,
+        join_set,
     )
     .await?;
 
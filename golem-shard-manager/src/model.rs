@@ -361,6 +361,36 @@ impl Display for Unassignments {
     }
 }
 
+/// Resources and load reported by a worker executor
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PodCapacity {
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub cpu_count: u32,
+    pub active_agents: u64,
+    pub shard_loads: BTreeMap<ShardId, u64>,
+}
+
+impl From<golem::workerexecutor::v1::PodCapacity> for PodCapacity {
+    fn from(value: golem::workerexecutor::v1::PodCapacity) -> Self {
+        Self {
+            total_memory_bytes: value.total_memory_bytes,
+            available_memory_bytes: value.available_memory_bytes,
+            cpu_count: value.cpu_count,
+            active_agents: value.active_agents,
+            shard_loads: value
+                .shard_loads
+                .into_iter()
+                .filter_map(|shard_load| {
+                    shard_load
+                        .shard_id
+                        .map(|shard_id| (shard_id.into(), shard_load.active_agents))
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, BinaryCodec)]
 #[desert(evolution())]
 pub struct ShardManagerState {
@@ -14,11 +14,39 @@
 
 use crate::error::ShardManagerError;
 use crate::model::{RoutingTable, ShardManagerState};
+use anyhow::Context;
 use async_trait::async_trait;
 use bytes::Bytes;
+use conditional_trait_gen::trait_gen;
+use golem_common::config::{DbPostgresConfig, DbSqliteConfig};
 use golem_common::redis::RedisPool;
 use golem_common::serialization::{deserialize, serialize};
+use golem_service_base::db::postgres::PostgresPool;
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::db::{Pool, PoolApi};
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use include_dir::include_dir;
+use indoc::indoc;
 use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+static DB_MIGRATIONS: include_dir::Dir = include_dir!("$CARGO_MANIFEST_DIR/db/migration");
+
+/// Applies the schema migrations required by the SQLite routing table persistence and leader election
+pub async fn migrate_sqlite(config: &DbSqliteConfig) -> anyhow::Result<()> {
+    let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
+    golem_service_base::db::sqlite::migrate(config, migrations.sqlite_migrations())
+        .await
+        .context("SQLite DB migration")
+}
+
+/// Applies the schema migrations required by the Postgres routing table persistence and leader election
+pub async fn migrate_postgres(config: &DbPostgresConfig) -> anyhow::Result<()> {
+    let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
+    golem_service_base::db::postgres::migrate(config, migrations.postgres_migrations())
+        .await
+        .context("Postgres DB migration")
+}
 
 #[async_trait]
 pub trait RoutingTablePersistence {
@@ -116,3 +144,215 @@ impl RoutingTablePersistence for RoutingTableFileSystemPersistence {
         }
     }
 }
+
+#[derive(sqlx::FromRow)]
+struct RoutingTableRecord {
+    version: i64,
+    state: Vec<u8>,
+}
+
+/// Stores the routing table in a SQL database.
+///
+/// Every write increments the stored version, and only succeeds if the stored version is still
+/// the one this instance has last read or written. This way a shard manager replica which lost
+/// its leadership cannot overwrite the routing table written by the new leader.
+pub struct RoutingTableDbPersistence<DBP: Pool> {
+    pool: DBP,
+    number_of_shards: usize,
+    version: Mutex<Option<i64>>,
+}
+
+static ROUTING_TABLE_NAME: &str = "shard_manager_state";
+static METRICS_SVC_NAME: &str = "shard_manager_persistence";
+
+impl<DBP: Pool> RoutingTableDbPersistence<DBP> {
+    pub fn new(pool: DBP, number_of_shards: usize) -> Self {
+        Self {
+            pool,
+            number_of_shards,
+            version: Mutex::new(None),
+        }
+    }
+}
+
+#[trait_gen(PostgresPool -> PostgresPool, SqlitePool)]
+#[async_trait]
+impl RoutingTablePersistence for RoutingTableDbPersistence<PostgresPool> {
+    async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+        let shard_manager_state = ShardManagerState::new(routing_table);
+        let encoded =
+            serialize(&shard_manager_state).map_err(ShardManagerError::SerializationError)?;
+
+        let mut version = self.version.lock().await;
+        let result = match *version {
+            Some(current_version) => {
+                self.pool
+                    .with_rw(METRICS_SVC_NAME, "write")
+                    .execute(
+                        sqlx::query(indoc! { r#"
+                            UPDATE shard_manager_routing_table
+                            SET version = $1, state = $2
+                            WHERE name = $3 AND version = $4
+                        "#})
+                        .bind(current_version + 1)
+                        .bind(encoded)
+                        .bind(ROUTING_TABLE_NAME)
+                        .bind(current_version),
+                    )
+                    .await?
+            }
+            None => {
+                self.pool
+                    .with_rw(METRICS_SVC_NAME, "write")
+                    .execute(
+                        sqlx::query(indoc! { r#"
+                            INSERT INTO shard_manager_routing_table (name, version, state)
+                            VALUES ($1, $2, $3)
+                            ON CONFLICT (name) DO NOTHING
+                        "#})
+                        .bind(ROUTING_TABLE_NAME)
+                        .bind(1i64)
+                        .bind(encoded),
+                    )
+                    .await?
+            }
+        };
+
+        if result.rows_affected() == 0 {
+            Err(ShardManagerError::ConcurrentModification)
+        } else {
+            *version = Some(version.map_or(1, |v| v + 1));
+            Ok(())
+        }
+    }
+
+    async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+        let mut version = self.version.lock().await;
+        let record: Option<RoutingTableRecord> = self
+            .pool
+            .with_ro(METRICS_SVC_NAME, "read")
+            .fetch_optional_as(
+                sqlx::query_as(indoc! { r#"
+                    SELECT version, state
+                    FROM shard_manager_routing_table
+                    WHERE name = $1
+                "#})
+                .bind(ROUTING_TABLE_NAME),
+            )
+            .await?;
+
+        match record {
+            Some(record) => {
+                let shard_manager_state: ShardManagerState =
+                    deserialize(&record.state).map_err(ShardManagerError::SerializationError)?;
+                *version = Some(record.version);
+                Ok(shard_manager_state.get_routing_table())
+            }
+            None => {
+                *version = None;
+                Ok(RoutingTable::new(self.number_of_shards))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::error::ShardManagerError;
+    use crate::model::{Pod, RoutingTable};
+    use crate::persistence::{migrate_sqlite, RoutingTableDbPersistence, RoutingTablePersistence};
+    use golem_common::config::DbSqliteConfig;
+    use golem_service_base::db::sqlite::SqlitePool;
+    use tempfile::TempDir;
+
+    const NUMBER_OF_SHARDS: usize = 8;
+
+    async fn sqlite_pool(dir: &TempDir) -> SqlitePool {
+        let config = DbSqliteConfig {
+            database: dir
+                .path()
+                .join("shard-manager.db")
+                .to_string_lossy()
+                .to_string(),
+            max_connections: 4,
+            foreign_keys: false,
+        };
+        migrate_sqlite(&config).await.unwrap();
+        SqlitePool::configured(&config).await.unwrap()
+    }
+
+    fn routing_table_with_pod(port: u16) -> RoutingTable {
+        let mut routing_table = RoutingTable::new(NUMBER_OF_SHARDS);
+        routing_table.add_pod(&Pod::new("localhost".to_string(), port));
+        routing_table
+    }
+
+    #[test]
+    async fn write_and_read_back() {
+        let dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&dir).await;
+        let persistence = RoutingTableDbPersistence::new(pool.clone(), NUMBER_OF_SHARDS);
+
+        assert_eq!(
+            persistence.read().await.unwrap(),
+            RoutingTable::new(NUMBER_OF_SHARDS)
+        );
+
+        persistence
+            .write(&routing_table_with_pod(9000))
+            .await
+            .unwrap();
+        persistence
+            .write(&routing_table_with_pod(9001))
+            .await
+            .unwrap();
+
+        let other = RoutingTableDbPersistence::new(pool, NUMBER_OF_SHARDS);
+        assert_eq!(other.read().await.unwrap(), routing_table_with_pod(9001));
+    }
+
+    #[test]
+    async fn stale_writer_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&dir).await;
+        let stale = RoutingTableDbPersistence::new(pool.clone(), NUMBER_OF_SHARDS);
+        let current = RoutingTableDbPersistence::new(pool, NUMBER_OF_SHARDS);
+
+        stale.write(&routing_table_with_pod(9000)).await.unwrap();
+        current.read().await.unwrap();
+        current.write(&routing_table_with_pod(9001)).await.unwrap();
+
+        let result = stale.write(&routing_table_with_pod(9002)).await;
+        assert!(matches!(
+            result,
+            Err(ShardManagerError::ConcurrentModification)
+        ));
+        assert_eq!(current.read().await.unwrap(), routing_table_with_pod(9001));
+
+        // After reading the latest version the previously stale instance can write again
+        stale.read().await.unwrap();
+        stale.write(&routing_table_with_pod(9002)).await.unwrap();
+        assert_eq!(current.read().await.unwrap(), routing_table_with_pod(9002));
+    }
+
+    #[test]
+    async fn concurrent_first_write_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let pool = sqlite_pool(&dir).await;
+        let first = RoutingTableDbPersistence::new(pool.clone(), NUMBER_OF_SHARDS);
+        let second = RoutingTableDbPersistence::new(pool, NUMBER_OF_SHARDS);
+
+        first.read().await.unwrap();
+        second.read().await.unwrap();
+
+        first.write(&routing_table_with_pod(9000)).await.unwrap();
+        let result = second.write(&routing_table_with_pod(9001)).await;
+        assert!(matches!(
+            result,
+            Err(ShardManagerError::ConcurrentModification)
+        ));
+        assert_eq!(second.read().await.unwrap(), routing_table_with_pod(9000));
+    }
+}
@@ -12,18 +12,127 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::model::{Assignments, Pod, RoutingTable, Unassignments};
+use crate::model::{Assignments, Pod, PodCapacity, RoutingTable, Unassignments};
+use crate::shard_manager_config::RebalancingConfig;
+use golem_api_grpc::proto::golem;
 use golem_common::model::ShardId;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use tracing::trace;
 
+/// Lowest relative weight of a pod, so pods reporting very little capacity still get a share
+const MIN_POD_WEIGHT: f64 = 0.1;
+
+/// Tolerance for rounding errors when calculating the optimal loads
+const LOAD_EPSILON: f64 = 1e-9;
+
+/// Relative capacity of the pods and the load of the shards the rebalance plan is calculated with.
+///
+/// Pods and shards without a reported value have a weight and load of 1, so the default value
+/// results in balancing purely by shard count.
+#[derive(Clone, Debug, Default)]
+pub struct RebalanceWeights {
+    pod_weights: HashMap<Pod, f64>,
+    shard_loads: HashMap<ShardId, f64>,
+}
+
+impl RebalanceWeights {
+    /// Calculates the weights from the capacities reported by the pods.
+    ///
+    /// The weight of a pod is the average of its memory and CPU count relative to the mean of all
+    /// reporting pods. The load of a shard is 1 plus its number of active agents relative to the
+    /// mean number of active agents per shard.
+    pub fn new(
+        capacities: &BTreeMap<Pod, PodCapacity>,
+        number_of_shards: usize,
+        config: &RebalancingConfig,
+    ) -> Self {
+        let mut pod_weights = HashMap::new();
+        if config.capacity_weighting && !capacities.is_empty() {
+            let pod_count = capacities.len() as f64;
+            let mean_memory = capacities
+                .values()
+                .map(|capacity| capacity.total_memory_bytes as f64)
+                .sum::<f64>()
+                / pod_count;
+            let mean_cpu = capacities
+                .values()
+                .map(|capacity| capacity.cpu_count as f64)
+                .sum::<f64>()
+                / pod_count;
+
+            for (pod, capacity) in capacities {
+                let memory = relative(capacity.total_memory_bytes as f64, mean_memory);
+                let cpu = relative(capacity.cpu_count as f64, mean_cpu);
+                pod_weights.insert(pod.clone(), ((memory + cpu) / 2.0).max(MIN_POD_WEIGHT));
+            }
+        }
+
+        let mut shard_loads = HashMap::new();
+        if config.load_weighting && number_of_shards > 0 {
+            let total_agents: u64 = capacities
+                .values()
+                .map(|capacity| capacity.active_agents)
+                .sum();
+            if total_agents > 0 {
+                let mean_agents = total_agents as f64 / number_of_shards as f64;
+                for (shard_id, active_agents) in capacities
+                    .values()
+                    .flat_map(|capacity| capacity.shard_loads.iter())
+                {
+                    *shard_loads.entry(*shard_id).or_insert(1.0) +=
+                        *active_agents as f64 / mean_agents;
+                }
+            }
+        }
+
+        Self {
+            pod_weights,
+            shard_loads,
+        }
+    }
+
+    pub fn pod_weight(&self, pod: &Pod) -> f64 {
+        self.pod_weights.get(pod).copied().unwrap_or(1.0)
+    }
+
+    pub fn shard_load(&self, shard_id: &ShardId) -> f64 {
+        self.shard_loads.get(shard_id).copied().unwrap_or(1.0)
+    }
+
+    fn total_shard_load<'a>(&self, shard_ids: impl IntoIterator<Item = &'a ShardId>) -> f64 {
+        shard_ids
+            .into_iter()
+            .map(|shard_id| self.shard_load(shard_id))
+            .sum()
+    }
+}
+
+fn relative(value: f64, mean: f64) -> f64 {
+    if mean > 0.0 {
+        value / mean
+    } else {
+        1.0
+    }
+}
+
+/// Load of a pod before applying the rebalance plan, and the load it should have in the balanced state
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PodLoad {
+    pub weight: f64,
+    pub optimal_load: f64,
+    pub current_load: f64,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Rebalance {
     assignments: Assignments,
     unassignments: Unassignments,
+    pod_loads: BTreeMap<Pod, PodLoad>,
+    moves: usize,
+    truncated: bool,
 }
 
 impl Rebalance {
@@ -31,6 +140,9 @@ impl Rebalance {
         Rebalance {
             assignments,
             unassignments,
+            pod_loads: BTreeMap::new(),
+            moves: 0,
+            truncated: false,
         }
     }
 
@@ -43,15 +155,35 @@ impl Rebalance {
     /// Threshold is a percentage of the optimal count, so for 10 pods with 1000 shards,
     /// and a threshold of 10%, pods with shard count between 90 and 110 will be considered
     /// balanced.
+    #[cfg(test)]
     pub fn from_routing_table(routing_table: &RoutingTable, threshold: f64) -> Self {
-        let mut assignments = Assignments::new();
-        let mut unassignments = Unassignments::new();
+        Self::from_routing_table_weighted(
+            routing_table,
+            &RebalanceWeights::default(),
+            threshold,
+            None,
+        )
+    }
+
+    /// Constructs a rebalance plan from the current state of the routing table, taking the
+    /// capacity of the pods and the load of the shards into account.
+    ///
+    /// Works the same way as [`Rebalance::from_routing_table`], but the balanced state is
+    /// expressed in shard load instead of shard count: the optimal load of a pod is its weighted
+    /// share of the total load of all shards.
+    ///
+    /// With `max_moves`, at most that many shards are moved between pods. If more moves would be
+    /// needed to reach the balanced state, the plan is marked as truncated.
+    pub fn from_routing_table_weighted(
+        routing_table: &RoutingTable,
+        weights: &RebalanceWeights,
+        threshold: f64,
+        max_moves: Option<usize>,
+    ) -> Self {
+        let mut rebalance = Rebalance::empty();
         let pod_count = routing_table.get_pod_count();
         if pod_count == 0 {
-            return Rebalance {
-                assignments,
-                unassignments,
-            };
+            return rebalance;
         }
 
         let mut routing_table_entries = routing_table.get_entries_vec();
@@ -61,18 +193,57 @@ impl Rebalance {
             .filter(|&(_idx, entry)| entry.shard_ids.is_empty())
             .map(|(idx, _entry)| idx)
             .collect();
-        let optimal_count = routing_table.number_of_shards / pod_count;
-        let upper_threshold = (optimal_count as f64 * (1.0 + threshold)).ceil() as usize;
-        let lower_threshold = (optimal_count as f64 * (1.0 - threshold)).floor() as usize;
+
+        let total_load = weights.total_shard_load(
+            &(0..routing_table.number_of_shards)
+                .map(|shard_id| ShardId::new(shard_id as i64))
+                .collect::<Vec<_>>(),
+        );
+        let total_weight: f64 = routing_table_entries
+            .iter()
+            .map(|entry| weights.pod_weight(&entry.pod))
+            .sum();
+        let optimal_loads: Vec<f64> = routing_table_entries
+            .iter()
+            .map(|entry| {
+                (total_load * weights.pod_weight(&entry.pod) / total_weight + LOAD_EPSILON).floor()
+            })
+            .collect();
+        let upper_thresholds: Vec<f64> = optimal_loads
+            .iter()
+            .map(|optimal_load| (optimal_load * (1.0 + threshold)).ceil())
+            .collect();
+        let lower_thresholds: Vec<f64> = optimal_loads
+            .iter()
+            .map(|optimal_load| (optimal_load * (1.0 - threshold)).floor())
+            .collect();
+        let mut loads: Vec<f64> = routing_table_entries
+            .iter()
+            .map(|entry| weights.total_shard_load(&entry.shard_ids))
+            .collect();
+
+        rebalance.pod_loads = routing_table_entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                (
+                    entry.pod.clone(),
+                    PodLoad {
+                        weight: weights.pod_weight(&entry.pod),
+                        optimal_load: optimal_loads[idx],
+                        current_load: loads[idx],
+                    },
+                )
+            })
+            .collect();
 
         // Distributing unassigned shards evenly
         let unassigned_shards = routing_table.get_unassigned_shards();
         let mut unassigned_shards_iter = unassigned_shards.into_iter();
 
-        // First assign to and distribute among empty pods, until all of them reach the optimal count
+        // First assign to and distribute among empty pods, until all of them reach the optimal load
         if !initial_target_pods.is_empty() {
-            let pod_count = initial_target_pods.len();
-            let last_pod_idx = pod_count - 1;
+            let mut saturated = vec![false; initial_target_pods.len()];
 
             let mut idx = 0;
             for shard in unassigned_shards_iter.by_ref() {
@@ -84,107 +255,162 @@ impl Rebalance {
                     shard,
                     target_idx
                 );
-                assignments.assign(routing_table_entry.pod.clone(), shard);
+                rebalance
+                    .assignments
+                    .assign(routing_table_entry.pod.clone(), shard);
                 routing_table_entry.shard_ids.insert(shard);
+                loads[target_idx] += weights.shard_load(&shard);
 
-                // If the last pod is at optimal count, then all pods are at optimal count
-                if idx == last_pod_idx && routing_table_entry.shard_ids.len() == optimal_count {
-                    break;
+                if loads[target_idx] >= optimal_loads[target_idx] {
+                    saturated[idx] = true;
                 }
 
-                idx = (idx + 1) % pod_count;
+                // Continuing with the next pod still below its optimal load
+                match (1..=saturated.len())
+                    .map(|offset| (idx + offset) % saturated.len())
+                    .find(|idx| !saturated[*idx])
+                {
+                    Some(next_idx) => idx = next_idx,
+                    None => break,
+                }
             }
         }
 
-        // Now assign to and distribute among all pods
-        {
-            let mut idx = 0;
-            for shard in unassigned_shards_iter {
-                trace!("Assigning shard: {} to {}", shard, idx);
-                let routing_table_entry = &mut routing_table_entries[idx];
-                assignments.assign(routing_table_entry.pod.clone(), shard);
-                routing_table_entry.shard_ids.insert(shard);
-                idx = (idx + 1) % pod_count;
-            }
+        // Now assign to and distribute among all pods, always choosing the one furthest below its optimal load
+        for shard in unassigned_shards_iter {
+            let target_idx = (0..routing_table_entries.len())
+                .min_by(|a, b| {
+                    (optimal_loads[*b] - loads[*b]).total_cmp(&(optimal_loads[*a] - loads[*a]))
+                })
+                .unwrap();
+            trace!("Assigning shard: {} to {}", shard, target_idx);
+            let routing_table_entry = &mut routing_table_entries[target_idx];
+            rebalance
+                .assignments
+                .assign(routing_table_entry.pod.clone(), shard);
+            routing_table_entry.shard_ids.insert(shard);
+            loads[target_idx] += weights.shard_load(&shard);
         }
 
         if pod_count == 1 {
-            return Rebalance {
-                assignments,
-                unassignments,
-            };
+            return rebalance;
         };
 
-        // We redistribute shards from each entry having more than the optimal count
+        // We redistribute shards from each entry having more than its optimal load
         // to the last one until it becomes balanced, and repeat if we have more than one unbalanced entry.
-        // We also apply a threshold to the optimal count, to reduce the number of shard reassignments.
-        for target_idx in 0..routing_table_entries.len() {
+        // We also apply a threshold to the optimal load, to reduce the number of shard reassignments.
+        'targets: for target_idx in 0..routing_table_entries.len() {
             for (idx, entry) in routing_table_entries.iter().enumerate() {
                 trace!(
-                    "Pod {} has {} shards: {:?}",
+                    "Pod {} has {} shards with load {}: {:?}",
                     idx,
                     entry.shard_ids.len(),
+                    loads[idx],
                     entry.shard_ids
                 );
             }
 
-            if routing_table_entries[target_idx].shard_ids.len() < lower_threshold {
-                trace!("Found a pod with too few shards: {}", target_idx);
-
-                loop {
-                    trace!("Target count: {}..{}", lower_threshold, upper_threshold);
-                    let current_target_len = routing_table_entries[target_idx].shard_ids.len();
-                    if current_target_len < lower_threshold {
-                        // Finding a source pod which has more than enough shards
-                        if let Some((source_idx, _)) = routing_table_entries
-                            .iter()
-                            .enumerate()
-                            .filter(|(idx, entry)| {
-                                *idx != target_idx && // we need a different source
-                                    entry.shard_ids.len() > lower_threshold
-                            })
-                            .max_by(|(_, a), (_, b)| a.shard_ids.len().cmp(&b.shard_ids.len()))
-                        {
-                            let shard_id = *routing_table_entries[source_idx]
-                                .shard_ids
-                                .iter()
-                                .next()
-                                .unwrap();
-                            // this is guaranteed by check (**)
-                            trace!(
-                                "Moving first shard from {} to {}: {}",
-                                source_idx,
-                                target_idx,
-                                shard_id
-                            );
-                            routing_table_entries[source_idx]
-                                .shard_ids
-                                .remove(&shard_id);
-
-                            routing_table_entries[target_idx].shard_ids.insert(shard_id);
-                            assignments
-                                .assign(routing_table_entries[target_idx].pod.clone(), shard_id);
-                            unassignments
-                                .unassign(routing_table_entries[source_idx].pod.clone(), shard_id);
-                            assignments
-                                .unassign(routing_table_entries[source_idx].pod.clone(), shard_id);
-                        } else {
-                            trace!("Target reached a balanced state");
-                            // target reached a balanced state
-                            break;
-                        }
-                    } else {
-                        trace!("No more possible rebalance steps");
+            if loads[target_idx] < lower_thresholds[target_idx] {
+                trace!("Found a pod with too low load: {}", target_idx);
+
+                while loads[target_idx] < lower_thresholds[target_idx] {
+                    trace!(
+                        "Target load: {}..{}",
+                        lower_thresholds[target_idx],
+                        upper_thresholds[target_idx]
+                    );
+
+                    // Finding a source pod which has more than enough load, and a shard it can give away
+                    let room = upper_thresholds[target_idx] - loads[target_idx];
+                    let mut sources: Vec<usize> = (0..routing_table_entries.len())
+                        .rev()
+                        .filter(|idx| {
+                            *idx != target_idx && // we need a different source
+                                loads[*idx] > lower_thresholds[*idx]
+                        })
+                        .collect();
+                    sources.sort_by(|a, b| {
+                        (loads[*b] - optimal_loads[*b]).total_cmp(&(loads[*a] - optimal_loads[*a]))
+                    });
+                    let Some((source_idx, shard_id)) = sources.into_iter().find_map(|source_idx| {
+                        Self::select_shard_to_move(
+                            &routing_table_entries[source_idx].shard_ids,
+                            weights,
+                            loads[source_idx] - lower_thresholds[source_idx],
+                            room,
+                        )
+                        .map(|shard_id| (source_idx, shard_id))
+                    }) else {
+                        trace!("Target reached a balanced state");
+                        // target reached a balanced state
                         break;
+                    };
+
+                    if max_moves.is_some_and(|max_moves| rebalance.moves >= max_moves) {
+                        trace!("Reached the maximum number of shard moves");
+                        rebalance.truncated = true;
+                        break 'targets;
                     }
+
+                    trace!(
+                        "Moving shard from {} to {}: {}",
+                        source_idx,
+                        target_idx,
+                        shard_id
+                    );
+                    let shard_load = weights.shard_load(&shard_id);
+                    routing_table_entries[source_idx]
+                        .shard_ids
+                        .remove(&shard_id);
+                    loads[source_idx] -= shard_load;
+
+                    routing_table_entries[target_idx].shard_ids.insert(shard_id);
+                    loads[target_idx] += shard_load;
+
+                    rebalance
+                        .assignments
+                        .assign(routing_table_entries[target_idx].pod.clone(), shard_id);
+                    rebalance
+                        .unassignments
+                        .unassign(routing_table_entries[source_idx].pod.clone(), shard_id);
+                    rebalance
+                        .assignments
+                        .unassign(routing_table_entries[source_idx].pod.clone(), shard_id);
+                    rebalance.moves += 1;
                 }
             }
         }
 
-        Rebalance {
-            assignments,
-            unassignments,
+        rebalance
+    }
+
+    /// Selects the heaviest shard which fits in the target's room without making the source
+    /// go below its lower threshold. If none of them fits, the lightest one is selected.
+    fn select_shard_to_move(
+        shard_ids: &BTreeSet<ShardId>,
+        weights: &RebalanceWeights,
+        source_excess: f64,
+        target_room: f64,
+    ) -> Option<ShardId> {
+        let candidates = shard_ids
+            .iter()
+            .map(|shard_id| (*shard_id, weights.shard_load(shard_id)))
+            .filter(|(_, load)| *load <= source_excess + LOAD_EPSILON);
+
+        let mut heaviest_fitting: Option<(ShardId, f64)> = None;
+        let mut lightest: Option<(ShardId, f64)> = None;
+        for (shard_id, load) in candidates {
+            if load <= target_room + LOAD_EPSILON
+                && heaviest_fitting.is_none_or(|(_, best)| load > best)
+            {
+                heaviest_fitting = Some((shard_id, load));
+            }
+            if lightest.is_none_or(|(_, best)| load < best) {
+                lightest = Some((shard_id, load));
+            }
         }
+
+        heaviest_fitting.or(lightest).map(|(shard_id, _)| shard_id)
     }
 
     pub fn get_assignments(&self) -> &Assignments {
@@ -211,10 +437,17 @@ impl Rebalance {
     }
 
     pub fn empty() -> Self {
-        Rebalance {
-            assignments: Assignments::new(),
-            unassignments: Unassignments::new(),
-        }
+        Rebalance::new(Assignments::new(), Unassignments::new())
+    }
+
+    /// Number of shards the plan moves from one pod to another
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Whether the plan stopped at the maximum number of moves before reaching the balanced state
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
     }
 
     pub fn remove_pods(&mut self, pods: &HashSet<Pod>) {
@@ -257,6 +490,43 @@ impl Display for Rebalance {
     }
 }
 
+impl From<Rebalance> for golem::shardmanager::v1::RebalancePlan {
+    fn from(value: Rebalance) -> Self {
+        fn pod_shards(
+            shard_assignments: BTreeMap<Pod, BTreeSet<ShardId>>,
+        ) -> Vec<golem::shardmanager::v1::PodShards> {
+            shard_assignments
+                .into_iter()
+                .filter(|(_, shard_ids)| !shard_ids.is_empty())
+                .map(|(pod, shard_ids)| golem::shardmanager::v1::PodShards {
+                    pod: Some(pod.into()),
+                    shard_ids: shard_ids
+                        .into_iter()
+                        .map(|shard_id| shard_id.into())
+                        .collect(),
+                })
+                .collect()
+        }
+
+        Self {
+            assignments: pod_shards(value.assignments.assignments),
+            unassignments: pod_shards(value.unassignments.unassignments),
+            weights: value
+                .pod_loads
+                .into_iter()
+                .map(|(pod, load)| golem::shardmanager::v1::PodWeight {
+                    pod: Some(pod.into()),
+                    weight: load.weight,
+                    target_load: load.optimal_load,
+                    current_load: load.current_load,
+                })
+                .collect(),
+            moves: value.moves as u32,
+            truncated: value.truncated,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_r::test;
@@ -265,8 +535,11 @@ mod tests {
 
     use golem_common::model::ShardId;
 
-    use crate::model::{Pod, RoutingTable};
-    use crate::rebalancing::Rebalance;
+    use std::collections::BTreeMap;
+
+    use crate::model::{Pod, PodCapacity, RoutingTable};
+    use crate::rebalancing::{Rebalance, RebalanceWeights};
+    use crate::shard_manager_config::RebalancingConfig;
 
     struct TestConfig {
         number_of_shards: usize,
@@ -730,4 +1003,132 @@ mod tests {
 
         assert_eq!(rebalance.unassignments.unassignments.len(), 0);
     }
+
+    #[test]
+    #[traced_test]
+    fn initial_assign_is_proportional_to_capacity() {
+        let routing_table = new_routing_table(TestConfig {
+            number_of_shards: 9,
+            number_of_pods: 2,
+            initial_assignments: vec![],
+        });
+        let capacities = BTreeMap::from([
+            (
+                pod(0),
+                PodCapacity {
+                    total_memory_bytes: 2048,
+                    cpu_count: 4,
+                    ..Default::default()
+                },
+            ),
+            (
+                pod(1),
+                PodCapacity {
+                    total_memory_bytes: 1024,
+                    cpu_count: 2,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let weights = RebalanceWeights::new(
+            &capacities,
+            9,
+            &RebalancingConfig {
+                capacity_weighting: true,
+                load_weighting: false,
+                max_moves_per_round: 0,
+            },
+        );
+
+        let rebalance = Rebalance::from_routing_table_weighted(&routing_table, &weights, 0.0, None);
+
+        assert_assignments(
+            &rebalance,
+            vec![
+                //
+                (0, vec![0, 2, 4, 6, 7, 8]),
+                (1, vec![1, 3, 5]),
+            ],
+        );
+        assert_eq!(rebalance.unassignments.unassignments.len(), 0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn rebalance_keeps_heavy_shard_in_place() {
+        let routing_table = new_routing_table(TestConfig {
+            number_of_shards: 4,
+            number_of_pods: 2,
+            initial_assignments: vec![
+                //
+                (0, vec![0, 1]),
+                (1, vec![2, 3]),
+            ],
+        });
+        let capacities = BTreeMap::from([(
+            pod(0),
+            PodCapacity {
+                active_agents: 10,
+                shard_loads: BTreeMap::from([(ShardId::new(0), 10)]),
+                ..Default::default()
+            },
+        )]);
+        let weights = RebalanceWeights::new(
+            &capacities,
+            4,
+            &RebalancingConfig {
+                capacity_weighting: false,
+                load_weighting: true,
+                max_moves_per_round: 0,
+            },
+        );
+
+        let rebalance = Rebalance::from_routing_table_weighted(&routing_table, &weights, 0.0, None);
+
+        assert_assignments(
+            &rebalance,
+            vec![
+                //
+                (0, vec![]),
+                (1, vec![1]),
+            ],
+        );
+        assert_unassignments(
+            &rebalance,
+            vec![
+                //
+                (0, vec![1]),
+                (1, vec![]),
+            ],
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn rebalance_stops_at_max_moves() {
+        let routing_table = new_routing_table(TestConfig {
+            number_of_shards: 9,
+            number_of_pods: 3,
+            initial_assignments: vec![(0, vec![0, 1, 2, 3, 4, 5, 6, 7, 8])],
+        });
+
+        let rebalance = Rebalance::from_routing_table_weighted(
+            &routing_table,
+            &RebalanceWeights::default(),
+            0.0,
+            Some(2),
+        );
+
+        assert!(rebalance.is_truncated());
+        assert_eq!(rebalance.moves(), 2);
+        assert_assignments(
+            &rebalance,
+            vec![
+                //
+                (1, vec![0, 1]),
+                (2, vec![]),
+            ],
+        );
+        assert_unassignments(&rebalance, vec![(0, vec![0, 1])]);
+    }
 }
@@ -14,35 +14,45 @@
 
 use crate::error::ShardManagerError;
 use crate::healthcheck::{get_unhealthy_pods, HealthCheck};
-use crate::model::{Pod, RoutingTable};
+use crate::leader_election::Leadership;
+use crate::model::{Pod, PodCapacity, RoutingTable};
 use crate::persistence::RoutingTablePersistence;
-use crate::rebalancing::Rebalance;
+use crate::rebalancing::{Rebalance, RebalanceWeights};
+use crate::shard_manager_config::RebalancingConfig;
 use crate::worker_executor::{assign_shards, revoke_shards, WorkerExecutorService};
 use async_rwlock::RwLock;
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{Mutex, Notify};
-use tokio::task::JoinHandle;
-use tracing::{debug, info, warn, Instrument};
+use tokio::task::JoinSet;
+use tracing::{debug, error, info, warn, Instrument};
 
 #[derive(Clone)]
 pub struct ShardManagement {
     routing_table: Arc<RwLock<RoutingTable>>,
+    capacities: Arc<RwLock<BTreeMap<Pod, PodCapacity>>>,
     change: Arc<Notify>,
-    #[allow(dead_code)]
-    worker_handle: Arc<WorkerHandle>, // Just kept here for abort on dropping
+    threshold: f64,
+    rebalancing: RebalancingConfig,
     updates: Arc<Mutex<ShardManagementChanges>>,
 }
 
 impl ShardManagement {
     /// Initializes the shard management with an initial routing table and optionally
     /// a pending rebalance, both read from the persistence service.
+    ///
+    /// The management loop is spawned into `join_set`, and it fails if the leadership is lost or
+    /// the routing table was modified by another shard manager, stopping the whole service.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
         worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
         health_check: Arc<dyn HealthCheck + Send + Sync>,
         threshold: f64,
+        rebalancing: RebalancingConfig,
+        leadership: Leadership,
+        join_set: &mut JoinSet<anyhow::Result<()>>,
     ) -> Result<Self, ShardManagerError> {
         let routing_table = persistence_service.read().await?;
 
@@ -62,32 +72,46 @@ impl ShardManagement {
             unhealthy_pods,
         )));
         let routing_table = Arc::new(RwLock::new(routing_table));
+        let capacities = Arc::new(RwLock::new(BTreeMap::new()));
 
-        let worker_handle = {
+        {
             let change = change.clone();
             let updates = updates.clone();
             let routing_table = routing_table.clone();
-
-            Arc::new(WorkerHandle::new(tokio::spawn(async move {
-                Self::worker(
-                    routing_table,
-                    change,
-                    updates,
-                    persistence_service,
-                    worker_executors,
-                    threshold,
-                )
-                .in_current_span()
-                .await
-            })))
-        };
+            let capacities = capacities.clone();
+            let rebalancing = rebalancing.clone();
+
+            join_set.spawn(
+                async move {
+                    Self::worker(
+                        routing_table,
+                        capacities,
+                        change,
+                        updates,
+                        persistence_service,
+                        worker_executors,
+                        threshold,
+                        rebalancing,
+                        leadership,
+                    )
+                    .await
+                    .map_err(|err| {
+                        error!("Shard management loop stopped: {err}");
+                        anyhow::Error::from(err)
+                    })
+                }
+                .in_current_span(),
+            );
+        }
 
         change.notify_one();
 
         Ok(ShardManagement {
             routing_table,
+            capacities,
             change,
-            worker_handle,
+            threshold,
+            rebalancing,
             updates,
         })
     }
@@ -111,14 +135,51 @@ impl ShardManagement {
         self.routing_table.read().await.clone()
     }
 
+    /// Replaces the capacities reported by the pods, used by the following rebalance rounds
+    pub async fn update_capacities(&self, capacities: BTreeMap<Pod, PodCapacity>) {
+        *self.capacities.write().await = capacities;
+    }
+
+    /// Calculates the rebalance plan for the current routing table without applying it
+    pub async fn preview_rebalance(&self) -> Rebalance {
+        let routing_table = self.current_snapshot().await;
+        let capacities = self.capacities.read().await.clone();
+        Self::calculate_rebalance(
+            &routing_table,
+            &capacities,
+            self.threshold,
+            &self.rebalancing,
+        )
+    }
+
+    fn calculate_rebalance(
+        routing_table: &RoutingTable,
+        capacities: &BTreeMap<Pod, PodCapacity>,
+        threshold: f64,
+        rebalancing: &RebalancingConfig,
+    ) -> Rebalance {
+        let weights =
+            RebalanceWeights::new(capacities, routing_table.number_of_shards, rebalancing);
+        Rebalance::from_routing_table_weighted(
+            routing_table,
+            &weights,
+            threshold,
+            rebalancing.max_moves(),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn worker(
         routing_table: Arc<RwLock<RoutingTable>>,
+        capacities: Arc<RwLock<BTreeMap<Pod, PodCapacity>>>,
         change: Arc<Notify>,
         updates: Arc<Mutex<ShardManagementChanges>>,
         persistence_service: Arc<dyn RoutingTablePersistence + Send + Sync>,
         worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
         threshold: f64,
-    ) {
+        rebalancing: RebalancingConfig,
+        leadership: Leadership,
+    ) -> Result<(), ShardManagerError> {
         loop {
             debug!("Shard management loop awaiting changes");
             change.notified().await;
@@ -154,30 +215,43 @@ impl ShardManagement {
                         info!(pod= %pod, "Pod added");
                     }
                 }
-                let mut rebalance =
-                    Rebalance::from_routing_table(&current_routing_table, threshold);
+                let mut rebalance = Self::calculate_rebalance(
+                    &current_routing_table,
+                    &capacities.read().await.clone(),
+                    threshold,
+                    &rebalancing,
+                );
 
                 for pod in send_full_assignment {
                     let assignments = current_routing_table.get_shards(&pod).unwrap_or_default();
                     rebalance.add_assignments(&pod, assignments);
                 }
 
-                persistence_service
-                    .write(&current_routing_table)
-                    .await
-                    .expect("Failed to persist routing table after pod changes");
+                // The versioned write fails if another shard manager changed the routing table in
+                // the meantime, so a stale leader stops here before sending any assignments
+                persistence_service.write(&current_routing_table).await?;
 
                 rebalance
             };
 
-            debug!(rebalance=%rebalance, "Applying rebalance plan");
+            if !leadership.is_held() {
+                return Err(ShardManagerError::LostLeadership);
+            }
+
+            debug!(rebalance=%rebalance, moves=rebalance.moves(), "Applying rebalance plan");
             Self::execute_rebalance(worker_executors.clone(), &mut rebalance).await;
 
+            let truncated = rebalance.is_truncated();
             routing_table.write().await.rebalance(rebalance);
             persistence_service
                 .write(&routing_table.read().await.clone())
-                .await
-                .expect("Failed to persist routing table after rebalance");
+                .await?;
+
+            if truncated {
+                // The move limit was reached, continuing with the next round
+                info!("Rebalance reached the maximum number of shard moves, scheduling next round");
+                change.notify_one();
+            }
         }
     }
 
@@ -249,17 +323,3 @@ impl ShardManagementChanges {
         (new, removed)
     }
 }
-
-struct WorkerHandle(JoinHandle<()>);
-
-impl WorkerHandle {
-    pub fn new(handle: JoinHandle<()>) -> Self {
-        WorkerHandle(handle)
-    }
-}
-
-impl Drop for WorkerHandle {
-    fn drop(&mut self) {
-        self.0.abort();
-    }
-}
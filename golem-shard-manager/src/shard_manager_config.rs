@@ -14,7 +14,9 @@
 
 use crate::model::Empty;
 use crate::shard_manager_config::HealthCheckMode::K8s;
-use golem_common::config::{ConfigExample, ConfigLoader, HasConfigExamples, RedisConfig};
+use golem_common::config::{
+    ConfigExample, ConfigLoader, DbPostgresConfig, DbSqliteConfig, HasConfigExamples, RedisConfig,
+};
 use golem_common::model::RetryConfig;
 use golem_common::tracing::TracingConfig;
 use golem_common::SafeDisplay;
@@ -31,10 +33,12 @@ pub struct ShardManagerConfig {
     pub persistence: PersistenceConfig,
     pub worker_executors: WorkerExecutorServiceConfig,
     pub health_check: HealthCheckConfig,
+    pub leader_election: LeaderElectionConfig,
     pub http_port: u16,
     pub grpc: GrpcApiConfig,
     pub number_of_shards: usize,
     pub rebalance_threshold: f64,
+    pub rebalancing: RebalancingConfig,
 }
 
 impl SafeDisplay for ShardManagerConfig {
@@ -60,6 +64,12 @@ impl SafeDisplay for ShardManagerConfig {
             "{}",
             self.health_check.to_safe_string_indented()
         );
+        let _ = writeln!(&mut result, "leader election:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.leader_election.to_safe_string_indented()
+        );
         let _ = writeln!(&mut result, "HTTP port: {}", self.http_port);
 
         let _ = writeln!(&mut result, "grpc:");
@@ -71,6 +81,12 @@ impl SafeDisplay for ShardManagerConfig {
             "rebalance threshold: {}",
             self.rebalance_threshold
         );
+        let _ = writeln!(&mut result, "rebalancing:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.rebalancing.to_safe_string_indented()
+        );
         result
     }
 }
@@ -82,10 +98,12 @@ impl Default for ShardManagerConfig {
             persistence: PersistenceConfig::default(),
             worker_executors: WorkerExecutorServiceConfig::default(),
             health_check: HealthCheckConfig::default(),
+            leader_election: LeaderElectionConfig::default(),
             http_port: 8081,
             grpc: GrpcApiConfig::default(),
             number_of_shards: 1024,
             rebalance_threshold: 0.1,
+            rebalancing: RebalancingConfig::default(),
         }
     }
 }
@@ -144,6 +162,12 @@ pub struct WorkerExecutorServiceConfig {
     pub health_check_timeout: Duration,
     #[serde(with = "humantime_serde")]
     pub revoke_shards_timeout: Duration,
+    /// Time the executors are given to finish the in-flight invocations of revoked agents before
+    /// interrupting them. Zero disables draining.
+    #[serde(with = "humantime_serde")]
+    pub drain_timeout: Duration,
+    /// Whether the executors should save a snapshot of the drained agents, to speed up their recovery
+    pub snapshot_on_drain: bool,
     pub retries: RetryConfig,
     #[serde(flatten)]
     pub client_config: GrpcClientConfig,
@@ -167,6 +191,8 @@ impl SafeDisplay for WorkerExecutorServiceConfig {
             "revoke shards timeout: {:?}",
             self.revoke_shards_timeout
         );
+        let _ = writeln!(&mut result, "drain timeout: {:?}", self.drain_timeout);
+        let _ = writeln!(&mut result, "snapshot on drain: {}", self.snapshot_on_drain);
         let _ = writeln!(&mut result, "retries:");
         let _ = writeln!(&mut result, "{}", self.retries.to_safe_string_indented());
         let _ = writeln!(&mut result, "{}", self.client_config.to_safe_string());
@@ -180,6 +206,8 @@ impl Default for WorkerExecutorServiceConfig {
             assign_shards_timeout: Duration::from_secs(5),
             health_check_timeout: Duration::from_secs(2),
             revoke_shards_timeout: Duration::from_secs(5),
+            drain_timeout: Duration::ZERO,
+            snapshot_on_drain: false,
             retries: RetryConfig::max_attempts_5(),
             client_config: GrpcClientConfig {
                 connect_timeout: Duration::from_secs(10),
@@ -189,6 +217,80 @@ impl Default for WorkerExecutorServiceConfig {
     }
 }
 
+/// Leader election among shard manager replicas. Only the SQL persistence backends support running
+/// multiple replicas, with the other backends the shard manager always considers itself the leader.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderElectionConfig {
+    /// Time after which another replica can take over the leadership if it was not renewed
+    #[serde(with = "humantime_serde")]
+    pub lease_duration: Duration,
+    #[serde(with = "humantime_serde")]
+    pub renew_interval: Duration,
+}
+
+impl SafeDisplay for LeaderElectionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "lease duration: {:?}", self.lease_duration);
+        let _ = writeln!(&mut result, "renew interval: {:?}", self.renew_interval);
+        result
+    }
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            lease_duration: Duration::from_secs(15),
+            renew_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RebalancingConfig {
+    /// Distribute shards proportionally to the memory and CPU reported by the executors
+    pub capacity_weighting: bool,
+    /// Account for the number of active agents per shard instead of treating all shards equal
+    pub load_weighting: bool,
+    /// Maximum number of already assigned shards moved in one rebalance round, zero means unlimited.
+    /// The remaining moves are performed in the following rounds.
+    pub max_moves_per_round: usize,
+}
+
+impl SafeDisplay for RebalancingConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "capacity weighting: {}",
+            self.capacity_weighting
+        );
+        let _ = writeln!(&mut result, "load weighting: {}", self.load_weighting);
+        let _ = writeln!(
+            &mut result,
+            "max moves per round: {}",
+            self.max_moves_per_round
+        );
+        result
+    }
+}
+
+impl Default for RebalancingConfig {
+    fn default() -> Self {
+        Self {
+            capacity_weighting: false,
+            load_weighting: false,
+            max_moves_per_round: 0,
+        }
+    }
+}
+
+impl RebalancingConfig {
+    pub fn max_moves(&self) -> Option<usize> {
+        (self.max_moves_per_round > 0).then_some(self.max_moves_per_round)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HealthCheckConfig {
     #[serde(with = "humantime_serde")]
@@ -267,6 +369,8 @@ impl SafeDisplay for HealthCheckK8sConfig {
 pub enum PersistenceConfig {
     Redis(RedisConfig),
     FileSystem(FileSystemPersistenceConfig),
+    Sqlite(DbSqliteConfig),
+    Postgres(DbPostgresConfig),
 }
 
 impl SafeDisplay for PersistenceConfig {
@@ -281,6 +385,14 @@ impl SafeDisplay for PersistenceConfig {
                 let _ = writeln!(&mut result, "filesystem:");
                 let _ = writeln!(&mut result, "path: {:?}", inner.path);
             }
+            PersistenceConfig::Sqlite(inner) => {
+                let _ = writeln!(&mut result, "sqlite:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+            PersistenceConfig::Postgres(inner) => {
+                let _ = writeln!(&mut result, "postgres:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
         }
         result
     }
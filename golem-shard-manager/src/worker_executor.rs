@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::error::{HealthCheckError, ShardManagerError};
-use crate::model::{pod_shard_assignments_to_string, Assignments, Pod, Unassignments};
+use crate::model::{pod_shard_assignments_to_string, Assignments, Pod, PodCapacity, Unassignments};
 use crate::shard_manager_config::WorkerExecutorServiceConfig;
 use async_trait::async_trait;
 use golem_api_grpc::proto::golem;
@@ -22,7 +22,7 @@ use golem_common::model::ShardId;
 use golem_common::retries::with_retriable_errors;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::grpc::client::MultiTargetGrpcClient;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
 use tokio::time::error::Elapsed;
 use tokio::time::timeout;
@@ -33,7 +33,7 @@ use tonic_health::pb::health_check_response::ServingStatus;
 use tonic_health::pb::health_client::HealthClient;
 use tonic_health::pb::{HealthCheckRequest, HealthCheckResponse};
 use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
-use tracing::info;
+use tracing::{debug, info};
 
 #[async_trait]
 pub trait WorkerExecutorService {
@@ -50,6 +50,8 @@ pub trait WorkerExecutorService {
         pod: &Pod,
         shard_ids: &BTreeSet<ShardId>,
     ) -> Result<(), ShardManagerError>;
+
+    async fn get_pod_capacity(&self, pod: &Pod) -> Result<PodCapacity, ShardManagerError>;
 }
 
 /// Sends revoke requests to all worker executors based on an `Unassignments` plan
@@ -102,6 +104,43 @@ pub async fn assign_shards(
         .collect()
 }
 
+/// Collects the capacity reports of the given worker executors, skipping the ones failing to report
+pub async fn get_pod_capacities(
+    worker_executors: Arc<dyn WorkerExecutorService + Send + Sync>,
+    pods: &HashSet<Pod>,
+) -> BTreeMap<Pod, PodCapacity> {
+    let futures: Vec<_> = pods
+        .iter()
+        .map(|pod| {
+            let worker_executors = worker_executors.clone();
+            Box::pin(async move {
+                match worker_executors.get_pod_capacity(pod).await {
+                    Ok(capacity) => {
+                        debug!(
+                            pod = %pod,
+                            total_memory_bytes = capacity.total_memory_bytes,
+                            available_memory_bytes = capacity.available_memory_bytes,
+                            cpu_count = capacity.cpu_count,
+                            active_agents = capacity.active_agents,
+                            "Pod capacity reported"
+                        );
+                        Some((pod.clone(), capacity))
+                    }
+                    Err(err) => {
+                        debug!(pod = %pod, "Failed to get pod capacity: {err}");
+                        None
+                    }
+                }
+            })
+        })
+        .collect();
+    futures::future::join_all(futures)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
 pub struct WorkerExecutorServiceDefault {
     config: WorkerExecutorServiceConfig,
     client: MultiTargetGrpcClient<WorkerExecutorClient<OtelGrpcService<Channel>>>,
@@ -176,6 +215,46 @@ impl WorkerExecutorService for WorkerExecutorServiceDefault {
         )
         .await
     }
+
+    async fn get_pod_capacity(&self, pod: &Pod) -> Result<PodCapacity, ShardManagerError> {
+        // NOTE: not retried, as capacities are periodically refreshed by the health check
+        let response = timeout(
+            self.config.health_check_timeout,
+            self.client.call(
+                "get_pod_capacity",
+                pod.uri(self.config.client_config.tls_enabled()),
+                |client| {
+                    Box::pin(
+                        client
+                            .get_pod_capacity(golem::workerexecutor::v1::GetPodCapacityRequest {}),
+                    )
+                },
+            ),
+        )
+        .await
+        .map_err(|_: Elapsed| ShardManagerError::Timeout)?
+        .map_err(ShardManagerError::GrpcError)?;
+
+        match response.into_inner() {
+            golem::workerexecutor::v1::GetPodCapacityResponse {
+                result:
+                    Some(golem::workerexecutor::v1::get_pod_capacity_response::Result::Success(
+                        capacity,
+                    )),
+            } => Ok(capacity.into()),
+            golem::workerexecutor::v1::GetPodCapacityResponse {
+                result:
+                    Some(golem::workerexecutor::v1::get_pod_capacity_response::Result::Failure(failure)),
+            } => Err(ShardManagerError::WorkerExecutionError(
+                failure
+                    .try_into()
+                    .unwrap_or_else(WorkerExecutorError::unknown),
+            )),
+            golem::workerexecutor::v1::GetPodCapacityResponse { result: None } => {
+                Err(ShardManagerError::NoResult)
+            }
+        }
+    }
 }
 
 impl WorkerExecutorServiceDefault {
@@ -249,10 +328,14 @@ impl WorkerExecutorServiceDefault {
                 .into_iter()
                 .map(|shard_id| shard_id.into())
                 .collect(),
+            drain_timeout_millis: (!self.config.drain_timeout.is_zero())
+                .then(|| self.config.drain_timeout.as_millis() as u64),
+            snapshot_on_drain: self.config.snapshot_on_drain,
         };
 
         let revoke_shards_response = timeout(
-            self.config.revoke_shards_timeout,
+            // The executor only responds once the agents of the revoked shards are handed over
+            self.config.revoke_shards_timeout + self.config.drain_timeout,
             self.client.call(
                 "revoke_shards",
                 pod.uri(self.config.client_config.tls_enabled()),
@@ -268,8 +351,17 @@ impl WorkerExecutorServiceDefault {
 
         match revoke_shards_response.into_inner() {
             golem::workerexecutor::v1::RevokeShardsResponse {
-                result: Some(golem::workerexecutor::v1::revoke_shards_response::Result::Success(_)),
-            } => Ok(()),
+                result:
+                    Some(golem::workerexecutor::v1::revoke_shards_response::Result::Success(success)),
+            } => {
+                info!(
+                    pod = pod.to_string(),
+                    drained_agents = success.drained_agents,
+                    interrupted_agents = success.interrupted_agents,
+                    "Agents of revoked shards handed over"
+                );
+                Ok(())
+            }
             golem::workerexecutor::v1::RevokeShardsResponse {
                 result:
                     Some(golem::workerexecutor::v1::revoke_shards_response::Result::Failure(failure)),
@@ -532,6 +532,8 @@ impl<Deps: TestDependencies> TestDsl for TestUserContext<Deps> {
                 &CompleteParameters {
                     oplog_idx: promise_id.oplog_idx.as_u64(),
                     data,
+                    json: None,
+                    rejection: None,
                 },
             )
             .await?;
@@ -387,6 +387,23 @@ pub fn debug_render_oplog_entry(entry: &PublicOplogEntry) -> String {
             let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
             let _ = writeln!(result, "{pad}begin index:       {}", &params.begin_index);
         }
+        PublicOplogEntry::Snapshot(params) => {
+            let _ = writeln!(result, "SNAPSHOT");
+            let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
+            let _ = writeln!(result, "{pad}size:              {}", params.data.len());
+        }
+        PublicOplogEntry::ChangeLabels(params) => {
+            let _ = writeln!(result, "CHANGE LABELS");
+            let _ = writeln!(result, "{pad}at:                {}", &params.timestamp);
+            let _ = writeln!(result, "{pad}set:");
+            for (k, v) in &params.set {
+                let _ = writeln!(result, "{pad}  - {}: {}", k, &v);
+            }
+            let _ = writeln!(result, "{pad}removed:");
+            for k in &params.removed {
+                let _ = writeln!(result, "{pad}  - {k}");
+            }
+        }
     }
 
     result
@@ -1006,6 +1006,9 @@ pub fn worker_error_message(error: &WorkerExecutorError) -> String {
             format!("File system error: {}", reason)
         }
         WorkerExecutorError::InvocationFailed { .. } => "Invocation failed".to_string(),
+        WorkerExecutorError::AgentQuotaExceeded { details } => {
+            format!("Agent quota exceeded: {}", details)
+        }
     }
 }
 
@@ -3289,6 +3289,4374 @@ pub mod golem {
             }
         }
     }
+    pub mod rpc0_2_3 {
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod types {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            pub type Datetime = super::super::super::__with_name0::Datetime;
+            pub type Pollable = super::super::super::__with_name1::Pollable;
+            pub type AgentId = super::super::super::golem::rpc0_2_2::types::AgentId;
+            pub type ComponentId = super::super::super::golem::rpc0_2_2::types::ComponentId;
+            pub type AccountId = super::super::super::golem::rpc0_2_2::types::AccountId;
+            pub type Uuid = super::super::super::golem::rpc0_2_2::types::Uuid;
+            pub type NodeIndex = super::super::super::golem::rpc0_2_2::types::NodeIndex;
+            pub type WitValue = super::super::super::golem::rpc0_2_2::types::WitValue;
+            pub type WitNode = super::super::super::golem::rpc0_2_2::types::WitNode;
+            pub type WitType = super::super::super::golem::rpc0_2_2::types::WitType;
+            pub type ResourceId = super::super::super::golem::rpc0_2_2::types::ResourceId;
+            pub type ResourceMode = super::super::super::golem::rpc0_2_2::types::ResourceMode;
+            pub type NamedWitTypeNode = super::super::super::golem::rpc0_2_2::types::NamedWitTypeNode;
+            pub type WitTypeNode = super::super::super::golem::rpc0_2_2::types::WitTypeNode;
+            pub type ValueAndType = super::super::super::golem::rpc0_2_2::types::ValueAndType;
+            pub type Uri = super::super::super::golem::rpc0_2_2::types::Uri;
+            pub type CancellationToken = super::super::super::golem::rpc0_2_2::types::CancellationToken;
+            /// Possible failures of an RPC call
+            #[derive(Clone)]
+            pub enum RpcError {
+                /// Protocol level error
+                ProtocolError(_rt::String),
+                /// Access denied
+                Denied(_rt::String),
+                /// Target agent or function not found
+                NotFound(_rt::String),
+                /// Internal error on the remote side
+                RemoteInternalError(_rt::String),
+                /// The invocation did not complete before its deadline. The remote invocation is not cancelled.
+                Timeout(_rt::String),
+            }
+            impl ::core::fmt::Debug for RpcError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        RpcError::ProtocolError(e) => {
+                            f.debug_tuple("RpcError::ProtocolError").field(e).finish()
+                        }
+                        RpcError::Denied(e) => {
+                            f.debug_tuple("RpcError::Denied").field(e).finish()
+                        }
+                        RpcError::NotFound(e) => {
+                            f.debug_tuple("RpcError::NotFound").field(e).finish()
+                        }
+                        RpcError::RemoteInternalError(e) => {
+                            f.debug_tuple("RpcError::RemoteInternalError")
+                                .field(e)
+                                .finish()
+                        }
+                        RpcError::Timeout(e) => {
+                            f.debug_tuple("RpcError::Timeout").field(e).finish()
+                        }
+                    }
+                }
+            }
+            impl ::core::fmt::Display for RpcError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for RpcError {}
+            /// Repeats a scheduled invocation with a fixed interval
+            #[repr(C)]
+            #[derive(Clone, Copy)]
+            pub struct IntervalRecurrence {
+                /// Interval between two occurrences in milliseconds
+                pub interval_millis: u64,
+                /// No occurrences are scheduled after this point in time
+                pub until: Option<Datetime>,
+            }
+            impl ::core::fmt::Debug for IntervalRecurrence {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("IntervalRecurrence")
+                        .field("interval-millis", &self.interval_millis)
+                        .field("until", &self.until)
+                        .finish()
+                }
+            }
+            /// Repeats a scheduled invocation according to a cron expression, evaluated in UTC
+            #[derive(Clone)]
+            pub struct CronRecurrence {
+                /// Cron expression with a leading seconds field, for example `0 */5 * * * *`
+                pub expression: _rt::String,
+                /// No occurrences are scheduled after this point in time
+                pub until: Option<Datetime>,
+            }
+            impl ::core::fmt::Debug for CronRecurrence {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("CronRecurrence")
+                        .field("expression", &self.expression)
+                        .field("until", &self.until)
+                        .finish()
+                }
+            }
+            /// Describes how a recurring scheduled invocation is repeated
+            #[derive(Clone)]
+            pub enum ScheduleRecurrence {
+                Interval(IntervalRecurrence),
+                Cron(CronRecurrence),
+            }
+            impl ::core::fmt::Debug for ScheduleRecurrence {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        ScheduleRecurrence::Interval(e) => {
+                            f.debug_tuple("ScheduleRecurrence::Interval")
+                                .field(e)
+                                .finish()
+                        }
+                        ScheduleRecurrence::Cron(e) => {
+                            f.debug_tuple("ScheduleRecurrence::Cron").field(e).finish()
+                        }
+                    }
+                }
+            }
+            /// An RPC client
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct WasmRpc {
+                handle: _rt::Resource<WasmRpc>,
+            }
+            impl WasmRpc {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for WasmRpc {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[resource-drop]wasm-rpc"]
+                            fn drop(_: u32);
+                        }
+                        unsafe { drop(_handle) };
+                    }
+                }
+            }
+            /// Represents a pollable invocation result
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct FutureInvokeResult {
+                handle: _rt::Resource<FutureInvokeResult>,
+            }
+            impl FutureInvokeResult {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for FutureInvokeResult {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[resource-drop]future-invoke-result"]
+                            fn drop(_: u32);
+                        }
+                        unsafe { drop(_handle) };
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Constructs the RPC client connecting to the given target agent
+                pub fn new(agent_id: &AgentId) -> Self {
+                    unsafe {
+                        let super::super::super::golem::rpc0_2_2::types::AgentId {
+                            component_id: component_id0,
+                            agent_id: agent_id0,
+                        } = agent_id;
+                        let super::super::super::golem::rpc0_2_2::types::ComponentId {
+                            uuid: uuid1,
+                        } = component_id0;
+                        let super::super::super::golem::rpc0_2_2::types::Uuid {
+                            high_bits: high_bits2,
+                            low_bits: low_bits2,
+                        } = uuid1;
+                        let vec3 = agent_id0;
+                        let ptr3 = vec3.as_ptr().cast::<u8>();
+                        let len3 = vec3.len();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[constructor]wasm-rpc"]
+                            fn wit_import4(_: i64, _: i64, _: *mut u8, _: usize) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import4(
+                            _: i64,
+                            _: i64,
+                            _: *mut u8,
+                            _: usize,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import4(
+                                _rt::as_i64(high_bits2),
+                                _rt::as_i64(low_bits2),
+                                ptr3.cast_mut(),
+                                len3,
+                            )
+                        };
+                        unsafe { WasmRpc::from_handle(ret as u32) }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Invokes a remote function with the given parameters, and awaits the result
+                pub fn invoke_and_await(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> Result<WitValue, RpcError> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec13.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes1,
+                                } = e;
+                                let vec12 = nodes1;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V11;
+                                        match e {
+                                            V11::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            V11::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec4.len() * 1,
+                                                    1,
+                                                );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list.extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            V11::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            V11::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V11::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V11::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V11::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V11::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V11::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V11::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value9,
+                                                } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        let ptr14 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-and-await"]
+                            fn wit_import15(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import15(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import15(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                result13,
+                                len13,
+                                ptr14,
+                            )
+                        };
+                        let l16 = i32::from(*ptr14.add(0).cast::<u8>());
+                        let result82 = match l16 {
+                            0 => {
+                                let e = {
+                                    let l17 = *ptr14
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *ptr14
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base64 = l17;
+                                    let len64 = l18;
+                                    let mut result64 = _rt::Vec::with_capacity(len64);
+                                    for i in 0..len64 {
+                                        let base = base64
+                                            .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                        let e64 = {
+                                            let l19 = i32::from(*base.add(0).cast::<u8>());
+                                            use super::super::super::golem::rpc0_2_2::types::WitNode as V63;
+                                            let v63 = match l19 {
+                                                0 => {
+                                                    let e63 = {
+                                                        let l20 = *base.add(8).cast::<*mut u8>();
+                                                        let l21 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len22 = l21;
+                                                        _rt::Vec::from_raw_parts(l20.cast(), len22, len22)
+                                                    };
+                                                    V63::RecordValue(e63)
+                                                }
+                                                1 => {
+                                                    let e63 = {
+                                                        let l23 = *base.add(8).cast::<i32>();
+                                                        let l24 = i32::from(*base.add(12).cast::<u8>());
+                                                        (
+                                                            l23 as u32,
+                                                            match l24 {
+                                                                0 => None,
+                                                                1 => {
+                                                                    let e = {
+                                                                        let l25 = *base.add(16).cast::<i32>();
+                                                                        l25
+                                                                    };
+                                                                    Some(e)
+                                                                }
+                                                                _ => _rt::invalid_enum_discriminant(),
+                                                            },
+                                                        )
+                                                    };
+                                                    V63::VariantValue(e63)
+                                                }
+                                                2 => {
+                                                    let e63 = {
+                                                        let l26 = *base.add(8).cast::<i32>();
+                                                        l26 as u32
+                                                    };
+                                                    V63::EnumValue(e63)
+                                                }
+                                                3 => {
+                                                    let e63 = {
+                                                        let l27 = *base.add(8).cast::<*mut u8>();
+                                                        let l28 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let base30 = l27;
+                                                        let len30 = l28;
+                                                        let mut result30 = _rt::Vec::with_capacity(len30);
+                                                        for i in 0..len30 {
+                                                            let base = base30.add(i * 1);
+                                                            let e30 = {
+                                                                let l29 = i32::from(*base.add(0).cast::<u8>());
+                                                                _rt::bool_lift(l29 as u8)
+                                                            };
+                                                            result30.push(e30);
+                                                        }
+                                                        _rt::cabi_dealloc(base30, len30 * 1, 1);
+                                                        result30
+                                                    };
+                                                    V63::FlagsValue(e63)
+                                                }
+                                                4 => {
+                                                    let e63 = {
+                                                        let l31 = *base.add(8).cast::<*mut u8>();
+                                                        let l32 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len33 = l32;
+                                                        _rt::Vec::from_raw_parts(l31.cast(), len33, len33)
+                                                    };
+                                                    V63::TupleValue(e63)
+                                                }
+                                                5 => {
+                                                    let e63 = {
+                                                        let l34 = *base.add(8).cast::<*mut u8>();
+                                                        let l35 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len36 = l35;
+                                                        _rt::Vec::from_raw_parts(l34.cast(), len36, len36)
+                                                    };
+                                                    V63::ListValue(e63)
+                                                }
+                                                6 => {
+                                                    let e63 = {
+                                                        let l37 = i32::from(*base.add(8).cast::<u8>());
+                                                        match l37 {
+                                                            0 => None,
+                                                            1 => {
+                                                                let e = {
+                                                                    let l38 = *base.add(12).cast::<i32>();
+                                                                    l38
+                                                                };
+                                                                Some(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    V63::OptionValue(e63)
+                                                }
+                                                7 => {
+                                                    let e63 = {
+                                                        let l39 = i32::from(*base.add(8).cast::<u8>());
+                                                        match l39 {
+                                                            0 => {
+                                                                let e = {
+                                                                    let l40 = i32::from(*base.add(12).cast::<u8>());
+                                                                    match l40 {
+                                                                        0 => None,
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l41 = *base.add(16).cast::<i32>();
+                                                                                l41
+                                                                            };
+                                                                            Some(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                Ok(e)
+                                                            }
+                                                            1 => {
+                                                                let e = {
+                                                                    let l42 = i32::from(*base.add(12).cast::<u8>());
+                                                                    match l42 {
+                                                                        0 => None,
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l43 = *base.add(16).cast::<i32>();
+                                                                                l43
+                                                                            };
+                                                                            Some(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                Err(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    V63::ResultValue(e63)
+                                                }
+                                                8 => {
+                                                    let e63 = {
+                                                        let l44 = i32::from(*base.add(8).cast::<u8>());
+                                                        l44 as u8
+                                                    };
+                                                    V63::PrimU8(e63)
+                                                }
+                                                9 => {
+                                                    let e63 = {
+                                                        let l45 = i32::from(*base.add(8).cast::<u16>());
+                                                        l45 as u16
+                                                    };
+                                                    V63::PrimU16(e63)
+                                                }
+                                                10 => {
+                                                    let e63 = {
+                                                        let l46 = *base.add(8).cast::<i32>();
+                                                        l46 as u32
+                                                    };
+                                                    V63::PrimU32(e63)
+                                                }
+                                                11 => {
+                                                    let e63 = {
+                                                        let l47 = *base.add(8).cast::<i64>();
+                                                        l47 as u64
+                                                    };
+                                                    V63::PrimU64(e63)
+                                                }
+                                                12 => {
+                                                    let e63 = {
+                                                        let l48 = i32::from(*base.add(8).cast::<i8>());
+                                                        l48 as i8
+                                                    };
+                                                    V63::PrimS8(e63)
+                                                }
+                                                13 => {
+                                                    let e63 = {
+                                                        let l49 = i32::from(*base.add(8).cast::<i16>());
+                                                        l49 as i16
+                                                    };
+                                                    V63::PrimS16(e63)
+                                                }
+                                                14 => {
+                                                    let e63 = {
+                                                        let l50 = *base.add(8).cast::<i32>();
+                                                        l50
+                                                    };
+                                                    V63::PrimS32(e63)
+                                                }
+                                                15 => {
+                                                    let e63 = {
+                                                        let l51 = *base.add(8).cast::<i64>();
+                                                        l51
+                                                    };
+                                                    V63::PrimS64(e63)
+                                                }
+                                                16 => {
+                                                    let e63 = {
+                                                        let l52 = *base.add(8).cast::<f32>();
+                                                        l52
+                                                    };
+                                                    V63::PrimFloat32(e63)
+                                                }
+                                                17 => {
+                                                    let e63 = {
+                                                        let l53 = *base.add(8).cast::<f64>();
+                                                        l53
+                                                    };
+                                                    V63::PrimFloat64(e63)
+                                                }
+                                                18 => {
+                                                    let e63 = {
+                                                        let l54 = *base.add(8).cast::<i32>();
+                                                        _rt::char_lift(l54 as u32)
+                                                    };
+                                                    V63::PrimChar(e63)
+                                                }
+                                                19 => {
+                                                    let e63 = {
+                                                        let l55 = i32::from(*base.add(8).cast::<u8>());
+                                                        _rt::bool_lift(l55 as u8)
+                                                    };
+                                                    V63::PrimBool(e63)
+                                                }
+                                                20 => {
+                                                    let e63 = {
+                                                        let l56 = *base.add(8).cast::<*mut u8>();
+                                                        let l57 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len58 = l57;
+                                                        let bytes58 = _rt::Vec::from_raw_parts(
+                                                            l56.cast(),
+                                                            len58,
+                                                            len58,
+                                                        );
+                                                        _rt::string_lift(bytes58)
+                                                    };
+                                                    V63::PrimString(e63)
+                                                }
+                                                n => {
+                                                    debug_assert_eq!(n, 21, "invalid enum discriminant");
+                                                    let e63 = {
+                                                        let l59 = *base.add(8).cast::<*mut u8>();
+                                                        let l60 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len61 = l60;
+                                                        let bytes61 = _rt::Vec::from_raw_parts(
+                                                            l59.cast(),
+                                                            len61,
+                                                            len61,
+                                                        );
+                                                        let l62 = *base
+                                                            .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<i64>();
+                                                        (
+                                                            super::super::super::golem::rpc0_2_2::types::Uri {
+                                                                value: _rt::string_lift(bytes61),
+                                                            },
+                                                            l62 as u64,
+                                                        )
+                                                    };
+                                                    V63::Handle(e63)
+                                                }
+                                            };
+                                            v63
+                                        };
+                                        result64.push(e64);
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base64,
+                                        len64 * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                        8,
+                                    );
+                                    super::super::super::golem::rpc0_2_2::types::WitValue {
+                                        nodes: result64,
+                                    }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l65 = i32::from(
+                                        *ptr14.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v81 = match l65 {
+                                        0 => {
+                                            let e81 = {
+                                                let l66 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l67 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len68 = l67;
+                                                let bytes68 = _rt::Vec::from_raw_parts(
+                                                    l66.cast(),
+                                                    len68,
+                                                    len68,
+                                                );
+                                                _rt::string_lift(bytes68)
+                                            };
+                                            RpcError::ProtocolError(e81)
+                                        }
+                                        1 => {
+                                            let e81 = {
+                                                let l69 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l70 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len71 = l70;
+                                                let bytes71 = _rt::Vec::from_raw_parts(
+                                                    l69.cast(),
+                                                    len71,
+                                                    len71,
+                                                );
+                                                _rt::string_lift(bytes71)
+                                            };
+                                            RpcError::Denied(e81)
+                                        }
+                                        2 => {
+                                            let e81 = {
+                                                let l72 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l73 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len74 = l73;
+                                                let bytes74 = _rt::Vec::from_raw_parts(
+                                                    l72.cast(),
+                                                    len74,
+                                                    len74,
+                                                );
+                                                _rt::string_lift(bytes74)
+                                            };
+                                            RpcError::NotFound(e81)
+                                        }
+                                        3 => {
+                                            let e81 = {
+                                                let l75 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l76 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len77 = l76;
+                                                let bytes77 = _rt::Vec::from_raw_parts(
+                                                    l75.cast(),
+                                                    len77,
+                                                    len77,
+                                                );
+                                                _rt::string_lift(bytes77)
+                                            };
+                                            RpcError::RemoteInternalError(e81)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e81 = {
+                                                let l78 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l79 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len80 = l79;
+                                                let bytes80 = _rt::Vec::from_raw_parts(
+                                                    l78.cast(),
+                                                    len80,
+                                                    len80,
+                                                );
+                                                _rt::string_lift(bytes80)
+                                            };
+                                            RpcError::Timeout(e81)
+                                        }
+                                    };
+                                    v81
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        result82
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Invokes a remote function with the given parameters, and awaits the result for at most `timeout-millis`
+                /// milliseconds, failing with `rpc-error::timeout` after that.
+                ///
+                /// The deadline is also applied to the RPC calls the target agent makes while serving this invocation.
+                pub fn invoke_and_await_with_timeout(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    timeout_millis: u64,
+                ) -> Result<WitValue, RpcError> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec13.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes1,
+                                } = e;
+                                let vec12 = nodes1;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V11;
+                                        match e {
+                                            V11::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            V11::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec4.len() * 1,
+                                                    1,
+                                                );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list.extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            V11::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            V11::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V11::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V11::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V11::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V11::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V11::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V11::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value9,
+                                                } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        let ptr14 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke-and-await-with-timeout"]
+                            fn wit_import15(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: i64,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import15(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i64,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import15(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                result13,
+                                len13,
+                                _rt::as_i64(&timeout_millis),
+                                ptr14,
+                            )
+                        };
+                        let l16 = i32::from(*ptr14.add(0).cast::<u8>());
+                        let result82 = match l16 {
+                            0 => {
+                                let e = {
+                                    let l17 = *ptr14
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *ptr14
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base64 = l17;
+                                    let len64 = l18;
+                                    let mut result64 = _rt::Vec::with_capacity(len64);
+                                    for i in 0..len64 {
+                                        let base = base64
+                                            .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                        let e64 = {
+                                            let l19 = i32::from(*base.add(0).cast::<u8>());
+                                            use super::super::super::golem::rpc0_2_2::types::WitNode as V63;
+                                            let v63 = match l19 {
+                                                0 => {
+                                                    let e63 = {
+                                                        let l20 = *base.add(8).cast::<*mut u8>();
+                                                        let l21 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len22 = l21;
+                                                        _rt::Vec::from_raw_parts(l20.cast(), len22, len22)
+                                                    };
+                                                    V63::RecordValue(e63)
+                                                }
+                                                1 => {
+                                                    let e63 = {
+                                                        let l23 = *base.add(8).cast::<i32>();
+                                                        let l24 = i32::from(*base.add(12).cast::<u8>());
+                                                        (
+                                                            l23 as u32,
+                                                            match l24 {
+                                                                0 => None,
+                                                                1 => {
+                                                                    let e = {
+                                                                        let l25 = *base.add(16).cast::<i32>();
+                                                                        l25
+                                                                    };
+                                                                    Some(e)
+                                                                }
+                                                                _ => _rt::invalid_enum_discriminant(),
+                                                            },
+                                                        )
+                                                    };
+                                                    V63::VariantValue(e63)
+                                                }
+                                                2 => {
+                                                    let e63 = {
+                                                        let l26 = *base.add(8).cast::<i32>();
+                                                        l26 as u32
+                                                    };
+                                                    V63::EnumValue(e63)
+                                                }
+                                                3 => {
+                                                    let e63 = {
+                                                        let l27 = *base.add(8).cast::<*mut u8>();
+                                                        let l28 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let base30 = l27;
+                                                        let len30 = l28;
+                                                        let mut result30 = _rt::Vec::with_capacity(len30);
+                                                        for i in 0..len30 {
+                                                            let base = base30.add(i * 1);
+                                                            let e30 = {
+                                                                let l29 = i32::from(*base.add(0).cast::<u8>());
+                                                                _rt::bool_lift(l29 as u8)
+                                                            };
+                                                            result30.push(e30);
+                                                        }
+                                                        _rt::cabi_dealloc(base30, len30 * 1, 1);
+                                                        result30
+                                                    };
+                                                    V63::FlagsValue(e63)
+                                                }
+                                                4 => {
+                                                    let e63 = {
+                                                        let l31 = *base.add(8).cast::<*mut u8>();
+                                                        let l32 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len33 = l32;
+                                                        _rt::Vec::from_raw_parts(l31.cast(), len33, len33)
+                                                    };
+                                                    V63::TupleValue(e63)
+                                                }
+                                                5 => {
+                                                    let e63 = {
+                                                        let l34 = *base.add(8).cast::<*mut u8>();
+                                                        let l35 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len36 = l35;
+                                                        _rt::Vec::from_raw_parts(l34.cast(), len36, len36)
+                                                    };
+                                                    V63::ListValue(e63)
+                                                }
+                                                6 => {
+                                                    let e63 = {
+                                                        let l37 = i32::from(*base.add(8).cast::<u8>());
+                                                        match l37 {
+                                                            0 => None,
+                                                            1 => {
+                                                                let e = {
+                                                                    let l38 = *base.add(12).cast::<i32>();
+                                                                    l38
+                                                                };
+                                                                Some(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    V63::OptionValue(e63)
+                                                }
+                                                7 => {
+                                                    let e63 = {
+                                                        let l39 = i32::from(*base.add(8).cast::<u8>());
+                                                        match l39 {
+                                                            0 => {
+                                                                let e = {
+                                                                    let l40 = i32::from(*base.add(12).cast::<u8>());
+                                                                    match l40 {
+                                                                        0 => None,
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l41 = *base.add(16).cast::<i32>();
+                                                                                l41
+                                                                            };
+                                                                            Some(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                Ok(e)
+                                                            }
+                                                            1 => {
+                                                                let e = {
+                                                                    let l42 = i32::from(*base.add(12).cast::<u8>());
+                                                                    match l42 {
+                                                                        0 => None,
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l43 = *base.add(16).cast::<i32>();
+                                                                                l43
+                                                                            };
+                                                                            Some(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                Err(e)
+                                                            }
+                                                            _ => _rt::invalid_enum_discriminant(),
+                                                        }
+                                                    };
+                                                    V63::ResultValue(e63)
+                                                }
+                                                8 => {
+                                                    let e63 = {
+                                                        let l44 = i32::from(*base.add(8).cast::<u8>());
+                                                        l44 as u8
+                                                    };
+                                                    V63::PrimU8(e63)
+                                                }
+                                                9 => {
+                                                    let e63 = {
+                                                        let l45 = i32::from(*base.add(8).cast::<u16>());
+                                                        l45 as u16
+                                                    };
+                                                    V63::PrimU16(e63)
+                                                }
+                                                10 => {
+                                                    let e63 = {
+                                                        let l46 = *base.add(8).cast::<i32>();
+                                                        l46 as u32
+                                                    };
+                                                    V63::PrimU32(e63)
+                                                }
+                                                11 => {
+                                                    let e63 = {
+                                                        let l47 = *base.add(8).cast::<i64>();
+                                                        l47 as u64
+                                                    };
+                                                    V63::PrimU64(e63)
+                                                }
+                                                12 => {
+                                                    let e63 = {
+                                                        let l48 = i32::from(*base.add(8).cast::<i8>());
+                                                        l48 as i8
+                                                    };
+                                                    V63::PrimS8(e63)
+                                                }
+                                                13 => {
+                                                    let e63 = {
+                                                        let l49 = i32::from(*base.add(8).cast::<i16>());
+                                                        l49 as i16
+                                                    };
+                                                    V63::PrimS16(e63)
+                                                }
+                                                14 => {
+                                                    let e63 = {
+                                                        let l50 = *base.add(8).cast::<i32>();
+                                                        l50
+                                                    };
+                                                    V63::PrimS32(e63)
+                                                }
+                                                15 => {
+                                                    let e63 = {
+                                                        let l51 = *base.add(8).cast::<i64>();
+                                                        l51
+                                                    };
+                                                    V63::PrimS64(e63)
+                                                }
+                                                16 => {
+                                                    let e63 = {
+                                                        let l52 = *base.add(8).cast::<f32>();
+                                                        l52
+                                                    };
+                                                    V63::PrimFloat32(e63)
+                                                }
+                                                17 => {
+                                                    let e63 = {
+                                                        let l53 = *base.add(8).cast::<f64>();
+                                                        l53
+                                                    };
+                                                    V63::PrimFloat64(e63)
+                                                }
+                                                18 => {
+                                                    let e63 = {
+                                                        let l54 = *base.add(8).cast::<i32>();
+                                                        _rt::char_lift(l54 as u32)
+                                                    };
+                                                    V63::PrimChar(e63)
+                                                }
+                                                19 => {
+                                                    let e63 = {
+                                                        let l55 = i32::from(*base.add(8).cast::<u8>());
+                                                        _rt::bool_lift(l55 as u8)
+                                                    };
+                                                    V63::PrimBool(e63)
+                                                }
+                                                20 => {
+                                                    let e63 = {
+                                                        let l56 = *base.add(8).cast::<*mut u8>();
+                                                        let l57 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len58 = l57;
+                                                        let bytes58 = _rt::Vec::from_raw_parts(
+                                                            l56.cast(),
+                                                            len58,
+                                                            len58,
+                                                        );
+                                                        _rt::string_lift(bytes58)
+                                                    };
+                                                    V63::PrimString(e63)
+                                                }
+                                                n => {
+                                                    debug_assert_eq!(n, 21, "invalid enum discriminant");
+                                                    let e63 = {
+                                                        let l59 = *base.add(8).cast::<*mut u8>();
+                                                        let l60 = *base
+                                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<usize>();
+                                                        let len61 = l60;
+                                                        let bytes61 = _rt::Vec::from_raw_parts(
+                                                            l59.cast(),
+                                                            len61,
+                                                            len61,
+                                                        );
+                                                        let l62 = *base
+                                                            .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                            .cast::<i64>();
+                                                        (
+                                                            super::super::super::golem::rpc0_2_2::types::Uri {
+                                                                value: _rt::string_lift(bytes61),
+                                                            },
+                                                            l62 as u64,
+                                                        )
+                                                    };
+                                                    V63::Handle(e63)
+                                                }
+                                            };
+                                            v63
+                                        };
+                                        result64.push(e64);
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base64,
+                                        len64 * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                        8,
+                                    );
+                                    super::super::super::golem::rpc0_2_2::types::WitValue {
+                                        nodes: result64,
+                                    }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l65 = i32::from(
+                                        *ptr14.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v81 = match l65 {
+                                        0 => {
+                                            let e81 = {
+                                                let l66 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l67 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len68 = l67;
+                                                let bytes68 = _rt::Vec::from_raw_parts(
+                                                    l66.cast(),
+                                                    len68,
+                                                    len68,
+                                                );
+                                                _rt::string_lift(bytes68)
+                                            };
+                                            RpcError::ProtocolError(e81)
+                                        }
+                                        1 => {
+                                            let e81 = {
+                                                let l69 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l70 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len71 = l70;
+                                                let bytes71 = _rt::Vec::from_raw_parts(
+                                                    l69.cast(),
+                                                    len71,
+                                                    len71,
+                                                );
+                                                _rt::string_lift(bytes71)
+                                            };
+                                            RpcError::Denied(e81)
+                                        }
+                                        2 => {
+                                            let e81 = {
+                                                let l72 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l73 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len74 = l73;
+                                                let bytes74 = _rt::Vec::from_raw_parts(
+                                                    l72.cast(),
+                                                    len74,
+                                                    len74,
+                                                );
+                                                _rt::string_lift(bytes74)
+                                            };
+                                            RpcError::NotFound(e81)
+                                        }
+                                        3 => {
+                                            let e81 = {
+                                                let l75 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l76 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len77 = l76;
+                                                let bytes77 = _rt::Vec::from_raw_parts(
+                                                    l75.cast(),
+                                                    len77,
+                                                    len77,
+                                                );
+                                                _rt::string_lift(bytes77)
+                                            };
+                                            RpcError::RemoteInternalError(e81)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e81 = {
+                                                let l78 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l79 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len80 = l79;
+                                                let bytes80 = _rt::Vec::from_raw_parts(
+                                                    l78.cast(),
+                                                    len80,
+                                                    len80,
+                                                );
+                                                _rt::string_lift(bytes80)
+                                            };
+                                            RpcError::Timeout(e81)
+                                        }
+                                    };
+                                    v81
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        result82
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Triggers the invocation of a remote function with the given parameters, and returns immediately.
+                pub fn invoke(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> Result<(), RpcError> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 4 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 4
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec13.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes1,
+                                } = e;
+                                let vec12 = nodes1;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V11;
+                                        match e {
+                                            V11::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            V11::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec4.len() * 1,
+                                                    1,
+                                                );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list.extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            V11::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            V11::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V11::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V11::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V11::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V11::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V11::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V11::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value9,
+                                                } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        let ptr14 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.invoke"]
+                            fn wit_import15(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import15(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import15(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                result13,
+                                len13,
+                                ptr14,
+                            )
+                        };
+                        let l16 = i32::from(*ptr14.add(0).cast::<u8>());
+                        let result34 = match l16 {
+                            0 => {
+                                let e = ();
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l17 = i32::from(
+                                        *ptr14.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    let v33 = match l17 {
+                                        0 => {
+                                            let e33 = {
+                                                let l18 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l19 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len20 = l19;
+                                                let bytes20 = _rt::Vec::from_raw_parts(
+                                                    l18.cast(),
+                                                    len20,
+                                                    len20,
+                                                );
+                                                _rt::string_lift(bytes20)
+                                            };
+                                            RpcError::ProtocolError(e33)
+                                        }
+                                        1 => {
+                                            let e33 = {
+                                                let l21 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l22 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len23 = l22;
+                                                let bytes23 = _rt::Vec::from_raw_parts(
+                                                    l21.cast(),
+                                                    len23,
+                                                    len23,
+                                                );
+                                                _rt::string_lift(bytes23)
+                                            };
+                                            RpcError::Denied(e33)
+                                        }
+                                        2 => {
+                                            let e33 = {
+                                                let l24 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l25 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len26 = l25;
+                                                let bytes26 = _rt::Vec::from_raw_parts(
+                                                    l24.cast(),
+                                                    len26,
+                                                    len26,
+                                                );
+                                                _rt::string_lift(bytes26)
+                                            };
+                                            RpcError::NotFound(e33)
+                                        }
+                                        3 => {
+                                            let e33 = {
+                                                let l27 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l28 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len29 = l28;
+                                                let bytes29 = _rt::Vec::from_raw_parts(
+                                                    l27.cast(),
+                                                    len29,
+                                                    len29,
+                                                );
+                                                _rt::string_lift(bytes29)
+                                            };
+                                            RpcError::RemoteInternalError(e33)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                            let e33 = {
+                                                let l30 = *ptr14
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l31 = *ptr14
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len32 = l31;
+                                                let bytes32 = _rt::Vec::from_raw_parts(
+                                                    l30.cast(),
+                                                    len32,
+                                                    len32,
+                                                );
+                                                _rt::string_lift(bytes32)
+                                            };
+                                            RpcError::Timeout(e33)
+                                        }
+                                    };
+                                    v33
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        result34
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Invokes a remote function with the given parameters, and returns a `future-invoke-result` value which can
+                /// be polled for the result.
+                ///
+                /// With this function it is possible to call multiple (different) agents simultaneously.
+                pub fn async_invoke_and_await(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> FutureInvokeResult {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec13.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes1,
+                                } = e;
+                                let vec12 = nodes1;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V11;
+                                        match e {
+                                            V11::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            V11::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec4.len() * 1,
+                                                    1,
+                                                );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list.extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            V11::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            V11::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V11::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V11::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V11::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V11::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V11::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V11::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value9,
+                                                } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.async-invoke-and-await"]
+                            fn wit_import14(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                            ) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import14(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import14(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                result13,
+                                len13,
+                            )
+                        };
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        unsafe { FutureInvokeResult::from_handle(ret as u32) }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Same as `async-invoke-and-await`, but the returned `future-invoke-result` completes with `rpc-error::timeout`
+                /// if the result does not arrive in `timeout-millis` milliseconds.
+                pub fn async_invoke_and_await_with_timeout(
+                    &self,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                    timeout_millis: u64,
+                ) -> FutureInvokeResult {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let vec0 = function_name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec13 = function_params;
+                        let len13 = vec13.len();
+                        let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec13.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result13 = if layout13.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout13);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec13.into_iter().enumerate() {
+                            let base = result13
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes1,
+                                } = e;
+                                let vec12 = nodes1;
+                                let len12 = vec12.len();
+                                let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec12.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result12 = if layout12.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout12);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec12.into_iter().enumerate() {
+                                    let base = result12
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V11;
+                                        match e {
+                                            V11::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec2 = e;
+                                                let ptr2 = vec2.as_ptr().cast::<u8>();
+                                                let len2 = vec2.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len2;
+                                                *base.add(8).cast::<*mut u8>() = ptr2.cast_mut();
+                                            }
+                                            V11::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t3_0, t3_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t3_0);
+                                                match t3_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec4 = e;
+                                                let len4 = vec4.len();
+                                                let layout4 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec4.len() * 1,
+                                                    1,
+                                                );
+                                                let result4 = if layout4.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout4).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout4);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec4.into_iter().enumerate() {
+                                                    let base = result4.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len4;
+                                                *base.add(8).cast::<*mut u8>() = result4;
+                                                cleanup_list.extend_from_slice(&[(result4, layout4)]);
+                                            }
+                                            V11::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec5 = e;
+                                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                                let len5 = vec5.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                            }
+                                            V11::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V11::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V11::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V11::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V11::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V11::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V11::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V11::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V11::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V11::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V11::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V11::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t8_0, t8_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value9,
+                                                } = t8_0;
+                                                let vec10 = value9;
+                                                let ptr10 = vec10.as_ptr().cast::<u8>();
+                                                let len10 = vec10.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len10;
+                                                *base.add(8).cast::<*mut u8>() = ptr10.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t8_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len12;
+                                *base.add(0).cast::<*mut u8>() = result12;
+                                cleanup_list.extend_from_slice(&[(result12, layout12)]);
+                            }
+                        }
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.async-invoke-and-await-with-timeout"]
+                            fn wit_import14(
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: i64,
+                            ) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import14(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i64,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import14(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                result13,
+                                len13,
+                                _rt::as_i64(&timeout_millis),
+                            )
+                        };
+                        if layout13.size() != 0 {
+                            _rt::alloc::dealloc(result13.cast(), layout13);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        unsafe { FutureInvokeResult::from_handle(ret as u32) }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Schedule invocation for later
+                pub fn schedule_invocation(
+                    &self,
+                    scheduled_time: Datetime,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> () {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let super::super::super::__with_name0::Datetime {
+                            seconds: seconds0,
+                            nanoseconds: nanoseconds0,
+                        } = scheduled_time;
+                        let vec1 = function_name;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        let vec14 = function_params;
+                        let len14 = vec14.len();
+                        let layout14 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec14.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result14 = if layout14.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout14).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout14);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec14.into_iter().enumerate() {
+                            let base = result14
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes2,
+                                } = e;
+                                let vec13 = nodes2;
+                                let len13 = vec13.len();
+                                let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec13.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result13 = if layout13.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout13);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec13.into_iter().enumerate() {
+                                    let base = result13
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V12;
+                                        match e {
+                                            V12::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec3 = e;
+                                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                let len3 = vec3.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len3;
+                                                *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                            }
+                                            V12::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t4_0, t4_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t4_0);
+                                                match t4_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V12::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec5 = e;
+                                                let len5 = vec5.len();
+                                                let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec5.len() * 1,
+                                                    1,
+                                                );
+                                                let result5 = if layout5.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout5);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec5.into_iter().enumerate() {
+                                                    let base = result5.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = result5;
+                                                cleanup_list.extend_from_slice(&[(result5, layout5)]);
+                                            }
+                                            V12::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V12::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V12::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V12::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V12::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V12::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V12::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V12::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V12::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V12::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V12::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V12::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V12::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V12::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec8 = e;
+                                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                let len8 = vec8.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len8;
+                                                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+                                            }
+                                            V12::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t9_0, t9_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value10,
+                                                } = t9_0;
+                                                let vec11 = value10;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t9_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len13;
+                                *base.add(0).cast::<*mut u8>() = result13;
+                                cleanup_list.extend_from_slice(&[(result13, layout13)]);
+                            }
+                        }
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.schedule-invocation"]
+                            fn wit_import15(
+                                _: i32,
+                                _: i64,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import15(
+                            _: i32,
+                            _: i64,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import15(
+                                (self).handle() as i32,
+                                _rt::as_i64(seconds0),
+                                _rt::as_i32(nanoseconds0),
+                                ptr1.cast_mut(),
+                                len1,
+                                result14,
+                                len14,
+                            )
+                        };
+                        if layout14.size() != 0 {
+                            _rt::alloc::dealloc(result14.cast(), layout14);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Schedule invocation for later. Call cancel on the returned resource to cancel the invocation before the scheduled time.
+                pub fn schedule_cancelable_invocation(
+                    &self,
+                    scheduled_time: Datetime,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> CancellationToken {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        let super::super::super::__with_name0::Datetime {
+                            seconds: seconds0,
+                            nanoseconds: nanoseconds0,
+                        } = scheduled_time;
+                        let vec1 = function_name;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        let vec14 = function_params;
+                        let len14 = vec14.len();
+                        let layout14 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec14.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result14 = if layout14.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout14).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout14);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec14.into_iter().enumerate() {
+                            let base = result14
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes2,
+                                } = e;
+                                let vec13 = nodes2;
+                                let len13 = vec13.len();
+                                let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec13.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result13 = if layout13.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout13);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec13.into_iter().enumerate() {
+                                    let base = result13
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V12;
+                                        match e {
+                                            V12::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec3 = e;
+                                                let ptr3 = vec3.as_ptr().cast::<u8>();
+                                                let len3 = vec3.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len3;
+                                                *base.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                                            }
+                                            V12::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t4_0, t4_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t4_0);
+                                                match t4_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V12::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec5 = e;
+                                                let len5 = vec5.len();
+                                                let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec5.len() * 1,
+                                                    1,
+                                                );
+                                                let result5 = if layout5.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout5);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec5.into_iter().enumerate() {
+                                                    let base = result5.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len5;
+                                                *base.add(8).cast::<*mut u8>() = result5;
+                                                cleanup_list.extend_from_slice(&[(result5, layout5)]);
+                                            }
+                                            V12::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec6 = e;
+                                                let ptr6 = vec6.as_ptr().cast::<u8>();
+                                                let len6 = vec6.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len6;
+                                                *base.add(8).cast::<*mut u8>() = ptr6.cast_mut();
+                                            }
+                                            V12::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec7 = e;
+                                                let ptr7 = vec7.as_ptr().cast::<u8>();
+                                                let len7 = vec7.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len7;
+                                                *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                            }
+                                            V12::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V12::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V12::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V12::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V12::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V12::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V12::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V12::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V12::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V12::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V12::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V12::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V12::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec8 = e;
+                                                let ptr8 = vec8.as_ptr().cast::<u8>();
+                                                let len8 = vec8.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len8;
+                                                *base.add(8).cast::<*mut u8>() = ptr8.cast_mut();
+                                            }
+                                            V12::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t9_0, t9_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value10,
+                                                } = t9_0;
+                                                let vec11 = value10;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t9_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len13;
+                                *base.add(0).cast::<*mut u8>() = result13;
+                                cleanup_list.extend_from_slice(&[(result13, layout13)]);
+                            }
+                        }
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.schedule-cancelable-invocation"]
+                            fn wit_import15(
+                                _: i32,
+                                _: i64,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                            ) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import15(
+                            _: i32,
+                            _: i64,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import15(
+                                (self).handle() as i32,
+                                _rt::as_i64(seconds0),
+                                _rt::as_i32(nanoseconds0),
+                                ptr1.cast_mut(),
+                                len1,
+                                result14,
+                                len14,
+                            )
+                        };
+                        if layout14.size() != 0 {
+                            _rt::alloc::dealloc(result14.cast(), layout14);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        unsafe {
+                            super::super::super::golem::rpc0_2_2::types::CancellationToken::from_handle(
+                                ret as u32,
+                            )
+                        }
+                    }
+                }
+            }
+            impl WasmRpc {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Schedule a recurring invocation, with the first occurrence at or after the scheduled time.
+                /// Call cancel on the returned resource to stop all further occurrences.
+                /// Fails if the recurrence is invalid, for example because of a malformed cron expression.
+                pub fn schedule_recurring_invocation(
+                    &self,
+                    scheduled_time: Datetime,
+                    recurrence: &ScheduleRecurrence,
+                    function_name: &str,
+                    function_params: &[WitValue],
+                ) -> Result<CancellationToken, _rt::String> {
+                    unsafe {
+                        let mut cleanup_list = _rt::Vec::new();
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 3 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 3
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let super::super::super::__with_name0::Datetime {
+                            seconds: seconds0,
+                            nanoseconds: nanoseconds0,
+                        } = scheduled_time;
+                        let (
+                            result8_0,
+                            result8_1,
+                            result8_2,
+                            result8_3,
+                            result8_4,
+                            result8_5,
+                        ) = match recurrence {
+                            ScheduleRecurrence::Interval(e) => {
+                                let IntervalRecurrence {
+                                    interval_millis: interval_millis1,
+                                    until: until1,
+                                } = e;
+                                let (result3_0, result3_1, result3_2) = match until1 {
+                                    Some(e) => {
+                                        let super::super::super::__with_name0::Datetime {
+                                            seconds: seconds2,
+                                            nanoseconds: nanoseconds2,
+                                        } = e;
+                                        (1i32, _rt::as_i64(seconds2), _rt::as_i32(nanoseconds2))
+                                    }
+                                    None => (0i32, 0i64, 0i32),
+                                };
+                                (
+                                    0i32,
+                                    ::core::mem::MaybeUninit::new(
+                                        _rt::as_i64(interval_millis1) as u64,
+                                    ),
+                                    result3_0 as usize,
+                                    result3_1,
+                                    i64::from(result3_2),
+                                    0i32,
+                                )
+                            }
+                            ScheduleRecurrence::Cron(e) => {
+                                let CronRecurrence {
+                                    expression: expression4,
+                                    until: until4,
+                                } = e;
+                                let vec5 = expression4;
+                                let ptr5 = vec5.as_ptr().cast::<u8>();
+                                let len5 = vec5.len();
+                                let (result7_0, result7_1, result7_2) = match until4 {
+                                    Some(e) => {
+                                        let super::super::super::__with_name0::Datetime {
+                                            seconds: seconds6,
+                                            nanoseconds: nanoseconds6,
+                                        } = e;
+                                        (1i32, _rt::as_i64(seconds6), _rt::as_i32(nanoseconds6))
+                                    }
+                                    None => (0i32, 0i64, 0i32),
+                                };
+                                (
+                                    1i32,
+                                    {
+                                        let mut t = ::core::mem::MaybeUninit::<u64>::uninit();
+                                        t.as_mut_ptr().cast::<*mut u8>().write(ptr5.cast_mut());
+                                        t
+                                    },
+                                    len5,
+                                    i64::from(result7_0),
+                                    result7_1,
+                                    result7_2,
+                                )
+                            }
+                        };
+                        let vec9 = function_name;
+                        let ptr9 = vec9.as_ptr().cast::<u8>();
+                        let len9 = vec9.len();
+                        let vec22 = function_params;
+                        let len22 = vec22.len();
+                        let layout22 = _rt::alloc::Layout::from_size_align_unchecked(
+                            vec22.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result22 = if layout22.size() != 0 {
+                            let ptr = _rt::alloc::alloc(layout22).cast::<u8>();
+                            if ptr.is_null() {
+                                _rt::alloc::handle_alloc_error(layout22);
+                            }
+                            ptr
+                        } else {
+                            ::core::ptr::null_mut()
+                        };
+                        for (i, e) in vec22.into_iter().enumerate() {
+                            let base = result22
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            {
+                                let super::super::super::golem::rpc0_2_2::types::WitValue {
+                                    nodes: nodes10,
+                                } = e;
+                                let vec21 = nodes10;
+                                let len21 = vec21.len();
+                                let layout21 = _rt::alloc::Layout::from_size_align_unchecked(
+                                    vec21.len()
+                                        * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                    8,
+                                );
+                                let result21 = if layout21.size() != 0 {
+                                    let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+                                    if ptr.is_null() {
+                                        _rt::alloc::handle_alloc_error(layout21);
+                                    }
+                                    ptr
+                                } else {
+                                    ::core::ptr::null_mut()
+                                };
+                                for (i, e) in vec21.into_iter().enumerate() {
+                                    let base = result21
+                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                    {
+                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V20;
+                                        match e {
+                                            V20::RecordValue(e) => {
+                                                *base.add(0).cast::<u8>() = (0i32) as u8;
+                                                let vec11 = e;
+                                                let ptr11 = vec11.as_ptr().cast::<u8>();
+                                                let len11 = vec11.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len11;
+                                                *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                            }
+                                            V20::VariantValue(e) => {
+                                                *base.add(0).cast::<u8>() = (1i32) as u8;
+                                                let (t12_0, t12_1) = e;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(t12_0);
+                                                match t12_1 {
+                                                    Some(e) => {
+                                                        *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V20::EnumValue(e) => {
+                                                *base.add(0).cast::<u8>() = (2i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V20::FlagsValue(e) => {
+                                                *base.add(0).cast::<u8>() = (3i32) as u8;
+                                                let vec13 = e;
+                                                let len13 = vec13.len();
+                                                let layout13 = _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec13.len() * 1,
+                                                    1,
+                                                );
+                                                let result13 = if layout13.size() != 0 {
+                                                    let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                                                    if ptr.is_null() {
+                                                        _rt::alloc::handle_alloc_error(layout13);
+                                                    }
+                                                    ptr
+                                                } else {
+                                                    ::core::ptr::null_mut()
+                                                };
+                                                for (i, e) in vec13.into_iter().enumerate() {
+                                                    let base = result13.add(i * 1);
+                                                    {
+                                                        *base.add(0).cast::<u8>() = (match e {
+                                                            true => 1,
+                                                            false => 0,
+                                                        }) as u8;
+                                                    }
+                                                }
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len13;
+                                                *base.add(8).cast::<*mut u8>() = result13;
+                                                cleanup_list.extend_from_slice(&[(result13, layout13)]);
+                                            }
+                                            V20::TupleValue(e) => {
+                                                *base.add(0).cast::<u8>() = (4i32) as u8;
+                                                let vec14 = e;
+                                                let ptr14 = vec14.as_ptr().cast::<u8>();
+                                                let len14 = vec14.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len14;
+                                                *base.add(8).cast::<*mut u8>() = ptr14.cast_mut();
+                                            }
+                                            V20::ListValue(e) => {
+                                                *base.add(0).cast::<u8>() = (5i32) as u8;
+                                                let vec15 = e;
+                                                let ptr15 = vec15.as_ptr().cast::<u8>();
+                                                let len15 = vec15.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len15;
+                                                *base.add(8).cast::<*mut u8>() = ptr15.cast_mut();
+                                            }
+                                            V20::OptionValue(e) => {
+                                                *base.add(0).cast::<u8>() = (6i32) as u8;
+                                                match e {
+                                                    Some(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        *base.add(12).cast::<i32>() = _rt::as_i32(e);
+                                                    }
+                                                    None => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                    }
+                                                };
+                                            }
+                                            V20::ResultValue(e) => {
+                                                *base.add(0).cast::<u8>() = (7i32) as u8;
+                                                match e {
+                                                    Ok(e) => {
+                                                        *base.add(8).cast::<u8>() = (0i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                    Err(e) => {
+                                                        *base.add(8).cast::<u8>() = (1i32) as u8;
+                                                        match e {
+                                                            Some(e) => {
+                                                                *base.add(12).cast::<u8>() = (1i32) as u8;
+                                                                *base.add(16).cast::<i32>() = _rt::as_i32(e);
+                                                            }
+                                                            None => {
+                                                                *base.add(12).cast::<u8>() = (0i32) as u8;
+                                                            }
+                                                        };
+                                                    }
+                                                };
+                                            }
+                                            V20::PrimU8(e) => {
+                                                *base.add(0).cast::<u8>() = (8i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V20::PrimU16(e) => {
+                                                *base.add(0).cast::<u8>() = (9i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V20::PrimU32(e) => {
+                                                *base.add(0).cast::<u8>() = (10i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V20::PrimU64(e) => {
+                                                *base.add(0).cast::<u8>() = (11i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V20::PrimS8(e) => {
+                                                *base.add(0).cast::<u8>() = (12i32) as u8;
+                                                *base.add(8).cast::<u8>() = (_rt::as_i32(e)) as u8;
+                                            }
+                                            V20::PrimS16(e) => {
+                                                *base.add(0).cast::<u8>() = (13i32) as u8;
+                                                *base.add(8).cast::<u16>() = (_rt::as_i32(e)) as u16;
+                                            }
+                                            V20::PrimS32(e) => {
+                                                *base.add(0).cast::<u8>() = (14i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V20::PrimS64(e) => {
+                                                *base.add(0).cast::<u8>() = (15i32) as u8;
+                                                *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                            }
+                                            V20::PrimFloat32(e) => {
+                                                *base.add(0).cast::<u8>() = (16i32) as u8;
+                                                *base.add(8).cast::<f32>() = _rt::as_f32(e);
+                                            }
+                                            V20::PrimFloat64(e) => {
+                                                *base.add(0).cast::<u8>() = (17i32) as u8;
+                                                *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                            }
+                                            V20::PrimChar(e) => {
+                                                *base.add(0).cast::<u8>() = (18i32) as u8;
+                                                *base.add(8).cast::<i32>() = _rt::as_i32(e);
+                                            }
+                                            V20::PrimBool(e) => {
+                                                *base.add(0).cast::<u8>() = (19i32) as u8;
+                                                *base.add(8).cast::<u8>() = (match e {
+                                                    true => 1,
+                                                    false => 0,
+                                                }) as u8;
+                                            }
+                                            V20::PrimString(e) => {
+                                                *base.add(0).cast::<u8>() = (20i32) as u8;
+                                                let vec16 = e;
+                                                let ptr16 = vec16.as_ptr().cast::<u8>();
+                                                let len16 = vec16.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len16;
+                                                *base.add(8).cast::<*mut u8>() = ptr16.cast_mut();
+                                            }
+                                            V20::Handle(e) => {
+                                                *base.add(0).cast::<u8>() = (21i32) as u8;
+                                                let (t17_0, t17_1) = e;
+                                                let super::super::super::golem::rpc0_2_2::types::Uri {
+                                                    value: value18,
+                                                } = t17_0;
+                                                let vec19 = value18;
+                                                let ptr19 = vec19.as_ptr().cast::<u8>();
+                                                let len19 = vec19.len();
+                                                *base
+                                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>() = len19;
+                                                *base.add(8).cast::<*mut u8>() = ptr19.cast_mut();
+                                                *base
+                                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>() = _rt::as_i64(t17_1);
+                                            }
+                                        }
+                                    }
+                                }
+                                *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>() = len21;
+                                *base.add(0).cast::<*mut u8>() = result21;
+                                cleanup_list.extend_from_slice(&[(result21, layout21)]);
+                            }
+                        }
+                        let ptr23 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]wasm-rpc.schedule-recurring-invocation"]
+                            fn wit_import24(
+                                _: i32,
+                                _: i64,
+                                _: i32,
+                                _: i32,
+                                _: ::core::mem::MaybeUninit<u64>,
+                                _: usize,
+                                _: i64,
+                                _: i64,
+                                _: i32,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                            );
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import24(
+                            _: i32,
+                            _: i64,
+                            _: i32,
+                            _: i32,
+                            _: ::core::mem::MaybeUninit<u64>,
+                            _: usize,
+                            _: i64,
+                            _: i64,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        ) {
+                            unreachable!()
+                        }
+                        unsafe {
+                            wit_import24(
+                                (self).handle() as i32,
+                                _rt::as_i64(seconds0),
+                                _rt::as_i32(nanoseconds0),
+                                result8_0,
+                                result8_1,
+                                result8_2,
+                                result8_3,
+                                result8_4,
+                                result8_5,
+                                ptr9.cast_mut(),
+                                len9,
+                                result22,
+                                len22,
+                                ptr23,
+                            )
+                        };
+                        let l25 = i32::from(*ptr23.add(0).cast::<u8>());
+                        let result30 = match l25 {
+                            0 => {
+                                let e = {
+                                    let l26 = *ptr23
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<i32>();
+                                    unsafe {
+                                        super::super::super::golem::rpc0_2_2::types::CancellationToken::from_handle(
+                                            l26 as u32,
+                                        )
+                                    }
+                                };
+                                Ok(e)
+                            }
+                            1 => {
+                                let e = {
+                                    let l27 = *ptr23
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l28 = *ptr23
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len29 = l28;
+                                    let bytes29 = _rt::Vec::from_raw_parts(
+                                        l27.cast(),
+                                        len29,
+                                        len29,
+                                    );
+                                    _rt::string_lift(bytes29)
+                                };
+                                Err(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        if layout22.size() != 0 {
+                            _rt::alloc::dealloc(result22.cast(), layout22);
+                        }
+                        for (ptr, layout) in cleanup_list {
+                            if layout.size() != 0 {
+                                _rt::alloc::dealloc(ptr.cast(), layout);
+                            }
+                        }
+                        result30
+                    }
+                }
+            }
+            impl FutureInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Subscribes to the result of the invocation
+                pub fn subscribe(&self) -> Pollable {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]future-invoke-result.subscribe"]
+                            fn wit_import0(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
+                        unsafe {
+                            super::super::super::__with_name1::Pollable::from_handle(
+                                ret as u32,
+                            )
+                        }
+                    }
+                }
+            }
+            impl FutureInvokeResult {
+                #[allow(unused_unsafe, clippy::all)]
+                /// Poll for the invocation. If the invocation has not completed yet, returns `none`.
+                pub fn get(&self) -> Option<Result<WitValue, RpcError>> {
+                    unsafe {
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 5 * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let mut ret_area = RetArea(
+                            [::core::mem::MaybeUninit::uninit(); 5
+                                * ::core::mem::size_of::<*const u8>()],
+                        );
+                        let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "golem:rpc/types@0.2.3")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]future-invoke-result.get"]
+                            fn wit_import1(_: i32, _: *mut u8);
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
+                            unreachable!()
+                        }
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                        let result69 = match l2 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l3 = i32::from(
+                                        *ptr0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                    );
+                                    match l3 {
+                                        0 => {
+                                            let e = {
+                                                let l4 = *ptr0
+                                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l5 = *ptr0
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let base51 = l4;
+                                                let len51 = l5;
+                                                let mut result51 = _rt::Vec::with_capacity(len51);
+                                                for i in 0..len51 {
+                                                    let base = base51
+                                                        .add(i * (16 + 2 * ::core::mem::size_of::<*const u8>()));
+                                                    let e51 = {
+                                                        let l6 = i32::from(*base.add(0).cast::<u8>());
+                                                        use super::super::super::golem::rpc0_2_2::types::WitNode as V50;
+                                                        let v50 = match l6 {
+                                                            0 => {
+                                                                let e50 = {
+                                                                    let l7 = *base.add(8).cast::<*mut u8>();
+                                                                    let l8 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let len9 = l8;
+                                                                    _rt::Vec::from_raw_parts(l7.cast(), len9, len9)
+                                                                };
+                                                                V50::RecordValue(e50)
+                                                            }
+                                                            1 => {
+                                                                let e50 = {
+                                                                    let l10 = *base.add(8).cast::<i32>();
+                                                                    let l11 = i32::from(*base.add(12).cast::<u8>());
+                                                                    (
+                                                                        l10 as u32,
+                                                                        match l11 {
+                                                                            0 => None,
+                                                                            1 => {
+                                                                                let e = {
+                                                                                    let l12 = *base.add(16).cast::<i32>();
+                                                                                    l12
+                                                                                };
+                                                                                Some(e)
+                                                                            }
+                                                                            _ => _rt::invalid_enum_discriminant(),
+                                                                        },
+                                                                    )
+                                                                };
+                                                                V50::VariantValue(e50)
+                                                            }
+                                                            2 => {
+                                                                let e50 = {
+                                                                    let l13 = *base.add(8).cast::<i32>();
+                                                                    l13 as u32
+                                                                };
+                                                                V50::EnumValue(e50)
+                                                            }
+                                                            3 => {
+                                                                let e50 = {
+                                                                    let l14 = *base.add(8).cast::<*mut u8>();
+                                                                    let l15 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let base17 = l14;
+                                                                    let len17 = l15;
+                                                                    let mut result17 = _rt::Vec::with_capacity(len17);
+                                                                    for i in 0..len17 {
+                                                                        let base = base17.add(i * 1);
+                                                                        let e17 = {
+                                                                            let l16 = i32::from(*base.add(0).cast::<u8>());
+                                                                            _rt::bool_lift(l16 as u8)
+                                                                        };
+                                                                        result17.push(e17);
+                                                                    }
+                                                                    _rt::cabi_dealloc(base17, len17 * 1, 1);
+                                                                    result17
+                                                                };
+                                                                V50::FlagsValue(e50)
+                                                            }
+                                                            4 => {
+                                                                let e50 = {
+                                                                    let l18 = *base.add(8).cast::<*mut u8>();
+                                                                    let l19 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let len20 = l19;
+                                                                    _rt::Vec::from_raw_parts(l18.cast(), len20, len20)
+                                                                };
+                                                                V50::TupleValue(e50)
+                                                            }
+                                                            5 => {
+                                                                let e50 = {
+                                                                    let l21 = *base.add(8).cast::<*mut u8>();
+                                                                    let l22 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let len23 = l22;
+                                                                    _rt::Vec::from_raw_parts(l21.cast(), len23, len23)
+                                                                };
+                                                                V50::ListValue(e50)
+                                                            }
+                                                            6 => {
+                                                                let e50 = {
+                                                                    let l24 = i32::from(*base.add(8).cast::<u8>());
+                                                                    match l24 {
+                                                                        0 => None,
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l25 = *base.add(12).cast::<i32>();
+                                                                                l25
+                                                                            };
+                                                                            Some(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                V50::OptionValue(e50)
+                                                            }
+                                                            7 => {
+                                                                let e50 = {
+                                                                    let l26 = i32::from(*base.add(8).cast::<u8>());
+                                                                    match l26 {
+                                                                        0 => {
+                                                                            let e = {
+                                                                                let l27 = i32::from(*base.add(12).cast::<u8>());
+                                                                                match l27 {
+                                                                                    0 => None,
+                                                                                    1 => {
+                                                                                        let e = {
+                                                                                            let l28 = *base.add(16).cast::<i32>();
+                                                                                            l28
+                                                                                        };
+                                                                                        Some(e)
+                                                                                    }
+                                                                                    _ => _rt::invalid_enum_discriminant(),
+                                                                                }
+                                                                            };
+                                                                            Ok(e)
+                                                                        }
+                                                                        1 => {
+                                                                            let e = {
+                                                                                let l29 = i32::from(*base.add(12).cast::<u8>());
+                                                                                match l29 {
+                                                                                    0 => None,
+                                                                                    1 => {
+                                                                                        let e = {
+                                                                                            let l30 = *base.add(16).cast::<i32>();
+                                                                                            l30
+                                                                                        };
+                                                                                        Some(e)
+                                                                                    }
+                                                                                    _ => _rt::invalid_enum_discriminant(),
+                                                                                }
+                                                                            };
+                                                                            Err(e)
+                                                                        }
+                                                                        _ => _rt::invalid_enum_discriminant(),
+                                                                    }
+                                                                };
+                                                                V50::ResultValue(e50)
+                                                            }
+                                                            8 => {
+                                                                let e50 = {
+                                                                    let l31 = i32::from(*base.add(8).cast::<u8>());
+                                                                    l31 as u8
+                                                                };
+                                                                V50::PrimU8(e50)
+                                                            }
+                                                            9 => {
+                                                                let e50 = {
+                                                                    let l32 = i32::from(*base.add(8).cast::<u16>());
+                                                                    l32 as u16
+                                                                };
+                                                                V50::PrimU16(e50)
+                                                            }
+                                                            10 => {
+                                                                let e50 = {
+                                                                    let l33 = *base.add(8).cast::<i32>();
+                                                                    l33 as u32
+                                                                };
+                                                                V50::PrimU32(e50)
+                                                            }
+                                                            11 => {
+                                                                let e50 = {
+                                                                    let l34 = *base.add(8).cast::<i64>();
+                                                                    l34 as u64
+                                                                };
+                                                                V50::PrimU64(e50)
+                                                            }
+                                                            12 => {
+                                                                let e50 = {
+                                                                    let l35 = i32::from(*base.add(8).cast::<i8>());
+                                                                    l35 as i8
+                                                                };
+                                                                V50::PrimS8(e50)
+                                                            }
+                                                            13 => {
+                                                                let e50 = {
+                                                                    let l36 = i32::from(*base.add(8).cast::<i16>());
+                                                                    l36 as i16
+                                                                };
+                                                                V50::PrimS16(e50)
+                                                            }
+                                                            14 => {
+                                                                let e50 = {
+                                                                    let l37 = *base.add(8).cast::<i32>();
+                                                                    l37
+                                                                };
+                                                                V50::PrimS32(e50)
+                                                            }
+                                                            15 => {
+                                                                let e50 = {
+                                                                    let l38 = *base.add(8).cast::<i64>();
+                                                                    l38
+                                                                };
+                                                                V50::PrimS64(e50)
+                                                            }
+                                                            16 => {
+                                                                let e50 = {
+                                                                    let l39 = *base.add(8).cast::<f32>();
+                                                                    l39
+                                                                };
+                                                                V50::PrimFloat32(e50)
+                                                            }
+                                                            17 => {
+                                                                let e50 = {
+                                                                    let l40 = *base.add(8).cast::<f64>();
+                                                                    l40
+                                                                };
+                                                                V50::PrimFloat64(e50)
+                                                            }
+                                                            18 => {
+                                                                let e50 = {
+                                                                    let l41 = *base.add(8).cast::<i32>();
+                                                                    _rt::char_lift(l41 as u32)
+                                                                };
+                                                                V50::PrimChar(e50)
+                                                            }
+                                                            19 => {
+                                                                let e50 = {
+                                                                    let l42 = i32::from(*base.add(8).cast::<u8>());
+                                                                    _rt::bool_lift(l42 as u8)
+                                                                };
+                                                                V50::PrimBool(e50)
+                                                            }
+                                                            20 => {
+                                                                let e50 = {
+                                                                    let l43 = *base.add(8).cast::<*mut u8>();
+                                                                    let l44 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let len45 = l44;
+                                                                    let bytes45 = _rt::Vec::from_raw_parts(
+                                                                        l43.cast(),
+                                                                        len45,
+                                                                        len45,
+                                                                    );
+                                                                    _rt::string_lift(bytes45)
+                                                                };
+                                                                V50::PrimString(e50)
+                                                            }
+                                                            n => {
+                                                                debug_assert_eq!(n, 21, "invalid enum discriminant");
+                                                                let e50 = {
+                                                                    let l46 = *base.add(8).cast::<*mut u8>();
+                                                                    let l47 = *base
+                                                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<usize>();
+                                                                    let len48 = l47;
+                                                                    let bytes48 = _rt::Vec::from_raw_parts(
+                                                                        l46.cast(),
+                                                                        len48,
+                                                                        len48,
+                                                                    );
+                                                                    let l49 = *base
+                                                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                                                        .cast::<i64>();
+                                                                    (
+                                                                        super::super::super::golem::rpc0_2_2::types::Uri {
+                                                                            value: _rt::string_lift(bytes48),
+                                                                        },
+                                                                        l49 as u64,
+                                                                    )
+                                                                };
+                                                                V50::Handle(e50)
+                                                            }
+                                                        };
+                                                        v50
+                                                    };
+                                                    result51.push(e51);
+                                                }
+                                                _rt::cabi_dealloc(
+                                                    base51,
+                                                    len51 * (16 + 2 * ::core::mem::size_of::<*const u8>()),
+                                                    8,
+                                                );
+                                                super::super::super::golem::rpc0_2_2::types::WitValue {
+                                                    nodes: result51,
+                                                }
+                                            };
+                                            Ok(e)
+                                        }
+                                        1 => {
+                                            let e = {
+                                                let l52 = i32::from(
+                                                    *ptr0
+                                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                                        .cast::<u8>(),
+                                                );
+                                                let v68 = match l52 {
+                                                    0 => {
+                                                        let e68 = {
+                                                            let l53 = *ptr0
+                                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<*mut u8>();
+                                                            let l54 = *ptr0
+                                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let len55 = l54;
+                                                            let bytes55 = _rt::Vec::from_raw_parts(
+                                                                l53.cast(),
+                                                                len55,
+                                                                len55,
+                                                            );
+                                                            _rt::string_lift(bytes55)
+                                                        };
+                                                        RpcError::ProtocolError(e68)
+                                                    }
+                                                    1 => {
+                                                        let e68 = {
+                                                            let l56 = *ptr0
+                                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<*mut u8>();
+                                                            let l57 = *ptr0
+                                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let len58 = l57;
+                                                            let bytes58 = _rt::Vec::from_raw_parts(
+                                                                l56.cast(),
+                                                                len58,
+                                                                len58,
+                                                            );
+                                                            _rt::string_lift(bytes58)
+                                                        };
+                                                        RpcError::Denied(e68)
+                                                    }
+                                                    2 => {
+                                                        let e68 = {
+                                                            let l59 = *ptr0
+                                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<*mut u8>();
+                                                            let l60 = *ptr0
+                                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let len61 = l60;
+                                                            let bytes61 = _rt::Vec::from_raw_parts(
+                                                                l59.cast(),
+                                                                len61,
+                                                                len61,
+                                                            );
+                                                            _rt::string_lift(bytes61)
+                                                        };
+                                                        RpcError::NotFound(e68)
+                                                    }
+                                                    3 => {
+                                                        let e68 = {
+                                                            let l62 = *ptr0
+                                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<*mut u8>();
+                                                            let l63 = *ptr0
+                                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let len64 = l63;
+                                                            let bytes64 = _rt::Vec::from_raw_parts(
+                                                                l62.cast(),
+                                                                len64,
+                                                                len64,
+                                                            );
+                                                            _rt::string_lift(bytes64)
+                                                        };
+                                                        RpcError::RemoteInternalError(e68)
+                                                    }
+                                                    n => {
+                                                        debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                                        let e68 = {
+                                                            let l65 = *ptr0
+                                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<*mut u8>();
+                                                            let l66 = *ptr0
+                                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                                .cast::<usize>();
+                                                            let len67 = l66;
+                                                            let bytes67 = _rt::Vec::from_raw_parts(
+                                                                l65.cast(),
+                                                                len67,
+                                                                len67,
+                                                            );
+                                                            _rt::string_lift(bytes67)
+                                                        };
+                                                        RpcError::Timeout(e68)
+                                                    }
+                                                };
+                                                v68
+                                            };
+                                            Err(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    }
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        };
+                        result69
+                    }
+                }
+            }
+        }
+    }
 }
 #[rustfmt::skip]
 mod _rt {
@@ -3533,27 +7901,27 @@ mod _rt {
 )]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2482] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb3\x12\x01A\x02\x01\
-A\x08\x01B\x05\x01r\x02\x07secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\x01\
-@\0\0\x01\x04\0\x03now\x01\x02\x04\0\x0aresolution\x01\x02\x03\0\x1cwasi:clocks/\
-wall-clock@0.2.3\x05\0\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04sel\
-f\x01\0\x7f\x04\0\x16[method]pollable.ready\x01\x02\x01@\x01\x04self\x01\x01\0\x04\
-\0\x16[method]pollable.block\x01\x03\x01p\x01\x01py\x01@\x01\x02in\x04\0\x05\x04\
-\0\x04poll\x01\x06\x03\0\x12wasi:io/poll@0.2.3\x05\x01\x02\x03\0\0\x08datetime\x02\
-\x03\0\x01\x08pollable\x01BW\x02\x03\x02\x01\x02\x04\0\x08datetime\x03\0\0\x02\x03\
-\x02\x01\x03\x04\0\x08pollable\x03\0\x02\x01r\x02\x09high-bitsw\x08low-bitsw\x04\
-\0\x04uuid\x03\0\x04\x01r\x01\x04uuid\x05\x04\0\x0ccomponent-id\x03\0\x06\x01r\x02\
-\x0ccomponent-id\x07\x08agent-ids\x04\0\x08agent-id\x03\0\x08\x01r\x01\x04uuid\x05\
-\x04\0\x0aaccount-id\x03\0\x0a\x01z\x04\0\x0anode-index\x03\0\x0c\x01w\x04\0\x0b\
-resource-id\x03\0\x0e\x01m\x02\x05owned\x08borrowed\x04\0\x0dresource-mode\x03\0\
-\x10\x01o\x02s\x0d\x01p\x12\x01k\x0d\x01o\x02s\x14\x01p\x15\x01ps\x01p\x0d\x01o\x02\
-\x14\x14\x01o\x02\x0f\x11\x01q\x16\x0brecord-type\x01\x13\0\x0cvariant-type\x01\x16\
-\0\x09enum-type\x01\x17\0\x0aflags-type\x01\x17\0\x0atuple-type\x01\x18\0\x09lis\
-t-type\x01\x0d\0\x0boption-type\x01\x0d\0\x0bresult-type\x01\x19\0\x0cprim-u8-ty\
-pe\0\0\x0dprim-u16-type\0\0\x0dprim-u32-type\0\0\x0dprim-u64-type\0\0\x0cprim-s8\
--type\0\0\x0dprim-s16-type\0\0\x0dprim-s32-type\0\0\x0dprim-s64-type\0\0\x0dprim\
--f32-type\0\0\x0dprim-f64-type\0\0\x0eprim-char-type\0\0\x0eprim-bool-type\0\0\x10\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 4371] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x94!\x01A\x02\x01A\x19\
+\x01B\x05\x01r\x02\x07secondsw\x0bnanosecondsy\x04\0\x08datetime\x03\0\0\x01@\0\0\
+\x01\x04\0\x03now\x01\x02\x04\0\x0aresolution\x01\x02\x03\0\x1cwasi:clocks/wall-\
+clock@0.2.3\x05\0\x01B\x0a\x04\0\x08pollable\x03\x01\x01h\0\x01@\x01\x04self\x01\
+\0\x7f\x04\0\x16[method]pollable.ready\x01\x02\x01@\x01\x04self\x01\x01\0\x04\0\x16\
+[method]pollable.block\x01\x03\x01p\x01\x01py\x01@\x01\x02in\x04\0\x05\x04\0\x04\
+poll\x01\x06\x03\0\x12wasi:io/poll@0.2.3\x05\x01\x02\x03\0\0\x08datetime\x02\x03\
+\0\x01\x08pollable\x01BW\x02\x03\x02\x01\x02\x04\0\x08datetime\x03\0\0\x02\x03\x02\
+\x01\x03\x04\0\x08pollable\x03\0\x02\x01r\x02\x09high-bitsw\x08low-bitsw\x04\0\x04\
+uuid\x03\0\x04\x01r\x01\x04uuid\x05\x04\0\x0ccomponent-id\x03\0\x06\x01r\x02\x0c\
+component-id\x07\x08agent-ids\x04\0\x08agent-id\x03\0\x08\x01r\x01\x04uuid\x05\x04\
+\0\x0aaccount-id\x03\0\x0a\x01z\x04\0\x0anode-index\x03\0\x0c\x01w\x04\0\x0breso\
+urce-id\x03\0\x0e\x01m\x02\x05owned\x08borrowed\x04\0\x0dresource-mode\x03\0\x10\
+\x01o\x02s\x0d\x01p\x12\x01k\x0d\x01o\x02s\x14\x01p\x15\x01ps\x01p\x0d\x01o\x02\x14\
+\x14\x01o\x02\x0f\x11\x01q\x16\x0brecord-type\x01\x13\0\x0cvariant-type\x01\x16\0\
+\x09enum-type\x01\x17\0\x0aflags-type\x01\x17\0\x0atuple-type\x01\x18\0\x09list-\
+type\x01\x0d\0\x0boption-type\x01\x0d\0\x0bresult-type\x01\x19\0\x0cprim-u8-type\
+\0\0\x0dprim-u16-type\0\0\x0dprim-u32-type\0\0\x0dprim-u64-type\0\0\x0cprim-s8-t\
+ype\0\0\x0dprim-s16-type\0\0\x0dprim-s32-type\0\0\x0dprim-s64-type\0\0\x0dprim-f\
+32-type\0\0\x0dprim-f64-type\0\0\x0eprim-char-type\0\0\x0eprim-bool-type\0\0\x10\
 prim-string-type\0\0\x0bhandle-type\x01\x1a\0\x04\0\x0dwit-type-node\x03\0\x1b\x01\
 ks\x01r\x03\x04name\x1d\x05owner\x1d\x04type\x1c\x04\0\x13named-wit-type-node\x03\
 \0\x1e\x01p\x1f\x01r\x01\x05nodes\x20\x04\0\x08wit-type\x03\0!\x01r\x01\x05value\
@@ -3582,9 +7950,47 @@ d]future-invoke-result.subscribe\x01D\x01k9\x01@\x01\x04self\xc2\0\0\xc5\0\x04\0
 \x20[method]future-invoke-result.get\x01F\x01h4\x01@\x01\x04self\xc7\0\x01\0\x04\
 \0![method]cancellation-token.cancel\x01H\x01j\x01\x05\x01s\x01@\x01\x04uuids\0\xc9\
 \0\x04\0\x0aparse-uuid\x01J\x01@\x01\x04uuid\x05\0s\x04\0\x0euuid-to-string\x01K\
-\x03\0\x15golem:rpc/types@0.2.2\x05\x04\x04\0\x12golem:rpc/wasm-rpc\x04\0\x0b\x0e\
-\x01\0\x08wasm-rpc\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compon\
-ent\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+\x03\0\x15golem:rpc/types@0.2.2\x05\x04\x02\x03\0\x02\x08agent-id\x02\x03\0\x02\x0c\
+component-id\x02\x03\0\x02\x0aaccount-id\x02\x03\0\x02\x04uuid\x02\x03\0\x02\x0a\
+node-index\x02\x03\0\x02\x09wit-value\x02\x03\0\x02\x08wit-node\x02\x03\0\x02\x08\
+wit-type\x02\x03\0\x02\x0bresource-id\x02\x03\0\x02\x0dresource-mode\x02\x03\0\x02\
+\x13named-wit-type-node\x02\x03\0\x02\x0dwit-type-node\x02\x03\0\x02\x0evalue-an\
+d-type\x02\x03\0\x02\x03uri\x02\x03\0\x02\x12cancellation-token\x01BN\x02\x03\x02\
+\x01\x02\x04\0\x08datetime\x03\0\0\x02\x03\x02\x01\x03\x04\0\x08pollable\x03\0\x02\
+\x02\x03\x02\x01\x05\x04\0\x08agent-id\x03\0\x04\x02\x03\x02\x01\x06\x04\0\x0cco\
+mponent-id\x03\0\x06\x02\x03\x02\x01\x07\x04\0\x0aaccount-id\x03\0\x08\x02\x03\x02\
+\x01\x08\x04\0\x04uuid\x03\0\x0a\x02\x03\x02\x01\x09\x04\0\x0anode-index\x03\0\x0c\
+\x02\x03\x02\x01\x0a\x04\0\x09wit-value\x03\0\x0e\x02\x03\x02\x01\x0b\x04\0\x08w\
+it-node\x03\0\x10\x02\x03\x02\x01\x0c\x04\0\x08wit-type\x03\0\x12\x02\x03\x02\x01\
+\x0d\x04\0\x0bresource-id\x03\0\x14\x02\x03\x02\x01\x0e\x04\0\x0dresource-mode\x03\
+\0\x16\x02\x03\x02\x01\x0f\x04\0\x13named-wit-type-node\x03\0\x18\x02\x03\x02\x01\
+\x10\x04\0\x0dwit-type-node\x03\0\x1a\x02\x03\x02\x01\x11\x04\0\x0evalue-and-typ\
+e\x03\0\x1c\x02\x03\x02\x01\x12\x04\0\x03uri\x03\0\x1e\x02\x03\x02\x01\x13\x04\0\
+\x12cancellation-token\x03\0\x20\x01q\x05\x0eprotocol-error\x01s\0\x06denied\x01\
+s\0\x09not-found\x01s\0\x15remote-internal-error\x01s\0\x07timeout\x01s\0\x04\0\x09\
+rpc-error\x03\0\"\x01k\x01\x01r\x02\x0finterval-millisw\x05until$\x04\0\x13inter\
+val-recurrence\x03\0%\x01r\x02\x0aexpressions\x05until$\x04\0\x0fcron-recurrence\
+\x03\0'\x01q\x02\x08interval\x01&\0\x04cron\x01(\0\x04\0\x13schedule-recurrence\x03\
+\0)\x04\0\x08wasm-rpc\x03\x01\x04\0\x14future-invoke-result\x03\x01\x01i+\x01@\x01\
+\x08agent-id\x05\0-\x04\0\x15[constructor]wasm-rpc\x01.\x01h+\x01p\x0f\x01j\x01\x0f\
+\x01#\x01@\x03\x04self/\x0dfunction-names\x0ffunction-params0\01\x04\0![method]w\
+asm-rpc.invoke-and-await\x012\x01@\x04\x04self/\x0dfunction-names\x0ffunction-pa\
+rams0\x0etimeout-millisw\01\x04\0.[method]wasm-rpc.invoke-and-await-with-timeout\
+\x013\x01j\0\x01#\x01@\x03\x04self/\x0dfunction-names\x0ffunction-params0\04\x04\
+\0\x17[method]wasm-rpc.invoke\x015\x01i,\x01@\x03\x04self/\x0dfunction-names\x0f\
+function-params0\06\x04\0'[method]wasm-rpc.async-invoke-and-await\x017\x01@\x04\x04\
+self/\x0dfunction-names\x0ffunction-params0\x0etimeout-millisw\06\x04\04[method]\
+wasm-rpc.async-invoke-and-await-with-timeout\x018\x01@\x04\x04self/\x0escheduled\
+-time\x01\x0dfunction-names\x0ffunction-params0\x01\0\x04\0$[method]wasm-rpc.sch\
+edule-invocation\x019\x01i!\x01@\x04\x04self/\x0escheduled-time\x01\x0dfunction-\
+names\x0ffunction-params0\0:\x04\0/[method]wasm-rpc.schedule-cancelable-invocati\
+on\x01;\x01j\x01:\x01s\x01@\x05\x04self/\x0escheduled-time\x01\x0arecurrence*\x0d\
+function-names\x0ffunction-params0\0<\x04\0.[method]wasm-rpc.schedule-recurring-\
+invocation\x01=\x01h,\x01i\x03\x01@\x01\x04self>\0?\x04\0&[method]future-invoke-\
+result.subscribe\x01@\x01k1\x01@\x01\x04self>\0\xc1\0\x04\0\x20[method]future-in\
+voke-result.get\x01B\x03\0\x15golem:rpc/types@0.2.3\x05\x14\x04\0\x12golem:rpc/w\
+asm-rpc\x04\0\x0b\x0e\x01\0\x08wasm-rpc\x03\0\0\0G\x09producers\x01\x0cprocessed\
+-by\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
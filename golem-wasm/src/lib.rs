@@ -89,7 +89,11 @@ pub use wstd::wasi;
 
 #[cfg(not(feature = "host"))]
 #[cfg(feature = "stub")]
-pub use bindings::golem::rpc0_2_2 as golem_rpc_0_2_x;
+pub use bindings::golem::rpc0_2_3 as golem_rpc_0_2_x;
+
+#[cfg(not(feature = "host"))]
+#[cfg(feature = "stub")]
+pub use bindings::golem::rpc0_2_2 as golem_rpc_0_2_2;
 
 #[cfg(not(feature = "host"))]
 #[cfg(feature = "stub")]
@@ -128,7 +132,10 @@ mod generated {
 pub use generated::wasi;
 
 #[cfg(feature = "host")]
-pub use generated::golem::rpc0_2_2 as golem_rpc_0_2_x;
+pub use generated::golem::rpc0_2_3 as golem_rpc_0_2_x;
+
+#[cfg(feature = "host")]
+pub use generated::golem::rpc0_2_2 as golem_rpc_0_2_2;
 
 #[cfg(feature = "host")]
 pub use golem_rpc_0_2_x::types::{
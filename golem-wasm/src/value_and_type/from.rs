@@ -673,6 +673,7 @@ impl FromValue for crate::RpcError {
                     3 => Ok(crate::RpcError::RemoteInternalError(String::from_value(
                         inner,
                     )?)),
+                    4 => Ok(crate::RpcError::Timeout(String::from_value(inner)?)),
                     _ => Err(format!("Invalid RpcError variant index: {case_idx}")),
                 }
             }
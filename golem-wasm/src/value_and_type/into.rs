@@ -798,6 +798,10 @@ impl IntoValue for crate::RpcError {
                 case_idx: 3,
                 case_value: Some(Box::new(Value::String(value))),
             },
+            crate::RpcError::Timeout(value) => Value::Variant {
+                case_idx: 4,
+                case_value: Some(Box::new(Value::String(value))),
+            },
         }
     }
 
@@ -809,6 +813,7 @@ impl IntoValue for crate::RpcError {
             case("denied", analysed_type::str()),
             case("not-found", analysed_type::str()),
             case("remote-internal-error", analysed_type::str()),
+            case("timeout", analysed_type::str()),
         ])
     }
 }
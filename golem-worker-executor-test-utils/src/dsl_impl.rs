@@ -733,6 +733,7 @@ impl TestDsl for TestWorkerExecutor {
                 environment_id: Some(latest_version.environment_id.into()),
                 data,
                 auth_ctx: Some(self.auth_ctx().into()),
+                rejection: None,
             })
             .await?
             .into_inner();
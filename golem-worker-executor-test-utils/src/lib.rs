@@ -72,7 +72,7 @@ use golem_worker_executor::model::{
     ExecutionStatus, LastError, ReadFileResult, TrapType, WorkerConfig,
 };
 use golem_worker_executor::preview2::golem::durability;
-use golem_worker_executor::preview2::{golem_agent, golem_api_1_x};
+use golem_worker_executor::preview2::{golem_agent, golem_api_1_4, golem_api_1_x};
 use golem_worker_executor::services::active_workers::ActiveWorkers;
 use golem_worker_executor::services::agent_types::AgentTypesService;
 use golem_worker_executor::services::blob_store::BlobStoreService;
@@ -860,6 +860,18 @@ impl HostWasmRpc for TestWorkerCtx {
             .await
     }
 
+    async fn invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
+    ) -> anyhow::Result<Result<WitValue, RpcError>> {
+        self.durable_ctx
+            .invoke_and_await_with_timeout(self_, function_name, function_params, timeout_millis)
+            .await
+    }
+
     async fn invoke(
         &mut self,
         self_: Resource<WasmRpc>,
@@ -882,6 +894,23 @@ impl HostWasmRpc for TestWorkerCtx {
             .await
     }
 
+    async fn async_invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
+    ) -> anyhow::Result<Resource<FutureInvokeResult>> {
+        self.durable_ctx
+            .async_invoke_and_await_with_timeout(
+                self_,
+                function_name,
+                function_params,
+                timeout_millis,
+            )
+            .await
+    }
+
     async fn schedule_invocation(
         &mut self,
         self_: Resource<WasmRpc>,
@@ -906,6 +935,25 @@ impl HostWasmRpc for TestWorkerCtx {
             .await
     }
 
+    async fn schedule_recurring_invocation(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
+        recurrence: golem_wasm::golem_rpc_0_2_x::types::ScheduleRecurrence,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Resource<golem_wasm::golem_rpc_0_2_x::types::CancellationToken>> {
+        self.durable_ctx
+            .schedule_recurring_invocation(
+                self_,
+                datetime,
+                recurrence,
+                function_name,
+                function_params,
+            )
+            .await
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
         HostWasmRpc::drop(&mut self.durable_ctx, rep).await
     }
@@ -1145,11 +1193,13 @@ impl Bootstrap<TestWorkerCtx> for TestServerBootstrap {
     fn create_wasmtime_linker(&self, engine: &Engine) -> anyhow::Result<Linker<TestWorkerCtx>> {
         let mut linker = create_linker(engine, get_durable_ctx)?;
         golem_api_1_x::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
+        golem_api_1_4::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         golem_api_1_x::oplog::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         golem_api_1_x::context::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         durability::durability::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         golem_agent::host::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         golem_wasm::golem_rpc_0_2_x::types::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
+        golem_wasm::golem_rpc_0_2_2::types::add_to_linker_get_host(&mut linker, get_durable_ctx)?;
         Ok(linker)
     }
 }
@@ -0,0 +1,54 @@
+CREATE TABLE index_storage
+(
+    namespace TEXT   NOT NULL,
+    key       TEXT   NOT NULL,
+    id        BIGINT NOT NULL,
+    value     BYTEA  NOT NULL,
+
+    CONSTRAINT index_storage_pk
+        PRIMARY KEY (namespace, key, id)
+);
+
+-- Supports prefix patterns in scan (LIKE 'prefix%') independently of the database collation
+CREATE INDEX index_storage_namespace_key_pattern_idx
+    ON index_storage (namespace, key text_pattern_ops);
+
+CREATE TABLE kv_storage
+(
+    key       TEXT  NOT NULL,
+    value     BYTEA NOT NULL,
+    namespace TEXT  NOT NULL,
+
+    CONSTRAINT kv_storage_pk
+        PRIMARY KEY (key, namespace)
+);
+
+CREATE INDEX kv_storage_namespace_idx
+    ON kv_storage (namespace);
+
+CREATE TABLE set_storage
+(
+    key       TEXT  NOT NULL,
+    value     BYTEA NOT NULL,
+    namespace TEXT  NOT NULL,
+
+    CONSTRAINT set_storage_pk
+        PRIMARY KEY (key, value, namespace)
+);
+
+CREATE INDEX set_storage_key_namespace_idx
+    ON set_storage (key, namespace);
+
+CREATE TABLE sorted_set_storage
+(
+    key       TEXT             NOT NULL,
+    value     BYTEA            NOT NULL,
+    namespace TEXT             NOT NULL,
+    score     DOUBLE PRECISION NOT NULL,
+
+    CONSTRAINT sorted_set_storage_pk
+        PRIMARY KEY (key, value, namespace)
+);
+
+CREATE INDEX sorted_set_storage_key_namespace_score_idx
+    ON sorted_set_storage (key, namespace, score);
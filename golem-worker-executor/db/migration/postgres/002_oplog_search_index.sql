@@ -0,0 +1,37 @@
+CREATE TABLE oplog_search_entries
+(
+    component_id TEXT   NOT NULL,
+    worker_name  TEXT   NOT NULL,
+    oplog_index  BIGINT NOT NULL,
+    timestamp    BIGINT NOT NULL,
+    entry        TEXT   NOT NULL,
+
+    CONSTRAINT oplog_search_entries_pk
+        PRIMARY KEY (component_id, worker_name, oplog_index)
+);
+
+CREATE INDEX oplog_search_entries_component_timestamp_idx
+    ON oplog_search_entries (component_id, timestamp);
+
+CREATE TABLE oplog_search_terms
+(
+    component_id TEXT             NOT NULL,
+    worker_name  TEXT             NOT NULL,
+    oplog_index  BIGINT           NOT NULL,
+    path         TEXT             NOT NULL,
+    value        TEXT             NOT NULL,
+    lower_value  TEXT             NOT NULL,
+    number       DOUBLE PRECISION NULL,
+    instant      BIGINT           NULL,
+
+    CONSTRAINT oplog_search_terms_entry_fk
+        FOREIGN KEY (component_id, worker_name, oplog_index)
+            REFERENCES oplog_search_entries (component_id, worker_name, oplog_index)
+            ON DELETE CASCADE
+);
+
+CREATE INDEX oplog_search_terms_entry_idx
+    ON oplog_search_terms (component_id, worker_name, oplog_index);
+
+CREATE INDEX oplog_search_terms_component_path_idx
+    ON oplog_search_terms (component_id, path);
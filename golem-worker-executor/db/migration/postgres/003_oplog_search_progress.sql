@@ -0,0 +1,10 @@
+-- Last oplog index of each worker whose entries were added to the oplog search index
+CREATE TABLE oplog_search_progress
+(
+    component_id TEXT   NOT NULL,
+    worker_name  TEXT   NOT NULL,
+    last_indexed BIGINT NOT NULL,
+
+    CONSTRAINT oplog_search_progress_pk
+        PRIMARY KEY (component_id, worker_name)
+);
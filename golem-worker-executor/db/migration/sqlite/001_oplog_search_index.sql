@@ -0,0 +1,42 @@
+-- The tables may already exist, created by earlier versions of the executor
+CREATE TABLE IF NOT EXISTS oplog_search_entries
+(
+    component_id TEXT    NOT NULL,
+    worker_name  TEXT    NOT NULL,
+    oplog_index  INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL, -- Milliseconds since the Unix epoch
+    entry        TEXT    NOT NULL, -- The public oplog entry as JSON
+
+    PRIMARY KEY (component_id, worker_name, oplog_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oplog_search_entries_component_timestamp
+    ON oplog_search_entries (component_id, timestamp);
+
+CREATE TABLE IF NOT EXISTS oplog_search_terms
+(
+    component_id TEXT    NOT NULL,
+    worker_name  TEXT    NOT NULL,
+    oplog_index  INTEGER NOT NULL,
+    path         TEXT    NOT NULL, -- Lowercase field path, joined by dots
+    value        TEXT    NOT NULL,
+    lower_value  TEXT    NOT NULL,
+    number       REAL    NULL,     -- The value if it is a number
+    instant      INTEGER NULL      -- The value in milliseconds if it is a date
+);
+
+CREATE INDEX IF NOT EXISTS idx_oplog_search_terms_entry
+    ON oplog_search_terms (component_id, worker_name, oplog_index);
+
+CREATE INDEX IF NOT EXISTS idx_oplog_search_terms_component_path
+    ON oplog_search_terms (component_id, path);
+
+-- Last oplog index of each worker whose entries were added to the oplog search index
+CREATE TABLE IF NOT EXISTS oplog_search_progress
+(
+    component_id TEXT    NOT NULL,
+    worker_name  TEXT    NOT NULL,
+    last_indexed INTEGER NOT NULL,
+
+    PRIMARY KEY (component_id, worker_name)
+);
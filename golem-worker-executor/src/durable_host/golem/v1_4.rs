@@ -0,0 +1,324 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host implementation of the additions of `golem:api/host@1.4.0`.
+//!
+//! The `get-promise-result` and `get-agents` resources are shared with `golem:api/host@1.3.0`, so
+//! `get-promise` and the methods that already existed in the previous version are forwarded to its
+//! implementation.
+
+use crate::durable_host::golem::v1x::{GetAgentsEntry, GetPromiseResultEntry};
+use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx};
+use crate::preview2::golem_api_1_4::host::{
+    AgentAllFilter, AgentAnyFilter, AgentMetadata, AgentPropertyFilter, ComponentId, Host,
+    HostGetAgents, HostGetPromiseResult, PromiseId, PromiseOptions,
+};
+use crate::preview2::{golem_api_1_4, golem_api_1_x, Pollable};
+use crate::workerctx::WorkerCtx;
+use anyhow::anyhow;
+use chrono::Utc;
+use golem_common::model::oplog::host_functions::{
+    GolemApiCreatePromiseWithOptions, GolemApiGetPromiseOutcome, GolemApiRejectPromise,
+};
+use golem_common::model::oplog::{
+    DurableFunctionType, HostRequestGolemApiPromiseOptions, HostRequestGolemApiPromiseRejection,
+    HostRequestNoInput, HostResponseGolemApiPromiseCompletion, HostResponseGolemApiPromiseId,
+    HostResponseGolemApiPromiseOutcome, OplogEntry,
+};
+use golem_common::model::worker::validate_label_key;
+use golem_common::model::{PromiseOutcome, WorkerFilter};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_wasm::analysis::AnalysedType;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use wasmtime::component::Resource;
+use wasmtime_wasi::IoView;
+
+impl<Ctx: WorkerCtx> HostGetAgents for DurableWorkerCtx<Ctx> {
+    async fn new(
+        &mut self,
+        component_id: ComponentId,
+        filter: Option<AgentAnyFilter>,
+        precise: bool,
+    ) -> anyhow::Result<Resource<GetAgentsEntry>> {
+        self.observe_function_call("golem::api::get-workers", "new");
+        let entry = GetAgentsEntry::new(
+            component_id.into(),
+            filter
+                .map(|f| f.try_into())
+                .transpose()
+                .map_err(|e: String| anyhow!(e))?,
+            precise,
+        );
+        let resource = self.table().push(entry)?;
+        Ok(resource)
+    }
+
+    async fn get_next(
+        &mut self,
+        self_: Resource<GetAgentsEntry>,
+    ) -> anyhow::Result<Option<Vec<AgentMetadata>>> {
+        golem_api_1_x::host::HostGetAgents::get_next(self, self_).await
+    }
+
+    async fn drop(&mut self, rep: Resource<GetAgentsEntry>) -> anyhow::Result<()> {
+        golem_api_1_x::host::HostGetAgents::drop(self, rep).await
+    }
+}
+
+impl<Ctx: WorkerCtx> HostGetPromiseResult for DurableWorkerCtx<Ctx> {
+    async fn subscribe(
+        &mut self,
+        resource: Resource<GetPromiseResultEntry>,
+    ) -> anyhow::Result<Resource<Pollable>> {
+        golem_api_1_x::host::HostGetPromiseResult::subscribe(self, resource).await
+    }
+
+    async fn get(
+        &mut self,
+        resource: Resource<GetPromiseResultEntry>,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        golem_api_1_x::host::HostGetPromiseResult::get(self, resource).await
+    }
+
+    async fn get_outcome(
+        &mut self,
+        resource: Resource<GetPromiseResultEntry>,
+    ) -> anyhow::Result<Option<golem_api_1_4::host::PromiseOutcome>> {
+        let durability =
+            Durability::<GolemApiGetPromiseOutcome>::new(self, DurableFunctionType::ReadRemote)
+                .await?;
+
+        let result = if durability.is_live() {
+            let self_worker_id = self.worker_id().clone();
+            let entry = self.table().get(&resource)?;
+
+            // only the agent that originally created the promise is woken up when it is completed.
+            if entry.promise_id.worker_id != self_worker_id {
+                return Err(anyhow!(
+                    "Tried awaiting a promise not created by the current agent"
+                ));
+            }
+
+            let result = entry.get_handle().await.get().await;
+            durability
+                .persist(
+                    self,
+                    HostRequestNoInput {},
+                    HostResponseGolemApiPromiseOutcome { result },
+                )
+                .await
+        } else {
+            durability.replay(self).await
+        }?;
+
+        Ok(result.result.map(|outcome| outcome.into()))
+    }
+
+    async fn drop(&mut self, resource: Resource<GetPromiseResultEntry>) -> anyhow::Result<()> {
+        golem_api_1_x::host::HostGetPromiseResult::drop(self, resource).await
+    }
+}
+
+impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
+    async fn create_promise_with_options(
+        &mut self,
+        options: PromiseOptions,
+    ) -> anyhow::Result<PromiseId> {
+        let durability = Durability::<GolemApiCreatePromiseWithOptions>::new(
+            self,
+            DurableFunctionType::WriteLocal,
+        )
+        .await?;
+
+        let result = if durability.is_live() {
+            let payload_type: Option<AnalysedType> = options.payload_type.map(|typ| typ.into());
+            let oplog_idx = self.state.current_oplog_index().await.next();
+            let promise_id = self
+                .public_state
+                .promise_service
+                .create(
+                    &self.owned_worker_id.worker_id,
+                    oplog_idx,
+                    payload_type.clone(),
+                )
+                .await;
+
+            durability
+                .persist(
+                    self,
+                    HostRequestGolemApiPromiseOptions {
+                        timeout_in_nanos: options.timeout,
+                        payload_type,
+                    },
+                    HostResponseGolemApiPromiseId { promise_id },
+                )
+                .await?
+        } else {
+            durability.replay(self).await?
+        };
+
+        // The expiration is only scheduled once the promise's creation is persisted. It is also
+        // requested when replaying, in case the executor stopped before it could be scheduled;
+        // already completed promises and already scheduled expirations are left untouched.
+        if let Some(timeout) = options.timeout {
+            let expires_at = Utc::now() + Duration::from_nanos(timeout);
+            self.public_state
+                .promise_service
+                .schedule_expiry(
+                    &result.promise_id,
+                    expires_at,
+                    self.created_by(),
+                    self.owned_worker_id.environment_id(),
+                )
+                .await?;
+        }
+
+        Ok(result.promise_id.into())
+    }
+
+    async fn get_promise(
+        &mut self,
+        promise_id: PromiseId,
+    ) -> anyhow::Result<Resource<GetPromiseResultEntry>> {
+        golem_api_1_x::host::Host::get_promise(self, promise_id).await
+    }
+
+    async fn reject_promise(
+        &mut self,
+        promise_id: PromiseId,
+        error: String,
+    ) -> anyhow::Result<bool> {
+        let durability =
+            Durability::<GolemApiRejectPromise>::new(self, DurableFunctionType::WriteLocal).await?;
+
+        let promise_id: golem_common::model::PromiseId = promise_id.into();
+        let result = if durability.is_live() {
+            let promise_completion_result = self
+                .resolve_promise(promise_id.clone(), PromiseOutcome::Rejected(error.clone()))
+                .await?;
+
+            durability
+                .persist(
+                    self,
+                    HostRequestGolemApiPromiseRejection { promise_id, error },
+                    HostResponseGolemApiPromiseCompletion {
+                        completed: promise_completion_result,
+                    },
+                )
+                .await
+        } else {
+            durability.replay(self).await
+        }?;
+
+        Ok(result.completed)
+    }
+
+    async fn set_label(&mut self, key: String, value: String) -> anyhow::Result<()> {
+        self.observe_function_call("golem::api", "set_label");
+        validate_label_key(&key).map_err(WorkerExecutorError::invalid_request)?;
+        // Labels are stored in hint entries only, nothing to replay
+        if self.state.is_live() {
+            self.state
+                .oplog
+                .add(OplogEntry::change_labels(
+                    BTreeMap::from([(key, value)]),
+                    BTreeSet::new(),
+                ))
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn remove_label(&mut self, key: String) -> anyhow::Result<()> {
+        self.observe_function_call("golem::api", "remove_label");
+        validate_label_key(&key).map_err(WorkerExecutorError::invalid_request)?;
+        if self.state.is_live() {
+            self.state
+                .oplog
+                .add(OplogEntry::change_labels(
+                    BTreeMap::new(),
+                    BTreeSet::from([key]),
+                ))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+impl From<PromiseOutcome> for golem_api_1_4::host::PromiseOutcome {
+    fn from(outcome: PromiseOutcome) -> Self {
+        match outcome {
+            PromiseOutcome::Completed(data) => Self::Completed(data),
+            PromiseOutcome::Rejected(error) => Self::Rejected(error),
+            PromiseOutcome::Expired => Self::Expired,
+        }
+    }
+}
+
+impl TryFrom<AgentPropertyFilter> for WorkerFilter {
+    type Error = String;
+
+    fn try_from(filter: AgentPropertyFilter) -> Result<Self, Self::Error> {
+        use golem_api_1_x::host::AgentPropertyFilter as PreviousAgentPropertyFilter;
+
+        match filter {
+            AgentPropertyFilter::Name(filter) => {
+                PreviousAgentPropertyFilter::Name(filter).try_into()
+            }
+            AgentPropertyFilter::Status(filter) => {
+                PreviousAgentPropertyFilter::Status(filter).try_into()
+            }
+            AgentPropertyFilter::Version(filter) => {
+                PreviousAgentPropertyFilter::Version(filter).try_into()
+            }
+            AgentPropertyFilter::CreatedAt(filter) => {
+                PreviousAgentPropertyFilter::CreatedAt(filter).try_into()
+            }
+            AgentPropertyFilter::Env(filter) => PreviousAgentPropertyFilter::Env(filter).try_into(),
+            AgentPropertyFilter::WasiConfigVars(filter) => {
+                PreviousAgentPropertyFilter::WasiConfigVars(filter).try_into()
+            }
+            AgentPropertyFilter::Label(filter) => Ok(WorkerFilter::new_label(
+                filter.name,
+                filter.comparator.into(),
+                filter.value,
+            )),
+        }
+    }
+}
+
+impl TryFrom<AgentAllFilter> for WorkerFilter {
+    type Error = String;
+    fn try_from(filter: AgentAllFilter) -> Result<Self, Self::Error> {
+        let filters = filter
+            .filters
+            .into_iter()
+            .map(|f| f.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(WorkerFilter::new_and(filters))
+    }
+}
+
+impl TryFrom<AgentAnyFilter> for WorkerFilter {
+    type Error = String;
+    fn try_from(filter: AgentAnyFilter) -> Result<Self, Self::Error> {
+        let filters = filter
+            .filters
+            .into_iter()
+            .map(|f| f.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(WorkerFilter::new_or(filters))
+    }
+}
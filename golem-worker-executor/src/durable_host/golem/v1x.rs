@@ -51,7 +51,7 @@ use golem_common::model::oplog::{
     HostResponseGolemApiUnit, OplogEntry,
 };
 use golem_common::model::regions::OplogRegion;
-use golem_common::model::{IdempotencyKey, OplogIndex, PromiseId, RetryConfig};
+use golem_common::model::{IdempotencyKey, OplogIndex, PromiseId, PromiseOutcome, RetryConfig};
 use golem_common::model::{OwnedWorkerId, ScanCursor, WorkerId};
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
 use std::sync::Arc;
@@ -143,7 +143,7 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
             let promise_id = self
                 .public_state
                 .promise_service
-                .create(&self.owned_worker_id.worker_id, oplog_idx)
+                .create(&self.owned_worker_id.worker_id, oplog_idx, None)
                 .await;
             durability
                 .persist(
@@ -179,27 +179,9 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
 
         let promise_id: PromiseId = promise_id.into();
         let result = if durability.is_live() {
-            // A promise must be completed on the instance that is owning the agent that originally created here.
-            let worker_id = &promise_id.worker_id;
-
-            let is_local_worker = match self.state.shard_service.check_worker(worker_id) {
-                Ok(()) => true,
-                Err(WorkerExecutorError::InvalidShardId { .. }) => false,
-                Err(other) => Err(other)?,
-            };
-
-            let promise_completion_result = if is_local_worker {
-                self.public_state
-                    .promise_service
-                    .complete(promise_id.clone(), data, self.created_by())
-                    .await?
-            } else {
-                // talk to the executor that actually owns the promise
-                self.state
-                    .worker_proxy
-                    .complete_promise(promise_id.clone(), data, self.created_by())
-                    .await?
-            };
+            let promise_completion_result = self
+                .resolve_promise(promise_id.clone(), PromiseOutcome::Completed(data))
+                .await?;
 
             durability
                 .persist(
@@ -976,7 +958,15 @@ impl<Ctx: WorkerCtx> HostGetPromiseResult for DurableWorkerCtx<Ctx> {
                 ));
             }
 
-            let result = entry.get_handle().await.get().await;
+            let result = entry
+                .get_handle()
+                .await
+                .get()
+                .await
+                .map(|outcome| match outcome {
+                    PromiseOutcome::Completed(data) => data,
+                    PromiseOutcome::Rejected(_) | PromiseOutcome::Expired => vec![],
+                });
             durability
                 .persist(
                     self,
@@ -1134,6 +1124,36 @@ impl<Ctx: WorkerCtx> HostSearchOplog for DurableWorkerCtx<Ctx> {
 }
 
 impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Completes or rejects a promise on the instance that is owning the agent that originally created it
+    pub(crate) async fn resolve_promise(
+        &self,
+        promise_id: PromiseId,
+        outcome: PromiseOutcome,
+    ) -> anyhow::Result<bool> {
+        let worker_id = &promise_id.worker_id;
+
+        let is_local_worker = match self.state.shard_service.check_worker(worker_id) {
+            Ok(()) => true,
+            Err(WorkerExecutorError::InvalidShardId { .. }) => false,
+            Err(other) => Err(other)?,
+        };
+
+        if is_local_worker {
+            Ok(self
+                .public_state
+                .promise_service
+                .complete(promise_id, outcome, self.created_by())
+                .await?)
+        } else {
+            // talk to the executor that actually owns the promise
+            Ok(self
+                .state
+                .worker_proxy
+                .complete_promise(promise_id, outcome, self.created_by())
+                .await?)
+        }
+    }
+
     async fn resolve_agent_id_strict_internal(
         &self,
         component_slug: String,
@@ -1476,7 +1496,7 @@ impl GetAgentsEntry {
 
 #[derive(Clone)]
 pub struct GetPromiseResultEntry {
-    promise_id: PromiseId,
+    pub(crate) promise_id: PromiseId,
     promise_service: Arc<dyn PromiseService>,
     handle: Arc<OnceCell<PromiseHandle>>,
 }
@@ -23,6 +23,7 @@ use wasmtime::component::Resource;
 use wasmtime_wasi::IoView;
 
 use crate::durable_host::keyvalue::error::ErrorEntry;
+use crate::durable_host::keyvalue::handle_watch::KeyValueChange;
 use crate::durable_host::keyvalue::types::{BucketEntry, IncomingValueEntry, OutgoingValueEntry};
 use crate::durable_host::{Durability, DurableWorkerCtx};
 use crate::preview2::wasi::keyvalue::eventual::{
@@ -113,6 +114,10 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 key: key.clone(),
                 length: outgoing_value.len(),
             };
+            let changes = vec![KeyValueChange::Set {
+                key: key.clone(),
+                value: outgoing_value.clone(),
+            }];
             let result = self
                 .state
                 .key_value_service
@@ -120,6 +125,9 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 .await
                 .map_err(|err| err.to_string());
             durability.try_trigger_retry(self, &result).await?;
+            if result.is_ok() {
+                self.notify_bucket_watchers(&input.bucket, changes).await?;
+            }
             durability
                 .persist(self, input, HostResponseKVUnit { result })
                 .await
@@ -158,6 +166,7 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 bucket: bucket.clone(),
                 key: key.clone(),
             };
+            let changes = vec![KeyValueChange::Delete { key: key.clone() }];
             let result = self
                 .state
                 .key_value_service
@@ -165,6 +174,9 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 .await
                 .map_err(|err| err.to_string());
             durability.try_trigger_retry(self, &result).await?;
+            if result.is_ok() {
+                self.notify_bucket_watchers(&input.bucket, changes).await?;
+            }
             durability
                 .persist(self, input, HostResponseKVUnit { result })
                 .await
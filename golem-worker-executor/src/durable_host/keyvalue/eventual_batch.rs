@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::durable_host::keyvalue::error::ErrorEntry;
+use crate::durable_host::keyvalue::handle_watch::KeyValueChange;
 use crate::durable_host::keyvalue::types::{BucketEntry, IncomingValueEntry, OutgoingValueEntry};
 use crate::durable_host::{Durability, DurableWorkerCtx};
 use crate::preview2::wasi::keyvalue::eventual_batch::{
@@ -174,6 +175,13 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                     .map(|(k, v)| (k.clone(), v.len()))
                     .collect(),
             };
+            let changes = key_values
+                .iter()
+                .map(|(key, value)| KeyValueChange::Set {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect();
             let result = self
                 .state
                 .key_value_service
@@ -181,6 +189,9 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 .await
                 .map_err(|err| err.to_string());
             durability.try_trigger_retry(self, &result).await?;
+            if result.is_ok() {
+                self.notify_bucket_watchers(&input.bucket, changes).await?;
+            }
             durability
                 .persist(self, input, HostResponseKVUnit { result })
                 .await
@@ -221,6 +232,10 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 bucket: bucket.clone(),
                 keys: keys.clone(),
             };
+            let changes = keys
+                .iter()
+                .map(|key| KeyValueChange::Delete { key: key.clone() })
+                .collect();
             let result = self
                 .state
                 .key_value_service
@@ -228,6 +243,9 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
                 .await
                 .map_err(|err| err.to_string());
             durability.try_trigger_retry(self, &result).await?;
+            if result.is_ok() {
+                self.notify_bucket_watchers(&input.bucket, changes).await?;
+            }
             durability
                 .persist(self, input, HostResponseKVUnit { result })
                 .await
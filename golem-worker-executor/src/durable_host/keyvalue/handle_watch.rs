@@ -0,0 +1,589 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Support for the exported `wasi:keyvalue/handle-watch` interface.
+//!
+//! Agents whose component exports `handle-watch` are registered as watchers of every bucket they
+//! open. Writes through `eventual` and `eventual-batch` are delivered to the watchers as regular
+//! invocations of `on-set` and `on-delete`, so the received notifications are recorded in the
+//! watchers' oplog and replayed exactly as they were observed.
+//!
+//! Resources cannot be passed between agents, so the notifications are sent with the bucket's
+//! name and the value's bytes, and the receiving side recreates the `bucket` and `incoming-value`
+//! resources before calling the exported function.
+
+use crate::durable_host::keyvalue::types::{BucketEntry, IncomingValueEntry};
+use crate::durable_host::DurableWorkerCtx;
+use crate::model::WorkerConfig;
+use crate::services::key_value::KeyValueService;
+use crate::services::rpc::Rpc;
+use crate::workerctx::{HasWasiConfigVars, InvocationManagement, WorkerCtx};
+use futures::future::join_all;
+use golem_common::model::account::AccountId;
+use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::{IdempotencyKey, OwnedWorkerId};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_wasm::wasmtime::DecodeParamResult;
+use golem_wasm::{Value, WitValue};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn, Instrument};
+use wasmtime::component::Val;
+use wasmtime::AsContextMut;
+use wasmtime_wasi::p2::bindings::cli::environment::Host;
+use wasmtime_wasi::IoView;
+
+pub const INTERFACE_NAME: &str = "wasi:keyvalue/handle-watch@0.1.0";
+pub const ON_SET_FUNCTION_NAME: &str = "wasi:keyvalue/handle-watch@0.1.0.{on-set}";
+pub const ON_DELETE_FUNCTION_NAME: &str = "wasi:keyvalue/handle-watch@0.1.0.{on-delete}";
+
+/// A single change of a bucket to be delivered to its watchers
+pub enum KeyValueChange {
+    Set { key: String, value: Vec<u8> },
+    Delete { key: String },
+}
+
+impl KeyValueChange {
+    fn function_name(&self) -> &'static str {
+        match self {
+            KeyValueChange::Set { .. } => ON_SET_FUNCTION_NAME,
+            KeyValueChange::Delete { .. } => ON_DELETE_FUNCTION_NAME,
+        }
+    }
+
+    fn into_function_params(self, bucket: &str) -> Vec<WitValue> {
+        match self {
+            KeyValueChange::Set { key, value } => vec![
+                Value::String(bucket.to_string()).into(),
+                Value::String(key).into(),
+                Value::List(value.into_iter().map(Value::U8).collect()).into(),
+            ],
+            KeyValueChange::Delete { key } => vec![
+                Value::String(bucket.to_string()).into(),
+                Value::String(key).into(),
+            ],
+        }
+    }
+}
+
+/// Decodes the input of an `on-set` or `on-delete` invocation, pushing the `bucket` and
+/// `incoming-value` resources to the receiving worker's resource table.
+///
+/// The bucket is owned by the guest after the call, while the borrowed incoming value is
+/// returned as a resource to be dropped once the invocation finishes.
+pub fn decode_handler_params<Ctx: WorkerCtx>(
+    store: &mut impl AsContextMut<Data = Ctx>,
+    raw_function_name: &str,
+    function_input: &[Value],
+) -> Result<Vec<DecodeParamResult>, WorkerExecutorError> {
+    let mut store = store.as_context_mut();
+
+    let (bucket, key, value) = match function_input {
+        [Value::String(bucket), Value::String(key)] => (bucket, key, None),
+        [Value::String(bucket), Value::String(key), Value::List(bytes)] => {
+            let value = bytes
+                .iter()
+                .map(|byte| match byte {
+                    Value::U8(byte) => Ok(*byte),
+                    _ => Err(WorkerExecutorError::ValueMismatch {
+                        details: format!(
+                            "unexpected incoming value element for {raw_function_name}"
+                        ),
+                    }),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            (bucket, key, Some(value))
+        }
+        _ => {
+            return Err(WorkerExecutorError::ValueMismatch {
+                details: format!("unexpected function input for {raw_function_name}"),
+            })
+        }
+    };
+
+    let bucket = store
+        .data_mut()
+        .as_wasi_view()
+        .table()
+        .push(BucketEntry::new(bucket.clone()))
+        .map_err(|err| WorkerExecutorError::runtime(err.to_string()))?;
+    let bucket = bucket
+        .try_into_resource_any(&mut store)
+        .map_err(|err| WorkerExecutorError::runtime(err.to_string()))?;
+
+    let mut results = vec![
+        DecodeParamResult::simple(Val::Resource(bucket)),
+        DecodeParamResult::simple(Val::String(key.clone())),
+    ];
+
+    if let Some(value) = value {
+        let incoming_value = store
+            .data_mut()
+            .as_wasi_view()
+            .table()
+            .push(IncomingValueEntry::new(value))
+            .map_err(|err| WorkerExecutorError::runtime(err.to_string()))?;
+        let incoming_value = incoming_value
+            .try_into_resource_any(&mut store)
+            .map_err(|err| WorkerExecutorError::runtime(err.to_string()))?;
+        results.push(DecodeParamResult {
+            val: Val::Resource(incoming_value),
+            resources_to_drop: vec![incoming_value],
+        });
+    }
+
+    Ok(results)
+}
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Registers the worker as a watcher of the given bucket if its component exports the
+    /// `handle-watch` interface. Registration is idempotent, and it is only performed in live
+    /// mode, as replayed workers have already been registered.
+    pub(crate) async fn watch_bucket_if_supported(&self, bucket: &str) -> anyhow::Result<()> {
+        if !self.state.is_live() {
+            return Ok(());
+        }
+
+        let exports_handler = matches!(
+            self.component_metadata()
+                .metadata
+                .find_function(ON_SET_FUNCTION_NAME),
+            Ok(Some(_))
+        );
+        if exports_handler {
+            self.state
+                .key_value_service
+                .watch(&self.owned_worker_id, bucket.to_string())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Queues the changes of a bucket to be delivered to all the other workers watching it.
+    ///
+    /// Must be called in live mode only, before persisting the result of the host function
+    /// performing the changes. Notifications are sent with idempotency keys derived from the
+    /// current invocation and oplog position, so if the writer gets retried before its oplog
+    /// is committed, the watchers do not observe the same change twice.
+    pub(crate) async fn notify_bucket_watchers(
+        &mut self,
+        bucket: &str,
+        changes: Vec<KeyValueChange>,
+    ) -> anyhow::Result<()> {
+        let mut env = self.get_environment().await?;
+        WorkerConfig::remove_dynamic_vars(&mut env);
+
+        let current_idempotency_key = self
+            .get_current_idempotency_key()
+            .await
+            .unwrap_or(IdempotencyKey::fresh());
+        let oplog_index = self.state.oplog.current_oplog_index().await;
+
+        let notification = BucketNotification {
+            bucket: bucket.to_string(),
+            changes: changes
+                .into_iter()
+                .enumerate()
+                .map(|(idx, change)| {
+                    let idempotency_key = IdempotencyKey::derived_for_change(
+                        &current_idempotency_key,
+                        oplog_index,
+                        idx,
+                    );
+                    (idempotency_key, change)
+                })
+                .collect(),
+            created_by: self.created_by(),
+            env,
+            wasi_config_vars: self.wasi_config_vars(),
+            stack: self.get_current_invocation_context().await,
+        };
+
+        self.state.bucket_watch_notifier.notify(notification).await;
+        Ok(())
+    }
+}
+
+/// Number of notifications a writer can queue before it has to wait for their delivery
+const NOTIFICATION_BUFFER: usize = 64;
+
+/// The changes of a bucket made by a single host call, with the context of the writer
+struct BucketNotification {
+    bucket: String,
+    changes: Vec<(IdempotencyKey, KeyValueChange)>,
+    created_by: AccountId,
+    env: Vec<(String, String)>,
+    wasi_config_vars: BTreeMap<String, String>,
+    stack: InvocationContextStack,
+}
+
+/// Delivers the changes made by a worker to the watchers of the changed buckets in the background.
+///
+/// The writer only queues the changes, so its host call does not wait for looking up and invoking
+/// the watchers. A single task per writer delivers the notifications in the order the changes were
+/// made, and it stops once the writer is dropped and the queue is drained.
+pub struct BucketWatchNotifier {
+    owned_worker_id: OwnedWorkerId,
+    key_value_service: Arc<dyn KeyValueService>,
+    rpc: Arc<dyn Rpc>,
+    sender: Option<mpsc::Sender<BucketNotification>>,
+}
+
+impl BucketWatchNotifier {
+    pub fn new(
+        owned_worker_id: OwnedWorkerId,
+        key_value_service: Arc<dyn KeyValueService>,
+        rpc: Arc<dyn Rpc>,
+    ) -> Self {
+        Self {
+            owned_worker_id,
+            key_value_service,
+            rpc,
+            sender: None,
+        }
+    }
+
+    async fn notify(&mut self, notification: BucketNotification) {
+        let sender = match &self.sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let sender = self.spawn_delivery();
+                self.sender = Some(sender.clone());
+                sender
+            }
+        };
+
+        if sender.send(notification).await.is_err() {
+            warn!(
+                "Bucket change notifications of {} are no longer delivered",
+                self.owned_worker_id.worker_id
+            );
+        }
+    }
+
+    fn spawn_delivery(&self) -> mpsc::Sender<BucketNotification> {
+        let (sender, mut receiver) = mpsc::channel(NOTIFICATION_BUFFER);
+        let owned_worker_id = self.owned_worker_id.clone();
+        let key_value_service = self.key_value_service.clone();
+        let rpc = self.rpc.clone();
+
+        tokio::spawn(
+            async move {
+                while let Some(notification) = receiver.recv().await {
+                    deliver(&owned_worker_id, &key_value_service, &rpc, notification).await;
+                }
+            }
+            .in_current_span(),
+        );
+
+        sender
+    }
+}
+
+/// Sends each change to all the watchers of the bucket concurrently, waiting for every watcher
+/// to accept a change before sending the next one
+async fn deliver(
+    owned_worker_id: &OwnedWorkerId,
+    key_value_service: &Arc<dyn KeyValueService>,
+    rpc: &Arc<dyn Rpc>,
+    notification: BucketNotification,
+) {
+    let bucket = &notification.bucket;
+    let watchers: Vec<OwnedWorkerId> = match key_value_service
+        .get_watchers(owned_worker_id.environment_id(), bucket.clone())
+        .await
+    {
+        Ok(watchers) => watchers
+            .into_iter()
+            .filter(|watcher| watcher != owned_worker_id)
+            .collect(),
+        Err(err) => {
+            warn!("Failed to get the watchers of bucket {bucket}: {err}");
+            return;
+        }
+    };
+
+    if watchers.is_empty() {
+        return;
+    }
+
+    let created_by = notification.created_by;
+    for (idempotency_key, change) in notification.changes {
+        let function_name = change.function_name();
+        let function_params = change.into_function_params(bucket);
+
+        join_all(watchers.iter().map(|watcher| {
+            let idempotency_key = idempotency_key.clone();
+            let function_params = function_params.clone();
+            let env = &notification.env;
+            let wasi_config_vars = notification.wasi_config_vars.clone();
+            let stack = notification.stack.clone();
+            async move {
+                debug!(
+                    "Notifying {} about a change of bucket {bucket} using {function_name}",
+                    watcher.worker_id
+                );
+                if let Err(err) = rpc
+                    .invoke(
+                        watcher,
+                        Some(idempotency_key),
+                        function_name.to_string(),
+                        function_params,
+                        created_by,
+                        &owned_worker_id.worker_id,
+                        env,
+                        wasi_config_vars,
+                        stack,
+                    )
+                    .await
+                {
+                    warn!(
+                        "Failed to notify {} about a change of bucket {bucket}: {err}",
+                        watcher.worker_id
+                    );
+                }
+            }
+        }))
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BucketNotification, BucketWatchNotifier, KeyValueChange, ON_DELETE_FUNCTION_NAME,
+        ON_SET_FUNCTION_NAME,
+    };
+    use crate::services::key_value::{DefaultKeyValueService, KeyValueService};
+    use crate::services::rpc::{Rpc, RpcDemand, RpcError};
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+    use async_trait::async_trait;
+    use golem_common::model::account::AccountId;
+    use golem_common::model::component::ComponentId;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::invocation_context::InvocationContextStack;
+    use golem_common::model::{IdempotencyKey, OwnedWorkerId, WorkerId};
+    use golem_wasm::{ValueAndType, WitValue};
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use test_r::test;
+    use tokio::sync::Semaphore;
+    use uuid::Uuid;
+
+    /// Records the received invocations, each one waiting for a permit of the gate first
+    struct RecordingRpc {
+        gate: Semaphore,
+        invocations: Mutex<Vec<(String, IdempotencyKey, String)>>,
+    }
+
+    impl RecordingRpc {
+        fn new(open: bool) -> Self {
+            Self {
+                gate: Semaphore::new(if open { Semaphore::MAX_PERMITS } else { 0 }),
+                invocations: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn invocations(&self) -> Vec<(String, IdempotencyKey, String)> {
+            self.invocations.lock().unwrap().clone()
+        }
+
+        async fn wait_for_invocations(
+            &self,
+            count: usize,
+        ) -> Vec<(String, IdempotencyKey, String)> {
+            for _ in 0..100 {
+                let invocations = self.invocations();
+                if invocations.len() >= count {
+                    return invocations;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            panic!("Expected {count} invocations, got {:?}", self.invocations());
+        }
+    }
+
+    #[async_trait]
+    impl Rpc for RecordingRpc {
+        async fn create_demand(
+            &self,
+            _owned_worker_id: &OwnedWorkerId,
+            _self_created_by: AccountId,
+            _self_worker_id: &WorkerId,
+            _self_env: &[(String, String)],
+            _self_config: BTreeMap<String, String>,
+            _self_stack: InvocationContextStack,
+        ) -> Result<Box<dyn RpcDemand>, RpcError> {
+            unimplemented!()
+        }
+
+        async fn invoke_and_await(
+            &self,
+            _owned_worker_id: &OwnedWorkerId,
+            _idempotency_key: Option<IdempotencyKey>,
+            _function_name: String,
+            _function_params: Vec<WitValue>,
+            _self_created_by: AccountId,
+            _self_worker_id: &WorkerId,
+            _self_env: &[(String, String)],
+            _self_config: BTreeMap<String, String>,
+            _self_stack: InvocationContextStack,
+        ) -> Result<Option<ValueAndType>, RpcError> {
+            unimplemented!()
+        }
+
+        async fn invoke(
+            &self,
+            owned_worker_id: &OwnedWorkerId,
+            idempotency_key: Option<IdempotencyKey>,
+            function_name: String,
+            _function_params: Vec<WitValue>,
+            _self_created_by: AccountId,
+            _self_worker_id: &WorkerId,
+            _self_env: &[(String, String)],
+            _self_config: BTreeMap<String, String>,
+            _self_stack: InvocationContextStack,
+        ) -> Result<(), RpcError> {
+            self.gate.acquire().await.unwrap().forget();
+            self.invocations.lock().unwrap().push((
+                owned_worker_id.worker_id.worker_name.clone(),
+                idempotency_key.unwrap(),
+                function_name,
+            ));
+            Ok(())
+        }
+    }
+
+    fn owned_worker_id(environment_id: EnvironmentId, name: &str) -> OwnedWorkerId {
+        OwnedWorkerId::new(
+            environment_id,
+            &WorkerId {
+                component_id: ComponentId(Uuid::new_v4()),
+                worker_name: name.to_string(),
+            },
+        )
+    }
+
+    fn notification(changes: Vec<(IdempotencyKey, KeyValueChange)>) -> BucketNotification {
+        BucketNotification {
+            bucket: "bucket".to_string(),
+            changes,
+            created_by: AccountId::new(),
+            env: vec![],
+            wasi_config_vars: BTreeMap::new(),
+            stack: InvocationContextStack::fresh(),
+        }
+    }
+
+    async fn setup(rpc: Arc<RecordingRpc>) -> BucketWatchNotifier {
+        let environment_id = EnvironmentId::new();
+        let writer = owned_worker_id(environment_id, "writer");
+        let key_value_service = Arc::new(DefaultKeyValueService::new(Arc::new(
+            InMemoryKeyValueStorage::new(),
+        )));
+        for watcher in [
+            writer.clone(),
+            owned_worker_id(environment_id, "watcher1"),
+            owned_worker_id(environment_id, "watcher2"),
+        ] {
+            key_value_service
+                .watch(&watcher, "bucket".to_string())
+                .await
+                .unwrap();
+        }
+        BucketWatchNotifier::new(writer, key_value_service, rpc)
+    }
+
+    #[test]
+    async fn changes_are_delivered_in_order_to_the_other_watchers() {
+        let rpc = Arc::new(RecordingRpc::new(true));
+        let mut notifier = setup(rpc.clone()).await;
+
+        let key1 = IdempotencyKey::fresh();
+        let key2 = IdempotencyKey::fresh();
+        let key3 = IdempotencyKey::fresh();
+        notifier
+            .notify(notification(vec![
+                (
+                    key1.clone(),
+                    KeyValueChange::Set {
+                        key: "a".to_string(),
+                        value: vec![1, 2, 3],
+                    },
+                ),
+                (
+                    key2.clone(),
+                    KeyValueChange::Delete {
+                        key: "b".to_string(),
+                    },
+                ),
+            ]))
+            .await;
+        notifier
+            .notify(notification(vec![(
+                key3.clone(),
+                KeyValueChange::Delete {
+                    key: "a".to_string(),
+                },
+            )]))
+            .await;
+
+        let invocations = rpc.wait_for_invocations(6).await;
+        assert_eq!(invocations.len(), 6);
+        assert!(invocations.iter().all(|(worker, _, _)| worker != "writer"));
+
+        for watcher in ["watcher1", "watcher2"] {
+            let received: Vec<_> = invocations
+                .iter()
+                .filter(|(worker, _, _)| worker == watcher)
+                .map(|(_, key, function)| (key.clone(), function.clone()))
+                .collect();
+            assert_eq!(
+                received,
+                vec![
+                    (key1.clone(), ON_SET_FUNCTION_NAME.to_string()),
+                    (key2.clone(), ON_DELETE_FUNCTION_NAME.to_string()),
+                    (key3.clone(), ON_DELETE_FUNCTION_NAME.to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    async fn writer_does_not_wait_for_the_watchers() {
+        let rpc = Arc::new(RecordingRpc::new(false));
+        let mut notifier = setup(rpc.clone()).await;
+
+        // None of the watchers accept the invocation until the gate opens, notifying must
+        // still return immediately
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            notifier.notify(notification(vec![(
+                IdempotencyKey::fresh(),
+                KeyValueChange::Delete {
+                    key: "a".to_string(),
+                },
+            )])),
+        )
+        .await
+        .expect("Notifying the watchers blocked the writer");
+        assert!(rpc.invocations().is_empty());
+
+        rpc.gate.add_permits(2);
+        let invocations = rpc.wait_for_invocations(2).await;
+        assert_eq!(invocations.len(), 2);
+    }
+}
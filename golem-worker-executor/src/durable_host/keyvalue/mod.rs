@@ -17,4 +17,5 @@ pub mod caching;
 pub mod error;
 pub mod eventual;
 pub mod eventual_batch;
+pub mod handle_watch;
 pub mod types;
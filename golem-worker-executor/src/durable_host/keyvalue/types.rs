@@ -34,6 +34,7 @@ impl<Ctx: WorkerCtx> HostBucket for DurableWorkerCtx<Ctx> {
         name: String,
     ) -> anyhow::Result<Result<Resource<BucketEntry>, Resource<Error>>> {
         self.observe_function_call("keyvalue::types::bucket", "open");
+        self.watch_bucket_if_supported(&name).await?;
         let bucket = self.as_wasi_view().table().push(BucketEntry::new(name))?;
         Ok(Ok(bucket))
     }
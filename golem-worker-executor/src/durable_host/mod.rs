@@ -35,6 +35,7 @@ pub mod wasm_rpc;
 
 use self::golem::v1x::GetPromiseResultEntry;
 use crate::durable_host::io::{ManagedStdErr, ManagedStdIn, ManagedStdOut};
+use crate::durable_host::keyvalue::handle_watch::BucketWatchNotifier;
 use crate::durable_host::replay_state::{OplogEntryLookupResult, ReplayState};
 use crate::metrics::wasm::{record_number_of_replayed_functions, record_resume_worker};
 use crate::model::event::InternalWorkerEvent;
@@ -45,7 +46,7 @@ use crate::services::agent_types::AgentTypesService;
 use crate::services::blob_store::BlobStoreService;
 use crate::services::component::ComponentService;
 use crate::services::file_loader::{FileLoader, FileUseToken};
-use crate::services::golem_config::GolemConfig;
+use crate::services::golem_config::{GolemConfig, OplogSearchIndexStorageConfig};
 use crate::services::key_value::KeyValueService;
 use crate::services::oplog::{CommitLevel, Oplog, OplogOps, OplogService};
 use crate::services::promise::PromiseService;
@@ -268,6 +269,7 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
                 shard_service,
                 pending_update,
                 original_phantom_id,
+                worker_config.snapshot_index,
             )
             .await,
             temp_dir,
@@ -1119,6 +1121,110 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> DurableWorkerCtx<Ctx> {
             }
         }
     }
+
+    /// Loads the automatic snapshot selected as the starting point of the replay, if there is any.
+    ///
+    /// The oplog entries preceding the snapshot are already skipped by the replay state.
+    pub async fn load_automatic_snapshot(
+        instance: &Instance,
+        store: &mut (impl AsContextMut<Data = Ctx> + Send),
+    ) -> Result<(), String> {
+        let snapshot_index = store
+            .as_context_mut()
+            .data_mut()
+            .durable_ctx_mut()
+            .state
+            .snapshot_to_load
+            .take();
+
+        if let Some(snapshot_index) = snapshot_index {
+            debug!("Loading automatic snapshot from oplog index {snapshot_index}");
+
+            let oplog = store.as_context().data().get_public_state().oplog();
+            let data = match oplog.read(snapshot_index).await {
+                OplogEntry::Snapshot { data, .. } => oplog.download_payload(data).await?,
+                other => {
+                    return Err(format!(
+                        "Expected a snapshot at oplog index {snapshot_index}, found {other:?}"
+                    ))
+                }
+            };
+
+            let component_metadata = store
+                .as_context()
+                .data()
+                .component_metadata()
+                .metadata
+                .clone();
+
+            let load_snapshot = component_metadata
+                .load_snapshot()
+                .map_err(|err| format!("Failed to find exported load-snapshot function: {err}"))?
+                .ok_or_else(|| "Failed to find exported load-snapshot function".to_string())?;
+
+            store
+                .as_context_mut()
+                .data_mut()
+                .durable_ctx_mut()
+                .set_current_idempotency_key(IdempotencyKey::fresh())
+                .await;
+
+            store
+                .as_context_mut()
+                .data_mut()
+                .begin_call_snapshotting_function();
+
+            let load_result = invoke_observed_and_traced(
+                load_snapshot.name.to_string(),
+                vec![Value::List(data.iter().map(|b| Value::U8(*b)).collect())],
+                store,
+                instance,
+                &component_metadata,
+                true,
+            )
+            .await;
+
+            store
+                .as_context_mut()
+                .data_mut()
+                .end_call_snapshotting_function();
+
+            match load_result {
+                Err(error) => Err(format!("Failed to load snapshot: {error}")),
+                Ok(InvokeResult::Failed { error, .. }) => {
+                    let stderr = store
+                        .as_context()
+                        .data()
+                        .get_public_state()
+                        .event_service()
+                        .get_last_invocation_errors();
+                    Err(format!(
+                        "Failed to load snapshot: {}",
+                        error.to_string(&stderr)
+                    ))
+                }
+                Ok(InvokeResult::Exited { .. }) => {
+                    Err("Failed to load snapshot: it called exit".to_string())
+                }
+                Ok(InvokeResult::Interrupted { interrupt_kind, .. }) => {
+                    Err(format!("Failed to load snapshot: {interrupt_kind:?}"))
+                }
+                Ok(InvokeResult::Succeeded {
+                    output: Some(Value::Result(Err(Some(error)))),
+                    ..
+                }) => match *error {
+                    Value::String(error) => Err(format!("Failed to load snapshot: {error}")),
+                    _ => Err("Unexpected result value from the snapshot load function".to_string()),
+                },
+                Ok(InvokeResult::Succeeded { output: None, .. }) => {
+                    Err("Unexpected result value from the snapshot load function".to_string())
+                }
+                Ok(InvokeResult::Succeeded { .. }) => Ok(()),
+            }
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
@@ -2193,12 +2299,27 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
                         }
                     }
                 }
-                None => {
-                    let result = Self::resume_replay(store, instance, false).await;
-                    record_resume_worker(start.elapsed());
+                None => match Self::load_automatic_snapshot(instance, store).await {
+                    Ok(()) => {
+                        let result = Self::resume_replay(store, instance, false).await;
+                        record_resume_worker(start.elapsed());
 
-                    result
-                }
+                        result
+                    }
+                    Err(error) => {
+                        // The instance may already be in an inconsistent state, so we have to
+                        // recreate it and replay the whole oplog
+                        warn!("{error}, falling back to full replay");
+                        store
+                            .as_context()
+                            .data()
+                            .get_public_state()
+                            .worker()
+                            .disable_snapshot_recovery();
+
+                        Ok(Some(RetryDecision::Immediate))
+                    }
+                },
             }
         };
         match prepare_result {
@@ -2260,6 +2381,49 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
         }
 
         info!("Finished recovering workers");
+
+        if !matches!(
+            this.config().oplog_search_index.storage,
+            OplogSearchIndexStorageConfig::Disabled(_)
+        ) {
+            let shard_service = this.shard_service();
+            let worker_service = this.worker_service();
+            let oplog_service = this.oplog_service();
+            tokio::spawn(
+                async move {
+                    let Some(shard_assignment) = shard_service.try_get_current_assignment() else {
+                        return;
+                    };
+
+                    // Adding the entries of the workers which are not opened, or were committed
+                    // while the search index was not available
+                    for shard_id in shard_assignment.shard_ids {
+                        for owned_worker_id in worker_service.get_workers_in_shard(&shard_id).await
+                        {
+                            let Some(metadata) = worker_service.get(&owned_worker_id).await else {
+                                continue;
+                            };
+                            let component_revision = metadata
+                                .last_known_status
+                                .unwrap_or(metadata.initial_worker_metadata.last_known_status)
+                                .component_revision;
+
+                            if let Err(err) = oplog_service
+                                .backfill_search_index(&owned_worker_id, component_revision)
+                                .await
+                            {
+                                warn!(
+                                    worker_id = %owned_worker_id.worker_id,
+                                    "Failed to backfill the oplog search index: {err}"
+                                );
+                            }
+                        }
+                    }
+                }
+                .in_current_span(),
+            );
+        }
+
         Ok(())
     }
 }
@@ -2707,6 +2871,11 @@ struct PrivateDurableWorkerState {
 
     /// Stores the phantom ID associated with the currently replayed oplog region. Forks can change it
     current_phantom_id: Option<Uuid>,
+
+    /// Index of the automatic snapshot to be loaded before resuming the replay
+    snapshot_to_load: Option<OplogIndex>,
+
+    bucket_watch_notifier: BucketWatchNotifier,
 }
 
 impl PrivateDurableWorkerState {
@@ -2741,11 +2910,17 @@ impl PrivateDurableWorkerState {
         shard_service: Arc<dyn ShardService>,
         pending_update: Option<TimestampedUpdateDescription>,
         original_phantom_id: Option<Uuid>,
+        snapshot_to_load: Option<OplogIndex>,
     ) -> Self {
         let replay_state =
             ReplayState::new(owned_worker_id.clone(), oplog.clone(), deleted_regions).await;
         let invocation_context = InvocationContext::new(None);
         let current_span_id = invocation_context.root.span_id().clone();
+        let bucket_watch_notifier = BucketWatchNotifier::new(
+            owned_worker_id.clone(),
+            key_value_service.clone(),
+            rpc.clone(),
+        );
         Self {
             oplog_service,
             oplog,
@@ -2792,6 +2967,8 @@ impl PrivateDurableWorkerState {
             current_retry_point: OplogIndex::INITIAL,
             active_atomic_regions: Vec::new(),
             current_phantom_id: original_phantom_id,
+            snapshot_to_load,
+            bucket_watch_notifier,
         }
     }
 
@@ -2806,8 +2983,11 @@ impl PrivateDurableWorkerState {
     }
 
     /// Returns whether we are in live mode where we are executing new calls.
+    ///
+    /// Snapshotting functions are never replayed, so they are always executed in live mode,
+    /// even if they get called during recovery.
     pub fn is_live(&self) -> bool {
-        self.replay_state.is_live()
+        self.snapshotting_mode.is_some() || self.replay_state.is_live()
     }
 
     /// Returns whether we are in replay mode where we are replaying old calls.
@@ -2821,6 +3001,7 @@ impl PrivateDurableWorkerState {
             .create(
                 &self.owned_worker_id.worker_id,
                 self.current_oplog_index().await,
+                None,
             )
             .await;
 
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod v0_2_2;
+
 use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx};
 use crate::get_oplog_entry;
 use crate::model::WorkerConfig;
@@ -27,29 +29,35 @@ use async_trait::async_trait;
 use futures::future::Either;
 use golem_common::model::account::AccountId;
 use golem_common::model::component::ComponentId;
-use golem_common::model::invocation_context::{AttributeValue, InvocationContextSpan, SpanId};
+use golem_common::model::invocation_context::{
+    AttributeValue, InvocationContextSpan, SpanId, DEADLINE_ATTRIBUTE,
+};
 use golem_common::model::oplog::host_functions::GolemRpcFutureInvokeResultGet;
 use golem_common::model::oplog::host_functions::{
     GolemRpcCancellationTokenCancel, GolemRpcWasmRpcInvoke, GolemRpcWasmRpcInvokeAndAwaitResult,
-    GolemRpcWasmRpcScheduleInvocation,
+    GolemRpcWasmRpcScheduleInvocation, GolemRpcWasmRpcScheduleRecurringInvocation,
 };
 use golem_common::model::oplog::types::{
     SerializableInvokeResult, SerializableScheduledInvocation,
 };
 use golem_common::model::oplog::{
     DurableFunctionType, HostPayloadPair, HostRequest, HostRequestGolemRpcInvoke,
-    HostRequestGolemRpcScheduledInvocation, HostRequestGolemRpcScheduledInvocationCancellation,
-    HostResponse, HostResponseGolemRpcInvokeAndAwait, HostResponseGolemRpcInvokeGet,
+    HostRequestGolemRpcRecurringInvocation, HostRequestGolemRpcScheduledInvocation,
+    HostRequestGolemRpcScheduledInvocationCancellation, HostResponse,
+    HostResponseGolemRpcInvokeAndAwait, HostResponseGolemRpcInvokeGet,
     HostResponseGolemRpcScheduledInvocation, HostResponseGolemRpcUnit,
     HostResponseGolemRpcUnitOrFailure, OplogEntry, PersistenceLevel,
 };
-use golem_common::model::{IdempotencyKey, OplogIndex, OwnedWorkerId, ScheduledAction, WorkerId};
+use golem_common::model::worker::{CronRecurrence, IntervalRecurrence, ScheduleRecurrence};
+use golem_common::model::{
+    IdempotencyKey, OplogIndex, OwnedWorkerId, ScheduleId, ScheduledAction, Timestamp, WorkerId,
+};
 use golem_common::serialization::{deserialize, serialize};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_wasm::analysis::analysed_type;
+use golem_wasm::golem_rpc_0_2_2::types::HostCancellationToken;
 use golem_wasm::golem_rpc_0_2_x::types::{
-    CancellationToken, FutureInvokeResult, HostCancellationToken, HostFutureInvokeResult, Pollable,
-    Uri,
+    CancellationToken, FutureInvokeResult, HostFutureInvokeResult, Pollable, Uri,
 };
 use golem_wasm::{
     CancellationTokenEntry, FutureInvokeResultEntry, HostWasmRpc, SubscribeAny, Value,
@@ -58,9 +66,10 @@ use golem_wasm::{
 use std::any::Any;
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Formatter};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{error, Instrument};
-use uuid::Uuid;
 use wasmtime::component::Resource;
 use wasmtime_wasi::p2::bindings::cli::environment::Host;
 use wasmtime_wasi::runtime::AbortOnDropJoinHandle;
@@ -87,8 +96,29 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         &mut self,
         self_: Resource<WasmRpcEntry>,
         function_name: String,
-        mut function_params: Vec<WitValue>,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Result<WitValue, golem_wasm::RpcError>> {
+        self.invoke_and_await_impl(self_, function_name, function_params, None)
+            .await
+    }
+
+    async fn invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
     ) -> anyhow::Result<Result<WitValue, golem_wasm::RpcError>> {
+        self.invoke_and_await_impl(self_, function_name, function_params, Some(timeout_millis))
+            .await
+    }
+
+    async fn invoke(
+        &mut self,
+        self_: Resource<WasmRpcEntry>,
+        function_name: String,
+        mut function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Result<(), golem_wasm::RpcError>> {
         let mut env = self.get_environment().await?;
         WorkerConfig::remove_dynamic_vars(&mut env);
 
@@ -111,16 +141,19 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
 
         let idempotency_key = IdempotencyKey::derived(&current_idempotency_key, oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &function_name, &idempotency_key)
-                .await?;
-
-        let durability = Durability::<GolemRpcWasmRpcInvokeAndAwaitResult>::new(
+        let span = create_invocation_span(
             self,
-            DurableFunctionType::WriteRemote,
+            &connection_span_id,
+            &function_name,
+            &idempotency_key,
+            None,
         )
         .await?;
 
+        let durability =
+            Durability::<GolemRpcWasmRpcInvoke>::new(self, DurableFunctionType::WriteRemote)
+                .await?;
+
         if remote_worker_id == own_worker_id {
             return Err(anyhow!("RPC calls to the same agent are not supported"));
         }
@@ -137,6 +170,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                     &function_params,
                 )
                 .await,
+                deadline: None,
                 remote_agent_type: None,
                 remote_agent_parameters: None,
             };
@@ -144,83 +178,308 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                 .state
                 .invocation_context
                 .clone_as_inherited_stack(span.span_id());
-
-            let interrupt_signal = self
-                .execution_status
-                .read()
-                .unwrap()
-                .create_await_interrupt_signal();
-            let rpc = self.rpc();
-            let created_by = self.created_by();
-            let worker_id = self.worker_id().clone();
-
-            let either_result = futures::future::select(
-                rpc.invoke_and_await(
+            let result = self
+                .rpc()
+                .invoke(
                     &remote_worker_id,
                     Some(idempotency_key),
                     function_name,
                     function_params,
-                    created_by,
-                    &worker_id,
+                    self.created_by(),
+                    self.worker_id(),
                     &env,
                     wasi_config_vars,
                     stack,
-                ),
-                interrupt_signal,
-            )
-            .await;
-            let result = match either_result {
-                Either::Left((result, _)) => result,
-                Either::Right((interrupt_kind, _)) => {
-                    tracing::info!("Interrupted while waiting for RPC result");
-                    return Err(interrupt_kind.into());
-                }
-            };
+                )
+                .await;
             durability.try_trigger_retry(self, &result).await?;
 
+            let result = result.map_err(|err| err.into());
+            durability
+                .persist(self, request, HostResponseGolemRpcUnitOrFailure { result })
+                .await
+        } else {
+            durability.replay(self).await
+        };
+
+        self.finish_span(span.span_id()).await?;
+
+        match result?.result {
+            Ok(_) => Ok(Ok(())),
+            Err(err) => {
+                let rpc_error: RpcError = err.into();
+                error!("RPC error for: {rpc_error}");
+                Ok(Err(rpc_error.into()))
+            }
+        }
+    }
+
+    async fn async_invoke_and_await(
+        &mut self,
+        this: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Resource<FutureInvokeResult>> {
+        self.async_invoke_and_await_impl(this, function_name, function_params, None)
+            .await
+    }
+
+    async fn async_invoke_and_await_with_timeout(
+        &mut self,
+        this: Resource<WasmRpcEntry>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
+    ) -> anyhow::Result<Resource<FutureInvokeResult>> {
+        self.async_invoke_and_await_impl(this, function_name, function_params, Some(timeout_millis))
+            .await
+    }
+
+    async fn schedule_invocation(
+        &mut self,
+        this: Resource<WasmRpcEntry>,
+        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
+        full_function_name: String,
+        function_input: Vec<golem_wasm::golem_rpc_0_2_x::types::WitValue>,
+    ) -> anyhow::Result<()> {
+        self.schedule_cancelable_invocation(this, datetime, full_function_name, function_input)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn schedule_cancelable_invocation(
+        &mut self,
+        this: Resource<WasmRpcEntry>,
+        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
+        function_name: String,
+        mut function_params: Vec<golem_wasm::golem_rpc_0_2_x::types::WitValue>,
+    ) -> anyhow::Result<Resource<CancellationToken>> {
+        let durability = Durability::<GolemRpcWasmRpcScheduleInvocation>::new(
+            self,
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
+
+        let result = if durability.is_live() {
+            let entry = self.table().get(&this)?;
+            let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
+            let remote_worker_id = payload.remote_worker_id().clone();
+
+            Self::add_self_parameter_if_needed(&mut function_params, payload);
+
+            let current_idempotency_key = self
+                .state
+                .get_current_idempotency_key()
+                .expect("Expected to get an idempotency key as we are inside an invocation");
+
+            let current_oplog_index = self.state.oplog.current_oplog_index().await;
+
+            let idempotency_key =
+                IdempotencyKey::derived(&current_idempotency_key, current_oplog_index);
+
+            let request = HostRequestGolemRpcScheduledInvocation {
+                remote_worker_id: remote_worker_id.worker_id(),
+                idempotency_key: idempotency_key.clone(),
+                function_name: function_name.clone(),
+                function_params: try_get_typed_parameters(
+                    self.state.component_service.clone(),
+                    remote_worker_id.worker_id.component_id,
+                    &function_name,
+                    &function_params,
+                )
+                .await,
+                datetime: datetime.into(),
+                remote_agent_type: None,
+                remote_agent_parameters: None,
+            };
+
+            let stack = self
+                .state
+                .invocation_context
+                .clone_as_inherited_stack(&self.state.current_span_id);
+            let action = ScheduledAction::Invoke {
+                account_id: self.created_by(),
+                owned_worker_id: remote_worker_id,
+                idempotency_key,
+                full_function_name: function_name,
+                function_input: function_params.into_iter().map(|e| e.into()).collect(),
+                invocation_context: stack,
+            };
+
+            let result = self
+                .state
+                .scheduler_service
+                .schedule(
+                    chrono::DateTime::from_timestamp(datetime.seconds as i64, datetime.nanoseconds)
+                        .expect("Received invalid datetime from wasi"),
+                    action,
+                )
+                .await;
+
+            let invocation =
+                SerializableScheduledInvocation::from_domain(result).map_err(|err| anyhow!(err))?;
+
             durability
                 .persist(
                     self,
                     request,
-                    HostResponseGolemRpcInvokeAndAwait {
-                        result: result.map_err(|err| err.into()),
-                    },
+                    HostResponseGolemRpcScheduledInvocation { invocation },
                 )
                 .await
         } else {
             durability.replay(self).await
         }?;
 
-        self.finish_span(span.span_id()).await?;
+        let serialized_result = serialize(&result.invocation).expect("Failed to serialize result");
+        let cancellation_token = CancellationTokenEntry {
+            schedule_id: serialized_result,
+        };
 
-        match result.result {
-            Ok(value_and_type) => {
-                // Temporary wrapping of the WitValue in a tuple to keep the original WIT interface
-                let wit_value = match value_and_type {
-                    Some(value_and_type) => {
-                        let value: Value = value_and_type.value;
-                        let wrapped = Value::Tuple(vec![value]);
-                        WitValue::from(wrapped)
+        let resource = self.table().push(cancellation_token)?;
+        Ok(resource)
+    }
+
+    async fn schedule_recurring_invocation(
+        &mut self,
+        this: Resource<WasmRpcEntry>,
+        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
+        recurrence: golem_wasm::golem_rpc_0_2_x::types::ScheduleRecurrence,
+        function_name: String,
+        mut function_params: Vec<golem_wasm::golem_rpc_0_2_x::types::WitValue>,
+    ) -> anyhow::Result<Result<Resource<CancellationToken>, String>> {
+        // Validation only depends on the parameters, so it is done before anything gets persisted
+        // and replay takes the same path
+        let recurrence = recurrence_from_wit(recurrence);
+        if let Err(err) = recurrence.validate() {
+            return Ok(Err(err));
+        }
+
+        let durability = Durability::<GolemRpcWasmRpcScheduleRecurringInvocation>::new(
+            self,
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
+
+        let result = if durability.is_live() {
+            let entry = self.table().get(&this)?;
+            let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
+            let remote_worker_id = payload.remote_worker_id().clone();
+
+            Self::add_self_parameter_if_needed(&mut function_params, payload);
+
+            let current_idempotency_key = self
+                .state
+                .get_current_idempotency_key()
+                .expect("Expected to get an idempotency key as we are inside an invocation");
+
+            let current_oplog_index = self.state.oplog.current_oplog_index().await;
+
+            let idempotency_key =
+                IdempotencyKey::derived(&current_idempotency_key, current_oplog_index);
+
+            let request = HostRequestGolemRpcRecurringInvocation {
+                remote_worker_id: remote_worker_id.worker_id(),
+                idempotency_key: idempotency_key.clone(),
+                function_name: function_name.clone(),
+                function_params: try_get_typed_parameters(
+                    self.state.component_service.clone(),
+                    remote_worker_id.worker_id.component_id,
+                    &function_name,
+                    &function_params,
+                )
+                .await,
+                datetime: datetime.into(),
+                recurrence: recurrence.clone(),
+                remote_agent_type: None,
+                remote_agent_parameters: None,
+            };
+
+            let stack = self
+                .state
+                .invocation_context
+                .clone_as_inherited_stack(&self.state.current_span_id);
+            let action = ScheduledAction::RecurringInvoke {
+                account_id: self.created_by(),
+                owned_worker_id: remote_worker_id,
+                idempotency_key,
+                full_function_name: function_name,
+                function_input: function_params.into_iter().map(|e| e.into()).collect(),
+                invocation_context: stack,
+                recurrence: recurrence.clone(),
+            };
+
+            let scheduled_time =
+                chrono::DateTime::from_timestamp(datetime.seconds as i64, datetime.nanoseconds)
+                    .expect("Received invalid datetime from wasi");
+            let scheduled_millis = scheduled_time.timestamp_millis();
+
+            let first_occurrence = recurrence
+                .next_occurrence(scheduled_millis, scheduled_millis)
+                .map_err(|err| anyhow!(err))?
+                .and_then(chrono::DateTime::from_timestamp_millis);
+
+            let result = match first_occurrence {
+                Some(first_occurrence) => {
+                    self.state
+                        .scheduler_service
+                        .schedule(first_occurrence, action)
+                        .await
+                }
+                None => {
+                    // There are no occurrences before the recurrence's end, so nothing gets scheduled
+                    // and cancelling the returned token is a no-op
+                    ScheduleId {
+                        timestamp: scheduled_time.timestamp() / 3600,
+                        action,
                     }
-                    None => WitValue::from(Value::Record(vec![])),
-                };
+                }
+            };
 
-                Ok(Ok(wit_value))
-            }
-            Err(err) => {
-                let rpc_error: RpcError = err.into();
-                error!("RPC error: {rpc_error}");
-                Ok(Err(rpc_error.into()))
-            }
+            let invocation =
+                SerializableScheduledInvocation::from_domain(result).map_err(|err| anyhow!(err))?;
+
+            durability
+                .persist(
+                    self,
+                    request,
+                    HostResponseGolemRpcScheduledInvocation { invocation },
+                )
+                .await
+        } else {
+            durability.replay(self).await
+        }?;
+
+        let serialized_result = serialize(&result.invocation).expect("Failed to serialize result");
+        let cancellation_token = CancellationTokenEntry {
+            schedule_id: serialized_result,
+        };
+
+        let resource = self.table().push(cancellation_token)?;
+        Ok(Ok(resource))
+    }
+
+    async fn drop(&mut self, rep: Resource<WasmRpcEntry>) -> anyhow::Result<()> {
+        self.observe_function_call("golem::rpc::wasm-rpc", "drop");
+
+        let entry = self.table().delete(rep)?;
+        let payload = entry.payload.downcast::<WasmRpcEntryPayload>();
+        if let Ok(payload) = payload {
+            self.finish_span(payload.span_id()).await?;
         }
+
+        Ok(())
     }
+}
 
-    async fn invoke(
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    async fn invoke_and_await_impl(
         &mut self,
         self_: Resource<WasmRpcEntry>,
         function_name: String,
         mut function_params: Vec<WitValue>,
-    ) -> anyhow::Result<Result<(), golem_wasm::RpcError>> {
+        timeout_millis: Option<u64>,
+    ) -> anyhow::Result<Result<WitValue, golem_wasm::RpcError>> {
         let mut env = self.get_environment().await?;
         WorkerConfig::remove_dynamic_vars(&mut env);
 
@@ -243,13 +502,21 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
 
         let idempotency_key = IdempotencyKey::derived(&current_idempotency_key, oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &function_name, &idempotency_key)
-                .await?;
+        let deadline = self.rpc_deadline(&connection_span_id, timeout_millis);
+        let span = create_invocation_span(
+            self,
+            &connection_span_id,
+            &function_name,
+            &idempotency_key,
+            deadline,
+        )
+        .await?;
 
-        let durability =
-            Durability::<GolemRpcWasmRpcInvoke>::new(self, DurableFunctionType::WriteRemote)
-                .await?;
+        let durability = Durability::<GolemRpcWasmRpcInvokeAndAwaitResult>::new(
+            self,
+            DurableFunctionType::WriteRemote,
+        )
+        .await?;
 
         if remote_worker_id == own_worker_id {
             return Err(anyhow!("RPC calls to the same agent are not supported"));
@@ -267,54 +534,97 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                     &function_params,
                 )
                 .await,
+                deadline,
                 remote_agent_type: None,
                 remote_agent_parameters: None,
             };
-            let stack = self
-                .state
-                .invocation_context
-                .clone_as_inherited_stack(span.span_id());
-            let result = self
-                .rpc()
-                .invoke(
-                    &remote_worker_id,
-                    Some(idempotency_key),
-                    function_name,
-                    function_params,
-                    self.created_by(),
-                    self.worker_id(),
-                    &env,
-                    wasi_config_vars,
-                    stack,
-                )
-                .await;
-            durability.try_trigger_retry(self, &result).await?;
+            let stack = self
+                .state
+                .invocation_context
+                .clone_as_inherited_stack(span.span_id());
+
+            let interrupt_signal = self
+                .execution_status
+                .read()
+                .unwrap()
+                .create_await_interrupt_signal();
+            let rpc = self.rpc();
+            let created_by = self.created_by();
+            let worker_id = self.worker_id().clone();
+
+            let either_result = futures::future::select(
+                Box::pin(with_deadline(
+                    deadline,
+                    rpc.invoke_and_await(
+                        &remote_worker_id,
+                        Some(idempotency_key),
+                        function_name,
+                        function_params,
+                        created_by,
+                        &worker_id,
+                        &env,
+                        wasi_config_vars,
+                        stack,
+                    ),
+                )),
+                interrupt_signal,
+            )
+            .await;
+            let result = match either_result {
+                Either::Left((result, _)) => result,
+                Either::Right((interrupt_kind, _)) => {
+                    tracing::info!("Interrupted while waiting for RPC result");
+                    return Err(interrupt_kind.into());
+                }
+            };
+            // Timeouts are returned to the caller instead of being retried
+            if !matches!(result, Err(RpcError::Timeout { .. })) {
+                durability.try_trigger_retry(self, &result).await?;
+            }
 
-            let result = result.map_err(|err| err.into());
             durability
-                .persist(self, request, HostResponseGolemRpcUnitOrFailure { result })
+                .persist(
+                    self,
+                    request,
+                    HostResponseGolemRpcInvokeAndAwait {
+                        result: result.map_err(|err| err.into()),
+                    },
+                )
                 .await
         } else {
             durability.replay(self).await
-        };
+        }?;
 
         self.finish_span(span.span_id()).await?;
 
-        match result?.result {
-            Ok(_) => Ok(Ok(())),
+        match result.result {
+            Ok(value_and_type) => {
+                // Temporary wrapping of the WitValue in a tuple to keep the original WIT interface
+                let wit_value = match value_and_type {
+                    Some(value_and_type) => {
+                        let value: Value = value_and_type.value;
+                        let wrapped = Value::Tuple(vec![value]);
+                        WitValue::from(wrapped)
+                    }
+                    None => WitValue::from(Value::Record(vec![])),
+                };
+
+                Ok(Ok(wit_value))
+            }
             Err(err) => {
                 let rpc_error: RpcError = err.into();
-                error!("RPC error for: {rpc_error}");
+                error!("RPC error: {rpc_error}");
                 Ok(Err(rpc_error.into()))
             }
         }
     }
 
-    async fn async_invoke_and_await(
+    async fn async_invoke_and_await_impl(
         &mut self,
         this: Resource<WasmRpcEntry>,
         function_name: String,
         mut function_params: Vec<WitValue>,
+        timeout_millis: Option<u64>,
     ) -> anyhow::Result<Resource<FutureInvokeResult>> {
         let mut env = self.get_environment().await?;
         WorkerConfig::remove_dynamic_vars(&mut env);
@@ -346,9 +656,15 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
 
         let idempotency_key = IdempotencyKey::derived(&current_idempotency_key, oplog_index);
 
-        let span =
-            create_invocation_span(self, &connection_span_id, &function_name, &idempotency_key)
-                .await?;
+        let deadline = self.rpc_deadline(&connection_span_id, timeout_millis);
+        let span = create_invocation_span(
+            self,
+            &connection_span_id,
+            &function_name,
+            &idempotency_key,
+            deadline,
+        )
+        .await?;
 
         let worker_id = self.worker_id().clone();
         let created_by = self.created_by();
@@ -363,6 +679,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                 &function_params,
             )
             .await,
+            deadline,
             remote_agent_type: None,
             remote_agent_parameters: None,
         };
@@ -375,8 +692,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                 .clone_as_inherited_stack(span.span_id());
             let handle = wasmtime_wasi::runtime::spawn(
                 async move {
-                    Ok(rpc
-                        .invoke_and_await(
+                    Ok(with_deadline(
+                        deadline,
+                        rpc.invoke_and_await(
                             &remote_worker_id,
                             Some(idempotency_key),
                             function_name,
@@ -386,8 +704,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                             &env,
                             wasi_config_vars,
                             stack,
-                        )
-                        .await)
+                        ),
+                    )
+                    .await)
                 }
                 .in_current_span(),
             );
@@ -412,6 +731,7 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                     function_name,
                     function_params,
                     idempotency_key,
+                    deadline,
                     span_id: span.span_id().clone(),
                     begin_index,
                 }),
@@ -427,125 +747,28 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
         result
     }
 
-    async fn schedule_invocation(
-        &mut self,
-        this: Resource<WasmRpcEntry>,
-        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
-        full_function_name: String,
-        function_input: Vec<golem_wasm::golem_rpc_0_2_x::types::WitValue>,
-    ) -> anyhow::Result<()> {
-        self.schedule_cancelable_invocation(this, datetime, full_function_name, function_input)
-            .await?;
-
-        Ok(())
-    }
-
-    async fn schedule_cancelable_invocation(
-        &mut self,
-        this: Resource<WasmRpcEntry>,
-        datetime: golem_wasm::wasi::clocks::wall_clock::Datetime,
-        function_name: String,
-        mut function_params: Vec<golem_wasm::golem_rpc_0_2_x::types::WitValue>,
-    ) -> anyhow::Result<Resource<CancellationToken>> {
-        let durability = Durability::<GolemRpcWasmRpcScheduleInvocation>::new(
-            self,
-            DurableFunctionType::WriteRemote,
-        )
-        .await?;
-
-        let result = if durability.is_live() {
-            let entry = self.table().get(&this)?;
-            let payload = entry.payload.downcast_ref::<WasmRpcEntryPayload>().unwrap();
-            let remote_worker_id = payload.remote_worker_id().clone();
-
-            Self::add_self_parameter_if_needed(&mut function_params, payload);
-
-            let current_idempotency_key = self
-                .state
-                .get_current_idempotency_key()
-                .expect("Expected to get an idempotency key as we are inside an invocation");
-
-            let current_oplog_index = self.state.oplog.current_oplog_index().await;
-
-            let idempotency_key =
-                IdempotencyKey::derived(&current_idempotency_key, current_oplog_index);
-
-            let request = HostRequestGolemRpcScheduledInvocation {
-                remote_worker_id: remote_worker_id.worker_id(),
-                idempotency_key: idempotency_key.clone(),
-                function_name: function_name.clone(),
-                function_params: try_get_typed_parameters(
-                    self.state.component_service.clone(),
-                    remote_worker_id.worker_id.component_id,
-                    &function_name,
-                    &function_params,
-                )
-                .await,
-                datetime: datetime.into(),
-                remote_agent_type: None,
-                remote_agent_parameters: None,
-            };
-
-            let stack = self
-                .state
-                .invocation_context
-                .clone_as_inherited_stack(&self.state.current_span_id);
-            let action = ScheduledAction::Invoke {
-                account_id: self.created_by(),
-                owned_worker_id: remote_worker_id,
-                idempotency_key,
-                full_function_name: function_name,
-                function_input: function_params.into_iter().map(|e| e.into()).collect(),
-                invocation_context: stack,
-            };
-
-            let result = self
-                .state
-                .scheduler_service
-                .schedule(
-                    chrono::DateTime::from_timestamp(datetime.seconds as i64, datetime.nanoseconds)
-                        .expect("Received invalid datetime from wasi"),
-                    action,
-                )
-                .await;
-
-            let invocation =
-                SerializableScheduledInvocation::from_domain(result).map_err(|err| anyhow!(err))?;
-
-            durability
-                .persist(
-                    self,
-                    request,
-                    HostResponseGolemRpcScheduledInvocation { invocation },
-                )
-                .await
-        } else {
-            durability.replay(self).await
-        }?;
-
-        let serialized_result = serialize(&result.invocation).expect("Failed to serialize result");
-        let cancellation_token = CancellationTokenEntry {
-            schedule_id: serialized_result,
-        };
-
-        let resource = self.table().push(cancellation_token)?;
-        Ok(resource)
-    }
-
-    async fn drop(&mut self, rep: Resource<WasmRpcEntry>) -> anyhow::Result<()> {
-        self.observe_function_call("golem::rpc::wasm-rpc", "drop");
-
-        let entry = self.table().delete(rep)?;
-        let payload = entry.payload.downcast::<WasmRpcEntryPayload>();
-        if let Ok(payload) = payload {
-            self.finish_span(payload.span_id()).await?;
-        }
-
-        Ok(())
+    /// The deadline of an RPC invocation is the earlier of the requested timeout and the
+    /// deadline the current invocation inherited from its own caller
+    fn rpc_deadline(
+        &self,
+        connection_span_id: &SpanId,
+        timeout_millis: Option<u64>,
+    ) -> Option<Timestamp> {
+        let inherited = self
+            .state
+            .invocation_context
+            .clone_as_inherited_stack(connection_span_id)
+            .deadline();
+        let requested = timeout_millis.map(|timeout_millis| {
+            Timestamp::from(
+                Timestamp::now_utc()
+                    .to_millis()
+                    .saturating_add(timeout_millis),
+            )
+        });
+        inherited.into_iter().chain(requested).min()
     }
-}
 
-impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
     fn add_self_parameter_if_needed(
         function_params: &mut Vec<WitValue>,
         payload: &WasmRpcEntryPayload,
@@ -577,6 +800,7 @@ impl From<RpcError> for golem_wasm::RpcError {
             RpcError::RemoteInternalError { details } => {
                 golem_wasm::RpcError::RemoteInternalError(details)
             }
+            RpcError::Timeout { details } => golem_wasm::RpcError::Timeout(details),
         }
     }
 }
@@ -604,6 +828,7 @@ enum FutureInvokeResultState {
         function_name: String,
         function_params: Vec<WitValue>,
         idempotency_key: IdempotencyKey,
+        deadline: Option<Timestamp>,
         span_id: SpanId,
         begin_index: OplogIndex,
     },
@@ -642,6 +867,15 @@ impl FutureInvokeResultState {
             Self::Consumed { begin_index, .. } => *begin_index,
         }
     }
+
+    /// A deferred invocation's deadline is computed from the current time when the
+    /// `async-invoke-and-await` call is replayed, so it gets replaced by the absolute deadline
+    /// persisted when the invocation was originally started
+    pub fn restore_deadline(&mut self, persisted: Option<Timestamp>) {
+        if let Self::Deferred { deadline, .. } = self {
+            *deadline = persisted;
+        }
+    }
 }
 
 #[async_trait]
@@ -807,6 +1041,7 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                                 function_name,
                                 function_params,
                                 idempotency_key,
+                                deadline,
                                 ..
                             } = request
                             else {
@@ -814,8 +1049,9 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                                     "unexpected incoming response state".to_string()
                                 ));
                             };
-                            Ok(rpc
-                                .invoke_and_await(
+                            Ok(with_deadline(
+                                deadline,
+                                rpc.invoke_and_await(
                                     &remote_worker_id,
                                     Some(idempotency_key),
                                     function_name,
@@ -825,8 +1061,9 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                                     &env,
                                     wasi_config_vars,
                                     stack,
-                                )
-                                .await)
+                                ),
+                            )
+                            .await)
                         }
                         .in_current_span(),
                     );
@@ -835,6 +1072,7 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                         function_name,
                         function_params,
                         idempotency_key,
+                        deadline,
                         span_id,
                         ..
                     } = &entry
@@ -852,6 +1090,7 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                             function_params,
                         )
                         .await,
+                        deadline: *deadline,
                         remote_agent_type: None,
                         remote_agent_parameters: None
                     };
@@ -877,6 +1116,8 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
 
             let for_retry = match &result {
                 Err(err) => Err(anyhow!(err.to_string())),
+                // Timeouts are returned to the caller instead of being retried
+                Ok(Some(Err(golem_wasm::RpcError::Timeout(_)))) => Ok(()),
                 Ok(Some(Err(err))) => Err(anyhow!(err.to_string())),
                 _ => Ok(()),
             };
@@ -955,8 +1196,16 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                 )
                     })?;
 
-            let serialized_invoke_result = match oplog_entry {
-                OplogEntry::ImportedFunctionInvoked { response, .. } => {
+            let (persisted_deadline, serialized_invoke_result) = match oplog_entry {
+                OplogEntry::ImportedFunctionInvoked {
+                    request, response, ..
+                } => {
+                    let request = self
+                        .state
+                        .oplog
+                        .download_payload(request)
+                        .await
+                        .map_err(|err| anyhow!("Failed to download oplog payload: {err}"))?;
                     let response = self
                         .state
                         .oplog
@@ -964,12 +1213,17 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                         .await
                         .map_err(|err| anyhow!("Failed to download oplog payload: {err}"))?;
 
-                    match response {
+                    let deadline = match request {
+                        HostRequest::GolemRpcInvoke(request) => request.deadline,
+                        _ => panic!("unexpected oplog payload type"),
+                    };
+                    let result = match response {
                         HostResponse::GolemRpcInvokeGet(HostResponseGolemRpcInvokeGet {
                             result,
                         }) => result,
                         _ => panic!("unexpected oplog payload type"),
-                    }
+                    };
+                    (deadline, result)
                 }
                 _ => panic!("unexpected oplog entry type"),
             };
@@ -980,6 +1234,7 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                 .as_any_mut()
                 .downcast_mut::<FutureInvokeResultState>()
                 .unwrap();
+            entry.restore_deadline(persisted_deadline);
             let begin_index = entry.begin_index();
 
             if !matches!(serialized_invoke_result, SerializableInvokeResult::Pending) {
@@ -1069,21 +1324,7 @@ impl<Ctx: WorkerCtx> HostCancellationToken for DurableWorkerCtx<Ctx> {
     }
 }
 
-impl<Ctx: WorkerCtx> golem_wasm::Host for DurableWorkerCtx<Ctx> {
-    async fn parse_uuid(
-        &mut self,
-        uuid: String,
-    ) -> anyhow::Result<Result<golem_wasm::Uuid, String>> {
-        Ok(Uuid::parse_str(&uuid)
-            .map(|uuid| uuid.into())
-            .map_err(|e| e.to_string()))
-    }
-
-    async fn uuid_to_string(&mut self, uuid: golem_wasm::Uuid) -> anyhow::Result<String> {
-        let uuid: Uuid = uuid.into();
-        Ok(uuid.to_string())
-    }
-}
+impl<Ctx: WorkerCtx> golem_wasm::Host for DurableWorkerCtx<Ctx> {}
 
 pub async fn construct_wasm_rpc_resource<Ctx: WorkerCtx>(
     ctx: &mut DurableWorkerCtx<Ctx>,
@@ -1121,6 +1362,29 @@ pub async fn construct_wasm_rpc_resource<Ctx: WorkerCtx>(
     Ok(entry)
 }
 
+fn recurrence_from_wit(
+    recurrence: golem_wasm::golem_rpc_0_2_x::types::ScheduleRecurrence,
+) -> ScheduleRecurrence {
+    fn timestamp_from_wit(datetime: golem_wasm::wasi::clocks::wall_clock::Datetime) -> Timestamp {
+        Timestamp::from(datetime.seconds * 1000 + (datetime.nanoseconds / 1_000_000) as u64)
+    }
+
+    match recurrence {
+        golem_wasm::golem_rpc_0_2_x::types::ScheduleRecurrence::Interval(interval) => {
+            ScheduleRecurrence::Interval(IntervalRecurrence {
+                interval_millis: interval.interval_millis,
+                until: interval.until.map(timestamp_from_wit),
+            })
+        }
+        golem_wasm::golem_rpc_0_2_x::types::ScheduleRecurrence::Cron(cron) => {
+            ScheduleRecurrence::Cron(CronRecurrence {
+                expression: cron.expression,
+                until: cron.until.map(timestamp_from_wit),
+            })
+        }
+    }
+}
+
 /// Tries to get a `ValueAndType` representation for the given `WitValue` parameters by querying the latest component metadata for the
 /// target component.
 /// If the query fails, or the expected function name is not in its metadata or the number of parameters does not match, then it returns an
@@ -1243,24 +1507,165 @@ pub async fn create_invocation_span<Ctx: InvocationContextManagement>(
     connection_span_id: &SpanId,
     function_name: &str,
     idempotency_key: &IdempotencyKey,
+    deadline: Option<Timestamp>,
 ) -> anyhow::Result<Arc<InvocationContextSpan>> {
+    let mut attributes = vec![
+        (
+            "name".to_string(),
+            AttributeValue::String("rpc-invocation".to_string()),
+        ),
+        (
+            "function_name".to_string(),
+            AttributeValue::String(function_name.to_string()),
+        ),
+        (
+            "idempotency_key".to_string(),
+            AttributeValue::String(idempotency_key.to_string()),
+        ),
+    ];
+    // The deadline is inherited by the remote invocation context, so nested RPC calls made by
+    // the callee are also bounded by it
+    if let Some(deadline) = deadline {
+        attributes.push((
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(deadline.to_string()),
+        ));
+    }
     Ok(ctx
-        .start_child_span(
-            connection_span_id,
-            &[
-                (
-                    "name".to_string(),
-                    AttributeValue::String("rpc-invocation".to_string()),
-                ),
-                (
-                    "function_name".to_string(),
-                    AttributeValue::String(function_name.to_string()),
-                ),
-                (
-                    "idempotency_key".to_string(),
-                    AttributeValue::String(idempotency_key.to_string()),
-                ),
-            ],
-        )
+        .start_child_span(connection_span_id, &attributes)
         .await?)
 }
+
+/// Awaits an RPC invocation, failing with [`RpcError::Timeout`] if it does not complete before
+/// the given deadline. The remote invocation itself is not cancelled.
+async fn with_deadline(
+    deadline: Option<Timestamp>,
+    invocation: impl Future<Output = Result<Option<ValueAndType>, RpcError>>,
+) -> Result<Option<ValueAndType>, RpcError> {
+    match deadline {
+        Some(deadline) => {
+            let remaining = Duration::from_millis(
+                deadline
+                    .to_millis()
+                    .saturating_sub(Timestamp::now_utc().to_millis()),
+            );
+            tokio::time::timeout(remaining, invocation)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(RpcError::Timeout {
+                        details: format!("the invocation did not complete before {deadline}"),
+                    })
+                })
+        }
+        None => invocation.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{with_deadline, FutureInvokeResultState};
+    use crate::services::rpc::RpcError;
+    use golem_common::model::account::AccountId;
+    use golem_common::model::component::ComponentId;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::invocation_context::SpanId;
+    use golem_common::model::oplog::{HostRequest, HostRequestGolemRpcInvoke};
+    use golem_common::model::{IdempotencyKey, OplogIndex, OwnedWorkerId, Timestamp, WorkerId};
+    use golem_common::serialization::{deserialize, serialize};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn worker_id(name: &str) -> WorkerId {
+        WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: name.to_string(),
+        }
+    }
+
+    fn in_millis(millis: u64) -> Timestamp {
+        Timestamp::from(Timestamp::now_utc().to_millis() + millis)
+    }
+
+    #[test]
+    async fn invocations_not_completing_before_the_deadline_time_out() {
+        let result = with_deadline(
+            Some(in_millis(50)),
+            futures::future::pending::<Result<_, RpcError>>(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(RpcError::Timeout { .. })));
+    }
+
+    #[test]
+    async fn invocations_completing_before_the_deadline_return_their_result() {
+        let result = with_deadline(Some(in_millis(10_000)), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(None)
+        })
+        .await;
+
+        assert!(matches!(result, Ok(None)));
+    }
+
+    #[test]
+    async fn passed_deadlines_time_out_immediately() {
+        let result = with_deadline(
+            Some(Timestamp::from(Timestamp::now_utc().to_millis() - 1_000)),
+            futures::future::pending::<Result<_, RpcError>>(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(RpcError::Timeout { .. })));
+    }
+
+    #[test]
+    fn persisted_deadline_survives_serialization() {
+        let deadline = in_millis(60_000);
+        let request = HostRequest::GolemRpcInvoke(HostRequestGolemRpcInvoke {
+            remote_worker_id: worker_id("remote"),
+            idempotency_key: IdempotencyKey::fresh(),
+            function_name: "f".to_string(),
+            function_params: vec![],
+            deadline: Some(deadline),
+            remote_agent_type: None,
+            remote_agent_parameters: None,
+        });
+
+        let bytes = serialize(&request).unwrap();
+        let deserialized: HostRequest = deserialize(&bytes).unwrap();
+
+        let HostRequest::GolemRpcInvoke(deserialized) = deserialized else {
+            panic!("unexpected payload: {deserialized:?}");
+        };
+        assert_eq!(deserialized.deadline, Some(deadline));
+    }
+
+    #[test]
+    fn replayed_deferred_invocations_keep_the_persisted_deadline() {
+        let persisted = Timestamp::from(Timestamp::now_utc().to_millis() - 30_000);
+        let mut state = FutureInvokeResultState::Deferred {
+            remote_worker_id: OwnedWorkerId::new(EnvironmentId::new(), &worker_id("remote")),
+            self_worker_id: worker_id("self"),
+            self_created_by: AccountId::new(),
+            env: vec![],
+            wasi_config_vars: BTreeMap::new(),
+            function_name: "f".to_string(),
+            function_params: vec![],
+            idempotency_key: IdempotencyKey::fresh(),
+            // recomputed from the current time while replaying
+            deadline: Some(in_millis(60_000)),
+            span_id: SpanId::generate(),
+            begin_index: OplogIndex::INITIAL,
+        };
+
+        state.restore_deadline(Some(persisted));
+
+        let FutureInvokeResultState::Deferred { deadline, .. } = state else {
+            panic!("unexpected state: {state:?}");
+        };
+        assert_eq!(deadline, Some(persisted));
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host implementation of `golem:rpc/types@0.2.2`, kept for components built against it.
+//!
+//! The resources are shared with `golem:rpc/types@0.2.3`, so every call is forwarded to the
+//! current implementation, only converting the error type which has no `timeout` case here.
+
+use crate::durable_host::DurableWorkerCtx;
+use crate::workerctx::WorkerCtx;
+use golem_wasm::golem_rpc_0_2_2::types::{
+    AgentId, CancellationToken, Datetime, FutureInvokeResult, Host, HostFutureInvokeResult,
+    HostWasmRpc, Pollable, RpcError, WasmRpc, WitValue,
+};
+use golem_wasm::golem_rpc_0_2_x::types as current;
+use uuid::Uuid;
+use wasmtime::component::Resource;
+
+impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
+    async fn new(&mut self, agent_id: AgentId) -> anyhow::Result<Resource<WasmRpc>> {
+        current::HostWasmRpc::new(self, agent_id).await
+    }
+
+    async fn invoke_and_await(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Result<WitValue, RpcError>> {
+        let result =
+            current::HostWasmRpc::invoke_and_await(self, self_, function_name, function_params)
+                .await?;
+        Ok(result.map_err(to_rpc_error))
+    }
+
+    async fn invoke(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Result<(), RpcError>> {
+        let result =
+            current::HostWasmRpc::invoke(self, self_, function_name, function_params).await?;
+        Ok(result.map_err(to_rpc_error))
+    }
+
+    async fn async_invoke_and_await(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Resource<FutureInvokeResult>> {
+        current::HostWasmRpc::async_invoke_and_await(self, self_, function_name, function_params)
+            .await
+    }
+
+    async fn schedule_invocation(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        scheduled_time: Datetime,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<()> {
+        current::HostWasmRpc::schedule_invocation(
+            self,
+            self_,
+            scheduled_time,
+            function_name,
+            function_params,
+        )
+        .await
+    }
+
+    async fn schedule_cancelable_invocation(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        scheduled_time: Datetime,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Resource<CancellationToken>> {
+        current::HostWasmRpc::schedule_cancelable_invocation(
+            self,
+            self_,
+            scheduled_time,
+            function_name,
+            function_params,
+        )
+        .await
+    }
+
+    async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
+        current::HostWasmRpc::drop(self, rep).await
+    }
+}
+
+impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
+    async fn subscribe(
+        &mut self,
+        self_: Resource<FutureInvokeResult>,
+    ) -> anyhow::Result<Resource<Pollable>> {
+        current::HostFutureInvokeResult::subscribe(self, self_).await
+    }
+
+    async fn get(
+        &mut self,
+        self_: Resource<FutureInvokeResult>,
+    ) -> anyhow::Result<Option<Result<WitValue, RpcError>>> {
+        let result = current::HostFutureInvokeResult::get(self, self_).await?;
+        Ok(result.map(|result| result.map_err(to_rpc_error)))
+    }
+
+    async fn drop(&mut self, rep: Resource<FutureInvokeResult>) -> anyhow::Result<()> {
+        current::HostFutureInvokeResult::drop(self, rep).await
+    }
+}
+
+impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
+    async fn parse_uuid(
+        &mut self,
+        uuid: String,
+    ) -> anyhow::Result<Result<golem_wasm::Uuid, String>> {
+        Ok(Uuid::parse_str(&uuid)
+            .map(|uuid| uuid.into())
+            .map_err(|e| e.to_string()))
+    }
+
+    async fn uuid_to_string(&mut self, uuid: golem_wasm::Uuid) -> anyhow::Result<String> {
+        let uuid: Uuid = uuid.into();
+        Ok(uuid.to_string())
+    }
+}
+
+/// Callers of this version cannot set a timeout, but they can still run into a deadline inherited
+/// from their own invocation, which is reported as an internal error of the remote side.
+fn to_rpc_error(error: current::RpcError) -> RpcError {
+    match error {
+        current::RpcError::ProtocolError(details) => RpcError::ProtocolError(details),
+        current::RpcError::Denied(details) => RpcError::Denied(details),
+        current::RpcError::NotFound(details) => RpcError::NotFound(details),
+        current::RpcError::RemoteInternalError(details) => RpcError::RemoteInternalError(details),
+        current::RpcError::Timeout(details) => {
+            RpcError::RemoteInternalError(format!("Timeout: {details}"))
+        }
+    }
+}
@@ -339,6 +339,59 @@ impl GrpcInvokeRequest
     }
 }
 
+impl ProtobufInvocationDetails
+    for golem_api_grpc::proto::golem::workerexecutor::v1::ScheduleRecurringInvocationRequest
+{
+    fn proto_worker_id(&self) -> &Option<golem_api_grpc::proto::golem::worker::WorkerId> {
+        &self.worker_id
+    }
+
+    fn proto_environment_id(&self) -> &Option<golem_api_grpc::proto::golem::common::EnvironmentId> {
+        &self.environment_id
+    }
+
+    fn proto_invocation_context(
+        &self,
+    ) -> &Option<golem_api_grpc::proto::golem::worker::InvocationContext> {
+        &self.context
+    }
+
+    fn proto_auth_ctx(&self) -> &Option<golem_api_grpc::proto::golem::auth::AuthCtx> {
+        &self.auth_ctx
+    }
+}
+
+impl GrpcInvokeRequest
+    for golem_api_grpc::proto::golem::workerexecutor::v1::ScheduleRecurringInvocationRequest
+{
+    async fn input<Ctx: WorkerCtx>(
+        &self,
+        worker: &Arc<Worker<Ctx>>,
+    ) -> Result<Vec<Val>, WorkerExecutorError> {
+        match &self.input {
+            Some(golem_api_grpc::proto::golem::workerexecutor::v1::schedule_recurring_invocation_request::Input::TypedInput(input)) => {
+                Ok(input.values.clone())
+            }
+            Some(golem_api_grpc::proto::golem::workerexecutor::v1::schedule_recurring_invocation_request::Input::JsonInput(input)) => {
+                interpret_json_input(&self.name, &input.values, worker).await
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn idempotency_key(&self) -> Result<Option<IdempotencyKey>, WorkerExecutorError> {
+        Ok(self.idempotency_key.clone().map(IdempotencyKey::from))
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn invocation_context(&self) -> InvocationContextStack {
+        from_proto_invocation_context(&self.context)
+    }
+}
+
 /// Assumes what component revision a worker will execute the next enqueued invocation with
 fn assume_future_component_revision(metadata: &WorkerMetadata) -> ComponentRevision {
     let mut revision = metadata.last_known_status.component_revision;
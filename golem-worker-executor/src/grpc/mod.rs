@@ -21,15 +21,18 @@ use crate::model::public_oplog::{
 };
 use crate::model::{LastError, ReadFileResult};
 use crate::services::events::Event;
+use crate::services::oplog::export::{export_oplog, import_oplog};
+use crate::services::retention::AgentRetention;
 use crate::services::worker_activator::{DefaultWorkerActivator, LazyWorkerActivator};
 use crate::services::worker_event::WorkerEventReceiver;
 use crate::services::{
-    All, HasActiveWorkers, HasAll, HasComponentService, HasEvents, HasOplogService,
+    All, HasActiveWorkers, HasAll, HasComponentService, HasEvents, HasFileLoader, HasOplogService,
     HasPromiseService, HasRunningWorkerEnumerationService, HasShardManagerService, HasShardService,
     HasWorkerEnumerationService, HasWorkerService, UsesAllDeps,
 };
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
+use futures::future::join_all;
 use futures::Stream;
 use futures::StreamExt;
 use gethostname::gethostname;
@@ -38,14 +41,20 @@ use golem_api_grpc::proto::golem::worker::{Cursor, UpdateMode};
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_server::WorkerExecutor;
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
     ActivatePluginRequest, ActivatePluginResponse, CancelInvocationRequest,
-    CancelInvocationResponse, ConnectWorkerRequest, DeactivatePluginRequest,
-    DeactivatePluginResponse, DeleteWorkerRequest, ForkWorkerRequest, ForkWorkerResponse,
-    GetFileContentsRequest, GetFileContentsResponse, GetFileSystemNodeRequest,
-    GetFileSystemNodeResponse, GetOplogRequest, GetOplogResponse, GetRunningWorkersMetadataRequest,
+    CancelInvocationResponse, CancelRecurringInvocationRequest, CancelRecurringInvocationResponse,
+    ChangeWorkerLabelsRequest, ChangeWorkerLabelsResponse, ConnectWorkerRequest,
+    DeactivatePluginRequest, DeactivatePluginResponse, DeleteWorkerRequest, ExportOplogRequest,
+    ExportOplogResponse, ForkWorkerRequest, ForkWorkerResponse, GetFileContentsRequest,
+    GetFileContentsResponse, GetFileSystemNodeRequest, GetFileSystemNodeResponse, GetOplogRequest,
+    GetOplogResponse, GetRecurringInvocationsRequest, GetRecurringInvocationsResponse,
+    GetRetentionReportRequest, GetRetentionReportResponse, GetRunningWorkersMetadataRequest,
     GetRunningWorkersMetadataResponse, GetWorkersMetadataRequest, GetWorkersMetadataResponse,
+    ImportOplogRequest, ImportOplogRequestHeader, ImportOplogResponse,
     InvokeAndAwaitWorkerJsonRequest, InvokeAndAwaitWorkerRequest,
     InvokeAndAwaitWorkerResponseTyped, InvokeAndAwaitWorkerSuccess, InvokeJsonWorkerRequest,
-    InvokeWorkerResponse, RevertWorkerRequest, RevertWorkerResponse, SearchOplogRequest,
+    InvokeWorkerResponse, RevertWorkerRequest, RevertWorkerResponse,
+    ScheduleRecurringInvocationRequest, ScheduleRecurringInvocationResponse,
+    SearchComponentOplogRequest, SearchComponentOplogResponse, SearchOplogRequest,
     SearchOplogResponse, UpdateWorkerRequest, UpdateWorkerResponse,
 };
 use golem_common::metrics::api::record_new_grpc_api_active_stream;
@@ -54,8 +63,13 @@ use golem_common::model::agent::{AgentId, AgentMode};
 use golem_common::model::component::{ComponentFilePath, ComponentId, PluginPriority};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
-use golem_common::model::oplog::{OplogIndex, UpdateDescription};
+use golem_common::model::lucene::Query;
+use golem_common::model::oplog::{OplogIndex, OplogSearchHit, UpdateDescription};
 use golem_common::model::protobuf::to_protobuf_resource_description;
+use golem_common::model::worker::{
+    validate_label_key, RecurringInvocation, RetentionCandidate, ScheduleRecurrence,
+    WorkerResourceUsage,
+};
 use golem_common::model::{
     IdempotencyKey, OwnedWorkerId, ScanCursor, ShardId, Timestamp, TimestampedWorkerInvocation,
     WorkerEvent, WorkerFilter, WorkerId, WorkerInvocation, WorkerMetadata, WorkerStatus,
@@ -71,11 +85,12 @@ use golem_service_base::model::GetFileSystemNodeResult;
 use golem_wasm::protobuf::Val;
 use golem_wasm::ValueAndType;
 use std::cmp::min;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tokio;
 use tokio::sync::broadcast::error::RecvError;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
@@ -302,7 +317,10 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 
         self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
 
-        let data = request.data;
+        let outcome = match request.rejection {
+            Some(error) => common_model::PromiseOutcome::Rejected(error),
+            None => common_model::PromiseOutcome::Completed(request.data),
+        };
 
         let promise_id: common_model::PromiseId = promise_id
             .clone()
@@ -311,7 +329,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 
         let completed = self
             .promise_service()
-            .complete(promise_id, data, auth_ctx.account_id())
+            .complete(promise_id, outcome, auth_ctx.account_id())
             .await?;
 
         let success = golem::workerexecutor::v1::CompletePromiseSuccess { completed };
@@ -335,44 +353,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 WorkerExecutorError::invalid_request(format!("failed converting auth_ctx: {e}"))
             })?;
 
-        if Worker::<Ctx>::get_latest_metadata(&self.services, &owned_worker_id)
-            .await
-            .is_some()
-        {
-            let worker = Worker::get_or_create_suspended(
-                self,
-                auth_ctx.account_id(),
-                &owned_worker_id,
-                None,
-                None,
-                None,
-                None,
-                &InvocationContextStack::fresh(),
-            )
-            .await?;
-
-            info!("Interrupting worker before deletion");
-            if let Some(mut rx) = worker
-                .set_interrupting(InterruptKind::Interrupt(Timestamp::now_utc()))
-                .await
-            {
-                info!("Awaiting interruption");
-                let _ = rx.recv().await;
-                info!("Interrupted");
-            }
-            info!("Marking worker for deletion");
-            worker.start_deleting().await?;
-
-            self.worker_service().remove(&owned_worker_id).await;
-            self.active_workers()
-                .remove(&owned_worker_id.worker_id)
-                .await;
-
-            // ensure we are holding the worker while we are doing cleanup.
-            drop(worker);
-        }
-
-        Ok(())
+        Worker::<Ctx>::delete(self, auth_ctx.account_id(), &owned_worker_id).await
     }
 
     async fn fork_worker_internal(
@@ -544,6 +525,156 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn schedule_recurring_invocation_internal(
+        &self,
+        request: &ScheduleRecurringInvocationRequest,
+    ) -> Result<RecurringInvocation, WorkerExecutorError> {
+        let owned_worker_id = OwnedWorkerId::new(request.environment_id()?, &request.worker_id()?);
+
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let account_id: AccountId = request
+            .component_owner_account_id
+            .ok_or(WorkerExecutorError::invalid_request("account_id not found"))?
+            .try_into()
+            .map_err(|e| {
+                WorkerExecutorError::invalid_request(format!("Invalid account id: {e}"))
+            })?;
+
+        let recurrence: ScheduleRecurrence = request
+            .recurrence
+            .clone()
+            .ok_or(WorkerExecutorError::invalid_request("recurrence not found"))?
+            .try_into()
+            .map_err(WorkerExecutorError::invalid_request)?;
+        recurrence
+            .validate()
+            .map_err(WorkerExecutorError::invalid_request)?;
+
+        let idempotency_key = request
+            .idempotency_key()?
+            .unwrap_or(IdempotencyKey::fresh());
+
+        // Scheduling the same recurring invocation again is a no-op
+        if let Some(existing) = self
+            .scheduler_service()
+            .get_recurring(&owned_worker_id)
+            .await
+            .map_err(WorkerExecutorError::runtime)?
+            .into_iter()
+            .find(|recurring| recurring.idempotency_key == idempotency_key)
+        {
+            return Ok(existing);
+        }
+
+        let start_at = request
+            .start_at
+            .map(Timestamp::from)
+            .unwrap_or_else(Timestamp::now_utc)
+            .to_millis() as i64;
+        let first_occurrence = recurrence
+            .next_occurrence(start_at, start_at)
+            .map_err(WorkerExecutorError::invalid_request)?
+            .ok_or(WorkerExecutorError::invalid_request(
+                "The recurrence has no occurrences before its end",
+            ))?;
+        let first_occurrence_time = chrono::DateTime::from_timestamp_millis(first_occurrence)
+            .ok_or(WorkerExecutorError::invalid_request(
+                "The first occurrence is out of range",
+            ))?;
+
+        let worker = self.get_or_create_pending(request).await?;
+
+        let function_input = request
+            .input(&worker)
+            .await?
+            .iter()
+            .map(|val| val.clone().try_into())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|msg| WorkerExecutorError::ValueMismatch { details: msg })?;
+
+        let full_function_name = request.name();
+        self.scheduler_service()
+            .schedule(
+                first_occurrence_time,
+                common_model::ScheduledAction::RecurringInvoke {
+                    account_id,
+                    owned_worker_id,
+                    idempotency_key: idempotency_key.clone(),
+                    full_function_name: full_function_name.clone(),
+                    function_input,
+                    invocation_context: request.invocation_context(),
+                    recurrence: recurrence.clone(),
+                },
+            )
+            .await;
+
+        Ok(RecurringInvocation {
+            idempotency_key,
+            function_name: full_function_name,
+            recurrence,
+            next_occurrence: Timestamp::from(first_occurrence as u64),
+        })
+    }
+
+    async fn get_recurring_invocations_internal(
+        &self,
+        request: GetRecurringInvocationsRequest,
+    ) -> Result<Vec<RecurringInvocation>, WorkerExecutorError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        self.scheduler_service()
+            .get_recurring(&owned_worker_id)
+            .await
+            .map_err(WorkerExecutorError::runtime)
+    }
+
+    async fn cancel_recurring_invocation_internal(
+        &self,
+        request: CancelRecurringInvocationRequest,
+    ) -> Result<bool, WorkerExecutorError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let idempotency_key = request
+            .idempotency_key
+            .ok_or(WorkerExecutorError::invalid_request(
+                "idempotency_key not found",
+            ))?
+            .into();
+
+        self.scheduler_service()
+            .cancel_recurring(&owned_worker_id, &idempotency_key)
+            .await
+            .map_err(WorkerExecutorError::runtime)
+    }
+
+    async fn get_retention_report_internal(
+        &self,
+        request: GetRetentionReportRequest,
+    ) -> Result<Vec<RetentionCandidate>, WorkerExecutorError> {
+        let component_id: ComponentId = request
+            .component_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(WorkerExecutorError::invalid_request("Invalid component id"))?;
+
+        let environment_id: EnvironmentId = request
+            .environment_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(WorkerExecutorError::invalid_request(
+                "Invalid environment id",
+            ))?;
+
+        AgentRetention::new(self.services.clone(), self.config().retention.clone())
+            .find_candidates(&environment_id, &component_id, Timestamp::now_utc())
+            .await
+    }
+
     async fn interrupt_worker_internal(
         &self,
         request: golem::workerexecutor::v1::InterruptWorkerRequest,
@@ -853,25 +984,69 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
     async fn revoke_shards_internal(
         &self,
         request: golem::workerexecutor::v1::RevokeShardsRequest,
-    ) -> Result<(), WorkerExecutorError> {
-        let proto_shard_ids = request.shard_ids;
-
-        let shard_ids = proto_shard_ids.into_iter().map(ShardId::from).collect();
+    ) -> Result<golem::workerexecutor::v1::RevokeShardsSuccess, WorkerExecutorError> {
+        let start = Instant::now();
+        let shard_ids = request.shard_ids.into_iter().map(ShardId::from).collect();
+        let drain_timeout = request.drain_timeout_millis.map(Duration::from_millis);
+        let snapshot_on_drain = request.snapshot_on_drain;
 
+        // From this point new invocations targeting the revoked shards are rejected
         self.shard_service().revoke_shards(&shard_ids)?;
 
-        for (worker_id, worker_details) in self.active_workers().snapshot().await {
-            if self.shard_service().check_worker(&worker_id).is_err() {
-                if let Some(mut await_interrupted) = worker_details
-                    .set_interrupting(InterruptKind::Restart)
-                    .await
+        let revoked_workers = self
+            .active_workers()
+            .snapshot()
+            .await
+            .into_iter()
+            .filter(|(worker_id, _)| self.shard_service().check_worker(worker_id).is_err())
+            .collect::<Vec<_>>();
+
+        let deadline = drain_timeout.map(|timeout| tokio::time::Instant::now() + timeout);
+        let drained = join_all(revoked_workers.into_iter().map(|(worker_id, worker)| {
+            async move {
+                if let Some(deadline) = deadline {
+                    // Let the in-flight invocations finish, and hand over the agent with a committed oplog
+                    if snapshot_on_drain {
+                        match tokio::time::timeout_at(deadline, worker.take_snapshot()).await {
+                            Ok(Ok(_)) => {}
+                            Ok(Err(err)) => {
+                                warn!("Failed to take snapshot of {worker_id} before hand-over: {err}")
+                            }
+                            Err(_) => {
+                                warn!("Taking snapshot of {worker_id} before hand-over timed out")
+                            }
+                        }
+                    }
+                    if worker.stop_when_idle(deadline).await {
+                        return true;
+                    }
+                    debug!("Agent {worker_id} did not finish in time, interrupting it");
+                }
+
+                if let Some(mut await_interrupted) =
+                    worker.set_interrupting(InterruptKind::Restart).await
                 {
                     await_interrupted.recv().await.unwrap();
                 }
+                false
             }
-        }
+        }))
+        .await;
 
-        Ok(())
+        let drained_agents = drained.iter().filter(|drained| **drained).count() as u64;
+        let interrupted_agents = drained.len() as u64 - drained_agents;
+
+        let elapsed = start.elapsed();
+        crate::metrics::sharding::record_shard_drain(elapsed, drained_agents, interrupted_agents);
+        info!(
+            drained_agents,
+            interrupted_agents, "Handed over agents of revoked shards in {elapsed:?}"
+        );
+
+        Ok(golem::workerexecutor::v1::RevokeShardsSuccess {
+            drained_agents,
+            interrupted_agents,
+        })
     }
 
     async fn assign_shards_internal(
@@ -888,6 +1063,39 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         Ok(())
     }
 
+    async fn get_pod_capacity_internal(
+        &self,
+    ) -> Result<golem::workerexecutor::v1::PodCapacity, WorkerExecutorError> {
+        let number_of_shards = self.shard_service().current_assignment()?.number_of_shards;
+
+        let mut shard_loads = BTreeMap::<ShardId, u64>::new();
+        let active_workers = self.active_workers().snapshot().await;
+        for (worker_id, _) in &active_workers {
+            *shard_loads
+                .entry(ShardId::from_worker_id(worker_id, number_of_shards))
+                .or_default() += 1;
+        }
+
+        let config = self.config();
+        Ok(golem::workerexecutor::v1::PodCapacity {
+            total_memory_bytes: config.memory.total_system_memory(),
+            available_memory_bytes: config.memory.system_memory(),
+            cpu_count: std::thread::available_parallelism()
+                .map(|count| count.get() as u32)
+                .unwrap_or(1),
+            active_agents: active_workers.len() as u64,
+            shard_loads: shard_loads
+                .into_iter()
+                .map(
+                    |(shard_id, active_agents)| golem::workerexecutor::v1::ShardLoad {
+                        shard_id: Some(shard_id.into()),
+                        active_agents,
+                    },
+                )
+                .collect(),
+        })
+    }
+
     async fn get_worker_metadata_internal(
         &self,
         request: golem::workerexecutor::v1::GetWorkerMetadataRequest,
@@ -1302,6 +1510,114 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         })
     }
 
+    async fn export_oplog_internal(
+        &self,
+        request: ExportOplogRequest,
+    ) -> Result<<Self as WorkerExecutor>::ExportOplogStream, WorkerExecutorError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let archive = export_oplog(
+            self.component_service(),
+            self.oplog_service(),
+            self.file_loader(),
+            &owned_worker_id,
+        )
+        .await?;
+
+        Ok(Box::pin(archive.map(|chunk| {
+            let result = match chunk {
+                Ok(data) => golem::workerexecutor::v1::export_oplog_response::Result::Success(data),
+                Err(err) => {
+                    golem::workerexecutor::v1::export_oplog_response::Result::Failure(err.into())
+                }
+            };
+            Ok::<_, Status>(ExportOplogResponse {
+                result: Some(result),
+            })
+        })))
+    }
+
+    async fn import_oplog_internal(
+        &self,
+        header: ImportOplogRequestHeader,
+        archive: tonic::Streaming<ImportOplogRequest>,
+    ) -> Result<ImportOplogResponse, WorkerExecutorError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&header, |r| &r.worker_id, |r| &r.environment_id)?;
+
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let account_id: AccountId = header
+            .component_owner_account_id
+            .ok_or(WorkerExecutorError::invalid_request("account_id not found"))?
+            .try_into()
+            .map_err(|e| {
+                WorkerExecutorError::invalid_request(format!("Invalid account id: {e}"))
+            })?;
+
+        let archive = archive.map(|message| match message {
+            Ok(ImportOplogRequest {
+                request:
+                    Some(golem::workerexecutor::v1::import_oplog_request::Request::Chunk(chunk)),
+            }) => Ok(chunk),
+            Ok(_) => Err(WorkerExecutorError::invalid_request(
+                "Unexpected message in the oplog archive stream",
+            )),
+            Err(status) => Err(WorkerExecutorError::runtime(format!(
+                "Failed to receive the oplog archive: {status}"
+            ))),
+        });
+
+        let last_index = import_oplog(
+            self.component_service(),
+            self.oplog_service(),
+            self.file_loader(),
+            &owned_worker_id,
+            account_id,
+            archive,
+        )
+        .await?;
+
+        Ok(ImportOplogResponse {
+            result: Some(
+                golem::workerexecutor::v1::import_oplog_response::Result::Success(
+                    golem::workerexecutor::v1::ImportOplogSuccessResponse {
+                        last_index: last_index.into(),
+                    },
+                ),
+            ),
+        })
+    }
+
+    async fn search_component_oplog_internal(
+        &self,
+        request: SearchComponentOplogRequest,
+    ) -> Result<Vec<OplogSearchHit>, WorkerExecutorError> {
+        let component_id: ComponentId = request
+            .component_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(WorkerExecutorError::invalid_request("Invalid component id"))?;
+
+        let query = Query::parse(&request.query).map_err(|err| {
+            WorkerExecutorError::invalid_request(format!("Invalid oplog query: {err}"))
+        })?;
+        let count = min(
+            request.count,
+            self.services.config().limits.max_oplog_query_pages_size as u64,
+        );
+
+        // Only returning the workers owned by this executor, as the index may be shared
+        let shard_service = self.shard_service();
+        self.oplog_service()
+            .search_component_oplogs(&component_id, &query, count, &|worker_id| {
+                shard_service.check_worker(worker_id).is_ok()
+            })
+            .await
+    }
+
     async fn search_oplog_internal(
         &self,
         request: SearchOplogRequest,
@@ -1648,6 +1964,50 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn change_worker_labels_internal(
+        &self,
+        request: ChangeWorkerLabelsRequest,
+    ) -> Result<(), WorkerExecutorError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.environment_id)?;
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let auth_ctx: AuthCtx = request
+            .auth_ctx
+            .ok_or(WorkerExecutorError::invalid_request("auth_ctx not found"))?
+            .try_into()
+            .map_err(|e| {
+                WorkerExecutorError::invalid_request(format!("failed converting auth_ctx: {e}"))
+            })?;
+
+        let set: BTreeMap<String, String> = request.set.into_iter().collect();
+        let removed: BTreeSet<String> = request.removed.into_iter().collect();
+
+        set.keys()
+            .chain(removed.iter())
+            .try_for_each(|key| validate_label_key(key))
+            .map_err(WorkerExecutorError::invalid_request)?;
+
+        Worker::<Ctx>::get_latest_metadata(&self.services, &owned_worker_id)
+            .await
+            .ok_or(WorkerExecutorError::worker_not_found(
+                owned_worker_id.worker_id(),
+            ))?;
+
+        let worker = Worker::get_or_create_suspended(
+            self,
+            auth_ctx.account_id(),
+            &owned_worker_id,
+            None,
+            None,
+            None,
+            None,
+            &InvocationContextStack::fresh(),
+        )
+        .await?;
+        worker.change_labels(set, removed).await
+    }
+
     fn create_proto_metadata(
         metadata: WorkerMetadata,
         last_error_and_retry_count: Option<LastError>,
@@ -1735,6 +2095,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 .map(|last_error| last_error.error.to_string(&last_error.stderr)),
             component_size: latest_status.component_size,
             total_linear_memory_size: latest_status.total_linear_memory_size,
+            resource_usage: Some(WorkerResourceUsage::from(latest_status.resource_usage).into()),
+            labels: latest_status.labels.into_iter().collect(),
             owned_resources,
             active_plugins: active_plugins.into_iter().map(|id| id.0).collect(),
             skipped_regions: latest_status
@@ -2150,12 +2512,10 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             .instrument(record.span.clone())
             .await
         {
-            Ok(_) => record.succeed(Ok(Response::new(
+            Ok(success) => record.succeed(Ok(Response::new(
                 golem::workerexecutor::v1::RevokeShardsResponse {
                     result: Some(
-                        golem::workerexecutor::v1::revoke_shards_response::Result::Success(
-                            golem::common::Empty {},
-                        ),
+                        golem::workerexecutor::v1::revoke_shards_response::Result::Success(success),
                     ),
                 },
             ))),
@@ -2210,6 +2570,41 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_pod_capacity(
+        &self,
+        _request: Request<golem::workerexecutor::v1::GetPodCapacityRequest>,
+    ) -> Result<Response<golem::workerexecutor::v1::GetPodCapacityResponse>, Status> {
+        let record = recorded_grpc_api_request!("get_pod_capacity",);
+
+        match self
+            .get_pod_capacity_internal()
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(capacity) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::v1::GetPodCapacityResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_pod_capacity_response::Result::Success(
+                            capacity,
+                        ),
+                    ),
+                },
+            ))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(
+                    golem::workerexecutor::v1::GetPodCapacityResponse {
+                        result: Some(
+                            golem::workerexecutor::v1::get_pod_capacity_response::Result::Failure(
+                                err.clone().into(),
+                            ),
+                        ),
+                    },
+                )),
+                &mut err,
+            ),
+        }
+    }
+
     async fn get_worker_metadata(
         &self,
         request: Request<golem::workerexecutor::v1::GetWorkerMetadataRequest>,
@@ -2473,6 +2868,133 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn search_component_oplog(
+        &self,
+        request: Request<SearchComponentOplogRequest>,
+    ) -> Result<Response<SearchComponentOplogResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "search_component_oplog",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let result = self
+            .search_component_oplog_internal(request)
+            .instrument(record.span.clone())
+            .await
+            .and_then(|hits| {
+                hits.into_iter()
+                    .map(|hit| hit.try_into())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(WorkerExecutorError::unknown)
+            });
+        match result {
+            Ok(hits) => record.succeed(Ok(Response::new(SearchComponentOplogResponse {
+                result: Some(
+                    golem::workerexecutor::v1::search_component_oplog_response::Result::Success(
+                        golem::workerexecutor::v1::SearchComponentOplogSuccessResponse { hits },
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(SearchComponentOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::search_component_oplog_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
+    type ExportOplogStream =
+        Pin<Box<dyn Stream<Item = Result<ExportOplogResponse, Status>> + Send + 'static>>;
+
+    async fn export_oplog(
+        &self,
+        request: Request<ExportOplogRequest>,
+    ) -> ResponseResult<Self::ExportOplogStream> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "export_oplog",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        let result = self
+            .export_oplog_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        let stream: Self::ExportOplogStream = match result {
+            Ok(stream) => record.succeed(stream),
+            Err(mut err) => {
+                let res = ExportOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::export_oplog_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                };
+
+                let err_stream: Self::ExportOplogStream =
+                    Box::pin(tokio_stream::iter(vec![Ok(res)]));
+
+                record.fail(err_stream, &mut err)
+            }
+        };
+        Ok(Response::new(stream))
+    }
+
+    async fn import_oplog(
+        &self,
+        request: Request<tonic::Streaming<ImportOplogRequest>>,
+    ) -> Result<Response<ImportOplogResponse>, Status> {
+        let mut archive = request.into_inner();
+        let header = match archive.message().await? {
+            Some(ImportOplogRequest {
+                request:
+                    Some(golem::workerexecutor::v1::import_oplog_request::Request::Header(header)),
+            }) => header,
+            _ => {
+                let err = WorkerExecutorError::invalid_request(
+                    "The oplog archive stream must start with a header",
+                );
+                return Ok(Response::new(ImportOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::import_oplog_response::Result::Failure(
+                            err.into(),
+                        ),
+                    ),
+                }));
+            }
+        };
+
+        let record = recorded_grpc_api_request!(
+            "import_oplog",
+            worker_id = proto_worker_id_string(&header.worker_id),
+        );
+
+        let result = self
+            .import_oplog_internal(header, archive)
+            .instrument(record.span.clone())
+            .await;
+        match result {
+            Ok(response) => record.succeed(Ok(Response::new(response))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(ImportOplogResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::import_oplog_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
     async fn fork_worker(
         &self,
         request: Request<ForkWorkerRequest>,
@@ -2586,6 +3108,161 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn schedule_recurring_invocation(
+        &self,
+        request: Request<ScheduleRecurringInvocationRequest>,
+    ) -> Result<Response<ScheduleRecurringInvocationResponse>, Status> {
+        let request = request.into_inner();
+
+        let record = recorded_grpc_api_request!(
+            "schedule_recurring_invocation",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            idempotency_key = proto_idempotency_key_string(&request.idempotency_key),
+            function = request.name,
+        );
+
+        let result = self
+            .schedule_recurring_invocation_internal(&request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(recurring) => record.succeed(Ok(Response::new(ScheduleRecurringInvocationResponse {
+                result: Some(
+                    golem::workerexecutor::v1::schedule_recurring_invocation_response::Result::Success(
+                        recurring.into(),
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(ScheduleRecurringInvocationResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::schedule_recurring_invocation_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
+    async fn get_recurring_invocations(
+        &self,
+        request: Request<GetRecurringInvocationsRequest>,
+    ) -> Result<Response<GetRecurringInvocationsResponse>, Status> {
+        let request = request.into_inner();
+
+        let record = recorded_grpc_api_request!(
+            "get_recurring_invocations",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        let result = self
+            .get_recurring_invocations_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(invocations) => record.succeed(Ok(Response::new(GetRecurringInvocationsResponse {
+                result: Some(
+                    golem::workerexecutor::v1::get_recurring_invocations_response::Result::Success(
+                        golem::workerexecutor::v1::GetRecurringInvocationsSuccessResponse {
+                            invocations: invocations.into_iter().map(|i| i.into()).collect(),
+                        },
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(GetRecurringInvocationsResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_recurring_invocations_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
+    async fn cancel_recurring_invocation(
+        &self,
+        request: Request<CancelRecurringInvocationRequest>,
+    ) -> Result<Response<CancelRecurringInvocationResponse>, Status> {
+        let request = request.into_inner();
+
+        let record = recorded_grpc_api_request!(
+            "cancel_recurring_invocation",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            idempotency_key = proto_idempotency_key_string(&request.idempotency_key),
+        );
+
+        let result = self
+            .cancel_recurring_invocation_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(canceled) => record.succeed(Ok(Response::new(CancelRecurringInvocationResponse {
+                result: Some(
+                    golem::workerexecutor::v1::cancel_recurring_invocation_response::Result::Success(
+                        canceled,
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(CancelRecurringInvocationResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::cancel_recurring_invocation_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
+    async fn get_retention_report(
+        &self,
+        request: Request<GetRetentionReportRequest>,
+    ) -> Result<Response<GetRetentionReportResponse>, Status> {
+        let request = request.into_inner();
+
+        let record = recorded_grpc_api_request!(
+            "get_retention_report",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let result = self
+            .get_retention_report_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(candidates) => record.succeed(Ok(Response::new(GetRetentionReportResponse {
+                result: Some(
+                    golem::workerexecutor::v1::get_retention_report_response::Result::Success(
+                        golem::workerexecutor::v1::GetRetentionReportSuccessResponse {
+                            candidates: candidates.into_iter().map(|c| c.into()).collect(),
+                        },
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(GetRetentionReportResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_retention_report_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
+
     async fn get_file_system_node(
         &self,
         request: Request<GetFileSystemNodeRequest>,
@@ -2728,6 +3405,42 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             ),
         }
     }
+
+    async fn change_worker_labels(
+        &self,
+        request: Request<ChangeWorkerLabelsRequest>,
+    ) -> Result<Response<ChangeWorkerLabelsResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "change_worker_labels",
+            worker_id = proto_worker_id_string(&request.worker_id),
+        );
+
+        let result = self
+            .change_worker_labels_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(_) => record.succeed(Ok(Response::new(ChangeWorkerLabelsResponse {
+                result: Some(
+                    golem::workerexecutor::v1::change_worker_labels_response::Result::Success(
+                        golem::common::Empty {},
+                    ),
+                ),
+            }))),
+            Err(mut err) => record.fail(
+                Ok(Response::new(ChangeWorkerLabelsResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::change_worker_labels_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &mut err,
+            ),
+        }
+    }
 }
 
 pub struct WorkerEventStream {
@@ -37,17 +37,26 @@ use crate::services::blob_store::{BlobStoreService, DefaultBlobStoreService};
 use crate::services::component::ComponentService;
 use crate::services::events::Events;
 use crate::services::golem_config::{
-    EngineConfig, GolemConfig, IndexedStorageConfig, KeyValueStorageConfig,
+    EngineConfig, GolemConfig, IndexedStorageConfig, KeyValueStorageConfig, OplogEncryptionConfig,
+    OplogSearchIndexStorageConfig,
 };
+use crate::services::key_rotation::OplogKeyRotation;
 use crate::services::key_value::{DefaultKeyValueService, KeyValueService};
+use crate::services::oplog::compression::OplogCompression;
+use crate::services::oplog::encryption::OplogEncryption;
+use crate::services::oplog::indexing::IndexingOplogService;
 use crate::services::oplog::plugin::{
     ForwardingOplogService, OplogProcessorPlugin, PerExecutorOplogProcessorPlugin,
 };
+use crate::services::oplog::search_index::{
+    OplogSearchIndex, PostgresOplogSearchIndex, SqliteOplogSearchIndex,
+};
 use crate::services::oplog::{
     BlobOplogArchiveService, CompressedOplogArchiveService, MultiLayerOplogService,
     OplogArchiveService, OplogService, PrimaryOplogService,
 };
 use crate::services::promise::{DefaultPromiseService, PromiseService};
+use crate::services::retention::AgentRetention;
 use crate::services::scheduler::{SchedulerService, SchedulerServiceDefault};
 use crate::services::shard::{ShardService, ShardServiceDefault};
 use crate::services::shard_manager::ShardManagerService;
@@ -60,11 +69,13 @@ use crate::services::worker_enumeration::{
 use crate::services::worker_proxy::{RemoteWorkerProxy, WorkerProxy};
 use crate::services::{rdbms, shard_manager, All, HasConfig};
 use crate::storage::indexed::multi_sqlite::MultiSqliteIndexedStorage;
+use crate::storage::indexed::postgres::PostgresIndexedStorage;
 use crate::storage::indexed::redis::RedisIndexedStorage;
 use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use crate::storage::indexed::IndexedStorage;
 use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
 use crate::storage::keyvalue::multi_sqlite::MultiSqliteKeyValueStorage;
+use crate::storage::keyvalue::postgres::PostgresKeyValueStorage;
 use crate::storage::keyvalue::redis::RedisKeyValueStorage;
 use crate::storage::keyvalue::KeyValueStorage;
 use crate::workerctx::WorkerCtx;
@@ -73,9 +84,11 @@ use async_trait::async_trait;
 use futures::TryFutureExt;
 use golem_api_grpc::proto;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_server::WorkerExecutorServer;
+use golem_common::model::component_bundle::wasmtime_config;
 use golem_common::redis::RedisPool;
 use golem_service_base::clients::registry::{GrpcRegistryService, RegistryService};
 use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::db::postgres::PostgresPool;
 use golem_service_base::db::sqlite::SqlitePool;
 use golem_service_base::grpc::server::GrpcServerTlsConfig;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
@@ -100,7 +113,7 @@ use tonic_tracing_opentelemetry::middleware;
 use tonic_tracing_opentelemetry::middleware::filters;
 use tracing::{info, Instrument};
 use wasmtime::component::Linker;
-use wasmtime::{Config, Engine, WasmBacktraceDetails};
+use wasmtime::{Config, Engine};
 
 pub struct RunDetails {
     pub http_port: u16,
@@ -216,14 +229,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
 
     /// Can be overridden to customize the wasmtime configuration
     fn create_wasmtime_config(&self, engine_config: &EngineConfig) -> Config {
-        let mut config = Config::default();
-
-        config.wasm_multi_value(true);
-        config.async_support(true);
-        config.wasm_component_model(true);
-        config.epoch_interruption(true);
-        config.consume_fuel(true);
-        config.wasm_backtrace_details(WasmBacktraceDetails::Enable);
+        let mut config = wasmtime_config();
 
         if engine_config.enable_fs_cache {
             config.cache(Some(
@@ -295,9 +301,10 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
     runtime: Handle,
     lazy_worker_activator: &Arc<LazyWorkerActivator<Ctx>>,
 ) -> Result<(All<Ctx>, std::thread::JoinHandle<()>), anyhow::Error> {
-    let (redis, sqlite, key_value_storage): (
+    let (redis, sqlite, postgres, key_value_storage): (
         Option<RedisPool>,
         Option<SqlitePool>,
+        Option<PostgresPool>,
         Arc<dyn KeyValueStorage + Send + Sync>,
     ) = match &golem_config.key_value_storage {
         KeyValueStorageConfig::Redis(redis) => {
@@ -306,10 +313,10 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                 .map_err(|err| anyhow!(err))?;
             let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
                 Arc::new(RedisKeyValueStorage::new(pool.clone()));
-            (Some(pool), None, key_value_storage)
+            (Some(pool), None, None, key_value_storage)
         }
         KeyValueStorageConfig::InMemory(_) => {
-            (None, None, Arc::new(InMemoryKeyValueStorage::new()))
+            (None, None, None, Arc::new(InMemoryKeyValueStorage::new()))
         }
         KeyValueStorageConfig::Sqlite(sqlite) => {
             let pool = SqlitePool::configured(sqlite)
@@ -320,7 +327,7 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                     .await
                     .map_err(|err| anyhow!(err))?,
             );
-            (None, Some(pool), key_value_storage)
+            (None, Some(pool), None, key_value_storage)
         }
         KeyValueStorageConfig::MultiSqlite(multi_sqlite) => {
             let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
@@ -329,7 +336,14 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                     multi_sqlite.max_connections,
                     multi_sqlite.foreign_keys,
                 ));
-            (None, None, key_value_storage)
+            (None, None, None, key_value_storage)
+        }
+        KeyValueStorageConfig::Postgres(postgres) => {
+            storage::migrate_postgres(postgres).await?;
+            let pool = PostgresPool::configured(postgres).await?;
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                Arc::new(PostgresKeyValueStorage::new(pool.clone()));
+            (None, None, Some(pool), key_value_storage)
         }
     };
 
@@ -354,6 +368,12 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                     .map_err(|err| anyhow!(err))?,
             )
         }
+        IndexedStorageConfig::KVStorePostgres(_) => {
+            let postgres = postgres.clone().expect(
+                "Postgres must be configured as key-value storage when using KVStorePostgres",
+            );
+            Arc::new(PostgresIndexedStorage::new(postgres))
+        }
         IndexedStorageConfig::KVStoreMultiSqlite(_) => {
             match &golem_config.key_value_storage {
                 KeyValueStorageConfig::MultiSqlite(multi_sqlite) =>
@@ -382,6 +402,11 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                 multi_sqlite.foreign_keys,
             ))
         }
+        IndexedStorageConfig::Postgres(postgres) => {
+            storage::migrate_postgres(postgres).await?;
+            let pool = PostgresPool::configured(postgres).await?;
+            Arc::new(PostgresIndexedStorage::new(pool))
+        }
         IndexedStorageConfig::InMemory(_) => {
             Arc::new(storage::indexed::memory::InMemoryIndexedStorage::new())
         }
@@ -439,17 +464,27 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
 
     let shard_service = Arc::new(ShardServiceDefault::new());
 
+    let oplog_compression =
+        OplogCompression::configured(&golem_config.oplog.compression, indexed_storage.clone());
+    let oplog_encryption = OplogEncryption::configured(&golem_config.oplog.encryption);
+
     let mut oplog_archives: Vec<Arc<dyn OplogArchiveService>> = Vec::new();
     for idx in 1..golem_config.oplog.indexed_storage_layers {
         let svc: Arc<dyn OplogArchiveService> = Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             idx,
+            oplog_compression.clone(),
+            oplog_encryption.clone(),
         ));
         oplog_archives.push(svc);
     }
     for idx in 0..golem_config.oplog.blob_storage_layers {
-        let svc: Arc<dyn OplogArchiveService> =
-            Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), idx));
+        let svc: Arc<dyn OplogArchiveService> = Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            idx,
+            oplog_compression.clone(),
+            oplog_encryption.clone(),
+        ));
         oplog_archives.push(svc);
     }
     let oplog_archives = NEVec::try_from_vec(oplog_archives);
@@ -462,6 +497,7 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                 golem_config.oplog.max_operations_before_commit,
                 golem_config.oplog.max_operations_before_commit_ephemeral,
                 golem_config.oplog.max_payload_size,
+                oplog_encryption.clone(),
             )
             .await,
         ),
@@ -473,6 +509,7 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
                     golem_config.oplog.max_operations_before_commit,
                     golem_config.oplog.max_operations_before_commit_ephemeral,
                     golem_config.oplog.max_payload_size,
+                    oplog_encryption.clone(),
                 )
                 .await,
             );
@@ -534,6 +571,32 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
         component_service.clone(),
     ));
 
+    let oplog_search_index: Option<Arc<dyn OplogSearchIndex>> =
+        match &golem_config.oplog_search_index.storage {
+            OplogSearchIndexStorageConfig::Disabled(_) => None,
+            OplogSearchIndexStorageConfig::Sqlite(sqlite) => {
+                storage::migrate_sqlite(sqlite).await?;
+                let pool = SqlitePool::configured(sqlite)
+                    .await
+                    .map_err(|err| anyhow!(err))?;
+                Some(Arc::new(SqliteOplogSearchIndex::new(pool)))
+            }
+            OplogSearchIndexStorageConfig::Postgres(postgres) => {
+                storage::migrate_postgres(postgres).await?;
+                let pool = PostgresPool::configured(postgres).await?;
+                Some(Arc::new(PostgresOplogSearchIndex::new(pool)))
+            }
+        };
+    let oplog_service: Arc<dyn OplogService> = match oplog_search_index {
+        Some(index) => Arc::new(IndexingOplogService::new(
+            oplog_service,
+            index,
+            component_service.clone(),
+            golem_config.oplog_search_index.clone(),
+        )),
+        None => oplog_service,
+    };
+
     let worker_service = Arc::new(DefaultWorkerService::new(
         key_value_storage.clone(),
         shard_service.clone(),
@@ -594,5 +657,14 @@ pub async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Si
         ))
         .await;
 
+    if golem_config.retention.enabled {
+        AgentRetention::new(all.clone(), golem_config.retention.clone()).start(&runtime);
+    }
+
+    if let OplogEncryptionConfig::LocalFile(config) = &golem_config.oplog.encryption {
+        OplogKeyRotation::new(all.clone(), oplog_encryption, config.refresh_interval)
+            .start(&runtime);
+    }
+
     Ok((all, epoch_thread))
 }
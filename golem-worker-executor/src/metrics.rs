@@ -150,17 +150,41 @@ pub mod promises {
 }
 
 pub mod sharding {
+    use std::time::Duration;
+
     use lazy_static::lazy_static;
     use prometheus::*;
 
     lazy_static! {
         static ref ASSIGNED_SHARD_COUNT: Gauge =
             register_gauge!("assigned_shard_count", "Current number of assigned shards").unwrap();
+        static ref SHARD_DRAIN_SECONDS: Histogram = register_histogram!(
+            "shard_drain_seconds",
+            "Time taken to hand over the agents of revoked shards",
+            golem_common::metrics::DEFAULT_TIME_BUCKETS.to_vec()
+        )
+        .unwrap();
+        static ref SHARD_DRAIN_AGENTS_TOTAL: CounterVec = register_counter_vec!(
+            "shard_drain_agents_total",
+            "Number of agents handed over because of shard revocation",
+            &["outcome"]
+        )
+        .unwrap();
     }
 
     pub fn record_assigned_shard_count(size: usize) {
         ASSIGNED_SHARD_COUNT.set(size as f64);
     }
+
+    pub fn record_shard_drain(duration: Duration, drained: u64, interrupted: u64) {
+        SHARD_DRAIN_SECONDS.observe(duration.as_secs_f64());
+        SHARD_DRAIN_AGENTS_TOTAL
+            .with_label_values(&["drained"])
+            .inc_by(drained as f64);
+        SHARD_DRAIN_AGENTS_TOTAL
+            .with_label_values(&["interrupted"])
+            .inc_by(interrupted as f64);
+    }
 }
 
 pub mod wasm {
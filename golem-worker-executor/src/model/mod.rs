@@ -66,6 +66,8 @@ pub struct WorkerConfig {
     pub component_revision_for_replay: ComponentRevision,
     pub created_by: AccountId,
     pub initial_wasi_config_vars: BTreeMap<String, String>,
+    /// Index of the automatic snapshot to load before replaying the rest of the oplog
+    pub snapshot_index: Option<OplogIndex>,
 }
 
 impl WorkerConfig {
@@ -75,6 +77,7 @@ impl WorkerConfig {
         component_revision_for_replay: ComponentRevision,
         created_by: AccountId,
         initial_wasi_config_vars: BTreeMap<String, String>,
+        snapshot_index: Option<OplogIndex>,
     ) -> WorkerConfig {
         WorkerConfig {
             deleted_regions,
@@ -82,6 +85,7 @@ impl WorkerConfig {
             component_revision_for_replay,
             created_by,
             initial_wasi_config_vars,
+            snapshot_index,
         }
     }
 
@@ -278,6 +282,12 @@ impl TrapType {
                                     retry_from,
                                 }
                             }
+                            Some(WorkerExecutorError::AgentQuotaExceeded { details }) => {
+                                TrapType::Error {
+                                    error: WorkerError::QuotaExceeded(details.clone()),
+                                    retry_from,
+                                }
+                            }
                             _ => TrapType::Error {
                                 error: WorkerError::Unknown(format!("{error:#}")),
                                 retry_from,
@@ -298,6 +308,9 @@ impl TrapType {
                 WorkerError::InvalidRequest(msg) => {
                     Some(WorkerExecutorError::invalid_request(msg.clone()))
                 }
+                WorkerError::QuotaExceeded(msg) => Some(WorkerExecutorError::AgentQuotaExceeded {
+                    details: msg.clone(),
+                }),
                 _ => Some(WorkerExecutorError::InvocationFailed {
                     error: error.clone(),
                     stderr: error_logs.to_string(),
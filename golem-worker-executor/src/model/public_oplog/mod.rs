@@ -23,24 +23,24 @@ use golem_common::model::component::{ComponentRevision, InstalledPlugin};
 use golem_common::model::lucene::Query;
 use golem_common::model::oplog::public_oplog_entry::{
     ActivatePluginParams, BeginAtomicRegionParams, BeginRemoteTransactionParams,
-    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangePersistenceLevelParams,
-    ChangeRetryPolicyParams, CommittedRemoteTransactionParams, CreateParams, CreateResourceParams,
-    DeactivatePluginParams, DropResourceParams, EndAtomicRegionParams, EndRemoteWriteParams,
-    ErrorParams, ExitedParams, ExportedFunctionCompletedParams, ExportedFunctionInvokedParams,
-    FailedUpdateParams, FinishSpanParams, GrowMemoryParams, ImportedFunctionInvokedParams,
-    InterruptedParams, JumpParams, LogParams, NoOpParams, PendingUpdateParams,
-    PendingWorkerInvocationParams, PreCommitRemoteTransactionParams,
-    PreRollbackRemoteTransactionParams, RestartParams, RevertParams,
-    RolledBackRemoteTransactionParams, SetSpanAttributeParams, StartSpanParams,
-    SuccessfulUpdateParams, SuspendParams,
+    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangeLabelsParams,
+    ChangePersistenceLevelParams, ChangeRetryPolicyParams, CommittedRemoteTransactionParams,
+    CreateParams, CreateResourceParams, DeactivatePluginParams, DropResourceParams,
+    EndAtomicRegionParams, EndRemoteWriteParams, ErrorParams, ExitedParams,
+    ExportedFunctionCompletedParams, ExportedFunctionInvokedParams, FailedUpdateParams,
+    FinishSpanParams, GrowMemoryParams, ImportedFunctionInvokedParams, InterruptedParams,
+    JumpParams, LogParams, NoOpParams, PendingUpdateParams, PendingWorkerInvocationParams,
+    PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams, RestartParams,
+    RevertParams, RolledBackRemoteTransactionParams, SetSpanAttributeParams, SnapshotParams,
+    StartSpanParams, SuccessfulUpdateParams, SuspendParams,
 };
 use golem_common::model::oplog::types::encode_span_data;
 use golem_common::model::oplog::{
     ExportedFunctionParameters, HostRequest, HostRequestGolemRpcInvoke,
-    HostRequestGolemRpcScheduledInvocation, HostResponse, ManualUpdateParameters, OplogEntry,
-    OplogIndex, PluginInstallationDescription, PublicAttribute, PublicOplogEntry,
-    PublicUpdateDescription, PublicWorkerInvocation, SnapshotBasedUpdateParameters,
-    UpdateDescription,
+    HostRequestGolemRpcRecurringInvocation, HostRequestGolemRpcScheduledInvocation, HostResponse,
+    ManualUpdateParameters, OplogEntry, OplogIndex, PluginInstallationDescription, PublicAttribute,
+    PublicOplogEntry, PublicUpdateDescription, PublicWorkerInvocation,
+    SnapshotBasedUpdateParameters, UpdateDescription,
 };
 use golem_common::model::{Empty, OwnedWorkerId, WorkerId, WorkerInvocation};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
@@ -283,6 +283,11 @@ impl PublicOplogEntryOps for PublicOplogEntry {
                             enrich_golem_rpc_scheduled_invocation(components, inner).await,
                         )
                     }
+                    HostRequest::GolemRpcRecurringInvocation(inner) => {
+                        HostRequest::GolemRpcRecurringInvocation(
+                            enrich_golem_rpc_recurring_invocation(components, inner).await,
+                        )
+                    }
                     other => other,
                 };
 
@@ -733,6 +738,24 @@ impl PublicOplogEntryOps for PublicOplogEntry {
                     begin_index,
                 },
             )),
+            OplogEntry::Snapshot { timestamp, data } => {
+                let data = oplog_service
+                    .download_payload(owned_worker_id, data)
+                    .await?;
+                Ok(PublicOplogEntry::Snapshot(SnapshotParams {
+                    timestamp,
+                    data,
+                }))
+            }
+            OplogEntry::ChangeLabels {
+                timestamp,
+                set,
+                removed,
+            } => Ok(PublicOplogEntry::ChangeLabels(ChangeLabelsParams {
+                timestamp,
+                set,
+                removed,
+            })),
         }
     }
 }
@@ -775,6 +798,18 @@ async fn enrich_golem_rpc_scheduled_invocation(
     payload
 }
 
+async fn enrich_golem_rpc_recurring_invocation(
+    components: Arc<dyn ComponentService>,
+    mut payload: HostRequestGolemRpcRecurringInvocation,
+) -> HostRequestGolemRpcRecurringInvocation {
+    let agent_id = try_resolve_agent_id(components, &payload.remote_worker_id).await;
+    payload.remote_agent_type = agent_id
+        .as_ref()
+        .map(|agent_id| agent_id.agent_type.clone());
+    payload.remote_agent_parameters = agent_id.map(|agent_id| agent_id.parameters);
+    payload
+}
+
 fn make_plugin_installation_description(
     installation: InstalledPlugin,
 ) -> PluginInstallationDescription {
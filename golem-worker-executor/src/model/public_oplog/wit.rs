@@ -17,19 +17,19 @@ use crate::preview2::wasi::clocks::wall_clock::Datetime;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::oplog::public_oplog_entry::{
     ActivatePluginParams, BeginAtomicRegionParams, BeginRemoteTransactionParams,
-    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangePersistenceLevelParams,
-    ChangeRetryPolicyParams, CommittedRemoteTransactionParams, CreateParams, CreateResourceParams,
-    DeactivatePluginParams, DropResourceParams, EndAtomicRegionParams, EndRemoteWriteParams,
-    ErrorParams, ExitedParams, ExportedFunctionCompletedParams, ExportedFunctionInvokedParams,
-    ExportedFunctionParameters, FailedUpdateParams, FinishSpanParams, GrowMemoryParams,
-    ImportedFunctionInvokedParams, InterruptedParams, JumpParams, LogParams,
-    ManualUpdateParameters, NoOpParams, PendingUpdateParams, PendingWorkerInvocationParams,
-    PluginInstallationDescription, PreCommitRemoteTransactionParams,
-    PreRollbackRemoteTransactionParams, PublicAttributeValue, PublicDurableFunctionType,
-    PublicRetryConfig, PublicSpanData, PublicWorkerInvocation, RestartParams, RevertParams,
-    RolledBackRemoteTransactionParams, SetSpanAttributeParams, StartSpanParams,
-    StringAttributeValue, SuccessfulUpdateParams, SuspendParams, WriteRemoteBatchedParameters,
-    WriteRemoteTransactionParameters,
+    BeginRemoteWriteParams, CancelPendingInvocationParams, ChangeLabelsParams,
+    ChangePersistenceLevelParams, ChangeRetryPolicyParams, CommittedRemoteTransactionParams,
+    CreateParams, CreateResourceParams, DeactivatePluginParams, DropResourceParams,
+    EndAtomicRegionParams, EndRemoteWriteParams, ErrorParams, ExitedParams,
+    ExportedFunctionCompletedParams, ExportedFunctionInvokedParams, ExportedFunctionParameters,
+    FailedUpdateParams, FinishSpanParams, GrowMemoryParams, ImportedFunctionInvokedParams,
+    InterruptedParams, JumpParams, LogParams, ManualUpdateParameters, NoOpParams,
+    PendingUpdateParams, PendingWorkerInvocationParams, PluginInstallationDescription,
+    PreCommitRemoteTransactionParams, PreRollbackRemoteTransactionParams, PublicAttributeValue,
+    PublicDurableFunctionType, PublicRetryConfig, PublicSpanData, PublicWorkerInvocation,
+    RestartParams, RevertParams, RolledBackRemoteTransactionParams, SetSpanAttributeParams,
+    SnapshotParams, StartSpanParams, StringAttributeValue, SuccessfulUpdateParams, SuspendParams,
+    WriteRemoteBatchedParameters, WriteRemoteTransactionParameters,
 };
 use golem_common::model::oplog::{
     PublicOplogEntry, PublicUpdateDescription, SnapshotBasedUpdateParameters,
@@ -353,6 +353,21 @@ impl From<PublicOplogEntry> for oplog::OplogEntry {
                 timestamp: timestamp.into(),
                 begin_index: begin_index.into(),
             }),
+            PublicOplogEntry::Snapshot(SnapshotParams { timestamp, data }) => {
+                Self::Snapshot(oplog::SnapshotParameters {
+                    timestamp: timestamp.into(),
+                    data,
+                })
+            }
+            PublicOplogEntry::ChangeLabels(ChangeLabelsParams {
+                timestamp,
+                set,
+                removed,
+            }) => Self::ChangeLabels(oplog::ChangeLabelsParameters {
+                timestamp: timestamp.into(),
+                set: set.into_iter().collect(),
+                removed: removed.into_iter().collect(),
+            }),
         }
     }
 }
@@ -36,7 +36,7 @@ wasmtime::component::bindgen!({
         "golem:api/oplog/get-oplog": super::durable_host::golem::v1x::GetOplogEntry,
         "golem:api/oplog/search-oplog": super::durable_host::golem::v1x::SearchOplogEntry,
         "golem:durability/durability/lazy-initialized-pollable": super::durable_host::durability::LazyInitializedPollableEntry,
-        "golem:rpc": golem_wasm::golem_rpc_0_2_x,
+        "golem:rpc": golem_wasm::golem_rpc_0_2_2,
         // shared wasi dependencies of golem:rpc/wasm-rpc and golem:api/golem
         "wasi:io/poll/pollable": golem_wasm::wasi::io::poll::Pollable,
         "golem:rdbms/mysql/db-connection": super::durable_host::rdbms::mysql::MysqlDbConnection,
@@ -57,5 +57,6 @@ pub type Pollable = golem_wasm::wasi::io::poll::Pollable;
 
 // reexports so that we don't have to change version numbers everywhere
 pub use self::golem::api1_3_0 as golem_api_1_x;
+pub use self::golem::api1_4_0 as golem_api_1_4;
 pub use self::golem::durability as golem_durability;
 pub use golem_common::model::agent::bindings::golem::agent as golem_agent;
@@ -17,7 +17,9 @@ use async_lock::Mutex;
 use futures::TryStreamExt;
 use golem_common::model::component::ComponentFileContentHash;
 use golem_common::model::environment::EnvironmentId;
+use golem_common::widen_infallible;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_service_base::replayable_stream::ReplayableStream;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use std::collections::HashMap;
 use std::path::Path;
@@ -102,6 +104,37 @@ impl FileLoader {
             })
     }
 
+    /// Reads the whole content of an initial component file into memory.
+    pub async fn read_to_vec(
+        &self,
+        environment_id: EnvironmentId,
+        key: ComponentFileContentHash,
+    ) -> Result<Vec<u8>, WorkerExecutorError> {
+        self.read_to_vec_impl(environment_id, key)
+            .await
+            .map_err(|e| {
+                WorkerExecutorError::initial_file_download_failed(key.to_string(), e.to_string())
+            })
+    }
+
+    /// Stores an initial component file if it does not exist yet, returning its content hash.
+    pub async fn store(
+        &self,
+        environment_id: EnvironmentId,
+        data: Vec<u8>,
+    ) -> Result<ComponentFileContentHash, WorkerExecutorError> {
+        self.initial_component_files_service
+            .put_if_not_exists(
+                environment_id,
+                data.map_error(widen_infallible::<anyhow::Error>)
+                    .map_item(|i| i.map_err(widen_infallible::<anyhow::Error>)),
+            )
+            .await
+            .map_err(|e| {
+                WorkerExecutorError::runtime(format!("Failed to store initial component file: {e}"))
+            })
+    }
+
     async fn get_read_only_to_impl(
         &self,
         environment_id: EnvironmentId,
@@ -244,6 +277,25 @@ impl FileLoader {
         Ok(cache_entry)
     }
 
+    async fn read_to_vec_impl(
+        &self,
+        environment_id: EnvironmentId,
+        key: ComponentFileContentHash,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let mut data = self
+            .initial_component_files_service
+            .get(environment_id, key)
+            .await
+            .map_err(|e| anyhow!(e))?
+            .ok_or_else(|| anyhow!("File not found"))?;
+
+        let mut result = Vec::new();
+        while let Some(chunk) = data.try_next().await.map_err(|e| anyhow!(e))? {
+            result.extend_from_slice(&chunk);
+        }
+        Ok(result)
+    }
+
     async fn download_file_to_path_as_read_only(
         &self,
         environment_id: EnvironmentId,
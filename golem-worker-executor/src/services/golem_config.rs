@@ -16,8 +16,9 @@ use anyhow::Context;
 use figment::providers::{Format, Toml};
 use figment::Figment;
 use golem_common::config::{
-    ConfigExample, ConfigLoader, DbSqliteConfig, HasConfigExamples, RedisConfig,
+    ConfigExample, ConfigLoader, DbPostgresConfig, DbSqliteConfig, HasConfigExamples, RedisConfig,
 };
+use golem_common::model::environment::EnvironmentId;
 use golem_common::model::RetryConfig;
 use golem_common::tracing::TracingConfig;
 use golem_common::{grpc_uri, SafeDisplay};
@@ -28,6 +29,7 @@ use golem_service_base::grpc::server::GrpcServerTlsConfig;
 use golem_service_base::service::compiled_component::CompiledComponentServiceConfig;
 use http::Uri;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use std::path::{Path, PathBuf};
@@ -46,9 +48,12 @@ pub struct GolemConfig {
     pub compiled_component_service: CompiledComponentServiceConfig,
     pub shard_manager_service: ShardManagerServiceConfig,
     pub oplog: OplogConfig,
+    pub oplog_search_index: OplogSearchIndexConfig,
     pub suspend: SuspendConfig,
     pub active_workers: ActiveWorkersConfig,
     pub scheduler: SchedulerConfig,
+    pub retention: RetentionConfig,
+    pub agent_quotas: AgentQuotasConfig,
     pub public_worker_api: WorkerServiceGrpcConfig,
     pub memory: MemoryConfig,
     pub rdbms: RdbmsConfig,
@@ -111,6 +116,12 @@ impl SafeDisplay for GolemConfig {
         );
         let _ = writeln!(&mut result, "oplog:");
         let _ = writeln!(&mut result, "{}", self.oplog.to_safe_string_indented());
+        let _ = writeln!(&mut result, "oplog search index:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.oplog_search_index.to_safe_string_indented()
+        );
         let _ = writeln!(&mut result, "suspend:");
         let _ = writeln!(&mut result, "{}", self.suspend.to_safe_string_indented());
         let _ = writeln!(&mut result, "active_workers:");
@@ -121,6 +132,14 @@ impl SafeDisplay for GolemConfig {
         );
         let _ = writeln!(&mut result, "scheduler:");
         let _ = writeln!(&mut result, "{}", self.scheduler.to_safe_string_indented());
+        let _ = writeln!(&mut result, "retention:");
+        let _ = writeln!(&mut result, "{}", self.retention.to_safe_string_indented());
+        let _ = writeln!(&mut result, "agent quotas:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.agent_quotas.to_safe_string_indented()
+        );
         let _ = writeln!(&mut result, "public worker api:");
         let _ = writeln!(
             &mut result,
@@ -182,8 +201,11 @@ impl Default for GolemConfig {
             compiled_component_service: CompiledComponentServiceConfig::default(),
             shard_manager_service: ShardManagerServiceConfig::default(),
             oplog: OplogConfig::default(),
+            oplog_search_index: OplogSearchIndexConfig::default(),
             suspend: SuspendConfig::default(),
             scheduler: SchedulerConfig::default(),
+            retention: RetentionConfig::default(),
+            agent_quotas: AgentQuotasConfig::default(),
             active_workers: ActiveWorkersConfig::default(),
             public_worker_api: WorkerServiceGrpcConfig::default(),
             memory: MemoryConfig::default(),
@@ -467,6 +489,185 @@ impl SafeDisplay for SchedulerConfig {
     }
 }
 
+/// Configures the background task deleting agents that are no longer needed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    pub scan_page_size: u64,
+    /// Policy used for environments not listed in `environments`
+    pub default_policy: RetentionPolicy,
+    #[serde(default)]
+    pub environments: HashMap<EnvironmentId, RetentionPolicy>,
+}
+
+impl RetentionConfig {
+    pub fn policy(&self, environment_id: &EnvironmentId) -> &RetentionPolicy {
+        self.environments
+            .get(environment_id)
+            .unwrap_or(&self.default_policy)
+    }
+}
+
+impl SafeDisplay for RetentionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "interval: {:?}", self.interval);
+        let _ = writeln!(&mut result, "scan page size: {}", self.scan_page_size);
+        let _ = writeln!(&mut result, "default policy:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.default_policy.to_safe_string_indented()
+        );
+        for (environment_id, policy) in &self.environments {
+            let _ = writeln!(&mut result, "policy of environment {environment_id}:");
+            let _ = writeln!(&mut result, "{}", policy.to_safe_string_indented());
+        }
+        result
+    }
+}
+
+/// Agents are deleted together with all their oplog layers, including the archived ones, once
+/// they have been in the given state without any oplog activity for at least the configured time.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default, with = "humantime_serde")]
+    pub delete_exited_after: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub delete_failed_after: Option<Duration>,
+    /// Applies to agents in any other state that have no pending invocations
+    #[serde(default, with = "humantime_serde")]
+    pub delete_idle_after: Option<Duration>,
+    /// Drops the archived oplog entries preceding the latest snapshot of an agent once the
+    /// snapshot is older than the configured time. Agents recover from their latest snapshot,
+    /// so this requires automatic snapshots to be enabled in the oplog configuration.
+    #[serde(default, with = "humantime_serde")]
+    pub drop_archived_layers_after: Option<Duration>,
+    /// Agents having any of these labels with the same value are never deleted
+    #[serde(default)]
+    pub keep_labels: HashMap<String, String>,
+}
+
+impl RetentionPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.delete_exited_after.is_none()
+            && self.delete_failed_after.is_none()
+            && self.delete_idle_after.is_none()
+            && self.drop_archived_layers_after.is_none()
+    }
+}
+
+impl SafeDisplay for RetentionPolicy {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "delete exited after: {:?}",
+            self.delete_exited_after
+        );
+        let _ = writeln!(
+            &mut result,
+            "delete failed after: {:?}",
+            self.delete_failed_after
+        );
+        let _ = writeln!(
+            &mut result,
+            "delete idle after: {:?}",
+            self.delete_idle_after
+        );
+        let _ = writeln!(
+            &mut result,
+            "drop archived layers after: {:?}",
+            self.drop_archived_layers_after
+        );
+        for (key, value) in &self.keep_labels {
+            let _ = writeln!(&mut result, "keep label: {key}={value}");
+        }
+        result
+    }
+}
+
+/// Per-agent quotas enforced by the invocation loop, on top of the account-wide plan limits.
+///
+/// Components can set the quotas of their agents in their environment, see `worker::quota`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AgentQuotasConfig {
+    /// Quotas used for the agents of components not setting them
+    pub default_quotas: AgentQuotas,
+}
+
+impl SafeDisplay for AgentQuotasConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "default quotas:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.default_quotas.to_safe_string_indented()
+        );
+        result
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentQuotas {
+    /// Invocations consuming more fuel than this are aborted, failing the agent
+    #[serde(default)]
+    pub max_fuel_per_invocation: Option<u64>,
+    /// Invocations above this rate are postponed until the one minute window allows them
+    #[serde(default)]
+    pub max_invocations_per_minute: Option<u32>,
+    /// The agent fails when starting an invocation with more oplog entries than this
+    #[serde(default)]
+    pub max_oplog_entries: Option<u64>,
+}
+
+impl AgentQuotas {
+    pub fn is_empty(&self) -> bool {
+        self.max_fuel_per_invocation.is_none()
+            && self.max_invocations_per_minute.is_none()
+            && self.max_oplog_entries.is_none()
+    }
+
+    /// Fills the quotas not defined in `self` from `fallback`
+    pub fn or(self, fallback: AgentQuotas) -> AgentQuotas {
+        AgentQuotas {
+            max_fuel_per_invocation: self
+                .max_fuel_per_invocation
+                .or(fallback.max_fuel_per_invocation),
+            max_invocations_per_minute: self
+                .max_invocations_per_minute
+                .or(fallback.max_invocations_per_minute),
+            max_oplog_entries: self.max_oplog_entries.or(fallback.max_oplog_entries),
+        }
+    }
+}
+
+impl SafeDisplay for AgentQuotas {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "max fuel per invocation: {:?}",
+            self.max_fuel_per_invocation
+        );
+        let _ = writeln!(
+            &mut result,
+            "max invocations per minute: {:?}",
+            self.max_invocations_per_minute
+        );
+        let _ = writeln!(
+            &mut result,
+            "max oplog entries: {:?}",
+            self.max_oplog_entries
+        );
+        result
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OplogConfig {
     pub max_operations_before_commit: u64,
@@ -477,6 +678,13 @@ pub struct OplogConfig {
     pub entry_count_limit: u64,
     #[serde(with = "humantime_serde")]
     pub archive_interval: Duration,
+    pub snapshot_entry_interval: Option<u64>,
+    #[serde(default, with = "humantime_serde")]
+    pub snapshot_time_interval: Option<Duration>,
+    #[serde(default)]
+    pub compression: OplogCompressionConfig,
+    #[serde(default)]
+    pub encryption: OplogEncryptionConfig,
 }
 
 impl SafeDisplay for OplogConfig {
@@ -505,16 +713,251 @@ impl SafeDisplay for OplogConfig {
         );
         let _ = writeln!(&mut result, "entry count limit: {}", self.entry_count_limit);
         let _ = writeln!(&mut result, "archive interval: {:?}", self.archive_interval);
+        let _ = writeln!(
+            &mut result,
+            "snapshot entry interval: {:?}",
+            self.snapshot_entry_interval
+        );
+        let _ = writeln!(
+            &mut result,
+            "snapshot time interval: {:?}",
+            self.snapshot_time_interval
+        );
+        let _ = writeln!(&mut result, "compression:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.compression.to_safe_string_indented()
+        );
+        let _ = writeln!(&mut result, "encryption:");
+        let _ = writeln!(&mut result, "{}", self.encryption.to_safe_string_indented());
+        result
+    }
+}
+
+/// Compression of the chunks stored in the archived oplog layers.
+///
+/// Every chunk records how it was compressed, so changing these settings only affects newly
+/// archived chunks.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompressionConfig {
+    pub codec: OplogCompressionCodecConfig,
+    pub max_chunk_size: usize,
+    pub dictionary: OplogCompressionDictionaryConfig,
+}
+
+impl SafeDisplay for OplogCompressionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "codec:");
+        let _ = writeln!(&mut result, "{}", self.codec.to_safe_string_indented());
+        let _ = writeln!(&mut result, "max chunk size: {}", self.max_chunk_size);
+        let _ = writeln!(&mut result, "dictionary:");
+        let _ = writeln!(&mut result, "{}", self.dictionary.to_safe_string_indented());
         result
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum OplogCompressionCodecConfig {
+    None(OplogCompressionNoneConfig),
+    Zstd(OplogCompressionZstdConfig),
+    Lz4(OplogCompressionLz4Config),
+}
+
+impl SafeDisplay for OplogCompressionCodecConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            OplogCompressionCodecConfig::None(_) => {
+                let _ = writeln!(&mut result, "none");
+            }
+            OplogCompressionCodecConfig::Zstd(inner) => {
+                let _ = writeln!(&mut result, "zstd:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+            OplogCompressionCodecConfig::Lz4(_) => {
+                let _ = writeln!(&mut result, "lz4");
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompressionNoneConfig {}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompressionZstdConfig {
+    /// Zstd compression level, 0 selects zstd's default level
+    pub level: i32,
+}
+
+impl SafeDisplay for OplogCompressionZstdConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "level: {}", self.level);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompressionLz4Config {}
+
+/// Per-component zstd dictionaries, trained from the first archived entries of each component.
+///
+/// Only used with the zstd codec. Dictionaries are kept in the indexed storage, as archived
+/// chunks refer to them for decompression.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompressionDictionaryConfig {
+    pub enabled: bool,
+    /// Maximum size of a trained dictionary in bytes
+    pub max_size: usize,
+    /// Number of archived oplog entries to collect before training a component's dictionary
+    pub training_samples: usize,
+    /// Maximum number of components whose dictionaries and training samples are kept in memory
+    pub max_capacity: usize,
+}
+
+impl SafeDisplay for OplogCompressionDictionaryConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "enabled: {}", self.enabled);
+        let _ = writeln!(&mut result, "max size: {}", self.max_size);
+        let _ = writeln!(&mut result, "training samples: {}", self.training_samples);
+        let _ = writeln!(&mut result, "max capacity: {}", self.max_capacity);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum OplogEncryptionConfig {
+    Disabled(OplogEncryptionDisabledConfig),
+    LocalFile(OplogEncryptionLocalFileConfig),
+}
+
+impl SafeDisplay for OplogEncryptionConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            OplogEncryptionConfig::Disabled(_) => {
+                let _ = writeln!(&mut result, "disabled");
+            }
+            OplogEncryptionConfig::LocalFile(inner) => {
+                let _ = writeln!(&mut result, "local file:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogEncryptionDisabledConfig {}
+
+/// Reads the keys of each environment from `<root>/<environment-id>/<key-id>.key` files.
+///
+/// The key directories are re-read after `refresh_interval`, and the archived oplog layers are
+/// re-encrypted when a new key is found.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogEncryptionLocalFileConfig {
+    pub root: PathBuf,
+    #[serde(with = "humantime_serde")]
+    pub refresh_interval: Duration,
+}
+
+impl SafeDisplay for OplogEncryptionLocalFileConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "root: {:?}", self.root);
+        let _ = writeln!(&mut result, "refresh interval: {:?}", self.refresh_interval);
+        result
+    }
+}
+
+/// Secondary index of the committed oplog entries, used for searching the oplogs of all the
+/// workers of a component at once.
+///
+/// The SQLite storage is local to the executor, so it only sees the workers running on it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogSearchIndexConfig {
+    pub storage: OplogSearchIndexStorageConfig,
+    /// Number of candidate entries fetched from the index at once while searching
+    pub search_page_size: u64,
+    /// Upper limit of the candidate entries checked by a single search
+    pub max_scanned_entries: u64,
+    /// Number of oplog entries added to the index at once
+    pub indexing_batch_size: u64,
+    /// Delay before retrying to index the entries of a worker after a failure
+    #[serde(with = "humantime_serde")]
+    pub indexing_retry_delay: Duration,
+}
+
+impl SafeDisplay for OplogSearchIndexConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "storage:");
+        let _ = writeln!(&mut result, "{}", self.storage.to_safe_string_indented());
+        let _ = writeln!(&mut result, "search page size: {}", self.search_page_size);
+        let _ = writeln!(
+            &mut result,
+            "max scanned entries: {}",
+            self.max_scanned_entries
+        );
+        let _ = writeln!(
+            &mut result,
+            "indexing batch size: {}",
+            self.indexing_batch_size
+        );
+        let _ = writeln!(
+            &mut result,
+            "indexing retry delay: {:?}",
+            self.indexing_retry_delay
+        );
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum OplogSearchIndexStorageConfig {
+    Disabled(OplogSearchIndexDisabledConfig),
+    Sqlite(DbSqliteConfig),
+    Postgres(DbPostgresConfig),
+}
+
+impl SafeDisplay for OplogSearchIndexStorageConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            OplogSearchIndexStorageConfig::Disabled(_) => {
+                let _ = writeln!(&mut result, "disabled");
+            }
+            OplogSearchIndexStorageConfig::Sqlite(inner) => {
+                let _ = writeln!(&mut result, "sqlite:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+            OplogSearchIndexStorageConfig::Postgres(inner) => {
+                let _ = writeln!(&mut result, "postgres:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogSearchIndexDisabledConfig {}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum KeyValueStorageConfig {
     Redis(RedisConfig),
     Sqlite(DbSqliteConfig),
     MultiSqlite(KeyValueStorageMultiSqliteConfig),
+    Postgres(DbPostgresConfig),
     InMemory(KeyValueStorageInMemoryConfig),
 }
 
@@ -534,6 +977,10 @@ impl SafeDisplay for KeyValueStorageConfig {
                 let _ = writeln!(&mut result, "multi-sqlite:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
             }
+            KeyValueStorageConfig::Postgres(inner) => {
+                let _ = writeln!(&mut result, "postgres:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
             KeyValueStorageConfig::InMemory(inner) => {
                 let _ = writeln!(&mut result, "in-memory:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
@@ -576,8 +1023,10 @@ pub enum IndexedStorageConfig {
     Redis(RedisConfig),
     KVStoreSqlite(IndexedStorageKVStoreSqliteConfig),
     KVStoreMultiSqlite(IndexedStorageKVStoreMultiSqliteConfig),
+    KVStorePostgres(IndexedStorageKVStorePostgresConfig),
     Sqlite(DbSqliteConfig),
     MultiSqlite(IndexedStorageMultiSqliteConfig),
+    Postgres(DbPostgresConfig),
     InMemory(IndexedStorageInMemoryConfig),
 }
 
@@ -601,6 +1050,10 @@ impl SafeDisplay for IndexedStorageConfig {
                 let _ = writeln!(&mut result, "multi-sqlite kv-store:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
             }
+            IndexedStorageConfig::KVStorePostgres(inner) => {
+                let _ = writeln!(&mut result, "postgres kv-store:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
             IndexedStorageConfig::Sqlite(inner) => {
                 let _ = writeln!(&mut result, "sqlite:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
@@ -609,6 +1062,10 @@ impl SafeDisplay for IndexedStorageConfig {
                 let _ = writeln!(&mut result, "multi-sqlite:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
             }
+            IndexedStorageConfig::Postgres(inner) => {
+                let _ = writeln!(&mut result, "postgres:");
+                let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
+            }
             IndexedStorageConfig::InMemory(inner) => {
                 let _ = writeln!(&mut result, "in-memory:");
                 let _ = writeln!(&mut result, "{}", inner.to_safe_string_indented());
@@ -645,6 +1102,15 @@ impl SafeDisplay for IndexedStorageKVStoreMultiSqliteConfig {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexedStorageKVStorePostgresConfig {}
+
+impl SafeDisplay for IndexedStorageKVStorePostgresConfig {
+    fn to_safe_string(&self) -> String {
+        "".to_string()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IndexedStorageMultiSqliteConfig {
     pub root_dir: PathBuf,
@@ -944,6 +1410,24 @@ impl HasConfigExamples<GolemConfig> for GolemConfig {
                     ..Self::default()
                 },
             ),
+            (
+                "with postgres key value storage and indexed storage",
+                Self {
+                    key_value_storage: KeyValueStorageConfig::Postgres(DbPostgresConfig {
+                        host: "localhost".to_string(),
+                        database: "postgres".to_string(),
+                        username: "postgres".to_string(),
+                        password: "postgres".to_string(),
+                        port: 5432,
+                        max_connections: 10,
+                        schema: None,
+                    }),
+                    indexed_storage: IndexedStorageConfig::KVStorePostgres(
+                        IndexedStorageKVStorePostgresConfig {},
+                    ),
+                    ..Self::default()
+                },
+            ),
         ]
     }
 }
@@ -980,6 +1464,43 @@ impl Default for OplogConfig {
             blob_storage_layers: 1,
             entry_count_limit: 1024,
             archive_interval: Duration::from_secs(60 * 60 * 24), // 24 hours
+            snapshot_entry_interval: None,
+            snapshot_time_interval: None,
+            compression: OplogCompressionConfig::default(),
+            encryption: OplogEncryptionConfig::default(),
+        }
+    }
+}
+
+impl Default for OplogCompressionConfig {
+    fn default() -> Self {
+        Self {
+            codec: OplogCompressionCodecConfig::Zstd(OplogCompressionZstdConfig { level: 0 }),
+            max_chunk_size: 4096,
+            dictionary: OplogCompressionDictionaryConfig {
+                enabled: false,
+                max_size: 112640,
+                training_samples: 1000,
+                max_capacity: 1024,
+            },
+        }
+    }
+}
+
+impl Default for OplogEncryptionConfig {
+    fn default() -> Self {
+        Self::Disabled(OplogEncryptionDisabledConfig {})
+    }
+}
+
+impl Default for OplogSearchIndexConfig {
+    fn default() -> Self {
+        Self {
+            storage: OplogSearchIndexStorageConfig::Disabled(OplogSearchIndexDisabledConfig {}),
+            search_page_size: 500,
+            max_scanned_entries: 100000,
+            indexing_batch_size: 100,
+            indexing_retry_delay: Duration::from_secs(5),
         }
     }
 }
@@ -1009,6 +1530,18 @@ impl Default for SchedulerConfig {
     }
 }
 
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60 * 60),
+            scan_page_size: 100,
+            default_policy: RetentionPolicy::default(),
+            environments: HashMap::new(),
+        }
+    }
+}
+
 impl Default for KeyValueStorageConfig {
     fn default() -> Self {
         Self::default_redis()
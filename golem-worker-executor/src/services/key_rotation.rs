@@ -0,0 +1,178 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Re-encryption of the archived oplog layers after the encryption key of an environment has
+//! been rotated.
+//!
+//! New oplog entries are always written with the current key, and entries of the primary layer
+//! are re-encrypted when they get archived, so only the archive layers have to be rewritten.
+//! Agents with an open oplog are skipped and retried in the next round.
+
+use crate::services::oplog::encryption::OplogEncryption;
+use crate::services::{HasAll, HasComponentService, HasOplogService, HasShardService};
+use crate::workerctx::WorkerCtx;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::ScanCursor;
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::{BTreeSet, HashMap};
+use std::marker::PhantomData;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tracing::{debug, error, info, span, warn, Instrument, Level};
+
+/// Re-encrypts the archived oplogs of the agents owned by this executor whenever the current
+/// key of their environment changes
+pub struct OplogKeyRotation<Ctx: WorkerCtx, Svcs: HasAll<Ctx>> {
+    services: Svcs,
+    encryption: OplogEncryption,
+    interval: Duration,
+    /// The key each component's archives were completely re-encrypted with
+    rotated: HashMap<ComponentId, String>,
+    ctx: PhantomData<Ctx>,
+}
+
+impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + Send + Sync + 'static> OplogKeyRotation<Ctx, Svcs> {
+    const SCAN_PAGE_SIZE: u64 = 100;
+
+    pub fn new(services: Svcs, encryption: OplogEncryption, interval: Duration) -> Self {
+        Self {
+            services,
+            encryption,
+            interval,
+            rotated: HashMap::new(),
+            ctx: PhantomData,
+        }
+    }
+
+    /// Spawns the background task periodically checking the current keys of the environments.
+    /// The first check after startup re-encrypts everything that is not using the current keys.
+    pub fn start(mut self, runtime: &Handle) {
+        runtime.spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(self.interval).await;
+                    if self.services.shard_service().is_ready() {
+                        self.rotate_all().await;
+                    } else {
+                        warn!("Skipping oplog key rotation, shard service is not ready")
+                    }
+                }
+            }
+            .instrument(span!(parent: None, Level::INFO, "Oplog key rotation loop")),
+        );
+    }
+
+    /// Components are discovered from the executor's component cache, so the archives of
+    /// components that were never loaded by this executor are not considered.
+    async fn rotate_all(&mut self) {
+        let components: BTreeSet<(EnvironmentId, ComponentId)> = self
+            .services
+            .component_service()
+            .all_cached_metadata()
+            .await
+            .into_iter()
+            .map(|component| (component.environment_id, component.id))
+            .collect();
+
+        for (environment_id, component_id) in components {
+            let key_id = match self.encryption.current_key_id(&environment_id).await {
+                Ok(Some(key_id)) => key_id,
+                Ok(None) => continue,
+                Err(err) => {
+                    error!(%environment_id, "Failed to get the current oplog encryption key: {err}");
+                    continue;
+                }
+            };
+            if self.rotated.get(&component_id) == Some(&key_id) {
+                continue;
+            }
+
+            match self.rotate_component(&environment_id, &component_id).await {
+                Ok(true) => {
+                    debug!(
+                        %environment_id,
+                        %component_id,
+                        %key_id,
+                        "Archived oplogs are encrypted with the current key"
+                    );
+                    self.rotated.insert(component_id, key_id);
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    error!(
+                        %environment_id,
+                        %component_id,
+                        "Failed to re-encrypt archived oplogs: {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns whether the archives of all the component's agents have been re-encrypted
+    async fn rotate_component(
+        &self,
+        environment_id: &EnvironmentId,
+        component_id: &ComponentId,
+    ) -> Result<bool, WorkerExecutorError> {
+        let oplog_service = self.services.oplog_service();
+        let mut completed = true;
+        let mut cursor = ScanCursor::default();
+        loop {
+            let (new_cursor, owned_worker_ids) = oplog_service
+                .scan_for_component(environment_id, component_id, cursor, Self::SCAN_PAGE_SIZE)
+                .await?;
+
+            for owned_worker_id in owned_worker_ids {
+                if self
+                    .services
+                    .shard_service()
+                    .check_worker(&owned_worker_id.worker_id)
+                    .is_err()
+                {
+                    continue;
+                }
+
+                match oplog_service.reencrypt_archives(&owned_worker_id).await {
+                    Ok(Some(0)) => {}
+                    Ok(Some(count)) => {
+                        info!(
+                            worker_id = %owned_worker_id.worker_id,
+                            "Re-encrypted {count} archived oplog entries"
+                        );
+                    }
+                    Ok(None) => {
+                        completed = false;
+                    }
+                    Err(err) => {
+                        warn!(
+                            worker_id = %owned_worker_id.worker_id,
+                            "Failed to re-encrypt archived oplog entries: {err}"
+                        );
+                        completed = false;
+                    }
+                }
+            }
+
+            if new_cursor.is_finished() {
+                break;
+            } else {
+                cursor = new_cursor;
+            }
+        }
+
+        Ok(completed)
+    }
+}
@@ -19,6 +19,7 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use bytes::Bytes;
 use golem_common::model::environment::EnvironmentId;
+use golem_common::model::OwnedWorkerId;
 use std::sync::Arc;
 
 /// Service implementing a persistent key-value store
@@ -79,6 +80,19 @@ pub trait KeyValueService: Send + Sync {
         bucket: String,
         key_values: Vec<(String, Vec<u8>)>,
     ) -> anyhow::Result<()>;
+
+    /// Registers a worker to be notified about the changes of a bucket of its environment
+    async fn watch(&self, owned_worker_id: &OwnedWorkerId, bucket: String) -> anyhow::Result<()>;
+
+    /// Removes all the bucket watches registered by a worker
+    async fn unwatch_all(&self, owned_worker_id: &OwnedWorkerId) -> anyhow::Result<()>;
+
+    /// Gets the workers to be notified about the changes of a bucket
+    async fn get_watchers(
+        &self,
+        environment_id: EnvironmentId,
+        bucket: String,
+    ) -> anyhow::Result<Vec<OwnedWorkerId>>;
 }
 
 #[derive(Clone, Debug)]
@@ -90,6 +104,17 @@ impl DefaultKeyValueService {
     pub fn new(key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>) -> Self {
         Self { key_value_storage }
     }
+
+    fn watchers_key(environment_id: &EnvironmentId, bucket: &str) -> String {
+        format!("key-value:watchers:{environment_id}:{bucket}")
+    }
+
+    fn watched_buckets_key(owned_worker_id: &OwnedWorkerId) -> String {
+        format!(
+            "key-value:watched-buckets:{}",
+            owned_worker_id.worker_id.to_redis_key()
+        )
+    }
 }
 
 #[async_trait]
@@ -263,4 +288,196 @@ impl KeyValueService for DefaultKeyValueService {
             .map_err(|err| anyhow!(err))?;
         Ok(())
     }
+    async fn watch(&self, owned_worker_id: &OwnedWorkerId, bucket: String) -> anyhow::Result<()> {
+        self.key_value_storage
+            .with_entity("key_value", "watch", "worker_id")
+            .add_to_set(
+                KeyValueStorageNamespace::BucketWatchers,
+                &Self::watchers_key(&owned_worker_id.environment_id, &bucket),
+                owned_worker_id,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        self.key_value_storage
+            .with_entity("key_value", "watch", "bucket")
+            .add_to_set(
+                KeyValueStorageNamespace::BucketWatchers,
+                &Self::watched_buckets_key(owned_worker_id),
+                &bucket,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+
+    async fn unwatch_all(&self, owned_worker_id: &OwnedWorkerId) -> anyhow::Result<()> {
+        let watched_buckets_key = Self::watched_buckets_key(owned_worker_id);
+        let buckets: Vec<String> = self
+            .key_value_storage
+            .with_entity("key_value", "unwatch_all", "bucket")
+            .members_of_set(
+                KeyValueStorageNamespace::BucketWatchers,
+                &watched_buckets_key,
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+
+        for bucket in buckets {
+            self.key_value_storage
+                .with_entity("key_value", "unwatch_all", "worker_id")
+                .remove_from_set(
+                    KeyValueStorageNamespace::BucketWatchers,
+                    &Self::watchers_key(&owned_worker_id.environment_id, &bucket),
+                    owned_worker_id,
+                )
+                .await
+                .map_err(|err| anyhow!(err))?;
+            self.key_value_storage
+                .with_entity("key_value", "unwatch_all", "bucket")
+                .remove_from_set(
+                    KeyValueStorageNamespace::BucketWatchers,
+                    &watched_buckets_key,
+                    &bucket,
+                )
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+        Ok(())
+    }
+
+    async fn get_watchers(
+        &self,
+        environment_id: EnvironmentId,
+        bucket: String,
+    ) -> anyhow::Result<Vec<OwnedWorkerId>> {
+        let watchers: Vec<OwnedWorkerId> = self
+            .key_value_storage
+            .with_entity("key_value", "get_watchers", "worker_id")
+            .members_of_set(
+                KeyValueStorageNamespace::BucketWatchers,
+                &Self::watchers_key(&environment_id, &bucket),
+            )
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(watchers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::services::key_value::{DefaultKeyValueService, KeyValueService};
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+    use golem_common::model::component::ComponentId;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::{OwnedWorkerId, WorkerId};
+    use std::sync::Arc;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn owned_worker_id(environment_id: EnvironmentId, name: &str) -> OwnedWorkerId {
+        OwnedWorkerId::new(
+            environment_id,
+            &WorkerId {
+                component_id: ComponentId(Uuid::new_v4()),
+                worker_name: name.to_string(),
+            },
+        )
+    }
+
+    fn key_value_service() -> DefaultKeyValueService {
+        DefaultKeyValueService::new(Arc::new(InMemoryKeyValueStorage::new()))
+    }
+
+    #[test]
+    async fn watchers_are_registered_per_bucket() {
+        let service = key_value_service();
+        let environment_id = EnvironmentId::new();
+        let worker1 = owned_worker_id(environment_id, "worker1");
+        let worker2 = owned_worker_id(environment_id, "worker2");
+
+        service.watch(&worker1, "a".to_string()).await.unwrap();
+        service.watch(&worker1, "a".to_string()).await.unwrap();
+        service.watch(&worker1, "b".to_string()).await.unwrap();
+        service.watch(&worker2, "a".to_string()).await.unwrap();
+
+        let mut watchers_of_a = service
+            .get_watchers(environment_id, "a".to_string())
+            .await
+            .unwrap();
+        watchers_of_a.sort_by_key(|w| w.worker_id.worker_name.clone());
+        assert_eq!(watchers_of_a, vec![worker1.clone(), worker2.clone()]);
+
+        let watchers_of_b = service
+            .get_watchers(environment_id, "b".to_string())
+            .await
+            .unwrap();
+        assert_eq!(watchers_of_b, vec![worker1]);
+
+        let watchers_of_c = service
+            .get_watchers(environment_id, "c".to_string())
+            .await
+            .unwrap();
+        assert!(watchers_of_c.is_empty());
+    }
+
+    #[test]
+    async fn watchers_are_isolated_by_environment() {
+        let service = key_value_service();
+        let environment1 = EnvironmentId::new();
+        let environment2 = EnvironmentId::new();
+        let worker = owned_worker_id(environment1, "worker");
+
+        service.watch(&worker, "a".to_string()).await.unwrap();
+
+        assert_eq!(
+            service
+                .get_watchers(environment1, "a".to_string())
+                .await
+                .unwrap(),
+            vec![worker]
+        );
+        assert!(service
+            .get_watchers(environment2, "a".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    async fn unwatch_all_removes_every_watch_of_the_worker() {
+        let service = key_value_service();
+        let environment_id = EnvironmentId::new();
+        let worker1 = owned_worker_id(environment_id, "worker1");
+        let worker2 = owned_worker_id(environment_id, "worker2");
+
+        service.watch(&worker1, "a".to_string()).await.unwrap();
+        service.watch(&worker1, "b".to_string()).await.unwrap();
+        service.watch(&worker2, "a".to_string()).await.unwrap();
+
+        service.unwatch_all(&worker1).await.unwrap();
+
+        assert_eq!(
+            service
+                .get_watchers(environment_id, "a".to_string())
+                .await
+                .unwrap(),
+            vec![worker2.clone()]
+        );
+        assert!(service
+            .get_watchers(environment_id, "b".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Unwatching is idempotent, and watching again after it works
+        service.unwatch_all(&worker1).await.unwrap();
+        service.watch(&worker1, "b".to_string()).await.unwrap();
+        assert_eq!(
+            service
+                .get_watchers(environment_id, "b".to_string())
+                .await
+                .unwrap(),
+            vec![worker1]
+        );
+    }
 }
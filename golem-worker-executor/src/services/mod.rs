@@ -19,11 +19,13 @@ pub mod component;
 pub mod events;
 pub mod file_loader;
 pub mod golem_config;
+pub mod key_rotation;
 pub mod key_value;
 pub mod oplog;
 pub mod promise;
 pub mod rdbms;
 pub mod resource_limits;
+pub mod retention;
 pub mod rpc;
 pub mod scheduler;
 pub mod shard;
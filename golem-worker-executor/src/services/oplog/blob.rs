@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::services::oplog::multilayer::OplogArchive;
+use crate::services::oplog::compression::OplogCompression;
+use crate::services::oplog::encryption::OplogEncryption;
+use crate::services::oplog::multilayer::{chunks_within_range, OplogArchive};
 use crate::services::oplog::{CompressedOplogChunk, OplogArchiveService};
 use anyhow::anyhow;
 use async_lock::RwLockUpgradableReadGuard;
@@ -37,16 +39,24 @@ use tokio::sync::RwLock;
 pub struct BlobOplogArchiveService {
     blob_storage: Arc<dyn BlobStorage + Send + Sync>,
     level: usize,
+    compression: OplogCompression,
+    encryption: OplogEncryption,
 }
 
 impl BlobOplogArchiveService {
-    const MAX_CHUNK_SIZE: usize = 4096;
     const CACHE_SIZE: usize = 4096;
 
-    pub fn new(blob_storage: Arc<dyn BlobStorage + Send + Sync>, level: usize) -> Self {
+    pub fn new(
+        blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+        level: usize,
+        compression: OplogCompression,
+        encryption: OplogEncryption,
+    ) -> Self {
         BlobOplogArchiveService {
             blob_storage,
             level,
+            compression,
+            encryption,
         }
     }
 }
@@ -59,6 +69,8 @@ impl OplogArchiveService for BlobOplogArchiveService {
                 owned_worker_id.clone(),
                 self.blob_storage.clone(),
                 self.level,
+                self.compression.clone(),
+                self.encryption.clone(),
             )
             .await,
         )
@@ -205,6 +217,8 @@ struct BlobOplogArchive {
     owned_worker_id: OwnedWorkerId,
     blob_storage: Arc<dyn BlobStorage + Send + Sync>,
     level: usize,
+    compression: OplogCompression,
+    encryption: OplogEncryption,
     entries: Arc<RwLock<BTreeMap<OplogIndex, PathBuf>>>,
     created: Arc<async_lock::RwLock<bool>>,
     #[allow(clippy::type_complexity)]
@@ -223,6 +237,8 @@ impl BlobOplogArchive {
         owned_worker_id: OwnedWorkerId,
         blob_storage: Arc<dyn BlobStorage + Send + Sync>,
         level: usize,
+        compression: OplogCompression,
+        encryption: OplogEncryption,
     ) -> Self {
         let exists = Self::exists(owned_worker_id.clone(), blob_storage.clone(), level).await;
         let created = Arc::new(async_lock::RwLock::new(exists));
@@ -236,6 +252,8 @@ impl BlobOplogArchive {
             owned_worker_id,
             blob_storage,
             level,
+            compression,
+            encryption,
             created,
             entries,
             cache: RwLock::new(EvictingCacheMap::new()),
@@ -340,6 +358,22 @@ impl BlobOplogArchive {
         path
     }
 
+    async fn get_chunk(&self, path: &Path) -> Result<CompressedOplogChunk, String> {
+        self.blob_storage
+            .with("blob_oplog", "get_chunk")
+            .get(
+                BlobStorageNamespace::CompressedOplog {
+                    environment_id: self.owned_worker_id.environment_id(),
+                    component_id: self.owned_worker_id.component_id(),
+                    level: self.level,
+                },
+                path,
+            )
+            .await
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("compressed chunk {path:?} not found"))
+    }
+
     // Fetch a range of entries from the storage. At most one chunk of data will be returned,
     // but it will always begin with the end of the range. So a given prefix of the of the oplog might be missing,
     // but the suffix will always be correct if it is returned. Returns None if there is no chunk containing any matching data.
@@ -370,9 +404,22 @@ impl BlobOplogArchive {
                 &self.oplog_index_to_path(*last_idx),
             )
             .await?
-            .ok_or_else(|| anyhow!("compressed chunk for {last_idx} not found"))?;
+            .ok_or_else(|| anyhow!("compressed chunk for {last_idx} not found"))?
+            .decrypt(
+                &self.encryption,
+                &self.owned_worker_id.environment_id(),
+                &self.owned_worker_id.worker_id,
+                self.level,
+                *last_idx,
+            )
+            .await
+            .map_err(|e| anyhow!(e))?;
 
-        let entries = chunk.decompress()?;
+        let entries = self
+            .compression
+            .decompress(&self.owned_worker_id.component_id(), &chunk)
+            .await
+            .map_err(|e| anyhow!(e))?;
         let mut cache = self.cache.write().await;
 
         let mut current_idx = Into::<u64>::into(*last_idx) - chunk.count + 1;
@@ -453,7 +500,7 @@ impl OplogArchive for BlobOplogArchive {
             return;
         }
 
-        for sub_chunk in chunk.chunks(BlobOplogArchiveService::MAX_CHUNK_SIZE) {
+        for sub_chunk in chunk.chunks(self.compression.max_chunk_size()) {
             let last = sub_chunk.last().unwrap();
             let oplog_index = last.0;
             let path = self.oplog_index_to_path(oplog_index);
@@ -461,8 +508,25 @@ impl OplogArchive for BlobOplogArchive {
             let entries: Vec<OplogEntry> =
                 sub_chunk.iter().map(|(_, entry)| entry.clone()).collect();
 
-            let compressed_chunk = CompressedOplogChunk::compress(entries)
-                .unwrap_or_else(|err| panic!("failed to compress oplog chunk: {err}"));
+            let compressed_chunk = self
+                .compression
+                .compress(&self.owned_worker_id.component_id(), entries)
+                .await
+                .unwrap_or_else(|err| panic!("failed to compress oplog chunk: {err}"))
+                .encrypt(
+                    &self.encryption,
+                    &self.owned_worker_id.environment_id(),
+                    &self.owned_worker_id.worker_id,
+                    self.level,
+                    oplog_index,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "failed to encrypt compressed oplog chunk for worker {}: {err}",
+                        self.owned_worker_id.worker_id
+                    )
+                });
 
             let mut entries_map = self.entries.write().await;
 
@@ -564,6 +628,46 @@ impl OplogArchive for BlobOplogArchive {
         drop_count as u64
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) -> u64 {
+        self.ensure_is_created().await;
+
+        let mut entries = self.entries.write().await;
+
+        let idx_to_drop =
+            chunks_within_range(entries.keys().copied(), first_dropped_id, last_dropped_id);
+        if idx_to_drop.is_empty() {
+            return 0;
+        }
+
+        let to_drop = idx_to_drop
+            .iter()
+            .map(|idx| self.oplog_index_to_path(*idx))
+            .collect::<Vec<_>>();
+
+        let ns = BlobStorageNamespace::CompressedOplog {
+            environment_id: self.owned_worker_id.environment_id(),
+            component_id: self.owned_worker_id.component_id(),
+            level: self.level,
+        };
+
+        self.blob_storage
+            .with("blob_oplog", "drop_range")
+            .delete_many(ns, &to_drop)
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to drop compressed oplog chunks for worker {} in blob storage: {err}",
+                    self.owned_worker_id.worker_id
+                )
+            });
+
+        for idx in &idx_to_drop {
+            let _ = entries.remove(idx);
+        }
+
+        idx_to_drop.len() as u64
+    }
+
     async fn length(&self) -> u64 {
         let entries = self.entries.read().await;
         entries.len() as u64
@@ -572,4 +676,71 @@ impl OplogArchive for BlobOplogArchive {
     async fn get_last_index(&self) -> OplogIndex {
         self.current_oplog_index().await
     }
+
+    async fn needs_reencryption(&self) -> Result<bool, String> {
+        let entries = self.entries.read().await;
+        for path in entries.values() {
+            let chunk = self.get_chunk(path).await?;
+            if self
+                .encryption
+                .needs_reencryption(
+                    &self.owned_worker_id.environment_id(),
+                    &chunk.compressed_data,
+                )
+                .await?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Chunks are stored in separate blobs, so they get overwritten one by one
+    async fn reencrypt(&self) -> Result<u64, String> {
+        let environment_id = self.owned_worker_id.environment_id();
+        let entries = self.entries.read().await;
+        let worker_id = &self.owned_worker_id.worker_id;
+        let mut reencrypted = 0;
+        for (last_index, path) in entries.iter() {
+            let chunk = self.get_chunk(path).await?;
+            if self
+                .encryption
+                .needs_reencryption(&environment_id, &chunk.compressed_data)
+                .await?
+            {
+                let chunk = chunk
+                    .decrypt(
+                        &self.encryption,
+                        &environment_id,
+                        worker_id,
+                        self.level,
+                        *last_index,
+                    )
+                    .await?
+                    .encrypt(
+                        &self.encryption,
+                        &environment_id,
+                        worker_id,
+                        self.level,
+                        *last_index,
+                    )
+                    .await?;
+                self.blob_storage
+                    .with("blob_oplog", "reencrypt")
+                    .put(
+                        BlobStorageNamespace::CompressedOplog {
+                            environment_id,
+                            component_id: self.owned_worker_id.component_id(),
+                            level: self.level,
+                        },
+                        path,
+                        &chunk,
+                    )
+                    .await
+                    .map_err(|err| err.to_string())?;
+                reencrypted += chunk.count;
+            }
+        }
+        Ok(reencrypted)
+    }
 }
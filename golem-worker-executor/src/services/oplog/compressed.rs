@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::services::oplog::multilayer::{OplogArchive, OplogArchiveService};
+use crate::services::oplog::compression::{OplogChunkCodec, OplogCompression};
+use crate::services::oplog::encryption::{OplogEncryption, OplogRecordId};
+use crate::services::oplog::multilayer::{chunks_within_range, OplogArchive, OplogArchiveService};
 use crate::services::oplog::PrimaryOplogService;
 use crate::storage::indexed::{
     IndexedStorage, IndexedStorageLabelledApi, IndexedStorageMetaNamespace, IndexedStorageNamespace,
@@ -25,7 +27,6 @@ use golem_common::model::component::ComponentId;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::oplog::{OplogEntry, OplogIndex};
 use golem_common::model::{OwnedWorkerId, ScanCursor, WorkerId};
-use golem_common::serialization::{deserialize, serialize};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -35,17 +36,24 @@ use tokio::sync::RwLock;
 pub struct CompressedOplogArchiveService {
     indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
     level: usize,
+    compression: OplogCompression,
+    encryption: OplogEncryption,
 }
 
 impl CompressedOplogArchiveService {
-    const MAX_CHUNK_SIZE: usize = 4096;
     const CACHE_SIZE: usize = 4096;
-    const ZSTD_LEVEL: i32 = 0;
 
-    pub fn new(indexed_storage: Arc<dyn IndexedStorage + Send + Sync>, level: usize) -> Self {
+    pub fn new(
+        indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
+        level: usize,
+        compression: OplogCompression,
+        encryption: OplogEncryption,
+    ) -> Self {
         Self {
             indexed_storage,
             level,
+            compression,
+            encryption,
         }
     }
 
@@ -57,11 +65,20 @@ impl CompressedOplogArchiveService {
 #[async_trait]
 impl OplogArchiveService for CompressedOplogArchiveService {
     async fn open(&self, owned_worker_id: &OwnedWorkerId) -> Arc<dyn OplogArchive + Send + Sync> {
-        Arc::new(CompressedOplogArchive::new(
-            owned_worker_id.worker_id(),
+        let archive = CompressedOplogArchive::new(
+            owned_worker_id,
             self.indexed_storage.clone(),
             self.level,
-        ))
+            self.compression.clone(),
+            self.encryption.clone(),
+        );
+        archive
+            .complete_interrupted_reencryption()
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to complete interrupted re-encryption of compressed oplog for worker {owned_worker_id} in indexed storage: {err}")
+            });
+        Arc::new(archive)
     }
 
     async fn delete(&self, owned_worker_id: &OwnedWorkerId) {
@@ -72,6 +89,13 @@ impl OplogArchiveService for CompressedOplogArchiveService {
             .unwrap_or_else(|err| {
                 panic!("failed to drop compressed oplog for worker {owned_worker_id} in indexed storage: {err}")
             });
+        self.indexed_storage
+            .with("compressed_oplog", "delete")
+            .delete(IndexedStorageNamespace::ReencryptedOpLog { worker_id: owned_worker_id.worker_id(), level: self.level }, &Self::compressed_oplog_key(&owned_worker_id.worker_id))
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to drop re-encrypted compressed oplog for worker {owned_worker_id} in indexed storage: {err}")
+            });
     }
 
     async fn read(
@@ -147,6 +171,7 @@ impl OplogArchiveService for CompressedOplogArchiveService {
 #[derive(Debug)]
 pub struct CompressedOplogArchive {
     worker_id: WorkerId,
+    environment_id: EnvironmentId,
     key: String,
     indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
     #[allow(clippy::type_complexity)]
@@ -159,21 +184,67 @@ pub struct CompressedOplogArchive {
         >,
     >,
     level: usize,
+    compression: OplogCompression,
+    encryption: OplogEncryption,
 }
 
 impl CompressedOplogArchive {
     pub fn new(
-        worker_id: WorkerId,
+        owned_worker_id: &OwnedWorkerId,
         indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
         level: usize,
+        compression: OplogCompression,
+        encryption: OplogEncryption,
     ) -> Self {
+        let worker_id = owned_worker_id.worker_id();
         let key = CompressedOplogArchiveService::compressed_oplog_key(&worker_id);
         Self {
             worker_id,
+            environment_id: owned_worker_id.environment_id(),
             key,
             indexed_storage,
             cache: RwLock::new(EvictingCacheMap::new()),
             level,
+            compression,
+            encryption,
+        }
+    }
+
+    fn namespace(&self) -> IndexedStorageNamespace {
+        IndexedStorageNamespace::CompressedOpLog {
+            worker_id: self.worker_id.clone(),
+            level: self.level,
+        }
+    }
+
+    fn reencrypted_namespace(&self) -> IndexedStorageNamespace {
+        IndexedStorageNamespace::ReencryptedOpLog {
+            worker_id: self.worker_id.clone(),
+            level: self.level,
+        }
+    }
+
+    /// Reads all the stored chunks of the archive
+    async fn chunks(&self) -> Result<Vec<(u64, CompressedOplogChunk)>, String> {
+        self.chunks_in(self.namespace()).await
+    }
+
+    /// Reads all the chunks stored under the archive's key in the given namespace
+    async fn chunks_in(
+        &self,
+        namespace: IndexedStorageNamespace,
+    ) -> Result<Vec<(u64, CompressedOplogChunk)>, String> {
+        let storage =
+            self.indexed_storage
+                .with_entity("compressed_oplog", "chunks", "compressed_entry");
+
+        let first_id = storage.first_id(namespace.clone(), &self.key).await?;
+        let last_id = storage.last_id(namespace.clone(), &self.key).await?;
+        match (first_id, last_id) {
+            (Some(first_id), Some(last_id)) => {
+                storage.read(namespace, &self.key, first_id, last_id).await
+            }
+            _ => Ok(Vec::new()),
         }
     }
 
@@ -204,7 +275,21 @@ impl CompressedOplogArchive {
             return Ok(None);
         };
 
-        let entries = chunk.decompress()?;
+        let chunk = chunk
+            .decrypt(
+                &self.encryption,
+                &self.environment_id,
+                &self.worker_id,
+                self.level,
+                OplogIndex::from_u64(last_idx_in_chunk),
+            )
+            .await
+            .map_err(|e| anyhow!(e))?;
+        let entries = self
+            .compression
+            .decompress(&self.worker_id.component_id, &chunk)
+            .await
+            .map_err(|e| anyhow!(e))?;
         let mut cache = self.cache.write().await;
 
         let mut current_idx = last_idx_in_chunk - chunk.count + 1;
@@ -229,6 +314,55 @@ impl CompressedOplogArchive {
 
         Ok(Some(collected))
     }
+
+    /// Rewrites the archive with the given chunks, which must already be stored in the
+    /// `ReencryptedOpLog` namespace, and deletes them from there afterwards
+    async fn replace_with_reencrypted(
+        &self,
+        chunks: &[(u64, &CompressedOplogChunk)],
+    ) -> Result<(), String> {
+        let storage = self.indexed_storage.with("compressed_oplog", "reencrypt");
+        storage.delete(self.namespace(), &self.key).await?;
+        self.indexed_storage
+            .with_entity("compressed_oplog", "reencrypt", "compressed_entry")
+            .append_many(self.namespace(), &self.key, chunks)
+            .await?;
+        storage
+            .delete(self.reencrypted_namespace(), &self.key)
+            .await
+    }
+
+    /// Completes a re-encryption which got interrupted before the re-encrypted chunks were
+    /// removed from the `ReencryptedOpLog` namespace.
+    ///
+    /// The chunks are written there completely before the archive gets deleted, so the archive is
+    /// only rewritten from them if it is missing or ends before them. Otherwise the archive is
+    /// intact (and either not yet or already fully re-encrypted), and the stored chunks are
+    /// just dropped.
+    async fn complete_interrupted_reencryption(&self) -> Result<(), String> {
+        let storage =
+            self.indexed_storage
+                .with_entity("compressed_oplog", "reencrypt", "compressed_entry");
+        let Some(staged_last_id) = storage
+            .last_id(self.reencrypted_namespace(), &self.key)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let last_id = storage.last_id(self.namespace(), &self.key).await?;
+        if last_id.is_some_and(|last_id| last_id >= staged_last_id) {
+            self.indexed_storage
+                .with("compressed_oplog", "reencrypt")
+                .delete(self.reencrypted_namespace(), &self.key)
+                .await
+        } else {
+            let chunks = self.chunks_in(self.reencrypted_namespace()).await?;
+            let pairs: Vec<(u64, &CompressedOplogChunk)> =
+                chunks.iter().map(|(id, chunk)| (*id, chunk)).collect();
+            self.replace_with_reencrypted(&pairs).await
+        }
+    }
 }
 
 /// Currently only the background-transfer fiber calls `append` and `drop_prefix` on oplog archives,
@@ -297,14 +431,28 @@ impl OplogArchive for CompressedOplogArchive {
             cache.insert(*idx, entry.clone());
         }
 
-        for sub_chunk in chunk.chunks(CompressedOplogArchiveService::MAX_CHUNK_SIZE) {
+        for sub_chunk in chunk.chunks(self.compression.max_chunk_size()) {
             let last_id = sub_chunk.last().unwrap().0;
 
             let entries: Vec<OplogEntry> =
                 sub_chunk.iter().map(|(_, entry)| entry.clone()).collect();
 
-            let compressed_chunk = CompressedOplogChunk::compress(entries)
-                .unwrap_or_else(|err| panic!("failed to compress oplog chunk: {err}"));
+            let compressed_chunk = self
+                .compression
+                .compress(&worker_id.component_id, entries)
+                .await
+                .unwrap_or_else(|err| panic!("failed to compress oplog chunk: {err}"))
+                .encrypt(
+                    &self.encryption,
+                    &self.environment_id,
+                    worker_id,
+                    self.level,
+                    last_id,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to encrypt compressed oplog chunk for worker {worker_id}: {err}")
+                });
 
             self.indexed_storage
                 .with_entity("compressed_oplog", "append", "compressed_entry")
@@ -357,6 +505,44 @@ impl OplogArchive for CompressedOplogArchive {
         before - remaining
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) -> u64 {
+        let worker_id = &self.worker_id;
+        let namespace = IndexedStorageNamespace::CompressedOpLog {
+            worker_id: self.worker_id.clone(),
+            level: self.level,
+        };
+        // Only the chunks ending in the range can be dropped, and the one ending right before
+        // it tells whether the first of them begins in the range
+        let chunks = self
+            .indexed_storage
+            .with_entity("compressed_oplog", "drop_range", "compressed_entry")
+            .read::<CompressedOplogChunk>(
+                namespace.clone(),
+                &self.key,
+                u64::from(first_dropped_id).saturating_sub(1),
+                last_dropped_id.into(),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to read compressed oplog chunks for worker {worker_id} from indexed storage: {err}")
+            });
+        let dropped = chunks_within_range(
+            chunks.into_iter().map(|(id, _)| OplogIndex::from_u64(id)),
+            first_dropped_id,
+            last_dropped_id,
+        );
+        // The selected chunks are always consecutive
+        if let (Some(first), Some(last)) = (dropped.first(), dropped.last()) {
+            self.indexed_storage.with("compressed_oplog", "drop_range")
+                .drop_range(namespace, &self.key, (*first).into(), (*last).into())
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to drop range from compressed oplog for worker {worker_id} in indexed storage: {err}")
+                });
+        }
+        dropped.len() as u64
+    }
+
     async fn length(&self) -> u64 {
         self.indexed_storage
             .with("compressed_oplog", "length")
@@ -376,35 +562,137 @@ impl OplogArchive for CompressedOplogArchive {
     async fn get_last_index(&self) -> OplogIndex {
         self.current_oplog_index().await
     }
+
+    async fn needs_reencryption(&self) -> Result<bool, String> {
+        for (_, chunk) in self.chunks().await? {
+            if self
+                .encryption
+                .needs_reencryption(&self.environment_id, &chunk.compressed_data)
+                .await?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Entries of the indexed storage cannot be overwritten, so the whole archive gets deleted and
+    /// written again if any of its chunks has to be re-encrypted. To not lose any entries if this
+    /// gets interrupted, all the chunks are stored in the `ReencryptedOpLog` namespace first, and
+    /// only removed from there once the archive has been rewritten; an interrupted run is completed
+    /// by `complete_interrupted_reencryption`. `MultiLayerOplogService` still only uses it when
+    /// there is no lower layer the entries could be moved to instead.
+    async fn reencrypt(&self) -> Result<u64, String> {
+        self.complete_interrupted_reencryption().await?;
+
+        let mut chunks = Vec::new();
+        let mut reencrypted = 0;
+        for (id, chunk) in self.chunks().await? {
+            if self
+                .encryption
+                .needs_reencryption(&self.environment_id, &chunk.compressed_data)
+                .await?
+            {
+                let last_index = OplogIndex::from_u64(id);
+                let chunk = chunk
+                    .decrypt(
+                        &self.encryption,
+                        &self.environment_id,
+                        &self.worker_id,
+                        self.level,
+                        last_index,
+                    )
+                    .await?
+                    .encrypt(
+                        &self.encryption,
+                        &self.environment_id,
+                        &self.worker_id,
+                        self.level,
+                        last_index,
+                    )
+                    .await?;
+                reencrypted += chunk.count;
+                chunks.push((id, chunk));
+            } else {
+                chunks.push((id, chunk));
+            }
+        }
+
+        if reencrypted > 0 {
+            let pairs: Vec<(u64, &CompressedOplogChunk)> =
+                chunks.iter().map(|(id, chunk)| (*id, chunk)).collect();
+            self.indexed_storage
+                .with_entity("compressed_oplog", "reencrypt", "compressed_entry")
+                .append_many(self.reencrypted_namespace(), &self.key, &pairs)
+                .await?;
+            self.replace_with_reencrypted(&pairs).await?;
+        }
+
+        Ok(reencrypted)
+    }
 }
 
+/// A chunk of consecutive oplog entries, compressed as a whole. Chunks written before the codec
+/// was recorded are compressed with zstd.
 #[derive(Debug, Clone, BinaryCodec)]
-#[desert(evolution())]
+#[desert(evolution(FieldAdded("codec", None)))]
 pub struct CompressedOplogChunk {
     pub count: u64,
     pub compressed_data: Vec<u8>,
+    pub codec: Option<OplogChunkCodec>,
 }
 
 impl CompressedOplogChunk {
-    pub fn compress(entries: Vec<OplogEntry>) -> Result<Self, String> {
-        let count = entries.len() as u64;
-        let uncompressed_data =
-            serialize(&entries).map_err(|err| format!("failed to serialize oplog chunk: {err}"))?;
-        let compressed_data = zstd::encode_all(
-            &*uncompressed_data,
-            CompressedOplogArchiveService::ZSTD_LEVEL,
-        )
-        .map_err(|err| format!("failed to compress oplog chunk: {err}"))?;
+    /// Encrypts the compressed data with the current key of the environment, bound to the
+    /// worker, archive level and range of entries of the chunk
+    pub async fn encrypt(
+        self,
+        encryption: &OplogEncryption,
+        environment_id: &EnvironmentId,
+        worker_id: &WorkerId,
+        level: usize,
+        last_index: OplogIndex,
+    ) -> Result<Self, String> {
+        let record_id = self.record_id(worker_id, level, last_index);
+        Ok(Self {
+            count: self.count,
+            compressed_data: encryption
+                .encrypt(environment_id, record_id, self.compressed_data)
+                .await?,
+            codec: self.codec,
+        })
+    }
+
+    /// Decrypts the compressed data if it was stored encrypted
+    pub async fn decrypt(
+        self,
+        encryption: &OplogEncryption,
+        environment_id: &EnvironmentId,
+        worker_id: &WorkerId,
+        level: usize,
+        last_index: OplogIndex,
+    ) -> Result<Self, String> {
+        let record_id = self.record_id(worker_id, level, last_index);
         Ok(Self {
-            count,
-            compressed_data,
+            count: self.count,
+            compressed_data: encryption
+                .decrypt(environment_id, record_id, self.compressed_data)
+                .await?,
+            codec: self.codec,
         })
     }
 
-    pub fn decompress(&self) -> anyhow::Result<Vec<OplogEntry>> {
-        let uncompressed_data = zstd::decode_all(&*self.compressed_data)
-            .map_err(|err| anyhow!("failed to decompress oplog chunk: {err}"))?;
-        deserialize(&uncompressed_data)
-            .map_err(|err| anyhow!("failed to deserialize oplog chunk: {err}"))
+    fn record_id<'a>(
+        &self,
+        worker_id: &'a WorkerId,
+        level: usize,
+        last_index: OplogIndex,
+    ) -> OplogRecordId<'a> {
+        OplogRecordId::Chunk {
+            worker_id,
+            level,
+            last_index,
+            count: self.count,
+        }
     }
 }
@@ -0,0 +1,575 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compression of the chunks stored in the archived oplog layers.
+//!
+//! Every chunk records the codec it was compressed with, so chunks remain readable after the
+//! configured codec changes. Chunks written before the codec was recorded are zstd frames.
+//!
+//! Oplog entries of the same component are highly repetitive, so zstd can use a dictionary
+//! trained per component. The first archived entries of a component are collected as training
+//! samples, and once enough of them are available a dictionary is trained and appended to the
+//! component's dictionaries in the indexed storage. Chunks refer to their dictionary by its id,
+//! so dictionaries are never deleted.
+
+use crate::services::golem_config::{
+    OplogCompressionCodecConfig, OplogCompressionConfig, OplogCompressionDictionaryConfig,
+};
+use crate::services::oplog::CompressedOplogChunk;
+use crate::storage::indexed::{IndexedStorage, IndexedStorageLabelledApi, IndexedStorageNamespace};
+use desert_rust::BinaryCodec;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::component::ComponentId;
+use golem_common::model::oplog::OplogEntry;
+use golem_common::serialization::{deserialize, serialize};
+use std::fmt::{Debug, Formatter};
+use std::io::{Read, Write};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// The codec a chunk was compressed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BinaryCodec)]
+#[desert(evolution())]
+pub enum OplogChunkCodec {
+    None,
+    Zstd,
+    Lz4,
+    ZstdWithDictionary { dictionary_id: u64 },
+}
+
+/// Compresses and decompresses the chunks of the archived oplog layers
+#[derive(Clone, Debug)]
+pub struct OplogCompression {
+    codec: OplogCompressionCodecConfig,
+    max_chunk_size: usize,
+    dictionaries: Option<Arc<OplogDictionaries>>,
+}
+
+impl OplogCompression {
+    /// Dictionaries are stored in the given indexed storage. They are loaded from there when
+    /// decompressing chunks even if training new dictionaries is disabled.
+    pub fn configured(
+        config: &OplogCompressionConfig,
+        indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
+    ) -> Self {
+        let training = match &config.codec {
+            OplogCompressionCodecConfig::Zstd(_) if config.dictionary.enabled => {
+                Some(config.dictionary.clone())
+            }
+            _ => None,
+        };
+        Self {
+            codec: config.codec.clone(),
+            max_chunk_size: config.max_chunk_size,
+            dictionaries: Some(Arc::new(OplogDictionaries::new(
+                indexed_storage,
+                training,
+                config.dictionary.max_capacity,
+            ))),
+        }
+    }
+
+    /// The maximum number of entries stored in a single chunk
+    pub fn max_chunk_size(&self) -> usize {
+        self.max_chunk_size
+    }
+
+    pub async fn compress(
+        &self,
+        component_id: &ComponentId,
+        entries: Vec<OplogEntry>,
+    ) -> Result<CompressedOplogChunk, String> {
+        let count = entries.len() as u64;
+        let uncompressed_data =
+            serialize(&entries).map_err(|err| format!("failed to serialize oplog chunk: {err}"))?;
+
+        let (codec, compressed_data) = match &self.codec {
+            OplogCompressionCodecConfig::None(_) => (OplogChunkCodec::None, uncompressed_data),
+            OplogCompressionCodecConfig::Lz4(_) => {
+                (OplogChunkCodec::Lz4, lz4_compress(&uncompressed_data)?)
+            }
+            OplogCompressionCodecConfig::Zstd(config) => {
+                let dictionary = match &self.dictionaries {
+                    Some(dictionaries) => dictionaries.current(component_id, &entries).await?,
+                    None => None,
+                };
+                match dictionary {
+                    Some((dictionary_id, dictionary)) => (
+                        OplogChunkCodec::ZstdWithDictionary { dictionary_id },
+                        zstd_compress_with_dictionary(
+                            &uncompressed_data,
+                            config.level,
+                            &dictionary,
+                        )?,
+                    ),
+                    None => (
+                        OplogChunkCodec::Zstd,
+                        zstd::encode_all(&*uncompressed_data, config.level)
+                            .map_err(|err| format!("failed to compress oplog chunk: {err}"))?,
+                    ),
+                }
+            }
+        };
+
+        Ok(CompressedOplogChunk {
+            count,
+            compressed_data,
+            codec: Some(codec),
+        })
+    }
+
+    pub async fn decompress(
+        &self,
+        component_id: &ComponentId,
+        chunk: &CompressedOplogChunk,
+    ) -> Result<Vec<OplogEntry>, String> {
+        let uncompressed_data = match chunk.codec.unwrap_or(OplogChunkCodec::Zstd) {
+            OplogChunkCodec::None => chunk.compressed_data.clone(),
+            OplogChunkCodec::Zstd => zstd::decode_all(&*chunk.compressed_data)
+                .map_err(|err| format!("failed to decompress oplog chunk: {err}"))?,
+            OplogChunkCodec::Lz4 => lz4_decompress(&chunk.compressed_data)?,
+            OplogChunkCodec::ZstdWithDictionary { dictionary_id } => {
+                let dictionaries = self.dictionaries.as_ref().ok_or_else(|| {
+                    format!("oplog chunk requires dictionary {dictionary_id} of component {component_id}, but dictionaries are not available")
+                })?;
+                let dictionary = dictionaries.get(component_id, dictionary_id).await?;
+                zstd_decompress_with_dictionary(&chunk.compressed_data, &dictionary)?
+            }
+        };
+        deserialize(&uncompressed_data)
+            .map_err(|err| format!("failed to deserialize oplog chunk: {err}"))
+    }
+}
+
+/// Zstd compression with the default level and no dictionaries
+impl Default for OplogCompression {
+    fn default() -> Self {
+        let config = OplogCompressionConfig::default();
+        Self {
+            codec: config.codec,
+            max_chunk_size: config.max_chunk_size,
+            dictionaries: None,
+        }
+    }
+}
+
+/// The zstd dictionaries of the components, stored in the indexed storage with increasing ids
+struct OplogDictionaries {
+    indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
+    training: Option<OplogCompressionDictionaryConfig>,
+    /// Dictionaries loaded from the indexed storage, keyed by their component and id
+    loaded: Cache<(ComponentId, u64), (), Arc<Vec<u8>>, String>,
+    /// The training state of the components, locked separately for each component
+    components: Cache<ComponentId, (), Arc<Mutex<ComponentDictionaries>>, String>,
+}
+
+#[derive(Default)]
+struct ComponentDictionaries {
+    /// The dictionary used for compressing new chunks, `None` until it has been looked up
+    current: Option<Option<u64>>,
+    samples: Vec<Vec<u8>>,
+    samples_size: usize,
+}
+
+impl OplogDictionaries {
+    /// zstd recommends training samples of about a hundred times the size of the dictionary in
+    /// total, so collecting more than that is pointless even if the number of samples is lower
+    const SAMPLES_SIZE_PER_DICTIONARY_BYTE: usize = 100;
+
+    fn new(
+        indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
+        training: Option<OplogCompressionDictionaryConfig>,
+        max_capacity: usize,
+    ) -> Self {
+        Self {
+            indexed_storage,
+            training,
+            loaded: Cache::new(
+                Some(max_capacity),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "oplog_dictionaries",
+            ),
+            components: Cache::new(
+                Some(max_capacity),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "oplog_dictionary_training",
+            ),
+        }
+    }
+
+    fn namespace(component_id: &ComponentId) -> IndexedStorageNamespace {
+        IndexedStorageNamespace::OplogDictionary {
+            component_id: *component_id,
+        }
+    }
+
+    fn key(component_id: &ComponentId) -> String {
+        component_id.to_string()
+    }
+
+    /// Gets the dictionary to compress the given entries with. If the component does not have a
+    /// dictionary yet, the entries are collected as training samples.
+    async fn current(
+        &self,
+        component_id: &ComponentId,
+        entries: &[OplogEntry],
+    ) -> Result<Option<(u64, Arc<Vec<u8>>)>, String> {
+        let Some(training) = &self.training else {
+            return Ok(None);
+        };
+
+        let component = self
+            .components
+            .get_or_insert_simple(component_id, async || {
+                Ok(Arc::new(Mutex::new(ComponentDictionaries::default())))
+            })
+            .await?;
+        let mut dictionaries = component.lock().await;
+
+        if dictionaries.current.is_none() {
+            dictionaries.current = Some(self.last_id(component_id).await?);
+        }
+        if let Some(Some(id)) = dictionaries.current {
+            drop(dictionaries);
+            let dictionary = self.get(component_id, id).await?;
+            return Ok(Some((id, dictionary)));
+        }
+
+        let max_samples_size = training.max_size * Self::SAMPLES_SIZE_PER_DICTIONARY_BYTE;
+        for entry in entries {
+            if dictionaries.samples.len() >= training.training_samples
+                || dictionaries.samples_size >= max_samples_size
+            {
+                break;
+            }
+            let sample = serialize(entry)
+                .map_err(|err| format!("failed to serialize oplog entry: {err}"))?;
+            dictionaries.samples_size += sample.len();
+            dictionaries.samples.push(sample);
+        }
+        if dictionaries.samples.len() < training.training_samples
+            && dictionaries.samples_size < max_samples_size
+        {
+            return Ok(None);
+        }
+
+        let samples = std::mem::take(&mut dictionaries.samples);
+        dictionaries.samples_size = 0;
+        let max_size = training.max_size;
+        let trained =
+            tokio::task::spawn_blocking(move || zstd::dict::from_samples(&samples, max_size))
+                .await
+                .map_err(|err| format!("failed to train oplog compression dictionary: {err}"))?;
+        let dictionary = match trained {
+            Ok(dictionary) => dictionary,
+            Err(err) => {
+                // Collecting new samples, the current ones may have been too small or too few
+                warn!(%component_id, "Failed to train oplog compression dictionary: {err}");
+                return Ok(None);
+            }
+        };
+
+        // Another executor may have stored a dictionary for the same component in the meantime
+        let id = match self.last_id(component_id).await? {
+            Some(id) => id,
+            None => {
+                let id = 1;
+                match self
+                    .indexed_storage
+                    .with_entity("oplog_dictionary", "store", "dictionary")
+                    .append_raw(
+                        Self::namespace(component_id),
+                        &Self::key(component_id),
+                        id,
+                        dictionary.clone(),
+                    )
+                    .await
+                {
+                    Ok(()) => {
+                        info!(
+                            %component_id,
+                            "Trained oplog compression dictionary of {} bytes",
+                            dictionary.len()
+                        );
+                        let dictionary = Arc::new(dictionary);
+                        self.loaded
+                            .get_or_insert_simple(&(*component_id, id), async || Ok(dictionary))
+                            .await?;
+                        id
+                    }
+                    Err(err) => self.last_id(component_id).await?.ok_or(err)?,
+                }
+            }
+        };
+        dictionaries.current = Some(Some(id));
+        drop(dictionaries);
+        let dictionary = self.get(component_id, id).await?;
+        Ok(Some((id, dictionary)))
+    }
+
+    async fn get(&self, component_id: &ComponentId, id: u64) -> Result<Arc<Vec<u8>>, String> {
+        self.loaded
+            .get_or_insert_simple(&(*component_id, id), async || {
+                let (_, dictionary) = self
+                    .indexed_storage
+                    .with_entity("oplog_dictionary", "load", "dictionary")
+                    .read_raw(
+                        Self::namespace(component_id),
+                        &Self::key(component_id),
+                        id,
+                        id,
+                    )
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| {
+                        format!(
+                            "oplog compression dictionary {id} of component {component_id} not found"
+                        )
+                    })?;
+                Ok(Arc::new(dictionary))
+            })
+            .await
+    }
+
+    async fn last_id(&self, component_id: &ComponentId) -> Result<Option<u64>, String> {
+        self.indexed_storage
+            .with_entity("oplog_dictionary", "last_id", "dictionary")
+            .last_id(Self::namespace(component_id), &Self::key(component_id))
+            .await
+    }
+}
+
+impl Debug for OplogDictionaries {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OplogDictionaries")
+            .field("training", &self.training)
+            .finish()
+    }
+}
+
+fn zstd_compress_with_dictionary(
+    data: &[u8],
+    level: i32,
+    dictionary: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut encoder = zstd::stream::Encoder::with_dictionary(Vec::new(), level, dictionary)
+        .map_err(|err| format!("failed to compress oplog chunk: {err}"))?;
+    encoder
+        .write_all(data)
+        .map_err(|err| format!("failed to compress oplog chunk: {err}"))?;
+    encoder
+        .finish()
+        .map_err(|err| format!("failed to compress oplog chunk: {err}"))
+}
+
+fn zstd_decompress_with_dictionary(data: &[u8], dictionary: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = zstd::stream::Decoder::with_dictionary(data, dictionary)
+        .map_err(|err| format!("failed to decompress oplog chunk: {err}"))?;
+    let mut result = Vec::new();
+    decoder
+        .read_to_end(&mut result)
+        .map_err(|err| format!("failed to decompress oplog chunk: {err}"))?;
+    Ok(result)
+}
+
+/// Uses the lz4 frame format, as unlike raw blocks its first byte never clashes with the marker
+/// of encrypted records
+fn lz4_compress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+    encoder
+        .write_all(data)
+        .map_err(|err| format!("failed to compress oplog chunk: {err}"))?;
+    encoder
+        .finish()
+        .map_err(|err| format!("failed to compress oplog chunk: {err}"))
+}
+
+fn lz4_decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = lz4_flex::frame::FrameDecoder::new(data);
+    let mut result = Vec::new();
+    decoder
+        .read_to_end(&mut result)
+        .map_err(|err| format!("failed to decompress oplog chunk: {err}"))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use crate::services::golem_config::{
+        OplogCompressionLz4Config, OplogCompressionNoneConfig, OplogCompressionZstdConfig,
+    };
+    use crate::storage::indexed::memory::InMemoryIndexedStorage;
+    use assert2::check;
+    use golem_common::model::oplog::{OplogIndex, WorkerError};
+    use golem_common::model::Timestamp;
+
+    fn entries(n: usize) -> Vec<OplogEntry> {
+        let timestamp = Timestamp::now_utc();
+        (0..n)
+            .map(|i| {
+                OplogEntry::Error {
+                    timestamp,
+                    error: WorkerError::Unknown(format!("error number {i}")),
+                    retry_from: OplogIndex::NONE,
+                }
+                .rounded()
+            })
+            .collect()
+    }
+
+    fn compression(
+        codec: OplogCompressionCodecConfig,
+        dictionary_enabled: bool,
+        indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
+    ) -> OplogCompression {
+        let mut config = OplogCompressionConfig {
+            codec,
+            ..OplogCompressionConfig::default()
+        };
+        config.dictionary.enabled = dictionary_enabled;
+        config.dictionary.max_size = 1024;
+        config.dictionary.training_samples = 1000;
+        OplogCompression::configured(&config, indexed_storage)
+    }
+
+    #[test]
+    async fn chunks_roundtrip_with_every_codec() {
+        let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+        let component_id = ComponentId::new();
+        let codecs = [
+            (
+                OplogCompressionCodecConfig::None(OplogCompressionNoneConfig {}),
+                OplogChunkCodec::None,
+            ),
+            (
+                OplogCompressionCodecConfig::Zstd(OplogCompressionZstdConfig { level: 3 }),
+                OplogChunkCodec::Zstd,
+            ),
+            (
+                OplogCompressionCodecConfig::Lz4(OplogCompressionLz4Config {}),
+                OplogChunkCodec::Lz4,
+            ),
+        ];
+
+        for (codec, expected_codec) in codecs {
+            let compression = compression(codec, false, indexed_storage.clone());
+            let chunk = compression
+                .compress(&component_id, entries(10))
+                .await
+                .unwrap();
+            let decompressed = compression.decompress(&component_id, &chunk).await.unwrap();
+
+            check!(chunk.count == 10);
+            check!(chunk.codec == Some(expected_codec));
+            check!(decompressed == entries(10));
+        }
+    }
+
+    #[test]
+    async fn chunks_without_codec_are_zstd() {
+        let component_id = ComponentId::new();
+        let chunk = CompressedOplogChunk {
+            count: 10,
+            compressed_data: zstd::encode_all(&*serialize(&entries(10)).unwrap(), 0).unwrap(),
+            codec: None,
+        };
+
+        let decompressed = OplogCompression::default()
+            .decompress(&component_id, &chunk)
+            .await
+            .unwrap();
+
+        check!(decompressed == entries(10));
+    }
+
+    #[test]
+    async fn dictionaries_are_trained_and_shared() {
+        let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+        let component_id = ComponentId::new();
+        let zstd = OplogCompressionCodecConfig::Zstd(OplogCompressionZstdConfig { level: 0 });
+        let compression = compression(zstd.clone(), true, indexed_storage.clone());
+
+        // Not enough samples for training yet
+        let first = compression
+            .compress(&component_id, entries(50))
+            .await
+            .unwrap();
+        let second = compression
+            .compress(&component_id, entries(2000))
+            .await
+            .unwrap();
+
+        check!(first.codec == Some(OplogChunkCodec::Zstd));
+        check!(second.codec == Some(OplogChunkCodec::ZstdWithDictionary { dictionary_id: 1 }));
+
+        // Another executor without dictionary training can still read the chunk
+        let other = compression(zstd, false, indexed_storage);
+        let decompressed = other.decompress(&component_id, &second).await.unwrap();
+        check!(decompressed == entries(2000));
+    }
+
+    #[test]
+    async fn evicted_dictionaries_are_reloaded() {
+        let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+        let first_component_id = ComponentId::new();
+        let second_component_id = ComponentId::new();
+        let mut config = OplogCompressionConfig {
+            codec: OplogCompressionCodecConfig::Zstd(OplogCompressionZstdConfig { level: 0 }),
+            ..OplogCompressionConfig::default()
+        };
+        config.dictionary.enabled = true;
+        config.dictionary.max_size = 1024;
+        config.dictionary.training_samples = 1000;
+        config.dictionary.max_capacity = 1;
+        let compression = OplogCompression::configured(&config, indexed_storage);
+
+        let first = compression
+            .compress(&first_component_id, entries(2000))
+            .await
+            .unwrap();
+        let second = compression
+            .compress(&second_component_id, entries(2000))
+            .await
+            .unwrap();
+        let third = compression
+            .compress(&first_component_id, entries(10))
+            .await
+            .unwrap();
+
+        check!(first.codec == Some(OplogChunkCodec::ZstdWithDictionary { dictionary_id: 1 }));
+        check!(second.codec == Some(OplogChunkCodec::ZstdWithDictionary { dictionary_id: 1 }));
+        check!(third.codec == Some(OplogChunkCodec::ZstdWithDictionary { dictionary_id: 1 }));
+        check!(
+            compression
+                .decompress(&first_component_id, &first)
+                .await
+                .unwrap()
+                == entries(2000)
+        );
+        check!(
+            compression
+                .decompress(&second_component_id, &second)
+                .await
+                .unwrap()
+                == entries(2000)
+        );
+    }
+}
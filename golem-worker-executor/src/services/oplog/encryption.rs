@@ -0,0 +1,767 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Envelope encryption of the data stored by the oplog services.
+//!
+//! Every stored record (an oplog entry, a compressed chunk of an archive layer or an external
+//! payload) is encrypted with its own random data key using AES-256-GCM, and the data key is
+//! stored next to it, wrapped by the current key of the worker's environment. Environment keys
+//! are provided by an [`OplogKeyProvider`]; environments without a key are stored unencrypted.
+//! The identity of the record ([`OplogRecordId`]) is authenticated as associated data, so an
+//! encrypted record cannot be swapped with another record, not even one of the same worker.
+//!
+//! Records written before encryption was enabled are still readable, as encrypted records are
+//! distinguished from plain ones by their first byte. Rotating an environment's key affects only
+//! the newly written records, archived layers have to be re-encrypted explicitly. Previous keys
+//! must remain available to the key provider while there are records encrypted with them.
+
+use crate::services::golem_config::OplogEncryptionConfig;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use desert_rust::{BinaryCodec, BinaryDeserializer, BinarySerializer};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::{OplogIndex, PayloadId};
+use golem_common::model::WorkerId;
+use golem_common::serialization::{deserialize, serialize};
+use rand::RngCore;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+/// The first byte of encrypted records. Plain records are either serialized values, starting
+/// with the serialization version, or zstd and lz4 frames starting with their magic number.
+const ENCRYPTED_RECORD_MARKER: u8 = 0xE0;
+
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12;
+const KEY_FILE_EXTENSION: &str = "key";
+
+/// Identifies a stored record by its place in the worker's oplog
+#[derive(Debug, Clone, Copy)]
+pub enum OplogRecordId<'a> {
+    /// An entry of the primary oplog
+    Entry {
+        worker_id: &'a WorkerId,
+        index: OplogIndex,
+    },
+    /// A compressed chunk of an archive layer, holding `count` entries up to `last_index`
+    Chunk {
+        worker_id: &'a WorkerId,
+        level: usize,
+        last_index: OplogIndex,
+        count: u64,
+    },
+    /// An externally stored payload
+    Payload {
+        worker_id: &'a WorkerId,
+        payload_id: &'a PayloadId,
+    },
+}
+
+impl OplogRecordId<'_> {
+    /// The associated data authenticated together with the record. Variable length fields are
+    /// prefixed by their length, so different identities never have the same encoding.
+    fn associated_data(&self, environment_id: &EnvironmentId) -> Vec<u8> {
+        fn push_worker_id(aad: &mut Vec<u8>, worker_id: &WorkerId) {
+            aad.extend(worker_id.component_id.0.as_bytes());
+            aad.extend((worker_id.worker_name.len() as u64).to_be_bytes());
+            aad.extend(worker_id.worker_name.as_bytes());
+        }
+
+        let mut aad = environment_id.0.as_bytes().to_vec();
+        match self {
+            Self::Entry { worker_id, index } => {
+                aad.push(0);
+                push_worker_id(&mut aad, worker_id);
+                aad.extend(u64::from(*index).to_be_bytes());
+            }
+            Self::Chunk {
+                worker_id,
+                level,
+                last_index,
+                count,
+            } => {
+                aad.push(1);
+                push_worker_id(&mut aad, worker_id);
+                aad.extend((*level as u64).to_be_bytes());
+                aad.extend(u64::from(*last_index).to_be_bytes());
+                aad.extend(count.to_be_bytes());
+            }
+            Self::Payload {
+                worker_id,
+                payload_id,
+            } => {
+                aad.push(2);
+                push_worker_id(&mut aad, worker_id);
+                aad.extend(payload_id.0.as_bytes());
+            }
+        }
+        aad
+    }
+}
+
+/// A 256-bit key used to wrap the data keys of the records of an environment
+#[derive(Clone)]
+pub struct OplogEncryptionKey {
+    pub id: String,
+    material: [u8; KEY_SIZE],
+}
+
+impl OplogEncryptionKey {
+    pub fn new(id: String, material: [u8; KEY_SIZE]) -> Self {
+        Self { id, material }
+    }
+}
+
+impl Debug for OplogEncryptionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OplogEncryptionKey")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Provides the keys used for encrypting the oplogs of each environment
+#[async_trait]
+pub trait OplogKeyProvider: Debug + Send + Sync {
+    /// Gets the key new records of the environment have to be encrypted with, or `None` if the
+    /// environment's oplogs are not encrypted
+    async fn current_key(
+        &self,
+        environment_id: &EnvironmentId,
+    ) -> Result<Option<OplogEncryptionKey>, String>;
+
+    /// Gets a current or previous key of the environment by its identifier
+    async fn get_key(
+        &self,
+        environment_id: &EnvironmentId,
+        key_id: &str,
+    ) -> Result<OplogEncryptionKey, String>;
+}
+
+/// A key provider for self-hosted deployments, reading the keys from the local file system.
+///
+/// Keys are stored in `<root>/<environment-id>/<key-id>.key` files, each containing 32 bytes
+/// encoded as hexadecimal characters. The current key of an environment is the one with the
+/// greatest identifier, so keys can be rotated by adding a new file with a greater name (for
+/// example a timestamp). Environments without a directory are not encrypted.
+#[derive(Debug)]
+pub struct LocalFileKeyProvider {
+    root: PathBuf,
+    refresh_interval: Duration,
+    keys: RwLock<HashMap<EnvironmentId, LoadedKeys>>,
+}
+
+#[derive(Clone)]
+struct LoadedKeys {
+    loaded_at: Instant,
+    keys: BTreeMap<String, [u8; KEY_SIZE]>,
+}
+
+impl Debug for LoadedKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadedKeys")
+            .field("loaded_at", &self.loaded_at)
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl LocalFileKeyProvider {
+    pub fn new(root: PathBuf, refresh_interval: Duration) -> Self {
+        Self {
+            root,
+            refresh_interval,
+            keys: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn keys(
+        &self,
+        environment_id: &EnvironmentId,
+        force_refresh: bool,
+    ) -> Result<LoadedKeys, String> {
+        if !force_refresh {
+            let keys = self.keys.read().await;
+            if let Some(loaded) = keys.get(environment_id) {
+                if loaded.loaded_at.elapsed() < self.refresh_interval {
+                    return Ok(loaded.clone());
+                }
+            }
+        }
+
+        let loaded = LoadedKeys {
+            loaded_at: Instant::now(),
+            keys: self.load(environment_id).await?,
+        };
+        self.keys
+            .write()
+            .await
+            .insert(*environment_id, loaded.clone());
+        Ok(loaded)
+    }
+
+    async fn load(
+        &self,
+        environment_id: &EnvironmentId,
+    ) -> Result<BTreeMap<String, [u8; KEY_SIZE]>, String> {
+        let dir = self.root.join(environment_id.to_string());
+        let mut result = BTreeMap::new();
+
+        if !tokio::fs::try_exists(&dir)
+            .await
+            .map_err(|err| format!("Failed to access key directory {dir:?}: {err}"))?
+        {
+            return Ok(result);
+        }
+
+        let mut entries = tokio::fs::read_dir(&dir)
+            .await
+            .map_err(|err| format!("Failed to list key directory {dir:?}: {err}"))?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|err| format!("Failed to list key directory {dir:?}: {err}"))?
+        {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(KEY_FILE_EXTENSION) {
+                continue;
+            }
+            let Some(key_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| format!("Failed to read key file {path:?}: {err}"))?;
+            let material: [u8; KEY_SIZE] = hex::decode(content.trim())
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| {
+                    format!("Key file {path:?} must contain {KEY_SIZE} hex encoded bytes")
+                })?;
+
+            result.insert(key_id.to_string(), material);
+        }
+
+        debug!(
+            %environment_id,
+            "Loaded {} oplog encryption keys from {dir:?}",
+            result.len()
+        );
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl OplogKeyProvider for LocalFileKeyProvider {
+    async fn current_key(
+        &self,
+        environment_id: &EnvironmentId,
+    ) -> Result<Option<OplogEncryptionKey>, String> {
+        let loaded = self.keys(environment_id, false).await?;
+        Ok(loaded
+            .keys
+            .last_key_value()
+            .map(|(id, material)| OplogEncryptionKey::new(id.clone(), *material)))
+    }
+
+    async fn get_key(
+        &self,
+        environment_id: &EnvironmentId,
+        key_id: &str,
+    ) -> Result<OplogEncryptionKey, String> {
+        let mut loaded = self.keys(environment_id, false).await?;
+        if !loaded.keys.contains_key(key_id) {
+            // The key may have been added since the last refresh
+            loaded = self.keys(environment_id, true).await?;
+        }
+        loaded
+            .keys
+            .get(key_id)
+            .map(|material| OplogEncryptionKey::new(key_id.to_string(), *material))
+            .ok_or_else(|| {
+                format!("Oplog encryption key {key_id} of environment {environment_id} not found")
+            })
+    }
+}
+
+#[derive(Debug, Clone, BinaryCodec)]
+#[desert(evolution())]
+struct EncryptedRecord {
+    key_id: String,
+    key_nonce: Vec<u8>,
+    wrapped_data_key: Vec<u8>,
+    data_nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+/// Encrypts and decrypts the records stored by the oplog services using the keys of a
+/// [`OplogKeyProvider`]. When disabled, records are stored in plain form.
+#[derive(Clone, Debug)]
+pub struct OplogEncryption {
+    key_provider: Option<Arc<dyn OplogKeyProvider>>,
+}
+
+impl OplogEncryption {
+    pub fn new(key_provider: Arc<dyn OplogKeyProvider>) -> Self {
+        Self {
+            key_provider: Some(key_provider),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { key_provider: None }
+    }
+
+    pub fn configured(config: &OplogEncryptionConfig) -> Self {
+        match config {
+            OplogEncryptionConfig::Disabled(_) => Self::disabled(),
+            OplogEncryptionConfig::LocalFile(config) => Self::new(Arc::new(
+                LocalFileKeyProvider::new(config.root.clone(), config.refresh_interval),
+            )),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.key_provider.is_some()
+    }
+
+    /// Checks whether the given stored record is encrypted
+    pub fn is_encrypted(data: &[u8]) -> bool {
+        data.first() == Some(&ENCRYPTED_RECORD_MARKER)
+    }
+
+    /// Gets the identifier of the key new records of the environment are encrypted with
+    pub async fn current_key_id(
+        &self,
+        environment_id: &EnvironmentId,
+    ) -> Result<Option<String>, String> {
+        Ok(self.current_key(environment_id).await?.map(|key| key.id))
+    }
+
+    /// Encrypts a record with the current key of the environment, or returns it unchanged if
+    /// the environment is not encrypted
+    pub async fn encrypt(
+        &self,
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        match self.current_key(environment_id).await? {
+            Some(key) => Self::encrypt_with(environment_id, record_id, &key, &data),
+            None => Ok(data),
+        }
+    }
+
+    /// Decrypts a stored record, or returns it unchanged if it was stored in plain form. Fails if
+    /// the record was encrypted with a different identity.
+    pub async fn decrypt(
+        &self,
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        if !Self::is_encrypted(&data) {
+            return Ok(data);
+        }
+
+        let record: EncryptedRecord = deserialize(&data[1..])
+            .map_err(|err| format!("Failed to deserialize encrypted oplog record: {err}"))?;
+        let key_provider = self.key_provider.as_ref().ok_or_else(|| {
+            format!(
+                "Oplog record is encrypted with key {}, but oplog encryption is disabled",
+                record.key_id
+            )
+        })?;
+        if record.key_nonce.len() != NONCE_SIZE || record.data_nonce.len() != NONCE_SIZE {
+            return Err(format!(
+                "Encrypted oplog record has a nonce of invalid length, expected {NONCE_SIZE} bytes"
+            ));
+        }
+        let key = key_provider.get_key(environment_id, &record.key_id).await?;
+
+        let aad = record_id.associated_data(environment_id);
+        let aad = aad.as_slice();
+        let data_key = Self::cipher(&key.material)
+            .decrypt(
+                Nonce::from_slice(&record.key_nonce),
+                Payload {
+                    msg: &record.wrapped_data_key,
+                    aad,
+                },
+            )
+            .map_err(|_| format!("Failed to unwrap data key with key {}", record.key_id))?;
+        if data_key.len() != KEY_SIZE {
+            return Err(format!(
+                "Unwrapped data key has invalid length, expected {KEY_SIZE} bytes"
+            ));
+        }
+        Self::cipher(&data_key)
+            .decrypt(
+                Nonce::from_slice(&record.data_nonce),
+                Payload {
+                    msg: &record.ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| "Failed to decrypt oplog record".to_string())
+    }
+
+    /// Serializes and encrypts a value
+    pub async fn encrypt_value<V: BinarySerializer>(
+        &self,
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        value: &V,
+    ) -> Result<Vec<u8>, String> {
+        self.encrypt(environment_id, record_id, serialize(value)?)
+            .await
+    }
+
+    /// Decrypts and deserializes a value
+    pub async fn decrypt_value<V: BinaryDeserializer>(
+        &self,
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        data: Vec<u8>,
+    ) -> Result<V, String> {
+        deserialize(&self.decrypt(environment_id, record_id, data).await?)
+    }
+
+    /// Checks whether a stored record is not encrypted with the current key of the environment
+    pub async fn needs_reencryption(
+        &self,
+        environment_id: &EnvironmentId,
+        data: &[u8],
+    ) -> Result<bool, String> {
+        match self.current_key(environment_id).await? {
+            Some(key) => {
+                if Self::is_encrypted(data) {
+                    let record: EncryptedRecord = deserialize(&data[1..]).map_err(|err| {
+                        format!("Failed to deserialize encrypted oplog record: {err}")
+                    })?;
+                    Ok(record.key_id != key.id)
+                } else {
+                    Ok(true)
+                }
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Re-encrypts a stored record with the current key of the environment. Returns `None` if
+    /// the record does not have to be changed.
+    pub async fn reencrypt(
+        &self,
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        data: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, String> {
+        if self.needs_reencryption(environment_id, &data).await? {
+            let plain = self.decrypt(environment_id, record_id, data).await?;
+            Ok(Some(self.encrypt(environment_id, record_id, plain).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn current_key(
+        &self,
+        environment_id: &EnvironmentId,
+    ) -> Result<Option<OplogEncryptionKey>, String> {
+        match &self.key_provider {
+            Some(key_provider) => key_provider.current_key(environment_id).await,
+            None => Ok(None),
+        }
+    }
+
+    fn encrypt_with(
+        environment_id: &EnvironmentId,
+        record_id: OplogRecordId<'_>,
+        key: &OplogEncryptionKey,
+        data: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let mut data_key = [0u8; KEY_SIZE];
+        let mut key_nonce = [0u8; NONCE_SIZE];
+        let mut data_nonce = [0u8; NONCE_SIZE];
+        let mut rng = rand::rng();
+        rng.fill_bytes(&mut data_key);
+        rng.fill_bytes(&mut key_nonce);
+        rng.fill_bytes(&mut data_nonce);
+
+        let aad = record_id.associated_data(environment_id);
+        let aad = aad.as_slice();
+        let wrapped_data_key = Self::cipher(&key.material)
+            .encrypt(
+                Nonce::from_slice(&key_nonce),
+                Payload {
+                    msg: &data_key,
+                    aad,
+                },
+            )
+            .map_err(|_| format!("Failed to wrap data key with key {}", key.id))?;
+        let ciphertext = Self::cipher(&data_key)
+            .encrypt(Nonce::from_slice(&data_nonce), Payload { msg: data, aad })
+            .map_err(|_| "Failed to encrypt oplog record".to_string())?;
+
+        let record = serialize(&EncryptedRecord {
+            key_id: key.id.clone(),
+            key_nonce: key_nonce.to_vec(),
+            wrapped_data_key,
+            data_nonce: data_nonce.to_vec(),
+            ciphertext,
+        })?;
+
+        let mut result = Vec::with_capacity(record.len() + 1);
+        result.push(ENCRYPTED_RECORD_MARKER);
+        result.extend(record);
+        Ok(result)
+    }
+
+    fn cipher(key: &[u8]) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use assert2::check;
+    use golem_common::model::component::ComponentId;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    async fn write_key(root: &TempDir, environment_id: &EnvironmentId, key_id: &str, byte: u8) {
+        let dir = root.path().join(environment_id.to_string());
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(
+            dir.join(format!("{key_id}.key")),
+            hex::encode([byte; KEY_SIZE]),
+        )
+        .await
+        .unwrap();
+    }
+
+    fn test_worker_id() -> WorkerId {
+        WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "test".to_string(),
+        }
+    }
+
+    fn entry(worker_id: &WorkerId, index: u64) -> OplogRecordId<'_> {
+        OplogRecordId::Entry {
+            worker_id,
+            index: OplogIndex::from_u64(index),
+        }
+    }
+
+    fn encryption(root: &TempDir) -> OplogEncryption {
+        OplogEncryption::new(Arc::new(LocalFileKeyProvider::new(
+            root.path().to_path_buf(),
+            Duration::ZERO,
+        )))
+    }
+
+    #[test]
+    async fn environments_without_keys_are_not_encrypted() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+
+        let data = serialize(&"hello".to_string()).unwrap();
+        let stored = encryption
+            .encrypt(&environment_id, entry(&worker_id, 1), data.clone())
+            .await
+            .unwrap();
+
+        check!(stored == data);
+        check!(!OplogEncryption::is_encrypted(&stored));
+    }
+
+    #[test]
+    async fn encrypted_records_roundtrip() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+        write_key(&root, &environment_id, "001", 1).await;
+
+        let stored = encryption
+            .encrypt_value(&environment_id, entry(&worker_id, 1), &"hello".to_string())
+            .await
+            .unwrap();
+        let value: String = encryption
+            .decrypt_value(&environment_id, entry(&worker_id, 1), stored.clone())
+            .await
+            .unwrap();
+
+        check!(OplogEncryption::is_encrypted(&stored));
+        check!(value == "hello");
+    }
+
+    #[test]
+    async fn records_are_bound_to_their_environment() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+        let other_environment_id = EnvironmentId::new();
+        write_key(&root, &environment_id, "001", 1).await;
+        write_key(&root, &other_environment_id, "001", 1).await;
+
+        let stored = encryption
+            .encrypt(&environment_id, entry(&worker_id, 1), b"secret".to_vec())
+            .await
+            .unwrap();
+        let result = encryption
+            .decrypt(&other_environment_id, entry(&worker_id, 1), stored)
+            .await;
+
+        check!(result.is_err());
+    }
+
+    #[test]
+    async fn records_are_bound_to_their_identity() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+        let other_worker_id = WorkerId {
+            component_id: worker_id.component_id,
+            worker_name: "other".to_string(),
+        };
+        let payload_id = PayloadId::new();
+        write_key(&root, &environment_id, "001", 1).await;
+
+        let stored = encryption
+            .encrypt(&environment_id, entry(&worker_id, 1), b"secret".to_vec())
+            .await
+            .unwrap();
+
+        let swapped_ids = [
+            entry(&worker_id, 2),
+            entry(&other_worker_id, 1),
+            OplogRecordId::Chunk {
+                worker_id: &worker_id,
+                level: 1,
+                last_index: OplogIndex::from_u64(1),
+                count: 1,
+            },
+            OplogRecordId::Payload {
+                worker_id: &worker_id,
+                payload_id: &payload_id,
+            },
+        ];
+        for record_id in swapped_ids {
+            let result = encryption
+                .decrypt(&environment_id, record_id, stored.clone())
+                .await;
+            check!(result.is_err(), "{record_id:?}");
+        }
+
+        let result = encryption
+            .decrypt(&environment_id, entry(&worker_id, 1), stored)
+            .await;
+        check!(result == Ok(b"secret".to_vec()));
+    }
+
+    #[test]
+    async fn records_with_invalid_nonces_are_rejected() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+        write_key(&root, &environment_id, "001", 1).await;
+
+        let stored = encryption
+            .encrypt(&environment_id, entry(&worker_id, 1), b"secret".to_vec())
+            .await
+            .unwrap();
+        let mut record: EncryptedRecord = deserialize(&stored[1..]).unwrap();
+        record.data_nonce.truncate(NONCE_SIZE - 1);
+        let mut tampered = vec![ENCRYPTED_RECORD_MARKER];
+        tampered.extend(serialize(&record).unwrap());
+
+        let result = encryption
+            .decrypt(&environment_id, entry(&worker_id, 1), tampered)
+            .await;
+
+        check!(result.is_err());
+    }
+
+    #[test]
+    async fn rotation_reencrypts_with_the_latest_key() {
+        let root = TempDir::new().unwrap();
+        let encryption = encryption(&root);
+        let environment_id = EnvironmentId::new();
+        let worker_id = test_worker_id();
+        write_key(&root, &environment_id, "001", 1).await;
+
+        let plain = serialize(&"before encryption".to_string()).unwrap();
+        let stored = encryption
+            .encrypt(&environment_id, entry(&worker_id, 1), b"secret".to_vec())
+            .await
+            .unwrap();
+        check!(encryption
+            .reencrypt(&environment_id, entry(&worker_id, 1), stored.clone())
+            .await
+            .unwrap()
+            .is_none());
+
+        write_key(&root, &environment_id, "002", 2).await;
+        check!(
+            encryption.current_key_id(&environment_id).await.unwrap() == Some("002".to_string())
+        );
+
+        let rotated = encryption
+            .reencrypt(&environment_id, entry(&worker_id, 1), stored)
+            .await
+            .unwrap()
+            .unwrap();
+        let encrypted_plain = encryption
+            .reencrypt(&environment_id, entry(&worker_id, 1), plain.clone())
+            .await
+            .unwrap()
+            .unwrap();
+
+        check!(!encryption
+            .needs_reencryption(&environment_id, &rotated)
+            .await
+            .unwrap());
+        check!(
+            encryption
+                .decrypt(&environment_id, entry(&worker_id, 1), rotated)
+                .await
+                .unwrap()
+                == b"secret".to_vec()
+        );
+        check!(
+            encryption
+                .decrypt(&environment_id, entry(&worker_id, 1), encrypted_plain)
+                .await
+                .unwrap()
+                == plain
+        );
+    }
+}
@@ -0,0 +1,498 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::ExecutionStatus;
+use crate::services::component::ComponentService;
+use crate::services::file_loader::FileLoader;
+use crate::services::oplog::{CommitLevel, Oplog, OplogService};
+use futures::{Stream, StreamExt};
+use golem_common::model::account::AccountId;
+use golem_common::model::agent::AgentMode;
+use golem_common::model::component::ComponentRevision;
+use golem_common::model::oplog::{
+    encode_exported_oplog_part, ExportedInitialFile, ExportedOplogDecoder, ExportedOplogHeader,
+    ExportedOplogPart, ExportedOplogPayload, OplogEntry, OplogIndex, PayloadId, RawOplogPayload,
+};
+use golem_common::model::{
+    OwnedWorkerId, Timestamp, WorkerMetadata, WorkerResourceUsageRecord, WorkerStatusRecord,
+};
+use golem_common::read_only_lock;
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::{BTreeSet, HashMap};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, Instrument};
+
+/// Maximum size of a single chunk of an exported oplog archive
+pub const EXPORT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Number of oplog entries read and written together while exporting
+const ENTRIES_PER_PART: u64 = 100;
+
+/// Number of archive chunks buffered between the exporter and the consumer of the archive
+const EXPORT_BUFFER: usize = 4;
+
+pub type ExportedOplogStream =
+    Pin<Box<dyn Stream<Item = Result<Vec<u8>, WorkerExecutorError>> + Send + 'static>>;
+
+/// Streams the full oplog of a worker, together with all the externally stored payloads and the
+/// initial component files it depends on, as a self-contained archive.
+///
+/// The archive is produced in chunks of at most [`EXPORT_CHUNK_SIZE`] bytes, and only a single
+/// payload, initial file or page of entries is held in memory at a time. The exported entries are
+/// the ones that existed when the export started.
+pub async fn export_oplog(
+    component_service: Arc<dyn ComponentService>,
+    oplog_service: Arc<dyn OplogService>,
+    file_loader: Arc<FileLoader>,
+    owned_worker_id: &OwnedWorkerId,
+) -> Result<ExportedOplogStream, WorkerExecutorError> {
+    if !oplog_service.exists(owned_worker_id).await {
+        return Err(WorkerExecutorError::worker_not_found(
+            owned_worker_id.worker_id(),
+        ));
+    }
+
+    let last_index = oplog_service.get_last_index(owned_worker_id).await;
+    let owned_worker_id = owned_worker_id.clone();
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER);
+
+    tokio::spawn(
+        async move {
+            let result = write_exported_oplog(
+                component_service,
+                oplog_service,
+                file_loader,
+                &owned_worker_id,
+                last_index,
+                &sender,
+            )
+            .await;
+            if let Err(err) = result {
+                let _ = sender.send(Err(err)).await;
+            }
+        }
+        .in_current_span(),
+    );
+
+    Ok(Box::pin(ReceiverStream::new(receiver)))
+}
+
+async fn write_exported_oplog(
+    component_service: Arc<dyn ComponentService>,
+    oplog_service: Arc<dyn OplogService>,
+    file_loader: Arc<FileLoader>,
+    owned_worker_id: &OwnedWorkerId,
+    last_index: OplogIndex,
+    sender: &mpsc::Sender<Result<Vec<u8>, WorkerExecutorError>>,
+) -> Result<(), WorkerExecutorError> {
+    // The first pass only collects the references, so the payloads and files can be sent before
+    // the entries using them
+    let mut payload_refs = Vec::new();
+    let mut seen_payloads = BTreeSet::new();
+    let mut revisions = BTreeSet::new();
+    let mut start = OplogIndex::INITIAL;
+    while start <= last_index {
+        let end = start.range_end(ENTRIES_PER_PART).min(last_index);
+        let entries = oplog_service.read_range(owned_worker_id, start, end).await;
+        for entry in entries.values() {
+            for (payload_id, md5_hash) in entry.external_payloads() {
+                if seen_payloads.insert(payload_id.clone()) {
+                    payload_refs.push((payload_id.clone(), md5_hash.clone()));
+                }
+            }
+            if let Some(revision) = entry.specifies_component_revision() {
+                revisions.insert(revision);
+            }
+        }
+        start = end.next();
+    }
+
+    send_part(
+        sender,
+        ExportedOplogPart::Header(ExportedOplogHeader {
+            worker_id: owned_worker_id.worker_id(),
+            exported_at: Timestamp::now_utc(),
+            component_revisions: revisions.iter().copied().collect(),
+        }),
+    )
+    .await?;
+
+    let mut initial_files = 0;
+    for component_revision in revisions {
+        let component = component_service
+            .get_metadata(owned_worker_id.component_id(), Some(component_revision))
+            .await?;
+        for file in component.files {
+            let data = file_loader
+                .read_to_vec(owned_worker_id.environment_id(), file.content_hash)
+                .await?;
+            send_part(
+                sender,
+                ExportedOplogPart::InitialFile(ExportedInitialFile {
+                    component_revision,
+                    path: file.path.to_string(),
+                    data,
+                }),
+            )
+            .await?;
+            initial_files += 1;
+        }
+    }
+
+    for (payload_id, md5_hash) in &payload_refs {
+        let data = oplog_service
+            .download_raw_payload(owned_worker_id, payload_id.clone(), md5_hash.clone())
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to download oplog payload {payload_id}: {err}"
+                ))
+            })?;
+        send_part(
+            sender,
+            ExportedOplogPart::Payload(ExportedOplogPayload {
+                payload_id: payload_id.clone(),
+                data,
+            }),
+        )
+        .await?;
+    }
+
+    let mut start = OplogIndex::INITIAL;
+    while start <= last_index {
+        let end = start.range_end(ENTRIES_PER_PART).min(last_index);
+        let entries = oplog_service.read_range(owned_worker_id, start, end).await;
+        send_part(
+            sender,
+            ExportedOplogPart::Entries(entries.into_values().collect()),
+        )
+        .await?;
+        start = end.next();
+    }
+    send_part(sender, ExportedOplogPart::End).await?;
+
+    debug!(
+        "Exported {} oplog entries, {} payloads and {initial_files} initial files of {owned_worker_id}",
+        last_index.as_u64(),
+        payload_refs.len(),
+    );
+
+    Ok(())
+}
+
+async fn send_part(
+    sender: &mpsc::Sender<Result<Vec<u8>, WorkerExecutorError>>,
+    part: ExportedOplogPart,
+) -> Result<(), WorkerExecutorError> {
+    let encoded = encode_exported_oplog_part(&part).map_err(WorkerExecutorError::unknown)?;
+    for chunk in encoded.chunks(EXPORT_CHUNK_SIZE) {
+        sender.send(Ok(chunk.to_vec())).await.map_err(|_| {
+            WorkerExecutorError::runtime("The consumer of the oplog export went away")
+        })?;
+    }
+    Ok(())
+}
+
+/// Creates a new worker from a streamed oplog archive produced by [`export_oplog`].
+///
+/// The target worker must not exist yet. The component of the target worker must have all the
+/// component revisions referenced by the archive. The archive is processed part by part, and the
+/// partially imported oplog is deleted if the import fails or the archive is truncated. The imported worker is left suspended,
+/// it gets recovered on the next invocation or explicit resume.
+pub async fn import_oplog(
+    component_service: Arc<dyn ComponentService>,
+    oplog_service: Arc<dyn OplogService>,
+    file_loader: Arc<FileLoader>,
+    owned_worker_id: &OwnedWorkerId,
+    created_by: AccountId,
+    archive: impl Stream<Item = Result<Vec<u8>, WorkerExecutorError>> + Send,
+) -> Result<OplogIndex, WorkerExecutorError> {
+    if oplog_service.exists(owned_worker_id).await {
+        return Err(WorkerExecutorError::worker_already_exists(
+            owned_worker_id.worker_id(),
+        ));
+    }
+
+    let mut importer = OplogImporter {
+        component_service,
+        oplog_service: oplog_service.clone(),
+        file_loader,
+        owned_worker_id,
+        created_by,
+        revisions: None,
+        payload_mapping: HashMap::new(),
+        oplog: None,
+        complete: false,
+    };
+
+    let result = importer.import(archive).await;
+    if result.is_err() {
+        if let Some(oplog) = importer.oplog.take() {
+            drop(oplog);
+            oplog_service.delete(owned_worker_id).await;
+        }
+    }
+    result
+}
+
+struct OplogImporter<'a> {
+    component_service: Arc<dyn ComponentService>,
+    oplog_service: Arc<dyn OplogService>,
+    file_loader: Arc<FileLoader>,
+    owned_worker_id: &'a OwnedWorkerId,
+    created_by: AccountId,
+    /// The component revisions listed in the header of the archive, once validated
+    revisions: Option<BTreeSet<ComponentRevision>>,
+    payload_mapping: HashMap<PayloadId, RawOplogPayload>,
+    /// The new oplog, created when the first entry arrives
+    oplog: Option<Arc<dyn Oplog>>,
+    complete: bool,
+}
+
+impl OplogImporter<'_> {
+    async fn import(
+        &mut self,
+        archive: impl Stream<Item = Result<Vec<u8>, WorkerExecutorError>> + Send,
+    ) -> Result<OplogIndex, WorkerExecutorError> {
+        let mut archive = std::pin::pin!(archive);
+        let mut decoder = ExportedOplogDecoder::new();
+        while let Some(chunk) = archive.next().await {
+            decoder.push(&chunk?);
+            while let Some(part) = decoder
+                .next_part()
+                .map_err(WorkerExecutorError::invalid_request)?
+            {
+                self.import_part(part).await?;
+            }
+        }
+        decoder
+            .finish()
+            .map_err(WorkerExecutorError::invalid_request)?;
+        if !self.complete {
+            return Err(WorkerExecutorError::invalid_request(
+                "The oplog archive is incomplete",
+            ));
+        }
+
+        match &self.oplog {
+            Some(oplog) => Ok(oplog.current_oplog_index().await),
+            None => Err(WorkerExecutorError::invalid_request(
+                "The exported oplog does not contain any entries",
+            )),
+        }
+    }
+
+    async fn import_part(&mut self, part: ExportedOplogPart) -> Result<(), WorkerExecutorError> {
+        if self.complete {
+            return Err(WorkerExecutorError::invalid_request(
+                "The oplog archive continues after its end",
+            ));
+        }
+
+        match part {
+            ExportedOplogPart::Header(header) => self.import_header(header).await,
+            ExportedOplogPart::InitialFile(file) => {
+                // Files of revisions not used by the exported entries are not needed
+                if self.revisions()?.contains(&file.component_revision) {
+                    self.file_loader
+                        .store(self.owned_worker_id.environment_id(), file.data)
+                        .await?;
+                }
+                Ok(())
+            }
+            ExportedOplogPart::Payload(payload) => {
+                self.revisions()?;
+                let uploaded = self
+                    .oplog_service
+                    .upload_raw_payload(self.owned_worker_id, payload.data)
+                    .await
+                    .map_err(|err| {
+                        WorkerExecutorError::runtime(format!(
+                            "Failed to upload oplog payload: {err}"
+                        ))
+                    })?;
+                self.payload_mapping.insert(payload.payload_id, uploaded);
+                Ok(())
+            }
+            ExportedOplogPart::Entries(entries) => self.import_entries(entries).await,
+            ExportedOplogPart::End => {
+                self.revisions()?;
+                self.complete = true;
+                Ok(())
+            }
+        }
+    }
+
+    fn revisions(&self) -> Result<&BTreeSet<ComponentRevision>, WorkerExecutorError> {
+        self.revisions.as_ref().ok_or_else(|| {
+            WorkerExecutorError::invalid_request("The oplog archive must start with a header")
+        })
+    }
+
+    async fn import_header(
+        &mut self,
+        header: ExportedOplogHeader,
+    ) -> Result<(), WorkerExecutorError> {
+        if self.revisions.is_some() {
+            return Err(WorkerExecutorError::invalid_request(
+                "The oplog archive contains more than one header",
+            ));
+        }
+
+        for component_revision in &header.component_revisions {
+            self.component_service
+                .get_metadata(self.owned_worker_id.component_id(), Some(*component_revision))
+                .await
+                .map_err(|err| {
+                    WorkerExecutorError::invalid_request(format!(
+                        "Component revision {component_revision} required by the exported oplog is not available: {err}"
+                    ))
+                })?;
+        }
+
+        self.revisions = Some(header.component_revisions.into_iter().collect());
+        Ok(())
+    }
+
+    async fn import_entries(
+        &mut self,
+        entries: Vec<OplogEntry>,
+    ) -> Result<(), WorkerExecutorError> {
+        let mut entries = entries.into_iter();
+
+        let oplog = match &self.oplog {
+            Some(oplog) => oplog.clone(),
+            None => {
+                let Some(entry) = entries.next() else {
+                    return Ok(());
+                };
+                self.check_revision(&entry)?;
+                let oplog = self.create_oplog(entry).await?;
+                self.oplog = Some(oplog.clone());
+                oplog
+            }
+        };
+
+        for mut entry in entries {
+            self.check_revision(&entry)?;
+            entry
+                .remap_external_payloads(&self.payload_mapping)
+                .map_err(WorkerExecutorError::invalid_request)?;
+            oplog.add(entry).await;
+        }
+        oplog.commit(CommitLevel::Always).await;
+
+        Ok(())
+    }
+
+    /// Only the revisions listed in the header were validated against the target component
+    fn check_revision(&self, entry: &OplogEntry) -> Result<(), WorkerExecutorError> {
+        match entry.specifies_component_revision() {
+            Some(revision) if !self.revisions()?.contains(&revision) => {
+                Err(WorkerExecutorError::invalid_request(format!(
+                    "Component revision {revision} is missing from the header of the oplog archive"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn create_oplog(&self, entry: OplogEntry) -> Result<Arc<dyn Oplog>, WorkerExecutorError> {
+        let owned_worker_id = self.owned_worker_id;
+        let created_by = self.created_by;
+        let (initial_entry, initial_worker_metadata) = match entry {
+            OplogEntry::Create {
+                timestamp,
+                worker_id: _,
+                component_revision,
+                env,
+                environment_id: _,
+                created_by: _,
+                parent: _,
+                component_size,
+                initial_total_linear_memory_size,
+                initial_active_plugins,
+                wasi_config_vars,
+                original_phantom_id,
+            } => {
+                let metadata = WorkerMetadata {
+                    worker_id: owned_worker_id.worker_id(),
+                    env: env.clone(),
+                    environment_id: owned_worker_id.environment_id(),
+                    created_by,
+                    wasi_config_vars: wasi_config_vars.clone(),
+                    created_at: Timestamp::now_utc(),
+                    parent: None,
+                    last_known_status: WorkerStatusRecord {
+                        component_revision,
+                        component_revision_for_replay: component_revision,
+                        component_size,
+                        total_linear_memory_size: initial_total_linear_memory_size,
+                        active_plugins: initial_active_plugins.clone(),
+                        resource_usage: WorkerResourceUsageRecord {
+                            peak_linear_memory_size: initial_total_linear_memory_size,
+                            ..WorkerResourceUsageRecord::default()
+                        },
+                        ..WorkerStatusRecord::default()
+                    },
+                    original_phantom_id,
+                };
+                let entry = OplogEntry::Create {
+                    timestamp,
+                    worker_id: owned_worker_id.worker_id(),
+                    component_revision,
+                    env,
+                    environment_id: owned_worker_id.environment_id(),
+                    created_by,
+                    parent: None,
+                    component_size,
+                    initial_total_linear_memory_size,
+                    initial_active_plugins,
+                    wasi_config_vars,
+                    original_phantom_id,
+                };
+                (entry, metadata)
+            }
+            _ => {
+                return Err(WorkerExecutorError::invalid_request(
+                    "The exported oplog must start with a Create entry",
+                ))
+            }
+        };
+
+        let oplog = self
+            .oplog_service
+            .create(
+                owned_worker_id,
+                initial_entry,
+                initial_worker_metadata.clone(),
+                read_only_lock::tokio::ReadOnlyLock::new(Arc::new(tokio::sync::RwLock::new(
+                    initial_worker_metadata.last_known_status,
+                ))),
+                // Only durable agents have a meaningful oplog to import
+                read_only_lock::std::ReadOnlyLock::new(Arc::new(std::sync::RwLock::new(
+                    ExecutionStatus::Suspended {
+                        agent_mode: AgentMode::Durable,
+                        timestamp: Timestamp::now_utc(),
+                    },
+                ))),
+            )
+            .await;
+
+        Ok(oplog)
+    }
+}
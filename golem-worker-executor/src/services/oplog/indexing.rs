@@ -0,0 +1,533 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::public_oplog::PublicOplogEntryOps;
+use crate::model::ExecutionStatus;
+use crate::services::component::ComponentService;
+use crate::services::golem_config::OplogSearchIndexConfig;
+use crate::services::oplog::search_index::{OplogSearchIndex, OplogSearchIndexUpdate};
+use crate::services::oplog::{CommitLevel, OpenOplogs, Oplog, OplogConstructor, OplogService};
+use async_trait::async_trait;
+use golem_common::model::component::{ComponentId, ComponentRevision};
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::lucene::Query;
+use golem_common::model::oplog::{
+    OplogEntry, OplogIndex, OplogSearchHit, PayloadId, PersistenceLevel, PublicOplogEntry,
+    RawOplogPayload,
+};
+use golem_common::model::{
+    OwnedWorkerId, ScanCursor, WorkerId, WorkerMetadata, WorkerStatusRecord,
+};
+use golem_common::read_only_lock;
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{error, warn, Instrument};
+
+#[derive(Clone)]
+struct CreateOplogConstructor {
+    owned_worker_id: OwnedWorkerId,
+    initial_entry: Option<OplogEntry>,
+    inner: Arc<dyn OplogService>,
+    last_oplog_index: OplogIndex,
+    indexer: Arc<SearchIndexer>,
+    initial_worker_metadata: WorkerMetadata,
+    last_known_status: read_only_lock::tokio::ReadOnlyLock<WorkerStatusRecord>,
+    execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
+}
+
+#[async_trait]
+impl OplogConstructor for CreateOplogConstructor {
+    async fn create_oplog(self, close: Box<dyn FnOnce() + Send + Sync>) -> Arc<dyn Oplog> {
+        let inner = if let Some(initial_entry) = self.initial_entry {
+            self.inner
+                .create(
+                    &self.owned_worker_id,
+                    initial_entry,
+                    self.initial_worker_metadata,
+                    self.last_known_status.clone(),
+                    self.execution_status,
+                )
+                .await
+        } else {
+            self.inner
+                .open(
+                    &self.owned_worker_id,
+                    self.last_oplog_index,
+                    self.initial_worker_metadata,
+                    self.last_known_status.clone(),
+                    self.execution_status,
+                )
+                .await
+        };
+
+        // Everything committed before opening the oplog is indexed first, in case it was missed
+        // earlier
+        let committed = inner.current_oplog_index().await;
+
+        Arc::new(IndexingOplog::new(
+            inner,
+            self.indexer,
+            self.owned_worker_id,
+            committed,
+            self.last_known_status,
+            close,
+        ))
+    }
+}
+
+/// Adds the committed oplog entries of every worker to an `OplogSearchIndex`, and answers
+/// searches over all the workers of a component using it
+pub struct IndexingOplogService {
+    pub inner: Arc<dyn OplogService>,
+    oplogs: OpenOplogs,
+
+    indexer: Arc<SearchIndexer>,
+}
+
+impl IndexingOplogService {
+    pub fn new(
+        inner: Arc<dyn OplogService>,
+        index: Arc<dyn OplogSearchIndex>,
+        components: Arc<dyn ComponentService>,
+        config: OplogSearchIndexConfig,
+    ) -> Self {
+        Self {
+            indexer: Arc::new(SearchIndexer {
+                index,
+                oplog_service: inner.clone(),
+                components,
+                config,
+            }),
+            inner,
+            oplogs: OpenOplogs::new("indexing_oplog_service"),
+        }
+    }
+
+    fn constructor(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        initial_entry: Option<OplogEntry>,
+        last_oplog_index: OplogIndex,
+        initial_worker_metadata: WorkerMetadata,
+        last_known_status: read_only_lock::tokio::ReadOnlyLock<WorkerStatusRecord>,
+        execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
+    ) -> CreateOplogConstructor {
+        CreateOplogConstructor {
+            owned_worker_id: owned_worker_id.clone(),
+            initial_entry,
+            inner: self.inner.clone(),
+            last_oplog_index,
+            indexer: self.indexer.clone(),
+            initial_worker_metadata,
+            last_known_status,
+            execution_status,
+        }
+    }
+}
+
+impl Debug for IndexingOplogService {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexingOplogService").finish()
+    }
+}
+
+#[async_trait]
+impl OplogService for IndexingOplogService {
+    async fn create(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        initial_entry: OplogEntry,
+        initial_worker_metadata: WorkerMetadata,
+        last_known_status: read_only_lock::tokio::ReadOnlyLock<WorkerStatusRecord>,
+        execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
+    ) -> Arc<dyn Oplog + 'static> {
+        self.oplogs
+            .get_or_open(
+                &owned_worker_id.worker_id,
+                self.constructor(
+                    owned_worker_id,
+                    Some(initial_entry),
+                    OplogIndex::INITIAL,
+                    initial_worker_metadata,
+                    last_known_status,
+                    execution_status,
+                ),
+            )
+            .await
+    }
+
+    async fn open(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        last_oplog_index: OplogIndex,
+        initial_worker_metadata: WorkerMetadata,
+        last_known_status: read_only_lock::tokio::ReadOnlyLock<WorkerStatusRecord>,
+        execution_status: read_only_lock::std::ReadOnlyLock<ExecutionStatus>,
+    ) -> Arc<dyn Oplog + 'static> {
+        self.oplogs
+            .get_or_open(
+                &owned_worker_id.worker_id,
+                self.constructor(
+                    owned_worker_id,
+                    None,
+                    last_oplog_index,
+                    initial_worker_metadata,
+                    last_known_status,
+                    execution_status,
+                ),
+            )
+            .await
+    }
+
+    async fn get_last_index(&self, owned_worker_id: &OwnedWorkerId) -> OplogIndex {
+        self.inner.get_last_index(owned_worker_id).await
+    }
+
+    async fn delete(&self, owned_worker_id: &OwnedWorkerId) {
+        self.inner.delete(owned_worker_id).await;
+        if let Err(err) = self.indexer.index.delete(&owned_worker_id.worker_id).await {
+            error!(
+                worker_id = %owned_worker_id.worker_id,
+                "Failed to delete the worker from the oplog search index: {err}"
+            );
+        }
+    }
+
+    async fn read(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idx: OplogIndex,
+        n: u64,
+    ) -> BTreeMap<OplogIndex, OplogEntry> {
+        self.inner.read(owned_worker_id, idx, n).await
+    }
+
+    async fn exists(&self, owned_worker_id: &OwnedWorkerId) -> bool {
+        self.inner.exists(owned_worker_id).await
+    }
+
+    async fn scan_for_component(
+        &self,
+        environment_id: &EnvironmentId,
+        component_id: &ComponentId,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), WorkerExecutorError> {
+        self.inner
+            .scan_for_component(environment_id, component_id, cursor, count)
+            .await
+    }
+
+    async fn upload_raw_payload(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        data: Vec<u8>,
+    ) -> Result<RawOplogPayload, String> {
+        self.inner.upload_raw_payload(owned_worker_id, data).await
+    }
+
+    async fn download_raw_payload(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        payload_id: PayloadId,
+        md5_hash: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        self.inner
+            .download_raw_payload(owned_worker_id, payload_id, md5_hash)
+            .await
+    }
+
+    async fn reencrypt_archives(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<u64>, String> {
+        self.inner.reencrypt_archives(owned_worker_id).await
+    }
+
+    async fn drop_archived_entries(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        last_dropped_idx: OplogIndex,
+    ) -> Option<u64> {
+        self.inner
+            .drop_archived_entries(owned_worker_id, last_dropped_idx)
+            .await
+    }
+
+    async fn search_component_oplogs(
+        &self,
+        component_id: &ComponentId,
+        query: &Query,
+        count: u64,
+        include: &(dyn Fn(&WorkerId) -> bool + Send + Sync),
+    ) -> Result<Vec<OplogSearchHit>, WorkerExecutorError> {
+        let config = &self.indexer.config;
+        let mut hits = Vec::new();
+        let mut scanned = 0;
+        while (hits.len() as u64) < count && scanned < config.max_scanned_entries {
+            let page_size = config
+                .search_page_size
+                .min(config.max_scanned_entries - scanned);
+            let candidates = self
+                .indexer
+                .index
+                .candidates(component_id, query, scanned, page_size)
+                .await
+                .map_err(|err| {
+                    WorkerExecutorError::runtime(format!("Failed to search the oplog index: {err}"))
+                })?;
+            let fetched = candidates.len() as u64;
+
+            hits.extend(
+                candidates
+                    .into_iter()
+                    .filter(|hit| include(&hit.worker_id) && hit.entry.matches(query)),
+            );
+
+            if fetched < page_size {
+                break;
+            }
+            scanned += fetched;
+        }
+        hits.truncate(count as usize);
+        Ok(hits)
+    }
+
+    async fn backfill_search_index(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        component_revision: ComponentRevision,
+    ) -> Result<(), WorkerExecutorError> {
+        let last_index = self.inner.get_last_index(owned_worker_id).await;
+        self.indexer
+            .catch_up(owned_worker_id, component_revision, last_index)
+            .await
+            .map_err(|err| {
+                WorkerExecutorError::runtime(format!(
+                    "Failed to backfill the oplog search index: {err}"
+                ))
+            })
+    }
+}
+
+/// Adds the committed oplog entries of workers to the search index, continuing from the last
+/// entry the index already covers
+struct SearchIndexer {
+    index: Arc<dyn OplogSearchIndex>,
+    oplog_service: Arc<dyn OplogService>,
+    components: Arc<dyn ComponentService>,
+    config: OplogSearchIndexConfig,
+}
+
+impl SearchIndexer {
+    async fn catch_up(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        component_revision: ComponentRevision,
+        target: OplogIndex,
+    ) -> Result<(), String> {
+        let mut last_indexed = self.index.last_indexed(&owned_worker_id.worker_id).await?;
+        let mut component_revision = component_revision;
+
+        while last_indexed < target {
+            let count = self
+                .config
+                .indexing_batch_size
+                .min(u64::from(target) - u64::from(last_indexed));
+            let entries = self
+                .oplog_service
+                .read(owned_worker_id, last_indexed.next(), count)
+                .await;
+            let Some(last_index) = entries.keys().next_back().copied() else {
+                break;
+            };
+
+            let mut update = OplogSearchIndexUpdate {
+                last_index,
+                ..Default::default()
+            };
+            for (idx, entry) in entries {
+                if let Some(revision) = entry.specifies_component_revision() {
+                    component_revision = revision;
+                }
+                if let OplogEntry::Revert { dropped_region, .. } = &entry {
+                    update.dropped_regions.push((idx, dropped_region.clone()));
+                }
+
+                match PublicOplogEntry::from_oplog_entry(
+                    idx,
+                    entry,
+                    self.oplog_service.clone(),
+                    self.components.clone(),
+                    owned_worker_id,
+                    component_revision,
+                )
+                .await
+                {
+                    Ok(public_entry) => update.entries.push((idx, public_entry)),
+                    Err(err) => warn!("Failed to enrich oplog entry {idx} for indexing: {err}"),
+                }
+            }
+
+            self.index
+                .update(&owned_worker_id.worker_id, &update)
+                .await?;
+            last_indexed = last_index;
+        }
+
+        Ok(())
+    }
+}
+
+/// A wrapper for `Oplog` that adds the committed entries to the oplog search index in the
+/// background
+pub struct IndexingOplog {
+    inner: Arc<dyn Oplog>,
+    committed: watch::Sender<OplogIndex>,
+    close_fn: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl IndexingOplog {
+    fn new(
+        inner: Arc<dyn Oplog>,
+        indexer: Arc<SearchIndexer>,
+        owned_worker_id: OwnedWorkerId,
+        committed: OplogIndex,
+        last_known_status: read_only_lock::tokio::ReadOnlyLock<WorkerStatusRecord>,
+        close_fn: Box<dyn FnOnce() + Send + Sync>,
+    ) -> Self {
+        let (committed, mut receiver) = watch::channel(committed);
+
+        // Not aborted when the oplog gets closed, so the last committed entries get indexed
+        // too; it stops after a final round once the sender is dropped
+        tokio::spawn(
+            async move {
+                let mut closed = false;
+                loop {
+                    let target = *receiver.borrow_and_update();
+                    let component_revision = last_known_status.read().await.component_revision;
+
+                    if let Err(err) = indexer
+                        .catch_up(&owned_worker_id, component_revision, target)
+                        .await
+                    {
+                        if closed || receiver.has_changed().is_err() {
+                            // The missing entries are added by the next backfill of the worker
+                            error!("Failed to add oplog entries to the search index: {err}");
+                            break;
+                        }
+                        warn!("Failed to add oplog entries to the search index, retrying: {err}");
+                        tokio::time::sleep(indexer.config.indexing_retry_delay).await;
+                        continue;
+                    }
+
+                    if closed {
+                        break;
+                    }
+                    closed = receiver.changed().await.is_err();
+                }
+            }
+            .in_current_span(),
+        );
+
+        Self {
+            inner,
+            committed,
+            close_fn: Some(close_fn),
+        }
+    }
+}
+
+impl Debug for IndexingOplog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexingOplog").finish()
+    }
+}
+
+impl Drop for IndexingOplog {
+    fn drop(&mut self) {
+        if let Some(close_fn) = self.close_fn.take() {
+            close_fn();
+        }
+    }
+}
+
+#[async_trait]
+impl Oplog for IndexingOplog {
+    async fn add(&self, entry: OplogEntry) -> OplogIndex {
+        self.inner.add(entry).await
+    }
+
+    async fn drop_prefix(&self, last_dropped_id: OplogIndex) -> u64 {
+        self.inner.drop_prefix(last_dropped_id).await
+    }
+
+    async fn commit(&self, level: CommitLevel) -> BTreeMap<OplogIndex, OplogEntry> {
+        let result = self.inner.commit(level).await;
+        if let Some(last) = result.keys().next_back() {
+            self.committed.send_if_modified(|committed| {
+                let advanced = *committed < *last;
+                if advanced {
+                    *committed = *last;
+                }
+                advanced
+            });
+        }
+        result
+    }
+
+    async fn current_oplog_index(&self) -> OplogIndex {
+        self.inner.current_oplog_index().await
+    }
+
+    async fn last_added_non_hint_entry(&self) -> Option<OplogIndex> {
+        self.inner.last_added_non_hint_entry().await
+    }
+
+    async fn wait_for_replicas(&self, replicas: u8, timeout: Duration) -> bool {
+        self.inner.wait_for_replicas(replicas, timeout).await
+    }
+
+    async fn read(&self, oplog_index: OplogIndex) -> OplogEntry {
+        self.inner.read(oplog_index).await
+    }
+
+    async fn read_many(&self, oplog_index: OplogIndex, n: u64) -> BTreeMap<OplogIndex, OplogEntry> {
+        self.inner.read_many(oplog_index, n).await
+    }
+
+    async fn length(&self) -> u64 {
+        self.inner.length().await
+    }
+
+    async fn upload_raw_payload(&self, data: Vec<u8>) -> Result<RawOplogPayload, String> {
+        self.inner.upload_raw_payload(data).await
+    }
+
+    async fn download_raw_payload(
+        &self,
+        payload_id: PayloadId,
+        md5_hash: Vec<u8>,
+    ) -> Result<Vec<u8>, String> {
+        self.inner.download_raw_payload(payload_id, md5_hash).await
+    }
+
+    async fn switch_persistence_level(&self, mode: PersistenceLevel) {
+        self.inner.switch_persistence_level(mode).await;
+    }
+}
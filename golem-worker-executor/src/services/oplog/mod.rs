@@ -21,10 +21,11 @@ use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode};
 use golem_common::model::component::{ComponentId, ComponentRevision};
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::lucene::Query;
 use golem_common::model::oplog::host_functions::HostFunctionName;
 use golem_common::model::oplog::{
     DurableFunctionType, HostRequest, HostResponse, OplogEntry, OplogIndex, OplogPayload,
-    PayloadId, PersistenceLevel, RawOplogPayload, UpdateDescription,
+    OplogSearchHit, PayloadId, PersistenceLevel, RawOplogPayload, UpdateDescription,
 };
 use golem_common::model::{
     IdempotencyKey, OwnedWorkerId, ScanCursor, Timestamp, WorkerId, WorkerMetadata,
@@ -46,10 +47,15 @@ use std::time::Duration;
 
 mod blob;
 mod compressed;
+pub mod compression;
+pub mod encryption;
 mod ephemeral;
+pub mod export;
+pub mod indexing;
 mod multilayer;
 pub mod plugin;
 mod primary;
+pub mod search_index;
 
 #[cfg(test)]
 pub mod tests;
@@ -156,6 +162,63 @@ pub trait OplogService: Debug + Send + Sync {
         payload_id: PayloadId,
         md5_hash: Vec<u8>,
     ) -> Result<Vec<u8>, String>;
+
+    /// Re-encrypts the archived layers of the worker's oplog with the current encryption key of
+    /// its environment, returning the number of re-encrypted entries. Returns `None` if the oplog
+    /// is currently open, in which case it has to be retried later.
+    ///
+    /// Entries of the primary layer are not affected, they get re-encrypted when they are moved
+    /// to the archive layers.
+    async fn reencrypt_archives(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<u64>, String> {
+        Ok(Some(0))
+    }
+
+    /// Drops the archived entries of the worker's oplog following its initial entry, up to and
+    /// including `last_dropped_idx`, returning the number of dropped chunks. Returns `None` if
+    /// the oplog is currently open, in which case it has to be retried later.
+    ///
+    /// Only whole chunks of the archive layers are dropped, so the primary layer and the chunks
+    /// also holding entries outside of the range are kept. The dropped entries cannot be read
+    /// anymore, so this must only be used for entries the worker never replays again, like the
+    /// ones preceding the snapshot it recovers from.
+    async fn drop_archived_entries(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+        _last_dropped_idx: OplogIndex,
+    ) -> Option<u64> {
+        Some(0)
+    }
+
+    /// Searches the committed entries of all the workers of a component for the ones matching
+    /// the query, newest first. Only the workers accepted by `include` are considered.
+    ///
+    /// Requires the oplog search index to be enabled.
+    async fn search_component_oplogs(
+        &self,
+        _component_id: &ComponentId,
+        _query: &Query,
+        _count: u64,
+        _include: &(dyn Fn(&WorkerId) -> bool + Send + Sync),
+    ) -> Result<Vec<OplogSearchHit>, WorkerExecutorError> {
+        Err(WorkerExecutorError::invalid_request(
+            "The oplog search index is not enabled",
+        ))
+    }
+
+    /// Adds the committed entries of a worker which are missing from the oplog search index.
+    ///
+    /// `component_revision` is the revision used for the entries which are not preceded by
+    /// an entry specifying it. Does nothing if the oplog search index is not enabled.
+    async fn backfill_search_index(
+        &self,
+        _owned_worker_id: &OwnedWorkerId,
+        _component_revision: ComponentRevision,
+    ) -> Result<(), WorkerExecutorError> {
+        Ok(())
+    }
 }
 
 /// Level of commit guarantees
@@ -337,6 +400,16 @@ pub trait OplogOps: Oplog {
         Ok(entry)
     }
 
+    async fn add_snapshot(&self, data: &Vec<u8>) -> Result<OplogEntry, String> {
+        let payload = self.upload_payload(data).await?;
+        let entry = OplogEntry::Snapshot {
+            timestamp: Timestamp::now_utc(),
+            data: payload,
+        };
+        self.add(entry.clone()).await;
+        Ok(entry)
+    }
+
     async fn create_snapshot_based_update_description(
         &self,
         target_revision: ComponentRevision,
@@ -464,8 +537,11 @@ impl OpenOplogs {
                         Ok(OpenOplogEntry::new(result))
                     },
                 )
-                .await
-                .unwrap();
+                .await;
+            let Ok(entry) = entry else {
+                // The oplog was kept closed by `while_closed`, trying again
+                continue;
+            };
             if let Some(oplog) = entry.oplog.upgrade() {
                 let oplog = if entry.initial.load(Ordering::Acquire) {
                     let oplog = unsafe {
@@ -486,6 +562,39 @@ impl OpenOplogs {
             }
         }
     }
+
+    /// Runs `f` while the oplog of the given worker is closed, preventing it from being opened
+    /// until `f` finishes. Returns `None` without running `f` if the oplog is open.
+    pub async fn while_closed<R>(
+        &self,
+        worker_id: &WorkerId,
+        f: impl AsyncFnOnce() -> R,
+    ) -> Option<R> {
+        let mut f = Some(f);
+        let mut result = None;
+        loop {
+            let entry = self
+                .oplogs
+                .get_or_insert(
+                    worker_id,
+                    || (),
+                    async |_| {
+                        if let Some(f) = f.take() {
+                            result = Some(f().await);
+                        }
+                        // Not caching anything, so the waiting `get_or_open` calls try again
+                        Err(())
+                    },
+                )
+                .await;
+            match entry {
+                Ok(entry) if entry.oplog.upgrade().is_none() => {
+                    self.oplogs.remove(worker_id).await;
+                }
+                _ => break result,
+            }
+        }
+    }
 }
 
 impl Debug for OpenOplogs {
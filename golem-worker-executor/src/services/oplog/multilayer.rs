@@ -107,11 +107,46 @@ pub trait OplogArchive: Debug {
     /// This should only be called _after_ `append` succeeded in the archive below this one
     async fn drop_prefix(&self, last_dropped_id: OplogIndex) -> u64;
 
+    /// Drops the chunks holding only entries of the closed range `first_dropped_id..=last_dropped_id`,
+    /// returning the number of dropped chunks. Chunks also holding entries outside of the range are kept.
+    ///
+    /// Like `append` and `drop_prefix`, this must not be called concurrently with other writes.
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) -> u64;
+
     /// Gets the total number of entries in this oplog archive
     async fn length(&self) -> u64;
 
     /// Gets the last index in this oplog archive
     async fn get_last_index(&self) -> OplogIndex;
+
+    /// Checks whether any of the stored chunks is not encrypted with the current key of the
+    /// worker's environment
+    async fn needs_reencryption(&self) -> Result<bool, String>;
+
+    /// Re-encrypts the stored chunks with the current key of the worker's environment, returning
+    /// the number of re-encrypted entries
+    ///
+    /// Like `append` and `drop_prefix`, this must not be called concurrently with other writes.
+    async fn reencrypt(&self) -> Result<u64, String>;
+}
+
+/// Selects the chunks of an archive, identified by the index of their last entry, that hold only
+/// entries of the closed range `first..=last`. Each chunk begins right after the previous one, so
+/// the first chunk is never selected as it is not known where it begins.
+pub(crate) fn chunks_within_range(
+    chunk_ids: impl IntoIterator<Item = OplogIndex>,
+    first: OplogIndex,
+    last: OplogIndex,
+) -> Vec<OplogIndex> {
+    let mut result = Vec::new();
+    let mut previous: Option<OplogIndex> = None;
+    for id in chunk_ids {
+        if previous.is_some_and(|previous| previous.next() >= first) && id <= last {
+            result.push(id);
+        }
+        previous = Some(id);
+    }
+    result
 }
 
 #[derive(Debug)]
@@ -502,6 +537,64 @@ impl OplogService for MultiLayerOplogService {
             .download_raw_payload(owned_worker_id, payload_id, md5_hash)
             .await
     }
+
+    /// Archives are only written by open oplogs, so the oplog is kept closed while its layers are
+    /// re-encrypted. Entries of the upper layers are moved to the next layer, which re-encrypts
+    /// them the same way as the background transfer, and only the last layer is rewritten in place.
+    async fn reencrypt_archives(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<u64>, String> {
+        self.oplogs
+            .while_closed(&owned_worker_id.worker_id, async || {
+                let mut archives = Vec::new();
+                for layer in self.lower.iter() {
+                    archives.push(layer.open(owned_worker_id).await);
+                }
+
+                let mut reencrypted = 0;
+                for (idx, archive) in archives.iter().enumerate() {
+                    if !archive.needs_reencryption().await? {
+                        continue;
+                    }
+                    match archives.get(idx + 1) {
+                        Some(next) => {
+                            let last_idx = archive.current_oplog_index().await;
+                            let entries = archive.read_prefix(last_idx).await;
+                            reencrypted += entries.len() as u64;
+                            next.append(entries.into_iter().collect()).await;
+                            archive.drop_prefix(last_idx).await;
+                        }
+                        None => {
+                            reencrypted += archive.reencrypt().await?;
+                        }
+                    }
+                }
+                Ok::<_, String>(reencrypted)
+            })
+            .await
+            .transpose()
+    }
+
+    async fn drop_archived_entries(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        last_dropped_idx: OplogIndex,
+    ) -> Option<u64> {
+        self.oplogs
+            .while_closed(&owned_worker_id.worker_id, async || {
+                let mut dropped = 0;
+                for layer in self.lower.iter() {
+                    dropped += layer
+                        .open(owned_worker_id)
+                        .await
+                        .drop_range(OplogIndex::INITIAL.next(), last_dropped_idx)
+                        .await;
+                }
+                dropped
+            })
+            .await
+    }
 }
 
 pub struct MultiLayerOplog {
@@ -972,6 +1065,18 @@ impl OplogArchive for WrappedOplogArchive {
         dropped_entries
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) -> u64 {
+        let dropped_entries = self
+            .archive
+            .drop_range(first_dropped_id, last_dropped_id)
+            .await;
+        let new_length = self.archive.length().await;
+        let old_entry_count = self.entry_count.load(Ordering::Acquire);
+        let new_entry_count = min(new_length, old_entry_count);
+        self.entry_count.store(new_entry_count, Ordering::Release);
+        dropped_entries
+    }
+
     async fn length(&self) -> u64 {
         self.archive.length().await
     }
@@ -979,6 +1084,14 @@ impl OplogArchive for WrappedOplogArchive {
     async fn get_last_index(&self) -> OplogIndex {
         self.archive.get_last_index().await
     }
+
+    async fn needs_reencryption(&self) -> Result<bool, String> {
+        self.archive.needs_reencryption().await
+    }
+
+    async fn reencrypt(&self) -> Result<u64, String> {
+        self.archive.reencrypt().await
+    }
 }
 
 struct BackgroundTransferFromPrimary {
@@ -541,6 +541,23 @@ impl OplogService for ForwardingOplogService {
             .download_raw_payload(owned_worker_id, payload_id, md5_hash)
             .await
     }
+
+    async fn reencrypt_archives(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Option<u64>, String> {
+        self.inner.reencrypt_archives(owned_worker_id).await
+    }
+
+    async fn drop_archived_entries(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        last_dropped_idx: OplogIndex,
+    ) -> Option<u64> {
+        self.inner
+            .drop_archived_entries(owned_worker_id, last_dropped_idx)
+            .await
+    }
 }
 
 /// A wrapper for `Oplog` that periodically sends buffered oplog entries to oplog processor plugins
@@ -14,6 +14,7 @@
 
 use crate::metrics::oplog::record_oplog_call;
 use crate::model::ExecutionStatus;
+use crate::services::oplog::encryption::{OplogEncryption, OplogRecordId};
 use crate::services::oplog::{CommitLevel, OpenOplogs, Oplog, OplogConstructor, OplogService};
 use crate::storage::indexed::{
     IndexedStorage, IndexedStorageLabelledApi, IndexedStorageMetaNamespace, IndexedStorageNamespace,
@@ -51,6 +52,7 @@ pub struct PrimaryOplogService {
     max_operations_before_commit: u64,
     max_operations_before_commit_in_persist_nothing: u64,
     max_payload_size: usize,
+    encryption: OplogEncryption,
     oplogs: OpenOplogs,
 }
 
@@ -61,6 +63,7 @@ impl PrimaryOplogService {
         max_operations_before_commit: u64,
         max_operations_before_commit_in_persist_nothing: u64,
         max_payload_size: usize,
+        encryption: OplogEncryption,
     ) -> Self {
         let replicas = indexed_storage
             .with("oplog", "new")
@@ -76,6 +79,7 @@ impl PrimaryOplogService {
             max_operations_before_commit,
             max_operations_before_commit_in_persist_nothing,
             max_payload_size,
+            encryption,
             oplogs: OpenOplogs::new("primary oplog"),
         }
     }
@@ -101,14 +105,28 @@ impl PrimaryOplogService {
         }
     }
 
+    /// Big payloads are encrypted before uploading them, and their hash is computed from the
+    /// stored bytes, so the blob paths do not reveal anything about the plain payloads
     async fn upload_raw_payload(
         blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+        encryption: &OplogEncryption,
         max_payload_size: usize,
         owned_worker_id: &OwnedWorkerId,
         data: Vec<u8>,
     ) -> Result<RawOplogPayload, String> {
         if data.len() > max_payload_size {
             let payload_id: PayloadId = PayloadId::new();
+            let data = encryption
+                .encrypt(
+                    &owned_worker_id.environment_id(),
+                    OplogRecordId::Payload {
+                        worker_id: &owned_worker_id.worker_id,
+                        payload_id: &payload_id,
+                    },
+                    data,
+                )
+                .await
+                .map_err(|e| format!("Failed encrypting oplog data: {e}"))?;
             let md5_hash = md5::compute(&data).to_vec();
 
             blob_storage
@@ -136,11 +154,12 @@ impl PrimaryOplogService {
 
     async fn download_raw_payload(
         blob_storage: Arc<dyn BlobStorage + Send + Sync>,
+        encryption: &OplogEncryption,
         owned_worker_id: &OwnedWorkerId,
         payload_id: PayloadId,
         md5_hash: Vec<u8>,
     ) -> Result<Vec<u8>, String> {
-        blob_storage
+        let data = blob_storage
                     .get_raw(
                         "oplog",
                         "download_payload",
@@ -152,7 +171,18 @@ impl PrimaryOplogService {
                     )
                     .await
                     .map_err(|e| format!("Failed downloading oplog data from the blob store {e}"))?
-                    .ok_or(format!("Payload not found (worker: {owned_worker_id}, payload_id: {payload_id}, md5 hash: {md5_hash:02X?})"))
+                    .ok_or(format!("Payload not found (worker: {owned_worker_id}, payload_id: {payload_id}, md5 hash: {md5_hash:02X?})"))?;
+        encryption
+            .decrypt(
+                &owned_worker_id.environment_id(),
+                OplogRecordId::Payload {
+                    worker_id: &owned_worker_id.worker_id,
+                    payload_id: &payload_id,
+                },
+                data,
+            )
+            .await
+            .map_err(|e| format!("Failed decrypting oplog data: {e}"))
     }
 }
 
@@ -182,9 +212,23 @@ impl OplogService for PrimaryOplogService {
             panic!("oplog for worker {owned_worker_id} already exists in indexed storage")
         }
 
+        let initial_entry = self
+            .encryption
+            .encrypt_value(
+                &owned_worker_id.environment_id(),
+                OplogRecordId::Entry {
+                    worker_id: &owned_worker_id.worker_id,
+                    index: OplogIndex::INITIAL,
+                },
+                &initial_entry,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to encrypt initial oplog entry for worker {owned_worker_id}: {err}")
+            });
         self.indexed_storage
             .with_entity("oplog", "create", "entry")
-            .append(IndexedStorageNamespace::OpLog { worker_id: owned_worker_id.worker_id() }, &key, 1, &initial_entry)
+            .append_raw(IndexedStorageNamespace::OpLog { worker_id: owned_worker_id.worker_id() }, &key, 1, initial_entry)
             .await
             .unwrap_or_else(|err| {
                 panic!(
@@ -224,6 +268,7 @@ impl OplogService for PrimaryOplogService {
                     self.max_operations_before_commit,
                     self.max_operations_before_commit_in_persist_nothing,
                     self.max_payload_size,
+                    self.encryption.clone(),
                     key,
                     last_oplog_index,
                     owned_worker_id.clone(),
@@ -276,9 +321,10 @@ impl OplogService for PrimaryOplogService {
     ) -> BTreeMap<OplogIndex, OplogEntry> {
         record_oplog_call("read");
 
-        self.indexed_storage
+        let entries = self
+            .indexed_storage
             .with_entity("oplog", "read", "entry")
-            .read(
+            .read_raw(
                 IndexedStorageNamespace::OpLog {
                     worker_id: owned_worker_id.worker_id(),
                 },
@@ -291,10 +337,13 @@ impl OplogService for PrimaryOplogService {
                 panic!(
                     "failed to read oplog for worker {owned_worker_id} from indexed storage: {err}"
                 )
+            });
+
+        decrypt_entries(&self.encryption, owned_worker_id, entries)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to decrypt oplog for worker {owned_worker_id}: {err}")
             })
-            .into_iter()
-            .map(|(k, v): (u64, OplogEntry)| (OplogIndex::from_u64(k), v))
-            .collect()
     }
 
     async fn exists(&self, owned_worker_id: &OwnedWorkerId) -> bool {
@@ -350,6 +399,7 @@ impl OplogService for PrimaryOplogService {
     ) -> Result<RawOplogPayload, String> {
         Self::upload_raw_payload(
             self.blob_storage.clone(),
+            &self.encryption,
             self.max_payload_size,
             owned_worker_id,
             data,
@@ -365,6 +415,7 @@ impl OplogService for PrimaryOplogService {
     ) -> Result<Vec<u8>, String> {
         Self::download_raw_payload(
             self.blob_storage.clone(),
+            &self.encryption,
             owned_worker_id,
             payload_id,
             md5_hash,
@@ -381,6 +432,7 @@ struct CreateOplogConstructor {
     max_operations_before_commit: u64,
     max_operations_before_commit_in_persist_nothing: u64,
     max_payload_size: usize,
+    encryption: OplogEncryption,
     key: String,
     last_oplog_idx: OplogIndex,
     owned_worker_id: OwnedWorkerId,
@@ -394,6 +446,7 @@ impl CreateOplogConstructor {
         max_operations_before_commit: u64,
         max_operations_before_commit_in_persist_nothing: u64,
         max_payload_size: usize,
+        encryption: OplogEncryption,
         key: String,
         last_oplog_idx: OplogIndex,
         owned_worker_id: OwnedWorkerId,
@@ -405,6 +458,7 @@ impl CreateOplogConstructor {
             max_operations_before_commit,
             max_operations_before_commit_in_persist_nothing,
             max_payload_size,
+            encryption,
             key,
             last_oplog_idx,
             owned_worker_id,
@@ -422,6 +476,7 @@ impl OplogConstructor for CreateOplogConstructor {
             self.max_operations_before_commit,
             self.max_operations_before_commit_in_persist_nothing,
             self.max_payload_size,
+            self.encryption,
             self.key,
             self.last_oplog_idx,
             self.owned_worker_id,
@@ -452,6 +507,7 @@ impl PrimaryOplog {
         max_operations_before_commit: u64,
         max_operations_before_commit_in_persist_nothing: u64,
         max_payload_size: usize,
+        encryption: OplogEncryption,
         key: String,
         last_oplog_idx: OplogIndex,
         owned_worker_id: OwnedWorkerId,
@@ -465,6 +521,7 @@ impl PrimaryOplog {
                 max_operations_before_commit,
                 max_operations_before_commit_in_persist_nothing,
                 max_payload_size,
+                encryption,
                 key: key.clone(),
                 buffer: VecDeque::new(),
                 last_committed_idx: last_oplog_idx,
@@ -486,6 +543,7 @@ struct PrimaryOplogState {
     max_operations_before_commit: u64,
     max_operations_before_commit_in_persist_nothing: u64,
     max_payload_size: usize,
+    encryption: OplogEncryption,
     key: String,
     buffer: VecDeque<OplogEntry>,
     last_oplog_idx: OplogIndex,
@@ -506,15 +564,33 @@ impl PrimaryOplogState {
             pairs.push((oplog_idx.into(), entry));
             last_idx = oplog_idx;
         }
-        let pairs_ref: Vec<(u64, &OplogEntry)> = pairs.iter().map(|(id, e)| (*id, e)).collect();
+        let environment_id = self.owned_worker_id.environment_id();
+        let mut encrypted_pairs = Vec::with_capacity(pairs.len());
+        for (id, entry) in &pairs {
+            let encrypted = self
+                .encryption
+                .encrypt_value(
+                    &environment_id,
+                    OplogRecordId::Entry {
+                        worker_id: &self.owned_worker_id.worker_id,
+                        index: OplogIndex::from_u64(*id),
+                    },
+                    entry,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to encrypt oplog entry for {}: {err}", self.key)
+                });
+            encrypted_pairs.push((*id, encrypted));
+        }
         self.indexed_storage
             .with_entity("oplog", "append", "entry")
-            .append_many(
+            .append_many_raw(
                 IndexedStorageNamespace::OpLog {
                     worker_id: self.owned_worker_id.worker_id(),
                 },
                 &self.key,
-                &pairs_ref,
+                encrypted_pairs,
             )
             .await
             .unwrap_or_else(|err| {
@@ -523,7 +599,6 @@ impl PrimaryOplogState {
                     self.key
                 )
             });
-        drop(pairs_ref);
 
         self.last_committed_idx = last_idx;
         BTreeMap::from_iter(
@@ -590,10 +665,10 @@ impl PrimaryOplogState {
     async fn read(&self, oplog_index: OplogIndex) -> OplogEntry {
         record_oplog_call("read");
 
-        let entries: Vec<(u64, OplogEntry)> = self
+        let entries = self
             .indexed_storage
             .with_entity("oplog", "read", "entry")
-            .read(
+            .read_raw(
                 IndexedStorageNamespace::OpLog {
                     worker_id: self.owned_worker_id.worker_id(),
                 },
@@ -609,26 +684,31 @@ impl PrimaryOplogState {
                 )
             });
 
-        entries
-            .into_iter()
-            .next()
+        decrypt_entries(&self.encryption, &self.owned_worker_id, entries)
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to decrypt oplog entry {oplog_index} from {}: {err}",
+                    self.key
+                )
+            })
+            .remove(&oplog_index)
             .unwrap_or_else(|| {
                 panic!(
                     "Missing oplog entry {oplog_index} for {} in indexed storage",
                     self.key
                 )
             })
-            .1
     }
 
     async fn read_many(&self, oplog_index: OplogIndex, n: u64) -> BTreeMap<OplogIndex, OplogEntry> {
         record_oplog_call("read_many");
 
         let last_idx = oplog_index.range_end(n);
-        let mut result: BTreeMap<OplogIndex, OplogEntry> = self
+        let entries = self
             .indexed_storage
             .with_entity("oplog", "read", "entry")
-            .read(
+            .read_raw(
                 IndexedStorageNamespace::OpLog { worker_id: self.owned_worker_id.worker_id() },
                 &self.key,
                 oplog_index.into(),
@@ -640,7 +720,15 @@ impl PrimaryOplogState {
                     "failed to read {n} oplog entries from index {oplog_index} from {} from indexed storage: {err}",
                     self.key
                 )
-            }).into_iter().map(|(idx, entry)| (OplogIndex::from_u64(idx), entry)).collect();
+            });
+        let mut result = decrypt_entries(&self.encryption, &self.owned_worker_id, entries)
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to decrypt {n} oplog entries from index {oplog_index} from {}: {err}",
+                    self.key
+                )
+            });
 
         if last_idx < self.last_committed_idx {
             // The whole range is already committed, no further action needed
@@ -789,16 +877,23 @@ impl Oplog for PrimaryOplog {
     }
 
     async fn upload_raw_payload(&self, data: Vec<u8>) -> Result<RawOplogPayload, String> {
-        let (blob_storage, owned_worker_id, max_length) = {
+        let (blob_storage, encryption, owned_worker_id, max_length) = {
             let state = self.state.lock().await;
             (
                 state.blob_storage.clone(),
+                state.encryption.clone(),
                 state.owned_worker_id.clone(),
                 state.max_payload_size,
             )
         };
-        PrimaryOplogService::upload_raw_payload(blob_storage, max_length, &owned_worker_id, data)
-            .await
+        PrimaryOplogService::upload_raw_payload(
+            blob_storage,
+            &encryption,
+            max_length,
+            &owned_worker_id,
+            data,
+        )
+        .await
     }
 
     async fn download_raw_payload(
@@ -806,12 +901,17 @@ impl Oplog for PrimaryOplog {
         payload_id: PayloadId,
         md5_hash: Vec<u8>,
     ) -> Result<Vec<u8>, String> {
-        let (blob_storage, owned_worker_id) = {
+        let (blob_storage, encryption, owned_worker_id) = {
             let state = self.state.lock().await;
-            (state.blob_storage.clone(), state.owned_worker_id.clone())
+            (
+                state.blob_storage.clone(),
+                state.encryption.clone(),
+                state.owned_worker_id.clone(),
+            )
         };
         PrimaryOplogService::download_raw_payload(
             blob_storage,
+            &encryption,
             &owned_worker_id,
             payload_id,
             md5_hash,
@@ -824,3 +924,27 @@ impl Oplog for PrimaryOplog {
         state.switch_persistence_level(mode)
     }
 }
+
+async fn decrypt_entries(
+    encryption: &OplogEncryption,
+    owned_worker_id: &OwnedWorkerId,
+    entries: Vec<(u64, Vec<u8>)>,
+) -> Result<BTreeMap<OplogIndex, OplogEntry>, String> {
+    let environment_id = owned_worker_id.environment_id();
+    let mut result = BTreeMap::new();
+    for (idx, bytes) in entries {
+        let index = OplogIndex::from_u64(idx);
+        let entry = encryption
+            .decrypt_value(
+                &environment_id,
+                OplogRecordId::Entry {
+                    worker_id: &owned_worker_id.worker_id,
+                    index,
+                },
+                bytes,
+            )
+            .await?;
+        result.insert(index, entry);
+    }
+    Ok(result)
+}
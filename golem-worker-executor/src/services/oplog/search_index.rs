@@ -0,0 +1,765 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use golem_common::model::component::ComponentId;
+use golem_common::model::lucene::{parse_instant, parse_number, Query, RangeValue};
+use golem_common::model::oplog::{OplogIndex, OplogSearchHit, PublicOplogEntry};
+use golem_common::model::regions::OplogRegion;
+use golem_common::model::WorkerId;
+use golem_common::SafeDisplay;
+use golem_service_base::db::postgres::PostgresPool;
+use golem_service_base::db::sqlite::SqlitePool;
+use std::collections::HashSet;
+use std::ops::Bound;
+
+/// Changes of the index caused by a range of committed oplog entries of a worker
+#[derive(Debug, Clone, Default)]
+pub struct OplogSearchIndexUpdate {
+    /// Public form of the entries to add, entries which could not be converted are left out
+    pub entries: Vec<(OplogIndex, PublicOplogEntry)>,
+    /// Regions dropped by revert entries, with the index of the revert entry
+    pub dropped_regions: Vec<(OplogIndex, OplogRegion)>,
+    /// The last oplog index covered by the update
+    pub last_index: OplogIndex,
+}
+
+/// Secondary index of the committed oplog entries of all the workers, making it possible to
+/// search the oplogs of a whole component without reading each of them.
+///
+/// The index only preselects candidates for a query, they still have to be checked with
+/// `PublicOplogEntry::matches`.
+#[async_trait]
+pub trait OplogSearchIndex: Send + Sync {
+    /// The last oplog index of the worker covered by the index, `OplogIndex::NONE` if none
+    async fn last_indexed(&self, worker_id: &WorkerId) -> Result<OplogIndex, String>;
+
+    /// Adds the entries of a worker to the index and removes the ones dropped by reverts.
+    ///
+    /// Parts of the update which are already covered by the index are ignored, so applying the
+    /// same update again, or concurrently, is harmless.
+    async fn update(
+        &self,
+        worker_id: &WorkerId,
+        update: &OplogSearchIndexUpdate,
+    ) -> Result<(), String>;
+
+    /// Removes all the indexed entries of a worker
+    async fn delete(&self, worker_id: &WorkerId) -> Result<(), String>;
+
+    /// Returns a page of the indexed entries of a component which may match the query, newest
+    /// first
+    async fn candidates(
+        &self,
+        component_id: &ComponentId,
+        query: &Query,
+        offset: u64,
+        count: u64,
+    ) -> Result<Vec<OplogSearchHit>, String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SqliteOplogSearchIndex {
+    pool: SqlitePool,
+}
+
+impl SqliteOplogSearchIndex {
+    /// Creates a new index on top of a pool whose schema has already been migrated
+    /// (see `crate::storage::migrate_sqlite`)
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OplogSearchIndex for SqliteOplogSearchIndex {
+    async fn last_indexed(&self, worker_id: &WorkerId) -> Result<OplogIndex, String> {
+        let last_indexed: Option<(i64,)> = self
+            .pool
+            .with_ro("oplog_search_index", "last_indexed")
+            .fetch_optional_as(
+                sqlx::query_as(
+                    "SELECT last_indexed FROM oplog_search_progress WHERE component_id = ? AND worker_name = ?;",
+                )
+                .bind(worker_id.component_id.to_string())
+                .bind(&worker_id.worker_name),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+        Ok(to_oplog_index(last_indexed))
+    }
+
+    async fn update(
+        &self,
+        worker_id: &WorkerId,
+        update: &OplogSearchIndexUpdate,
+    ) -> Result<(), String> {
+        let component_id = worker_id.component_id.to_string();
+        let api = self.pool.with_rw("oplog_search_index", "update");
+        let mut tx = api.begin().await.map_err(|err| err.to_safe_string())?;
+
+        let last_indexed: Option<(i64,)> = tx
+            .fetch_optional_as(
+                sqlx::query_as(
+                    "SELECT last_indexed FROM oplog_search_progress WHERE component_id = ? AND worker_name = ?;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+        let last_indexed = to_oplog_index(last_indexed);
+
+        for (oplog_index, entry) in update.entries.iter().filter(|(idx, _)| *idx > last_indexed) {
+            let oplog_index = u64::from(*oplog_index) as i64;
+            let json = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+
+            tx.execute(
+                sqlx::query(
+                    "DELETE FROM oplog_search_terms WHERE component_id = ? AND worker_name = ? AND oplog_index = ?;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(oplog_index),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+            tx.execute(
+                sqlx::query(
+                    "INSERT INTO oplog_search_entries (component_id, worker_name, oplog_index, timestamp, entry) VALUES (?, ?, ?, ?, ?) ON CONFLICT (component_id, worker_name, oplog_index) DO UPDATE SET timestamp = excluded.timestamp, entry = excluded.entry;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(oplog_index)
+                .bind(entry.timestamp().to_millis() as i64)
+                .bind(json),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+            for term in IndexedTerm::all(entry) {
+                tx.execute(
+                    sqlx::query(
+                        "INSERT INTO oplog_search_terms (component_id, worker_name, oplog_index, path, value, lower_value, number, instant) VALUES (?, ?, ?, ?, ?, ?, ?, ?);",
+                    )
+                    .bind(&component_id)
+                    .bind(&worker_id.worker_name)
+                    .bind(oplog_index)
+                    .bind(term.path)
+                    .bind(term.value)
+                    .bind(term.lower_value)
+                    .bind(term.number)
+                    .bind(term.instant),
+                )
+                .await
+                .map_err(|err| err.to_safe_string())?;
+            }
+        }
+
+        for (_, region) in update
+            .dropped_regions
+            .iter()
+            .filter(|(idx, _)| *idx > last_indexed)
+        {
+            let start = u64::from(region.start) as i64;
+            let end = u64::from(region.end) as i64;
+
+            tx.execute(
+                sqlx::query(
+                    "DELETE FROM oplog_search_terms WHERE component_id = ? AND worker_name = ? AND oplog_index BETWEEN ? AND ?;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(start)
+                .bind(end),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+            tx.execute(
+                sqlx::query(
+                    "DELETE FROM oplog_search_entries WHERE component_id = ? AND worker_name = ? AND oplog_index BETWEEN ? AND ?;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(start)
+                .bind(end),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+        }
+
+        tx.execute(
+            sqlx::query(
+                "INSERT INTO oplog_search_progress (component_id, worker_name, last_indexed) VALUES (?, ?, ?) ON CONFLICT (component_id, worker_name) DO UPDATE SET last_indexed = MAX(last_indexed, excluded.last_indexed);",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name)
+            .bind(u64::from(update.last_index) as i64),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+
+        tx.commit().await.map_err(|err| err.to_safe_string())
+    }
+
+    async fn delete(&self, worker_id: &WorkerId) -> Result<(), String> {
+        let component_id = worker_id.component_id.to_string();
+        let api = self.pool.with_rw("oplog_search_index", "delete");
+        let mut tx = api.begin().await.map_err(|err| err.to_safe_string())?;
+
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_terms WHERE component_id = ? AND worker_name = ?;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_entries WHERE component_id = ? AND worker_name = ?;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_progress WHERE component_id = ? AND worker_name = ?;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+
+        tx.commit().await.map_err(|err| err.to_safe_string())
+    }
+
+    async fn candidates(
+        &self,
+        component_id: &ComponentId,
+        query: &Query,
+        offset: u64,
+        count: u64,
+    ) -> Result<Vec<OplogSearchHit>, String> {
+        let statement = CandidatesStatement::new(component_id, query, offset, count, false);
+
+        let mut sqlx_query = sqlx::query_as::<_, (String, i64, String)>(&statement.sql);
+        for bind in &statement.binds {
+            sqlx_query = match bind {
+                SearchBind::Text(value) => sqlx_query.bind(value),
+                SearchBind::Number(value) => sqlx_query.bind(value),
+                SearchBind::Integer(value) => sqlx_query.bind(value),
+            };
+        }
+
+        let rows: Vec<(String, i64, String)> = self
+            .pool
+            .with_ro("oplog_search_index", "candidates")
+            .fetch_all_as(sqlx_query)
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+        rows.into_iter()
+            .map(|row| to_search_hit(component_id, row))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresOplogSearchIndex {
+    pool: PostgresPool,
+}
+
+impl PostgresOplogSearchIndex {
+    /// Creates a new index on top of a pool whose schema has already been migrated
+    /// (see `crate::storage::migrate_postgres`)
+    pub fn new(pool: PostgresPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OplogSearchIndex for PostgresOplogSearchIndex {
+    async fn last_indexed(&self, worker_id: &WorkerId) -> Result<OplogIndex, String> {
+        let last_indexed: Option<(i64,)> = self
+            .pool
+            .with("oplog_search_index", "last_indexed")
+            .fetch_optional_as(
+                sqlx::query_as(
+                    "SELECT last_indexed FROM oplog_search_progress WHERE component_id = $1 AND worker_name = $2;",
+                )
+                .bind(worker_id.component_id.to_string())
+                .bind(&worker_id.worker_name),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+        Ok(to_oplog_index(last_indexed))
+    }
+
+    async fn update(
+        &self,
+        worker_id: &WorkerId,
+        update: &OplogSearchIndexUpdate,
+    ) -> Result<(), String> {
+        let component_id = worker_id.component_id.to_string();
+        let api = self.pool.with("oplog_search_index", "update");
+        let mut tx = api.begin().await.map_err(|err| err.to_safe_string())?;
+
+        // Locking the progress of the worker, so concurrent updates of the same worker are
+        // applied one after the other
+        tx.execute(
+            sqlx::query(
+                "INSERT INTO oplog_search_progress (component_id, worker_name, last_indexed) VALUES ($1, $2, 0) ON CONFLICT (component_id, worker_name) DO NOTHING;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+        let last_indexed: Option<(i64,)> = tx
+            .fetch_optional_as(
+                sqlx::query_as(
+                    "SELECT last_indexed FROM oplog_search_progress WHERE component_id = $1 AND worker_name = $2 FOR UPDATE;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+        let last_indexed = to_oplog_index(last_indexed);
+
+        for (oplog_index, entry) in update.entries.iter().filter(|(idx, _)| *idx > last_indexed) {
+            let oplog_index = u64::from(*oplog_index) as i64;
+            let json = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+
+            tx.execute(
+                sqlx::query(
+                    "DELETE FROM oplog_search_terms WHERE component_id = $1 AND worker_name = $2 AND oplog_index = $3;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(oplog_index),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+            tx.execute(
+                sqlx::query(
+                    "INSERT INTO oplog_search_entries (component_id, worker_name, oplog_index, timestamp, entry) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (component_id, worker_name, oplog_index) DO UPDATE SET timestamp = excluded.timestamp, entry = excluded.entry;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(oplog_index)
+                .bind(entry.timestamp().to_millis() as i64)
+                .bind(json),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+            for term in IndexedTerm::all(entry) {
+                tx.execute(
+                    sqlx::query(
+                        "INSERT INTO oplog_search_terms (component_id, worker_name, oplog_index, path, value, lower_value, number, instant) VALUES ($1, $2, $3, $4, $5, $6, $7, $8);",
+                    )
+                    .bind(&component_id)
+                    .bind(&worker_id.worker_name)
+                    .bind(oplog_index)
+                    .bind(term.path)
+                    .bind(term.value)
+                    .bind(term.lower_value)
+                    .bind(term.number)
+                    .bind(term.instant),
+                )
+                .await
+                .map_err(|err| err.to_safe_string())?;
+            }
+        }
+
+        for (_, region) in update
+            .dropped_regions
+            .iter()
+            .filter(|(idx, _)| *idx > last_indexed)
+        {
+            // The terms are removed by the cascading foreign key
+            tx.execute(
+                sqlx::query(
+                    "DELETE FROM oplog_search_entries WHERE component_id = $1 AND worker_name = $2 AND oplog_index BETWEEN $3 AND $4;",
+                )
+                .bind(&component_id)
+                .bind(&worker_id.worker_name)
+                .bind(u64::from(region.start) as i64)
+                .bind(u64::from(region.end) as i64),
+            )
+            .await
+            .map_err(|err| err.to_safe_string())?;
+        }
+
+        tx.execute(
+            sqlx::query(
+                "UPDATE oplog_search_progress SET last_indexed = GREATEST(last_indexed, $3) WHERE component_id = $1 AND worker_name = $2;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name)
+            .bind(u64::from(update.last_index) as i64),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+
+        tx.commit().await.map_err(|err| err.to_safe_string())
+    }
+
+    async fn delete(&self, worker_id: &WorkerId) -> Result<(), String> {
+        let component_id = worker_id.component_id.to_string();
+        let api = self.pool.with("oplog_search_index", "delete");
+        let mut tx = api.begin().await.map_err(|err| err.to_safe_string())?;
+
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_terms WHERE component_id = $1 AND worker_name = $2;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_entries WHERE component_id = $1 AND worker_name = $2;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+        tx.execute(
+            sqlx::query(
+                "DELETE FROM oplog_search_progress WHERE component_id = $1 AND worker_name = $2;",
+            )
+            .bind(&component_id)
+            .bind(&worker_id.worker_name),
+        )
+        .await
+        .map_err(|err| err.to_safe_string())?;
+
+        tx.commit().await.map_err(|err| err.to_safe_string())
+    }
+
+    async fn candidates(
+        &self,
+        component_id: &ComponentId,
+        query: &Query,
+        offset: u64,
+        count: u64,
+    ) -> Result<Vec<OplogSearchHit>, String> {
+        let statement = CandidatesStatement::new(component_id, query, offset, count, true);
+
+        let mut sqlx_query = sqlx::query_as::<_, (String, i64, String)>(&statement.sql);
+        for bind in &statement.binds {
+            sqlx_query = match bind {
+                SearchBind::Text(value) => sqlx_query.bind(value),
+                SearchBind::Number(value) => sqlx_query.bind(value),
+                SearchBind::Integer(value) => sqlx_query.bind(value),
+            };
+        }
+
+        let rows: Vec<(String, i64, String)> = self
+            .pool
+            .with("oplog_search_index", "candidates")
+            .fetch_all_as(sqlx_query)
+            .await
+            .map_err(|err| err.to_safe_string())?;
+
+        rows.into_iter()
+            .map(|row| to_search_hit(component_id, row))
+            .collect()
+    }
+}
+
+fn to_oplog_index(last_indexed: Option<(i64,)>) -> OplogIndex {
+    last_indexed
+        .map(|(idx,)| OplogIndex::from_u64(idx.max(0) as u64))
+        .unwrap_or(OplogIndex::NONE)
+}
+
+fn to_search_hit(
+    component_id: &ComponentId,
+    (worker_name, oplog_index, entry): (String, i64, String),
+) -> Result<OplogSearchHit, String> {
+    Ok(OplogSearchHit {
+        worker_id: WorkerId {
+            component_id: *component_id,
+            worker_name,
+        },
+        oplog_index: OplogIndex::from_u64(oplog_index as u64),
+        entry: serde_json::from_str(&entry)
+            .map_err(|err| format!("Failed to deserialize indexed oplog entry: {err}"))?,
+    })
+}
+
+/// A searchable field of an oplog entry, as stored in the index
+struct IndexedTerm {
+    path: String,
+    value: String,
+    lower_value: String,
+    number: Option<f64>,
+    instant: Option<i64>,
+}
+
+impl IndexedTerm {
+    fn all(entry: &PublicOplogEntry) -> Vec<Self> {
+        let mut seen = HashSet::new();
+        entry
+            .searchable_fields()
+            .into_iter()
+            .map(|(path, value)| (path_key(&path), value))
+            .filter(|field| seen.insert(field.clone()))
+            .map(|(path, value)| Self {
+                path,
+                lower_value: value.to_lowercase(),
+                number: parse_number(&value),
+                instant: parse_instant(&value),
+                value,
+            })
+            .collect()
+    }
+}
+
+fn path_key(path: &[String]) -> String {
+    path.iter()
+        .map(|segment| segment.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+enum SearchBind {
+    Text(String),
+    Number(f64),
+    Integer(i64),
+}
+
+/// Selects the candidate entries for a query.
+///
+/// The query is translated to a condition which holds for every matching entry, but can hold
+/// for others too: negations, regular expressions and the case sensitivity of phrases are only
+/// checked when matching the candidates.
+struct CandidatesStatement {
+    sql: String,
+    binds: Vec<SearchBind>,
+    postgres: bool,
+}
+
+impl CandidatesStatement {
+    fn new(
+        component_id: &ComponentId,
+        query: &Query,
+        offset: u64,
+        count: u64,
+        postgres: bool,
+    ) -> Self {
+        let mut statement = Self {
+            sql: String::new(),
+            binds: vec![SearchBind::Text(component_id.to_string())],
+            postgres,
+        };
+        let condition = statement.condition(query, &[]);
+        statement.binds.push(SearchBind::Integer(count as i64));
+        statement.binds.push(SearchBind::Integer(offset as i64));
+
+        let sql = format!(
+            "SELECT e.worker_name, e.oplog_index, e.entry FROM oplog_search_entries e WHERE e.component_id = ? AND {condition} ORDER BY e.timestamp DESC, e.worker_name, e.oplog_index DESC LIMIT ? OFFSET ?;"
+        );
+        statement.sql = if postgres {
+            let mut idx = 0;
+            sql.split('?')
+                .enumerate()
+                .map(|(n, part)| {
+                    if n == 0 {
+                        part.to_string()
+                    } else {
+                        idx += 1;
+                        format!("${idx}{part}")
+                    }
+                })
+                .collect()
+        } else {
+            sql
+        };
+        statement
+    }
+
+    fn condition(&mut self, query: &Query, path: &[String]) -> String {
+        match query {
+            Query::Or { queries } => self.combine(queries, path, " OR ", "FALSE"),
+            Query::And { queries } => self.combine(queries, path, " AND ", "TRUE"),
+            Query::Not { .. } => "TRUE".to_string(),
+            Query::Field { field, query } => {
+                let mut new_path = path.to_vec();
+                new_path.extend(field.split(".").map(|s| s.to_string()));
+                self.condition(query, &new_path)
+            }
+            Query::Regex { .. } => {
+                if path.is_empty() {
+                    "TRUE".to_string()
+                } else {
+                    self.term_exists(path, |_| "TRUE".to_string())
+                }
+            }
+            Query::Term { value } => {
+                if PublicOplogEntry::is_timestamp_path(path) {
+                    "TRUE".to_string()
+                } else {
+                    let pattern = format!("%{}%", escape_like(&value.to_lowercase()));
+                    self.term_exists(path, |statement| {
+                        statement.binds.push(SearchBind::Text(pattern));
+                        "t.lower_value LIKE ? ESCAPE '\\'".to_string()
+                    })
+                }
+            }
+            Query::Phrase { value } => {
+                if PublicOplogEntry::is_timestamp_path(path) {
+                    "TRUE".to_string()
+                } else {
+                    let pattern = format!("%{}%", escape_like(value));
+                    self.term_exists(path, |statement| {
+                        statement.binds.push(SearchBind::Text(pattern));
+                        "t.value LIKE ? ESCAPE '\\'".to_string()
+                    })
+                }
+            }
+            Query::Range { lower, upper } => {
+                let lower = lower.as_ref().map(|value| RangeValue::parse(value));
+                let upper = upper.as_ref().map(|value| RangeValue::parse(value));
+                if PublicOplogEntry::is_timestamp_path(path) {
+                    let timestamp_condition = format!(
+                        "({} AND {})",
+                        self.timestamp_bound(&lower, true),
+                        self.timestamp_bound(&upper, false)
+                    );
+                    let term_condition = self.term_exists(path, |statement| {
+                        format!(
+                            "{} AND {}",
+                            statement.term_bound(&lower, true),
+                            statement.term_bound(&upper, false)
+                        )
+                    });
+                    format!("({timestamp_condition} OR {term_condition})")
+                } else {
+                    self.term_exists(path, |statement| {
+                        format!(
+                            "{} AND {}",
+                            statement.term_bound(&lower, true),
+                            statement.term_bound(&upper, false)
+                        )
+                    })
+                }
+            }
+        }
+    }
+
+    fn combine(
+        &mut self,
+        queries: &[Query],
+        path: &[String],
+        separator: &str,
+        empty: &str,
+    ) -> String {
+        if queries.is_empty() {
+            empty.to_string()
+        } else {
+            let conditions = queries
+                .iter()
+                .map(|query| self.condition(query, path))
+                .collect::<Vec<_>>();
+            format!("({})", conditions.join(separator))
+        }
+    }
+
+    fn term_exists(
+        &mut self,
+        path: &[String],
+        value_condition: impl FnOnce(&mut Self) -> String,
+    ) -> String {
+        let mut conditions = vec![
+            "t.component_id = e.component_id AND t.worker_name = e.worker_name AND t.oplog_index = e.oplog_index".to_string(),
+        ];
+        if !path.is_empty() {
+            self.binds.push(SearchBind::Text(path_key(path)));
+            conditions.push("t.path = ?".to_string());
+        }
+        conditions.push(value_condition(self));
+        format!(
+            "EXISTS (SELECT 1 FROM oplog_search_terms t WHERE {})",
+            conditions.join(" AND ")
+        )
+    }
+
+    fn term_bound(&mut self, bound: &Bound<RangeValue>, lower: bool) -> String {
+        let Some((value, operator)) = bound_operator(bound, lower) else {
+            return "TRUE".to_string();
+        };
+        match value {
+            RangeValue::Number(number) => {
+                self.binds.push(SearchBind::Number(*number));
+                format!("t.number {operator} ?")
+            }
+            RangeValue::Instant(instant) => {
+                self.binds.push(SearchBind::Integer(*instant));
+                format!("t.instant {operator} ?")
+            }
+            RangeValue::String(string) => {
+                self.binds.push(SearchBind::Text(string.clone()));
+                if self.postgres {
+                    // Comparing by bytes, like the matching of the candidates does
+                    format!("t.value COLLATE \"C\" {operator} ?")
+                } else {
+                    format!("t.value {operator} ?")
+                }
+            }
+        }
+    }
+
+    /// The entry timestamps are only stored in milliseconds, so bounds which are not dates can
+    /// only be checked when matching the candidates
+    fn timestamp_bound(&mut self, bound: &Bound<RangeValue>, lower: bool) -> String {
+        match bound_operator(bound, lower) {
+            Some((RangeValue::Instant(instant), operator)) => {
+                self.binds.push(SearchBind::Integer(*instant));
+                format!("e.timestamp {operator} ?")
+            }
+            Some((RangeValue::Number(_), _)) => "FALSE".to_string(),
+            _ => "TRUE".to_string(),
+        }
+    }
+}
+
+fn bound_operator(bound: &Bound<RangeValue>, lower: bool) -> Option<(&RangeValue, &'static str)> {
+    match (bound, lower) {
+        (Bound::Included(value), true) => Some((value, ">=")),
+        (Bound::Excluded(value), true) => Some((value, ">")),
+        (Bound::Included(value), false) => Some((value, "<=")),
+        (Bound::Excluded(value), false) => Some((value, "<")),
+        (Bound::Unbounded, _) => None,
+    }
+}
+
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -14,10 +14,12 @@
 
 use super::*;
 use crate::services::oplog::compressed::CompressedOplogArchiveService;
+use crate::services::oplog::compression::OplogCompression;
+use crate::services::oplog::encryption::{LocalFileKeyProvider, OplogEncryption};
 use crate::services::oplog::multilayer::OplogArchiveService;
 use crate::storage::indexed::memory::InMemoryIndexedStorage;
 use crate::storage::indexed::redis::RedisIndexedStorage;
-use crate::storage::indexed::IndexedStorage;
+use crate::storage::indexed::{IndexedStorage, IndexedStorageNamespace};
 use assert2::check;
 use golem_common::config::RedisConfig;
 use golem_common::model::account::AccountId;
@@ -73,7 +75,15 @@ fn default_execution_status(
 async fn open_add_and_read_back(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
-    let oplog_service = PrimaryOplogService::new(indexed_storage, blob_storage, 1, 1, 100).await;
+    let oplog_service = PrimaryOplogService::new(
+        indexed_storage,
+        blob_storage,
+        1,
+        1,
+        100,
+        OplogEncryption::disabled(),
+    )
+    .await;
     let account_id = AccountId::new();
     let environment_id = EnvironmentId::new();
     let worker_id = WorkerId {
@@ -127,7 +137,15 @@ async fn open_add_and_read_back(_tracing: &Tracing) {
 async fn open_add_and_read_back_many(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
-    let oplog_service = PrimaryOplogService::new(indexed_storage, blob_storage, 1, 1, 100).await;
+    let oplog_service = PrimaryOplogService::new(
+        indexed_storage,
+        blob_storage,
+        1,
+        1,
+        100,
+        OplogEncryption::disabled(),
+    )
+    .await;
     let account_id = AccountId::new();
     let environment_id = EnvironmentId::new();
     let worker_id = WorkerId {
@@ -175,13 +193,29 @@ async fn open_add_and_read_back_ephemeral(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
-    );
-    let secondary_layer: Arc<dyn OplogArchiveService> = Arc::new(
-        CompressedOplogArchiveService::new(indexed_storage.clone(), 1),
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
-    let tertiary_layer: Arc<dyn OplogArchiveService> =
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2));
+    let secondary_layer: Arc<dyn OplogArchiveService> =
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ));
+    let tertiary_layer: Arc<dyn OplogArchiveService> = Arc::new(BlobOplogArchiveService::new(
+        blob_storage.clone(),
+        2,
+        OplogCompression::default(),
+        OplogEncryption::disabled(),
+    ));
     let oplog_service = Arc::new(MultiLayerOplogService::new(
         primary_oplog_service.clone(),
         nev![secondary_layer.clone(), tertiary_layer.clone()],
@@ -243,13 +277,29 @@ async fn open_add_and_read_back_many_ephemeral(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
-    );
-    let secondary_layer: Arc<dyn OplogArchiveService> = Arc::new(
-        CompressedOplogArchiveService::new(indexed_storage.clone(), 1),
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
-    let tertiary_layer: Arc<dyn OplogArchiveService> =
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2));
+    let secondary_layer: Arc<dyn OplogArchiveService> =
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ));
+    let tertiary_layer: Arc<dyn OplogArchiveService> = Arc::new(BlobOplogArchiveService::new(
+        blob_storage.clone(),
+        2,
+        OplogCompression::default(),
+        OplogEncryption::disabled(),
+    ));
     let oplog_service = Arc::new(MultiLayerOplogService::new(
         primary_oplog_service.clone(),
         nev![secondary_layer.clone(), tertiary_layer.clone()],
@@ -303,7 +353,15 @@ async fn open_add_and_read_back_many_ephemeral(_tracing: &Tracing) {
 async fn entries_with_small_payload(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
-    let oplog_service = PrimaryOplogService::new(indexed_storage, blob_storage, 1, 1, 100).await;
+    let oplog_service = PrimaryOplogService::new(
+        indexed_storage,
+        blob_storage,
+        1,
+        1,
+        100,
+        OplogEncryption::disabled(),
+    )
+    .await;
     let account_id = AccountId::new();
     let environment_id = EnvironmentId::new();
     let worker_id = WorkerId {
@@ -453,7 +511,15 @@ async fn entries_with_small_payload(_tracing: &Tracing) {
 async fn entries_with_large_payload(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
-    let oplog_service = PrimaryOplogService::new(indexed_storage, blob_storage, 1, 1, 100).await;
+    let oplog_service = PrimaryOplogService::new(
+        indexed_storage,
+        blob_storage,
+        1,
+        1,
+        100,
+        OplogEncryption::disabled(),
+    )
+    .await;
     let account_id = AccountId::new();
     let environment_id = EnvironmentId::new();
     let worker_id = WorkerId {
@@ -652,22 +718,44 @@ async fn multilayer_transfers_entries_after_limit_reached(
 
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -783,22 +871,44 @@ async fn read_from_archive_impl(use_blob: bool) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -903,22 +1013,44 @@ async fn read_initial_from_archive_impl(use_blob: bool) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -1046,22 +1178,44 @@ async fn write_after_archive_impl(use_blob: bool, reopen: Reopen) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let mut primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let mut oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -1145,8 +1299,15 @@ async fn write_after_archive_impl(use_blob: bool, reopen: Reopen) {
     } else if reopen == Reopen::Full {
         drop(oplog);
         primary_oplog_service = Arc::new(
-            PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100)
-                .await,
+            PrimaryOplogService::new(
+                indexed_storage.clone(),
+                blob_storage.clone(),
+                1,
+                1,
+                100,
+                OplogEncryption::disabled(),
+            )
+            .await,
         );
         oplog_service = Arc::new(MultiLayerOplogService::new(
             primary_oplog_service.clone(),
@@ -1222,8 +1383,15 @@ async fn write_after_archive_impl(use_blob: bool, reopen: Reopen) {
     } else if reopen == Reopen::Full {
         drop(oplog);
         primary_oplog_service = Arc::new(
-            PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100)
-                .await,
+            PrimaryOplogService::new(
+                indexed_storage.clone(),
+                blob_storage.clone(),
+                1,
+                1,
+                100,
+                OplogEncryption::disabled(),
+            )
+            .await,
         );
         oplog_service = Arc::new(MultiLayerOplogService::new(
             primary_oplog_service.clone(),
@@ -1328,22 +1496,44 @@ async fn empty_layer_gets_deleted_impl(use_blob: bool) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -1442,22 +1632,44 @@ async fn scheduled_archive_impl(use_blob: bool) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
     let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 1))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2))
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
     } else {
         Arc::new(CompressedOplogArchiveService::new(
             indexed_storage.clone(),
             2,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
         ))
     };
     let oplog_service = Arc::new(MultiLayerOplogService::new(
@@ -1591,13 +1803,29 @@ async fn multilayer_scan_for_component(_tracing: &Tracing) {
     let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
     let blob_storage = Arc::new(InMemoryBlobStorage::new());
     let primary_oplog_service = Arc::new(
-        PrimaryOplogService::new(indexed_storage.clone(), blob_storage.clone(), 1, 1, 100).await,
-    );
-    let secondary_layer: Arc<dyn OplogArchiveService> = Arc::new(
-        CompressedOplogArchiveService::new(indexed_storage.clone(), 1),
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
     );
-    let tertiary_layer: Arc<dyn OplogArchiveService> =
-        Arc::new(BlobOplogArchiveService::new(blob_storage.clone(), 2));
+    let secondary_layer: Arc<dyn OplogArchiveService> =
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ));
+    let tertiary_layer: Arc<dyn OplogArchiveService> = Arc::new(BlobOplogArchiveService::new(
+        blob_storage.clone(),
+        2,
+        OplogCompression::default(),
+        OplogEncryption::disabled(),
+    ));
 
     let oplog_service = Arc::new(MultiLayerOplogService::new(
         primary_oplog_service.clone(),
@@ -1723,3 +1951,376 @@ async fn multilayer_scan_for_component(_tracing: &Tracing) {
 
     assert_eq!(result.len(), 100);
 }
+
+#[test]
+async fn archives_get_reencrypted_after_key_rotation(_tracing: &Tracing) {
+    archives_get_reencrypted_after_key_rotation_impl(false).await;
+}
+
+#[test]
+async fn blob_archives_get_reencrypted_after_key_rotation(_tracing: &Tracing) {
+    archives_get_reencrypted_after_key_rotation_impl(true).await;
+}
+
+async fn archives_get_reencrypted_after_key_rotation_impl(use_blob: bool) {
+    let key_root = tempfile::TempDir::new().unwrap();
+    let write_key = async |environment_id: &EnvironmentId, key_id: &str, byte: u8| {
+        let dir = key_root.path().join(environment_id.to_string());
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        tokio::fs::write(dir.join(format!("{key_id}.key")), hex::encode([byte; 32]))
+            .await
+            .unwrap();
+    };
+    let encryption = OplogEncryption::new(Arc::new(LocalFileKeyProvider::new(
+        key_root.path().to_path_buf(),
+        Duration::ZERO,
+    )));
+
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let blob_storage = Arc::new(InMemoryBlobStorage::new());
+    let primary_oplog_service = Arc::new(
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            encryption.clone(),
+        )
+        .await,
+    );
+    let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            encryption.clone(),
+        ))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            OplogCompression::default(),
+            encryption.clone(),
+        ))
+    };
+    let tertiary_layer: Arc<dyn OplogArchiveService> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            2,
+            OplogCompression::default(),
+            encryption.clone(),
+        ))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            2,
+            OplogCompression::default(),
+            encryption.clone(),
+        ))
+    };
+    let oplog_service = Arc::new(MultiLayerOplogService::new(
+        primary_oplog_service.clone(),
+        nev![secondary_layer.clone(), tertiary_layer.clone()],
+        10,
+        10,
+    ));
+    let account_id = AccountId::new();
+    let environment_id = EnvironmentId::new();
+    let worker_id = WorkerId {
+        component_id: ComponentId(Uuid::new_v4()),
+        worker_name: "test".to_string(),
+    };
+    let owned_worker_id = OwnedWorkerId::new(environment_id, &worker_id);
+
+    write_key(&environment_id, "001", 1).await;
+
+    let last_oplog_index = oplog_service.get_last_index(&owned_worker_id).await;
+    let oplog = oplog_service
+        .open(
+            &owned_worker_id,
+            last_oplog_index,
+            WorkerMetadata::default(worker_id.clone(), account_id, environment_id),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await;
+
+    let mut entries = Vec::new();
+    for _ in 0..10 {
+        let timestamp = Timestamp::now_utc();
+        for i in 0..100 {
+            let entry = OplogEntry::Error {
+                timestamp,
+                error: WorkerError::Unknown(i.to_string()),
+                retry_from: OplogIndex::NONE,
+            }
+            .rounded();
+            oplog.add(entry.clone()).await;
+            entries.push(entry);
+        }
+        oplog.commit(CommitLevel::Always).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // The archives cannot be re-encrypted while the oplog is open
+    write_key(&environment_id, "002", 2).await;
+    check!(oplog_service
+        .reencrypt_archives(&owned_worker_id)
+        .await
+        .unwrap()
+        .is_none());
+    drop(oplog);
+
+    let tertiary = tertiary_layer.open(&owned_worker_id).await;
+    check!(tertiary.needs_reencryption().await.unwrap());
+
+    let start = Instant::now();
+    let reencrypted = loop {
+        if let Some(reencrypted) = oplog_service
+            .reencrypt_archives(&owned_worker_id)
+            .await
+            .unwrap()
+        {
+            break reencrypted;
+        }
+        if start.elapsed().as_secs() > 10 {
+            panic!("Timeout");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+
+    let secondary = secondary_layer.open(&owned_worker_id).await;
+    check!(reencrypted > 0);
+    check!(!secondary.needs_reencryption().await.unwrap());
+    check!(!tertiary.needs_reencryption().await.unwrap());
+
+    let all_entries = oplog_service
+        .read(&owned_worker_id, OplogIndex::NONE, 1100)
+        .await;
+    check!(all_entries.into_values().collect::<Vec<_>>() == entries);
+}
+
+#[test]
+async fn drop_archived_entries(_tracing: &Tracing) {
+    drop_archived_entries_impl(false).await;
+}
+
+#[test]
+async fn blob_drop_archived_entries(_tracing: &Tracing) {
+    drop_archived_entries_impl(true).await;
+}
+
+async fn drop_archived_entries_impl(use_blob: bool) {
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let blob_storage = Arc::new(InMemoryBlobStorage::new());
+    let primary_oplog_service = Arc::new(
+        PrimaryOplogService::new(
+            indexed_storage.clone(),
+            blob_storage.clone(),
+            1,
+            1,
+            100,
+            OplogEncryption::disabled(),
+        )
+        .await,
+    );
+    let secondary_layer: Arc<dyn OplogArchiveService> = if use_blob {
+        Arc::new(BlobOplogArchiveService::new(
+            blob_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
+    } else {
+        Arc::new(CompressedOplogArchiveService::new(
+            indexed_storage.clone(),
+            1,
+            OplogCompression::default(),
+            OplogEncryption::disabled(),
+        ))
+    };
+    let oplog_service = Arc::new(MultiLayerOplogService::new(
+        primary_oplog_service.clone(),
+        nev![secondary_layer.clone()],
+        10,
+        10,
+    ));
+    let account_id = AccountId::new();
+    let environment_id = EnvironmentId::new();
+    let worker_id = WorkerId {
+        component_id: ComponentId(Uuid::new_v4()),
+        worker_name: "test".to_string(),
+    };
+    let owned_worker_id = OwnedWorkerId::new(environment_id, &worker_id);
+
+    let create_entry = OplogEntry::Create {
+        timestamp: Timestamp::now_utc(),
+        worker_id: worker_id.clone(),
+        component_revision: ComponentRevision::new(1).unwrap(),
+        env: vec![],
+        wasi_config_vars: BTreeMap::new(),
+        environment_id,
+        created_by: account_id,
+        parent: None,
+        component_size: 0,
+        initial_total_linear_memory_size: 0,
+        initial_active_plugins: HashSet::new(),
+        original_phantom_id: None,
+    }
+    .rounded();
+
+    let oplog = oplog_service
+        .create(
+            &owned_worker_id,
+            create_entry.clone(),
+            WorkerMetadata::default(worker_id.clone(), account_id, environment_id),
+            default_last_known_status(),
+            default_execution_status(AgentMode::Durable),
+        )
+        .await;
+
+    // Archiving the create entry, the entries 2..=5 and the entry 6 into three separate chunks
+    MultiLayerOplog::try_archive_blocking(&oplog).await;
+    let timestamp = Timestamp::now_utc();
+    for i in 0..4 {
+        oplog
+            .add(
+                OplogEntry::Error {
+                    timestamp,
+                    error: WorkerError::Unknown(i.to_string()),
+                    retry_from: OplogIndex::NONE,
+                }
+                .rounded(),
+            )
+            .await;
+    }
+    oplog.commit(CommitLevel::Always).await;
+    MultiLayerOplog::try_archive_blocking(&oplog).await;
+    let last_entry = OplogEntry::suspend().rounded();
+    oplog.add(last_entry.clone()).await;
+    oplog.commit(CommitLevel::Always).await;
+    MultiLayerOplog::try_archive_blocking(&oplog).await;
+
+    // The archives cannot be modified while the oplog is open
+    check!(oplog_service
+        .drop_archived_entries(&owned_worker_id, OplogIndex::from_u64(5))
+        .await
+        .is_none());
+    drop(oplog);
+
+    let start = Instant::now();
+    let dropped = loop {
+        if let Some(dropped) = oplog_service
+            .drop_archived_entries(&owned_worker_id, OplogIndex::from_u64(5))
+            .await
+        {
+            break dropped;
+        }
+        if start.elapsed().as_secs() > 10 {
+            panic!("Timeout");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+
+    let initial = oplog_service
+        .read(&owned_worker_id, OplogIndex::INITIAL, 1)
+        .await;
+    let dropped_entries = oplog_service
+        .read(&owned_worker_id, OplogIndex::from_u64(2), 4)
+        .await;
+    let remaining = oplog_service
+        .read(&owned_worker_id, OplogIndex::from_u64(6), 1)
+        .await;
+
+    check!(dropped == 1);
+    check!(initial.into_values().collect::<Vec<_>>() == vec![create_entry]);
+    check!(dropped_entries.is_empty());
+    check!(remaining.into_values().collect::<Vec<_>>() == vec![last_entry]);
+    check!(oplog_service.get_last_index(&owned_worker_id).await == OplogIndex::from_u64(6));
+}
+
+#[test]
+async fn interrupted_reencryption_is_completed_on_open(_tracing: &Tracing) {
+    let indexed_storage = Arc::new(InMemoryIndexedStorage::new());
+    let archive_service = CompressedOplogArchiveService::new(
+        indexed_storage.clone(),
+        1,
+        OplogCompression::default(),
+        OplogEncryption::disabled(),
+    );
+    let environment_id = EnvironmentId::new();
+    let worker_id = WorkerId {
+        component_id: ComponentId(Uuid::new_v4()),
+        worker_name: "test".to_string(),
+    };
+    let owned_worker_id = OwnedWorkerId::new(environment_id, &worker_id);
+
+    let timestamp = Timestamp::now_utc();
+    let entries: Vec<(OplogIndex, OplogEntry)> = (1..=10)
+        .map(|i| {
+            (
+                OplogIndex::from_u64(i),
+                OplogEntry::Error {
+                    timestamp,
+                    error: WorkerError::Unknown(i.to_string()),
+                    retry_from: OplogIndex::NONE,
+                }
+                .rounded(),
+            )
+        })
+        .collect();
+    let archive = archive_service.open(&owned_worker_id).await;
+    archive.append(entries[..5].to_vec()).await;
+    archive.append(entries[5..].to_vec()).await;
+    drop(archive);
+
+    // Simulating a re-encryption interrupted after staging the chunks and deleting the archive
+    let key = worker_id.to_redis_key();
+    let namespace = IndexedStorageNamespace::CompressedOpLog {
+        worker_id: worker_id.clone(),
+        level: 1,
+    };
+    let staging_namespace = IndexedStorageNamespace::ReencryptedOpLog {
+        worker_id: worker_id.clone(),
+        level: 1,
+    };
+    let chunks = indexed_storage
+        .read(
+            "test",
+            "read",
+            "chunk",
+            namespace.clone(),
+            &key,
+            0,
+            u64::MAX,
+        )
+        .await
+        .unwrap();
+    check!(chunks.len() == 2);
+    indexed_storage
+        .append_many(
+            "test",
+            "stage",
+            "chunk",
+            staging_namespace.clone(),
+            &key,
+            chunks,
+        )
+        .await
+        .unwrap();
+    indexed_storage
+        .delete("test", "delete", namespace, &key)
+        .await
+        .unwrap();
+
+    let archive = archive_service.open(&owned_worker_id).await;
+    let result = archive.read(OplogIndex::from_u64(1), 10).await;
+    check!(result.into_iter().collect::<Vec<_>>() == entries);
+    check!(!indexed_storage
+        .exists("test", "exists", staging_namespace, &key)
+        .await
+        .unwrap());
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::All;
+use super::{All, HasSchedulerService};
 use crate::metrics::promises::record_promise_created;
 use crate::storage::keyvalue::{
     KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
@@ -20,12 +20,19 @@ use crate::storage::keyvalue::{
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use desert_rust::BinaryCodec;
 use golem_common::model::account::AccountId;
+use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::{OwnedWorkerId, PromiseId, WorkerId, WorkerStatus};
+use golem_common::model::{
+    OwnedWorkerId, PromiseId, PromiseOutcome, ScheduleId, ScheduledAction, WorkerId, WorkerStatus,
+};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
+use golem_wasm::analysis::AnalysedType;
+use golem_wasm::json::ValueAndTypeJsonExtensions;
+use golem_wasm::ValueAndType;
 use std::collections::HashMap;
 #[cfg(test)]
 use std::collections::HashSet;
@@ -43,7 +50,7 @@ pub struct PromiseHandle {
 #[derive(Debug)]
 pub struct PromiseHandleInner {
     notify: Notify,
-    state: Mutex<Option<Vec<u8>>>,
+    state: Mutex<Option<PromiseOutcome>>,
 }
 
 impl PromiseHandle {
@@ -71,14 +78,14 @@ impl PromiseHandle {
         self.inner.notify.notified().await;
     }
 
-    pub async fn get(&self) -> Option<Vec<u8>> {
+    pub async fn get(&self) -> Option<PromiseOutcome> {
         let state = self.inner.state.lock().await;
         state.clone()
     }
 
-    pub async fn complete(&self, data: Vec<u8>) {
+    pub async fn complete(&self, outcome: PromiseOutcome) {
         let mut state = self.inner.state.lock().await;
-        *state = Some(data);
+        *state = Some(outcome);
         self.inner.notify.notify_waiters();
     }
 }
@@ -87,16 +94,33 @@ impl PromiseHandle {
 #[async_trait]
 pub trait PromiseService: Send + Sync {
     /// poll and complete for a given promise must be called on the same
-    async fn create(&self, worker_id: &WorkerId, oplog_idx: OplogIndex) -> PromiseId;
+    ///
+    /// If a payload type is given, the promise can only be completed with a JSON payload matching it.
+    async fn create(
+        &self,
+        worker_id: &WorkerId,
+        oplog_idx: OplogIndex,
+        payload_type: Option<AnalysedType>,
+    ) -> PromiseId;
+
+    /// Schedules the expiration of a pending promise, unless it is already completed or its
+    /// expiration is already scheduled. The scheduled expiration is cancelled when the promise gets completed.
+    async fn schedule_expiry(
+        &self,
+        promise_id: &PromiseId,
+        expires_at: DateTime<Utc>,
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+    ) -> Result<(), WorkerExecutorError>;
 
     async fn poll(&self, promise_id: PromiseId) -> Result<PromiseHandle, WorkerExecutorError>;
 
-    /// Completes a promise with the given payload.
+    /// Completes, rejects or expires a promise with the given outcome.
     /// If the promise was not completed before, it returns true. If the promise was completed before, it returns false.
     async fn complete(
         &self,
         promise_id: PromiseId,
-        data: Vec<u8>,
+        outcome: PromiseOutcome,
         completed_by: AccountId,
     ) -> Result<bool, WorkerExecutorError>;
 
@@ -124,9 +148,31 @@ impl LazyPromiseService {
 
 #[async_trait]
 impl PromiseService for LazyPromiseService {
-    async fn create(&self, worker_id: &WorkerId, oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        worker_id: &WorkerId,
+        oplog_idx: OplogIndex,
+        payload_type: Option<AnalysedType>,
+    ) -> PromiseId {
         let lock = self.0.read().await;
-        lock.as_ref().unwrap().create(worker_id, oplog_idx).await
+        lock.as_ref()
+            .unwrap()
+            .create(worker_id, oplog_idx, payload_type)
+            .await
+    }
+
+    async fn schedule_expiry(
+        &self,
+        promise_id: &PromiseId,
+        expires_at: DateTime<Utc>,
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+    ) -> Result<(), WorkerExecutorError> {
+        let lock = self.0.read().await;
+        lock.as_ref()
+            .unwrap()
+            .schedule_expiry(promise_id, expires_at, account_id, environment_id)
+            .await
     }
 
     async fn poll(&self, promise_id: PromiseId) -> Result<PromiseHandle, WorkerExecutorError> {
@@ -137,13 +183,13 @@ impl PromiseService for LazyPromiseService {
     async fn complete(
         &self,
         promise_id: PromiseId,
-        data: Vec<u8>,
+        outcome: PromiseOutcome,
         completed_by: AccountId,
     ) -> Result<bool, WorkerExecutorError> {
         let lock = self.0.read().await;
         lock.as_ref()
             .unwrap()
-            .complete(promise_id, data, completed_by)
+            .complete(promise_id, outcome, completed_by)
             .await
     }
 
@@ -182,12 +228,12 @@ impl PromiseRegistry {
         })
     }
 
-    async fn complete(&mut self, id: &PromiseId, data: Vec<u8>) {
+    async fn complete(&mut self, id: &PromiseId, outcome: PromiseOutcome) {
         if let Some(weak) = self.handles.get(id) {
             if let Some(inner) = weak.upgrade() {
                 tokio::spawn(async move {
                     let mut state = inner.state.lock().await;
-                    *state = Some(data.clone());
+                    *state = Some(outcome.clone());
                     inner.notify.notify_waiters();
                 });
             }
@@ -231,11 +277,56 @@ impl<Ctx: WorkerCtx> DefaultPromiseService<Ctx> {
                 panic!("failed to check if promise {promise_id} exists in Redis: {err}")
             })
     }
+
+    async fn get_state(&self, promise_id: &PromiseId) -> Option<RedisPromiseState> {
+        self.key_value_storage
+            .with_entity("promise", "complete", "promise")
+            .get(
+                KeyValueStorageNamespace::Promise {
+                    worker_id: promise_id.worker_id.clone(),
+                },
+                &get_promise_redis_key(promise_id),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("failed to get promise {promise_id} from Redis: {err}"))
+    }
+
+    async fn cancel_expiry(&self, promise_id: &PromiseId) {
+        let namespace = KeyValueStorageNamespace::Promise {
+            worker_id: promise_id.worker_id.clone(),
+        };
+        let key = get_promise_expiry_redis_key(promise_id);
+
+        let schedule_id: Option<ScheduleId> = self
+            .key_value_storage
+            .with_entity("promise", "cancel_expiry", "promise_expiry")
+            .get(namespace.clone(), &key)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the expiry of promise {promise_id} from Redis: {err}")
+            });
+
+        if let Some(schedule_id) = schedule_id {
+            self.services.scheduler_service().cancel(schedule_id).await;
+            self.key_value_storage
+                .with("promise", "cancel_expiry")
+                .del(namespace, &key)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to delete the expiry of promise {promise_id} from Redis: {err}")
+                });
+        }
+    }
 }
 
 #[async_trait]
 impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
-    async fn create(&self, worker_id: &WorkerId, oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        worker_id: &WorkerId,
+        oplog_idx: OplogIndex,
+        payload_type: Option<AnalysedType>,
+    ) -> PromiseId {
         let promise_id = PromiseId {
             worker_id: worker_id.clone(),
             oplog_idx,
@@ -250,7 +341,10 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
                     worker_id: worker_id.clone(),
                 },
                 &key,
-                &RedisPromiseState::Pending,
+                &match payload_type {
+                    Some(payload_type) => RedisPromiseState::PendingTyped(payload_type),
+                    None => RedisPromiseState::Pending,
+                },
             )
             .await
             .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
@@ -266,6 +360,60 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
         promise_id
     }
 
+    async fn schedule_expiry(
+        &self,
+        promise_id: &PromiseId,
+        expires_at: DateTime<Utc>,
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+    ) -> Result<(), WorkerExecutorError> {
+        let namespace = KeyValueStorageNamespace::Promise {
+            worker_id: promise_id.worker_id.clone(),
+        };
+        let expiry_key = get_promise_expiry_redis_key(promise_id);
+
+        let already_handled = self
+            .key_value_storage
+            .with("promise", "schedule_expiry")
+            .exists(namespace.clone(), &get_promise_result_redis_key(promise_id))
+            .await
+            .map_err(WorkerExecutorError::runtime)?
+            || self
+                .key_value_storage
+                .with("promise", "schedule_expiry")
+                .exists(namespace.clone(), &expiry_key)
+                .await
+                .map_err(WorkerExecutorError::runtime)?;
+        if already_handled {
+            return Ok(());
+        }
+
+        let scheduler_service = self.services.scheduler_service();
+        let schedule_id = scheduler_service
+            .schedule(
+                expires_at,
+                ScheduledAction::ExpirePromise {
+                    account_id,
+                    environment_id,
+                    promise_id: promise_id.clone(),
+                },
+            )
+            .await;
+
+        let written = self
+            .key_value_storage
+            .with_entity("promise", "schedule_expiry", "promise_expiry")
+            .set_if_not_exists(namespace, &expiry_key, &schedule_id)
+            .await
+            .map_err(WorkerExecutorError::runtime)?;
+        if !written {
+            // Another attempt registered the expiration concurrently
+            scheduler_service.cancel(schedule_id).await;
+        }
+
+        Ok(())
+    }
+
     async fn poll(&self, promise_id: PromiseId) -> Result<PromiseHandle, WorkerExecutorError> {
         // Fast path: check local registry first
         if let Some(handle) = self.registry.lock().await.get(&promise_id) {
@@ -282,7 +430,7 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
         };
 
         // Check if already completed in Redis
-        if let Some(RedisPromiseState::Complete(data)) = self
+        if let Some(outcome) = self
             .key_value_storage
             .with_entity("promise", "poll", "promise")
             .get(
@@ -293,8 +441,9 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
             )
             .await
             .unwrap_or_else(|err| panic!("failed to get promise {promise_id} from Redis: {err}"))
+            .and_then(RedisPromiseState::into_outcome)
         {
-            handle.complete(data).await;
+            handle.complete(outcome).await;
         }
 
         Ok(handle)
@@ -303,13 +452,19 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
     async fn complete(
         &self,
         promise_id: PromiseId,
-        data: Vec<u8>,
+        outcome: PromiseOutcome,
         completed_by: AccountId,
     ) -> Result<bool, WorkerExecutorError> {
         let key = get_promise_result_redis_key(&promise_id);
 
-        if !self.exists(&promise_id).await {
-            return Err(WorkerExecutorError::PromiseNotFound { promise_id });
+        match self.get_state(&promise_id).await {
+            None => return Err(WorkerExecutorError::PromiseNotFound { promise_id }),
+            Some(RedisPromiseState::PendingTyped(payload_type)) => {
+                if let PromiseOutcome::Completed(data) = &outcome {
+                    validate_typed_payload(data, &payload_type)?;
+                }
+            }
+            Some(_) => {}
         };
 
         let written: bool = self
@@ -320,15 +475,17 @@ impl<Ctx: WorkerCtx> PromiseService for DefaultPromiseService<Ctx> {
                     worker_id: promise_id.worker_id.clone(),
                 },
                 &key,
-                &RedisPromiseState::Complete(data.clone()),
+                &RedisPromiseState::from_outcome(outcome.clone()),
             )
             .await
             .unwrap_or_else(|err| panic!("failed to set promise {promise_id} in Redis: {err}"));
 
-        // Also wake any in-memory handle, ensuring that still running workers that wait on the pollable can continue
-        {
+        if written {
+            self.cancel_expiry(&promise_id).await;
+
+            // Also wake any in-memory handle, ensuring that still running workers that wait on the pollable can continue
             let mut reg = self.registry.lock().await;
-            reg.complete(&promise_id, data.clone()).await;
+            reg.complete(&promise_id, outcome).await;
         }
 
         // Wake up the worker that owns the promise, ensuring that it resumes its work.
@@ -400,16 +557,62 @@ fn get_promise_result_redis_key(promise_id: &PromiseId) -> String {
     format!("{}:completed", promise_id.to_redis_key())
 }
 
+fn get_promise_expiry_redis_key(promise_id: &PromiseId) -> String {
+    format!("{}:expiry", promise_id.to_redis_key())
+}
+
+/// Payloads of typed promises are JSON documents which must match the declared type
+fn validate_typed_payload(
+    data: &[u8],
+    payload_type: &AnalysedType,
+) -> Result<(), WorkerExecutorError> {
+    let json: serde_json::Value =
+        serde_json::from_slice(data).map_err(|err| WorkerExecutorError::ValueMismatch {
+            details: format!("promise payload is not valid JSON: {err}"),
+        })?;
+    ValueAndType::parse_with_type(&json, payload_type).map_err(|errors| {
+        WorkerExecutorError::ValueMismatch {
+            details: format!(
+                "promise payload does not match the declared type: {}",
+                errors.join(", ")
+            ),
+        }
+    })?;
+    Ok(())
+}
+
 #[derive(Debug, Eq, PartialEq, BinaryCodec)]
 #[desert(evolution())]
 pub enum RedisPromiseState {
     Pending,
     Complete(Vec<u8>),
+    PendingTyped(AnalysedType),
+    Rejected(String),
+    Expired,
+}
+
+impl RedisPromiseState {
+    fn from_outcome(outcome: PromiseOutcome) -> Self {
+        match outcome {
+            PromiseOutcome::Completed(data) => Self::Complete(data),
+            PromiseOutcome::Rejected(error) => Self::Rejected(error),
+            PromiseOutcome::Expired => Self::Expired,
+        }
+    }
+
+    fn into_outcome(self) -> Option<PromiseOutcome> {
+        match self {
+            Self::Pending | Self::PendingTyped(_) => None,
+            Self::Complete(data) => Some(PromiseOutcome::Completed(data)),
+            Self::Rejected(error) => Some(PromiseOutcome::Rejected(error)),
+            Self::Expired => Some(PromiseOutcome::Expired),
+        }
+    }
 }
 
 #[cfg(test)]
 pub struct PromiseServiceMock {
-    completed: Arc<Mutex<HashSet<PromiseId>>>,
+    completed: Arc<Mutex<HashMap<PromiseId, PromiseOutcome>>>,
 }
 
 #[cfg(test)]
@@ -423,19 +626,38 @@ impl Default for PromiseServiceMock {
 impl PromiseServiceMock {
     pub fn new() -> Self {
         Self {
-            completed: Arc::new(Mutex::new(HashSet::new())),
+            completed: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
     pub async fn all_completed(&self) -> HashSet<PromiseId> {
-        self.completed.lock().await.clone()
+        self.completed.lock().await.keys().cloned().collect()
+    }
+
+    pub async fn outcome(&self, promise_id: &PromiseId) -> Option<PromiseOutcome> {
+        self.completed.lock().await.get(promise_id).cloned()
     }
 }
 
 #[cfg(test)]
 #[async_trait]
 impl PromiseService for PromiseServiceMock {
-    async fn create(&self, _worker_id: &WorkerId, _oplog_idx: OplogIndex) -> PromiseId {
+    async fn create(
+        &self,
+        _worker_id: &WorkerId,
+        _oplog_idx: OplogIndex,
+        _payload_type: Option<AnalysedType>,
+    ) -> PromiseId {
+        unimplemented!()
+    }
+
+    async fn schedule_expiry(
+        &self,
+        _promise_id: &PromiseId,
+        _expires_at: DateTime<Utc>,
+        _account_id: AccountId,
+        _environment_id: EnvironmentId,
+    ) -> Result<(), WorkerExecutorError> {
         unimplemented!()
     }
 
@@ -446,12 +668,83 @@ impl PromiseService for PromiseServiceMock {
     async fn complete(
         &self,
         promise_id: PromiseId,
-        _data: Vec<u8>,
+        outcome: PromiseOutcome,
         _completed_by: AccountId,
     ) -> Result<bool, WorkerExecutorError> {
-        self.completed.lock().await.insert(promise_id);
-        Ok(true)
+        let mut completed = self.completed.lock().await;
+        if completed.contains_key(&promise_id) {
+            Ok(false)
+        } else {
+            completed.insert(promise_id, outcome);
+            Ok(true)
+        }
     }
 
     async fn cleanup(&self) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_typed_payload, RedisPromiseState};
+    use golem_common::model::PromiseOutcome;
+    use golem_common::serialization::{deserialize, serialize};
+    use golem_service_base::error::worker_executor::WorkerExecutorError;
+    use golem_wasm::analysis::analysed_type::{field, record, str, u32};
+    use golem_wasm::analysis::AnalysedType;
+    use test_r::test;
+
+    fn payload_type() -> AnalysedType {
+        record(vec![field("name", str()), field("count", u32())])
+    }
+
+    #[test]
+    fn matching_typed_payload_is_accepted() {
+        let result = validate_typed_payload(br#"{"name":"x","count":2}"#, &payload_type());
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn non_json_typed_payload_is_rejected() {
+        let result = validate_typed_payload(b"not json", &payload_type());
+
+        assert!(matches!(
+            result,
+            Err(WorkerExecutorError::ValueMismatch { details }) if details.contains("not valid JSON")
+        ));
+    }
+
+    #[test]
+    fn mismatching_typed_payload_is_rejected() {
+        let result = validate_typed_payload(br#"{"name":"x","count":"two"}"#, &payload_type());
+
+        assert!(matches!(
+            result,
+            Err(WorkerExecutorError::ValueMismatch { details }) if details.contains("does not match")
+        ));
+    }
+
+    #[test]
+    fn rejected_and_expired_outcomes_survive_serialization() {
+        for outcome in [
+            PromiseOutcome::Rejected("no longer needed".to_string()),
+            PromiseOutcome::Expired,
+            PromiseOutcome::Completed(vec![1, 2, 3]),
+        ] {
+            let state = RedisPromiseState::from_outcome(outcome.clone());
+            let bytes = serialize(&state).unwrap();
+            let deserialized: RedisPromiseState = deserialize(&bytes).unwrap();
+
+            assert_eq!(deserialized.into_outcome(), Some(outcome));
+        }
+    }
+
+    #[test]
+    fn pending_promises_have_no_outcome() {
+        assert_eq!(RedisPromiseState::Pending.into_outcome(), None);
+        assert_eq!(
+            RedisPromiseState::PendingTyped(payload_type()).into_outcome(),
+            None
+        );
+    }
+}
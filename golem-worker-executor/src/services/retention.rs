@@ -0,0 +1,575 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Retention policies deleting agents that exited, failed or have been idle for a long time.
+//!
+//! Agents are deleted as a whole, including all their oplog layers. The archived oplog entries
+//! preceding the latest snapshot of an agent can also be dropped on their own, keeping only the
+//! initial entry and the chunks the agent still recovers from.
+
+use crate::services::golem_config::{RetentionConfig, RetentionPolicy};
+use crate::services::{
+    HasActiveWorkers, HasAll, HasComponentService, HasConfig, HasOplogService, HasSchedulerService,
+    HasShardService, HasWorkerService,
+};
+use crate::worker::Worker;
+use crate::workerctx::WorkerCtx;
+use golem_common::model::component::ComponentId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::worker::{RetentionCandidate, RetentionReason};
+use golem_common::model::{OwnedWorkerId, ScanCursor, Timestamp, WorkerStatus, WorkerStatusRecord};
+use golem_service_base::error::worker_executor::WorkerExecutorError;
+use std::collections::{BTreeSet, HashSet};
+use std::marker::PhantomData;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tracing::{debug, error, info, span, warn, Instrument, Level};
+
+/// Evaluates and enforces the configured retention policies for the agents owned by this executor
+pub struct AgentRetention<Ctx: WorkerCtx, Svcs: HasAll<Ctx>> {
+    services: Svcs,
+    config: RetentionConfig,
+    ctx: PhantomData<Ctx>,
+}
+
+impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + Send + Sync + 'static> AgentRetention<Ctx, Svcs> {
+    pub fn new(services: Svcs, config: RetentionConfig) -> Self {
+        Self {
+            services,
+            config,
+            ctx: PhantomData,
+        }
+    }
+
+    /// Spawns the background task periodically deleting the agents selected by the retention
+    /// policies. The task runs until the runtime is shut down.
+    pub fn start(self, runtime: &Handle) {
+        let interval = self.config.interval;
+        runtime.spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    if self.services.shard_service().is_ready() {
+                        self.enforce_all(Timestamp::now_utc()).await;
+                    } else {
+                        warn!("Skipping retention, shard service is not ready")
+                    }
+                }
+            }
+            .instrument(span!(parent: None, Level::INFO, "Retention loop")),
+        );
+    }
+
+    /// Lists the agents of a component owned by this executor that would be deleted by the
+    /// retention policy of its environment at the given time, without deleting anything
+    pub async fn find_candidates(
+        &self,
+        environment_id: &EnvironmentId,
+        component_id: &ComponentId,
+        now: Timestamp,
+    ) -> Result<Vec<RetentionCandidate>, WorkerExecutorError> {
+        let policy = self.config.policy(environment_id);
+        if policy.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut result = Vec::new();
+        for owned_worker_id in self.scan_component(environment_id, component_id).await? {
+            if let Some(candidate) = self.evaluate(&owned_worker_id, policy, now).await {
+                result.push(candidate);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Applies the retention policies to all the agents of the shards owned by this executor.
+    ///
+    /// Agents are discovered from the index of workers per shard. Agents created before that
+    /// index existed are only found by scanning the components in the executor's cache.
+    async fn enforce_all(&self, now: Timestamp) {
+        let Some(shard_assignment) = self.services.shard_service().try_get_current_assignment()
+        else {
+            return;
+        };
+
+        let mut owned_worker_ids = HashSet::new();
+        for shard_id in &shard_assignment.shard_ids {
+            owned_worker_ids.extend(
+                self.services
+                    .worker_service()
+                    .get_workers_in_shard(shard_id)
+                    .await,
+            );
+        }
+
+        let cached_components: BTreeSet<(EnvironmentId, ComponentId)> = self
+            .services
+            .component_service()
+            .all_cached_metadata()
+            .await
+            .into_iter()
+            .map(|component| (component.environment_id, component.id))
+            .collect();
+        for (environment_id, component_id) in cached_components {
+            if self.config.policy(&environment_id).is_empty() {
+                continue;
+            }
+            match self.scan_component(&environment_id, &component_id).await {
+                Ok(ids) => owned_worker_ids.extend(ids),
+                Err(err) => {
+                    error!(
+                        %environment_id,
+                        %component_id,
+                        "Failed to list agents for the retention policy: {err}"
+                    );
+                }
+            }
+        }
+
+        for owned_worker_id in owned_worker_ids {
+            let policy = self.config.policy(&owned_worker_id.environment_id);
+            if policy.is_empty() {
+                continue;
+            }
+            match self.evaluate(&owned_worker_id, policy, now).await {
+                Some(candidate) => self.delete(owned_worker_id.environment_id, candidate).await,
+                None => {
+                    self.drop_archived_layers(&owned_worker_id, policy, now)
+                        .await
+                }
+            }
+        }
+    }
+
+    async fn scan_component(
+        &self,
+        environment_id: &EnvironmentId,
+        component_id: &ComponentId,
+    ) -> Result<Vec<OwnedWorkerId>, WorkerExecutorError> {
+        let mut result = Vec::new();
+        let mut cursor = ScanCursor::default();
+        loop {
+            let (new_cursor, owned_worker_ids) = self
+                .services
+                .oplog_service()
+                .scan_for_component(
+                    environment_id,
+                    component_id,
+                    cursor,
+                    self.config.scan_page_size,
+                )
+                .await?;
+            result.extend(owned_worker_ids);
+
+            if new_cursor.is_finished() {
+                break;
+            } else {
+                cursor = new_cursor;
+            }
+        }
+        Ok(result)
+    }
+
+    async fn delete(&self, environment_id: EnvironmentId, candidate: RetentionCandidate) {
+        let owned_worker_id = OwnedWorkerId::new(environment_id, &candidate.worker_id);
+
+        // The agent may have been resumed since it was evaluated
+        let Some(metadata) =
+            Worker::<Ctx>::get_latest_metadata(&self.services, &owned_worker_id).await
+        else {
+            return;
+        };
+        if metadata.last_known_status.status != candidate.status {
+            debug!(
+                worker_id = %candidate.worker_id,
+                "Skipping deletion, the status of the agent has changed"
+            );
+            return;
+        }
+
+        info!(
+            worker_id = %candidate.worker_id,
+            reason = %candidate.reason,
+            "Deleting agent according to the retention policy"
+        );
+        if let Err(err) =
+            Worker::<Ctx>::delete(&self.services, metadata.created_by, &owned_worker_id).await
+        {
+            error!(
+                worker_id = %candidate.worker_id,
+                "Failed to delete agent selected by the retention policy: {err}"
+            );
+        }
+    }
+
+    async fn drop_archived_layers(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        policy: &RetentionPolicy,
+        now: Timestamp,
+    ) {
+        // Without automatic snapshots the agents recover by replaying their whole oplog
+        let oplog_config = &self.services.config().oplog;
+        if oplog_config.snapshot_entry_interval.is_none()
+            && oplog_config.snapshot_time_interval.is_none()
+        {
+            return;
+        }
+        if policy.drop_archived_layers_after.is_none() || !self.is_inactive(owned_worker_id).await {
+            return;
+        }
+
+        let Some(metadata) =
+            Worker::<Ctx>::get_latest_metadata(&self.services, owned_worker_id).await
+        else {
+            return;
+        };
+        let Some(last_dropped_idx) =
+            archived_entries_to_drop(policy, &metadata.last_known_status, now)
+        else {
+            return;
+        };
+
+        // Archives are only written while the oplog is open, so nothing is dropped if the agent
+        // has been resumed since it was evaluated
+        match self
+            .services
+            .oplog_service()
+            .drop_archived_entries(owned_worker_id, last_dropped_idx)
+            .await
+        {
+            Some(0) => {}
+            Some(dropped) => {
+                info!(
+                    worker_id = %owned_worker_id.worker_id,
+                    "Dropped {dropped} archived oplog chunks preceding the snapshot at {}",
+                    last_dropped_idx.next()
+                );
+            }
+            None => {
+                debug!(
+                    worker_id = %owned_worker_id.worker_id,
+                    "Skipping dropping archived oplog entries, the oplog is open"
+                );
+            }
+        }
+    }
+
+    async fn evaluate(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        policy: &RetentionPolicy,
+        now: Timestamp,
+    ) -> Option<RetentionCandidate> {
+        if !self.is_inactive(owned_worker_id).await {
+            return None;
+        }
+
+        let metadata = Worker::<Ctx>::get_latest_metadata(&self.services, owned_worker_id).await?;
+        let status = &metadata.last_known_status;
+
+        let has_recurring_invocations = is_idle(status)
+            && policy.delete_idle_after.is_some()
+            && self.has_recurring_invocations(owned_worker_id).await;
+        let last_activity = self
+            .last_activity(owned_worker_id)
+            .await
+            .unwrap_or(metadata.created_at);
+        let inactive_for =
+            Duration::from_millis(now.to_millis().saturating_sub(last_activity.to_millis()));
+
+        retention_reason(policy, status, has_recurring_invocations, inactive_for).map(|reason| {
+            RetentionCandidate {
+                worker_id: owned_worker_id.worker_id(),
+                status: status.status.clone(),
+                reason,
+                last_activity,
+            }
+        })
+    }
+
+    /// Checks that the agent belongs to this executor and is not in memory. Agents in memory are
+    /// in use, even if their status is idle.
+    async fn is_inactive(&self, owned_worker_id: &OwnedWorkerId) -> bool {
+        self.services
+            .shard_service()
+            .check_worker(&owned_worker_id.worker_id)
+            .is_ok()
+            && self
+                .services
+                .active_workers()
+                .try_get(owned_worker_id)
+                .await
+                .is_none()
+    }
+
+    async fn has_recurring_invocations(&self, owned_worker_id: &OwnedWorkerId) -> bool {
+        match self
+            .services
+            .scheduler_service()
+            .get_recurring(owned_worker_id)
+            .await
+        {
+            Ok(recurring) => !recurring.is_empty(),
+            Err(err) => {
+                // Keeping the agent if we cannot tell whether it is going to be invoked
+                warn!(
+                    worker_id = %owned_worker_id.worker_id,
+                    "Failed to get recurring invocations: {err}"
+                );
+                true
+            }
+        }
+    }
+
+    async fn last_activity(&self, owned_worker_id: &OwnedWorkerId) -> Option<Timestamp> {
+        let oplog_service = self.services.oplog_service();
+        let last_index = oplog_service.get_last_index(owned_worker_id).await;
+        if last_index == OplogIndex::NONE {
+            return None;
+        }
+        oplog_service
+            .read(owned_worker_id, last_index, 1)
+            .await
+            .into_values()
+            .next()
+            .map(|entry| entry.timestamp())
+    }
+}
+
+fn is_idle(status: &WorkerStatusRecord) -> bool {
+    matches!(
+        status.status,
+        WorkerStatus::Idle | WorkerStatus::Suspended | WorkerStatus::Interrupted
+    ) && status.pending_invocations.is_empty()
+}
+
+/// Decides whether an agent is deleted by the retention policy, given its last known status and
+/// the time passed since its last oplog entry
+fn retention_reason(
+    policy: &RetentionPolicy,
+    status: &WorkerStatusRecord,
+    has_recurring_invocations: bool,
+    inactive_for: Duration,
+) -> Option<RetentionReason> {
+    if status
+        .labels
+        .iter()
+        .any(|(key, value)| policy.keep_labels.get(key) == Some(value))
+    {
+        return None;
+    }
+
+    let (reason, threshold) = match status.status {
+        WorkerStatus::Exited => (RetentionReason::Exited, policy.delete_exited_after?),
+        WorkerStatus::Failed => (RetentionReason::Failed, policy.delete_failed_after?),
+        WorkerStatus::Running | WorkerStatus::Retrying => return None,
+        _ if !is_idle(status) || has_recurring_invocations => return None,
+        _ => (RetentionReason::Idle, policy.delete_idle_after?),
+    };
+
+    (inactive_for >= threshold).then_some(reason)
+}
+
+/// Selects the last archived oplog entry to drop, which is the one preceding the latest snapshot
+/// of the agent once the snapshot is older than configured by the policy. Agents with pending
+/// updates are skipped, as updates may require replaying the oplog from the beginning.
+fn archived_entries_to_drop(
+    policy: &RetentionPolicy,
+    status: &WorkerStatusRecord,
+    now: Timestamp,
+) -> Option<OplogIndex> {
+    let threshold = policy.drop_archived_layers_after?;
+    let snapshot = status.last_snapshot.as_ref()?;
+    if !status.pending_updates.is_empty() {
+        return None;
+    }
+
+    let age = Duration::from_millis(
+        now.to_millis()
+            .saturating_sub(snapshot.timestamp.to_millis()),
+    );
+    (age >= threshold && snapshot.oplog_index > OplogIndex::INITIAL.next())
+        .then(|| snapshot.oplog_index.previous())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::{archived_entries_to_drop, retention_reason};
+    use crate::services::golem_config::RetentionPolicy;
+    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::worker::RetentionReason;
+    use golem_common::model::{SnapshotRecord, Timestamp, WorkerStatus, WorkerStatusRecord};
+    use std::time::Duration;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn policy() -> RetentionPolicy {
+        RetentionPolicy {
+            delete_exited_after: Some(DAY),
+            delete_failed_after: Some(7 * DAY),
+            delete_idle_after: Some(30 * DAY),
+            ..RetentionPolicy::default()
+        }
+    }
+
+    fn status(status: WorkerStatus) -> WorkerStatusRecord {
+        WorkerStatusRecord {
+            status,
+            ..WorkerStatusRecord::default()
+        }
+    }
+
+    #[test]
+    fn agents_are_deleted_after_the_threshold_of_their_status() {
+        let policy = policy();
+
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Exited), false, DAY),
+            Some(RetentionReason::Exited)
+        );
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Exited), false, DAY / 2),
+            None
+        );
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Failed), false, 2 * DAY),
+            None
+        );
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Failed), false, 7 * DAY),
+            Some(RetentionReason::Failed)
+        );
+        for idle in [
+            WorkerStatus::Idle,
+            WorkerStatus::Suspended,
+            WorkerStatus::Interrupted,
+        ] {
+            assert_eq!(
+                retention_reason(&policy, &status(idle.clone()), false, 29 * DAY),
+                None
+            );
+            assert_eq!(
+                retention_reason(&policy, &status(idle), false, 30 * DAY),
+                Some(RetentionReason::Idle)
+            );
+        }
+    }
+
+    #[test]
+    fn running_agents_are_never_deleted() {
+        let policy = policy();
+
+        for running in [WorkerStatus::Running, WorkerStatus::Retrying] {
+            assert_eq!(
+                retention_reason(&policy, &status(running), false, 365 * DAY),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn statuses_without_threshold_are_kept() {
+        let policy = RetentionPolicy {
+            delete_exited_after: Some(DAY),
+            ..RetentionPolicy::default()
+        };
+
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Failed), false, 365 * DAY),
+            None
+        );
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Idle), false, 365 * DAY),
+            None
+        );
+    }
+
+    #[test]
+    fn idle_agents_with_recurring_invocations_are_kept() {
+        let policy = policy();
+
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Idle), true, 365 * DAY),
+            None
+        );
+        // Recurring invocations do not keep agents that already exited
+        assert_eq!(
+            retention_reason(&policy, &status(WorkerStatus::Exited), true, 365 * DAY),
+            Some(RetentionReason::Exited)
+        );
+    }
+
+    #[test]
+    fn agents_with_kept_labels_are_not_deleted() {
+        let mut policy = policy();
+        policy
+            .keep_labels
+            .insert("tier".to_string(), "gold".to_string());
+
+        let mut gold = status(WorkerStatus::Exited);
+        gold.labels.insert("tier".to_string(), "gold".to_string());
+        let mut silver = status(WorkerStatus::Exited);
+        silver
+            .labels
+            .insert("tier".to_string(), "silver".to_string());
+
+        assert_eq!(retention_reason(&policy, &gold, false, 365 * DAY), None);
+        assert_eq!(
+            retention_reason(&policy, &silver, false, 365 * DAY),
+            Some(RetentionReason::Exited)
+        );
+    }
+
+    #[test]
+    fn archived_entries_preceding_old_snapshots_are_dropped() {
+        let policy = RetentionPolicy {
+            drop_archived_layers_after: Some(DAY),
+            ..RetentionPolicy::default()
+        };
+        let now = Timestamp::from(100 * DAY.as_millis() as u64);
+        let snapshot_at = |days_ago: u64, oplog_index: u64| WorkerStatusRecord {
+            last_snapshot: Some(SnapshotRecord {
+                timestamp: Timestamp::from((100 - days_ago) * DAY.as_millis() as u64),
+                oplog_index: OplogIndex::from_u64(oplog_index),
+            }),
+            ..WorkerStatusRecord::default()
+        };
+
+        assert_eq!(
+            archived_entries_to_drop(&policy, &snapshot_at(2, 100), now),
+            Some(OplogIndex::from_u64(99))
+        );
+        assert_eq!(
+            archived_entries_to_drop(&policy, &snapshot_at(0, 100), now),
+            None
+        );
+        // Nothing precedes a snapshot right after the initial entry
+        assert_eq!(
+            archived_entries_to_drop(&policy, &snapshot_at(2, 2), now),
+            None
+        );
+        assert_eq!(
+            archived_entries_to_drop(&policy, &WorkerStatusRecord::default(), now),
+            None
+        );
+        assert_eq!(
+            archived_entries_to_drop(&RetentionPolicy::default(), &snapshot_at(2, 100), now),
+            None
+        );
+    }
+}
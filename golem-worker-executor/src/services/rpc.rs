@@ -85,10 +85,22 @@ pub trait Rpc: Send + Sync {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RpcError {
-    ProtocolError { details: String },
-    Denied { details: String },
-    NotFound { details: String },
-    RemoteInternalError { details: String },
+    ProtocolError {
+        details: String,
+    },
+    Denied {
+        details: String,
+    },
+    NotFound {
+        details: String,
+    },
+    RemoteInternalError {
+        details: String,
+    },
+    /// The invocation did not complete before its deadline
+    Timeout {
+        details: String,
+    },
 }
 
 impl From<SerializableRpcError> for RpcError {
@@ -100,6 +112,7 @@ impl From<SerializableRpcError> for RpcError {
             SerializableRpcError::RemoteInternalError { details } => {
                 Self::RemoteInternalError { details }
             }
+            SerializableRpcError::Timeout { details } => Self::Timeout { details },
         }
     }
 }
@@ -113,6 +126,7 @@ impl From<RpcError> for SerializableRpcError {
             RpcError::RemoteInternalError { details } => {
                 SerializableRpcError::RemoteInternalError { details }
             }
+            RpcError::Timeout { details } => SerializableRpcError::Timeout { details },
         }
     }
 }
@@ -126,6 +140,7 @@ impl Display for RpcError {
             RpcError::RemoteInternalError { details } => {
                 write!(f, "Remote internal error: {details}")
             }
+            RpcError::Timeout { details } => write!(f, "Timeout: {details}"),
         }
     }
 }
@@ -191,6 +206,7 @@ impl From<golem_wasm::RpcError> for RpcError {
             golem_wasm::RpcError::RemoteInternalError(details) => {
                 Self::RemoteInternalError { details }
             }
+            golem_wasm::RpcError::Timeout(details) => Self::Timeout { details },
         }
     }
 }
@@ -27,23 +27,63 @@ use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 use async_trait::async_trait;
 use chrono::{DateTime, TimeZone, Utc};
+use desert_rust::BinaryCodec;
 use golem_common::model::account::AccountId;
+use golem_common::model::environment::EnvironmentId;
 use golem_common::model::invocation_context::InvocationContextStack;
-use golem_common::model::{IdempotencyKey, OwnedWorkerId, ScheduleId, ScheduledAction};
+use golem_common::model::worker::RecurringInvocation;
+use golem_common::model::{
+    IdempotencyKey, OwnedWorkerId, PromiseId, PromiseOutcome, ScheduleId, ScheduledAction,
+    Timestamp,
+};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_wasm::Value;
+use std::collections::BTreeSet;
 use std::ops::{Add, Deref};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
-use tracing::{error, info, span, warn, Instrument, Level};
+use tracing::{debug, error, info, span, warn, Instrument, Level};
 
 #[async_trait]
 pub trait SchedulerService: Send + Sync {
     async fn schedule(&self, time: DateTime<Utc>, action: ScheduledAction) -> ScheduleId;
 
     async fn cancel(&self, id: ScheduleId);
+
+    /// Gets the recurring invocations currently registered for the given worker
+    async fn get_recurring(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Vec<RecurringInvocation>, String>;
+
+    /// Cancels a recurring invocation identified by its base idempotency key.
+    /// Returns `false` if there was no such recurring invocation registered for the worker.
+    async fn cancel_recurring(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<bool, String>;
+}
+
+/// Registration of a recurring invocation, pointing to the bucket holding its next occurrence
+#[derive(Debug, Clone, PartialEq, BinaryCodec)]
+#[desert(evolution())]
+struct RecurringScheduleEntry {
+    next_at: i64,
+    action: ScheduledAction,
+}
+
+impl RecurringScheduleEntry {
+    fn idempotency_key(&self) -> Option<&IdempotencyKey> {
+        match &self.action {
+            ScheduledAction::RecurringInvoke {
+                idempotency_key, ..
+            } => Some(idempotency_key),
+            _ => None,
+        }
+    }
 }
 
 /// A lighter trait than `WorkerActivator` that only provides the required functionality
@@ -189,26 +229,39 @@ impl SchedulerServiceDefault {
 
     async fn process(&self, now: DateTime<Utc>) -> Result<(), String> {
         let (hours_since_epoch, remainder) = Self::split_time(now);
-        let previous_hours_since_epoch = hours_since_epoch - 1;
 
-        let previous_hour_key = Self::schedule_key_from_timestamp(previous_hours_since_epoch);
-        let current_hour_key = Self::schedule_key_from_timestamp(hours_since_epoch);
+        // Every bucket of a past hour is due entirely. Besides the previous hour, all the past buckets
+        // still registered as pending are processed, so actions are not lost when the scheduler was not
+        // running for more than an hour.
+        let mut past_hours: BTreeSet<i64> = self
+            .pending_buckets()
+            .await?
+            .into_iter()
+            .filter(|hour| *hour < hours_since_epoch)
+            .collect();
+        past_hours.insert(hours_since_epoch - 1);
 
-        // TODO: couple of issues with this implementation
-        // 1: We only query scheduled actions for the current hour - 1. If we are unavailable for longer than that actions will not be run.
-        // 2: We use the timestamp of the scheduled action as a unique key. If we have 2 actions scheduled for the same point in time one will be silently discarded.
+        // TODO: We use the timestamp of the scheduled action as a unique key. If we have 2 actions scheduled for the same point in time one will be silently discarded.
 
-        let all_from_prev_hour: Vec<(f64, ScheduledAction)> = self
-            .key_value_storage
-            .with_entity("scheduler", "process", "scheduled_action")
-            .get_sorted_set(KeyValueStorageNamespace::Schedule, &previous_hour_key)
-            .await?;
+        let mut all: Vec<(String, i64, ScheduledAction)> = Vec::new();
+        for hour in &past_hours {
+            let key = Self::schedule_key_from_timestamp(*hour);
+            let all_from_hour: Vec<(f64, ScheduledAction)> = self
+                .key_value_storage
+                .with_entity("scheduler", "process", "scheduled_action")
+                .get_sorted_set(KeyValueStorageNamespace::Schedule, &key)
+                .await?;
 
-        let mut all: Vec<(&str, ScheduledAction)> = all_from_prev_hour
-            .into_iter()
-            .map(|(_score, action)| (previous_hour_key.as_str(), action))
-            .collect();
+            all.extend(all_from_hour.into_iter().map(|(score, action)| {
+                (
+                    key.clone(),
+                    hour * Self::HOUR_IN_MILLIS + score as i64,
+                    action,
+                )
+            }));
+        }
 
+        let current_hour_key = Self::schedule_key_from_timestamp(hours_since_epoch);
         let all_from_this_hour: Vec<(f64, ScheduledAction)> = self
             .key_value_storage
             .with_entity("scheduler", "process", "scheduled_action")
@@ -220,15 +273,17 @@ impl SchedulerServiceDefault {
             )
             .await?;
 
-        all.extend(
-            all_from_this_hour
-                .into_iter()
-                .map(|(_score, action)| (current_hour_key.as_str(), action)),
-        );
+        all.extend(all_from_this_hour.into_iter().map(|(score, action)| {
+            (
+                current_hour_key.clone(),
+                hours_since_epoch * Self::HOUR_IN_MILLIS + score as i64,
+                action,
+            )
+        }));
 
-        let matching: Vec<(&str, ScheduledAction)> = all
+        let matching: Vec<(String, i64, ScheduledAction)> = all
             .into_iter()
-            .filter(|(_, action)| {
+            .filter(|(_, _, action)| {
                 self.shard_service
                     .check_worker(&action.owned_worker_id().worker_id)
                     .is_ok()
@@ -237,48 +292,33 @@ impl SchedulerServiceDefault {
 
         // ! Do not exist early from this loop because of failed actions, as it will cause all other actions to be skipped.
         // ! Errors will only be logged anyway, so just log them inline here and ignore.
-        for (key, action) in matching {
+        for (key, occurrence_millis, action) in matching {
             match action.clone() {
                 ScheduledAction::CompletePromise {
                     account_id,
                     promise_id,
                     environment_id,
                 } => {
-                    let owned_worker_id = OwnedWorkerId::new(environment_id, &promise_id.worker_id);
-
-                    let result = self
-                        .promise_service
-                        .complete(promise_id.clone(), vec![], account_id)
-                        .await;
-
-                    // TODO: We probably need more error handling here as not completing a promise that is expected to complete can lead to deadlocks.
-                    match result {
-                        Ok(_) => {
-                            // activate worker so it starts processing the newly completed promises
-                            // TODO: this is probably redundant with the wakeup in PromiseService. check and fix
-                            {
-                                let span = span!(
-                                    Level::INFO,
-                                    "scheduler",
-                                    worker_id = owned_worker_id.worker_id.to_string()
-                                );
-
-                                self.worker_access
-                                    .activate_worker(account_id, &owned_worker_id)
-                                    .instrument(span)
-                                    .await;
-                            }
-
-                            record_scheduled_promise_completed();
-                        }
-                        Err(e) => {
-                            error!(
-                                worker_id = owned_worker_id.to_string(),
-                                promise_id = promise_id.to_string(),
-                                "Failed to complete promise: {e}"
-                            );
-                        }
-                    }
+                    self.resolve_promise(
+                        account_id,
+                        environment_id,
+                        promise_id,
+                        PromiseOutcome::Completed(vec![]),
+                    )
+                    .await
+                }
+                ScheduledAction::ExpirePromise {
+                    account_id,
+                    promise_id,
+                    environment_id,
+                } => {
+                    self.resolve_promise(
+                        account_id,
+                        environment_id,
+                        promise_id,
+                        PromiseOutcome::Expired,
+                    )
+                    .await
                 }
                 ScheduledAction::ArchiveOplog {
                     account_id,
@@ -365,20 +405,239 @@ impl SchedulerServiceDefault {
                         );
                     };
                 }
+                ScheduledAction::RecurringInvoke {
+                    account_id,
+                    owned_worker_id,
+                    idempotency_key,
+                    full_function_name,
+                    function_input,
+                    invocation_context,
+                    ..
+                } => {
+                    // Each occurrence gets a deterministic idempotency key, so processing the same
+                    // occurrence twice does not lead to duplicate invocations.
+                    let occurrence_idempotency_key =
+                        IdempotencyKey::derived_for_occurrence(&idempotency_key, occurrence_millis);
+
+                    let result = self
+                        .worker_access
+                        .enqueue_invocation(
+                            account_id,
+                            &owned_worker_id,
+                            occurrence_idempotency_key,
+                            full_function_name.clone(),
+                            function_input,
+                            invocation_context,
+                        )
+                        .await;
+
+                    if let Err(e) = result {
+                        error!(
+                            worker_id = owned_worker_id.to_string(),
+                            full_function_name = full_function_name,
+                            "Failed to invoke worker with recurring scheduled invocation: {e}"
+                        );
+                    };
+                }
             }
 
             // We are completely done with the action, purge it from the queue
             self.key_value_storage
                 .with_entity("scheduler", "process", "scheduled_action")
-                .remove_from_sorted_set(KeyValueStorageNamespace::Schedule, key, &action)
+                .remove_from_sorted_set(KeyValueStorageNamespace::Schedule, &key, &action)
                 .await?;
+
+            if let ScheduledAction::RecurringInvoke { .. } = &action {
+                self.schedule_next_occurrence(action, occurrence_millis, now)
+                    .await?;
+            }
+        }
+
+        // Past buckets which still hold actions (for example of workers belonging to other shards) stay pending
+        for hour in past_hours {
+            let remaining: Vec<(f64, ScheduledAction)> = self
+                .key_value_storage
+                .with_entity("scheduler", "process", "scheduled_action")
+                .get_sorted_set(
+                    KeyValueStorageNamespace::Schedule,
+                    &Self::schedule_key_from_timestamp(hour),
+                )
+                .await?;
+            if remaining.is_empty() {
+                self.key_value_storage
+                    .with_entity("scheduler", "process", "pending_bucket")
+                    .remove_from_set(
+                        KeyValueStorageNamespace::Schedule,
+                        Self::PENDING_BUCKETS_KEY,
+                        &hour,
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn pending_buckets(&self) -> Result<Vec<i64>, String> {
+        self.key_value_storage
+            .with_entity("scheduler", "process", "pending_bucket")
+            .members_of_set(
+                KeyValueStorageNamespace::Schedule,
+                Self::PENDING_BUCKETS_KEY,
+            )
+            .await
+    }
+
+    async fn resolve_promise(
+        &self,
+        account_id: AccountId,
+        environment_id: EnvironmentId,
+        promise_id: PromiseId,
+        outcome: PromiseOutcome,
+    ) {
+        let owned_worker_id = OwnedWorkerId::new(environment_id, &promise_id.worker_id);
+
+        let result = self
+            .promise_service
+            .complete(promise_id.clone(), outcome, account_id)
+            .await;
+
+        // TODO: We probably need more error handling here as not completing a promise that is expected to complete can lead to deadlocks.
+        match result {
+            Ok(false) => {
+                debug!(
+                    worker_id = owned_worker_id.to_string(),
+                    promise_id = promise_id.to_string(),
+                    "Promise was already completed"
+                );
+            }
+            Ok(true) => {
+                // activate worker so it starts processing the newly completed promises
+                // TODO: this is probably redundant with the wakeup in PromiseService. check and fix
+                {
+                    let span = span!(
+                        Level::INFO,
+                        "scheduler",
+                        worker_id = owned_worker_id.worker_id.to_string()
+                    );
+
+                    self.worker_access
+                        .activate_worker(account_id, &owned_worker_id)
+                        .instrument(span)
+                        .await;
+                }
+
+                record_scheduled_promise_completed();
+            }
+            Err(e) => {
+                error!(
+                    worker_id = owned_worker_id.to_string(),
+                    promise_id = promise_id.to_string(),
+                    "Failed to complete promise: {e}"
+                );
+            }
+        }
+    }
+
+    async fn schedule_next_occurrence(
+        &self,
+        action: ScheduledAction,
+        occurrence_millis: i64,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let ScheduledAction::RecurringInvoke {
+            owned_worker_id,
+            idempotency_key,
+            recurrence,
+            ..
+        } = &action
+        else {
+            return Ok(());
+        };
+
+        // Occurrences missed while the scheduler was not running are skipped
+        let from_millis = (occurrence_millis + 1).max(now.timestamp_millis());
+        match recurrence.next_occurrence(occurrence_millis, from_millis) {
+            Ok(Some(next_millis)) => match Utc.timestamp_millis_opt(next_millis).single() {
+                Some(next) => {
+                    self.schedule(next, action).await;
+                }
+                None => {
+                    error!(
+                        worker_id = owned_worker_id.to_string(),
+                        "Next occurrence of recurring invocation is out of range: {next_millis}"
+                    );
+                    self.remove_recurring_entry(owned_worker_id, idempotency_key)
+                        .await?;
+                }
+            },
+            Ok(None) => {
+                info!(
+                    worker_id = owned_worker_id.to_string(),
+                    idempotency_key = idempotency_key.to_string(),
+                    "Recurring invocation has no more occurrences"
+                );
+                self.remove_recurring_entry(owned_worker_id, idempotency_key)
+                    .await?;
+            }
+            Err(err) => {
+                error!(
+                    worker_id = owned_worker_id.to_string(),
+                    idempotency_key = idempotency_key.to_string(),
+                    "Failed to calculate next occurrence of recurring invocation: {err}"
+                );
+                self.remove_recurring_entry(owned_worker_id, idempotency_key)
+                    .await?;
+            }
         }
 
         Ok(())
     }
 
+    async fn get_recurring_entries(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Vec<RecurringScheduleEntry>, String> {
+        self.key_value_storage
+            .with_entity("scheduler", "get_recurring", "recurring_schedule")
+            .members_of_set(
+                KeyValueStorageNamespace::Schedule,
+                &Self::recurring_key(owned_worker_id),
+            )
+            .await
+    }
+
+    /// Removes the registration of a recurring invocation, and returns it if it existed
+    async fn remove_recurring_entry(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<Option<RecurringScheduleEntry>, String> {
+        let entry = self
+            .get_recurring_entries(owned_worker_id)
+            .await?
+            .into_iter()
+            .find(|entry| entry.idempotency_key() == Some(idempotency_key));
+
+        if let Some(entry) = &entry {
+            self.key_value_storage
+                .with_entity("scheduler", "remove_recurring", "recurring_schedule")
+                .remove_from_set(
+                    KeyValueStorageNamespace::Schedule,
+                    &Self::recurring_key(owned_worker_id),
+                    entry,
+                )
+                .await?;
+        }
+
+        Ok(entry)
+    }
+
     const HOUR_IN_MILLIS: i64 = 1000 * 60 * 60;
 
+    /// Set of the hours (since epoch) having a bucket of scheduled actions
+    const PENDING_BUCKETS_KEY: &'static str = "worker:schedule:pending";
+
     fn split_time<Tz: TimeZone>(time: DateTime<Tz>) -> (i64, f64) {
         let millis = time.timestamp_millis();
         let hours_since_epoch = millis / Self::HOUR_IN_MILLIS;
@@ -393,6 +652,13 @@ impl SchedulerServiceDefault {
     fn schedule_key_from_timestamp(timestamp: i64) -> String {
         format!("worker:schedule:{timestamp}")
     }
+
+    fn recurring_key(owned_worker_id: &OwnedWorkerId) -> String {
+        format!(
+            "worker:recurring:{}:{}",
+            owned_worker_id.environment_id, owned_worker_id.worker_id
+        )
+    }
 }
 
 impl Drop for SchedulerServiceDefault {
@@ -413,6 +679,49 @@ impl SchedulerService for SchedulerServiceDefault {
             action: action.clone(),
         };
 
+        if let ScheduledAction::RecurringInvoke {
+            owned_worker_id,
+            idempotency_key,
+            ..
+        } = &action
+        {
+            // Scheduling a recurring invocation with an already registered idempotency key replaces the previous schedule
+            self.cancel_recurring(owned_worker_id, idempotency_key)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to replace recurring schedule for action {action} in KV storage: {err}")
+                });
+
+            self.key_value_storage
+                .with_entity("scheduler", "schedule", "recurring_schedule")
+                .add_to_set(
+                    KeyValueStorageNamespace::Schedule,
+                    &Self::recurring_key(owned_worker_id),
+                    &RecurringScheduleEntry {
+                        next_at: time.timestamp_millis(),
+                        action: action.clone(),
+                    },
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to register recurring schedule for action {action} in KV storage: {err}")
+                });
+        }
+
+        self.key_value_storage
+            .with_entity("scheduler", "schedule", "pending_bucket")
+            .add_to_set(
+                KeyValueStorageNamespace::Schedule,
+                Self::PENDING_BUCKETS_KEY,
+                &hours_since_epoch,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to register schedule bucket for action {action} in KV storage: {err}"
+                )
+            });
+
         self.key_value_storage
             .with_entity("scheduler", "schedule", "scheduled_action")
             .add_to_sorted_set(
@@ -430,6 +739,27 @@ impl SchedulerService for SchedulerServiceDefault {
     }
 
     async fn cancel(&self, id: ScheduleId) {
+        if let ScheduledAction::RecurringInvoke {
+            owned_worker_id,
+            idempotency_key,
+            ..
+        } = &id.action
+        {
+            // The recurring invocation may have been rescheduled to a different bucket since it was created
+            let found = self
+                .cancel_recurring(owned_worker_id, idempotency_key)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "failed to cancel recurring schedule for action {} in KV storage: {err}",
+                        id.action
+                    )
+                });
+            if found {
+                return;
+            }
+        }
+
         self.key_value_storage
             .with_entity("scheduler", "cancel", "scheduled_action")
             .remove_from_sorted_set(
@@ -445,13 +775,69 @@ impl SchedulerService for SchedulerServiceDefault {
                 )
             });
     }
+
+    async fn get_recurring(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<Vec<RecurringInvocation>, String> {
+        let mut entries = self.get_recurring_entries(owned_worker_id).await?;
+        entries.sort_by_key(|entry| entry.next_at);
+
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| match entry.action {
+                ScheduledAction::RecurringInvoke {
+                    idempotency_key,
+                    full_function_name,
+                    recurrence,
+                    ..
+                } => Some(RecurringInvocation {
+                    idempotency_key,
+                    function_name: full_function_name,
+                    recurrence,
+                    next_occurrence: Timestamp::from(entry.next_at.max(0) as u64),
+                }),
+                _ => None,
+            })
+            .collect())
+    }
+
+    async fn cancel_recurring(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Result<bool, String> {
+        match self
+            .remove_recurring_entry(owned_worker_id, idempotency_key)
+            .await?
+        {
+            Some(entry) => {
+                let (hours_since_epoch, _) = Self::split_time(
+                    Utc.timestamp_millis_opt(entry.next_at)
+                        .single()
+                        .ok_or_else(|| format!("Invalid timestamp: {}", entry.next_at))?,
+                );
+                self.key_value_storage
+                    .with_entity("scheduler", "cancel_recurring", "scheduled_action")
+                    .remove_from_sorted_set(
+                        KeyValueStorageNamespace::Schedule,
+                        &Self::schedule_key_from_timestamp(hours_since_epoch),
+                        &entry.action,
+                    )
+                    .await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::services::golem_config::GolemConfig;
+    use crate::services::oplog::encryption::OplogEncryption;
     use crate::services::oplog::{Oplog, OplogService, PrimaryOplogService};
-    use crate::services::promise::PromiseServiceMock;
+    use crate::services::promise::{PromiseService, PromiseServiceMock};
     use crate::services::scheduler::{
         SchedulerService, SchedulerServiceDefault, SchedulerWorkerAccess,
     };
@@ -460,22 +846,24 @@ mod tests {
     use crate::storage::indexed::memory::InMemoryIndexedStorage;
     use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
     use async_trait::async_trait;
-    use chrono::DateTime;
+    use chrono::{DateTime, Utc};
     use desert_rust::BinarySerializer;
     use golem_common::model::account::AccountId;
     use golem_common::model::component::ComponentId;
     use golem_common::model::environment::EnvironmentId;
     use golem_common::model::invocation_context::InvocationContextStack;
     use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::worker::{IntervalRecurrence, ScheduleRecurrence};
     use golem_common::model::{
-        IdempotencyKey, OwnedWorkerId, PromiseId, ScheduledAction, ShardId, WorkerId,
+        IdempotencyKey, OwnedWorkerId, PromiseId, PromiseOutcome, ScheduledAction, ShardId,
+        Timestamp, WorkerId,
     };
     use golem_service_base::error::worker_executor::WorkerExecutorError;
     use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
     use golem_wasm::Value;
     use std::collections::{HashMap, HashSet};
     use std::str::FromStr;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
     use std::time::Duration;
     use test_r::test;
     use uuid::Uuid;
@@ -505,6 +893,45 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct SchedulerWorkerAccessRecordingMock {
+        activations: Mutex<Vec<OwnedWorkerId>>,
+        invocations: Mutex<Vec<(OwnedWorkerId, IdempotencyKey, String)>>,
+    }
+
+    #[async_trait]
+    impl SchedulerWorkerAccess for SchedulerWorkerAccessRecordingMock {
+        async fn activate_worker(&self, _created_by: AccountId, owned_worker_id: &OwnedWorkerId) {
+            self.activations
+                .lock()
+                .unwrap()
+                .push(owned_worker_id.clone());
+        }
+        async fn open_oplog(
+            &self,
+            _created_by: AccountId,
+            _owned_worker_id: &OwnedWorkerId,
+        ) -> Result<Arc<dyn Oplog>, WorkerExecutorError> {
+            unimplemented!()
+        }
+        async fn enqueue_invocation(
+            &self,
+            _created_by: AccountId,
+            owned_worker_id: &OwnedWorkerId,
+            idempotency_key: IdempotencyKey,
+            full_function_name: String,
+            _function_input: Vec<Value>,
+            _invocation_context: InvocationContextStack,
+        ) -> Result<(), WorkerExecutorError> {
+            self.invocations.lock().unwrap().push((
+                owned_worker_id.clone(),
+                idempotency_key,
+                full_function_name,
+            ));
+            Ok(())
+        }
+    }
+
     fn serialized_bytes<T: BinarySerializer>(entry: &T) -> Vec<u8> {
         golem_common::serialization::serialize(entry)
             .expect("failed to serialize entry")
@@ -533,6 +960,7 @@ mod tests {
                 1,
                 1,
                 1024,
+                OplogEncryption::disabled(),
             )
             .await,
         )
@@ -1263,4 +1691,466 @@ mod tests {
         assert!(completed_promises.contains(&p3));
         assert!(completed_promises.contains(&p2));
     }
+
+    #[test]
+    pub async fn process_expires_promises() {
+        let c1: ComponentId = ComponentId(Uuid::new_v4());
+        let i1: WorkerId = WorkerId {
+            component_id: c1,
+            worker_name: "inst1".to_string(),
+        };
+
+        let environment_id = EnvironmentId::new();
+
+        let p1: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(101),
+        };
+        let p2: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(123),
+        };
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = create_shard_service_mock();
+        let promise_service = create_promise_service_mock();
+        let worker_access = create_worker_access_mock();
+        let oplog_service = create_oplog_service_mock().await;
+        let golem_config = Arc::new(GolemConfig::default());
+        let worker_service = create_worker_service_mock(
+            kvs.clone(),
+            shard_service.clone(),
+            oplog_service.clone(),
+            golem_config,
+        );
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service.clone(),
+            worker_access,
+            oplog_service,
+            worker_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let account_id = AccountId::new();
+
+        let _s1 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T10:05:00Z").unwrap(),
+                ScheduledAction::ExpirePromise {
+                    account_id,
+                    promise_id: p1.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+        let _s2 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T10:06:00Z").unwrap(),
+                ScheduledAction::CompletePromise {
+                    account_id,
+                    promise_id: p2.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            promise_service.outcome(&p1).await,
+            Some(PromiseOutcome::Expired)
+        );
+        assert_eq!(
+            promise_service.outcome(&p2).await,
+            Some(PromiseOutcome::Completed(vec![]))
+        );
+    }
+
+    #[test]
+    pub async fn expiring_already_completed_promises_does_not_activate_the_worker() {
+        let c1: ComponentId = ComponentId(Uuid::new_v4());
+        let i1: WorkerId = WorkerId {
+            component_id: c1,
+            worker_name: "inst1".to_string(),
+        };
+        let i2: WorkerId = WorkerId {
+            component_id: c1,
+            worker_name: "inst2".to_string(),
+        };
+
+        let environment_id = EnvironmentId::new();
+
+        let p1: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(101),
+        };
+        let p2: PromiseId = PromiseId {
+            worker_id: i2.clone(),
+            oplog_idx: OplogIndex::from_u64(123),
+        };
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = create_shard_service_mock();
+        let promise_service = create_promise_service_mock();
+        let worker_access = Arc::new(SchedulerWorkerAccessRecordingMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let golem_config = Arc::new(GolemConfig::default());
+        let worker_service = create_worker_service_mock(
+            kvs.clone(),
+            shard_service.clone(),
+            oplog_service.clone(),
+            golem_config,
+        );
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service.clone(),
+            worker_access.clone(),
+            oplog_service,
+            worker_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let account_id = AccountId::new();
+
+        promise_service
+            .complete(p1.clone(), PromiseOutcome::Completed(vec![1]), account_id)
+            .await
+            .unwrap();
+
+        for promise_id in [&p1, &p2] {
+            svc.schedule(
+                DateTime::from_str("2023-07-17T10:05:00Z").unwrap(),
+                ScheduledAction::ExpirePromise {
+                    account_id,
+                    promise_id: promise_id.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+        }
+
+        svc.process(DateTime::from_str("2023-07-17T10:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            promise_service.outcome(&p1).await,
+            Some(PromiseOutcome::Completed(vec![1]))
+        );
+        assert_eq!(
+            promise_service.outcome(&p2).await,
+            Some(PromiseOutcome::Expired)
+        );
+        assert_eq!(
+            *worker_access.activations.lock().unwrap(),
+            vec![OwnedWorkerId::new(environment_id, &i2)]
+        );
+    }
+
+    #[test]
+    pub async fn process_schedules_overdue_for_more_than_an_hour() {
+        let c1: ComponentId = ComponentId(Uuid::new_v4());
+        let i1: WorkerId = WorkerId {
+            component_id: c1,
+            worker_name: "inst1".to_string(),
+        };
+
+        let environment_id = EnvironmentId::new();
+
+        let p1: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(101),
+        };
+        let p2: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(123),
+        };
+        let p3: PromiseId = PromiseId {
+            worker_id: i1.clone(),
+            oplog_idx: OplogIndex::from_u64(200),
+        };
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = create_shard_service_mock();
+        let promise_service = create_promise_service_mock();
+        let worker_access = create_worker_access_mock();
+        let oplog_service = create_oplog_service_mock().await;
+        let golem_config = Arc::new(GolemConfig::default());
+        let worker_service = create_worker_service_mock(
+            kvs.clone(),
+            shard_service.clone(),
+            oplog_service.clone(),
+            golem_config,
+        );
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service.clone(),
+            worker_access,
+            oplog_service,
+            worker_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let account_id = AccountId::new();
+
+        let _s1 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T07:30:00Z").unwrap(),
+                ScheduledAction::ExpirePromise {
+                    account_id,
+                    promise_id: p1.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+        let _s2 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T10:06:00Z").unwrap(),
+                ScheduledAction::CompletePromise {
+                    account_id,
+                    promise_id: p2.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+        let _s3 = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T14:00:00Z").unwrap(),
+                ScheduledAction::CompletePromise {
+                    account_id,
+                    promise_id: p3.clone(),
+                    environment_id,
+                },
+            )
+            .await;
+
+        // The scheduler was not running for hours
+        svc.process(DateTime::from_str("2023-07-17T13:15:00Z").unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            promise_service.outcome(&p1).await,
+            Some(PromiseOutcome::Expired)
+        );
+        assert_eq!(
+            promise_service.outcome(&p2).await,
+            Some(PromiseOutcome::Completed(vec![]))
+        );
+        assert_eq!(promise_service.outcome(&p3).await, None);
+
+        // Only the bucket of the future action remains pending
+        assert_eq!(svc.pending_buckets().await.unwrap(), vec![469334]);
+    }
+
+    #[test]
+    pub async fn recurring_invocation_is_rescheduled_after_processing() {
+        let worker_id: WorkerId = WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "inst1".to_string(),
+        };
+        let environment_id = EnvironmentId::new();
+        let owned_worker_id = OwnedWorkerId::new(environment_id, &worker_id);
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = create_shard_service_mock();
+        let promise_service = create_promise_service_mock();
+        let worker_access = Arc::new(SchedulerWorkerAccessRecordingMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let golem_config = Arc::new(GolemConfig::default());
+        let worker_service = create_worker_service_mock(
+            kvs.clone(),
+            shard_service.clone(),
+            oplog_service.clone(),
+            golem_config,
+        );
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service,
+            worker_access.clone(),
+            oplog_service,
+            worker_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let account_id = AccountId::new();
+        let idempotency_key = IdempotencyKey::fresh();
+        let action = ScheduledAction::RecurringInvoke {
+            account_id,
+            owned_worker_id: owned_worker_id.clone(),
+            idempotency_key: idempotency_key.clone(),
+            full_function_name: "run".to_string(),
+            function_input: vec![],
+            invocation_context: InvocationContextStack::fresh(),
+            recurrence: ScheduleRecurrence::Interval(IntervalRecurrence {
+                interval_millis: 10 * 60 * 1000,
+                until: Some(Timestamp::from_str("2023-07-17T10:20:00Z").unwrap()),
+            }),
+        };
+
+        let first_occurrence: DateTime<Utc> = DateTime::from_str("2023-07-17T10:05:00Z").unwrap();
+        let schedule_id = svc.schedule(first_occurrence, action.clone()).await;
+
+        svc.process(DateTime::from_str("2023-07-17T10:12:00Z").unwrap())
+            .await
+            .unwrap();
+
+        // The first occurrence was invoked with a derived idempotency key
+        assert_eq!(
+            *worker_access.invocations.lock().unwrap(),
+            vec![(
+                owned_worker_id.clone(),
+                IdempotencyKey::derived_for_occurrence(
+                    &idempotency_key,
+                    first_occurrence.timestamp_millis()
+                ),
+                "run".to_string()
+            )]
+        );
+
+        // The next occurrence is aligned to the first one
+        let recurring = svc.get_recurring(&owned_worker_id).await.unwrap();
+        assert_eq!(recurring.len(), 1);
+        assert_eq!(recurring[0].idempotency_key, idempotency_key);
+        assert_eq!(
+            recurring[0].next_occurrence,
+            Timestamp::from_str("2023-07-17T10:15:00Z").unwrap()
+        );
+        assert_eq!(
+            non_empty_sorted_sets(&kvs).await,
+            HashMap::from([(
+                "Schedule/worker:schedule:469330".to_string(),
+                vec![(900000.0, serialized_bytes(&action))]
+            )])
+        );
+
+        svc.process(DateTime::from_str("2023-07-17T10:16:00Z").unwrap())
+            .await
+            .unwrap();
+
+        // The recurrence ended, so there are no more occurrences scheduled
+        assert_eq!(worker_access.invocations.lock().unwrap().len(), 2);
+        assert!(svc
+            .get_recurring(&owned_worker_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(non_empty_sorted_sets(&kvs).await.is_empty());
+
+        // Cancelling through the original schedule id is a no-op after the recurrence ended
+        svc.cancel(schedule_id).await;
+    }
+
+    #[test]
+    pub async fn cancel_recurring_removes_rescheduled_occurrence() {
+        let worker_id: WorkerId = WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "inst1".to_string(),
+        };
+        let environment_id = EnvironmentId::new();
+        let owned_worker_id = OwnedWorkerId::new(environment_id, &worker_id);
+
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+
+        let shard_service = create_shard_service_mock();
+        let promise_service = create_promise_service_mock();
+        let worker_access = Arc::new(SchedulerWorkerAccessRecordingMock::default());
+        let oplog_service = create_oplog_service_mock().await;
+        let golem_config = Arc::new(GolemConfig::default());
+        let worker_service = create_worker_service_mock(
+            kvs.clone(),
+            shard_service.clone(),
+            oplog_service.clone(),
+            golem_config,
+        );
+
+        let svc = SchedulerServiceDefault::new(
+            kvs.clone(),
+            shard_service,
+            promise_service,
+            worker_access.clone(),
+            oplog_service,
+            worker_service,
+            Duration::from_secs(1000), // explicitly calling process for testing
+        );
+
+        let account_id = AccountId::new();
+        let idempotency_key = IdempotencyKey::fresh();
+        let action = ScheduledAction::RecurringInvoke {
+            account_id,
+            owned_worker_id: owned_worker_id.clone(),
+            idempotency_key: idempotency_key.clone(),
+            full_function_name: "run".to_string(),
+            function_input: vec![],
+            invocation_context: InvocationContextStack::fresh(),
+            recurrence: ScheduleRecurrence::Interval(IntervalRecurrence {
+                interval_millis: 60 * 60 * 1000,
+                until: None,
+            }),
+        };
+
+        let schedule_id = svc
+            .schedule(
+                DateTime::from_str("2023-07-17T09:30:00Z").unwrap(),
+                action.clone(),
+            )
+            .await;
+
+        svc.process(DateTime::from_str("2023-07-17T10:00:00Z").unwrap())
+            .await
+            .unwrap();
+
+        // The next occurrence is in the next bucket
+        assert_eq!(
+            non_empty_sorted_sets(&kvs).await,
+            HashMap::from([(
+                "Schedule/worker:schedule:469330".to_string(),
+                vec![(1800000.0, serialized_bytes(&action))]
+            )])
+        );
+
+        // Cancelling with the original schedule id (pointing to the first bucket) still finds it
+        svc.cancel(schedule_id).await;
+
+        assert!(non_empty_sorted_sets(&kvs).await.is_empty());
+        assert!(svc
+            .get_recurring(&owned_worker_id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(!svc
+            .cancel_recurring(&owned_worker_id, &idempotency_key)
+            .await
+            .unwrap());
+    }
+
+    async fn non_empty_sorted_sets(
+        kvs: &InMemoryKeyValueStorage,
+    ) -> HashMap<String, Vec<(f64, Vec<u8>)>> {
+        let mut result = HashMap::new();
+        kvs.sorted_sets()
+            .iter_async(|key, entry| {
+                if !entry.is_empty() {
+                    result.insert(key.clone(), entry.clone());
+                }
+                true
+            })
+            .await;
+        result
+    }
 }
@@ -25,7 +25,8 @@ use async_trait::async_trait;
 use golem_common::model::agent::AgentMode;
 use golem_common::model::oplog::{OplogEntry, OplogIndex};
 use golem_common::model::{
-    OwnedWorkerId, ShardId, WorkerId, WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+    OwnedWorkerId, ShardId, WorkerId, WorkerMetadata, WorkerResourceUsageRecord, WorkerStatus,
+    WorkerStatusRecord,
 };
 use std::sync::Arc;
 use tracing::debug;
@@ -45,6 +46,12 @@ pub trait WorkerService: Send + Sync {
 
     async fn get_running_workers_in_shards(&self) -> Vec<GetWorkerMetadataResult>;
 
+    /// Registers a newly created worker in the index of all the workers of its shard
+    async fn add(&self, owned_worker_id: &OwnedWorkerId, agent_mode: AgentMode);
+
+    /// Gets all the durable workers of a shard created since the index of workers exists
+    async fn get_workers_in_shard(&self, shard_id: &ShardId) -> Vec<OwnedWorkerId>;
+
     async fn remove(&self, owned_worker_id: &OwnedWorkerId);
 
     async fn remove_cached_status(&self, owned_worker_id: &OwnedWorkerId);
@@ -110,6 +117,21 @@ impl DefaultWorkerService {
     fn running_in_shard_key(shard_id: &ShardId) -> String {
         format!("worker:running_in_shard:{shard_id}")
     }
+
+    fn known_in_shard_key(shard_id: &ShardId) -> String {
+        format!("worker:known_in_shard:{shard_id}")
+    }
+
+    fn shard_of(&self, owned_worker_id: &OwnedWorkerId) -> ShardId {
+        let shard_assignment = self
+            .shard_service
+            .current_assignment()
+            .expect("sharding assignment is not ready");
+        ShardId::from_worker_id(
+            &owned_worker_id.worker_id,
+            shard_assignment.number_of_shards,
+        )
+    }
 }
 
 #[async_trait]
@@ -159,6 +181,10 @@ impl WorkerService for DefaultWorkerService {
                         component_size,
                         total_linear_memory_size: initial_total_linear_memory_size,
                         active_plugins: initial_active_plugins,
+                        resource_usage: WorkerResourceUsageRecord {
+                            peak_linear_memory_size: initial_total_linear_memory_size,
+                            ..WorkerResourceUsageRecord::default()
+                        },
                         ..WorkerStatusRecord::default()
                     },
                     original_phantom_id,
@@ -221,20 +247,56 @@ impl WorkerService for DefaultWorkerService {
         result
     }
 
+    async fn add(&self, owned_worker_id: &OwnedWorkerId, agent_mode: AgentMode) {
+        record_worker_call("add");
+
+        if agent_mode != AgentMode::Ephemeral {
+            let shard_id = self.shard_of(owned_worker_id);
+            self.key_value_storage
+                .with_entity("worker", "add", "worker_id")
+                .add_to_set(
+                    KeyValueStorageNamespace::KnownWorkers,
+                    &Self::known_in_shard_key(&shard_id),
+                    owned_worker_id,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to add worker to the set of known workers per shard in KV storage: {err}")
+                });
+        }
+    }
+
+    async fn get_workers_in_shard(&self, shard_id: &ShardId) -> Vec<OwnedWorkerId> {
+        record_worker_call("enum_known");
+
+        self.key_value_storage
+            .with_entity("worker", "enum_known", "worker_id")
+            .members_of_set(
+                KeyValueStorageNamespace::KnownWorkers,
+                &Self::known_in_shard_key(shard_id),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("failed to get known worker ids from KV storage: {err}"))
+    }
+
     async fn remove(&self, owned_worker_id: &OwnedWorkerId) {
         record_worker_call("remove");
 
         self.oplog_service.delete(owned_worker_id).await;
         self.remove_cached_status(owned_worker_id).await;
 
-        let shard_assignment = self
-            .shard_service
-            .current_assignment()
-            .expect("sharding assigment is not ready");
-        let shard_id = ShardId::from_worker_id(
-            &owned_worker_id.worker_id,
-            shard_assignment.number_of_shards,
-        );
+        let shard_id = self.shard_of(owned_worker_id);
+
+        self
+            .key_value_storage
+            .with_entity("worker", "remove", "worker_id")
+            .remove_from_set(KeyValueStorageNamespace::KnownWorkers, &Self::known_in_shard_key(&shard_id), owned_worker_id)
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to remove worker from the set of known worker ids per shard in KV storage: {err}"
+                )
+            });
 
         self
             .key_value_storage
@@ -31,7 +31,7 @@ use golem_common::model::component::ComponentRevision;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::OplogIndex;
 use golem_common::model::worker::RevertWorkerTarget;
-use golem_common::model::{IdempotencyKey, OwnedWorkerId, PromiseId, WorkerId};
+use golem_common::model::{IdempotencyKey, OwnedWorkerId, PromiseId, PromiseOutcome, WorkerId};
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::grpc::client::GrpcClient;
 use golem_service_base::model::auth::AuthCtx;
@@ -110,10 +110,11 @@ pub trait WorkerProxy: Send + Sync {
         caller_account_id: AccountId,
     ) -> Result<(), WorkerProxyError>;
 
+    /// Completes or rejects a promise owned by an agent running on another executor
     async fn complete_promise(
         &self,
         promise_id: PromiseId,
-        data: Vec<u8>,
+        outcome: PromiseOutcome,
         caller_account_id: AccountId,
     ) -> Result<bool, WorkerProxyError>;
 }
@@ -533,11 +534,21 @@ impl WorkerProxy for RemoteWorkerProxy {
     async fn complete_promise(
         &self,
         promise_id: PromiseId,
-        data: Vec<u8>,
+        outcome: PromiseOutcome,
         caller_account_id: AccountId,
     ) -> Result<bool, WorkerProxyError> {
         let auth_ctx = self.get_auth_ctx(caller_account_id);
 
+        let (data, rejection) = match outcome {
+            PromiseOutcome::Completed(data) => (data, None),
+            PromiseOutcome::Rejected(error) => (vec![], Some(error)),
+            PromiseOutcome::Expired => {
+                return Err(WorkerProxyError::BadRequest(vec![
+                    "Promises can only be expired by the executor owning them".to_string(),
+                ]))
+            }
+        };
+
         let response: CompletePromiseResponse = self
             .worker_service_client
             .call("complete_promise", move |client| {
@@ -546,6 +557,7 @@ impl WorkerProxy for RemoteWorkerProxy {
                     complete_parameters: Some(CompleteParameters {
                         oplog_idx: promise_id.oplog_idx.into(),
                         data: data.clone(),
+                        rejection: rejection.clone(),
                     }),
                     auth_ctx: Some(auth_ctx.clone().into()),
                 }))
@@ -57,6 +57,17 @@ impl InMemoryIndexedStorage {
                     },
                 level,
             } => format!("compressed-oplog/{level}/{component_id}/{worker_name}/{key}"),
+            IndexedStorageNamespace::OplogDictionary { component_id } => {
+                format!("oplog-dictionary/{component_id}/{key}")
+            }
+            IndexedStorageNamespace::ReencryptedOpLog {
+                worker_id:
+                    WorkerId {
+                        component_id,
+                        worker_name,
+                    },
+                level,
+            } => format!("reencrypted-oplog/{level}/{component_id}/{worker_name}/{key}"),
         }
     }
 
@@ -325,6 +336,24 @@ impl IndexedStorage for InMemoryIndexedStorage {
             .await;
         Ok(())
     }
+
+    async fn drop_range(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let composite_key = Self::composite_key(namespace, key);
+        self.data
+            .update_async(&composite_key, |_, entry| {
+                entry.retain(|k, _| *k < first_dropped_id || *k > last_dropped_id);
+            })
+            .await;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -808,4 +837,30 @@ mod tests {
 
         check!(result == vec![(3, 300), (4, 400)]);
     }
+
+    #[test]
+    async fn drop_range() {
+        let storage = super::InMemoryIndexedStorage::new();
+        let api = storage.with_entity("test", "test", "test");
+        let key = "key";
+        let namespace = IndexedStorageNamespace::OpLog {
+            worker_id: test_worker_id(),
+        };
+
+        for id in 1..=5u64 {
+            api.append(namespace.clone(), key, id, &(id * 100))
+                .await
+                .unwrap();
+        }
+
+        storage
+            .with("test", "test")
+            .drop_range(namespace.clone(), key, 2, 4)
+            .await
+            .unwrap();
+
+        let result: Vec<(u64, u64)> = api.read(namespace, key, 1, 5).await.unwrap();
+
+        check!(result == vec![(1, 100), (5, 500)]);
+    }
 }
@@ -17,11 +17,13 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use desert_rust::{BinaryDeserializer, BinarySerializer};
+use golem_common::model::component::ComponentId;
 use golem_common::model::WorkerId;
 use golem_common::serialization::{deserialize, serialize};
 
 pub mod memory;
 pub mod multi_sqlite;
+pub mod postgres;
 pub mod redis;
 pub mod sqlite;
 
@@ -183,6 +185,18 @@ pub trait IndexedStorage: Debug + Sync {
         key: &str,
         last_dropped_id: u64,
     ) -> Result<(), String>;
+
+    /// Deletes the entries with ids in the closed range `first_dropped_id..=last_dropped_id`
+    /// from the index of the given key.
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String>;
 }
 
 pub trait IndexedStorageLabelledApi<T: IndexedStorage + ?Sized> {
@@ -307,6 +321,25 @@ impl<'a, S: ?Sized + IndexedStorage> LabelledIndexedStorage<'a, S> {
             )
             .await
     }
+
+    pub async fn drop_range(
+        &self,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        self.storage
+            .drop_range(
+                self.svc_name,
+                self.api_name,
+                namespace,
+                key,
+                first_dropped_id,
+                last_dropped_id,
+            )
+            .await
+    }
 }
 
 pub struct LabelledEntityIndexedStorage<'a, S: IndexedStorage + ?Sized> {
@@ -396,6 +429,25 @@ impl<'a, S: ?Sized + IndexedStorage> LabelledEntityIndexedStorage<'a, S> {
             .await
     }
 
+    /// Appends multiple entries to the given key with the given id
+    pub async fn append_many_raw(
+        &self,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        pairs: Vec<(u64, Vec<u8>)>,
+    ) -> Result<(), String> {
+        self.storage
+            .append_many(
+                self.svc_name,
+                self.api_name,
+                self.entity_name,
+                namespace,
+                key,
+                pairs,
+            )
+            .await
+    }
+
     /// Reads a closed range of entries from the index of the given key, deserializing each entry
     pub async fn read<V: BinaryDeserializer>(
         &self,
@@ -605,8 +657,21 @@ impl<'a, S: ?Sized + IndexedStorage> LabelledEntityIndexedStorage<'a, S> {
 /// Various namespaces for indexed storage
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub enum IndexedStorageNamespace {
-    OpLog { worker_id: WorkerId },
-    CompressedOpLog { worker_id: WorkerId, level: usize },
+    OpLog {
+        worker_id: WorkerId,
+    },
+    CompressedOpLog {
+        worker_id: WorkerId,
+        level: usize,
+    },
+    OplogDictionary {
+        component_id: ComponentId,
+    },
+    /// Re-encrypted chunks of a compressed oplog, stored until they replace the original ones
+    ReencryptedOpLog {
+        worker_id: WorkerId,
+        level: usize,
+    },
 }
 
 /// Various namespaces for operations working on multiple indexed storage namespaces such as scan
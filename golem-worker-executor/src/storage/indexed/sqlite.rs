@@ -64,6 +64,15 @@ impl SqliteIndexedStorage {
             } => {
                 format!("worker-c{level}-oplog")
             }
+            IndexedStorageNamespace::OplogDictionary { component_id: _ } => {
+                "component-oplog-dictionary".to_string()
+            }
+            IndexedStorageNamespace::ReencryptedOpLog {
+                worker_id: _,
+                level,
+            } => {
+                format!("worker-r{level}-oplog")
+            }
         }
     }
 
@@ -335,6 +344,31 @@ impl IndexedStorage for SqliteIndexedStorage {
             .map(|_| ())
             .map_err(|err| err.to_safe_string())
     }
+
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM index_storage WHERE namespace = ? AND key = ? AND id >= ? AND id <= ?;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(sqlx::types::Json(first_dropped_id))
+        .bind(sqlx::types::Json(last_dropped_id));
+
+        self.pool
+            .with_rw(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+            .map_err(|err| err.to_safe_string())
+    }
 }
 
 #[derive(sqlx::FromRow, Debug)]
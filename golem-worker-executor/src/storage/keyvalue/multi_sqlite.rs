@@ -110,6 +110,8 @@ impl MultiSqliteKeyValueStorage {
             }
             KeyValueStorageNamespace::Schedule => "kv-schedule.db".to_string(),
             KeyValueStorageNamespace::UserDefined { .. } => "kv-user-defined.db".to_string(),
+            KeyValueStorageNamespace::BucketWatchers => "kv-bucket-watchers.db".to_string(),
+            KeyValueStorageNamespace::KnownWorkers => "kv-known-workers.db".to_string(),
         }
     }
 
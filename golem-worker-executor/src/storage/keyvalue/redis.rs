@@ -41,6 +41,8 @@ impl RedisKeyValueStorage {
                 environment_id,
                 bucket,
             } => Some(format!("user-defined:{environment_id}:{bucket}")),
+            KeyValueStorageNamespace::BucketWatchers => None,
+            KeyValueStorageNamespace::KnownWorkers => None,
         }
     }
 }
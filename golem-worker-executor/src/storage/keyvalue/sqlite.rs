@@ -103,6 +103,8 @@ impl SqliteKeyValueStorage {
                 format!("user-defined:{environment_id}:{bucket}")
             }
             KeyValueStorageNamespace::RunningWorkers => "running-workers".to_string(),
+            KeyValueStorageNamespace::BucketWatchers => "bucket-watchers".to_string(),
+            KeyValueStorageNamespace::KnownWorkers => "known-workers".to_string(),
         }
     }
 }
@@ -12,5 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::Context;
+use golem_common::config::{DbPostgresConfig, DbSqliteConfig};
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use include_dir::include_dir;
+
 pub mod indexed;
 pub mod keyvalue;
+
+static DB_MIGRATIONS: include_dir::Dir = include_dir!("$CARGO_MANIFEST_DIR/db/migration");
+
+/// Applies the schema migrations required by the Postgres key-value and indexed storages
+pub async fn migrate_postgres(config: &DbPostgresConfig) -> anyhow::Result<()> {
+    let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
+    golem_service_base::db::postgres::migrate(config, migrations.postgres_migrations())
+        .await
+        .context("Postgres DB migration")
+}
+
+/// Applies the schema migrations required by the SQLite oplog search index
+pub async fn migrate_sqlite(config: &DbSqliteConfig) -> anyhow::Result<()> {
+    let migrations = IncludedMigrationsDir::new(&DB_MIGRATIONS);
+    golem_service_base::db::sqlite::migrate(config, migrations.sqlite_migrations())
+        .await
+        .context("SQLite DB migration")
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::durable_host::keyvalue::handle_watch;
 use crate::metrics::wasm::{record_invocation, record_invocation_consumption};
 use crate::model::TrapType;
 use crate::virtual_export_compat;
@@ -140,6 +141,9 @@ fn find_function<'a, Ctx: WorkerCtx>(
                 .and_then(|(_, idx)| instance.get_func(&mut store, idx));
 
             match func {
+                Some(func) if interface_name == handle_watch::INTERFACE_NAME => {
+                    Ok(FindFunctionResult::KeyValueWatchHandler(func))
+                }
                 Some(func) => Ok(FindFunctionResult::ExportedFunction(func)),
                 None => match parsed_function_name.method_as_static() {
                     None => Err(WorkerExecutorError::invalid_request(format!(
@@ -218,7 +222,8 @@ async fn invoke_observed<Ctx: WorkerCtx>(
     verify_agent_invocation(store.data().agent_id(), &metadata)?;
 
     let call_result = match function {
-        FindFunctionResult::ExportedFunction(function) => {
+        FindFunctionResult::ExportedFunction(function)
+        | FindFunctionResult::KeyValueWatchHandler(function) => {
             invoke(
                 &mut store,
                 function,
@@ -337,6 +342,9 @@ async fn validate_function_parameters(
             Ok(vec![])
         }
         FindFunctionResult::IncomingHttpHandlerBridge => Ok(vec![]),
+        FindFunctionResult::KeyValueWatchHandler(_) => {
+            handle_watch::decode_handler_params(store, raw_function_name, function_input)
+        }
     }
 }
 
@@ -670,4 +678,5 @@ enum FindFunctionResult {
     ExportedFunction(Func),
     ResourceDrop,
     IncomingHttpHandlerBridge,
+    KeyValueWatchHandler(Func),
 }
@@ -14,8 +14,9 @@
 
 use crate::model::{ReadFileResult, TrapType};
 use crate::services::events::Event;
+use crate::services::golem_config::AgentQuotas;
 use crate::services::oplog::{CommitLevel, OplogOps};
-use crate::services::{HasEvents, HasOplog, HasWorker};
+use crate::services::{HasConfig, HasEvents, HasOplog, HasWorker};
 use crate::worker::invocation::{invoke_observed_and_traced, InvokeResult};
 use crate::worker::{
     interpret_function_result, QueuedWorkerInvocation, RetryDecision, RunningWorker, Worker,
@@ -29,13 +30,14 @@ use futures::channel::oneshot;
 use futures::channel::oneshot::Sender;
 use golem_common::model::agent::{AgentId, AgentMode};
 use golem_common::model::component::{ComponentFilePath, ComponentRevision};
-use golem_common::model::oplog::WorkerError;
+use golem_common::model::oplog::{LogLevel, OplogEntry, WorkerError};
 use golem_common::model::{
     invocation_context::{AttributeValue, InvocationContextStack},
     OplogIndex,
 };
 use golem_common::model::{
-    IdempotencyKey, OwnedWorkerId, TimestampedWorkerInvocation, WorkerId, WorkerInvocation,
+    IdempotencyKey, OwnedWorkerId, Timestamp, TimestampedWorkerInvocation, WorkerId,
+    WorkerInvocation,
 };
 use golem_common::retries::get_delay;
 use golem_service_base::error::worker_executor::{InterruptKind, WorkerExecutorError};
@@ -52,6 +54,9 @@ use tracing::{debug, span, warn, Instrument, Level, Span};
 use wasmtime::component::Instance;
 use wasmtime::{AsContext, Store};
 
+/// Context of the oplog log entries explaining why an agent was throttled
+const AGENT_QUOTA_LOG_CONTEXT: &str = "agent-quota";
+
 /// Context of a running worker's invocation loop
 pub struct InvocationLoop<Ctx: WorkerCtx> {
     pub receiver: UnboundedReceiver<WorkerCommand>,
@@ -307,6 +312,10 @@ impl<Ctx: WorkerCtx> InnerInvocationLoop<'_, Ctx> {
                     break;
                 }
                 CommandOutcome::Continue => {}
+                CommandOutcome::Wait => {
+                    // Postponed invocations are still pending, so the worker is not idle
+                    continue;
+                }
             }
 
             self.waiting_for_command.store(true, Ordering::Release);
@@ -450,6 +459,11 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                 let _ = sender.send(Ok(()));
                 CommandOutcome::Continue
             }
+            QueuedWorkerInvocation::TakeSnapshot { sender } => {
+                let result = self.take_snapshot().await;
+                let _ = sender.send(Ok(result));
+                CommandOutcome::Continue
+            }
         }
     }
 
@@ -536,6 +550,20 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         full_function_name: String,
         function_input: Vec<Value>,
     ) -> CommandOutcome {
+        let quotas = self
+            .parent
+            .agent_quotas(self.store.data().component_metadata());
+        if let Some(outcome) = self
+            .enforce_quotas_before_invocation(&quotas, &idempotency_key, &full_function_name)
+            .await
+        {
+            return outcome;
+        }
+
+        // The fuel quota is enforced by the epoch deadline callback while the invocation runs
+        self.parent
+            .invocation_fuel()
+            .start(self.store.get_fuel().unwrap_or(0));
         let result = self
             .invoke_exported_function_with_context(
                 invocation_context,
@@ -544,6 +572,7 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                 &function_input,
             )
             .await;
+        self.parent.invocation_fuel().finish();
 
         match result {
             Ok(InvokeResult::Succeeded {
@@ -565,6 +594,105 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         }
     }
 
+    /// Checks the agent quotas that have to hold before starting an invocation.
+    ///
+    /// Invocations above the allowed rate are postponed until the one minute window allows them,
+    /// recording the throttling in the oplog. The invocation loop is not blocked meanwhile, so it
+    /// still processes interrupts and internal commands. If the oplog already grew beyond its
+    /// allowed size, the invocation fails the agent.
+    async fn enforce_quotas_before_invocation(
+        &mut self,
+        quotas: &AgentQuotas,
+        idempotency_key: &IdempotencyKey,
+        full_function_name: &str,
+    ) -> Option<CommandOutcome> {
+        if quotas.is_empty() {
+            return None;
+        }
+
+        if let Some(max_oplog_entries) = quotas.max_oplog_entries {
+            let oplog_entries = u64::from(self.parent.oplog().current_oplog_index().await);
+            if oplog_entries > max_oplog_entries {
+                return Some(
+                    self.fail_on_exceeded_quota(
+                        idempotency_key,
+                        full_function_name,
+                        format!(
+                            "The agent's oplog has {oplog_entries} entries, exceeding its quota of {max_oplog_entries} entries"
+                        ),
+                    )
+                    .await,
+                );
+            }
+        }
+
+        if let Some(max_invocations) = quotas.max_invocations_per_minute {
+            if max_invocations == 0 {
+                return Some(
+                    self.fail_on_exceeded_quota(
+                        idempotency_key,
+                        full_function_name,
+                        "The agent's quota does not allow any invocations".to_string(),
+                    )
+                    .await,
+                );
+            }
+
+            if let Err(throttled) = self
+                .parent
+                .try_start_rate_limited_invocation(max_invocations)
+            {
+                if !throttled.already_throttled {
+                    let message = format!(
+                        "Invocation of {full_function_name} is delayed by {:?} as the agent exceeded its quota of {max_invocations} invocations per minute",
+                        throttled.delay
+                    );
+                    warn!("{message}");
+                    self.parent
+                        .add_and_commit_oplog(OplogEntry::Log {
+                            timestamp: Timestamp::now_utc(),
+                            level: LogLevel::Warn,
+                            context: AGENT_QUOTA_LOG_CONTEXT.to_string(),
+                            message,
+                        })
+                        .await;
+                }
+                // The invocation stays pending, and is retried when the loop gets unblocked
+                self.parent.unblock_after(throttled.delay);
+                return Some(CommandOutcome::Wait);
+            }
+        }
+
+        None
+    }
+
+    /// Fails the current invocation and the agent because it violated one of its quotas.
+    /// The reason is recorded in the oplog's error entry.
+    async fn fail_on_exceeded_quota(
+        &mut self,
+        idempotency_key: &IdempotencyKey,
+        full_function_name: &str,
+        details: String,
+    ) -> CommandOutcome {
+        warn!("Agent quota exceeded: {details}");
+        // The failure has to be associated with the rejected invocation
+        self.store
+            .data_mut()
+            .set_current_idempotency_key(idempotency_key.clone())
+            .await;
+        self.store
+            .data_mut()
+            .on_invocation_failure(
+                full_function_name,
+                &TrapType::Error {
+                    error: WorkerError::QuotaExceeded(details),
+                    retry_from: OplogIndex::INITIAL,
+                },
+            )
+            .await;
+        CommandOutcome::BreakInnerLoop(RetryDecision::None)
+    }
+
     /// Sets the necessary contextual information on the worker and performs the actual
     /// invocation.
     async fn invoke_exported_function_with_context(
@@ -747,6 +875,7 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                         Ok(CommandOutcome::BreakInnerLoop(RetryDecision::None))
                     }
                 } else {
+                    self.take_automatic_snapshot_if_needed().await;
                     Ok(CommandOutcome::Continue)
                 }
             }
@@ -929,6 +1058,118 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         }
     }
 
+    /// Takes an automatic snapshot of the worker's state if the number of oplog entries or the time
+    /// passed since the last snapshot exceeds the intervals configured in `OplogConfig`.
+    ///
+    /// Failing to take a snapshot does not affect the invocation, recovery just has to replay more entries.
+    async fn take_automatic_snapshot_if_needed(&mut self) {
+        let config = self.parent.config();
+        let entry_interval = config.oplog.snapshot_entry_interval;
+        let time_interval = config.oplog.snapshot_time_interval;
+        if entry_interval.is_none() && time_interval.is_none() {
+            return;
+        }
+
+        let status = self.parent.last_known_status.read().await.clone();
+        let (last_index, last_timestamp) = match &status.last_snapshot {
+            Some(snapshot) => (snapshot.oplog_index, snapshot.timestamp),
+            None => (
+                OplogIndex::INITIAL,
+                self.parent.initial_worker_metadata.created_at,
+            ),
+        };
+        let current_index = self.parent.oplog().current_oplog_index().await;
+
+        let entry_interval_passed = entry_interval.is_some_and(|interval| {
+            u64::from(current_index).saturating_sub(u64::from(last_index)) >= interval
+        });
+        let time_interval_passed = time_interval.is_some_and(|interval| {
+            Timestamp::now_utc()
+                .to_millis()
+                .saturating_sub(last_timestamp.to_millis())
+                >= interval.as_millis() as u64
+        });
+        if !entry_interval_passed && !time_interval_passed {
+            return;
+        }
+
+        debug!("Taking automatic snapshot at oplog index {current_index}");
+        self.take_snapshot().await;
+    }
+
+    /// Saves a snapshot of the worker's state to the oplog, if the component supports it and the
+    /// worker's state can be restored from it. Returns whether a snapshot was stored.
+    async fn take_snapshot(&mut self) -> bool {
+        let status = self.parent.last_known_status.read().await.clone();
+        if !status.owned_resources.is_empty() || !status.pending_updates.is_empty() {
+            // Resource handles cannot be restored from a snapshot, and pending updates would drop it anyway
+            return false;
+        }
+
+        let component_metadata = self.store.data().component_metadata().metadata.clone();
+        let Ok(Some(save_snapshot)) = component_metadata.save_snapshot() else {
+            // The component does not support snapshotting
+            return false;
+        };
+
+        self.store
+            .data_mut()
+            .set_current_idempotency_key(IdempotencyKey::fresh())
+            .await;
+        self.store.data_mut().begin_call_snapshotting_function();
+
+        let result = invoke_observed_and_traced(
+            save_snapshot.name.to_string(),
+            vec![],
+            self.store,
+            self.instance,
+            &component_metadata,
+            true,
+        )
+        .await;
+        self.store.data_mut().end_call_snapshotting_function();
+
+        match result {
+            Ok(InvokeResult::Succeeded { output, .. }) => {
+                if let Some(bytes) = Self::decode_snapshot_result(output) {
+                    let oplog = self.store.data().get_public_state().oplog();
+                    match oplog.add_snapshot(&bytes).await {
+                        Ok(_) => {
+                            self.parent
+                                .commit_oplog_and_update_state(CommitLevel::Always)
+                                .await;
+                            self.parent.enable_snapshot_recovery();
+                            return true;
+                        }
+                        Err(error) => {
+                            warn!("Failed to store snapshot: {error}");
+                        }
+                    }
+                } else {
+                    warn!("Failed to take snapshot: invalid snapshot result");
+                }
+            }
+            Ok(InvokeResult::Failed { error, .. }) => {
+                let stderr = self
+                    .store
+                    .data()
+                    .get_public_state()
+                    .event_service()
+                    .get_last_invocation_errors();
+                let error = error.to_string(&stderr);
+                warn!("Failed to take snapshot: {error}");
+            }
+            Ok(other) => {
+                warn!("Failed to take snapshot: {other:?}");
+            }
+            Err(error) => {
+                warn!("Failed to take snapshot: {error:?}");
+            }
+        }
+
+        false
+    }
+
     /// Performs a directory listing command on the worker's file system
     ///
     /// These are threaded through the invocation loop to make sure they are not accessing the file system concurrently with invocations
@@ -1049,4 +1290,7 @@ enum CommandOutcome {
     BreakInnerLoop(RetryDecision),
     /// Continue processing in the inner loop
     Continue,
+    /// Stop processing the queue and the pending invocations until the next command arrives,
+    /// without the worker being considered idle
+    Wait,
 }
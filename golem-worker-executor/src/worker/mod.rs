@@ -14,6 +14,7 @@
 
 pub mod invocation;
 mod invocation_loop;
+mod quota;
 pub mod status;
 
 use self::status::{
@@ -22,6 +23,7 @@ use self::status::{
 use crate::durable_host::recover_stderr_logs;
 use crate::model::{ExecutionStatus, LookupResult, ReadFileResult, TrapType, WorkerConfig};
 use crate::services::events::{Event, EventsSubscription};
+use crate::services::golem_config::AgentQuotas;
 use crate::services::oplog::{CommitLevel, Oplog, OplogOps};
 use crate::services::worker::GetWorkerMetadataResult;
 use crate::services::worker_event::{WorkerEventService, WorkerEventServiceDefault};
@@ -33,6 +35,7 @@ use crate::services::{
     HasWorkerForkService, HasWorkerProxy, HasWorkerService, UsesAllDeps,
 };
 use crate::worker::invocation_loop::InvocationLoop;
+use crate::worker::quota::{InvocationFuel, InvocationRateWindow, Throttled};
 use crate::worker::status::calculate_last_known_status;
 use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
@@ -40,15 +43,15 @@ use futures::channel::oneshot;
 use golem_common::model::account::AccountId;
 use golem_common::model::agent::{AgentId, AgentMode};
 use golem_common::model::component::ComponentRevision;
-use golem_common::model::component::{ComponentFilePath, PluginPriority};
+use golem_common::model::component::{ComponentDto, ComponentFilePath, PluginPriority};
 use golem_common::model::invocation_context::InvocationContextStack;
-use golem_common::model::oplog::{OplogEntry, OplogIndex, UpdateDescription};
+use golem_common::model::oplog::{OplogEntry, OplogIndex, UpdateDescription, WorkerError};
 use golem_common::model::regions::OplogRegion;
 use golem_common::model::worker::RevertWorkerTarget;
 use golem_common::model::RetryConfig;
 use golem_common::model::{
     IdempotencyKey, OwnedWorkerId, Timestamp, TimestampedWorkerInvocation, WorkerId,
-    WorkerInvocation, WorkerMetadata, WorkerStatusRecord,
+    WorkerInvocation, WorkerMetadata, WorkerResourceUsageRecord, WorkerStatusRecord,
 };
 use golem_common::one_shot::OneShotEvent;
 use golem_common::read_only_lock;
@@ -58,7 +61,7 @@ use golem_service_base::error::worker_executor::{
 use golem_service_base::model::GetFileSystemNodeResult;
 use golem_wasm::analysis::AnalysedFunctionResult;
 use golem_wasm::{IntoValue, Value, ValueAndType};
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -110,6 +113,15 @@ pub struct Worker<Ctx: WorkerCtx> {
     oom_retry_config: RetryConfig,
 
     last_resume_request: Mutex<Timestamp>,
+
+    // Set when loading the latest automatic snapshot failed, forcing a full replay until the next snapshot
+    snapshot_recovery_disabled: AtomicBool,
+
+    // Invocations started in the last minute, for enforcing the per-agent invocation rate quota
+    invocation_rate_window: std::sync::Mutex<InvocationRateWindow>,
+
+    // Fuel level at the start of the running invocation, for enforcing the per-agent fuel quota
+    invocation_fuel: Arc<InvocationFuel>,
 }
 
 impl<Ctx: WorkerCtx> HasOplog for Worker<Ctx> {
@@ -212,6 +224,59 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// Interrupts the worker if it is running and deletes all its persisted state.
+    /// Does nothing if the worker does not exist.
+    pub async fn delete<T>(
+        deps: &T,
+        account_id: AccountId,
+        owned_worker_id: &OwnedWorkerId,
+    ) -> Result<(), WorkerExecutorError>
+    where
+        T: HasAll<Ctx> + Clone + Send + Sync + 'static,
+    {
+        if Self::get_latest_metadata(deps, owned_worker_id)
+            .await
+            .is_some()
+        {
+            let worker = Self::get_or_create_suspended(
+                deps,
+                account_id,
+                owned_worker_id,
+                None,
+                None,
+                None,
+                None,
+                &InvocationContextStack::fresh(),
+            )
+            .await?;
+
+            info!("Interrupting worker before deletion");
+            if let Some(mut rx) = worker
+                .set_interrupting(InterruptKind::Interrupt(Timestamp::now_utc()))
+                .await
+            {
+                info!("Awaiting interruption");
+                let _ = rx.recv().await;
+                info!("Interrupted");
+            }
+            info!("Marking worker for deletion");
+            worker.start_deleting().await?;
+
+            deps.worker_service().remove(owned_worker_id).await;
+            deps.active_workers()
+                .remove(&owned_worker_id.worker_id)
+                .await;
+            if let Err(err) = deps.key_value_service().unwatch_all(owned_worker_id).await {
+                warn!("Failed to remove the key-value watches of the deleted worker: {err}");
+            }
+
+            // ensure we are holding the worker while we are doing cleanup.
+            drop(worker);
+        }
+
+        Ok(())
+    }
+
     pub async fn new<T: HasAll<Ctx>>(
         deps: &T,
         account_id: &AccountId,
@@ -289,6 +354,9 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             update_state_lock: Mutex::new(()),
             last_known_status_detached: AtomicBool::new(false),
             last_resume_request: Mutex::new(Timestamp::now_utc()),
+            snapshot_recovery_disabled: AtomicBool::new(false),
+            invocation_rate_window: std::sync::Mutex::new(InvocationRateWindow::default()),
+            invocation_fuel: Arc::new(InvocationFuel::default()),
         };
 
         // just some sanity checking
@@ -723,6 +791,52 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         self.execution_status.read().unwrap().agent_mode()
     }
 
+    /// Makes the following recoveries of this worker replay the full oplog instead of starting
+    /// from the latest automatic snapshot. Used when loading the snapshot failed.
+    pub fn disable_snapshot_recovery(&self) {
+        self.snapshot_recovery_disabled
+            .store(true, Ordering::Release);
+    }
+
+    /// Re-enables starting the recovery from the latest automatic snapshot, called when a new one is taken
+    pub fn enable_snapshot_recovery(&self) {
+        self.snapshot_recovery_disabled
+            .store(false, Ordering::Release);
+    }
+
+    /// The per-agent quotas applying to this worker, based on the environment of its component
+    pub fn agent_quotas(&self, component: &ComponentDto) -> AgentQuotas {
+        quota::agent_quotas(&component.env, &self.config().agent_quotas.default_quotas)
+    }
+
+    /// Registers the start of an invocation if it fits in the `max_per_minute` invocation rate,
+    /// otherwise returns how long the invocation has to be delayed.
+    pub fn try_start_rate_limited_invocation(&self, max_per_minute: u32) -> Result<(), Throttled> {
+        self.invocation_rate_window
+            .lock()
+            .unwrap()
+            .try_start(std::time::Instant::now(), max_per_minute)
+    }
+
+    /// The fuel level tracking of the running invocation, for enforcing the fuel quota
+    pub fn invocation_fuel(&self) -> &InvocationFuel {
+        &self.invocation_fuel
+    }
+
+    /// Wakes up the invocation loop after the given delay, so it retries processing the
+    /// invocations it postponed
+    pub fn unblock_after(self: &Arc<Self>, delay: Duration) {
+        let worker = Arc::downgrade(self);
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Some(worker) = worker.upgrade() {
+                if let WorkerInstance::Running(running) = &*worker.instance.lock().await {
+                    let _ = running.sender.send(WorkerCommand::Unblock);
+                }
+            }
+        });
+    }
+
     /// Gets the estimated memory requirement of the worker
     pub async fn memory_requirement(&self) -> Result<u64, WorkerExecutorError> {
         let metadata = self.get_latest_worker_metadata().await;
@@ -924,6 +1038,54 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         receiver.await.unwrap()
     }
 
+    /// Takes a snapshot of the worker's state through the invocation loop, if the component supports
+    /// snapshotting. Returns whether a snapshot was stored.
+    pub async fn take_snapshot(&self) -> Result<bool, WorkerExecutorError> {
+        let instance_guard = self.lock_non_stopping_worker().await;
+
+        if !matches!(&*instance_guard, WorkerInstance::Running(_)) {
+            return Ok(false);
+        };
+
+        let (sender, receiver) = oneshot::channel();
+
+        self.queue
+            .write()
+            .await
+            .push_back(QueuedWorkerInvocation::TakeSnapshot { sender });
+
+        if let WorkerInstance::Running(running) = &*instance_guard {
+            running.sender.send(WorkerCommand::Unblock).unwrap();
+        };
+
+        drop(instance_guard);
+
+        receiver.await.unwrap()
+    }
+
+    /// Waits until the worker has no running or pending invocations and stops it.
+    ///
+    /// Returns false if the worker did not become idle until the deadline, in which case it is left running.
+    pub async fn stop_when_idle(&self, deadline: tokio::time::Instant) -> bool {
+        loop {
+            if matches!(
+                &*self.instance.lock().await,
+                WorkerInstance::Unloaded | WorkerInstance::Deleting
+            ) {
+                return true;
+            }
+            if self.stop_if_idle().await {
+                return true;
+            }
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            tokio::time::sleep((deadline - now).min(Duration::from_millis(100))).await;
+        }
+    }
+
     // Should only be called from invocation loop
     pub async fn add_to_oplog(&self, entry: OplogEntry) -> OplogIndex {
         self.oplog.add(entry).await
@@ -1030,6 +1192,29 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         Ok(())
     }
 
+    pub async fn change_labels(
+        &self,
+        set: BTreeMap<String, String>,
+        removed: BTreeSet<String>,
+    ) -> Result<(), WorkerExecutorError> {
+        let instance_guard = self.lock_non_stopping_worker().await;
+
+        if instance_guard.is_deleting() {
+            return Err(WorkerExecutorError::invalid_request(
+                "Cannot change labels of a deleting worker",
+            ));
+        };
+
+        self.add_and_commit_oplog_internal(
+            &instance_guard,
+            OplogEntry::change_labels(set, removed),
+        )
+        .await;
+
+        drop(instance_guard);
+        Ok(())
+    }
+
     /// Reverts the worker to a previous state, selected by either the last oplog index to keep
     /// or the number of invocations to drop.
     ///
@@ -1214,6 +1399,20 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                         }),
                     ..
                 } => LookupResult::Pending,
+                InvocationResult::Cached {
+                    result:
+                        Err(FailedInvocationResult {
+                            trap_type:
+                                TrapType::Error {
+                                    error: WorkerError::QuotaExceeded(details),
+                                    ..
+                                },
+                            ..
+                        }),
+                    ..
+                } => {
+                    LookupResult::Complete(Err(WorkerExecutorError::AgentQuotaExceeded { details }))
+                }
                 InvocationResult::Cached {
                     result:
                         Err(FailedInvocationResult {
@@ -1369,6 +1568,9 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                 QueuedWorkerInvocation::AwaitReadyToProcessCommands { sender } => {
                     let _ = sender.send(Err(error.clone()));
                 }
+                QueuedWorkerInvocation::TakeSnapshot { sender } => {
+                    let _ = sender.send(Err(error.clone()));
+                }
             }
         }
 
@@ -1565,17 +1767,23 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                 let worker_env = merge_worker_env_with_component_env(worker_env, component.env);
                 let created_at = Timestamp::now_utc();
 
+                let total_linear_memory_size: u64 = component
+                    .metadata
+                    .memories()
+                    .iter()
+                    .map(|m| m.initial)
+                    .sum();
+
                 // Note: Keep this in sync with the logic in crate::services::worker::WorkerService::get
                 let initial_status = WorkerStatusRecord {
                     component_revision: component.revision,
                     component_revision_for_replay: component.revision,
                     component_size: component.component_size,
-                    total_linear_memory_size: component
-                        .metadata
-                        .memories()
-                        .iter()
-                        .map(|m| m.initial)
-                        .sum(),
+                    total_linear_memory_size,
+                    resource_usage: WorkerResourceUsageRecord {
+                        peak_linear_memory_size: total_linear_memory_size,
+                        ..Default::default()
+                    },
                     active_plugins: component
                         .installed_plugins
                         .iter()
@@ -1634,6 +1842,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
                 initial_status.write().await.oplog_idx = oplog.current_oplog_index().await;
 
+                this.worker_service().add(owned_worker_id, agent_mode).await;
                 this.worker_service()
                     .update_cached_status(
                         owned_worker_id,
@@ -1990,21 +2199,49 @@ impl RunningWorker {
             }?
         };
 
-        let component_version_for_replay = worker_metadata
-            .last_known_status
-            .pending_updates
-            .front()
-            .and_then(|update| match update.description {
-                UpdateDescription::SnapshotBased {
-                    target_revision, ..
-                } => Some(target_revision),
-                _ => None,
-            })
-            .unwrap_or(
-                worker_metadata
-                    .last_known_status
-                    .component_revision_for_replay,
+        // The latest automatic snapshot is only used if automatic snapshots are enabled, there are
+        // no pending updates to apply, and the last attempt to load it did not fail
+        let golem_config = parent.config();
+        let snapshots_enabled = golem_config.oplog.snapshot_entry_interval.is_some()
+            || golem_config.oplog.snapshot_time_interval.is_some();
+        let snapshot = if snapshots_enabled
+            && pending_update.is_none()
+            && parent.agent_mode() == AgentMode::Durable
+            && !parent.snapshot_recovery_disabled.load(Ordering::Acquire)
+        {
+            worker_metadata.last_known_status.last_snapshot.clone()
+        } else {
+            None
+        };
+
+        let mut skipped_regions = worker_metadata.last_known_status.skipped_regions;
+        let component_version_for_replay = if let Some(snapshot) = &snapshot {
+            debug!(
+                "Starting replay from the snapshot taken at oplog index {}",
+                snapshot.oplog_index
             );
+            skipped_regions.add(OplogRegion::from_index_range(
+                OplogIndex::INITIAL.next()..=snapshot.oplog_index,
+            ));
+            // Snapshots are dropped on successful updates, so they always belong to the current revision
+            worker_metadata.last_known_status.component_revision
+        } else {
+            worker_metadata
+                .last_known_status
+                .pending_updates
+                .front()
+                .and_then(|update| match update.description {
+                    UpdateDescription::SnapshotBased {
+                        target_revision, ..
+                    } => Some(target_revision),
+                    _ => None,
+                })
+                .unwrap_or(
+                    worker_metadata
+                        .last_known_status
+                        .component_revision_for_replay,
+                )
+        };
 
         let context = Ctx::create(
             worker_metadata.created_by,
@@ -2028,11 +2265,12 @@ impl RunningWorker {
             parent.extra_deps(),
             parent.config(),
             WorkerConfig::new(
-                worker_metadata.last_known_status.skipped_regions,
+                skipped_regions,
                 worker_metadata.last_known_status.total_linear_memory_size,
                 component_version_for_replay,
                 worker_metadata.created_by,
                 worker_metadata.wasi_config_vars,
+                snapshot.map(|snapshot| snapshot.oplog_index),
             ),
             parent.execution_status.clone(),
             parent.file_loader(),
@@ -2048,9 +2286,26 @@ impl RunningWorker {
         let engine = parent.engine();
         let mut store = Store::new(&engine, context);
 
+        let max_fuel_per_invocation = parent
+            .agent_quotas(&component_metadata)
+            .max_fuel_per_invocation;
+        let invocation_fuel = parent.invocation_fuel.clone();
+
         store.set_epoch_deadline(parent.config().limits.epoch_ticks);
         store.epoch_deadline_callback(move |mut store| {
             let current_level = store.get_fuel().unwrap_or(0);
+            if let Some(max_fuel) = max_fuel_per_invocation {
+                if let Some(consumed) = invocation_fuel.exceeded(current_level, max_fuel) {
+                    warn!("Invocation consumed {consumed} fuel, exceeding the agent's fuel quota");
+                    return Err(WorkerExecutorError::AgentQuotaExceeded {
+                        details: format!(
+                            "Invocation consumed {consumed} fuel, exceeding the agent's quota of {max_fuel} fuel per invocation"
+                        ),
+                    }
+                    .into());
+                }
+            }
+
             let data_mut = store.data_mut();
             if !data_mut.borrow_fuel(current_level) {
                 warn!("Could not borrow more fuel, suspending");
@@ -2213,6 +2468,10 @@ pub enum QueuedWorkerInvocation {
     AwaitReadyToProcessCommands {
         sender: oneshot::Sender<Result<(), WorkerExecutorError>>,
     },
+    // Takes a snapshot of the worker's state, the sender is called with whether a snapshot was stored.
+    TakeSnapshot {
+        sender: oneshot::Sender<Result<bool, WorkerExecutorError>>,
+    },
 }
 
 pub enum ResultOrSubscription {
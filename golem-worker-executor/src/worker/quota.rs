@@ -0,0 +1,224 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::golem_config::AgentQuotas;
+use std::collections::{BTreeMap, VecDeque};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Environment variable of a component setting the `max_fuel_per_invocation` quota of its agents
+pub const MAX_FUEL_PER_INVOCATION_ENV_VAR: &str = "GOLEM_QUOTA_MAX_FUEL_PER_INVOCATION";
+/// Environment variable of a component setting the `max_invocations_per_minute` quota of its agents
+pub const MAX_INVOCATIONS_PER_MINUTE_ENV_VAR: &str = "GOLEM_QUOTA_MAX_INVOCATIONS_PER_MINUTE";
+/// Environment variable of a component setting the `max_oplog_entries` quota of its agents
+pub const MAX_OPLOG_ENTRIES_ENV_VAR: &str = "GOLEM_QUOTA_MAX_OPLOG_ENTRIES";
+
+/// Resolves the quotas of the agents of a component. The quotas set in the environment of the
+/// component, which is part of its deployment, take precedence over the executor's defaults.
+/// Values which are not valid numbers are ignored.
+pub fn agent_quotas(
+    component_env: &BTreeMap<String, String>,
+    defaults: &AgentQuotas,
+) -> AgentQuotas {
+    AgentQuotas {
+        max_fuel_per_invocation: env_quota(component_env, MAX_FUEL_PER_INVOCATION_ENV_VAR),
+        max_invocations_per_minute: env_quota(component_env, MAX_INVOCATIONS_PER_MINUTE_ENV_VAR),
+        max_oplog_entries: env_quota(component_env, MAX_OPLOG_ENTRIES_ENV_VAR),
+    }
+    .or(defaults.clone())
+}
+
+fn env_quota<T: FromStr>(component_env: &BTreeMap<String, String>, name: &str) -> Option<T> {
+    let value = component_env.get(name)?;
+    match value.trim().parse() {
+        Ok(quota) => Some(quota),
+        Err(_) => {
+            warn!("Ignoring invalid agent quota {name}={value}");
+            None
+        }
+    }
+}
+
+/// Sliding one minute window of the invocations started by a worker, used for enforcing
+/// the `max_invocations_per_minute` agent quota.
+#[derive(Debug, Default)]
+pub struct InvocationRateWindow {
+    started: VecDeque<Instant>,
+    throttled: bool,
+}
+
+/// An invocation which does not fit in the invocation rate window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttled {
+    /// How long the invocation has to wait before trying again
+    pub delay: Duration,
+    /// Whether the previous attempt to start an invocation was throttled too
+    pub already_throttled: bool,
+}
+
+impl InvocationRateWindow {
+    /// Registers a new invocation started at `now` if it fits in the window, otherwise returns
+    /// how long the invocation has to wait before trying again.
+    pub fn try_start(&mut self, now: Instant, max_per_minute: u32) -> Result<(), Throttled> {
+        while let Some(oldest) = self.started.front() {
+            if now.saturating_duration_since(*oldest) >= WINDOW {
+                self.started.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.started.len() < max_per_minute as usize {
+            self.started.push_back(now);
+            self.throttled = false;
+            Ok(())
+        } else {
+            let oldest = self.started.front().copied().unwrap_or(now);
+            let already_throttled = std::mem::replace(&mut self.throttled, true);
+            Err(Throttled {
+                delay: (oldest + WINDOW).saturating_duration_since(now),
+                already_throttled,
+            })
+        }
+    }
+}
+
+/// The fuel level of a worker's store when its running invocation started, used for enforcing
+/// the `max_fuel_per_invocation` agent quota while the invocation is executing. The fuel level
+/// of the store only decreases, so the consumed fuel is the difference of the levels.
+#[derive(Debug, Default)]
+pub struct InvocationFuel {
+    start_level: Mutex<Option<u64>>,
+}
+
+impl InvocationFuel {
+    pub fn start(&self, level: u64) {
+        *self.start_level.lock().unwrap() = Some(level);
+    }
+
+    pub fn finish(&self) {
+        *self.start_level.lock().unwrap() = None;
+    }
+
+    /// Returns the fuel consumed by the running invocation if it is more than `max_fuel`
+    pub fn exceeded(&self, current_level: u64, max_fuel: u64) -> Option<u64> {
+        let start_level = (*self.start_level.lock().unwrap())?;
+        let consumed = start_level.saturating_sub(current_level);
+        (consumed > max_fuel).then_some(consumed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use std::time::{Duration, Instant};
+    use test_r::test;
+
+    #[test]
+    fn allows_invocations_up_to_the_limit() {
+        let mut window = InvocationRateWindow::default();
+        let start = Instant::now();
+
+        assert_eq!(window.try_start(start, 2), Ok(()));
+        assert_eq!(window.try_start(start + Duration::from_secs(10), 2), Ok(()));
+        assert_eq!(
+            window.try_start(start + Duration::from_secs(20), 2),
+            Err(Throttled {
+                delay: Duration::from_secs(40),
+                already_throttled: false
+            })
+        );
+        assert_eq!(
+            window.try_start(start + Duration::from_secs(30), 2),
+            Err(Throttled {
+                delay: Duration::from_secs(30),
+                already_throttled: true
+            })
+        );
+    }
+
+    #[test]
+    fn old_invocations_leave_the_window() {
+        let mut window = InvocationRateWindow::default();
+        let start = Instant::now();
+
+        assert_eq!(window.try_start(start, 1), Ok(()));
+        assert_eq!(
+            window.try_start(start + Duration::from_secs(59), 1),
+            Err(Throttled {
+                delay: Duration::from_secs(1),
+                already_throttled: false
+            })
+        );
+        assert_eq!(window.try_start(start + Duration::from_secs(60), 1), Ok(()));
+    }
+
+    #[test]
+    fn zero_limit_never_allows_invocations() {
+        let mut window = InvocationRateWindow::default();
+        let start = Instant::now();
+
+        assert_eq!(
+            window.try_start(start, 0),
+            Err(Throttled {
+                delay: Duration::from_secs(60),
+                already_throttled: false
+            })
+        );
+    }
+
+    #[test]
+    fn invocations_exceeding_the_fuel_quota_are_detected_while_running() {
+        let fuel = InvocationFuel::default();
+        check!(fuel.exceeded(0, 100).is_none());
+
+        fuel.start(1000);
+        check!(fuel.exceeded(950, 100).is_none());
+        check!(fuel.exceeded(850, 100) == Some(150));
+
+        fuel.finish();
+        check!(fuel.exceeded(850, 100).is_none());
+    }
+
+    #[test]
+    fn component_environment_overrides_default_quotas() {
+        let defaults = AgentQuotas {
+            max_fuel_per_invocation: Some(1000),
+            max_invocations_per_minute: Some(10),
+            max_oplog_entries: None,
+        };
+        let env = BTreeMap::from([
+            (
+                MAX_FUEL_PER_INVOCATION_ENV_VAR.to_string(),
+                "500".to_string(),
+            ),
+            (MAX_OPLOG_ENTRIES_ENV_VAR.to_string(), "10000".to_string()),
+            (
+                MAX_INVOCATIONS_PER_MINUTE_ENV_VAR.to_string(),
+                "many".to_string(),
+            ),
+        ]);
+
+        let quotas = agent_quotas(&env, &defaults);
+
+        check!(quotas.max_fuel_per_invocation == Some(500));
+        check!(quotas.max_invocations_per_minute == Some(10));
+        check!(quotas.max_oplog_entries == Some(10000));
+    }
+}
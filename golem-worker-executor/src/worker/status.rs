@@ -7,9 +7,9 @@ use golem_common::model::oplog::{
 };
 use golem_common::model::regions::{DeletedRegions, DeletedRegionsBuilder, OplogRegion};
 use golem_common::model::{
-    FailedUpdateRecord, IdempotencyKey, OwnedWorkerId, RetryConfig, SuccessfulUpdateRecord,
-    TimestampedWorkerInvocation, WorkerInvocation, WorkerResourceDescription, WorkerStatus,
-    WorkerStatusRecord,
+    FailedUpdateRecord, IdempotencyKey, OwnedWorkerId, RetryConfig, SnapshotRecord,
+    SuccessfulUpdateRecord, TimestampedWorkerInvocation, WorkerInvocation,
+    WorkerResourceDescription, WorkerResourceUsageRecord, WorkerStatus, WorkerStatusRecord,
 };
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
@@ -166,6 +166,19 @@ pub fn update_status_with_new_entries(
 
     let active_plugins = calculate_active_plugins(active_plugins, &deleted_regions, &new_entries);
 
+    let last_snapshot =
+        calculate_last_snapshot(last_known.last_snapshot, &skipped_regions, &new_entries);
+
+    let resource_usage = calculate_resource_usage(
+        last_known.resource_usage,
+        last_known.total_linear_memory_size,
+        &last_known.deleted_regions,
+        &last_known.skipped_regions,
+        &new_entries,
+    );
+
+    let labels = calculate_labels(last_known.labels, &deleted_regions, &new_entries);
+
     let result = WorkerStatusRecord {
         oplog_idx: new_entries
             .keys()
@@ -189,6 +202,9 @@ pub fn update_status_with_new_entries(
         deleted_regions,
         component_revision_for_replay,
         current_retry_count,
+        last_snapshot,
+        resource_usage,
+        labels,
     };
 
     Some(result)
@@ -330,6 +346,8 @@ fn calculate_latest_worker_status(
             OplogEntry::RolledBackRemoteTransaction { .. } => {
                 current_status = WorkerStatus::Running;
             }
+            OplogEntry::Snapshot { .. } => {}
+            OplogEntry::ChangeLabels { .. } => {}
             OplogEntry::Error { .. } => {
                 // .. handled separately
             }
@@ -756,6 +774,135 @@ fn calculate_active_plugins(
     result
 }
 
+fn calculate_last_snapshot(
+    initial: Option<SnapshotRecord>,
+    skipped_regions: &DeletedRegions,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> Option<SnapshotRecord> {
+    let mut last_snapshot = initial;
+    for (idx, entry) in entries {
+        if skipped_regions.is_in_deleted_region(*idx) {
+            continue;
+        }
+
+        match entry {
+            OplogEntry::Snapshot { timestamp, .. } => {
+                last_snapshot = Some(SnapshotRecord {
+                    timestamp: *timestamp,
+                    oplog_index: *idx,
+                });
+            }
+            OplogEntry::SuccessfulUpdate { .. } => {
+                // Snapshots taken with a previous component revision cannot be used for replay
+                last_snapshot = None;
+            }
+            _ => {}
+        }
+    }
+
+    // Jumps, reverts and snapshot-based updates can make the snapshot entry itself skipped
+    last_snapshot.filter(|snapshot| !skipped_regions.is_in_deleted_region(snapshot.oplog_index))
+}
+
+fn calculate_resource_usage(
+    initial: WorkerResourceUsageRecord,
+    initial_total_linear_memory_size: u64,
+    initial_deleted_regions: &DeletedRegions,
+    initial_skipped_regions: &DeletedRegions,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> WorkerResourceUsageRecord {
+    let mut result = initial;
+    let mut total_linear_memory_size = initial_total_linear_memory_size;
+    // The entries determining the total linear memory size, to recalculate it when entries get skipped
+    let mut memory_entries = BTreeMap::new();
+    // Not skipping any region, as the resources were consumed even if the entries are not replayed
+    for (idx, entry) in entries {
+        match entry {
+            OplogEntry::Create {
+                initial_total_linear_memory_size,
+                ..
+            } => {
+                total_linear_memory_size = *initial_total_linear_memory_size;
+                result.peak_linear_memory_size = total_linear_memory_size;
+            }
+            OplogEntry::GrowMemory { delta, .. } => {
+                total_linear_memory_size += *delta;
+                result.peak_linear_memory_size =
+                    result.peak_linear_memory_size.max(total_linear_memory_size);
+            }
+            OplogEntry::ExportedFunctionInvoked { timestamp, .. } => {
+                result.current_invocation_started_at = Some(*timestamp);
+            }
+            OplogEntry::ExportedFunctionCompleted {
+                timestamp,
+                consumed_fuel,
+                ..
+            } => {
+                result.consumed_fuel = result
+                    .consumed_fuel
+                    .saturating_add((*consumed_fuel).max(0) as u64);
+                result.invocation_count += 1;
+                if let Some(started_at) = result.current_invocation_started_at.take() {
+                    result.invocation_time_millis +=
+                        timestamp.to_millis().saturating_sub(started_at.to_millis());
+                }
+            }
+            _ => {}
+        }
+
+        match entry {
+            OplogEntry::Create { .. }
+            | OplogEntry::GrowMemory { .. }
+            | OplogEntry::PendingUpdate { .. }
+            | OplogEntry::FailedUpdate { .. } => {
+                memory_entries.insert(*idx, entry.clone());
+            }
+            OplogEntry::Jump { .. }
+            | OplogEntry::Revert { .. }
+            | OplogEntry::SuccessfulUpdate { .. } => {
+                // The memory of the skipped entries is no longer allocated after these
+                memory_entries.insert(*idx, entry.clone());
+                let deleted_regions =
+                    calculate_deleted_regions(initial_deleted_regions.clone(), &memory_entries);
+                let skipped_regions = calculate_skipped_regions(
+                    initial_skipped_regions.clone(),
+                    &deleted_regions,
+                    &memory_entries,
+                );
+                total_linear_memory_size = calculate_total_linear_memory_size(
+                    initial_total_linear_memory_size,
+                    &skipped_regions,
+                    &memory_entries,
+                );
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+fn calculate_labels(
+    initial: BTreeMap<String, String>,
+    deleted_regions: &DeletedRegions,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> BTreeMap<String, String> {
+    let mut result = initial;
+    for (idx, entry) in entries {
+        // Skipping entries in deleted regions, so reverting a worker reverts its labels too
+        if deleted_regions.is_in_deleted_region(*idx) {
+            continue;
+        }
+
+        if let OplogEntry::ChangeLabels { set, removed, .. } = entry {
+            for key in removed {
+                result.remove(key);
+            }
+            result.extend(set.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+    }
+    result
+}
+
 fn is_worker_error_retriable(
     retry_config: &RetryConfig,
     error: &WorkerError,
@@ -767,6 +914,7 @@ fn is_worker_error_retriable(
         WorkerError::StackOverflow => false,
         WorkerError::OutOfMemory => true,
         WorkerError::ExceededMemoryLimit => false,
+        WorkerError::QuotaExceeded(_) => false,
     }
 }
 
@@ -792,15 +940,15 @@ mod test {
     };
     use golem_common::model::regions::{DeletedRegions, OplogRegion};
     use golem_common::model::{
-        FailedUpdateRecord, IdempotencyKey, OwnedWorkerId, RetryConfig, ScanCursor,
+        FailedUpdateRecord, IdempotencyKey, OwnedWorkerId, RetryConfig, ScanCursor, SnapshotRecord,
         SuccessfulUpdateRecord, Timestamp, TimestampedWorkerInvocation, WorkerId, WorkerInvocation,
-        WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+        WorkerMetadata, WorkerResourceUsageRecord, WorkerStatus, WorkerStatusRecord,
     };
     use golem_common::read_only_lock;
     use golem_service_base::error::worker_executor::WorkerExecutorError;
     use golem_wasm::{IntoValueAndType, Value, ValueAndType};
     use pretty_assertions::assert_eq;
-    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
     use std::sync::Arc;
     use test_r::test;
 
@@ -828,6 +976,91 @@ mod test {
         run_test_case(test_case).await;
     }
 
+    #[test]
+    async fn resource_usage() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+
+        let test_case = TestCase::builder(0)
+            .exported_function_invoked("a", vec![], k1.clone())
+            .grow_memory(10)
+            .exported_function_completed_with_fuel(None, k1, 1000)
+            .exported_function_invoked("b", vec![], k2.clone())
+            .grow_memory(100)
+            .exported_function_completed_with_fuel(None, k2, 234)
+            .build();
+
+        let final_status = &test_case.entries.last().unwrap().expected_status;
+        assert_eq!(final_status.resource_usage.consumed_fuel, 1234);
+        assert_eq!(final_status.resource_usage.invocation_count, 2);
+        assert_eq!(final_status.resource_usage.peak_linear_memory_size, 310);
+
+        run_test_case(test_case).await;
+    }
+
+    #[test]
+    async fn resource_usage_with_revert() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+
+        let test_case = TestCase::builder(0)
+            .exported_function_invoked("a", vec![], k1.clone())
+            .grow_memory(10)
+            .exported_function_completed_with_fuel(None, k1, 100)
+            .revert(OplogIndex::from_u64(1))
+            .exported_function_invoked("b", vec![], k2.clone())
+            .grow_memory(5)
+            .exported_function_completed_with_fuel(None, k2, 0)
+            .build();
+
+        // Reverted invocations still count as consumed resources, and the peak memory size is
+        // not increased by growing the memory of the reverted agent below it
+        let final_status = &test_case.entries.last().unwrap().expected_status;
+        assert_eq!(final_status.total_linear_memory_size, 205);
+        assert_eq!(final_status.resource_usage.peak_linear_memory_size, 210);
+        assert_eq!(final_status.resource_usage.consumed_fuel, 100);
+        assert_eq!(final_status.resource_usage.invocation_count, 1);
+
+        run_test_case(test_case).await;
+    }
+
+    #[test]
+    async fn labels() {
+        let test_case = TestCase::builder(0)
+            .change_labels(&[("tenant", "acme"), ("region", "eu")], &[])
+            .change_labels(&[("region", "us")], &[])
+            .change_labels(&[("cohort", "beta")], &["tenant"])
+            .build();
+
+        let final_status = &test_case.entries.last().unwrap().expected_status;
+        assert_eq!(
+            final_status.labels,
+            BTreeMap::from([
+                ("cohort".to_string(), "beta".to_string()),
+                ("region".to_string(), "us".to_string())
+            ])
+        );
+
+        run_test_case(test_case).await;
+    }
+
+    #[test]
+    async fn labels_with_revert() {
+        let test_case = TestCase::builder(0)
+            .change_labels(&[("tenant", "acme")], &[])
+            .change_labels(&[("region", "eu")], &[])
+            .revert(OplogIndex::from_u64(2))
+            .build();
+
+        let final_status = &test_case.entries.last().unwrap().expected_status;
+        assert_eq!(
+            final_status.labels,
+            BTreeMap::from([("tenant".to_string(), "acme".to_string())])
+        );
+
+        run_test_case(test_case).await;
+    }
+
     #[test]
     async fn invocation_results_with_jump() {
         let k1 = IdempotencyKey::fresh();
@@ -1183,6 +1416,62 @@ mod test {
         run_test_case(test_case).await;
     }
 
+    #[test]
+    async fn snapshots() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+
+        let test_case = TestCase::builder(1)
+            .exported_function_invoked("a", vec![], k1.clone())
+            .exported_function_completed(None, k1)
+            .snapshot()
+            .exported_function_invoked("b", vec![], k2.clone())
+            .exported_function_completed(None, k2)
+            .snapshot()
+            .build();
+
+        run_test_case(test_case).await;
+    }
+
+    #[test]
+    async fn snapshot_with_jump() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+
+        let test_case = TestCase::builder(1)
+            .exported_function_invoked("a", vec![], k1.clone())
+            .exported_function_completed(None, k1)
+            .snapshot()
+            .exported_function_invoked("b", vec![], k2.clone())
+            .snapshot()
+            .jump(OplogIndex::from_u64(5))
+            .exported_function_completed(None, k2)
+            .build();
+
+        run_test_case(test_case).await;
+    }
+
+    #[test]
+    async fn snapshot_with_auto_update() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+        let update1 = UpdateDescription::Automatic {
+            target_revision: ComponentRevision::new(2).unwrap(),
+        };
+
+        let test_case = TestCase::builder(1)
+            .exported_function_invoked("a", vec![], k1.clone())
+            .exported_function_completed(None, k1)
+            .snapshot()
+            .pending_update(&update1, |_| {})
+            .successful_update(update1, 2000, &HashSet::new())
+            .exported_function_invoked("b", vec![], k2.clone())
+            .exported_function_completed(None, k2)
+            .build();
+
+        run_test_case(test_case).await;
+    }
+
     #[test]
     async fn cancel_pending_invocation() {
         let k1 = IdempotencyKey::fresh();
@@ -1244,6 +1533,10 @@ mod test {
                 component_size: 100,
                 total_linear_memory_size: 200,
                 oplog_idx: OplogIndex::INITIAL,
+                resource_usage: WorkerResourceUsageRecord {
+                    peak_linear_memory_size: 200,
+                    ..Default::default()
+                },
                 ..Default::default()
             };
             TestCaseBuilder {
@@ -1288,9 +1581,10 @@ mod test {
             request: Vec<Value>,
             idempotency_key: IdempotencyKey,
         ) -> Self {
+            let timestamp = Timestamp::now_utc().rounded();
             self.add(
                 OplogEntry::ExportedFunctionInvoked {
-                    timestamp: Timestamp::now_utc(),
+                    timestamp,
                     function_name: function_name.to_string(),
                     request: OplogPayload::Inline(Box::new(request)),
                     idempotency_key: idempotency_key.clone(),
@@ -1299,6 +1593,7 @@ mod test {
                     invocation_context: vec![],
                 },
                 move |mut status| {
+                    status.resource_usage.current_invocation_started_at = Some(timestamp);
                     status.current_idempotency_key = Some(idempotency_key);
                     status.status = WorkerStatus::Running;
                     if !status.pending_invocations.is_empty() {
@@ -1314,13 +1609,30 @@ mod test {
             response: Option<ValueAndType>,
             idempotency_key: IdempotencyKey,
         ) -> Self {
+            self.exported_function_completed_with_fuel(response, idempotency_key, 0)
+        }
+
+        pub fn exported_function_completed_with_fuel(
+            self,
+            response: Option<ValueAndType>,
+            idempotency_key: IdempotencyKey,
+            consumed_fuel: i64,
+        ) -> Self {
+            let timestamp = Timestamp::now_utc().rounded();
             self.add(
                 OplogEntry::ExportedFunctionCompleted {
-                    timestamp: Timestamp::now_utc(),
+                    timestamp,
                     response: OplogPayload::Inline(Box::new(response)),
-                    consumed_fuel: 0,
+                    consumed_fuel,
                 },
                 move |mut status| {
+                    let usage = &mut status.resource_usage;
+                    usage.consumed_fuel += consumed_fuel as u64;
+                    usage.invocation_count += 1;
+                    if let Some(started_at) = usage.current_invocation_started_at.take() {
+                        usage.invocation_time_millis +=
+                            timestamp.to_millis() - started_at.to_millis();
+                    }
                     status
                         .invocation_results
                         .insert(idempotency_key, status.oplog_idx);
@@ -1358,6 +1670,10 @@ mod test {
                 },
                 |mut status| {
                     status.total_linear_memory_size += delta;
+                    status.resource_usage.peak_linear_memory_size = status
+                        .resource_usage
+                        .peak_linear_memory_size
+                        .max(status.total_linear_memory_size);
                     status
                 },
             )
@@ -1380,6 +1696,7 @@ mod test {
                 status.component_size = old_status.component_size;
                 status.owned_resources = old_status.owned_resources;
                 status.skipped_regions.add(region);
+                status.last_snapshot = old_status.last_snapshot;
                 status
             })
         }
@@ -1396,6 +1713,7 @@ mod test {
                 .clone();
             self.add(OplogEntry::revert(region.clone()), move |mut status| {
                 status.active_plugins = old_status.active_plugins;
+                status.labels = old_status.labels;
 
                 status.skipped_regions = old_status.skipped_regions;
                 status.skipped_regions.add(region.clone());
@@ -1411,6 +1729,7 @@ mod test {
                 status.failed_updates = old_status.failed_updates;
                 status.invocation_results = old_status.invocation_results;
                 status.component_revision_for_replay = old_status.component_revision_for_replay;
+                status.last_snapshot = old_status.last_snapshot;
 
                 status
             })
@@ -1429,6 +1748,22 @@ mod test {
             })
         }
 
+        pub fn change_labels(self, set: &[(&str, &str)], removed: &[&str]) -> Self {
+            let set: BTreeMap<String, String> = set
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            let removed: BTreeSet<String> = removed.iter().map(|k| k.to_string()).collect();
+            let entry = OplogEntry::change_labels(set.clone(), removed.clone()).rounded();
+            self.add(entry, move |mut status| {
+                for key in &removed {
+                    status.labels.remove(key);
+                }
+                status.labels.extend(set);
+                status
+            })
+        }
+
         pub fn cancel_pending_invocation(self, idempotency_key: IdempotencyKey) -> Self {
             let entry = OplogEntry::cancel_pending_invocation(idempotency_key.clone()).rounded();
             self.add(entry.clone(), move |mut status| {
@@ -1475,6 +1810,7 @@ mod test {
                         .set_override(DeletedRegions::from_regions(vec![
                             OplogRegion::from_index_range(OplogIndex::INITIAL.next()..=oplog_idx),
                         ]));
+                    status.last_snapshot = None;
                 }
 
                 extra_status_updates(&mut status);
@@ -1505,6 +1841,7 @@ mod test {
                 status.component_size = new_component_size;
                 status.component_revision = *update_description.target_revision();
                 status.active_plugins = new_active_plugins.clone();
+                status.last_snapshot = None;
 
                 if status.skipped_regions.is_overridden() {
                     status.skipped_regions.merge_override();
@@ -1564,6 +1901,19 @@ mod test {
             })
         }
 
+        pub fn snapshot(self) -> Self {
+            let entry =
+                OplogEntry::snapshot(OplogPayload::Inline(Box::new(vec![1, 2, 3]))).rounded();
+            let oplog_idx = OplogIndex::from_u64(self.entries.len() as u64 + 1);
+            self.add(entry.clone(), move |mut status| {
+                status.last_snapshot = Some(SnapshotRecord {
+                    timestamp: entry.timestamp(),
+                    oplog_index: oplog_idx,
+                });
+                status
+            })
+        }
+
         pub fn build(self) -> TestCase {
             TestCase {
                 owned_worker_id: self.owned_worker_id,
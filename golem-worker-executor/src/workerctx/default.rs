@@ -59,7 +59,7 @@ use golem_service_base::error::worker_executor::{
 };
 use golem_service_base::model::GetFileSystemNodeResult;
 use golem_wasm::golem_rpc_0_2_x::types::{
-    Datetime, FutureInvokeResult, HostFutureInvokeResult, Pollable, WasmRpc,
+    Datetime, FutureInvokeResult, HostFutureInvokeResult, Pollable, ScheduleRecurrence, WasmRpc,
 };
 use golem_wasm::wasmtime::{ResourceStore, ResourceTypeId};
 use golem_wasm::{
@@ -396,6 +396,18 @@ impl HostWasmRpc for Context {
             .await
     }
 
+    async fn invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
+    ) -> anyhow::Result<Result<WitValue, RpcError>> {
+        self.durable_ctx
+            .invoke_and_await_with_timeout(self_, function_name, function_params, timeout_millis)
+            .await
+    }
+
     async fn invoke(
         &mut self,
         self_: Resource<WasmRpc>,
@@ -418,6 +430,23 @@ impl HostWasmRpc for Context {
             .await
     }
 
+    async fn async_invoke_and_await_with_timeout(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        function_name: String,
+        function_params: Vec<WitValue>,
+        timeout_millis: u64,
+    ) -> anyhow::Result<Resource<FutureInvokeResult>> {
+        self.durable_ctx
+            .async_invoke_and_await_with_timeout(
+                self_,
+                function_name,
+                function_params,
+                timeout_millis,
+            )
+            .await
+    }
+
     async fn schedule_invocation(
         &mut self,
         self_: Resource<WasmRpc>,
@@ -442,6 +471,25 @@ impl HostWasmRpc for Context {
             .await
     }
 
+    async fn schedule_recurring_invocation(
+        &mut self,
+        self_: Resource<WasmRpc>,
+        scheduled_time: Datetime,
+        recurrence: ScheduleRecurrence,
+        function_name: String,
+        function_params: Vec<WitValue>,
+    ) -> anyhow::Result<Result<Resource<CancellationTokenEntry>, String>> {
+        self.durable_ctx
+            .schedule_recurring_invocation(
+                self_,
+                scheduled_time,
+                recurrence,
+                function_name,
+                function_params,
+            )
+            .await
+    }
+
     async fn drop(&mut self, rep: Resource<WasmRpc>) -> anyhow::Result<()> {
         HostWasmRpc::drop(&mut self.durable_ctx, rep).await
     }
@@ -3861,3 +3861,57 @@ async fn invoking_worker_while_its_getting_deleted_works(
 
     Ok(())
 }
+
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn throttled_invocations_do_not_block_the_worker(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let component = executor
+        .component(&context.default_environment_id, "environment-service")
+        .with_env(vec![(
+            "GOLEM_QUOTA_MAX_INVOCATIONS_PER_MINUTE".to_string(),
+            "1".to_string(),
+        )])
+        .store()
+        .await?;
+    let worker_id = executor
+        .start_worker(&component.id, "throttled-invocations-1")
+        .await?;
+
+    executor
+        .invoke_and_await(&worker_id, "golem:it/api.{get-environment}", vec![])
+        .await??;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(
+        async move {
+            executor_clone
+                .invoke_and_await(&worker_id_clone, "golem:it/api.{get-environment}", vec![])
+                .await
+        }
+        .in_current_span(),
+    );
+
+    // The second invocation is postponed by the quota, while the worker keeps processing other requests
+    sleep(Duration::from_secs(2)).await;
+    check!(!fiber.is_finished());
+
+    let node = tokio::time::timeout(
+        Duration::from_secs(10),
+        executor.get_file_system_node(&worker_id, "/"),
+    )
+    .await;
+    check!(node.is_ok());
+
+    fiber.abort();
+    drop(executor);
+    Ok(())
+}
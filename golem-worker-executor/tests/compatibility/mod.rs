@@ -15,6 +15,7 @@
 use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
 use test_r::inherit_test_dep;
 
+pub mod oplog_payloads;
 pub mod v1;
 pub mod worker_recovery;
 
@@ -0,0 +1,76 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Golden tests ensuring that the host request and response payloads stored in existing oplogs
+//! can still be deserialized after new cases or fields were added to the payload types.
+//! The binaries were written by the previous version of each type, do not regenerate them.
+
+use crate::compatibility::v1::backward_compatible;
+use goldenfile::Mint;
+use golem_common::model::agent::AgentTypeName;
+use golem_common::model::component::ComponentId;
+use golem_common::model::oplog::payload::{
+    HostRequest, HostRequestGolemAgentGetAgentType, HostRequestGolemRpcInvoke, HostResponse,
+    HostResponseGolemApiUnit,
+};
+use golem_common::model::{IdempotencyKey, WorkerId};
+use test_r::test;
+use uuid::Uuid;
+
+#[test]
+pub fn host_request_golem_rpc_invoke_without_deadline() {
+    let mut mint = Mint::new("tests/goldenfiles");
+    backward_compatible(
+        "host_request_golem_rpc_invoke",
+        &mut mint,
+        HostRequest::GolemRpcInvoke(HostRequestGolemRpcInvoke {
+            remote_worker_id: WorkerId {
+                component_id: ComponentId(
+                    Uuid::parse_str("4B29BF7C-13F6-4E37-AC03-830B81EAD478").unwrap(),
+                ),
+                worker_name: "worker_name".to_string(),
+            },
+            idempotency_key: IdempotencyKey::new("idempotency_key".to_string()),
+            function_name: "golem:it/api.{inc}".to_string(),
+            function_params: vec![],
+            remote_agent_type: None,
+            remote_agent_parameters: None,
+            deadline: None,
+        }),
+    );
+}
+
+#[test]
+pub fn host_request_case_index_is_stable() {
+    let mut mint = Mint::new("tests/goldenfiles");
+    backward_compatible(
+        "host_request_golem_agent_get_agent_type",
+        &mut mint,
+        HostRequest::GolemAgentGetAgentType(HostRequestGolemAgentGetAgentType {
+            agent_type_name: AgentTypeName("counter-agent".to_string()),
+        }),
+    );
+}
+
+#[test]
+pub fn host_response_case_index_is_stable() {
+    let mut mint = Mint::new("tests/goldenfiles");
+    backward_compatible(
+        "host_response_golem_api_unit",
+        &mut mint,
+        HostResponse::GolemApiUnit(HostResponseGolemApiUnit {
+            result: Err("failed".to_string()),
+        }),
+    );
+}
@@ -14,19 +14,24 @@
 
 use assert2::check;
 use async_trait::async_trait;
-use golem_common::config::RedisConfig;
+use golem_common::config::{DbPostgresConfig, RedisConfig};
 use golem_common::model::component::ComponentId;
 use golem_common::model::WorkerId;
 use golem_common::redis::RedisPool;
+use golem_service_base::db::postgres::PostgresPool;
 use golem_service_base::db::sqlite::SqlitePool;
+use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+use golem_test_framework::components::rdb::{DbInfo, PostgresInfo, Rdb};
 use golem_test_framework::components::redis::Redis;
 use golem_worker_executor::storage::indexed::memory::InMemoryIndexedStorage;
 use golem_worker_executor::storage::indexed::multi_sqlite::MultiSqliteIndexedStorage;
+use golem_worker_executor::storage::indexed::postgres::PostgresIndexedStorage;
 use golem_worker_executor::storage::indexed::redis::RedisIndexedStorage;
 use golem_worker_executor::storage::indexed::sqlite::SqliteIndexedStorage;
 use golem_worker_executor::storage::indexed::{
     IndexedStorage, IndexedStorageMetaNamespace, IndexedStorageNamespace, ScanCursor,
 };
+use golem_worker_executor::storage::migrate_postgres;
 use golem_worker_executor_test_utils::WorkerExecutorTestDependencies;
 use sqlx::sqlite::SqlitePoolOptions;
 use std::fmt::{Debug, Formatter};
@@ -173,6 +178,53 @@ async fn multi_sqlite_storage(
     Arc::new(MultiSqliteIndexedStorageWrapper::new())
 }
 
+#[test_dep]
+async fn postgres() -> DockerPostgresRdb {
+    let unique_network_id = Uuid::new_v4().to_string();
+    DockerPostgresRdb::new(&unique_network_id, false).await
+}
+
+struct PostgresIndexedStorageWrapper {
+    postgres: PostgresInfo,
+}
+
+impl Debug for PostgresIndexedStorageWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostgresIndexedStorageWrapper")
+    }
+}
+
+#[async_trait]
+impl GetIndexedStorage for PostgresIndexedStorageWrapper {
+    async fn get_indexed_storage(&self) -> Arc<dyn IndexedStorage + Send + Sync> {
+        // Each storage instance gets its own schema so tests do not see each other's data
+        let schema = format!("is_{}", Uuid::new_v4().simple());
+        let config = DbPostgresConfig {
+            host: self.postgres.public_host.clone(),
+            port: self.postgres.public_port,
+            database: self.postgres.database_name.clone(),
+            username: self.postgres.username.clone(),
+            password: self.postgres.password.clone(),
+            max_connections: 10,
+            schema: Some(schema),
+        };
+        migrate_postgres(&config).await.unwrap();
+        let pool = PostgresPool::configured(&config).await.unwrap();
+        Arc::new(PostgresIndexedStorage::new(pool))
+    }
+}
+
+#[test_dep(tagged_as = "postgres")]
+async fn postgres_storage(
+    _deps: &WorkerExecutorTestDependencies,
+    postgres: &DockerPostgresRdb,
+) -> Arc<dyn GetIndexedStorage + Send + Sync> {
+    let DbInfo::Postgres(postgres) = postgres.info() else {
+        unreachable!()
+    };
+    Arc::new(PostgresIndexedStorageWrapper { postgres })
+}
+
 #[derive(Debug, Clone)]
 struct IndexedStorageNamespaces {
     ns: IndexedStorageNamespace,
@@ -222,7 +274,7 @@ fn ns2() -> IndexedStorageNamespaces {
 
 inherit_test_dep!(WorkerExecutorTestDependencies);
 
-define_matrix_dimension!(is: Arc<dyn GetIndexedStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "multi_sqlite");
+define_matrix_dimension!(is: Arc<dyn GetIndexedStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "multi_sqlite", "postgres");
 
 #[test]
 #[tracing::instrument]
@@ -14,18 +14,23 @@
 
 use crate::WorkerExecutorTestDependencies;
 use async_trait::async_trait;
-use golem_common::config::RedisConfig;
+use golem_common::config::{DbPostgresConfig, RedisConfig};
 use golem_common::model::component::ComponentId;
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::WorkerId;
 use golem_common::redis::RedisPool;
+use golem_service_base::db::postgres::PostgresPool;
 use golem_service_base::db::sqlite::SqlitePool;
+use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+use golem_test_framework::components::rdb::{DbInfo, PostgresInfo, Rdb};
 use golem_test_framework::components::redis::Redis;
 use golem_worker_executor::storage::keyvalue::memory::InMemoryKeyValueStorage;
 use golem_worker_executor::storage::keyvalue::multi_sqlite::MultiSqliteKeyValueStorage;
+use golem_worker_executor::storage::keyvalue::postgres::PostgresKeyValueStorage;
 use golem_worker_executor::storage::keyvalue::redis::RedisKeyValueStorage;
 use golem_worker_executor::storage::keyvalue::sqlite::SqliteKeyValueStorage;
 use golem_worker_executor::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use golem_worker_executor::storage::migrate_postgres;
 use sqlx::sqlite::SqlitePoolOptions;
 use std::fmt::{Debug, Formatter};
 use std::sync::{Arc, Mutex};
@@ -170,6 +175,53 @@ async fn multi_sqlite_storage(
     Arc::new(MultiSqliteKeyValueStorageWrapper::new())
 }
 
+#[test_dep]
+async fn postgres() -> DockerPostgresRdb {
+    let unique_network_id = Uuid::new_v4().to_string();
+    DockerPostgresRdb::new(&unique_network_id, false).await
+}
+
+struct PostgresKeyValueStorageWrapper {
+    postgres: PostgresInfo,
+}
+
+impl Debug for PostgresKeyValueStorageWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostgresKeyValueStorageWrapper")
+    }
+}
+
+#[async_trait]
+impl GetKeyValueStorage for PostgresKeyValueStorageWrapper {
+    async fn get_key_value_storage(&self) -> Arc<dyn KeyValueStorage + Send + Sync> {
+        // Each storage instance gets its own schema so tests do not see each other's data
+        let schema = format!("kvs_{}", Uuid::new_v4().simple());
+        let config = DbPostgresConfig {
+            host: self.postgres.public_host.clone(),
+            port: self.postgres.public_port,
+            database: self.postgres.database_name.clone(),
+            username: self.postgres.username.clone(),
+            password: self.postgres.password.clone(),
+            max_connections: 10,
+            schema: Some(schema),
+        };
+        migrate_postgres(&config).await.unwrap();
+        let pool = PostgresPool::configured(&config).await.unwrap();
+        Arc::new(PostgresKeyValueStorage::new(pool))
+    }
+}
+
+#[test_dep(tagged_as = "postgres")]
+async fn postgres_storage(
+    _deps: &WorkerExecutorTestDependencies,
+    postgres: &DockerPostgresRdb,
+) -> Arc<dyn GetKeyValueStorage + Send + Sync> {
+    let DbInfo::Postgres(postgres) = postgres.info() else {
+        unreachable!()
+    };
+    Arc::new(PostgresKeyValueStorageWrapper { postgres })
+}
+
 #[derive(Debug)]
 struct Namespaces {
     pub ns: KeyValueStorageNamespace,
@@ -210,7 +262,7 @@ fn ns2() -> Namespaces {
 
 inherit_test_dep!(WorkerExecutorTestDependencies);
 
-define_matrix_dimension!(kvs: Arc<dyn GetKeyValueStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "multi_sqlite");
+define_matrix_dimension!(kvs: Arc<dyn GetKeyValueStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "multi_sqlite", "postgres");
 define_matrix_dimension!(nss: Namespaces -> "ns1", "ns2");
 
 #[test]
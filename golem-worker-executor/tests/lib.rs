@@ -31,12 +31,14 @@ pub mod indexed_storage;
 pub mod key_value_storage;
 pub mod keyvalue;
 pub mod observability;
+pub mod oplog_search_index;
 pub mod rdbms;
 pub mod rdbms_service;
 pub mod revert;
 pub mod rust_rpc;
 pub mod rust_rpc_stubless;
 pub mod scalability;
+pub mod shard_revocation;
 pub mod transactions;
 pub mod wasi;
 
@@ -0,0 +1,209 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert2::check;
+use golem_common::config::DbSqliteConfig;
+use golem_common::model::component::ComponentId;
+use golem_common::model::lucene::Query;
+use golem_common::model::oplog::{LogLevel, LogParams, OplogIndex, PublicOplogEntry};
+use golem_common::model::regions::OplogRegion;
+use golem_common::model::{Timestamp, WorkerId};
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_worker_executor::services::oplog::search_index::{
+    OplogSearchIndex, OplogSearchIndexUpdate, SqliteOplogSearchIndex,
+};
+use golem_worker_executor::storage::migrate_sqlite;
+use tempfile::TempDir;
+use test_r::test;
+
+async fn sqlite_index(dir: &TempDir) -> SqliteOplogSearchIndex {
+    let config = DbSqliteConfig {
+        database: dir
+            .path()
+            .join("oplog-search-index.db")
+            .to_string_lossy()
+            .to_string(),
+        max_connections: 4,
+        foreign_keys: false,
+    };
+    migrate_sqlite(&config).await.unwrap();
+    SqliteOplogSearchIndex::new(SqlitePool::configured(&config).await.unwrap())
+}
+
+fn log(message: &str) -> PublicOplogEntry {
+    PublicOplogEntry::Log(LogParams {
+        timestamp: Timestamp::now_utc().rounded(),
+        level: LogLevel::Stdout,
+        context: "test".to_string(),
+        message: message.to_string(),
+    })
+}
+
+fn update(entries: &[(u64, &str)], dropped_regions: &[(u64, u64, u64)]) -> OplogSearchIndexUpdate {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(idx, message)| (OplogIndex::from_u64(*idx), log(message)))
+        .collect();
+    let dropped_regions: Vec<_> = dropped_regions
+        .iter()
+        .map(|(idx, start, end)| {
+            (
+                OplogIndex::from_u64(*idx),
+                OplogRegion {
+                    start: OplogIndex::from_u64(*start),
+                    end: OplogIndex::from_u64(*end),
+                },
+            )
+        })
+        .collect();
+    let last_index = entries
+        .iter()
+        .map(|(idx, _)| *idx)
+        .chain(dropped_regions.iter().map(|(idx, _)| *idx))
+        .max()
+        .unwrap_or(OplogIndex::NONE);
+
+    OplogSearchIndexUpdate {
+        entries,
+        dropped_regions,
+        last_index,
+    }
+}
+
+async fn matching_indexes(
+    index: &SqliteOplogSearchIndex,
+    component_id: &ComponentId,
+    query: &str,
+) -> Vec<u64> {
+    let query = Query::parse(query).unwrap();
+    let mut result: Vec<u64> = index
+        .candidates(component_id, &query, 0, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|hit| hit.entry.matches(&query))
+        .map(|hit| u64::from(hit.oplog_index))
+        .collect();
+    result.sort();
+    result
+}
+
+#[test]
+async fn sqlite_update_tracks_progress() {
+    let dir = TempDir::new().unwrap();
+    let index = sqlite_index(&dir).await;
+    let worker_id = WorkerId {
+        component_id: ComponentId::new(),
+        worker_name: "worker".to_string(),
+    };
+
+    check!(index.last_indexed(&worker_id).await.unwrap() == OplogIndex::NONE);
+
+    index
+        .update(&worker_id, &update(&[(1, "hello"), (2, "world")], &[]))
+        .await
+        .unwrap();
+    index
+        .update(&worker_id, &update(&[(3, "hello again")], &[]))
+        .await
+        .unwrap();
+
+    check!(index.last_indexed(&worker_id).await.unwrap() == OplogIndex::from_u64(3));
+    check!(matching_indexes(&index, &worker_id.component_id, "hello").await == vec![1, 3]);
+}
+
+#[test]
+async fn sqlite_update_ignores_already_indexed_entries() {
+    let dir = TempDir::new().unwrap();
+    let index = sqlite_index(&dir).await;
+    let worker_id = WorkerId {
+        component_id: ComponentId::new(),
+        worker_name: "worker".to_string(),
+    };
+
+    index
+        .update(&worker_id, &update(&[(1, "hello"), (2, "world")], &[]))
+        .await
+        .unwrap();
+    // Applying an overlapping older update again does not change the indexed entries
+    index
+        .update(&worker_id, &update(&[(1, "other"), (2, "other")], &[]))
+        .await
+        .unwrap();
+
+    check!(index.last_indexed(&worker_id).await.unwrap() == OplogIndex::from_u64(2));
+    check!(matching_indexes(&index, &worker_id.component_id, "other").await == Vec::<u64>::new());
+    check!(matching_indexes(&index, &worker_id.component_id, "world").await == vec![2]);
+}
+
+#[test]
+async fn sqlite_update_removes_reverted_entries() {
+    let dir = TempDir::new().unwrap();
+    let index = sqlite_index(&dir).await;
+    let worker_id = WorkerId {
+        component_id: ComponentId::new(),
+        worker_name: "worker".to_string(),
+    };
+
+    index
+        .update(
+            &worker_id,
+            &update(&[(1, "hello"), (2, "hello"), (3, "hello")], &[]),
+        )
+        .await
+        .unwrap();
+    index
+        .update(&worker_id, &update(&[(5, "hello")], &[(4, 2, 3)]))
+        .await
+        .unwrap();
+
+    check!(index.last_indexed(&worker_id).await.unwrap() == OplogIndex::from_u64(5));
+    check!(matching_indexes(&index, &worker_id.component_id, "hello").await == vec![1, 5]);
+}
+
+#[test]
+async fn sqlite_delete_removes_entries_and_progress() {
+    let dir = TempDir::new().unwrap();
+    let index = sqlite_index(&dir).await;
+    let component_id = ComponentId::new();
+    let worker_id1 = WorkerId {
+        component_id: component_id.clone(),
+        worker_name: "worker1".to_string(),
+    };
+    let worker_id2 = WorkerId {
+        component_id: component_id.clone(),
+        worker_name: "worker2".to_string(),
+    };
+
+    index
+        .update(&worker_id1, &update(&[(1, "hello")], &[]))
+        .await
+        .unwrap();
+    index
+        .update(&worker_id2, &update(&[(1, "hello")], &[]))
+        .await
+        .unwrap();
+    index.delete(&worker_id1).await.unwrap();
+
+    let query = Query::parse("hello").unwrap();
+    let hits = index
+        .candidates(&component_id, &query, 0, 100)
+        .await
+        .unwrap();
+
+    check!(index.last_indexed(&worker_id1).await.unwrap() == OplogIndex::NONE);
+    check!(index.last_indexed(&worker_id2).await.unwrap() == OplogIndex::from_u64(1));
+    check!(hits.len() == 1);
+    check!(hits[0].worker_id == worker_id2);
+}
@@ -0,0 +1,181 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::hot_update::TestHttpServer;
+use crate::Tracing;
+use assert2::{check, let_assert};
+use golem_api_grpc::proto::golem::shardmanager::ShardId;
+use golem_api_grpc::proto::golem::workerexecutor::v1::{
+    assign_shards_response, revoke_shards_response, AssignShardsRequest, RevokeShardsRequest,
+    RevokeShardsSuccess,
+};
+use golem_common::model::oplog::{OplogIndex, PublicOplogEntry};
+use golem_test_framework::dsl::TestDsl;
+use golem_wasm::IntoValueAndType;
+use golem_worker_executor_test_utils::{
+    start, LastUniqueId, TestContext, TestWorkerExecutor, WorkerExecutorTestDependencies,
+};
+use std::collections::HashMap;
+use test_r::{inherit_test_dep, test, timeout};
+use tracing::Instrument;
+
+inherit_test_dep!(WorkerExecutorTestDependencies);
+inherit_test_dep!(LastUniqueId);
+inherit_test_dep!(Tracing);
+
+async fn revoke_all_shards(
+    executor: &TestWorkerExecutor,
+    drain_timeout_millis: Option<u64>,
+    snapshot_on_drain: bool,
+) -> anyhow::Result<RevokeShardsSuccess> {
+    let response = executor
+        .client
+        .clone()
+        .revoke_shards(RevokeShardsRequest {
+            shard_ids: vec![ShardId { value: 0 }],
+            drain_timeout_millis,
+            snapshot_on_drain,
+        })
+        .await?
+        .into_inner();
+
+    match response.result {
+        Some(revoke_shards_response::Result::Success(success)) => Ok(success),
+        other => Err(anyhow::anyhow!("Failed to revoke shards: {other:?}")),
+    }
+}
+
+async fn assign_all_shards(executor: &TestWorkerExecutor) -> anyhow::Result<()> {
+    let response = executor
+        .client
+        .clone()
+        .assign_shards(AssignShardsRequest {
+            shard_ids: vec![ShardId { value: 0 }],
+        })
+        .await?
+        .into_inner();
+
+    match response.result {
+        Some(assign_shards_response::Result::Success(_)) => Ok(()),
+        other => Err(anyhow::anyhow!("Failed to assign shards: {other:?}")),
+    }
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("2m")]
+async fn revoking_shards_drains_idle_agents_with_snapshot(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let http_server = TestHttpServer::start().await;
+    let mut env = HashMap::new();
+    env.insert("PORT".to_string(), http_server.port().to_string());
+
+    let component = executor
+        .component(&context.default_environment_id, "update-test-v2")
+        .unique()
+        .store()
+        .await?;
+    let worker_id = executor
+        .start_worker_with(&component.id, "drained-with-snapshot", env, vec![])
+        .await?;
+
+    executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:component/api.{f1}",
+            vec![0u64.into_value_and_type()],
+        )
+        .await??;
+
+    let result = revoke_all_shards(&executor, Some(10_000), true).await?;
+    assign_all_shards(&executor).await?;
+
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await?;
+
+    drop(executor);
+    http_server.abort();
+
+    check!(result.drained_agents == 1);
+    check!(result.interrupted_agents == 0);
+    check!(oplog
+        .iter()
+        .any(|entry| matches!(entry.entry, PublicOplogEntry::Snapshot(_))));
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout("2m")]
+async fn revoking_shards_interrupts_agents_not_finishing_in_time(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) -> anyhow::Result<()> {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await?;
+
+    let mut http_server = TestHttpServer::start().await;
+    let mut env = HashMap::new();
+    env.insert("PORT".to_string(), http_server.port().to_string());
+
+    let component = executor
+        .component(&context.default_environment_id, "update-test-v2")
+        .unique()
+        .store()
+        .await?;
+    let worker_id = executor
+        .start_worker_with(&component.id, "interrupted-on-drain", env, vec![])
+        .await?;
+
+    let mut control = http_server.f1_control(1).await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(
+        async move {
+            executor_clone
+                .invoke_and_await(
+                    &worker_id_clone,
+                    "golem:component/api.{f1}",
+                    vec![1u64.into_value_and_type()],
+                )
+                .await
+        }
+        .in_current_span(),
+    );
+
+    control.await_reached().await;
+
+    // The snapshot cannot be taken while the invocation is blocked, so it must not hold up the
+    // drain beyond its deadline either
+    let result = revoke_all_shards(&executor, Some(500), true).await;
+
+    control.resume();
+    fiber.abort();
+    drop(executor);
+    http_server.abort();
+
+    let_assert!(Ok(result) = result);
+    check!(result.drained_agents == 0);
+    check!(result.interrupted_agents == 1);
+
+    Ok(())
+}
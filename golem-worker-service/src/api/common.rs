@@ -158,12 +158,14 @@ impl From<WorkerServiceError> for ApiEndpointError {
         match error {
             WorkerServiceError::Internal(_) => Self::internal(error),
 
-            WorkerServiceError::FileNotFound(_) => Self::not_found(error),
-
-            WorkerServiceError::TypeChecker(_) | WorkerServiceError::BadFileType(_) => {
-                Self::bad_request(error)
+            WorkerServiceError::FileNotFound(_) | WorkerServiceError::BulkOperationNotFound(_) => {
+                Self::not_found(error)
             }
 
+            WorkerServiceError::TypeChecker(_)
+            | WorkerServiceError::BadFileType(_)
+            | WorkerServiceError::InvalidBulkOperation(_) => Self::bad_request(error),
+
             WorkerServiceError::ComponentNotFound(_)
             | WorkerServiceError::AccountIdNotFound(_)
             | WorkerServiceError::WorkerNotFound(_) => Self::not_found(error),
@@ -209,6 +211,8 @@ impl From<WorkerExecutorError> for ApiEndpointError {
     fn from(error: WorkerExecutorError) -> Self {
         match error {
             WorkerExecutorError::WorkerNotFound { .. } => Self::not_found(error),
+            WorkerExecutorError::AgentQuotaExceeded { .. } => Self::limit_exceeded(error),
+            WorkerExecutorError::ValueMismatch { .. } => Self::bad_request(error),
             WorkerExecutorError::InvocationFailed { error, stderr } => {
                 Self::InternalError(Json(ErrorBodyWithOptionalWorkerError {
                     error: "Invocation Failed".to_string(),
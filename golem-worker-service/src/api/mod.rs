@@ -31,6 +31,7 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<Apis, ()> {
             WorkerApi::new(
                 services.component_service.clone(),
                 services.worker_service.clone(),
+                services.bulk_operation_service.clone(),
                 services.auth_service.clone(),
             ),
             AgentsApi::new(
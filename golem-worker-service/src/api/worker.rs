@@ -17,7 +17,9 @@ use crate::model;
 use crate::service::auth::AuthService;
 use crate::service::component::ComponentService;
 use crate::service::worker::ConnectWorkerStream;
-use crate::service::worker::{InvocationParameters, WorkerService, proxy_worker_connection};
+use crate::service::worker::{
+    BulkOperationService, InvocationParameters, WorkerService, proxy_worker_connection,
+};
 use futures::StreamExt;
 use futures::TryStreamExt;
 use golem_common::model::auth::TokenSecret;
@@ -28,7 +30,11 @@ use golem_common::model::component_metadata::ComponentMetadata;
 use golem_common::model::error::{ErrorBody, ErrorsBody};
 use golem_common::model::oplog::OplogCursor;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::worker::{RevertWorkerTarget, WorkerCreationRequest, WorkerMetadataDto};
+use golem_common::model::worker::{
+    BulkWorkerOperationId, BulkWorkerOperationJob, BulkWorkerOperationRequest,
+    ChangeWorkerLabelsRequest, RecurringInvocation, RevertWorkerTarget, WorkerCreationRequest,
+    WorkerMetadataDto,
+};
 use golem_common::model::{IdempotencyKey, ScanCursor, WorkerFilter, WorkerId};
 use golem_common::{SafeDisplay, recorded_http_api_request};
 use golem_service_base::api_tags::ApiTags;
@@ -54,6 +60,7 @@ type Result<T> = std::result::Result<T, ApiEndpointError>;
 pub struct WorkerApi {
     component_service: Arc<dyn ComponentService>,
     worker_service: Arc<WorkerService>,
+    bulk_operation_service: Arc<BulkOperationService>,
     auth_service: Arc<dyn AuthService>,
 }
 
@@ -62,11 +69,13 @@ impl WorkerApi {
     pub fn new(
         component_service: Arc<dyn ComponentService>,
         worker_service: Arc<WorkerService>,
+        bulk_operation_service: Arc<BulkOperationService>,
         auth_service: Arc<dyn AuthService>,
     ) -> Self {
         Self {
             component_service,
             worker_service,
+            bulk_operation_service,
             auth_service,
         }
     }
@@ -361,6 +370,8 @@ impl WorkerApi {
     /// Completes a promise with a given custom array of bytes.
     /// The promise must be previously created from within the worker, and it's identifier (a combination of a worker identifier and an oplogIdx ) must be sent out to an external caller so it can use this endpoint to mark the promise completed.
     /// The data field is sent back to the worker, and it has no predefined meaning.
+    /// Promises created with a payload type must be completed with the json field instead, which is validated against the declared type.
+    /// If the rejection field is set, the promise is rejected with the given error instead of being completed.
     #[oai(
         path = "/:component_id/workers/:worker_name/complete",
         method = "post",
@@ -396,11 +407,16 @@ impl WorkerApi {
         params: CompleteParameters,
         auth: AuthCtx,
     ) -> Result<Json<bool>> {
-        let CompleteParameters { oplog_idx, data } = params;
+        let data = params.payload();
+        let CompleteParameters {
+            oplog_idx,
+            rejection,
+            ..
+        } = params;
 
         let response = self
             .worker_service
-            .complete_promise(&worker_id, oplog_idx, data, auth)
+            .complete_promise(&worker_id, oplog_idx, data, rejection, auth)
             .await?;
 
         Ok(Json(response))
@@ -686,6 +702,181 @@ impl WorkerApi {
         Ok(Json(model::WorkersMetadataResponse { workers, cursor }))
     }
 
+    /// Start a bulk worker operation
+    ///
+    /// Applies an operation (interrupt, resume, update, delete, revert, plugin activation or deactivation)
+    /// to every worker of the component matching the given filter. The operation runs in the background
+    /// with limited concurrency and rate, and its progress can be followed through the returned job.
+    #[oai(
+        path = "/:component_id/workers/bulk",
+        method = "post",
+        operation_id = "start_bulk_worker_operation"
+    )]
+    async fn start_bulk_worker_operation(
+        &self,
+        component_id: Path<ComponentId>,
+        request: Json<BulkWorkerOperationRequest>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let record = recorded_http_api_request!(
+            "start_bulk_worker_operation",
+            component_id = component_id.0.to_string(),
+            operation = request.0.operation.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .start_bulk_worker_operation_internal(component_id.0, request.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn start_bulk_worker_operation_internal(
+        &self,
+        component_id: ComponentId,
+        request: BulkWorkerOperationRequest,
+        auth: AuthCtx,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let job = self
+            .bulk_operation_service
+            .start(component_id, request, auth)
+            .await?;
+
+        Ok(Json(job))
+    }
+
+    /// Get the progress of a bulk worker operation
+    #[oai(
+        path = "/:component_id/workers/bulk/:operation_id",
+        method = "get",
+        operation_id = "get_bulk_worker_operation"
+    )]
+    async fn get_bulk_worker_operation(
+        &self,
+        component_id: Path<ComponentId>,
+        operation_id: Path<BulkWorkerOperationId>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let record = recorded_http_api_request!(
+            "get_bulk_worker_operation",
+            component_id = component_id.0.to_string(),
+            operation_id = operation_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_bulk_worker_operation_internal(component_id.0, operation_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_bulk_worker_operation_internal(
+        &self,
+        component_id: ComponentId,
+        operation_id: BulkWorkerOperationId,
+        auth: AuthCtx,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let job = self
+            .bulk_operation_service
+            .get(component_id, operation_id, auth)
+            .await?;
+
+        Ok(Json(job))
+    }
+
+    /// Cancel a bulk worker operation
+    ///
+    /// Stops processing further workers. Operations already applied to workers are not undone.
+    #[oai(
+        path = "/:component_id/workers/bulk/:operation_id",
+        method = "delete",
+        operation_id = "cancel_bulk_worker_operation"
+    )]
+    async fn cancel_bulk_worker_operation(
+        &self,
+        component_id: Path<ComponentId>,
+        operation_id: Path<BulkWorkerOperationId>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let record = recorded_http_api_request!(
+            "cancel_bulk_worker_operation",
+            component_id = component_id.0.to_string(),
+            operation_id = operation_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .cancel_bulk_worker_operation_internal(component_id.0, operation_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn cancel_bulk_worker_operation_internal(
+        &self,
+        component_id: ComponentId,
+        operation_id: BulkWorkerOperationId,
+        auth: AuthCtx,
+    ) -> Result<Json<BulkWorkerOperationJob>> {
+        let job = self
+            .bulk_operation_service
+            .cancel(component_id, operation_id, auth)
+            .await?;
+
+        Ok(Json(job))
+    }
+
+    /// Get the retention report of a component
+    ///
+    /// Lists the workers of the component which are going to be deleted by the retention policies of the
+    /// worker executors, together with the reason and the time of their last activity. Nothing gets deleted
+    /// by this call.
+    #[oai(
+        path = "/:component_id/workers/retention-report",
+        method = "get",
+        operation_id = "get_retention_report"
+    )]
+    async fn get_retention_report(
+        &self,
+        component_id: Path<ComponentId>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<GetRetentionReportResponse>> {
+        let record = recorded_http_api_request!(
+            "get_retention_report",
+            component_id = component_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .get_retention_report_internal(component_id.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_retention_report_internal(
+        &self,
+        component_id: ComponentId,
+        auth: AuthCtx,
+    ) -> Result<Json<GetRetentionReportResponse>> {
+        let candidates = self
+            .worker_service
+            .get_retention_report(&component_id, auth)
+            .await?;
+
+        Ok(Json(GetRetentionReportResponse { candidates }))
+    }
+
     /// Resume a worker
     #[oai(
         path = "/:component_id/workers/:worker_name/resume",
@@ -841,6 +1032,147 @@ impl WorkerApi {
         }
     }
 
+    /// Search the oplogs of all the workers of a component
+    ///
+    /// Returns the matching oplog entries of all the workers of the component, newest first. The query
+    /// language is the same as for searching a single worker's oplog, extended with range queries like
+    /// `field:[10 TO 20]` and comparisons like `timestamp:>=2025-01-01`. Requires the oplog search index
+    /// to be enabled on the worker executors.
+    #[oai(
+        path = "/:component_id/workers/oplog-search",
+        method = "get",
+        operation_id = "search_component_oplog"
+    )]
+    async fn search_component_oplog(
+        &self,
+        component_id: Path<ComponentId>,
+        query: Query<String>,
+        count: Query<u64>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<SearchComponentOplogResponse>> {
+        let record = recorded_http_api_request!(
+            "search_component_oplog",
+            component_id = component_id.0.to_string()
+        );
+
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let response = self
+            .search_component_oplog_internal(component_id.0, query.0, count.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn search_component_oplog_internal(
+        &self,
+        component_id: ComponentId,
+        query: String,
+        count: u64,
+        auth: AuthCtx,
+    ) -> Result<Json<SearchComponentOplogResponse>> {
+        let hits = self
+            .worker_service
+            .search_component_oplog(&component_id, query, count, auth)
+            .await?;
+
+        Ok(Json(SearchComponentOplogResponse { hits }))
+    }
+
+    /// Export the oplog of a worker
+    ///
+    /// Returns a self-contained archive of the worker's oplog, including all the externally stored
+    /// payloads and the initial files of the component revisions used by the worker.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/oplog/export",
+        method = "get",
+        operation_id = "export_oplog"
+    )]
+    async fn export_oplog(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        token: GolemSecurityScheme,
+    ) -> Result<Binary<Body>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!("export_oplog", worker_id = worker_id.to_string());
+
+        let response = self
+            .export_oplog_internal(worker_id, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn export_oplog_internal(
+        &self,
+        worker_id: WorkerId,
+        auth: AuthCtx,
+    ) -> Result<Binary<Body>> {
+        let archive = self.worker_service.export_oplog(&worker_id, auth).await?;
+
+        Ok(Binary(Body::from_bytes_stream(
+            archive.map_err(|e| std::io::Error::other(e.to_string())),
+        )))
+    }
+
+    /// Import the oplog of a worker
+    ///
+    /// Creates a new worker with the given name from an archive produced by the export endpoint.
+    /// The worker must not exist yet, and the component must have all the component revisions used
+    /// by the exported worker. The imported worker is recovered on its next invocation.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/oplog/import",
+        method = "post",
+        operation_id = "import_oplog"
+    )]
+    async fn import_oplog(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        archive: Binary<Body>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ImportOplogResponse>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!("import_oplog", worker_id = worker_id.to_string());
+
+        let response = self
+            .import_oplog_internal(worker_id, archive.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn import_oplog_internal(
+        &self,
+        worker_id: WorkerId,
+        archive: Body,
+        auth: AuthCtx,
+    ) -> Result<Json<ImportOplogResponse>> {
+        let last_index = self
+            .worker_service
+            .import_oplog(&worker_id, Box::pin(archive.into_bytes_stream()), auth)
+            .await?;
+
+        Ok(Json(ImportOplogResponse {
+            worker_id,
+            last_index: last_index.into(),
+        }))
+    }
+
     /// List files in a worker
     #[oai(
         path = "/:component_id/workers/:worker_name/files/:file_name",
@@ -1057,6 +1389,59 @@ impl WorkerApi {
         Ok(Json(DeactivatePluginResponse {}))
     }
 
+    /// Change the labels of a worker
+    ///
+    /// Labels are mutable key-value metadata attached to the worker. They can be used to filter
+    /// workers when enumerating them, and are not visible for the running worker.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/labels",
+        method = "post",
+        operation_id = "change_worker_labels"
+    )]
+    async fn change_worker_labels(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        request: Json<ChangeWorkerLabelsRequest>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<ChangeWorkerLabelsResponse>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record =
+            recorded_http_api_request!("change_worker_labels", worker_id = worker_id.to_string());
+
+        let response = self
+            .change_worker_labels_internal(worker_id, request.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn change_worker_labels_internal(
+        &self,
+        worker_id: WorkerId,
+        request: ChangeWorkerLabelsRequest,
+        auth: AuthCtx,
+    ) -> Result<Json<ChangeWorkerLabelsResponse>> {
+        request.validate().map_err(|error| {
+            ApiEndpointError::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+                cause: None,
+            }))
+        })?;
+
+        self.worker_service
+            .change_labels(&worker_id, request.set, request.removed, auth)
+            .await?;
+
+        Ok(Json(ChangeWorkerLabelsResponse {}))
+    }
+
     /// Revert a worker
     ///
     /// Reverts a worker by undoing either the last few invocations or the last few recorded oplog entries.
@@ -1201,6 +1586,182 @@ impl WorkerApi {
         Ok(Json(CancelInvocationResponse { canceled }))
     }
 
+    /// Schedule a recurring invocation
+    ///
+    /// Registers a function to be invoked repeatedly on the worker, either with a fixed interval or following a cron expression (evaluated in UTC).
+    /// Every occurrence is enqueued with an idempotency key derived from the one passed in the `Idempotency-Key` header, so it can be
+    /// tracked independently. Scheduling again with the same idempotency key returns the already registered recurring invocation.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/recurring-invocations",
+        method = "post",
+        operation_id = "schedule_recurring_invocation"
+    )]
+    async fn schedule_recurring_invocation(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
+        request: Json<ScheduleRecurringInvocationRequest>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<RecurringInvocation>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!(
+            "schedule_recurring_invocation",
+            worker_id = worker_id.to_string(),
+            idempotency_key = idempotency_key.0.as_ref().map(|v| v.value.clone()),
+            function = request.0.function.clone(),
+            recurrence = request.0.recurrence.to_string(),
+        );
+
+        let response = self
+            .schedule_recurring_invocation_internal(worker_id, idempotency_key.0, request.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn schedule_recurring_invocation_internal(
+        &self,
+        worker_id: WorkerId,
+        idempotency_key: Option<IdempotencyKey>,
+        request: ScheduleRecurringInvocationRequest,
+        auth: AuthCtx,
+    ) -> Result<Json<RecurringInvocation>> {
+        request.recurrence.validate().map_err(|error| {
+            ApiEndpointError::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+                cause: None,
+            }))
+        })?;
+
+        let params =
+            InvocationParameters::from_optionally_type_annotated_value_jsons(request.params)
+                .map_err(|errors| {
+                    ApiEndpointError::BadRequest(Json(ErrorsBody {
+                        errors,
+                        cause: None,
+                    }))
+                })?;
+
+        let recurring_invocation = self
+            .worker_service
+            .schedule_recurring_invocation(
+                &worker_id,
+                idempotency_key,
+                request.function,
+                params,
+                request.recurrence,
+                request.start_at,
+                None,
+                auth,
+            )
+            .await?;
+
+        Ok(Json(recurring_invocation))
+    }
+
+    /// Get the recurring invocations of a worker
+    ///
+    /// Lists the recurring invocations registered for the worker, together with the time of their next occurrence.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/recurring-invocations",
+        method = "get",
+        operation_id = "get_recurring_invocations"
+    )]
+    async fn get_recurring_invocations(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<GetRecurringInvocationsResponse>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!(
+            "get_recurring_invocations",
+            worker_id = worker_id.to_string(),
+        );
+
+        let response = self
+            .get_recurring_invocations_internal(worker_id, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn get_recurring_invocations_internal(
+        &self,
+        worker_id: WorkerId,
+        auth: AuthCtx,
+    ) -> Result<Json<GetRecurringInvocationsResponse>> {
+        let invocations = self
+            .worker_service
+            .get_recurring_invocations(&worker_id, auth)
+            .await?;
+
+        Ok(Json(GetRecurringInvocationsResponse { invocations }))
+    }
+
+    /// Cancels a recurring invocation
+    ///
+    /// The recurring invocation is identified by the idempotency key it was scheduled with. Occurrences which are
+    /// already enqueued on the worker are not affected.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/recurring-invocations/:idempotency_key",
+        method = "delete",
+        operation_id = "cancel_recurring_invocation"
+    )]
+    async fn cancel_recurring_invocation(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        idempotency_key: Path<IdempotencyKey>,
+        token: GolemSecurityScheme,
+    ) -> Result<Json<CancelRecurringInvocationResponse>> {
+        let auth = self.auth_service.authenticate_token(token.secret()).await?;
+
+        let worker_id = self
+            .normalize_worker_id(component_id.0, worker_name.as_str())
+            .await?;
+
+        let record = recorded_http_api_request!(
+            "cancel_recurring_invocation",
+            worker_id = worker_id.to_string(),
+            idempotency_key = idempotency_key.0.to_string(),
+        );
+
+        let response = self
+            .cancel_recurring_invocation_internal(worker_id, idempotency_key.0, auth)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
+    async fn cancel_recurring_invocation_internal(
+        &self,
+        worker_id: WorkerId,
+        idempotency_key: IdempotencyKey,
+        auth: AuthCtx,
+    ) -> Result<Json<CancelRecurringInvocationResponse>> {
+        let canceled = self
+            .worker_service
+            .cancel_recurring_invocation(&worker_id, &idempotency_key, auth)
+            .await?;
+
+        Ok(Json(CancelRecurringInvocationResponse { canceled }))
+    }
+
     /// Connect to a worker using a websocket and stream events
     #[oai(
         path = "/:component_id/workers/:worker_name/connect",
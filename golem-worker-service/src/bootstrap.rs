@@ -12,14 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::config::{SessionStoreConfig, WorkerServiceConfig};
+use crate::config::{
+    BulkOperationStoreConfig, RateLimitStoreConfig, SessionStoreConfig, WorkerServiceConfig,
+};
 use crate::custom_api::api_definition_lookup::{
     HttpApiDefinitionsLookup, RegistryServiceApiDefinitionsLookup,
 };
 use crate::custom_api::call_agent::CallAgentHandler;
+use crate::custom_api::rate_limit::RateLimiter;
+use crate::custom_api::rate_limit::store::{
+    InMemoryRateLimitStore, RateLimitStore, RedisRateLimitStore, SqliteRateLimitStore,
+};
 use crate::custom_api::request_handler::RequestHandler;
 use crate::custom_api::route_resolver::RouteResolver;
 use crate::custom_api::security::DefaultIdentityProvider;
+use crate::custom_api::security::api_key::{ApiKeyLookup, RegistryServiceApiKeyLookup};
 use crate::custom_api::security::handler::OidcHandler;
 use crate::custom_api::security::session_store::{
     RedisSessionStore, SessionStore, SqliteSessionStore,
@@ -28,7 +35,9 @@ use crate::service::auth::{AuthService, RemoteAuthService};
 use crate::service::component::{ComponentService, RemoteComponentService};
 use crate::service::limit::{LimitService, RemoteLimitService};
 use crate::service::worker::{
-    AgentsService, WorkerClient, WorkerExecutorWorkerClient, WorkerService,
+    AgentsService, BulkOperationService, BulkOperationStore, InMemoryBulkOperationStore,
+    RedisBulkOperationStore, SqliteBulkOperationStore, WorkerClient, WorkerExecutorWorkerClient,
+    WorkerService,
 };
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
 use golem_common::redis::RedisPool;
@@ -45,6 +54,7 @@ pub struct Services {
     pub limit_service: Arc<dyn LimitService>,
     pub component_service: Arc<dyn ComponentService>,
     pub worker_service: Arc<WorkerService>,
+    pub bulk_operation_service: Arc<BulkOperationService>,
     pub request_handler: Arc<RequestHandler>,
     pub agents_service: Arc<AgentsService>,
 }
@@ -94,6 +104,42 @@ impl Services {
             worker_client.clone(),
         ));
 
+        let bulk_operations = &config.bulk_operations;
+        let bulk_operation_store: Arc<dyn BulkOperationStore> = match &bulk_operations.storage {
+            BulkOperationStoreConfig::InMemory(inner) => Arc::new(InMemoryBulkOperationStore::new(
+                bulk_operations.finished_retention,
+                inner.cleanup_interval,
+            )),
+
+            BulkOperationStoreConfig::Redis(inner) => {
+                let redis = RedisPool::configured(inner).await?;
+                Arc::new(RedisBulkOperationStore::new(
+                    redis,
+                    bulk_operations.finished_retention,
+                ))
+            }
+
+            BulkOperationStoreConfig::Sqlite(inner) => {
+                let pool = SqlitePool::configured(&inner.sqlite_config).await?;
+                Arc::new(
+                    SqliteBulkOperationStore::new(
+                        pool,
+                        bulk_operations.finished_retention,
+                        inner.cleanup_interval,
+                    )
+                    .await?,
+                )
+            }
+        };
+
+        let bulk_operation_service = Arc::new(BulkOperationService::new(
+            component_service.clone(),
+            auth_service.clone(),
+            worker_service.clone(),
+            bulk_operation_store,
+            bulk_operations.clone(),
+        ));
+
         let api_definition_lookup_service: Arc<dyn HttpApiDefinitionsLookup> = Arc::new(
             RegistryServiceApiDefinitionsLookup::new(registry_service_client.clone()),
         );
@@ -105,7 +151,12 @@ impl Services {
 
         let call_agent_handler = Arc::new(CallAgentHandler::new(worker_service.clone()));
 
-        let identity_provider = Arc::new(DefaultIdentityProvider);
+        let identity_provider = Arc::new(DefaultIdentityProvider::default());
+
+        let api_key_lookup: Arc<dyn ApiKeyLookup> = Arc::new(RegistryServiceApiKeyLookup::new(
+            &config.api_key_lookup,
+            registry_service_client.clone(),
+        ));
 
         let session_store: Arc<dyn SessionStore> = match &config.gateway_session_storage {
             SessionStoreConfig::Redis(inner) => {
@@ -138,12 +189,37 @@ impl Services {
         let oidc_handler = Arc::new(OidcHandler::new(
             session_store.clone(),
             identity_provider.clone(),
+            api_key_lookup.clone(),
+        ));
+
+        let rate_limit_store: Arc<dyn RateLimitStore> = match &config.rate_limits.storage {
+            RateLimitStoreConfig::InMemory(inner) => {
+                Arc::new(InMemoryRateLimitStore::new(inner.cleanup_interval))
+            }
+
+            RateLimitStoreConfig::Redis(inner) => {
+                let redis = RedisPool::configured(inner).await?;
+                Arc::new(RedisRateLimitStore::new(redis))
+            }
+
+            RateLimitStoreConfig::Sqlite(inner) => {
+                let pool = SqlitePool::configured(&inner.sqlite_config).await?;
+                Arc::new(SqliteRateLimitStore::new(pool, inner.cleanup_interval).await?)
+            }
+        };
+
+        let rate_limiter = Arc::new(RateLimiter::new(
+            rate_limit_store,
+            config.rate_limits.on_store_failure,
+            config.rate_limits.client_ip_header.clone(),
+            config.rate_limits.client_ip_trusted_proxies,
         ));
 
         let request_handler = Arc::new(RequestHandler::new(
             route_resolver.clone(),
             call_agent_handler.clone(),
             oidc_handler.clone(),
+            rate_limiter,
         ));
 
         let agents_service: Arc<AgentsService> = Arc::new(AgentsService::new(
@@ -157,6 +233,7 @@ impl Services {
             limit_service,
             component_service,
             worker_service,
+            bulk_operation_service,
             request_handler,
             agents_service,
         })
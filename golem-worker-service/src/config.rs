@@ -32,6 +32,7 @@ pub struct WorkerServiceConfig {
     pub environment: String,
     pub tracing: TracingConfig,
     pub gateway_session_storage: SessionStoreConfig,
+    pub rate_limits: RateLimitConfig,
     pub port: u16,
     pub custom_request_port: u16,
     pub grpc: GrpcApiConfig,
@@ -41,8 +42,10 @@ pub struct WorkerServiceConfig {
     pub registry_service: GrpcRegistryServiceConfig,
     pub cors_origin_regex: String,
     pub route_resolver: RouteResolverConfig,
+    pub api_key_lookup: ApiKeyLookupConfig,
     pub component_service: ComponentServiceConfig,
     pub auth_service: AuthServiceConfig,
+    pub bulk_operations: BulkOperationsConfig,
 }
 
 impl WorkerServiceConfig {
@@ -63,6 +66,8 @@ impl SafeDisplay for WorkerServiceConfig {
             "{}",
             self.gateway_session_storage.to_safe_string_indented()
         );
+        let _ = writeln!(&mut result, "rate limits:");
+        let _ = writeln!(result, "{}", self.rate_limits.to_safe_string_indented());
         let _ = writeln!(&mut result, "HTTP port: {}", self.port);
         let _ = writeln!(
             &mut result,
@@ -99,6 +104,13 @@ impl SafeDisplay for WorkerServiceConfig {
             self.route_resolver.to_safe_string_indented()
         );
 
+        let _ = writeln!(&mut result, "api key lookup:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.api_key_lookup.to_safe_string_indented()
+        );
+
         let _ = writeln!(&mut result, "component service:");
         let _ = writeln!(
             &mut result,
@@ -113,6 +125,13 @@ impl SafeDisplay for WorkerServiceConfig {
             self.auth_service.to_safe_string_indented()
         );
 
+        let _ = writeln!(&mut result, "bulk operations:");
+        let _ = writeln!(
+            &mut result,
+            "{}",
+            self.bulk_operations.to_safe_string_indented()
+        );
+
         result
     }
 }
@@ -122,6 +141,7 @@ impl Default for WorkerServiceConfig {
         Self {
             environment: "local".to_string(),
             gateway_session_storage: SessionStoreConfig::Redis(Default::default()),
+            rate_limits: RateLimitConfig::default(),
             tracing: TracingConfig::local_dev("worker-service"),
             port: 9005,
             custom_request_port: 9006,
@@ -132,8 +152,10 @@ impl Default for WorkerServiceConfig {
             registry_service: GrpcRegistryServiceConfig::default(),
             cors_origin_regex: "https://*.golem.cloud".to_string(),
             route_resolver: RouteResolverConfig::default(),
+            api_key_lookup: ApiKeyLookupConfig::default(),
             component_service: ComponentServiceConfig::default(),
             auth_service: AuthServiceConfig::default(),
+            bulk_operations: BulkOperationsConfig::default(),
         }
     }
 }
@@ -250,6 +272,129 @@ impl SafeDisplay for SqliteSessionStoreConfig {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Header carrying the client address when running behind a proxy, for example X-Forwarded-For.
+    /// The connection's peer address is used when not set.
+    pub client_ip_header: Option<String>,
+    /// Number of trusted proxies appending to `client_ip_header`. The address appended by the
+    /// outermost trusted proxy is used, as everything left of it can be set by the client.
+    pub client_ip_trusted_proxies: usize,
+    /// How rate limited routes behave while the storage is failing
+    pub on_store_failure: RateLimitStoreFailureMode,
+    pub storage: RateLimitStoreConfig,
+}
+
+impl SafeDisplay for RateLimitConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "client_ip_header: {}",
+            self.client_ip_header.as_deref().unwrap_or("-")
+        );
+        let _ = writeln!(
+            &mut result,
+            "client_ip_trusted_proxies: {}",
+            self.client_ip_trusted_proxies
+        );
+        let _ = writeln!(&mut result, "on_store_failure: {:?}", self.on_store_failure);
+        let _ = writeln!(&mut result, "storage:");
+        let _ = writeln!(&mut result, "{}", self.storage.to_safe_string_indented());
+        result
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            client_ip_header: None,
+            client_ip_trusted_proxies: 1,
+            on_store_failure: RateLimitStoreFailureMode::InMemory,
+            storage: RateLimitStoreConfig::InMemory(InMemoryRateLimitStoreConfig::default()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RateLimitStoreFailureMode {
+    /// Let the requests through without rate limiting them
+    Allow,
+    /// Reject the requests with 503 Service Unavailable
+    Reject,
+    /// Rate limit the requests with buckets kept by the worker service instance. The limits are
+    /// applied per instance, so they are only approximated when running multiple instances.
+    InMemory,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum RateLimitStoreConfig {
+    InMemory(InMemoryRateLimitStoreConfig),
+    Redis(RedisConfig),
+    Sqlite(SqliteRateLimitStoreConfig),
+}
+
+impl SafeDisplay for RateLimitStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            RateLimitStoreConfig::InMemory(in_memory) => {
+                let _ = writeln!(&mut result, "in-memory:");
+                let _ = writeln!(&mut result, "{}", in_memory.to_safe_string_indented());
+            }
+            RateLimitStoreConfig::Redis(redis) => {
+                let _ = writeln!(&mut result, "redis:");
+                let _ = writeln!(&mut result, "{}", redis.to_safe_string_indented());
+            }
+            RateLimitStoreConfig::Sqlite(sqlite) => {
+                let _ = writeln!(&mut result, "sqlite:");
+                let _ = writeln!(&mut result, "{}", sqlite.to_safe_string_indented());
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InMemoryRateLimitStoreConfig {
+    #[serde(with = "humantime_serde")]
+    pub cleanup_interval: std::time::Duration,
+}
+
+impl Default for InMemoryRateLimitStoreConfig {
+    fn default() -> Self {
+        Self {
+            cleanup_interval: Duration::from_mins(1),
+        }
+    }
+}
+
+impl SafeDisplay for InMemoryRateLimitStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "cleanup_interval: {:?}", self.cleanup_interval);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SqliteRateLimitStoreConfig {
+    #[serde(with = "humantime_serde")]
+    pub cleanup_interval: std::time::Duration,
+    #[serde(flatten)]
+    pub sqlite_config: DbSqliteConfig,
+}
+
+impl SafeDisplay for SqliteRateLimitStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "cleanup_interval: {:?}", self.cleanup_interval);
+        let _ = writeln!(&mut result, "{}", self.sqlite_config.to_safe_string());
+        result
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RouteResolverConfig {
     pub router_cache_max_capacity: usize,
@@ -287,6 +432,49 @@ impl Default for RouteResolverConfig {
     }
 }
 
+/// Caching of the api keys of security schemes. Kept much shorter than the router cache, as it
+/// bounds how long a revoked key is still accepted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiKeyLookupConfig {
+    pub api_key_cache_max_capacity: usize,
+    #[serde(with = "humantime_serde")]
+    pub api_key_cache_ttl: Duration,
+    #[serde(with = "humantime_serde")]
+    pub api_key_cache_eviction_period: Duration,
+}
+
+impl SafeDisplay for ApiKeyLookupConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(
+            &mut result,
+            "api_key_cache_max_capacity: {}",
+            self.api_key_cache_max_capacity
+        );
+        let _ = writeln!(
+            &mut result,
+            "api_key_cache_ttl: {:?}",
+            self.api_key_cache_ttl
+        );
+        let _ = writeln!(
+            &mut result,
+            "api_key_cache_eviction_period: {:?}",
+            self.api_key_cache_eviction_period
+        );
+        result
+    }
+}
+
+impl Default for ApiKeyLookupConfig {
+    fn default() -> Self {
+        Self {
+            api_key_cache_max_capacity: 1024,
+            api_key_cache_ttl: Duration::from_secs(10),
+            api_key_cache_eviction_period: Duration::from_secs(1),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ComponentServiceConfig {
     pub component_cache_max_capacity: usize,
@@ -381,6 +569,139 @@ impl Default for AuthServiceConfig {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BulkOperationsConfig {
+    /// Upper limit of the number of workers processed at the same time by a single bulk operation
+    pub max_concurrency: u32,
+    /// Upper limit of the number of workers processed per second by a single bulk operation
+    pub max_rate: u32,
+    /// Number of worker metadata entries fetched at once while enumerating the matched workers
+    pub page_size: u64,
+    /// Number of individual failures kept in the progress report of a bulk operation
+    pub max_reported_failures: usize,
+    /// How long the progress of a finished bulk operation remains queryable
+    #[serde(with = "humantime_serde")]
+    pub finished_retention: Duration,
+    /// Running operations whose progress was not saved for this long are taken over by another
+    /// worker service instance
+    #[serde(with = "humantime_serde")]
+    pub abandoned_after: Duration,
+    #[serde(with = "humantime_serde")]
+    pub abandoned_check_interval: Duration,
+    pub storage: BulkOperationStoreConfig,
+}
+
+impl SafeDisplay for BulkOperationsConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "max_concurrency: {}", self.max_concurrency);
+        let _ = writeln!(&mut result, "max_rate: {}", self.max_rate);
+        let _ = writeln!(&mut result, "page_size: {}", self.page_size);
+        let _ = writeln!(
+            &mut result,
+            "max_reported_failures: {}",
+            self.max_reported_failures
+        );
+        let _ = writeln!(
+            &mut result,
+            "finished_retention: {:?}",
+            self.finished_retention
+        );
+        let _ = writeln!(&mut result, "abandoned_after: {:?}", self.abandoned_after);
+        let _ = writeln!(
+            &mut result,
+            "abandoned_check_interval: {:?}",
+            self.abandoned_check_interval
+        );
+        let _ = writeln!(&mut result, "storage:");
+        let _ = writeln!(&mut result, "{}", self.storage.to_safe_string_indented());
+        result
+    }
+}
+
+impl Default for BulkOperationsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 32,
+            max_rate: 200,
+            page_size: 1000,
+            max_reported_failures: 100,
+            finished_retention: Duration::from_hours(24),
+            abandoned_after: Duration::from_mins(5),
+            abandoned_check_interval: Duration::from_mins(1),
+            storage: BulkOperationStoreConfig::Redis(RedisConfig::default()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum BulkOperationStoreConfig {
+    InMemory(InMemoryBulkOperationStoreConfig),
+    Redis(RedisConfig),
+    Sqlite(SqliteBulkOperationStoreConfig),
+}
+
+impl SafeDisplay for BulkOperationStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        match self {
+            BulkOperationStoreConfig::InMemory(in_memory) => {
+                let _ = writeln!(&mut result, "in-memory:");
+                let _ = writeln!(&mut result, "{}", in_memory.to_safe_string_indented());
+            }
+            BulkOperationStoreConfig::Redis(redis) => {
+                let _ = writeln!(&mut result, "redis:");
+                let _ = writeln!(&mut result, "{}", redis.to_safe_string_indented());
+            }
+            BulkOperationStoreConfig::Sqlite(sqlite) => {
+                let _ = writeln!(&mut result, "sqlite:");
+                let _ = writeln!(&mut result, "{}", sqlite.to_safe_string_indented());
+            }
+        }
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InMemoryBulkOperationStoreConfig {
+    #[serde(with = "humantime_serde")]
+    pub cleanup_interval: std::time::Duration,
+}
+
+impl Default for InMemoryBulkOperationStoreConfig {
+    fn default() -> Self {
+        Self {
+            cleanup_interval: Duration::from_mins(10),
+        }
+    }
+}
+
+impl SafeDisplay for InMemoryBulkOperationStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "cleanup_interval: {:?}", self.cleanup_interval);
+        result
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SqliteBulkOperationStoreConfig {
+    #[serde(with = "humantime_serde")]
+    pub cleanup_interval: std::time::Duration,
+    #[serde(flatten)]
+    pub sqlite_config: DbSqliteConfig,
+}
+
+impl SafeDisplay for SqliteBulkOperationStoreConfig {
+    fn to_safe_string(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(&mut result, "cleanup_interval: {:?}", self.cleanup_interval);
+        let _ = writeln!(&mut result, "{}", self.sqlite_config.to_safe_string());
+        result
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WorkerExecutorClientConfig {
     pub retries: RetryConfig,
@@ -269,6 +269,10 @@ impl CallAgentHandler {
 }
 
 fn principal_from_request(request: &RichRequest) -> Result<Principal, RequestHandlerError> {
+    if let Some(api_key) = request.authenticated_api_key() {
+        return Ok(Principal::ApiKey(api_key.clone()));
+    }
+
     match request.authenticated_session() {
         Some(session) => Ok(Principal::Oidc(OidcPrincipal {
             sub: session.subject.clone(),
@@ -281,7 +285,7 @@ fn principal_from_request(request: &RichRequest) -> Result<Principal, RequestHan
             picture: session.picture.clone(),
             preferred_username: session.preferred_username.clone(),
             claims: serde_json::to_string(&session.claims)
-                .map_err(|e| anyhow!("CoreIdTokenClaims serialization error: {e}"))?,
+                .map_err(|e| anyhow!("Claims serialization error: {e}"))?,
         })),
         None => Ok(Principal::anonymous()),
     }
@@ -17,6 +17,7 @@ use super::security::IdentityProviderError;
 use super::security::session_store::SessionStoreError;
 use crate::service::worker::WorkerServiceError;
 use golem_common::{SafeDisplay, error_forwarding};
+use golem_service_base::clients::registry::RegistryServiceError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum RequestHandlerError {
@@ -93,5 +94,6 @@ impl SafeDisplay for RequestHandlerError {
 error_forwarding!(
     RequestHandlerError,
     SessionStoreError,
-    IdentityProviderError
+    IdentityProviderError,
+    RegistryServiceError
 );
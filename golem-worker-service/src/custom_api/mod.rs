@@ -18,6 +18,7 @@ mod cors;
 pub mod error;
 pub mod model;
 pub mod poem_endpoint;
+pub mod rate_limit;
 pub mod request_handler;
 pub mod route_resolver;
 pub mod router;
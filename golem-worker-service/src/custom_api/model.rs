@@ -15,16 +15,17 @@
 use super::error::RequestHandlerError;
 use chrono::{DateTime, Utc};
 use golem_common::model::account::AccountId;
-use golem_common::model::agent::BinarySource;
+use golem_common::model::agent::{ApiKeyPrincipal, BinarySource};
 use golem_common::model::environment::EnvironmentId;
 use golem_service_base::custom_api::{
-    CallAgentBehaviour, CorsOptions, CorsPreflightBehaviour, SecuritySchemeDetails,
+    CallAgentBehaviour, CorsOptions, CorsPreflightBehaviour, OidcSecuritySchemeDetails, RateLimit,
+    SecuritySchemeDetails,
 };
 use golem_service_base::custom_api::{PathSegment, RequestBodySchema, RouteBehaviour, RouteId};
 use http::{HeaderMap, Method};
 use http::{HeaderName, StatusCode};
-use openidconnect::Scope;
-use openidconnect::core::CoreIdTokenClaims;
+use openidconnect::core::CoreGenderClaim;
+use openidconnect::{AdditionalClaims, IdTokenClaims, Scope};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::{Arc, OnceLock};
@@ -36,6 +37,7 @@ pub struct RichRequest {
     pub underlying: poem::Request,
     pub request_id: Uuid,
     pub authenticated_session: Option<OidcSession>,
+    pub authenticated_api_key: Option<ApiKeyPrincipal>,
 
     parsed_cookies: OnceLock<HashMap<String, String>>,
     parsed_query_params: OnceLock<HashMap<String, Vec<String>>>,
@@ -47,6 +49,7 @@ impl RichRequest {
             underlying,
             request_id: Uuid::new_v4(),
             authenticated_session: None,
+            authenticated_api_key: None,
             parsed_cookies: OnceLock::new(),
             parsed_query_params: OnceLock::new(),
         }
@@ -121,6 +124,14 @@ impl RichRequest {
     pub fn authenticated_session(&self) -> Option<&OidcSession> {
         self.authenticated_session.as_ref()
     }
+
+    pub fn set_authenticated_api_key(&mut self, api_key: ApiKeyPrincipal) {
+        self.authenticated_api_key = Some(api_key);
+    }
+
+    pub fn authenticated_api_key(&self) -> Option<&ApiKeyPrincipal> {
+        self.authenticated_api_key.as_ref()
+    }
 }
 
 pub struct OidcSession {
@@ -135,12 +146,47 @@ pub struct OidcSession {
     pub picture: Option<String>,
     pub preferred_username: Option<String>,
 
-    pub claims: CoreIdTokenClaims,
+    /// All claims of the ID token, or of the bearer token for stateless authentication
+    pub claims: serde_json::Value,
     pub scopes: HashSet<Scope>,
     pub expires_at: DateTime<Utc>,
 }
 
 impl OidcSession {
+    pub fn from_claims<AC: AdditionalClaims>(
+        claims: &IdTokenClaims<AC, CoreGenderClaim>,
+        scopes: HashSet<Scope>,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            subject: claims.subject().to_string(),
+            issuer: claims.issuer().to_string(),
+
+            email: claims.email().map(|v| v.to_string()),
+            name: claims
+                .name()
+                .and_then(|v| v.get(None))
+                .map(|v| v.to_string()),
+            email_verified: claims.email_verified(),
+            given_name: claims
+                .given_name()
+                .and_then(|v| v.get(None))
+                .map(|v| v.to_string()),
+            family_name: claims
+                .family_name()
+                .and_then(|v| v.get(None))
+                .map(|v| v.to_string()),
+            picture: claims
+                .picture()
+                .and_then(|v| v.get(None))
+                .map(|v| v.to_string()),
+            preferred_username: claims.preferred_username().map(|v| v.to_string()),
+
+            claims: serde_json::to_value(claims)?,
+            scopes,
+            expires_at: claims.expiration(),
+        })
+    }
+
     pub fn is_expired(&self) -> bool {
         Utc::now() >= self.expires_at
     }
@@ -160,7 +206,9 @@ pub struct RichCompiledRoute {
     pub body: RequestBodySchema,
     pub behavior: RichRouteBehaviour,
     pub security_scheme: Option<Arc<SecuritySchemeDetails>>,
+    pub required_scopes: Vec<Scope>,
     pub cors: CorsOptions,
+    pub rate_limits: Vec<RateLimit>,
 }
 
 #[derive(Debug)]
@@ -168,6 +216,7 @@ pub enum RichRouteBehaviour {
     CallAgent(CallAgentBehaviour),
     CorsPreflight(CorsPreflightBehaviour),
     OidcCallback(OidcCallbackBehaviour),
+    OpenApiSpec(OpenApiSpecBehaviour),
 }
 
 impl From<RouteBehaviour> for RichRouteBehaviour {
@@ -181,7 +230,12 @@ impl From<RouteBehaviour> for RichRouteBehaviour {
 
 #[derive(Debug)]
 pub struct OidcCallbackBehaviour {
-    pub security_scheme: Arc<SecuritySchemeDetails>,
+    pub security_scheme: Arc<OidcSecuritySchemeDetails>,
+}
+
+#[derive(Debug)]
+pub struct OpenApiSpecBehaviour {
+    pub spec: Arc<serde_json::Value>,
 }
 
 #[derive(Debug)]
@@ -195,6 +249,7 @@ pub enum ResponseBody {
     NoBody,
     ComponentModelJsonBody { body: golem_wasm::ValueAndType },
     UnstructuredBinaryBody { body: BinarySource },
+    JsonBody { body: Arc<serde_json::Value> },
 }
 
 impl fmt::Debug for ResponseBody {
@@ -206,6 +261,7 @@ impl fmt::Debug for ResponseBody {
                 .field("body", body)
                 .finish(),
             ResponseBody::UnstructuredBinaryBody { .. } => f.write_str("UnstructuredBinaryBody"),
+            ResponseBody::JsonBody { .. } => f.write_str("JsonBody"),
         }
     }
 }
@@ -0,0 +1,375 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod store;
+
+use self::store::{InMemoryRateLimitStore, RateLimitStore};
+use super::model::RichRequest;
+use super::route_resolver::ResolvedRouteEntry;
+use super::{ResponseBody, RouteExecutionResult};
+use crate::config::RateLimitStoreFailureMode;
+use crate::metrics::record_rate_limit_store_failure;
+use chrono::{DateTime, Utc};
+use golem_service_base::custom_api::{RateLimit, RateLimitScope};
+use http::StatusCode;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+const FALLBACK_STORE_CLEANUP_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Point of the request handling at which a group of rate limits is applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitPhase {
+    /// Client IP and route scoped limits, checked before authenticating the caller so
+    /// unauthenticated traffic cannot exhaust the identity providers and key lookups
+    BeforeAuthentication,
+    /// Principal scoped limits, which need the authenticated caller
+    AfterAuthentication,
+}
+
+impl RateLimitPhase {
+    fn applies_to(self, scope: &RateLimitScope) -> bool {
+        match scope {
+            RateLimitScope::ClientIp | RateLimitScope::Route => {
+                self == RateLimitPhase::BeforeAuthentication
+            }
+            RateLimitScope::Principal => self == RateLimitPhase::AfterAuthentication,
+        }
+    }
+}
+
+enum OnStoreFailure {
+    Allow,
+    Reject,
+    InMemory(InMemoryRateLimitStore),
+}
+
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    on_store_failure: OnStoreFailure,
+    client_ip_header: Option<String>,
+    client_ip_trusted_proxies: usize,
+}
+
+impl RateLimiter {
+    pub fn new(
+        store: Arc<dyn RateLimitStore>,
+        on_store_failure: RateLimitStoreFailureMode,
+        client_ip_header: Option<String>,
+        client_ip_trusted_proxies: usize,
+    ) -> Self {
+        let on_store_failure = match on_store_failure {
+            RateLimitStoreFailureMode::Allow => OnStoreFailure::Allow,
+            RateLimitStoreFailureMode::Reject => OnStoreFailure::Reject,
+            RateLimitStoreFailureMode::InMemory => OnStoreFailure::InMemory(
+                InMemoryRateLimitStore::new(FALLBACK_STORE_CLEANUP_INTERVAL),
+            ),
+        };
+
+        Self {
+            store,
+            on_store_failure,
+            client_ip_header,
+            client_ip_trusted_proxies,
+        }
+    }
+
+    /// Takes a token from the bucket of each rate limit of the route applied in the given phase if
+    /// all of them have one, otherwise short-circuits the request with 429 Too Many Requests
+    /// without consuming any. Failures of the store are handled as configured by
+    /// [`RateLimitStoreFailureMode`].
+    pub async fn apply_rate_limits(
+        &self,
+        request: &RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+        phase: RateLimitPhase,
+    ) -> Option<RouteExecutionResult> {
+        let buckets = resolved_route
+            .route
+            .rate_limits
+            .iter()
+            .filter(|rate_limit| phase.applies_to(&rate_limit.scope))
+            .map(|rate_limit| {
+                (
+                    self.bucket_key(request, resolved_route, rate_limit),
+                    rate_limit,
+                )
+            })
+            .collect::<Vec<_>>();
+        if buckets.is_empty() {
+            return None;
+        }
+
+        self.try_take_all(&buckets, Utc::now()).await
+    }
+
+    async fn try_take_all(
+        &self,
+        buckets: &[(String, &RateLimit)],
+        now: DateTime<Utc>,
+    ) -> Option<RouteExecutionResult> {
+        let wait = match self.store.try_take_all(buckets, now).await {
+            Ok(wait) => wait,
+            Err(err) => {
+                record_rate_limit_store_failure();
+                let keys = buckets
+                    .iter()
+                    .map(|(key, _)| key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                match &self.on_store_failure {
+                    OnStoreFailure::Allow => {
+                        warn!("Failed to apply rate limits {keys}, allowing the request: {err}");
+                        None
+                    }
+                    OnStoreFailure::Reject => {
+                        warn!("Failed to apply rate limits {keys}, rejecting the request: {err}");
+                        return Some(service_unavailable());
+                    }
+                    OnStoreFailure::InMemory(fallback_store) => {
+                        warn!("Failed to apply rate limits {keys}, applying them in memory: {err}");
+                        fallback_store
+                            .try_take_all(buckets, now)
+                            .await
+                            .ok()
+                            .flatten()
+                    }
+                }
+            }
+        };
+
+        wait.map(too_many_requests)
+    }
+
+    fn bucket_key(
+        &self,
+        request: &RichRequest,
+        resolved_route: &ResolvedRouteEntry,
+        rate_limit: &RateLimit,
+    ) -> String {
+        let route = &resolved_route.route;
+        let caller = match rate_limit.scope {
+            RateLimitScope::Route => "route".to_string(),
+            RateLimitScope::Principal => self.principal_key(request),
+            RateLimitScope::ClientIp => format!("ip:{}", self.client_ip(request)),
+        };
+
+        // Limits with different parameters count separately, so a short term limit and a quota
+        // can be combined for the same scope
+        format!(
+            "{}:{}:/{}:{}:{}/{}s/{}",
+            resolved_route.domain,
+            route.method,
+            route
+                .path
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+            caller,
+            rate_limit.requests,
+            rate_limit.interval_seconds,
+            rate_limit.burst
+        )
+    }
+
+    fn principal_key(&self, request: &RichRequest) -> String {
+        if let Some(api_key) = request.authenticated_api_key() {
+            format!("api-key:{}", api_key.key_id)
+        } else if let Some(session) = request.authenticated_session() {
+            format!("oidc:{}:{}", session.issuer, session.subject)
+        } else {
+            format!("ip:{}", self.client_ip(request))
+        }
+    }
+
+    fn client_ip(&self, request: &RichRequest) -> String {
+        let forwarded = self.client_ip_header.as_ref().and_then(|header| {
+            forwarded_client_ip(
+                request
+                    .underlying
+                    .headers()
+                    .get_all(header.as_str())
+                    .iter()
+                    .filter_map(|value| value.to_str().ok()),
+                self.client_ip_trusted_proxies,
+            )
+        });
+
+        match forwarded {
+            Some(ip) => ip.to_string(),
+            None => {
+                let remote_addr = request.underlying.remote_addr();
+                match remote_addr.as_socket_addr() {
+                    Some(socket_addr) => socket_addr.ip().to_string(),
+                    None => remote_addr.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Picks the address appended by the outermost of the trusted proxies from the (possibly repeated)
+/// forwarding header. Entries left of it are untrusted, as the client can send the header itself.
+fn forwarded_client_ip<'a>(
+    values: impl Iterator<Item = &'a str>,
+    trusted_proxies: usize,
+) -> Option<&'a str> {
+    if trusted_proxies == 0 {
+        return None;
+    }
+
+    let hops = values
+        .flat_map(|value| value.split(','))
+        .map(|hop| hop.trim())
+        .collect::<Vec<_>>();
+
+    hops.len()
+        .checked_sub(trusted_proxies)
+        .and_then(|index| hops.get(index))
+        .copied()
+        .filter(|ip| !ip.is_empty())
+}
+
+fn service_unavailable() -> RouteExecutionResult {
+    RouteExecutionResult {
+        status: StatusCode::SERVICE_UNAVAILABLE,
+        headers: HashMap::new(),
+        body: ResponseBody::NoBody,
+    }
+}
+
+fn too_many_requests(retry_after: Duration) -> RouteExecutionResult {
+    // Retry-After only supports whole seconds, round up to not invite retries that fail again
+    let retry_after_seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+    RouteExecutionResult {
+        status: StatusCode::TOO_MANY_REQUESTS,
+        headers: HashMap::from([(
+            http::header::RETRY_AFTER,
+            retry_after_seconds.max(1).to_string(),
+        )]),
+        body: ResponseBody::NoBody,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::store::RateLimitStoreError;
+    use super::*;
+    use anyhow::anyhow;
+    use async_trait::async_trait;
+    use test_r::test;
+
+    struct FailingRateLimitStore;
+
+    #[async_trait]
+    impl RateLimitStore for FailingRateLimitStore {
+        async fn try_take_all(
+            &self,
+            _buckets: &[(String, &RateLimit)],
+            _now: DateTime<Utc>,
+        ) -> Result<Option<Duration>, RateLimitStoreError> {
+            Err(anyhow!("store unavailable").into())
+        }
+    }
+
+    fn rate_limiter_with_failing_store(on_store_failure: RateLimitStoreFailureMode) -> RateLimiter {
+        RateLimiter::new(Arc::new(FailingRateLimitStore), on_store_failure, None, 1)
+    }
+
+    fn single_request_limit() -> RateLimit {
+        RateLimit {
+            scope: RateLimitScope::Route,
+            requests: 1,
+            interval_seconds: 60,
+            burst: 1,
+        }
+    }
+
+    #[test]
+    async fn store_failures_are_handled_as_configured() {
+        let rate_limit = single_request_limit();
+        let buckets = [("route".to_string(), &rate_limit)];
+        let now = Utc::now();
+
+        let allowing = rate_limiter_with_failing_store(RateLimitStoreFailureMode::Allow);
+        assert!(allowing.try_take_all(&buckets, now).await.is_none());
+        assert!(allowing.try_take_all(&buckets, now).await.is_none());
+
+        let rejecting = rate_limiter_with_failing_store(RateLimitStoreFailureMode::Reject);
+        let result = rejecting.try_take_all(&buckets, now).await;
+        assert_eq!(
+            result.map(|result| result.status),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+
+        let in_memory = rate_limiter_with_failing_store(RateLimitStoreFailureMode::InMemory);
+        assert!(in_memory.try_take_all(&buckets, now).await.is_none());
+        let result = in_memory.try_take_all(&buckets, now).await;
+        assert_eq!(
+            result.map(|result| result.status),
+            Some(StatusCode::TOO_MANY_REQUESTS)
+        );
+    }
+
+    #[test]
+    fn retry_after_is_rounded_up_to_whole_seconds() {
+        let result = too_many_requests(Duration::from_millis(1500));
+        assert_eq!(result.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(result.headers[&http::header::RETRY_AFTER], "2");
+
+        let result = too_many_requests(Duration::from_millis(10));
+        assert_eq!(result.headers[&http::header::RETRY_AFTER], "1");
+
+        let result = too_many_requests(Duration::from_secs(3));
+        assert_eq!(result.headers[&http::header::RETRY_AFTER], "3");
+    }
+
+    #[test]
+    fn only_principal_limits_wait_for_authentication() {
+        let before = RateLimitPhase::BeforeAuthentication;
+        let after = RateLimitPhase::AfterAuthentication;
+
+        assert!(before.applies_to(&RateLimitScope::ClientIp));
+        assert!(before.applies_to(&RateLimitScope::Route));
+        assert!(!before.applies_to(&RateLimitScope::Principal));
+
+        assert!(!after.applies_to(&RateLimitScope::ClientIp));
+        assert!(!after.applies_to(&RateLimitScope::Route));
+        assert!(after.applies_to(&RateLimitScope::Principal));
+    }
+
+    #[test]
+    fn forwarded_client_ip_ignores_hops_added_by_the_client() {
+        let spoofed = ["1.1.1.1, 2.2.2.2, 3.3.3.3"];
+
+        assert_eq!(forwarded_client_ip(spoofed.into_iter(), 1), Some("3.3.3.3"));
+        assert_eq!(forwarded_client_ip(spoofed.into_iter(), 2), Some("2.2.2.2"));
+        assert_eq!(forwarded_client_ip(spoofed.into_iter(), 0), None);
+        assert_eq!(forwarded_client_ip(spoofed.into_iter(), 4), None);
+    }
+
+    #[test]
+    fn forwarded_client_ip_joins_repeated_headers() {
+        let values = ["1.1.1.1", "2.2.2.2,3.3.3.3"];
+
+        assert_eq!(forwarded_client_ip(values.into_iter(), 1), Some("3.3.3.3"));
+        assert_eq!(forwarded_client_ip(values.into_iter(), 3), Some("1.1.1.1"));
+        assert_eq!(forwarded_client_ip(["2.2.2.2, "].into_iter(), 1), None);
+    }
+}
@@ -0,0 +1,499 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::error_forwarding;
+use golem_common::redis::{RedisError, RedisPool};
+use golem_service_base::custom_api::RateLimit;
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::repo::RepoError;
+use sqlx::Row;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task;
+use tokio::time::interval;
+use tracing::{Instrument, error};
+
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitStoreError {
+    #[error(transparent)]
+    InternalError(#[from] anyhow::Error),
+}
+
+error_forwarding!(RateLimitStoreError, RepoError);
+
+impl From<RedisError> for RateLimitStoreError {
+    fn from(value: RedisError) -> Self {
+        Self::InternalError(anyhow::Error::from(value).context("RedisError"))
+    }
+}
+
+/// State of a single token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    pub tokens: f64,
+    /// Unix timestamp of the last refill in milliseconds
+    pub refilled_at: i64,
+}
+
+impl TokenBucket {
+    pub fn full(rate_limit: &RateLimit, now: i64) -> Self {
+        Self {
+            tokens: rate_limit.burst as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Refills the bucket for the time passed since the last refill
+    pub fn refill(&mut self, rate_limit: &RateLimit, now: i64) {
+        let elapsed_seconds = (now - self.refilled_at).max(0) as f64 / 1000.0;
+        self.tokens = (self.tokens + elapsed_seconds * rate_limit.refill_per_second())
+            .min(rate_limit.burst as f64);
+        self.refilled_at = self.refilled_at.max(now);
+    }
+
+    /// Returns how long the caller has to wait for the next token if the bucket is empty
+    pub fn time_to_next_token(&self, rate_limit: &RateLimit) -> Option<Duration> {
+        if self.tokens >= 1.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - self.tokens) / rate_limit.refill_per_second(),
+            ))
+        }
+    }
+
+    /// Refills the bucket for the time passed since the last refill and takes a token from it.
+    /// Returns how long the caller has to wait for the next token if the bucket is empty.
+    pub fn try_take(&mut self, rate_limit: &RateLimit, now: i64) -> Option<Duration> {
+        take_from_all(&mut [(self, rate_limit)], now)
+    }
+
+    /// Unix timestamp in milliseconds after which the untouched bucket is full again,
+    /// so forgetting it makes no difference
+    pub fn expires_at(&self, rate_limit: &RateLimit) -> i64 {
+        self.refilled_at + time_to_fill(rate_limit).as_millis() as i64
+    }
+}
+
+/// Refills the buckets and takes a token from each of them if all have one. Otherwise none of
+/// them is consumed and the longest wait for a token is returned.
+fn take_from_all(buckets: &mut [(&mut TokenBucket, &RateLimit)], now: i64) -> Option<Duration> {
+    let mut wait = None;
+    for (bucket, rate_limit) in buckets.iter_mut() {
+        bucket.refill(rate_limit, now);
+        wait = wait.max(bucket.time_to_next_token(rate_limit));
+    }
+
+    if wait.is_none() {
+        for (bucket, _) in buckets.iter_mut() {
+            bucket.tokens -= 1.0;
+        }
+    }
+
+    wait
+}
+
+fn time_to_fill(rate_limit: &RateLimit) -> Duration {
+    Duration::from_secs_f64(rate_limit.burst as f64 / rate_limit.refill_per_second())
+}
+
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Takes a token from each of the buckets stored under the given keys if all of them have one.
+    /// Otherwise takes none, returning how long to wait for the next token of the emptiest bucket.
+    async fn try_take_all(
+        &self,
+        buckets: &[(String, &RateLimit)],
+        now: DateTime<Utc>,
+    ) -> Result<Option<Duration>, RateLimitStoreError>;
+}
+
+/// Keeps the buckets of a single worker service instance
+pub struct InMemoryRateLimitStore {
+    buckets: Arc<Mutex<HashMap<String, (TokenBucket, i64)>>>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new(cleanup_interval: Duration) -> Self {
+        let buckets = Arc::new(Mutex::new(HashMap::new()));
+        Self::spawn_expiration_task(buckets.clone(), cleanup_interval);
+        Self { buckets }
+    }
+
+    fn spawn_expiration_task(
+        buckets: Arc<Mutex<HashMap<String, (TokenBucket, i64)>>>,
+        cleanup_interval: Duration,
+    ) {
+        task::spawn(
+            async move {
+                let mut cleanup_interval = interval(cleanup_interval);
+
+                loop {
+                    cleanup_interval.tick().await;
+
+                    let now = Utc::now().timestamp_millis();
+                    buckets
+                        .lock()
+                        .unwrap()
+                        .retain(|_, (_, expires_at)| *expires_at > now);
+                }
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn try_take_all(
+        &self,
+        buckets: &[(String, &RateLimit)],
+        now: DateTime<Utc>,
+    ) -> Result<Option<Duration>, RateLimitStoreError> {
+        let now = now.timestamp_millis();
+        let mut stored_buckets = self.buckets.lock().unwrap();
+
+        let mut updated_buckets = buckets
+            .iter()
+            .map(|(key, rate_limit)| {
+                stored_buckets
+                    .get(key)
+                    .map(|(bucket, _)| *bucket)
+                    .unwrap_or_else(|| TokenBucket::full(rate_limit, now))
+            })
+            .collect::<Vec<_>>();
+
+        let result = take_from_all(
+            &mut updated_buckets
+                .iter_mut()
+                .zip(buckets)
+                .map(|(bucket, (_, rate_limit))| (bucket, *rate_limit))
+                .collect::<Vec<_>>(),
+            now,
+        );
+
+        for (bucket, (key, rate_limit)) in updated_buckets.into_iter().zip(buckets) {
+            stored_buckets.insert(key.clone(), (bucket, bucket.expires_at(rate_limit)));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Shares the buckets between all worker service instances using the same redis
+pub struct RedisRateLimitStore {
+    redis: RedisPool,
+}
+
+impl RedisRateLimitStore {
+    // Same algorithm as take_from_all, evaluated atomically by redis. ARGV[1] is the current
+    // time, followed by the burst, refill rate and expiration of each key.
+    // Returns the number of milliseconds to wait for the next token, 0 if the tokens were taken.
+    const TRY_TAKE_ALL_SCRIPT: &'static str = r#"
+        local now = tonumber(ARGV[1])
+        local tokens = {}
+        local refilled_at = {}
+        local wait = 0
+        for i, key in ipairs(KEYS) do
+            local burst = tonumber(ARGV[3 * i - 1])
+            local refill_per_ms = tonumber(ARGV[3 * i])
+            local bucket = redis.call('HMGET', key, 'tokens', 'refilled_at')
+            local bucket_refilled_at = tonumber(bucket[2]) or now
+            tokens[i] = math.min(burst, (tonumber(bucket[1]) or burst) + math.max(0, now - bucket_refilled_at) * refill_per_ms)
+            refilled_at[i] = math.max(bucket_refilled_at, now)
+            if tokens[i] < 1 then
+                wait = math.max(wait, 1, math.ceil((1 - tokens[i]) / refill_per_ms))
+            end
+        end
+        for i, key in ipairs(KEYS) do
+            if wait == 0 then
+                tokens[i] = tokens[i] - 1
+            end
+            redis.call('HSET', key, 'tokens', tostring(tokens[i]), 'refilled_at', tostring(refilled_at[i]))
+            redis.call('PEXPIRE', key, ARGV[3 * i + 1])
+        end
+        return wait
+    "#;
+
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn redis_key_for_bucket(key: &str) -> String {
+        format!("http_rate_limit:{key}")
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn try_take_all(
+        &self,
+        buckets: &[(String, &RateLimit)],
+        now: DateTime<Utc>,
+    ) -> Result<Option<Duration>, RateLimitStoreError> {
+        let keys = buckets
+            .iter()
+            .map(|(key, _)| Self::redis_key_for_bucket(key))
+            .collect::<Vec<_>>();
+
+        let mut args = vec![now.timestamp_millis().to_string()];
+        for (_, rate_limit) in buckets {
+            let refill_per_ms = rate_limit.refill_per_second() / 1000.0;
+            let ttl_ms = (time_to_fill(rate_limit).as_millis() as u64).max(1);
+
+            args.push(rate_limit.burst.to_string());
+            args.push(refill_per_ms.to_string());
+            args.push(ttl_ms.to_string());
+        }
+
+        let wait_ms: u64 = self
+            .redis
+            .with("rate_limit_store", "try_take_all")
+            .eval(Self::TRY_TAKE_ALL_SCRIPT, keys, args)
+            .await?;
+
+        if wait_ms == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(Duration::from_millis(wait_ms)))
+        }
+    }
+}
+
+/// Keeps the buckets of the worker service instances sharing the same database file
+pub struct SqliteRateLimitStore {
+    pool: SqlitePool,
+}
+
+impl SqliteRateLimitStore {
+    pub async fn new(pool: SqlitePool, cleanup_interval: Duration) -> anyhow::Result<Self> {
+        Self::init(&pool).await?;
+        Self::spawn_expiration_task(pool.clone(), cleanup_interval);
+        Ok(Self { pool })
+    }
+
+    async fn init(pool: &SqlitePool) -> anyhow::Result<()> {
+        pool.with_rw("rate_limit_store", "init")
+            .execute(sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS http_rate_limit_bucket (
+                    bucket_key TEXT PRIMARY KEY,
+                    tokens REAL NOT NULL,
+                    refilled_at INTEGER NOT NULL,
+                    expires_at INTEGER NOT NULL
+                );
+                "#,
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    fn spawn_expiration_task(db_pool: SqlitePool, cleanup_interval: Duration) {
+        task::spawn(
+            async move {
+                let mut cleanup_interval = interval(cleanup_interval);
+
+                loop {
+                    cleanup_interval.tick().await;
+
+                    if let Err(e) = Self::cleanup_expired_buckets(
+                        db_pool.clone(),
+                        Utc::now().timestamp_millis(),
+                    )
+                    .await
+                    {
+                        error!("Failed to expire rate limit buckets: {}", e);
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    async fn cleanup_expired_buckets(pool: SqlitePool, current_time: i64) -> anyhow::Result<()> {
+        let query = sqlx::query("DELETE FROM http_rate_limit_bucket WHERE expires_at < ?;")
+            .bind(current_time);
+
+        pool.with_rw("rate_limit_store", "cleanup_expired_buckets")
+            .execute(query)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for SqliteRateLimitStore {
+    async fn try_take_all(
+        &self,
+        buckets: &[(String, &RateLimit)],
+        now: DateTime<Utc>,
+    ) -> Result<Option<Duration>, RateLimitStoreError> {
+        let now = now.timestamp_millis();
+
+        let mut tx = self
+            .pool
+            .with_rw("rate_limit_store", "try_take_all")
+            .begin()
+            .await?;
+
+        let mut updated_buckets = Vec::with_capacity(buckets.len());
+        for (key, rate_limit) in buckets {
+            let row = tx
+                .fetch_optional(
+                    sqlx::query(
+                        "SELECT tokens, refilled_at FROM http_rate_limit_bucket WHERE bucket_key = ?",
+                    )
+                    .bind(key.as_str()),
+                )
+                .await?;
+
+            updated_buckets.push(match row {
+                Some(row) => TokenBucket {
+                    tokens: row.get(0),
+                    refilled_at: row.get(1),
+                },
+                None => TokenBucket::full(rate_limit, now),
+            });
+        }
+
+        let result = take_from_all(
+            &mut updated_buckets
+                .iter_mut()
+                .zip(buckets)
+                .map(|(bucket, (_, rate_limit))| (bucket, *rate_limit))
+                .collect::<Vec<_>>(),
+            now,
+        );
+
+        for (bucket, (key, rate_limit)) in updated_buckets.iter().zip(buckets) {
+            tx.execute(
+                sqlx::query(
+                    "INSERT OR REPLACE INTO http_rate_limit_bucket (bucket_key, tokens, refilled_at, expires_at) VALUES (?, ?, ?, ?)",
+                )
+                .bind(key.as_str())
+                .bind(bucket.tokens)
+                .bind(bucket.refilled_at)
+                .bind(bucket.expires_at(rate_limit)),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_service_base::custom_api::RateLimitScope;
+    use test_r::test;
+
+    fn rate_limit(requests: u32, interval_seconds: u32, burst: u32) -> RateLimit {
+        RateLimit {
+            scope: RateLimitScope::Route,
+            requests,
+            interval_seconds,
+            burst,
+        }
+    }
+
+    #[test]
+    fn token_bucket_allows_bursts_and_refills_over_time() {
+        let rate_limit = rate_limit(1, 1, 3);
+        let mut bucket = TokenBucket::full(&rate_limit, 0);
+
+        assert_eq!(bucket.try_take(&rate_limit, 0), None);
+        assert_eq!(bucket.try_take(&rate_limit, 0), None);
+        assert_eq!(bucket.try_take(&rate_limit, 0), None);
+        assert_eq!(
+            bucket.try_take(&rate_limit, 0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            bucket.try_take(&rate_limit, 250),
+            Some(Duration::from_millis(750))
+        );
+        assert_eq!(bucket.try_take(&rate_limit, 1000), None);
+
+        // refilling never exceeds the burst
+        assert_eq!(bucket.try_take(&rate_limit, 60_000), None);
+        assert_eq!(bucket.tokens, 2.0);
+        assert_eq!(bucket.expires_at(&rate_limit), 63_000);
+    }
+
+    #[test]
+    fn token_bucket_expresses_quotas() {
+        let rate_limit = rate_limit(2, 86_400, 2);
+        let mut bucket = TokenBucket::full(&rate_limit, 0);
+
+        assert_eq!(bucket.try_take(&rate_limit, 0), None);
+        assert_eq!(bucket.try_take(&rate_limit, 1000), None);
+
+        let wait = bucket.try_take(&rate_limit, 1000).unwrap();
+        assert!(wait > Duration::from_secs(43_198) && wait < Duration::from_secs(43_200));
+    }
+
+    #[test]
+    async fn in_memory_store_keeps_buckets_per_key() {
+        let store = InMemoryRateLimitStore::new(Duration::from_secs(60));
+        let rate_limit = rate_limit(1, 1, 1);
+        let a = [("a".to_string(), &rate_limit)];
+        let b = [("b".to_string(), &rate_limit)];
+        let now = Utc::now();
+
+        assert_eq!(store.try_take_all(&a, now).await.unwrap(), None);
+        assert_eq!(
+            store.try_take_all(&a, now).await.unwrap(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(store.try_take_all(&b, now).await.unwrap(), None);
+    }
+
+    #[test]
+    async fn in_memory_store_takes_from_all_buckets_or_none() {
+        let store = InMemoryRateLimitStore::new(Duration::from_secs(60));
+        let lenient = rate_limit(2, 1, 10);
+        let strict = rate_limit(1, 1, 1);
+        let lenient_bucket = ("lenient".to_string(), &lenient);
+        let strict_bucket = ("strict".to_string(), &strict);
+        let both = [lenient_bucket.clone(), strict_bucket];
+        let now = Utc::now();
+
+        assert_eq!(store.try_take_all(&both, now).await.unwrap(), None);
+
+        // the empty strict bucket rejects the requests without draining the lenient one
+        for _ in 0..20 {
+            assert_eq!(
+                store.try_take_all(&both, now).await.unwrap(),
+                Some(Duration::from_secs(1))
+            );
+        }
+
+        let lenient_only = [lenient_bucket];
+        for _ in 0..9 {
+            assert_eq!(store.try_take_all(&lenient_only, now).await.unwrap(), None);
+        }
+        assert_eq!(
+            store.try_take_all(&lenient_only, now).await.unwrap(),
+            Some(Duration::from_millis(500))
+        );
+    }
+}
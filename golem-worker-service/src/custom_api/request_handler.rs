@@ -17,13 +17,16 @@ use super::cors::{apply_cors_outgoing_middleware, handle_cors_preflight_behaviou
 use super::error::RequestHandlerError;
 use super::model::RichRequest;
 use super::model::RichRouteBehaviour;
+use super::rate_limit::{RateLimitPhase, RateLimiter};
 use super::route_resolver::{ResolvedRouteEntry, RouteResolver};
 use super::security::handler::OidcHandler;
-use super::{OidcCallbackBehaviour, ResponseBody, RouteExecutionResult};
+use super::{OidcCallbackBehaviour, OpenApiSpecBehaviour, ResponseBody, RouteExecutionResult};
 use anyhow::anyhow;
 use golem_service_base::custom_api::CorsPreflightBehaviour;
 use golem_wasm::json::ValueAndTypeJsonExtensions;
+use http::StatusCode;
 use poem::{Request, Response};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{Instrument, debug};
 
@@ -31,6 +34,7 @@ pub struct RequestHandler {
     route_resolver: Arc<RouteResolver>,
     call_agent_handler: Arc<CallAgentHandler>,
     oidc_handler: Arc<OidcHandler>,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[allow(irrefutable_let_patterns)]
@@ -39,11 +43,13 @@ impl RequestHandler {
         route_resolver: Arc<RouteResolver>,
         call_agent_handler: Arc<CallAgentHandler>,
         oidc_handler: Arc<OidcHandler>,
+        rate_limiter: Arc<RateLimiter>,
     ) -> Self {
         Self {
             route_resolver,
             call_agent_handler,
             oidc_handler,
+            rate_limiter,
         }
     }
 
@@ -74,15 +80,35 @@ impl RequestHandler {
         request: &mut RichRequest,
         resolved_route: &ResolvedRouteEntry,
     ) -> Result<RouteExecutionResult, RequestHandlerError> {
-        if let Some(short_circuit) = self
-            .oidc_handler
-            .apply_oidc_incoming_middleware(request, resolved_route)
-            .await?
+        let mut result = match self
+            .rate_limiter
+            .apply_rate_limits(
+                request,
+                resolved_route,
+                RateLimitPhase::BeforeAuthentication,
+            )
+            .await
         {
-            return Ok(short_circuit);
-        }
-
-        let mut result = self.execute_route(request, resolved_route).await?;
+            Some(rejected) => rejected,
+            None => {
+                if let Some(short_circuit) = self
+                    .oidc_handler
+                    .apply_oidc_incoming_middleware(request, resolved_route)
+                    .await?
+                {
+                    return Ok(short_circuit);
+                }
+
+                match self
+                    .rate_limiter
+                    .apply_rate_limits(request, resolved_route, RateLimitPhase::AfterAuthentication)
+                    .await
+                {
+                    Some(rejected) => rejected,
+                    None => self.execute_route(request, resolved_route).await?,
+                }
+            }
+        };
 
         apply_cors_outgoing_middleware(&mut result, request, resolved_route).await?;
 
@@ -111,6 +137,14 @@ impl RequestHandler {
                     .handle_oidc_callback_behaviour(request, security_scheme)
                     .await
             }
+
+            RichRouteBehaviour::OpenApiSpec(OpenApiSpecBehaviour { spec }) => {
+                Ok(RouteExecutionResult {
+                    status: StatusCode::OK,
+                    headers: HashMap::new(),
+                    body: ResponseBody::JsonBody { body: spec.clone() },
+                })
+            }
         }
     }
 }
@@ -140,5 +174,10 @@ fn route_execution_result_to_response(
         ResponseBody::UnstructuredBinaryBody { body } => Ok(response_builder
             .body(body.data)
             .set_content_type(body.binary_type.mime_type)),
+
+        ResponseBody::JsonBody { body } => {
+            let body = poem::Body::from_json(body.as_ref()).map_err(anyhow::Error::from)?;
+            Ok(response_builder.body(body))
+        }
     }
 }
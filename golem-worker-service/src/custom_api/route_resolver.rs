@@ -16,12 +16,17 @@ use super::api_definition_lookup::{ApiDefinitionLookupError, HttpApiDefinitionsL
 use super::model::RichCompiledRoute;
 use super::router::Router;
 use crate::config::RouteResolverConfig;
-use crate::custom_api::{OidcCallbackBehaviour, RichRouteBehaviour};
+use crate::custom_api::{OidcCallbackBehaviour, OpenApiSpecBehaviour, RichRouteBehaviour};
 use golem_common::SafeDisplay;
 use golem_common::cache::SimpleCache;
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode};
 use golem_common::model::domain_registration::Domain;
-use golem_service_base::custom_api::{CompiledRoutes, CorsOptions, PathSegment, RequestBodySchema};
+use golem_common::model::security_scheme::SecuritySchemeMode;
+use golem_service_base::custom_api::openapi::{AgentHttpApiOpenApiSpec, OPENAPI_SPEC_PATH};
+use golem_service_base::custom_api::{
+    CompiledRoutes, CorsOptions, PathSegment, RequestBodySchema, SecuritySchemeDetails,
+};
+use openidconnect::Scope;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
@@ -127,7 +132,10 @@ impl RouteResolver {
             }
         };
 
-        let finalized_routes = match Self::finalize_routes(compiled_routes).await {
+        let openapi_spec =
+            Arc::new(AgentHttpApiOpenApiSpec::from_compiled_routes(&domain.0, &compiled_routes).0);
+
+        let finalized_routes = match Self::finalize_routes(compiled_routes, openapi_spec).await {
             Ok(value) => value,
             Err(err) => {
                 tracing::warn!("Failed to finalize routes for domain {domain}: {err:?}");
@@ -140,6 +148,7 @@ impl RouteResolver {
 
     async fn finalize_routes(
         compiled_routes: CompiledRoutes,
+        openapi_spec: Arc<serde_json::Value>,
     ) -> Result<Vec<RichCompiledRoute>, String> {
         let security_schemes: HashMap<_, _> = compiled_routes
             .security_schemes
@@ -172,14 +181,24 @@ impl RouteResolver {
                 body: route.body,
                 behavior: route.behavior.into(),
                 security_scheme,
+                required_scopes: route.required_scopes.into_iter().map(Scope::new).collect(),
                 cors: route.cors,
+                rate_limits: route.rate_limits,
             };
 
             enriched_routes.push(enriched);
         }
 
-        // add synthethic oidc callback routes
+        // add synthethic oidc callback routes, bearer tokens and api keys are obtained by callers
+        // themselves
         for scheme in security_schemes.values() {
+            let SecuritySchemeDetails::OpenIdConnect(scheme) = scheme.as_ref() else {
+                continue;
+            };
+            if scheme.mode != SecuritySchemeMode::AuthorizationCode {
+                continue;
+            }
+
             let redirect_url_path_segments: Vec<PathSegment> = scheme
                 .redirect_url
                 .url()
@@ -199,17 +218,41 @@ impl RouteResolver {
                 path: redirect_url_path_segments,
                 body: RequestBodySchema::Unused,
                 behavior: RichRouteBehaviour::OidcCallback(OidcCallbackBehaviour {
-                    security_scheme: scheme.clone(),
+                    security_scheme: Arc::new(scheme.clone()),
                 }),
                 security_scheme: None,
+                required_scopes: Vec::new(),
                 cors: CorsOptions {
                     allowed_patterns: Vec::new(),
                 },
+                rate_limits: Vec::new(),
             };
 
             enriched_routes.push(callback_route);
         }
 
+        // add synthetic route serving the openapi spec of the domain
+        enriched_routes.push(RichCompiledRoute {
+            account_id: compiled_routes.account_id,
+            environment_id: compiled_routes.environment_id,
+            route_id: -1,
+            method: http::Method::GET,
+            path: OPENAPI_SPEC_PATH
+                .iter()
+                .map(|s| PathSegment::Literal {
+                    value: s.to_string(),
+                })
+                .collect(),
+            body: RequestBodySchema::Unused,
+            behavior: RichRouteBehaviour::OpenApiSpec(OpenApiSpecBehaviour { spec: openapi_spec }),
+            security_scheme: None,
+            required_scopes: Vec::new(),
+            cors: CorsOptions {
+                allowed_patterns: Vec::new(),
+            },
+            rate_limits: Vec::new(),
+        });
+
         Ok(enriched_routes)
     }
 }
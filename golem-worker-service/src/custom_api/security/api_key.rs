@@ -0,0 +1,283 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::ApiKeyLookupConfig;
+use crate::custom_api::model::RichRequest;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::agent::ApiKeyPrincipal;
+use golem_common::model::security_scheme::{SecuritySchemeId, hash_api_key_secret};
+use golem_service_base::clients::registry::{RegistryService, RegistryServiceError};
+use golem_service_base::custom_api::{
+    API_KEY_HEADER, API_KEY_QUERY_PARAM, ApiKeySecuritySchemeDetails, SecuritySchemeApiKey,
+};
+use openidconnect::Scope;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApiKeyVerificationError {
+    #[error("Unknown or revoked api key")]
+    UnknownKey,
+    #[error("Api key {0} expired")]
+    Expired(String),
+    #[error("Api key {0} lacks required scopes")]
+    InsufficientScope(String),
+}
+
+#[async_trait]
+pub trait ApiKeyLookup: Send + Sync {
+    /// Keys of the api-key security scheme that were not revoked
+    async fn get(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+    ) -> Result<Arc<Vec<SecuritySchemeApiKey>>, RegistryServiceError>;
+}
+
+/// Looks up the keys in the registry, caching them for a short time only so that revoked keys
+/// are rejected soon after the revocation, independently of the caching of the routes.
+pub struct RegistryServiceApiKeyLookup {
+    api_key_cache:
+        Cache<SecuritySchemeId, (), Arc<Vec<SecuritySchemeApiKey>>, RegistryServiceError>,
+    registry_service_client: Arc<dyn RegistryService>,
+}
+
+impl RegistryServiceApiKeyLookup {
+    pub fn new(
+        config: &ApiKeyLookupConfig,
+        registry_service_client: Arc<dyn RegistryService>,
+    ) -> Self {
+        Self {
+            api_key_cache: Cache::new(
+                Some(config.api_key_cache_max_capacity),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::OlderThan {
+                    ttl: config.api_key_cache_ttl,
+                    period: config.api_key_cache_eviction_period,
+                },
+                "api_key_lookup_api_keys",
+            ),
+            registry_service_client,
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyLookup for RegistryServiceApiKeyLookup {
+    async fn get(
+        &self,
+        security_scheme_id: SecuritySchemeId,
+    ) -> Result<Arc<Vec<SecuritySchemeApiKey>>, RegistryServiceError> {
+        self.api_key_cache
+            .get_or_insert_simple(&security_scheme_id, async || {
+                Ok(Arc::new(
+                    self.registry_service_client
+                        .get_security_scheme_api_keys(security_scheme_id)
+                        .await?,
+                ))
+            })
+            .await
+    }
+}
+
+/// Secret of the api key sent with the request, preferring the header over the query parameter
+pub fn api_key_secret(request: &RichRequest) -> Option<&str> {
+    let from_header = request
+        .underlying
+        .header(API_KEY_HEADER)
+        .map(|secret| secret.trim());
+    let from_query = || match request.query_params().get(API_KEY_QUERY_PARAM) {
+        Some(values) if values.len() == 1 => Some(values[0].as_str()),
+        _ => None,
+    };
+    from_header
+        .or_else(from_query)
+        .filter(|secret| !secret.is_empty())
+}
+
+/// Looks up the key by the hash of the presented secret among the active keys of the scheme. The
+/// key has to grant the scopes required by the endpoint.
+pub fn verify_api_key(
+    security_scheme: &ApiKeySecuritySchemeDetails,
+    required_scopes: &[Scope],
+    api_keys: &[SecuritySchemeApiKey],
+    secret: &str,
+    now: DateTime<Utc>,
+) -> Result<ApiKeyPrincipal, ApiKeyVerificationError> {
+    let secret_hash = hash_api_key_secret(secret);
+
+    let api_key = api_keys
+        .iter()
+        .find(|api_key| api_key.secret_hash == secret_hash)
+        .ok_or(ApiKeyVerificationError::UnknownKey)?;
+
+    if api_key
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now)
+    {
+        return Err(ApiKeyVerificationError::Expired(api_key.name.clone()));
+    }
+
+    let has_required_scopes = required_scopes
+        .iter()
+        .all(|scope| api_key.scopes.contains(scope));
+
+    if !has_required_scopes {
+        return Err(ApiKeyVerificationError::InsufficientScope(
+            api_key.name.clone(),
+        ));
+    }
+
+    Ok(ApiKeyPrincipal {
+        key_id: api_key.id.to_string(),
+        name: api_key.name.clone(),
+        security_scheme: security_scheme.name.0.clone(),
+        scopes: api_key
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use golem_common::model::security_scheme::{ApiKeyId, SecuritySchemeName};
+    use test_r::test;
+
+    const SECRET: &str = "golem_ak_partner-secret";
+
+    fn scopes(scopes: Vec<&str>) -> Vec<Scope> {
+        scopes
+            .into_iter()
+            .map(|scope| Scope::new(scope.to_string()))
+            .collect()
+    }
+
+    fn api_key_security_scheme() -> ApiKeySecuritySchemeDetails {
+        ApiKeySecuritySchemeDetails {
+            id: SecuritySchemeId::new(),
+            name: SecuritySchemeName("partner-keys".to_string()),
+        }
+    }
+
+    fn api_keys(
+        key_scopes: Vec<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Vec<SecuritySchemeApiKey> {
+        vec![SecuritySchemeApiKey {
+            id: ApiKeyId::new(),
+            name: "partner".to_string(),
+            secret_hash: hash_api_key_secret(SECRET),
+            scopes: scopes(key_scopes),
+            expires_at,
+        }]
+    }
+
+    #[test]
+    fn verifies_known_api_key() {
+        let security_scheme = api_key_security_scheme();
+        let api_keys = api_keys(
+            vec!["counters:read", "counters:write"],
+            Some(Utc::now() + TimeDelta::hours(1)),
+        );
+
+        let principal = verify_api_key(
+            &security_scheme,
+            &scopes(vec!["counters:read"]),
+            &api_keys,
+            SECRET,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(principal.key_id, api_keys[0].id.to_string());
+        assert_eq!(principal.name, "partner");
+        assert_eq!(principal.security_scheme, "partner-keys");
+        assert_eq!(principal.scopes, vec!["counters:read", "counters:write"]);
+    }
+
+    #[test]
+    fn rejects_unknown_expired_and_underscoped_api_keys() {
+        let security_scheme = api_key_security_scheme();
+        assert_eq!(
+            verify_api_key(
+                &security_scheme,
+                &[],
+                &api_keys(vec![], None),
+                "golem_ak_other",
+                Utc::now()
+            ),
+            Err(ApiKeyVerificationError::UnknownKey)
+        );
+
+        let expires_at = Utc::now();
+        assert_eq!(
+            verify_api_key(
+                &security_scheme,
+                &[],
+                &api_keys(vec![], Some(expires_at)),
+                SECRET,
+                expires_at
+            ),
+            Err(ApiKeyVerificationError::Expired("partner".to_string()))
+        );
+
+        assert_eq!(
+            verify_api_key(
+                &security_scheme,
+                &scopes(vec!["counters:write"]),
+                &api_keys(vec!["counters:read"], None),
+                SECRET,
+                Utc::now()
+            ),
+            Err(ApiKeyVerificationError::InsufficientScope(
+                "partner".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn checks_the_scopes_required_by_each_endpoint() {
+        let security_scheme = api_key_security_scheme();
+        let api_keys = api_keys(vec!["counters:read"], None);
+        let read_endpoint_scopes = scopes(vec!["counters:read"]);
+        let write_endpoint_scopes = scopes(vec!["counters:write"]);
+
+        let principal = verify_api_key(
+            &security_scheme,
+            &read_endpoint_scopes,
+            &api_keys,
+            SECRET,
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(principal.scopes, vec!["counters:read"]);
+
+        assert_eq!(
+            verify_api_key(
+                &security_scheme,
+                &write_endpoint_scopes,
+                &api_keys,
+                SECRET,
+                Utc::now()
+            ),
+            Err(ApiKeyVerificationError::InsufficientScope(
+                "partner".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use openidconnect::core::{
+    CoreGenderClaim, CoreJsonWebKeyType, CoreJweContentEncryptionAlgorithm, CoreJwsSigningAlgorithm,
+};
+use openidconnect::{AdditionalClaims, IdToken, IdTokenClaims, Scope};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Claims of bearer tokens beyond the standard ones. Providers put the granted scopes either
+/// into a space delimited `scope` claim (RFC 9068) or into a `scp` claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearerTokenAdditionalClaims {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scp: Option<ScopeClaim>,
+}
+
+impl AdditionalClaims for BearerTokenAdditionalClaims {}
+
+impl BearerTokenAdditionalClaims {
+    pub fn scopes(&self) -> HashSet<Scope> {
+        let scp: Vec<&str> = match &self.scp {
+            Some(ScopeClaim::Delimited(scp)) => scp.split_whitespace().collect(),
+            Some(ScopeClaim::List(scp)) => scp.iter().map(|s| s.as_str()).collect(),
+            None => Vec::new(),
+        };
+        self.scope
+            .iter()
+            .flat_map(|scope| scope.split_whitespace())
+            .chain(scp)
+            .map(|scope| Scope::new(scope.to_string()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ScopeClaim {
+    Delimited(String),
+    List(Vec<String>),
+}
+
+pub type BearerTokenClaims = IdTokenClaims<BearerTokenAdditionalClaims, CoreGenderClaim>;
+
+pub type BearerToken = IdToken<
+    BearerTokenAdditionalClaims,
+    CoreGenderClaim,
+    CoreJweContentEncryptionAlgorithm,
+    CoreJwsSigningAlgorithm,
+    CoreJsonWebKeyType,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn scopes(claims: serde_json::Value) -> HashSet<String> {
+        serde_json::from_value::<BearerTokenAdditionalClaims>(claims)
+            .unwrap()
+            .scopes()
+            .into_iter()
+            .map(|scope| scope.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn scopes_from_scope_and_scp_claims() {
+        assert_eq!(
+            scopes(serde_json::json!({ "scope": "counters:read counters:write" })),
+            HashSet::from(["counters:read".to_string(), "counters:write".to_string()])
+        );
+        assert_eq!(
+            scopes(serde_json::json!({ "scp": ["counters:read"] })),
+            HashSet::from(["counters:read".to_string()])
+        );
+        assert_eq!(
+            scopes(serde_json::json!({ "scp": "counters:read", "scope": "email" })),
+            HashSet::from(["counters:read".to_string(), "email".to_string()])
+        );
+        assert!(scopes(serde_json::json!({})).is_empty());
+    }
+}
@@ -12,18 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::IdentityProvider;
+use super::api_key::{ApiKeyLookup, ApiKeyVerificationError, api_key_secret, verify_api_key};
 use super::model::AuthorizationUrl;
 use super::session_store::SessionStore;
+use super::{IdentityProvider, IdentityProviderError};
 use crate::custom_api::error::RequestHandlerError;
 use crate::custom_api::model::{OidcSession, RichRequest};
 use crate::custom_api::route_resolver::ResolvedRouteEntry;
 use crate::custom_api::security::model::SessionId;
 use crate::custom_api::{ResponseBody, RouteExecutionResult};
+use anyhow::anyhow;
+use chrono::Utc;
 use cookie::Cookie;
-use golem_service_base::custom_api::SecuritySchemeDetails;
+use golem_common::model::security_scheme::SecuritySchemeMode;
+use golem_service_base::custom_api::{
+    ApiKeySecuritySchemeDetails, OidcSecuritySchemeDetails, SecuritySchemeDetails,
+};
 use http::StatusCode;
-use openidconnect::{AuthorizationCode, OAuth2TokenResponse};
+use openidconnect::{AuthorizationCode, OAuth2TokenResponse, Scope};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::debug;
@@ -34,23 +40,26 @@ const GOLEM_SESSION_ID_COOKIE_NAME: &str = "golem_session_id";
 pub struct OidcHandler {
     session_store: Arc<dyn SessionStore>,
     identity_provider: Arc<dyn IdentityProvider>,
+    api_key_lookup: Arc<dyn ApiKeyLookup>,
 }
 
 impl OidcHandler {
     pub fn new(
         session_store: Arc<dyn SessionStore>,
         identity_provider: Arc<dyn IdentityProvider>,
+        api_key_lookup: Arc<dyn ApiKeyLookup>,
     ) -> Self {
         Self {
             session_store,
             identity_provider,
+            api_key_lookup,
         }
     }
 
     pub async fn handle_oidc_callback_behaviour(
         &self,
         request: &mut RichRequest,
-        scheme: &Arc<SecuritySchemeDetails>,
+        scheme: &Arc<OidcSecuritySchemeDetails>,
     ) -> Result<RouteExecutionResult, RequestHandlerError> {
         let code = request.get_single_param("code")?;
         let state = request.get_single_param("state")?;
@@ -79,34 +88,11 @@ impl OidcHandler {
             self.identity_provider
                 .get_claims(&id_token_verifier, &token_response, &nonce)?;
 
-        let session = OidcSession {
-            subject: id_token_claims.subject().to_string(),
-            issuer: id_token_claims.issuer().to_string(),
-
-            email: id_token_claims.email().map(|v| v.to_string()),
-            name: id_token_claims
-                .name()
-                .and_then(|v| v.get(None))
-                .map(|v| v.to_string()),
-            email_verified: id_token_claims.email_verified(),
-            given_name: id_token_claims
-                .given_name()
-                .and_then(|v| v.get(None))
-                .map(|v| v.to_string()),
-            family_name: id_token_claims
-                .family_name()
-                .and_then(|v| v.get(None))
-                .map(|v| v.to_string()),
-            picture: id_token_claims
-                .picture()
-                .and_then(|v| v.get(None))
-                .map(|v| v.to_string()),
-            preferred_username: id_token_claims.preferred_username().map(|v| v.to_string()),
-
-            claims: id_token_claims.clone(),
-            scopes: HashSet::from_iter(token_response.scopes().cloned().unwrap_or_default()),
-            expires_at: id_token_claims.expiration(),
-        };
+        let session = OidcSession::from_claims(
+            &id_token_claims,
+            HashSet::from_iter(token_response.scopes().cloned().unwrap_or_default()),
+        )
+        .map_err(|e| anyhow!("ID token claims serialization error: {e}"))?;
 
         let session_id = SessionId(Uuid::new_v4());
 
@@ -143,6 +129,37 @@ impl OidcHandler {
             return Ok(None);
         };
 
+        match security_scheme.as_ref() {
+            SecuritySchemeDetails::OpenIdConnect(security_scheme) => match security_scheme.mode {
+                SecuritySchemeMode::AuthorizationCode => {
+                    self.authenticate_with_session(request, security_scheme)
+                        .await
+                }
+                SecuritySchemeMode::BearerToken => {
+                    self.authenticate_with_bearer_token(
+                        request,
+                        security_scheme,
+                        &resolved_route.route.required_scopes,
+                    )
+                    .await
+                }
+            },
+            SecuritySchemeDetails::ApiKey(security_scheme) => {
+                self.authenticate_with_api_key(
+                    request,
+                    security_scheme,
+                    &resolved_route.route.required_scopes,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn authenticate_with_session(
+        &self,
+        request: &mut RichRequest,
+        security_scheme: &OidcSecuritySchemeDetails,
+    ) -> Result<Option<RouteExecutionResult>, RequestHandlerError> {
         let session_id = if let Some(s) = request.cookie(GOLEM_SESSION_ID_COOKIE_NAME)
             && let Ok(parsed) = Uuid::parse_str(s)
         {
@@ -170,10 +187,138 @@ impl OidcHandler {
 
         Ok(None)
     }
+
+    /// Stateless authentication for machine-to-machine callers. Nothing is stored, the token is
+    /// verified on every request and callers are never redirected. The token has to grant the
+    /// scopes required by the endpoint.
+    async fn authenticate_with_bearer_token(
+        &self,
+        request: &mut RichRequest,
+        security_scheme: &OidcSecuritySchemeDetails,
+        required_scopes: &[Scope],
+    ) -> Result<Option<RouteExecutionResult>, RequestHandlerError> {
+        let Some(token) = bearer_token(request) else {
+            return Ok(Some(bearer_token_challenge(StatusCode::UNAUTHORIZED, None)));
+        };
+
+        let claims = match self
+            .identity_provider
+            .verify_bearer_token(security_scheme, token)
+            .await
+        {
+            Ok(claims) => claims,
+            Err(IdentityProviderError::BearerTokenVerificationError(err)) => {
+                debug!("Rejected bearer token: {err}");
+                return Ok(Some(bearer_token_challenge(
+                    StatusCode::UNAUTHORIZED,
+                    Some(("invalid_token", None)),
+                )));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let session = OidcSession::from_claims(&claims, claims.additional_claims().scopes())
+            .map_err(|e| anyhow!("Bearer token claims serialization error: {e}"))?;
+
+        let has_required_scopes = required_scopes
+            .iter()
+            .all(|scope| session.scopes().contains(scope));
+
+        if !has_required_scopes {
+            debug!("Bearer token of {} lacks required scopes", session.subject);
+            let required_scopes = required_scopes
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            return Ok(Some(bearer_token_challenge(
+                StatusCode::FORBIDDEN,
+                Some(("insufficient_scope", Some(required_scopes.as_str()))),
+            )));
+        }
+
+        request.set_authenticated_session(session);
+
+        Ok(None)
+    }
+
+    /// Like bearer tokens, api keys are verified on every request and have to grant the scopes
+    /// required by the endpoint.
+    async fn authenticate_with_api_key(
+        &self,
+        request: &mut RichRequest,
+        security_scheme: &ApiKeySecuritySchemeDetails,
+        required_scopes: &[Scope],
+    ) -> Result<Option<RouteExecutionResult>, RequestHandlerError> {
+        let Some(secret) = api_key_secret(request) else {
+            return Ok(Some(api_key_rejection(StatusCode::UNAUTHORIZED)));
+        };
+
+        let api_keys = self.api_key_lookup.get(security_scheme.id).await?;
+
+        match verify_api_key(
+            security_scheme,
+            required_scopes,
+            &api_keys,
+            secret,
+            Utc::now(),
+        ) {
+            Ok(api_key) => {
+                request.set_authenticated_api_key(api_key);
+                Ok(None)
+            }
+            Err(err @ ApiKeyVerificationError::InsufficientScope(_)) => {
+                debug!("Rejected api key: {err}");
+                Ok(Some(api_key_rejection(StatusCode::FORBIDDEN)))
+            }
+            Err(err) => {
+                debug!("Rejected api key: {err}");
+                Ok(Some(api_key_rejection(StatusCode::UNAUTHORIZED)))
+            }
+        }
+    }
+}
+
+fn api_key_rejection(status: StatusCode) -> RouteExecutionResult {
+    RouteExecutionResult {
+        status,
+        headers: HashMap::new(),
+        body: ResponseBody::NoBody,
+    }
+}
+
+fn bearer_token(request: &RichRequest) -> Option<&str> {
+    let (scheme, token) = request
+        .underlying
+        .header(http::header::AUTHORIZATION)?
+        .split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// Rejects the request with a `WWW-Authenticate` challenge as defined by RFC 6750
+fn bearer_token_challenge(
+    status: StatusCode,
+    error: Option<(&str, Option<&str>)>,
+) -> RouteExecutionResult {
+    let challenge = match error {
+        None => "Bearer".to_string(),
+        Some((error, None)) => format!("Bearer error=\"{error}\""),
+        Some((error, Some(scope))) => format!("Bearer error=\"{error}\", scope=\"{scope}\""),
+    };
+
+    let mut headers = HashMap::new();
+    headers.insert(http::header::WWW_AUTHENTICATE, challenge);
+
+    RouteExecutionResult {
+        status,
+        headers,
+        body: ResponseBody::NoBody,
+    }
 }
 
 async fn start_oidc_flow_for_route(
-    security_scheme: &SecuritySchemeDetails,
+    security_scheme: &OidcSecuritySchemeDetails,
     identity_provider: Arc<dyn IdentityProvider>,
 ) -> Result<RouteExecutionResult, RequestHandlerError> {
     let client = identity_provider.get_client(security_scheme).await?;
@@ -12,21 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::bearer_token::{BearerToken, BearerTokenClaims};
 use super::identity_provider_metadata::GolemIdentityProviderMetadata;
 use super::model::AuthorizationUrl;
 use super::open_id_client::OpenIdClient;
 use async_trait::async_trait;
 use golem_common::IntoAnyhow;
-use golem_common::model::security_scheme::Provider;
-use golem_service_base::custom_api::SecuritySchemeDetails;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_service_base::custom_api::OidcSecuritySchemeDetails;
 use openidconnect::core::{
     CoreClient, CoreIdTokenClaims, CoreIdTokenVerifier, CoreProviderMetadata, CoreResponseType,
     CoreTokenResponse,
 };
-use openidconnect::{AuthenticationFlow, AuthorizationCode, CsrfToken, Nonce, Scope};
+use openidconnect::{
+    AuthenticationFlow, AuthorizationCode, ClaimsVerificationError, ClientId, CsrfToken, IssuerUrl,
+    Nonce, Scope,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::debug;
 
-#[derive(Debug, thiserror::Error)]
+/// Signing keys of providers rotate, so discovered metadata is only reused for a limited time
+const PROVIDER_METADATA_CACHE_TTL: Duration = Duration::from_mins(5);
+
+/// Minimum time between refreshes of the metadata of an issuer triggered by tokens with unknown
+/// signatures, so that forged tokens cannot make the service hammer the issuer
+const PROVIDER_METADATA_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum IdentityProviderError {
     #[error("Failed to initialize client: {0}")]
     ClientInitError(String),
@@ -38,6 +53,8 @@ pub enum IdentityProviderError {
     FailedToExchangeCodeForTokens(String),
     #[error("ID token verification error: {0}")]
     IdTokenVerificationError(String),
+    #[error("Bearer token verification error: {0}")]
+    BearerTokenVerificationError(String),
 }
 
 impl IntoAnyhow for IdentityProviderError {
@@ -48,9 +65,10 @@ impl IntoAnyhow for IdentityProviderError {
 
 #[async_trait]
 pub trait IdentityProvider: Send + Sync {
+    /// Discovers the provider metadata and signing keys published by the issuer
     async fn get_provider_metadata(
         &self,
-        provider: &Provider,
+        issuer_url: &IssuerUrl,
     ) -> Result<GolemIdentityProviderMetadata, IdentityProviderError>;
 
     async fn exchange_code_for_tokens(
@@ -61,7 +79,7 @@ pub trait IdentityProvider: Send + Sync {
 
     async fn get_client(
         &self,
-        security_scheme: &SecuritySchemeDetails,
+        security_scheme: &OidcSecuritySchemeDetails,
     ) -> Result<OpenIdClient, IdentityProviderError>;
 
     fn get_id_token_verifier<'a>(&self, client: &'a OpenIdClient) -> CoreIdTokenVerifier<'a>;
@@ -80,18 +98,75 @@ pub trait IdentityProvider: Send + Sync {
         state: Option<CsrfToken>,
         nonce: Option<Nonce>,
     ) -> AuthorizationUrl;
+
+    /// Verifies the signature, issuer, audience and expiry of a bearer JWT using the keys
+    /// published by the issuer of the security scheme
+    async fn verify_bearer_token(
+        &self,
+        security_scheme: &OidcSecuritySchemeDetails,
+        token: &str,
+    ) -> Result<BearerTokenClaims, IdentityProviderError>;
 }
 
-pub struct DefaultIdentityProvider;
+pub struct DefaultIdentityProvider {
+    provider_metadata_cache:
+        Cache<String, (), Arc<GolemIdentityProviderMetadata>, IdentityProviderError>,
+    provider_metadata_refreshed_at: Mutex<HashMap<String, Instant>>,
+}
+
+impl DefaultIdentityProvider {
+    async fn get_cached_provider_metadata(
+        &self,
+        issuer_url: &IssuerUrl,
+    ) -> Result<Arc<GolemIdentityProviderMetadata>, IdentityProviderError> {
+        self.provider_metadata_cache
+            .get_or_insert_simple(&issuer_url.to_string(), async || {
+                Ok(Arc::new(self.get_provider_metadata(issuer_url).await?))
+            })
+            .await
+    }
+
+    /// Whether the metadata of the issuer can be refreshed, recording the refresh if so
+    fn claim_provider_metadata_refresh(&self, issuer_url: &IssuerUrl) -> bool {
+        let mut refreshed_at = self.provider_metadata_refreshed_at.lock().unwrap();
+        let now = Instant::now();
+        match refreshed_at.get(issuer_url.as_str()) {
+            Some(last) if now.duration_since(*last) < PROVIDER_METADATA_MIN_REFRESH_INTERVAL => {
+                false
+            }
+            _ => {
+                refreshed_at.insert(issuer_url.to_string(), now);
+                true
+            }
+        }
+    }
+}
+
+impl Default for DefaultIdentityProvider {
+    fn default() -> Self {
+        Self {
+            provider_metadata_cache: Cache::new(
+                None,
+                FullCacheEvictionMode::None,
+                BackgroundEvictionMode::OlderThan {
+                    ttl: PROVIDER_METADATA_CACHE_TTL,
+                    period: Duration::from_mins(1),
+                },
+                "identity_provider_metadata",
+            ),
+            provider_metadata_refreshed_at: Mutex::new(HashMap::new()),
+        }
+    }
+}
 
 #[async_trait]
 impl IdentityProvider for DefaultIdentityProvider {
     async fn get_provider_metadata(
         &self,
-        provider: &Provider,
+        issuer_url: &IssuerUrl,
     ) -> Result<GolemIdentityProviderMetadata, IdentityProviderError> {
         let provider_metadata = CoreProviderMetadata::discover_async(
-            provider.issuer_url(),
+            issuer_url.clone(),
             openidconnect::reqwest::async_http_client,
         )
         .await
@@ -117,7 +192,7 @@ impl IdentityProvider for DefaultIdentityProvider {
 
     async fn get_client(
         &self,
-        security_scheme: &SecuritySchemeDetails,
+        security_scheme: &OidcSecuritySchemeDetails,
     ) -> Result<OpenIdClient, IdentityProviderError> {
         debug!(
             "Creating identity provider client for {}",
@@ -125,7 +200,7 @@ impl IdentityProvider for DefaultIdentityProvider {
         );
 
         let provider_metadata = self
-            .get_provider_metadata(&security_scheme.provider_type)
+            .get_provider_metadata(&security_scheme.issuer_url)
             .await?;
 
         let client = CoreClient::from_provider_metadata(
@@ -188,4 +263,415 @@ impl IdentityProvider for DefaultIdentityProvider {
             nonce,
         }
     }
+
+    async fn verify_bearer_token(
+        &self,
+        security_scheme: &OidcSecuritySchemeDetails,
+        token: &str,
+    ) -> Result<BearerTokenClaims, IdentityProviderError> {
+        let token = BearerToken::from_str(token)
+            .map_err(|err| IdentityProviderError::BearerTokenVerificationError(err.to_string()))?;
+
+        let provider_metadata = self
+            .get_cached_provider_metadata(&security_scheme.issuer_url)
+            .await?;
+
+        let result = match verify_bearer_token_claims(security_scheme, &token, &provider_metadata) {
+            // the issuer may have rotated its keys since the metadata was cached
+            Err(ClaimsVerificationError::SignatureVerification(_))
+                if self.claim_provider_metadata_refresh(&security_scheme.issuer_url) =>
+            {
+                self.provider_metadata_cache
+                    .remove(&security_scheme.issuer_url.to_string())
+                    .await;
+                let provider_metadata = self
+                    .get_cached_provider_metadata(&security_scheme.issuer_url)
+                    .await?;
+                verify_bearer_token_claims(security_scheme, &token, &provider_metadata)
+            }
+            result => result,
+        };
+
+        result.map_err(|err| IdentityProviderError::BearerTokenVerificationError(err.to_string()))
+    }
+}
+
+fn verify_bearer_token_claims(
+    security_scheme: &OidcSecuritySchemeDetails,
+    token: &BearerToken,
+    provider_metadata: &GolemIdentityProviderMetadata,
+) -> Result<BearerTokenClaims, ClaimsVerificationError> {
+    let verifier = CoreIdTokenVerifier::new_public_client(
+        ClientId::new((*security_scheme.audience).clone()),
+        security_scheme.issuer_url.clone(),
+        provider_metadata.jwks().clone(),
+    )
+    .set_allowed_algs(
+        provider_metadata
+            .id_token_signing_alg_values_supported()
+            .clone(),
+    )
+    // access tokens are often issued for several resource servers at once, it is enough
+    // if the configured audience is one of them
+    .set_other_audience_verifier_fn(|_| true);
+
+    // bearer tokens are not bound to a login attempt of this service, so there is no nonce
+    let no_nonce = |_: Option<&Nonce>| -> Result<(), String> { Ok(()) };
+
+    token.claims(&verifier, no_nonce).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::custom_api::security::bearer_token::BearerTokenAdditionalClaims;
+    use golem_common::model::security_scheme::{
+        Provider, SecuritySchemeId, SecuritySchemeMode, SecuritySchemeName,
+    };
+    use openidconnect::core::{CoreJwsSigningAlgorithm, CoreRsaPrivateSigningKey};
+    use openidconnect::{
+        Audience, ClientSecret, JsonWebKeyId, PrivateSigningKey, RedirectUrl, StandardClaims,
+        SubjectIdentifier,
+    };
+    use poem::endpoint::make_sync;
+    use poem::listener::{Acceptor, Listener, TcpListener};
+    use poem::web::Json;
+    use poem::{Route, Server, get};
+    use rsa::pkcs1::{EncodeRsaPrivateKey, LineEnding};
+    use serde_json::json;
+    use std::sync::LazyLock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use test_r::test;
+
+    const REALM_PATH: &str = "/realms/golem";
+
+    fn new_signing_key_pem() -> String {
+        rsa::RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 2048)
+            .unwrap()
+            .to_pkcs1_pem(LineEnding::LF)
+            .unwrap()
+            .to_string()
+    }
+
+    static SIGNING_KEY_PEM: LazyLock<String> = LazyLock::new(new_signing_key_pem);
+
+    /// Key not published by the mock identity provider, but using the same key id
+    static UNKNOWN_SIGNING_KEY_PEM: LazyLock<String> = LazyLock::new(new_signing_key_pem);
+
+    fn signing_key_from_pem(pem: &str) -> CoreRsaPrivateSigningKey {
+        CoreRsaPrivateSigningKey::from_pem(pem, Some(JsonWebKeyId::new("test-key".to_string())))
+            .unwrap()
+    }
+
+    fn signing_key() -> CoreRsaPrivateSigningKey {
+        signing_key_from_pem(&SIGNING_KEY_PEM)
+    }
+
+    /// Starts a minimal identity provider serving discovery metadata and the public part of
+    /// the test signing key. The advertised issuer can be overridden to simulate misconfigured
+    /// providers.
+    async fn start_mock_identity_provider(advertised_issuer: Option<&str>) -> IssuerUrl {
+        start_counting_mock_identity_provider(advertised_issuer)
+            .await
+            .0
+    }
+
+    /// Same as [start_mock_identity_provider], also counting the requests for the signing keys
+    async fn start_counting_mock_identity_provider(
+        advertised_issuer: Option<&str>,
+    ) -> (IssuerUrl, Arc<AtomicUsize>) {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let port = acceptor.local_addr()[0]
+            .as_socket_addr()
+            .expect("socket address")
+            .port();
+
+        let issuer = format!("http://127.0.0.1:{port}{REALM_PATH}");
+        let metadata = json!({
+            "issuer": advertised_issuer.unwrap_or(&issuer),
+            "authorization_endpoint": format!("{issuer}/protocol/openid-connect/auth"),
+            "token_endpoint": format!("{issuer}/protocol/openid-connect/token"),
+            "jwks_uri": format!("{issuer}/protocol/openid-connect/certs"),
+            "response_types_supported": ["code"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["RS256"],
+        });
+
+        let jwks = json!({ "keys": [signing_key().as_verification_key()] });
+        let jwks_requests = Arc::new(AtomicUsize::new(0));
+
+        let app = Route::new()
+            .at(
+                format!("{REALM_PATH}/.well-known/openid-configuration"),
+                get(make_sync(move |_| Json(metadata.clone()))),
+            )
+            .at(
+                format!("{REALM_PATH}/protocol/openid-connect/certs"),
+                get(make_sync({
+                    let jwks_requests = jwks_requests.clone();
+                    move |_| {
+                        jwks_requests.fetch_add(1, Ordering::SeqCst);
+                        Json(jwks.clone())
+                    }
+                })),
+            );
+
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(app));
+
+        (IssuerUrl::new(issuer).unwrap(), jwks_requests)
+    }
+
+    fn custom_security_scheme(issuer_url: IssuerUrl) -> OidcSecuritySchemeDetails {
+        OidcSecuritySchemeDetails {
+            id: SecuritySchemeId::new(),
+            name: SecuritySchemeName("custom-idp".to_string()),
+            provider_type: Provider::Custom,
+            issuer_url,
+            client_id: ClientId::new("golem-client".to_string()),
+            client_secret: ClientSecret::new("golem-secret".to_string()),
+            redirect_url: RedirectUrl::new("http://localhost:9006/auth/callback".to_string())
+                .unwrap(),
+            scopes: vec![Scope::new("email".to_string())],
+            mode: SecuritySchemeMode::AuthorizationCode,
+            audience: Audience::new("golem-client".to_string()),
+        }
+    }
+
+    fn bearer_token_security_scheme(issuer_url: IssuerUrl) -> OidcSecuritySchemeDetails {
+        OidcSecuritySchemeDetails {
+            mode: SecuritySchemeMode::BearerToken,
+            audience: Audience::new("counter-api".to_string()),
+            ..custom_security_scheme(issuer_url)
+        }
+    }
+
+    fn bearer_token(
+        issuer_url: &IssuerUrl,
+        audience: &str,
+        expiration: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        bearer_token_signed_with(&signing_key(), issuer_url, audience, expiration)
+    }
+
+    fn bearer_token_signed_with(
+        signing_key: &CoreRsaPrivateSigningKey,
+        issuer_url: &IssuerUrl,
+        audience: &str,
+        expiration: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        let claims = BearerTokenClaims::new(
+            issuer_url.clone(),
+            vec![Audience::new(audience.to_string())],
+            expiration,
+            chrono::Utc::now(),
+            StandardClaims::new(SubjectIdentifier::new("service-account".to_string())),
+            BearerTokenAdditionalClaims {
+                scope: Some("counters:read counters:write".to_string()),
+                scp: None,
+            },
+        );
+
+        BearerToken::new(
+            claims,
+            signing_key,
+            CoreJwsSigningAlgorithm::RsaSsaPkcs1V15Sha256,
+            None,
+            None,
+        )
+        .unwrap()
+        .to_string()
+    }
+
+    fn in_one_hour() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() + chrono::Duration::hours(1)
+    }
+
+    #[test]
+    async fn discovers_custom_provider_metadata() {
+        let issuer_url = start_mock_identity_provider(None).await;
+
+        let metadata = DefaultIdentityProvider::default()
+            .get_provider_metadata(&issuer_url)
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.issuer(), &issuer_url);
+        assert_eq!(
+            metadata.authorization_endpoint().url().path(),
+            format!("{REALM_PATH}/protocol/openid-connect/auth")
+        );
+        assert_eq!(metadata.jwks().keys().len(), 1);
+    }
+
+    #[test]
+    async fn authorization_url_of_custom_provider() {
+        let issuer_url = start_mock_identity_provider(None).await;
+        let security_scheme = custom_security_scheme(issuer_url);
+
+        let client = DefaultIdentityProvider::default()
+            .get_client(&security_scheme)
+            .await
+            .unwrap();
+        let authorization_url = DefaultIdentityProvider::default().get_authorization_url(
+            &client,
+            security_scheme.scopes.clone(),
+            None,
+            None,
+        );
+
+        assert_eq!(
+            authorization_url.url.path(),
+            format!("{REALM_PATH}/protocol/openid-connect/auth")
+        );
+        let query = authorization_url
+            .url
+            .query_pairs()
+            .into_owned()
+            .collect::<std::collections::HashMap<_, _>>();
+        assert_eq!(
+            query.get("client_id").map(String::as_str),
+            Some("golem-client")
+        );
+        assert_eq!(query.get("scope").map(String::as_str), Some("openid email"));
+        assert_eq!(
+            query.get("redirect_uri").map(String::as_str),
+            Some("http://localhost:9006/auth/callback")
+        );
+    }
+
+    #[test]
+    async fn rejects_provider_advertising_different_issuer() {
+        let issuer_url = start_mock_identity_provider(Some("https://other.example.com")).await;
+
+        let result = DefaultIdentityProvider::default()
+            .get_provider_metadata(&issuer_url)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IdentityProviderError::FailedToDiscoverProviderMetadata(_))
+        ));
+    }
+
+    #[test]
+    async fn verifies_bearer_token_signed_by_issuer() {
+        let issuer_url = start_mock_identity_provider(None).await;
+        let security_scheme = bearer_token_security_scheme(issuer_url.clone());
+        let token = bearer_token(&issuer_url, "counter-api", in_one_hour());
+
+        let claims = DefaultIdentityProvider::default()
+            .verify_bearer_token(&security_scheme, &token)
+            .await
+            .unwrap();
+
+        assert_eq!(claims.subject().as_str(), "service-account");
+        assert_eq!(claims.issuer(), &issuer_url);
+        assert!(
+            claims
+                .additional_claims()
+                .scopes()
+                .contains(&Scope::new("counters:write".to_string()))
+        );
+    }
+
+    #[test]
+    async fn rejects_bearer_token_for_other_audience() {
+        let issuer_url = start_mock_identity_provider(None).await;
+        let security_scheme = bearer_token_security_scheme(issuer_url.clone());
+        let token = bearer_token(&issuer_url, "other-api", in_one_hour());
+
+        let result = DefaultIdentityProvider::default()
+            .verify_bearer_token(&security_scheme, &token)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IdentityProviderError::BearerTokenVerificationError(_))
+        ));
+    }
+
+    #[test]
+    async fn rejects_bearer_token_of_other_issuer() {
+        let issuer_url = start_mock_identity_provider(None).await;
+        let security_scheme = bearer_token_security_scheme(issuer_url);
+        let other_issuer_url = IssuerUrl::new("https://other.example.com".to_string()).unwrap();
+        let token = bearer_token(&other_issuer_url, "counter-api", in_one_hour());
+
+        let result = DefaultIdentityProvider::default()
+            .verify_bearer_token(&security_scheme, &token)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(IdentityProviderError::BearerTokenVerificationError(_))
+        ));
+    }
+
+    #[test]
+    async fn rejects_expired_or_malformed_bearer_token() {
+        let issuer_url = start_mock_identity_provider(None).await;
+        let security_scheme = bearer_token_security_scheme(issuer_url.clone());
+        let identity_provider = DefaultIdentityProvider::default();
+
+        let expired_token = bearer_token(
+            &issuer_url,
+            "counter-api",
+            chrono::Utc::now() - chrono::Duration::hours(1),
+        );
+        let result = identity_provider
+            .verify_bearer_token(&security_scheme, &expired_token)
+            .await;
+        assert!(matches!(
+            result,
+            Err(IdentityProviderError::BearerTokenVerificationError(_))
+        ));
+
+        let result = identity_provider
+            .verify_bearer_token(&security_scheme, "not-a-jwt")
+            .await;
+        assert!(matches!(
+            result,
+            Err(IdentityProviderError::BearerTokenVerificationError(_))
+        ));
+    }
+
+    #[test]
+    async fn refreshes_signing_keys_of_issuer_at_most_once_per_interval() {
+        let (issuer_url, jwks_requests) = start_counting_mock_identity_provider(None).await;
+        let security_scheme = bearer_token_security_scheme(issuer_url.clone());
+        let identity_provider = DefaultIdentityProvider::default();
+
+        let unknown_signing_key = signing_key_from_pem(&UNKNOWN_SIGNING_KEY_PEM);
+        let forged_token = bearer_token_signed_with(
+            &unknown_signing_key,
+            &issuer_url,
+            "counter-api",
+            in_one_hour(),
+        );
+
+        for _ in 0..3 {
+            let result = identity_provider
+                .verify_bearer_token(&security_scheme, &forged_token)
+                .await;
+            assert!(matches!(
+                result,
+                Err(IdentityProviderError::BearerTokenVerificationError(_))
+            ));
+        }
+
+        // the initial discovery and a single refresh after the first signature failure
+        assert_eq!(jwks_requests.load(Ordering::SeqCst), 2);
+
+        let token = bearer_token(&issuer_url, "counter-api", in_one_hour());
+        assert!(
+            identity_provider
+                .verify_bearer_token(&security_scheme, &token)
+                .await
+                .is_ok()
+        );
+        assert_eq!(jwks_requests.load(Ordering::SeqCst), 2);
+    }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod api_key;
+mod bearer_token;
 pub mod handler;
 mod identity_provider;
 mod identity_provider_metadata;
@@ -492,7 +492,7 @@ mod records {
                 preferred_username: value.preferred_username,
 
                 claims: serde_json::to_string(&value.claims)
-                    .map_err(|e| anyhow!("CoreIdTokenClaims serialization error: {e}"))?,
+                    .map_err(|e| anyhow!("Claims serialization error: {e}"))?,
                 scopes: value.scopes.into_iter().map(|s| s.to_string()).collect(),
                 expires_at: value.expires_at,
             })
@@ -516,7 +516,7 @@ mod records {
                 preferred_username: value.preferred_username,
 
                 claims: serde_json::from_str(&value.claims)
-                    .map_err(|e| anyhow!("CoreIdTokenClaims deserialization error: {e}"))?,
+                    .map_err(|e| anyhow!("Claims deserialization error: {e}"))?,
                 scopes: value.scopes.into_iter().map(Scope::new).collect(),
                 expires_at: value.expires_at,
             })
@@ -226,6 +226,9 @@ pub fn error_to_status(error: WorkerError) -> Status {
                 worker_execution_error::Error::InvocationFailed(_) => {
                     "Invocation Failed".to_string()
                 }
+                worker_execution_error::Error::AgentQuotaExceeded(err) => {
+                    format!("Agent Quota Exceeded: {}", err.details)
+                }
             };
             Status::internal(message)
         }
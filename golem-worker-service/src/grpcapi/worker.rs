@@ -338,7 +338,13 @@ impl WorkerGrpcApi {
 
         let result = self
             .worker_service
-            .complete_promise(&worker_id, parameters.oplog_idx, parameters.data, auth)
+            .complete_promise(
+                &worker_id,
+                parameters.oplog_idx,
+                parameters.data,
+                parameters.rejection,
+                auth,
+            )
             .await?;
 
         Ok(result)
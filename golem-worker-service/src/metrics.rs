@@ -15,6 +15,19 @@
 use golem_common::golem_version;
 use golem_service_base::metrics::VERSION_INFO;
 use prometheus::*;
+use std::sync::LazyLock;
+
+static RATE_LIMIT_STORE_FAILURE_TOTAL: LazyLock<Counter> = LazyLock::new(|| {
+    register_counter!(
+        "rate_limit_store_failure_total",
+        "Number of rate limit checks failed because of the rate limit store"
+    )
+    .unwrap()
+});
+
+pub fn record_rate_limit_store_failure() {
+    RATE_LIMIT_STORE_FAILURE_TOTAL.inc();
+}
 
 pub fn register_all() -> Registry {
     VERSION_INFO.with_label_values(&[golem_version()]).inc();
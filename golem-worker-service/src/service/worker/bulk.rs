@@ -0,0 +1,813 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::BulkOperationsConfig;
+use crate::service::auth::AuthService;
+use crate::service::component::ComponentService;
+use crate::service::worker::{
+    BulkOperationPhase, BulkOperationRecord, BulkOperationSaveResult, BulkOperationStore,
+    BulkOperationStoreError, StoredBulkOperation, WorkerResult, WorkerService, WorkerServiceError,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use golem_common::SafeDisplay;
+use golem_common::model::component::ComponentId;
+use golem_common::model::worker::{
+    BulkWorkerOperation, BulkWorkerOperationFailure, BulkWorkerOperationId, BulkWorkerOperationJob,
+    BulkWorkerOperationRequest, BulkWorkerOperationStatus,
+};
+use golem_common::model::{ScanCursor, Timestamp, WorkerFilter, WorkerId};
+use golem_service_base::model::auth::{AuthCtx, EnvironmentAction};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task;
+use tokio::time::{MissedTickBehavior, interval};
+use tracing::{Instrument, error, info, info_span, warn};
+use uuid::Uuid;
+
+/// Upper limit of the configurable rate, keeping the period between two workers above zero
+pub const MAX_BULK_OPERATION_RATE: u32 = 1_000_000;
+
+/// Access to the workers targeted by bulk operations
+#[async_trait]
+pub trait BulkOperationWorkers: Send + Sync {
+    async fn find(
+        &self,
+        component_id: ComponentId,
+        filter: Option<WorkerFilter>,
+        cursor: ScanCursor,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Option<ScanCursor>, Vec<WorkerId>)>;
+
+    async fn apply(
+        &self,
+        worker_id: &WorkerId,
+        operation: &BulkWorkerOperation,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()>;
+}
+
+#[async_trait]
+impl BulkOperationWorkers for WorkerService {
+    async fn find(
+        &self,
+        component_id: ComponentId,
+        filter: Option<WorkerFilter>,
+        cursor: ScanCursor,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<(Option<ScanCursor>, Vec<WorkerId>)> {
+        let (next_cursor, workers) = self
+            .find_metadata(component_id, filter, cursor, count, false, auth_ctx)
+            .await?;
+        Ok((
+            next_cursor,
+            workers.into_iter().map(|worker| worker.worker_id).collect(),
+        ))
+    }
+
+    async fn apply(
+        &self,
+        worker_id: &WorkerId,
+        operation: &BulkWorkerOperation,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()> {
+        match operation {
+            BulkWorkerOperation::Interrupt(params) => {
+                self.interrupt(worker_id, params.recover_immediately, auth_ctx)
+                    .await
+            }
+            BulkWorkerOperation::Resume(_) => self.resume(worker_id, false, auth_ctx).await,
+            BulkWorkerOperation::Update(params) => {
+                self.update(worker_id, params.mode, params.target_revision, auth_ctx)
+                    .await
+            }
+            BulkWorkerOperation::Delete(_) => self.delete(worker_id, auth_ctx).await,
+            BulkWorkerOperation::Revert(params) => {
+                self.revert_worker(worker_id, params.target.clone(), auth_ctx)
+                    .await
+            }
+            BulkWorkerOperation::ActivatePlugin(params) => {
+                self.activate_plugin(worker_id, params.plugin_priority, auth_ctx)
+                    .await
+            }
+            BulkWorkerOperation::DeactivatePlugin(params) => {
+                self.deactivate_plugin(worker_id, params.plugin_priority, auth_ctx)
+                    .await
+            }
+        }
+    }
+}
+
+/// Applies an operation to every worker of a component matching a filter, in the background.
+///
+/// The progress of the operations is persisted in the configured store, so any worker service
+/// instance can report or cancel them, and operations abandoned by a stopped instance are resumed
+/// by another one.
+pub struct BulkOperationService {
+    component_service: Arc<dyn ComponentService>,
+    auth_service: Arc<dyn AuthService>,
+    workers: Arc<dyn BulkOperationWorkers>,
+    store: Arc<dyn BulkOperationStore>,
+    config: BulkOperationsConfig,
+    instance_id: Uuid,
+}
+
+impl BulkOperationService {
+    pub fn new(
+        component_service: Arc<dyn ComponentService>,
+        auth_service: Arc<dyn AuthService>,
+        workers: Arc<dyn BulkOperationWorkers>,
+        store: Arc<dyn BulkOperationStore>,
+        config: BulkOperationsConfig,
+    ) -> Self {
+        let instance_id = Uuid::new_v4();
+        Self::spawn_abandoned_operation_recovery(
+            workers.clone(),
+            store.clone(),
+            config.clone(),
+            instance_id,
+        );
+        Self {
+            component_service,
+            auth_service,
+            workers,
+            store,
+            config,
+            instance_id,
+        }
+    }
+
+    pub async fn start(
+        &self,
+        component_id: ComponentId,
+        request: BulkWorkerOperationRequest,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<BulkWorkerOperationJob> {
+        let concurrency = requested_limit(
+            "max_concurrency",
+            request.max_concurrency,
+            self.config.max_concurrency.max(1),
+        )?;
+        let rate = requested_limit(
+            "max_rate",
+            request.max_rate,
+            self.config.max_rate.clamp(1, MAX_BULK_OPERATION_RATE),
+        )?;
+
+        let component = self
+            .component_service
+            .get_latest_by_id(component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                required_action(&request.operation),
+                &auth_ctx,
+            )
+            .await?;
+
+        let record = BulkOperationRecord {
+            job: BulkWorkerOperationJob {
+                id: BulkWorkerOperationId::new(),
+                component_id,
+                filter: request.filter,
+                operation: request.operation,
+                status: BulkWorkerOperationStatus::Running,
+                started_at: Timestamp::now_utc(),
+                finished_at: None,
+                matched: 0,
+                succeeded: 0,
+                failed: 0,
+                failures: Vec::new(),
+                error: None,
+            },
+            environment_id: component.environment_id,
+            account_id: (!auth_ctx.is_system()).then(|| auth_ctx.account_id()),
+            concurrency,
+            rate,
+            phase: BulkOperationPhase::Enumerating {
+                cursor: ScanCursor::default(),
+            },
+        };
+
+        self.store
+            .create(&record, self.instance_id, Timestamp::now_utc())
+            .await?;
+
+        let job = record.job.clone();
+
+        BulkOperationRunner {
+            workers: self.workers.clone(),
+            store: self.store.clone(),
+            owner: self.instance_id,
+            record,
+            page_size: self.config.page_size,
+            max_reported_failures: self.config.max_reported_failures,
+            auth_ctx,
+        }
+        .spawn();
+
+        Ok(job)
+    }
+
+    pub async fn get(
+        &self,
+        component_id: ComponentId,
+        operation_id: BulkWorkerOperationId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<BulkWorkerOperationJob> {
+        let operation = self.get_operation(component_id, operation_id).await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                operation.record.environment_id,
+                EnvironmentAction::ViewWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        Ok(operation.record.job)
+    }
+
+    /// Stops processing further workers. Operations already sent to the executors are not undone.
+    pub async fn cancel(
+        &self,
+        component_id: ComponentId,
+        operation_id: BulkWorkerOperationId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<BulkWorkerOperationJob> {
+        let operation = self.get_operation(component_id, operation_id).await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                operation.record.environment_id,
+                EnvironmentAction::UpdateWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        if !self.store.request_cancellation(&operation_id).await? {
+            return Err(WorkerServiceError::BulkOperationNotFound(operation_id));
+        }
+
+        Ok(operation.record.job)
+    }
+
+    async fn get_operation(
+        &self,
+        component_id: ComponentId,
+        operation_id: BulkWorkerOperationId,
+    ) -> WorkerResult<StoredBulkOperation> {
+        self.store
+            .get(&operation_id)
+            .await?
+            .filter(|operation| operation.record.job.component_id == component_id)
+            .ok_or(WorkerServiceError::BulkOperationNotFound(operation_id))
+    }
+
+    fn spawn_abandoned_operation_recovery(
+        workers: Arc<dyn BulkOperationWorkers>,
+        store: Arc<dyn BulkOperationStore>,
+        config: BulkOperationsConfig,
+        instance_id: Uuid,
+    ) {
+        task::spawn(
+            async move {
+                let mut check_interval = interval(config.abandoned_check_interval);
+
+                loop {
+                    check_interval.tick().await;
+
+                    if let Err(err) =
+                        Self::resume_abandoned_operations(&workers, &store, &config, instance_id)
+                            .await
+                    {
+                        error!("Failed to resume abandoned bulk worker operations: {err}");
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    async fn resume_abandoned_operations(
+        workers: &Arc<dyn BulkOperationWorkers>,
+        store: &Arc<dyn BulkOperationStore>,
+        config: &BulkOperationsConfig,
+        instance_id: Uuid,
+    ) -> Result<(), BulkOperationStoreError> {
+        let now = Timestamp::now_utc();
+        let heartbeat_before = Timestamp::from(
+            now.to_millis()
+                .saturating_sub(config.abandoned_after.as_millis() as u64),
+        );
+
+        for operation in store.list_abandoned(heartbeat_before).await? {
+            if store.claim(&operation, instance_id, now).await? {
+                info!(
+                    operation_id = %operation.record.job.id,
+                    previous_owner = %operation.owner,
+                    "Resuming abandoned bulk worker operation"
+                );
+
+                // The operation was authorized when it was started, it continues on behalf of
+                // the same account
+                let auth_ctx = match operation.record.account_id {
+                    Some(account_id) => AuthCtx::impersonated_user(account_id),
+                    None => AuthCtx::system(),
+                };
+
+                BulkOperationRunner {
+                    workers: workers.clone(),
+                    store: store.clone(),
+                    owner: instance_id,
+                    record: operation.record,
+                    page_size: config.page_size,
+                    max_reported_failures: config.max_reported_failures,
+                    auth_ctx,
+                }
+                .spawn();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn required_action(operation: &BulkWorkerOperation) -> EnvironmentAction {
+    match operation {
+        BulkWorkerOperation::Delete(_) => EnvironmentAction::DeleteWorker,
+        _ => EnvironmentAction::UpdateWorker,
+    }
+}
+
+fn requested_limit(name: &str, requested: Option<u32>, cap: u32) -> WorkerResult<u32> {
+    match requested {
+        None => Ok(cap),
+        Some(value) if (1..=cap).contains(&value) => Ok(value),
+        Some(value) => Err(WorkerServiceError::InvalidBulkOperation(format!(
+            "{name} must be between 1 and {cap}, got {value}"
+        ))),
+    }
+}
+
+enum RunOutcome {
+    Finished { cancelled: bool },
+    OwnershipLost,
+}
+
+struct BulkOperationRunner {
+    workers: Arc<dyn BulkOperationWorkers>,
+    store: Arc<dyn BulkOperationStore>,
+    owner: Uuid,
+    record: BulkOperationRecord,
+    page_size: u64,
+    max_reported_failures: usize,
+    auth_ctx: AuthCtx,
+}
+
+impl BulkOperationRunner {
+    fn spawn(self) {
+        let span = info_span!(
+            "bulk_worker_operation",
+            operation_id = %self.record.job.id,
+            component_id = %self.record.job.component_id,
+            operation = %self.record.job.operation
+        );
+        tokio::spawn(self.run().instrument(span));
+    }
+
+    async fn run(mut self) {
+        info!("Running bulk worker operation");
+
+        let result = self.process_all().await;
+
+        let job = &mut self.record.job;
+        job.status = match result {
+            Ok(RunOutcome::OwnershipLost) => {
+                info!("Bulk worker operation was taken over by another worker service instance");
+                return;
+            }
+            Ok(RunOutcome::Finished { cancelled: true }) => BulkWorkerOperationStatus::Cancelled,
+            Ok(RunOutcome::Finished { cancelled: false }) => BulkWorkerOperationStatus::Completed,
+            Err(err) => {
+                warn!("Bulk worker operation failed: {err}");
+                job.error = Some(err.to_safe_string());
+                BulkWorkerOperationStatus::Failed
+            }
+        };
+        job.finished_at = Some(Timestamp::now_utc());
+
+        info!(
+            matched = job.matched,
+            succeeded = job.succeeded,
+            failed = job.failed,
+            "Bulk worker operation finished with status {}",
+            job.status
+        );
+
+        match self
+            .store
+            .save(&self.record, self.owner, Timestamp::now_utc())
+            .await
+        {
+            Ok(BulkOperationSaveResult::Saved { .. }) => {}
+            Ok(BulkOperationSaveResult::OwnershipLost) => {
+                info!("Bulk worker operation was taken over by another worker service instance");
+            }
+            Err(err) => {
+                error!("Failed to save the result of the bulk worker operation: {err}");
+            }
+        }
+    }
+
+    /// First stores the IDs of all matched workers, so deleting or updating them does not affect
+    /// the enumeration, then applies the operation to the stored workers. The progress is saved
+    /// after each step, a resumed operation may apply the operation to the workers of the
+    /// interrupted step again.
+    async fn process_all(&mut self) -> WorkerResult<RunOutcome> {
+        // A single interval shared by all concurrent tasks limits the overall rate of requests
+        // sent to the executors, independently of the concurrency
+        let mut interval = interval(Duration::from_secs(1) / self.record.rate.max(1));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let interval = tokio::sync::Mutex::new(interval);
+
+        // Applying roughly a second's worth of workers between two saves
+        let chunk_size = self.page_size.min(self.record.rate as u64).max(1);
+
+        loop {
+            match self.record.phase.clone() {
+                BulkOperationPhase::Enumerating { cursor } => {
+                    let (next_cursor, worker_ids) = self
+                        .workers
+                        .find(
+                            self.record.job.component_id,
+                            self.record.job.filter.clone(),
+                            cursor,
+                            self.page_size,
+                            self.auth_ctx.clone(),
+                        )
+                        .await?;
+
+                    self.store
+                        .add_targets(&self.record.job.id, self.record.job.matched, &worker_ids)
+                        .await?;
+
+                    self.record.job.matched += worker_ids.len() as u64;
+                    self.record.phase = match next_cursor {
+                        Some(cursor) => BulkOperationPhase::Enumerating { cursor },
+                        None => BulkOperationPhase::Applying { position: 0 },
+                    };
+                }
+                BulkOperationPhase::Applying { position } => {
+                    let worker_ids = if position < self.record.job.matched {
+                        self.store
+                            .get_targets(&self.record.job.id, position, chunk_size)
+                            .await?
+                    } else {
+                        Vec::new()
+                    };
+
+                    if worker_ids.is_empty() {
+                        return Ok(RunOutcome::Finished { cancelled: false });
+                    }
+
+                    let count = worker_ids.len() as u64;
+                    let results = self.apply_all(&interval, worker_ids).await;
+                    for (worker_id, result) in results {
+                        self.record_result(worker_id, result);
+                    }
+
+                    self.record.phase = BulkOperationPhase::Applying {
+                        position: position + count,
+                    };
+                }
+            }
+
+            match self
+                .store
+                .save(&self.record, self.owner, Timestamp::now_utc())
+                .await?
+            {
+                BulkOperationSaveResult::Saved {
+                    cancel_requested: true,
+                } => return Ok(RunOutcome::Finished { cancelled: true }),
+                BulkOperationSaveResult::Saved {
+                    cancel_requested: false,
+                } => {}
+                BulkOperationSaveResult::OwnershipLost => return Ok(RunOutcome::OwnershipLost),
+            }
+        }
+    }
+
+    async fn apply_all(
+        &self,
+        interval: &tokio::sync::Mutex<tokio::time::Interval>,
+        worker_ids: Vec<WorkerId>,
+    ) -> Vec<(WorkerId, WorkerResult<()>)> {
+        futures::stream::iter(worker_ids)
+            .map(|worker_id| async move {
+                interval.lock().await.tick().await;
+                let result = self
+                    .workers
+                    .apply(
+                        &worker_id,
+                        &self.record.job.operation,
+                        self.auth_ctx.clone(),
+                    )
+                    .await;
+                (worker_id, result)
+            })
+            .buffer_unordered(self.record.concurrency.max(1) as usize)
+            .collect()
+            .await
+    }
+
+    fn record_result(&mut self, worker_id: WorkerId, result: WorkerResult<()>) {
+        let job = &mut self.record.job;
+        match result {
+            Ok(()) => job.succeeded += 1,
+            Err(err) => {
+                job.failed += 1;
+                if job.failures.len() < self.max_reported_failures {
+                    job.failures.push(BulkWorkerOperationFailure {
+                        worker_id,
+                        error: err.to_safe_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::worker::InMemoryBulkOperationStore;
+    use golem_common::model::environment::EnvironmentId;
+    use golem_common::model::worker::BulkDeleteWorkers;
+    use std::sync::Mutex;
+    use test_r::test;
+
+    /// Workers of a single component, enumerated by offset like a paginated scan
+    struct TestWorkers {
+        workers: Mutex<Vec<WorkerId>>,
+        applied: Mutex<Vec<WorkerId>>,
+    }
+
+    impl TestWorkers {
+        fn new(component_id: ComponentId, count: usize) -> Arc<Self> {
+            Arc::new(Self {
+                workers: Mutex::new(
+                    (0..count)
+                        .map(|idx| WorkerId {
+                            component_id,
+                            worker_name: format!("worker-{idx}"),
+                        })
+                        .collect(),
+                ),
+                applied: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl BulkOperationWorkers for TestWorkers {
+        async fn find(
+            &self,
+            _component_id: ComponentId,
+            _filter: Option<WorkerFilter>,
+            cursor: ScanCursor,
+            count: u64,
+            _auth_ctx: AuthCtx,
+        ) -> WorkerResult<(Option<ScanCursor>, Vec<WorkerId>)> {
+            let workers = self.workers.lock().unwrap();
+            let start = (cursor.cursor as usize).min(workers.len());
+            let end = (start + count as usize).min(workers.len());
+            let next_cursor = (end < workers.len()).then_some(ScanCursor {
+                cursor: end as u64,
+                layer: 0,
+            });
+            Ok((next_cursor, workers[start..end].to_vec()))
+        }
+
+        async fn apply(
+            &self,
+            worker_id: &WorkerId,
+            operation: &BulkWorkerOperation,
+            _auth_ctx: AuthCtx,
+        ) -> WorkerResult<()> {
+            self.applied.lock().unwrap().push(worker_id.clone());
+            if let BulkWorkerOperation::Delete(_) = operation {
+                self.workers.lock().unwrap().retain(|id| id != worker_id);
+            }
+            Ok(())
+        }
+    }
+
+    fn delete_record(component_id: ComponentId) -> BulkOperationRecord {
+        BulkOperationRecord {
+            job: BulkWorkerOperationJob {
+                id: BulkWorkerOperationId::new(),
+                component_id,
+                filter: None,
+                operation: BulkWorkerOperation::Delete(BulkDeleteWorkers {}),
+                status: BulkWorkerOperationStatus::Running,
+                started_at: Timestamp::now_utc(),
+                finished_at: None,
+                matched: 0,
+                succeeded: 0,
+                failed: 0,
+                failures: Vec::new(),
+                error: None,
+            },
+            environment_id: EnvironmentId::new(),
+            account_id: None,
+            concurrency: 2,
+            rate: MAX_BULK_OPERATION_RATE,
+            phase: BulkOperationPhase::Enumerating {
+                cursor: ScanCursor::default(),
+            },
+        }
+    }
+
+    async fn runner(
+        workers: Arc<TestWorkers>,
+        store: Arc<InMemoryBulkOperationStore>,
+        record: BulkOperationRecord,
+    ) -> BulkOperationRunner {
+        let owner = Uuid::new_v4();
+        store
+            .create(&record, owner, Timestamp::now_utc())
+            .await
+            .unwrap();
+        BulkOperationRunner {
+            workers,
+            store,
+            owner,
+            record,
+            page_size: 2,
+            max_reported_failures: 10,
+            auth_ctx: AuthCtx::system(),
+        }
+    }
+
+    fn store() -> Arc<InMemoryBulkOperationStore> {
+        Arc::new(InMemoryBulkOperationStore::new(
+            Duration::from_hours(1),
+            Duration::from_mins(1),
+        ))
+    }
+
+    #[test]
+    fn requested_limits_must_be_within_the_configured_cap() {
+        assert_eq!(requested_limit("max_rate", None, 200).unwrap(), 200);
+        assert_eq!(requested_limit("max_rate", Some(1), 200).unwrap(), 1);
+        assert_eq!(requested_limit("max_rate", Some(200), 200).unwrap(), 200);
+        assert!(matches!(
+            requested_limit("max_rate", Some(0), 200),
+            Err(WorkerServiceError::InvalidBulkOperation(_))
+        ));
+        assert!(matches!(
+            requested_limit("max_rate", Some(201), 200),
+            Err(WorkerServiceError::InvalidBulkOperation(_))
+        ));
+    }
+
+    #[test]
+    fn the_highest_rate_has_a_non_zero_period() {
+        assert!(Duration::from_secs(1) / MAX_BULK_OPERATION_RATE > Duration::ZERO);
+    }
+
+    #[test]
+    async fn deleting_workers_applies_to_every_matched_worker() {
+        let component_id = ComponentId::new();
+        let workers = TestWorkers::new(component_id, 5);
+        let store = store();
+        let record = delete_record(component_id);
+        let id = record.job.id;
+
+        runner(workers.clone(), store.clone(), record)
+            .await
+            .run()
+            .await;
+
+        // enumerating by offset while deleting would skip workers
+        assert!(workers.workers.lock().unwrap().is_empty());
+        assert_eq!(workers.applied.lock().unwrap().len(), 5);
+
+        let job = store.get(&id).await.unwrap().unwrap().record.job;
+        assert_eq!(job.status, BulkWorkerOperationStatus::Completed);
+        assert_eq!(job.matched, 5);
+        assert_eq!(job.succeeded, 5);
+        assert!(job.finished_at.is_some());
+        assert!(store.get_targets(&id, 0, 10).await.unwrap().is_empty());
+    }
+
+    #[test]
+    async fn cancelled_operations_stop_at_the_next_save() {
+        let component_id = ComponentId::new();
+        let workers = TestWorkers::new(component_id, 5);
+        let store = store();
+        let record = delete_record(component_id);
+        let id = record.job.id;
+
+        let runner = runner(workers.clone(), store.clone(), record).await;
+        assert!(store.request_cancellation(&id).await.unwrap());
+        runner.run().await;
+
+        assert!(workers.applied.lock().unwrap().is_empty());
+        let job = store.get(&id).await.unwrap().unwrap().record.job;
+        assert_eq!(job.status, BulkWorkerOperationStatus::Cancelled);
+    }
+
+    #[test]
+    async fn runners_stop_when_another_instance_takes_over() {
+        let component_id = ComponentId::new();
+        let workers = TestWorkers::new(component_id, 5);
+        let store = store();
+        let record = delete_record(component_id);
+        let id = record.job.id;
+
+        let runner = runner(workers.clone(), store.clone(), record).await;
+        let stored = store.get(&id).await.unwrap().unwrap();
+        assert!(
+            store
+                .claim(&stored, Uuid::new_v4(), Timestamp::now_utc())
+                .await
+                .unwrap()
+        );
+        runner.run().await;
+
+        assert!(workers.applied.lock().unwrap().is_empty());
+        let job = store.get(&id).await.unwrap().unwrap().record.job;
+        assert_eq!(job.status, BulkWorkerOperationStatus::Running);
+        assert_eq!(job.finished_at, None);
+    }
+
+    #[test]
+    async fn abandoned_operations_are_resumed_from_the_stored_targets() {
+        let component_id = ComponentId::new();
+        let workers = TestWorkers::new(component_id, 5);
+        let store = store();
+        let mut record = delete_record(component_id);
+        let id = record.job.id;
+
+        // the previous owner stored all targets and applied the operation to the first two
+        let targets = workers.workers.lock().unwrap().clone();
+        record.job.matched = 5;
+        record.job.succeeded = 2;
+        record.phase = BulkOperationPhase::Applying { position: 2 };
+        record.concurrency = 1;
+        store
+            .create(&record, Uuid::new_v4(), Timestamp::from(1000))
+            .await
+            .unwrap();
+        store.add_targets(&id, 0, &targets).await.unwrap();
+
+        let config = BulkOperationsConfig {
+            page_size: 2,
+            ..BulkOperationsConfig::default()
+        };
+        let workers_dyn: Arc<dyn BulkOperationWorkers> = workers.clone();
+        let store_dyn: Arc<dyn BulkOperationStore> = store.clone();
+        BulkOperationService::resume_abandoned_operations(
+            &workers_dyn,
+            &store_dyn,
+            &config,
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+
+        let job = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let job = store.get(&id).await.unwrap().unwrap().record.job;
+                if job.finished_at.is_some() {
+                    break job;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(job.status, BulkWorkerOperationStatus::Completed);
+        assert_eq!(job.succeeded, 5);
+        assert_eq!(*workers.applied.lock().unwrap(), targets[2..].to_vec());
+    }
+}
@@ -0,0 +1,1150 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use golem_common::error_forwarding;
+use golem_common::model::account::AccountId;
+use golem_common::model::environment::EnvironmentId;
+use golem_common::model::worker::{BulkWorkerOperationId, BulkWorkerOperationJob};
+use golem_common::model::{ScanCursor, Timestamp, WorkerId};
+use golem_common::redis::{RedisError, RedisPool};
+use golem_service_base::db::sqlite::SqlitePool;
+use golem_service_base::repo::RepoError;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task;
+use tokio::time::interval;
+use tracing::{Instrument, error};
+use uuid::Uuid;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BulkOperationStoreError {
+    #[error(transparent)]
+    InternalError(#[from] anyhow::Error),
+}
+
+error_forwarding!(BulkOperationStoreError, RepoError);
+
+impl From<RedisError> for BulkOperationStoreError {
+    fn from(value: RedisError) -> Self {
+        Self::InternalError(anyhow::Error::from(value).context("RedisError"))
+    }
+}
+
+impl From<serde_json::Error> for BulkOperationStoreError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::InternalError(anyhow::Error::from(value).context("Invalid bulk operation record"))
+    }
+}
+
+/// Everything needed to continue a bulk operation on any worker service instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkOperationRecord {
+    pub job: BulkWorkerOperationJob,
+    pub environment_id: EnvironmentId,
+    /// Account which started the operation, `None` if it was started by the system
+    pub account_id: Option<AccountId>,
+    pub concurrency: u32,
+    pub rate: u32,
+    pub phase: BulkOperationPhase,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BulkOperationPhase {
+    /// Storing the IDs of the matched workers, continuing the enumeration from `cursor`
+    Enumerating { cursor: ScanCursor },
+    /// Applying the operation to the stored workers, continuing from `position`
+    Applying { position: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredBulkOperation {
+    pub record: BulkOperationRecord,
+    /// Worker service instance running the operation
+    pub owner: Uuid,
+    /// Last time the owner saved the progress of the operation
+    pub heartbeat_at: Timestamp,
+    pub cancel_requested: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkOperationSaveResult {
+    Saved {
+        cancel_requested: bool,
+    },
+    /// Another instance took over the operation, the caller must stop running it
+    OwnershipLost,
+}
+
+#[async_trait]
+pub trait BulkOperationStore: Send + Sync {
+    async fn create(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<(), BulkOperationStoreError>;
+
+    async fn get(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<Option<StoredBulkOperation>, BulkOperationStoreError>;
+
+    /// Saves the progress of an operation if it is still owned by `owner`, refreshing its heartbeat.
+    /// The stored worker IDs are dropped once the record is finished.
+    async fn save(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<BulkOperationSaveResult, BulkOperationStoreError>;
+
+    /// Returns false if the operation does not exist
+    async fn request_cancellation(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<bool, BulkOperationStoreError>;
+
+    /// Stores the IDs of matched workers starting at `first_position`, overwriting the ones
+    /// stored at the same positions by an earlier attempt
+    async fn add_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        worker_ids: &[WorkerId],
+    ) -> Result<(), BulkOperationStoreError>;
+
+    async fn get_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        count: u64,
+    ) -> Result<Vec<WorkerId>, BulkOperationStoreError>;
+
+    /// Unfinished operations whose owner did not save them since `heartbeat_before`
+    async fn list_abandoned(
+        &self,
+        heartbeat_before: Timestamp,
+    ) -> Result<Vec<StoredBulkOperation>, BulkOperationStoreError>;
+
+    /// Transfers the ownership of an abandoned operation to `owner`, unless its owner or
+    /// heartbeat changed since it was listed
+    async fn claim(
+        &self,
+        operation: &StoredBulkOperation,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<bool, BulkOperationStoreError>;
+}
+
+fn is_expired(record: &BulkOperationRecord, retention: Duration, now: Timestamp) -> bool {
+    match record.job.finished_at {
+        Some(finished_at) => {
+            now.to_millis().saturating_sub(finished_at.to_millis()) >= retention.as_millis() as u64
+        }
+        None => false,
+    }
+}
+
+struct InMemoryBulkOperation {
+    operation: StoredBulkOperation,
+    targets: BTreeMap<u64, WorkerId>,
+}
+
+/// Keeps the operations of a single worker service instance, they are lost on restart
+pub struct InMemoryBulkOperationStore {
+    operations: Arc<Mutex<HashMap<BulkWorkerOperationId, InMemoryBulkOperation>>>,
+}
+
+impl InMemoryBulkOperationStore {
+    pub fn new(retention: Duration, cleanup_interval: Duration) -> Self {
+        let operations = Arc::new(Mutex::new(HashMap::new()));
+        Self::spawn_expiration_task(operations.clone(), retention, cleanup_interval);
+        Self { operations }
+    }
+
+    fn spawn_expiration_task(
+        operations: Arc<Mutex<HashMap<BulkWorkerOperationId, InMemoryBulkOperation>>>,
+        retention: Duration,
+        cleanup_interval: Duration,
+    ) {
+        task::spawn(
+            async move {
+                let mut cleanup_interval = interval(cleanup_interval);
+
+                loop {
+                    cleanup_interval.tick().await;
+
+                    let now = Timestamp::now_utc();
+                    operations.lock().unwrap().retain(|_, operation| {
+                        !is_expired(&operation.operation.record, retention, now)
+                    });
+                }
+            }
+            .in_current_span(),
+        );
+    }
+}
+
+#[async_trait]
+impl BulkOperationStore for InMemoryBulkOperationStore {
+    async fn create(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<(), BulkOperationStoreError> {
+        self.operations.lock().unwrap().insert(
+            record.job.id,
+            InMemoryBulkOperation {
+                operation: StoredBulkOperation {
+                    record: record.clone(),
+                    owner,
+                    heartbeat_at: now,
+                    cancel_requested: false,
+                },
+                targets: BTreeMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<Option<StoredBulkOperation>, BulkOperationStoreError> {
+        Ok(self
+            .operations
+            .lock()
+            .unwrap()
+            .get(id)
+            .map(|operation| operation.operation.clone()))
+    }
+
+    async fn save(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<BulkOperationSaveResult, BulkOperationStoreError> {
+        let mut operations = self.operations.lock().unwrap();
+        match operations.get_mut(&record.job.id) {
+            Some(operation) if operation.operation.owner == owner => {
+                operation.operation.record = record.clone();
+                operation.operation.heartbeat_at = now;
+                if record.job.finished_at.is_some() {
+                    operation.targets.clear();
+                }
+                Ok(BulkOperationSaveResult::Saved {
+                    cancel_requested: operation.operation.cancel_requested,
+                })
+            }
+            _ => Ok(BulkOperationSaveResult::OwnershipLost),
+        }
+    }
+
+    async fn request_cancellation(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<bool, BulkOperationStoreError> {
+        match self.operations.lock().unwrap().get_mut(id) {
+            Some(operation) => {
+                operation.operation.cancel_requested = true;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn add_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        worker_ids: &[WorkerId],
+    ) -> Result<(), BulkOperationStoreError> {
+        if let Some(operation) = self.operations.lock().unwrap().get_mut(id) {
+            for (position, worker_id) in (first_position..).zip(worker_ids) {
+                operation.targets.insert(position, worker_id.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        count: u64,
+    ) -> Result<Vec<WorkerId>, BulkOperationStoreError> {
+        Ok(self
+            .operations
+            .lock()
+            .unwrap()
+            .get(id)
+            .map(|operation| {
+                operation
+                    .targets
+                    .range(first_position..first_position.saturating_add(count))
+                    .map(|(_, worker_id)| worker_id.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn list_abandoned(
+        &self,
+        heartbeat_before: Timestamp,
+    ) -> Result<Vec<StoredBulkOperation>, BulkOperationStoreError> {
+        Ok(self
+            .operations
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|operation| {
+                operation.operation.record.job.finished_at.is_none()
+                    && operation.operation.heartbeat_at.to_millis() < heartbeat_before.to_millis()
+            })
+            .map(|operation| operation.operation.clone())
+            .collect())
+    }
+
+    async fn claim(
+        &self,
+        operation: &StoredBulkOperation,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<bool, BulkOperationStoreError> {
+        match self
+            .operations
+            .lock()
+            .unwrap()
+            .get_mut(&operation.record.job.id)
+        {
+            Some(current)
+                if current.operation.owner == operation.owner
+                    && current.operation.heartbeat_at == operation.heartbeat_at =>
+            {
+                current.operation.owner = owner;
+                current.operation.heartbeat_at = now;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Shares the operations between all worker service instances using the same redis
+pub struct RedisBulkOperationStore {
+    redis: RedisPool,
+    retention: Duration,
+}
+
+impl RedisBulkOperationStore {
+    const RUNNING_OPERATIONS_KEY: &'static str = "bulk_worker_operations:running";
+
+    const OPERATION_FIELDS: [&'static str; 4] = ["record", "owner", "heartbeat", "cancel"];
+
+    // KEYS: operation, running operations, targets
+    // ARGV: owner, record, heartbeat, finished, retention in seconds, operation id
+    // Returns -1 if the operation is not owned by the caller, otherwise the cancellation flag
+    const SAVE_SCRIPT: &'static str = r#"
+        if redis.call('HGET', KEYS[1], 'owner') ~= ARGV[1] then
+            return -1
+        end
+        redis.call('HSET', KEYS[1], 'record', ARGV[2], 'heartbeat', ARGV[3])
+        if ARGV[4] == '1' then
+            redis.call('SREM', KEYS[2], ARGV[6])
+            redis.call('DEL', KEYS[3])
+            redis.call('EXPIRE', KEYS[1], ARGV[5])
+        end
+        return tonumber(redis.call('HGET', KEYS[1], 'cancel') or '0')
+    "#;
+
+    // KEYS: operation
+    // ARGV: expected owner, expected heartbeat, new owner, new heartbeat
+    const CLAIM_SCRIPT: &'static str = r#"
+        local current = redis.call('HMGET', KEYS[1], 'owner', 'heartbeat')
+        if current[1] ~= ARGV[1] or current[2] ~= ARGV[2] then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], 'owner', ARGV[3], 'heartbeat', ARGV[4])
+        return 1
+    "#;
+
+    // KEYS: operation
+    const CANCEL_SCRIPT: &'static str = r#"
+        if redis.call('EXISTS', KEYS[1]) == 0 then
+            return 0
+        end
+        redis.call('HSET', KEYS[1], 'cancel', '1')
+        return 1
+    "#;
+
+    pub fn new(redis: RedisPool, retention: Duration) -> Self {
+        Self { redis, retention }
+    }
+
+    fn redis_key_for_operation(id: &BulkWorkerOperationId) -> String {
+        format!("bulk_worker_operation:{id}")
+    }
+
+    fn redis_key_for_targets(id: &BulkWorkerOperationId) -> String {
+        format!("bulk_worker_operation:{id}:targets")
+    }
+
+    fn parse_operation(
+        fields: Vec<Option<String>>,
+    ) -> Result<Option<StoredBulkOperation>, BulkOperationStoreError> {
+        let [record, owner, heartbeat, cancel]: [Option<String>; 4] = fields
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Unexpected number of fields in stored bulk operation"))?;
+
+        let (Some(record), Some(owner), Some(heartbeat)) = (record, owner, heartbeat) else {
+            return Ok(None);
+        };
+
+        Ok(Some(StoredBulkOperation {
+            record: serde_json::from_str(&record)?,
+            owner: Uuid::parse_str(&owner).map_err(anyhow::Error::from)?,
+            heartbeat_at: Timestamp::from(heartbeat.parse::<u64>().map_err(anyhow::Error::from)?),
+            cancel_requested: cancel.as_deref() == Some("1"),
+        }))
+    }
+}
+
+#[async_trait]
+impl BulkOperationStore for RedisBulkOperationStore {
+    async fn create(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<(), BulkOperationStoreError> {
+        let fields: HashMap<String, String> = HashMap::from([
+            ("record".to_string(), serde_json::to_string(record)?),
+            ("owner".to_string(), owner.to_string()),
+            ("heartbeat".to_string(), now.to_millis().to_string()),
+            ("cancel".to_string(), "0".to_string()),
+        ]);
+
+        let _: () = self
+            .redis
+            .with("bulk_operation_store", "create")
+            .hset(Self::redis_key_for_operation(&record.job.id), fields)
+            .await?;
+        let _: () = self
+            .redis
+            .with("bulk_operation_store", "create")
+            .sadd(Self::RUNNING_OPERATIONS_KEY, record.job.id.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<Option<StoredBulkOperation>, BulkOperationStoreError> {
+        let fields: Vec<Option<String>> = self
+            .redis
+            .with("bulk_operation_store", "get")
+            .hmget(
+                Self::redis_key_for_operation(id),
+                Self::OPERATION_FIELDS.to_vec(),
+            )
+            .await?;
+
+        Self::parse_operation(fields)
+    }
+
+    async fn save(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<BulkOperationSaveResult, BulkOperationStoreError> {
+        let result: i64 = self
+            .redis
+            .with("bulk_operation_store", "save")
+            .eval(
+                Self::SAVE_SCRIPT,
+                vec![
+                    Self::redis_key_for_operation(&record.job.id),
+                    Self::RUNNING_OPERATIONS_KEY.to_string(),
+                    Self::redis_key_for_targets(&record.job.id),
+                ],
+                vec![
+                    owner.to_string(),
+                    serde_json::to_string(record)?,
+                    now.to_millis().to_string(),
+                    if record.job.finished_at.is_some() {
+                        "1".to_string()
+                    } else {
+                        "0".to_string()
+                    },
+                    self.retention.as_secs().max(1).to_string(),
+                    record.job.id.to_string(),
+                ],
+            )
+            .await?;
+
+        if result < 0 {
+            Ok(BulkOperationSaveResult::OwnershipLost)
+        } else {
+            Ok(BulkOperationSaveResult::Saved {
+                cancel_requested: result == 1,
+            })
+        }
+    }
+
+    async fn request_cancellation(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<bool, BulkOperationStoreError> {
+        let result: i64 = self
+            .redis
+            .with("bulk_operation_store", "request_cancellation")
+            .eval(
+                Self::CANCEL_SCRIPT,
+                vec![Self::redis_key_for_operation(id)],
+                vec![],
+            )
+            .await?;
+
+        Ok(result == 1)
+    }
+
+    async fn add_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        worker_ids: &[WorkerId],
+    ) -> Result<(), BulkOperationStoreError> {
+        if worker_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut targets = HashMap::new();
+        for (position, worker_id) in (first_position..).zip(worker_ids) {
+            targets.insert(position.to_string(), serde_json::to_string(worker_id)?);
+        }
+
+        let _: () = self
+            .redis
+            .with("bulk_operation_store", "add_targets")
+            .hset(Self::redis_key_for_targets(id), targets)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        count: u64,
+    ) -> Result<Vec<WorkerId>, BulkOperationStoreError> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let positions: Vec<String> = (first_position..first_position.saturating_add(count))
+            .map(|position| position.to_string())
+            .collect();
+
+        let targets: Vec<Option<String>> = self
+            .redis
+            .with("bulk_operation_store", "get_targets")
+            .hmget(Self::redis_key_for_targets(id), positions)
+            .await?;
+
+        let mut result = Vec::new();
+        for target in targets.into_iter().flatten() {
+            result.push(serde_json::from_str(&target)?);
+        }
+        Ok(result)
+    }
+
+    async fn list_abandoned(
+        &self,
+        heartbeat_before: Timestamp,
+    ) -> Result<Vec<StoredBulkOperation>, BulkOperationStoreError> {
+        let ids: Vec<String> = self
+            .redis
+            .with("bulk_operation_store", "list_abandoned")
+            .smembers(Self::RUNNING_OPERATIONS_KEY)
+            .await?;
+
+        let mut result = Vec::new();
+        for id in ids {
+            let Ok(id) = BulkWorkerOperationId::try_from(id.as_str()) else {
+                continue;
+            };
+            match self.get(&id).await? {
+                Some(operation) => {
+                    if operation.record.job.finished_at.is_none()
+                        && operation.heartbeat_at.to_millis() < heartbeat_before.to_millis()
+                    {
+                        result.push(operation);
+                    }
+                }
+                None => {
+                    let _: () = self
+                        .redis
+                        .with("bulk_operation_store", "list_abandoned")
+                        .srem(Self::RUNNING_OPERATIONS_KEY, id.to_string())
+                        .await?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    async fn claim(
+        &self,
+        operation: &StoredBulkOperation,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<bool, BulkOperationStoreError> {
+        let result: i64 = self
+            .redis
+            .with("bulk_operation_store", "claim")
+            .eval(
+                Self::CLAIM_SCRIPT,
+                vec![Self::redis_key_for_operation(&operation.record.job.id)],
+                vec![
+                    operation.owner.to_string(),
+                    operation.heartbeat_at.to_millis().to_string(),
+                    owner.to_string(),
+                    now.to_millis().to_string(),
+                ],
+            )
+            .await?;
+
+        Ok(result == 1)
+    }
+}
+
+/// Shares the operations between the worker service instances using the same database file
+pub struct SqliteBulkOperationStore {
+    pool: SqlitePool,
+}
+
+impl SqliteBulkOperationStore {
+    pub async fn new(
+        pool: SqlitePool,
+        retention: Duration,
+        cleanup_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        Self::init(&pool).await?;
+        Self::spawn_expiration_task(pool.clone(), retention, cleanup_interval);
+        Ok(Self { pool })
+    }
+
+    async fn init(pool: &SqlitePool) -> anyhow::Result<()> {
+        pool.with_rw("bulk_operation_store", "init")
+            .execute(sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS bulk_worker_operation (
+                    operation_id TEXT PRIMARY KEY,
+                    record TEXT NOT NULL,
+                    owner TEXT NOT NULL,
+                    heartbeat_at INTEGER NOT NULL,
+                    cancel_requested INTEGER NOT NULL,
+                    finished_at INTEGER
+                );
+                "#,
+            ))
+            .await?;
+
+        pool.with_rw("bulk_operation_store", "init")
+            .execute(sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS bulk_worker_operation_target (
+                    operation_id TEXT NOT NULL,
+                    position INTEGER NOT NULL,
+                    worker_id TEXT NOT NULL,
+                    PRIMARY KEY (operation_id, position)
+                );
+                "#,
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    fn spawn_expiration_task(db_pool: SqlitePool, retention: Duration, cleanup_interval: Duration) {
+        task::spawn(
+            async move {
+                let mut cleanup_interval = interval(cleanup_interval);
+
+                loop {
+                    cleanup_interval.tick().await;
+
+                    let finished_before = Timestamp::now_utc()
+                        .to_millis()
+                        .saturating_sub(retention.as_millis() as u64);
+
+                    if let Err(e) =
+                        Self::cleanup_expired_operations(db_pool.clone(), finished_before as i64)
+                            .await
+                    {
+                        error!("Failed to expire bulk worker operations: {}", e);
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    async fn cleanup_expired_operations(
+        pool: SqlitePool,
+        finished_before: i64,
+    ) -> anyhow::Result<()> {
+        let query = sqlx::query("DELETE FROM bulk_worker_operation WHERE finished_at < ?;")
+            .bind(finished_before);
+
+        pool.with_rw("bulk_operation_store", "cleanup_expired_operations")
+            .execute(query)
+            .await?;
+
+        Ok(())
+    }
+
+    fn parse_operation(
+        row: &sqlx::sqlite::SqliteRow,
+    ) -> Result<StoredBulkOperation, BulkOperationStoreError> {
+        let record: String = row.get("record");
+        let owner: String = row.get("owner");
+        let heartbeat_at: i64 = row.get("heartbeat_at");
+        let cancel_requested: bool = row.get("cancel_requested");
+
+        Ok(StoredBulkOperation {
+            record: serde_json::from_str(&record)?,
+            owner: Uuid::parse_str(&owner).map_err(anyhow::Error::from)?,
+            heartbeat_at: Timestamp::from(heartbeat_at as u64),
+            cancel_requested,
+        })
+    }
+}
+
+#[async_trait]
+impl BulkOperationStore for SqliteBulkOperationStore {
+    async fn create(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<(), BulkOperationStoreError> {
+        self.pool
+            .with_rw("bulk_operation_store", "create")
+            .execute(
+                sqlx::query(
+                    "INSERT INTO bulk_worker_operation (operation_id, record, owner, heartbeat_at, cancel_requested, finished_at) VALUES (?, ?, ?, ?, 0, NULL)",
+                )
+                .bind(record.job.id.to_string())
+                .bind(serde_json::to_string(record)?)
+                .bind(owner.to_string())
+                .bind(now.to_millis() as i64),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<Option<StoredBulkOperation>, BulkOperationStoreError> {
+        let row = self
+            .pool
+            .with_ro("bulk_operation_store", "get")
+            .fetch_optional(
+                sqlx::query(
+                    "SELECT record, owner, heartbeat_at, cancel_requested FROM bulk_worker_operation WHERE operation_id = ?",
+                )
+                .bind(id.to_string()),
+            )
+            .await?;
+
+        row.as_ref().map(Self::parse_operation).transpose()
+    }
+
+    async fn save(
+        &self,
+        record: &BulkOperationRecord,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<BulkOperationSaveResult, BulkOperationStoreError> {
+        let finished_at = record.job.finished_at.map(|at| at.to_millis() as i64);
+
+        let mut tx = self
+            .pool
+            .with_rw("bulk_operation_store", "save")
+            .begin()
+            .await?;
+
+        let row = tx
+            .fetch_optional(
+                sqlx::query(
+                    "UPDATE bulk_worker_operation SET record = ?, heartbeat_at = ?, finished_at = ? WHERE operation_id = ? AND owner = ? RETURNING cancel_requested",
+                )
+                .bind(serde_json::to_string(record)?)
+                .bind(now.to_millis() as i64)
+                .bind(finished_at)
+                .bind(record.job.id.to_string())
+                .bind(owner.to_string()),
+            )
+            .await?;
+
+        let result = match row {
+            Some(row) => {
+                if finished_at.is_some() {
+                    tx.execute(
+                        sqlx::query(
+                            "DELETE FROM bulk_worker_operation_target WHERE operation_id = ?",
+                        )
+                        .bind(record.job.id.to_string()),
+                    )
+                    .await?;
+                }
+                BulkOperationSaveResult::Saved {
+                    cancel_requested: row.get("cancel_requested"),
+                }
+            }
+            None => BulkOperationSaveResult::OwnershipLost,
+        };
+
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn request_cancellation(
+        &self,
+        id: &BulkWorkerOperationId,
+    ) -> Result<bool, BulkOperationStoreError> {
+        let row = self
+            .pool
+            .with_rw("bulk_operation_store", "request_cancellation")
+            .fetch_optional(
+                sqlx::query(
+                    "UPDATE bulk_worker_operation SET cancel_requested = 1 WHERE operation_id = ? RETURNING operation_id",
+                )
+                .bind(id.to_string()),
+            )
+            .await?;
+
+        Ok(row.is_some())
+    }
+
+    async fn add_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        worker_ids: &[WorkerId],
+    ) -> Result<(), BulkOperationStoreError> {
+        let mut tx = self
+            .pool
+            .with_rw("bulk_operation_store", "add_targets")
+            .begin()
+            .await?;
+
+        for (position, worker_id) in (first_position..).zip(worker_ids) {
+            tx.execute(
+                sqlx::query(
+                    "INSERT OR REPLACE INTO bulk_worker_operation_target (operation_id, position, worker_id) VALUES (?, ?, ?)",
+                )
+                .bind(id.to_string())
+                .bind(position as i64)
+                .bind(serde_json::to_string(worker_id)?),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_targets(
+        &self,
+        id: &BulkWorkerOperationId,
+        first_position: u64,
+        count: u64,
+    ) -> Result<Vec<WorkerId>, BulkOperationStoreError> {
+        let rows = self
+            .pool
+            .with_ro("bulk_operation_store", "get_targets")
+            .fetch_all(
+                sqlx::query(
+                    "SELECT worker_id FROM bulk_worker_operation_target WHERE operation_id = ? AND position >= ? ORDER BY position LIMIT ?",
+                )
+                .bind(id.to_string())
+                .bind(first_position as i64)
+                .bind(count.min(i64::MAX as u64) as i64),
+            )
+            .await?;
+
+        let mut result = Vec::with_capacity(rows.len());
+        for row in rows {
+            let worker_id: String = row.get("worker_id");
+            result.push(serde_json::from_str(&worker_id)?);
+        }
+        Ok(result)
+    }
+
+    async fn list_abandoned(
+        &self,
+        heartbeat_before: Timestamp,
+    ) -> Result<Vec<StoredBulkOperation>, BulkOperationStoreError> {
+        let rows = self
+            .pool
+            .with_ro("bulk_operation_store", "list_abandoned")
+            .fetch_all(
+                sqlx::query(
+                    "SELECT record, owner, heartbeat_at, cancel_requested FROM bulk_worker_operation WHERE finished_at IS NULL AND heartbeat_at < ?",
+                )
+                .bind(heartbeat_before.to_millis() as i64),
+            )
+            .await?;
+
+        rows.iter().map(Self::parse_operation).collect()
+    }
+
+    async fn claim(
+        &self,
+        operation: &StoredBulkOperation,
+        owner: Uuid,
+        now: Timestamp,
+    ) -> Result<bool, BulkOperationStoreError> {
+        let row = self
+            .pool
+            .with_rw("bulk_operation_store", "claim")
+            .fetch_optional(
+                sqlx::query(
+                    "UPDATE bulk_worker_operation SET owner = ?, heartbeat_at = ? WHERE operation_id = ? AND owner = ? AND heartbeat_at = ? RETURNING operation_id",
+                )
+                .bind(owner.to_string())
+                .bind(now.to_millis() as i64)
+                .bind(operation.record.job.id.to_string())
+                .bind(operation.owner.to_string())
+                .bind(operation.heartbeat_at.to_millis() as i64),
+            )
+            .await?;
+
+        Ok(row.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::component::ComponentId;
+    use golem_common::model::worker::{
+        BulkResumeWorkers, BulkWorkerOperation, BulkWorkerOperationStatus,
+    };
+    use test_r::test;
+
+    fn record() -> BulkOperationRecord {
+        BulkOperationRecord {
+            job: BulkWorkerOperationJob {
+                id: BulkWorkerOperationId::new(),
+                component_id: ComponentId::new(),
+                filter: None,
+                operation: BulkWorkerOperation::Resume(BulkResumeWorkers {}),
+                status: BulkWorkerOperationStatus::Running,
+                started_at: Timestamp::from(1000),
+                finished_at: None,
+                matched: 0,
+                succeeded: 0,
+                failed: 0,
+                failures: Vec::new(),
+                error: None,
+            },
+            environment_id: EnvironmentId::new(),
+            account_id: Some(AccountId::new()),
+            concurrency: 1,
+            rate: 1,
+            phase: BulkOperationPhase::Enumerating {
+                cursor: ScanCursor::default(),
+            },
+        }
+    }
+
+    fn worker_id(component_id: ComponentId, name: &str) -> WorkerId {
+        WorkerId {
+            component_id,
+            worker_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    async fn in_memory_store_only_saves_the_progress_of_the_owner() {
+        let store =
+            InMemoryBulkOperationStore::new(Duration::from_hours(1), Duration::from_mins(1));
+        let owner = Uuid::new_v4();
+        let mut record = record();
+
+        store
+            .create(&record, owner, Timestamp::from(1000))
+            .await
+            .unwrap();
+
+        record.job.matched = 10;
+        assert_eq!(
+            store
+                .save(&record, owner, Timestamp::from(2000))
+                .await
+                .unwrap(),
+            BulkOperationSaveResult::Saved {
+                cancel_requested: false
+            }
+        );
+        assert_eq!(
+            store
+                .save(&record, Uuid::new_v4(), Timestamp::from(3000))
+                .await
+                .unwrap(),
+            BulkOperationSaveResult::OwnershipLost
+        );
+
+        let stored = store.get(&record.job.id).await.unwrap().unwrap();
+        assert_eq!(stored.record.job.matched, 10);
+        assert_eq!(stored.heartbeat_at, Timestamp::from(2000));
+
+        assert!(store.request_cancellation(&record.job.id).await.unwrap());
+        assert!(
+            !store
+                .request_cancellation(&BulkWorkerOperationId::new())
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            store
+                .save(&record, owner, Timestamp::from(4000))
+                .await
+                .unwrap(),
+            BulkOperationSaveResult::Saved {
+                cancel_requested: true
+            }
+        );
+    }
+
+    #[test]
+    async fn in_memory_store_keeps_targets_by_position() {
+        let store =
+            InMemoryBulkOperationStore::new(Duration::from_hours(1), Duration::from_mins(1));
+        let owner = Uuid::new_v4();
+        let mut record = record();
+        let component_id = record.job.component_id;
+
+        store
+            .create(&record, owner, Timestamp::from(1000))
+            .await
+            .unwrap();
+
+        store
+            .add_targets(
+                &record.job.id,
+                0,
+                &[worker_id(component_id, "a"), worker_id(component_id, "b")],
+            )
+            .await
+            .unwrap();
+        // adding the same page again after a restart overwrites it
+        store
+            .add_targets(
+                &record.job.id,
+                1,
+                &[worker_id(component_id, "b"), worker_id(component_id, "c")],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get_targets(&record.job.id, 1, 10).await.unwrap(),
+            vec![worker_id(component_id, "b"), worker_id(component_id, "c")]
+        );
+
+        record.job.finished_at = Some(Timestamp::from(2000));
+        store
+            .save(&record, owner, Timestamp::from(2000))
+            .await
+            .unwrap();
+        assert!(
+            store
+                .get_targets(&record.job.id, 0, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    async fn in_memory_store_claims_abandoned_operations_once() {
+        let store =
+            InMemoryBulkOperationStore::new(Duration::from_hours(1), Duration::from_mins(1));
+        let record = record();
+
+        store
+            .create(&record, Uuid::new_v4(), Timestamp::from(1000))
+            .await
+            .unwrap();
+
+        assert!(
+            store
+                .list_abandoned(Timestamp::from(1000))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let abandoned = store.list_abandoned(Timestamp::from(5000)).await.unwrap();
+        assert_eq!(abandoned.len(), 1);
+
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        assert!(
+            store
+                .claim(&abandoned[0], first, Timestamp::from(6000))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !store
+                .claim(&abandoned[0], second, Timestamp::from(6000))
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            store.get(&record.job.id).await.unwrap().unwrap().owner,
+            first
+        );
+    }
+
+    #[test]
+    fn finished_records_expire_after_the_retention() {
+        let mut record = record();
+        let retention = Duration::from_secs(10);
+
+        assert!(!is_expired(&record, retention, Timestamp::from(1_000_000)));
+
+        record.job.finished_at = Some(Timestamp::from(1000));
+        assert!(!is_expired(&record, retention, Timestamp::from(10_999)));
+        assert!(is_expired(&record, retention, Timestamp::from(11_000)));
+    }
+}
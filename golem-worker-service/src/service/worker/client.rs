@@ -14,8 +14,8 @@
 
 use super::WorkerResult;
 use super::{
-    AllExecutors, CallWorkerExecutorError, HasWorkerExecutorClients, RandomExecutor,
-    ResponseMapResult, RoutingLogic, WorkerServiceError, WorkerStream,
+    AllExecutors, CallWorkerExecutorError, HasWorkerExecutorClients, InvocationParameters,
+    RandomExecutor, ResponseMapResult, RoutingLogic, WorkerServiceError, WorkerStream,
 };
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -25,10 +25,13 @@ use golem_api_grpc::proto::golem::worker::{InvocationContext, InvokeResult, LogE
 use golem_api_grpc::proto::golem::workerexecutor;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
-    ActivatePluginRequest, CancelInvocationRequest, CompletePromiseRequest, ConnectWorkerRequest,
-    CreateWorkerRequest, DeactivatePluginRequest, ForkWorkerRequest, InterruptWorkerRequest,
-    InvokeAndAwaitWorkerJsonRequest, InvokeAndAwaitWorkerRequest, ResumeWorkerRequest,
-    RevertWorkerRequest, SearchOplogResponse, UpdateWorkerRequest,
+    ActivatePluginRequest, CancelInvocationRequest, CancelRecurringInvocationRequest,
+    ChangeWorkerLabelsRequest, CompletePromiseRequest, ConnectWorkerRequest, CreateWorkerRequest,
+    DeactivatePluginRequest, ForkWorkerRequest, GetRecurringInvocationsRequest,
+    GetRetentionReportRequest, InterruptWorkerRequest, InvokeAndAwaitWorkerJsonRequest,
+    InvokeAndAwaitWorkerRequest, ResumeWorkerRequest, RevertWorkerRequest,
+    ScheduleRecurringInvocationRequest, SearchComponentOplogRequest, SearchOplogResponse,
+    UpdateWorkerRequest, schedule_recurring_invocation_request,
 };
 use golem_common::model::RetryConfig;
 use golem_common::model::account::AccountId;
@@ -37,11 +40,15 @@ use golem_common::model::component::{
 };
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::oplog::{OplogCursor, PublicOplogEntry};
-use golem_common::model::oplog::{OplogIndex, PublicOplogEntryWithIndex};
+use golem_common::model::oplog::{OplogIndex, OplogSearchHit, PublicOplogEntryWithIndex};
 use golem_common::model::worker::WorkerUpdateMode;
-use golem_common::model::worker::{RevertWorkerTarget, WorkerMetadataDto};
+use golem_common::model::worker::{
+    RecurringInvocation, RetentionCandidate, RevertWorkerTarget, ScheduleRecurrence,
+    WorkerMetadataDto,
+};
 use golem_common::model::{
-    FilterComparator, IdempotencyKey, PromiseId, ScanCursor, WorkerFilter, WorkerId, WorkerStatus,
+    FilterComparator, IdempotencyKey, PromiseId, ScanCursor, Timestamp, WorkerFilter, WorkerId,
+    WorkerStatus,
 };
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 use golem_service_base::grpc::client::MultiTargetGrpcClient;
@@ -51,12 +58,13 @@ use golem_service_base::service::routing_table::{HasRoutingTableService, Routing
 use golem_wasm::ValueAndType;
 use golem_wasm::analysis::AnalysedFunctionResult;
 use golem_wasm::protobuf::Val as ProtoVal;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::pin::Pin;
 use std::{collections::HashMap, sync::Arc};
 use tonic::Code;
 use tonic::transport::Channel;
 use tonic_tracing_opentelemetry::middleware::client::OtelGrpcService;
+use tracing::warn;
 
 #[async_trait]
 pub trait WorkerClient: Send + Sync {
@@ -163,6 +171,7 @@ pub trait WorkerClient: Send + Sync {
         worker_id: &WorkerId,
         oplog_id: u64,
         data: Vec<u8>,
+        rejection: Option<String>,
         environment_id: EnvironmentId,
         auth_ctx: AuthCtx,
     ) -> WorkerResult<bool>;
@@ -230,6 +239,22 @@ pub trait WorkerClient: Send + Sync {
         auth_ctx: AuthCtx,
     ) -> Result<GetOplogResponse, WorkerServiceError>;
 
+    async fn export_oplog(
+        &self,
+        worker_id: &WorkerId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>>;
+
+    async fn import_oplog(
+        &self,
+        worker_id: &WorkerId,
+        archive: Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + 'static>>,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<OplogIndex>;
+
     async fn get_file_system_node(
         &self,
         worker_id: &WorkerId,
@@ -264,6 +289,15 @@ pub trait WorkerClient: Send + Sync {
         auth_ctx: AuthCtx,
     ) -> WorkerResult<()>;
 
+    async fn change_labels(
+        &self,
+        worker_id: &WorkerId,
+        set: BTreeMap<String, String>,
+        removed: BTreeSet<String>,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()>;
+
     async fn fork_worker(
         &self,
         source_worker_id: &WorkerId,
@@ -289,6 +323,51 @@ pub trait WorkerClient: Send + Sync {
         environment_id: EnvironmentId,
         auth_ctx: AuthCtx,
     ) -> WorkerResult<bool>;
+
+    async fn schedule_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: Option<IdempotencyKey>,
+        function_name: String,
+        params: InvocationParameters,
+        recurrence: ScheduleRecurrence,
+        start_at: Option<Timestamp>,
+        invocation_context: Option<InvocationContext>,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<RecurringInvocation>;
+
+    async fn get_recurring_invocations(
+        &self,
+        worker_id: &WorkerId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RecurringInvocation>>;
+
+    async fn cancel_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<bool>;
+
+    async fn get_retention_report(
+        &self,
+        component_id: &ComponentId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RetentionCandidate>>;
+
+    async fn search_component_oplog(
+        &self,
+        component_id: &ComponentId,
+        environment_id: EnvironmentId,
+        query: String,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<OplogSearchHit>>;
 }
 
 pub struct TypedResult {
@@ -861,6 +940,7 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         worker_id: &WorkerId,
         oplog_id: u64,
         data: Vec<u8>,
+        rejection: Option<String>,
         environment_id: EnvironmentId,
         auth_ctx: AuthCtx,
     ) -> WorkerResult<bool> {
@@ -876,13 +956,15 @@ impl WorkerClient for WorkerExecutorWorkerClient {
                 move |worker_executor_client| {
                     let promise_id = promise_id.clone();
                     let data = data.clone();
+                    let rejection = rejection.clone();
                     Box::pin(
                         worker_executor_client
                             .complete_promise(CompletePromiseRequest {
                                 promise_id: Some(promise_id.into()),
                                 data,
                                 environment_id: Some(environment_id.into()),
-                                auth_ctx: Some(auth_ctx.clone().into())
+                                auth_ctx: Some(auth_ctx.clone().into()),
+                                rejection,
                             })
                     )
                 },
@@ -1232,6 +1314,130 @@ impl WorkerClient for WorkerExecutorWorkerClient {
             .await
     }
 
+    async fn export_oplog(
+        &self,
+        worker_id: &WorkerId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>> {
+        let worker_id = worker_id.clone();
+        let stream = self
+            .call_worker_executor(
+                worker_id.clone(),
+                "export_oplog",
+                move |worker_executor_client| {
+                    let worker_id = worker_id.clone();
+                    Box::pin(worker_executor_client.export_oplog(
+                        workerexecutor::v1::ExportOplogRequest {
+                            worker_id: Some(worker_id.into()),
+                            environment_id: Some(environment_id.into()),
+                            auth_ctx: Some(auth_ctx.clone().into()),
+                        },
+                    ))
+                },
+                |response| Ok(WorkerStream::new(response.into_inner())),
+                WorkerServiceError::InternalCallError,
+            )
+            .await?;
+
+        let to_chunk =
+            |item: Result<workerexecutor::v1::ExportOplogResponse, tonic::Status>| match item
+                .map_err(|_| WorkerServiceError::Internal("Stream error".to_string()))?
+                .result
+            {
+                Some(workerexecutor::v1::export_oplog_response::Result::Success(bytes)) => {
+                    Ok(Bytes::from(bytes))
+                }
+                Some(workerexecutor::v1::export_oplog_response::Result::Failure(err)) => {
+                    let converted = WorkerExecutorError::try_from(err).map_err(|err| {
+                        WorkerServiceError::Internal(format!("Failed converting errors {err}"))
+                    })?;
+                    Err(converted.into())
+                }
+                None => Err(WorkerServiceError::Internal("Malformed chunk".to_string())),
+            };
+
+        // The archive always has at least one chunk, failing before the first one (for example
+        // because the worker does not exist) is reported as an error instead of a broken stream
+        let (first, stream) = stream.into_future().await;
+        let first =
+            to_chunk(first.ok_or(WorkerServiceError::Internal("Empty stream".to_string()))?)?;
+
+        Ok(Box::pin(
+            futures::stream::once(async move { Ok(first) }).chain(stream.map(to_chunk)),
+        ))
+    }
+
+    async fn import_oplog(
+        &self,
+        worker_id: &WorkerId,
+        archive: Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + 'static>>,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<OplogIndex> {
+        let worker_id = worker_id.clone();
+        // The archive can only be streamed once, a retried call fails instead of sending a
+        // truncated archive
+        let archive = Arc::new(std::sync::Mutex::new(Some(archive)));
+        self.call_worker_executor(
+            worker_id.clone(),
+            "import_oplog",
+            move |worker_executor_client| {
+                let header = workerexecutor::v1::ImportOplogRequest {
+                    request: Some(workerexecutor::v1::import_oplog_request::Request::Header(
+                        workerexecutor::v1::ImportOplogRequestHeader {
+                            worker_id: Some(worker_id.clone().into()),
+                            component_owner_account_id: Some(account_id.into()),
+                            environment_id: Some(environment_id.into()),
+                            auth_ctx: Some(auth_ctx.clone().into()),
+                        },
+                    )),
+                };
+                let archive = archive.lock().unwrap().take();
+                Box::pin(async move {
+                    let archive = archive.ok_or_else(|| {
+                        tonic::Status::aborted("The oplog archive has already been consumed")
+                    })?;
+                    // A failing upload ends the stream early, which the executor rejects as an
+                    // incomplete archive
+                    let chunks = archive
+                        .take_while(|chunk| {
+                            if let Err(err) = chunk {
+                                warn!("Failed to read the uploaded oplog archive: {err}");
+                            }
+                            futures::future::ready(chunk.is_ok())
+                        })
+                        .filter_map(|chunk| futures::future::ready(chunk.ok()))
+                        .map(|chunk| workerexecutor::v1::ImportOplogRequest {
+                            request: Some(
+                                workerexecutor::v1::import_oplog_request::Request::Chunk(
+                                    chunk.to_vec(),
+                                ),
+                            ),
+                        });
+                    worker_executor_client
+                        .import_oplog(futures::stream::once(async move { header }).chain(chunks))
+                        .await
+                })
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::ImportOplogResponse {
+                    result:
+                        Some(workerexecutor::v1::import_oplog_response::Result::Success(
+                            workerexecutor::v1::ImportOplogSuccessResponse { last_index },
+                        )),
+                } => Ok(OplogIndex::from_u64(last_index)),
+                workerexecutor::v1::ImportOplogResponse {
+                    result: Some(workerexecutor::v1::import_oplog_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::ImportOplogResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+        .await
+    }
+
     async fn get_file_system_node(
         &self,
         worker_id: &WorkerId,
@@ -1466,6 +1672,47 @@ impl WorkerClient for WorkerExecutorWorkerClient {
         Ok(())
     }
 
+    async fn change_labels(
+        &self,
+        worker_id: &WorkerId,
+        set: BTreeMap<String, String>,
+        removed: BTreeSet<String>,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()> {
+        let worker_id = worker_id.clone();
+        self.call_worker_executor(
+            worker_id.clone(),
+            "change_worker_labels",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                Box::pin(
+                    worker_executor_client.change_worker_labels(ChangeWorkerLabelsRequest {
+                        worker_id: Some(worker_id.into()),
+                        set: set.clone().into_iter().collect(),
+                        removed: removed.iter().cloned().collect(),
+                        environment_id: Some(environment_id.into()),
+                        auth_ctx: Some(auth_ctx.clone().into())
+                    }),
+                )
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::ChangeWorkerLabelsResponse {
+                    result: Some(workerexecutor::v1::change_worker_labels_response::Result::Success(_)),
+                } => Ok(()),
+                workerexecutor::v1::ChangeWorkerLabelsResponse {
+                    result:
+                    Some(workerexecutor::v1::change_worker_labels_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::ChangeWorkerLabelsResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+
+        Ok(())
+    }
+
     async fn fork_worker(
         &self,
         source_worker_id: &WorkerId,
@@ -1579,6 +1826,275 @@ impl WorkerClient for WorkerExecutorWorkerClient {
             .await?;
         Ok(canceled)
     }
+
+    async fn schedule_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: Option<IdempotencyKey>,
+        function_name: String,
+        params: InvocationParameters,
+        recurrence: ScheduleRecurrence,
+        start_at: Option<Timestamp>,
+        invocation_context: Option<InvocationContext>,
+        environment_id: EnvironmentId,
+        account_id: AccountId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<RecurringInvocation> {
+        let worker_id = worker_id.clone();
+        let input = match params {
+            InvocationParameters::TypedProtoVals(vals) => {
+                schedule_recurring_invocation_request::Input::TypedInput(
+                    workerexecutor::v1::TypedInvocationInput {
+                        values: vals.into_iter().map(|v| ProtoVal::from(v.value)).collect(),
+                    },
+                )
+            }
+            InvocationParameters::RawJsonStrings(jsons) => {
+                schedule_recurring_invocation_request::Input::JsonInput(
+                    workerexecutor::v1::JsonInvocationInput { values: jsons },
+                )
+            }
+        };
+        let recurring_invocation = self.call_worker_executor(
+            worker_id.clone(),
+            "schedule_recurring_invocation",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                Box::pin(worker_executor_client.schedule_recurring_invocation(
+                    ScheduleRecurringInvocationRequest {
+                        worker_id: Some(worker_id.into()),
+                        name: function_name.clone(),
+                        input: Some(input.clone()),
+                        idempotency_key: idempotency_key.clone().map(|k| k.into()),
+                        recurrence: Some(recurrence.clone().into()),
+                        start_at: start_at.map(|t| t.into()),
+                        component_owner_account_id: Some(account_id.into()),
+                        environment_id: Some(environment_id.into()),
+                        context: invocation_context.clone(),
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                    },
+                ))
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::ScheduleRecurringInvocationResponse {
+                    result: Some(workerexecutor::v1::schedule_recurring_invocation_response::Result::Success(invocation)),
+                } => {
+                    let invocation: RecurringInvocation = invocation.try_into().map_err(|err| {
+                        WorkerExecutorError::unknown(format!(
+                            "Unexpected recurring invocation in response: {err}"
+                        ))
+                    })?;
+                    Ok(invocation)
+                }
+                workerexecutor::v1::ScheduleRecurringInvocationResponse {
+                    result: Some(workerexecutor::v1::schedule_recurring_invocation_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::ScheduleRecurringInvocationResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+        Ok(recurring_invocation)
+    }
+
+    async fn get_recurring_invocations(
+        &self,
+        worker_id: &WorkerId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RecurringInvocation>> {
+        let worker_id = worker_id.clone();
+        let invocations = self.call_worker_executor(
+            worker_id.clone(),
+            "get_recurring_invocations",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                Box::pin(worker_executor_client.get_recurring_invocations(
+                    GetRecurringInvocationsRequest {
+                        worker_id: Some(worker_id.into()),
+                        environment_id: Some(environment_id.into()),
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                    },
+                ))
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::GetRecurringInvocationsResponse {
+                    result: Some(workerexecutor::v1::get_recurring_invocations_response::Result::Success(
+                        workerexecutor::v1::GetRecurringInvocationsSuccessResponse { invocations },
+                    )),
+                } => {
+                    let invocations = invocations
+                        .into_iter()
+                        .map(|i| i.try_into())
+                        .collect::<Result<Vec<RecurringInvocation>, _>>()
+                        .map_err(|err| {
+                            WorkerExecutorError::unknown(format!(
+                                "Unexpected recurring invocation in response: {err}"
+                            ))
+                        })?;
+                    Ok(invocations)
+                }
+                workerexecutor::v1::GetRecurringInvocationsResponse {
+                    result: Some(workerexecutor::v1::get_recurring_invocations_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::GetRecurringInvocationsResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+        Ok(invocations)
+    }
+
+    async fn cancel_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<bool> {
+        let worker_id = worker_id.clone();
+        let idempotency_key = idempotency_key.clone();
+        let canceled = self.call_worker_executor(
+            worker_id.clone(),
+            "cancel_recurring_invocation",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                let idempotency_key = idempotency_key.clone();
+                Box::pin(worker_executor_client.cancel_recurring_invocation(
+                    CancelRecurringInvocationRequest {
+                        worker_id: Some(worker_id.into()),
+                        idempotency_key: Some(idempotency_key.into()),
+                        environment_id: Some(environment_id.into()),
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                    },
+                ))
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::CancelRecurringInvocationResponse {
+                    result: Some(workerexecutor::v1::cancel_recurring_invocation_response::Result::Success(canceled)),
+                } => Ok(canceled),
+                workerexecutor::v1::CancelRecurringInvocationResponse {
+                    result: Some(workerexecutor::v1::cancel_recurring_invocation_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::CancelRecurringInvocationResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+        Ok(canceled)
+    }
+
+    async fn get_retention_report(
+        &self,
+        component_id: &ComponentId,
+        environment_id: EnvironmentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RetentionCandidate>> {
+        let component_id = *component_id;
+        let result = self.call_worker_executor(
+            AllExecutors,
+            "get_retention_report",
+            move |worker_executor_client| {
+                Box::pin(worker_executor_client.get_retention_report(
+                    GetRetentionReportRequest {
+                        component_id: Some(component_id.into()),
+                        environment_id: Some(environment_id.into()),
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                    },
+                ))
+            },
+            |responses| {
+                responses.into_iter().map(|response| {
+                    match response.into_inner() {
+                        workerexecutor::v1::GetRetentionReportResponse {
+                            result: Some(workerexecutor::v1::get_retention_report_response::Result::Success(
+                                workerexecutor::v1::GetRetentionReportSuccessResponse { candidates },
+                            )),
+                        } => {
+                            let candidates = candidates
+                                .into_iter()
+                                .map(|c| c.try_into())
+                                .collect::<Result<Vec<RetentionCandidate>, _>>()
+                                .map_err(|err| {
+                                    WorkerExecutorError::unknown(format!(
+                                        "Unexpected retention candidate in response: {err}"
+                                    ))
+                                })?;
+                            Ok(candidates)
+                        }
+                        workerexecutor::v1::GetRetentionReportResponse {
+                            result: Some(workerexecutor::v1::get_retention_report_response::Result::Failure(err)),
+                        } => Err(err.into()),
+                        workerexecutor::v1::GetRetentionReportResponse { .. } => Err("Empty response".into()),
+                    }
+                }).collect::<Result<Vec<_>, ResponseMapResult>>()
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    async fn search_component_oplog(
+        &self,
+        component_id: &ComponentId,
+        environment_id: EnvironmentId,
+        query: String,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<OplogSearchHit>> {
+        let component_id = *component_id;
+        let result = self.call_worker_executor(
+            AllExecutors,
+            "search_component_oplog",
+            move |worker_executor_client| {
+                Box::pin(worker_executor_client.search_component_oplog(
+                    SearchComponentOplogRequest {
+                        component_id: Some(component_id.into()),
+                        environment_id: Some(environment_id.into()),
+                        query: query.clone(),
+                        count,
+                        auth_ctx: Some(auth_ctx.clone().into()),
+                    },
+                ))
+            },
+            |responses| {
+                responses.into_iter().map(|response| {
+                    match response.into_inner() {
+                        workerexecutor::v1::SearchComponentOplogResponse {
+                            result: Some(workerexecutor::v1::search_component_oplog_response::Result::Success(
+                                workerexecutor::v1::SearchComponentOplogSuccessResponse { hits },
+                            )),
+                        } => {
+                            let hits = hits
+                                .into_iter()
+                                .map(|hit| hit.try_into())
+                                .collect::<Result<Vec<OplogSearchHit>, _>>()
+                                .map_err(|err| {
+                                    WorkerExecutorError::unknown(format!(
+                                        "Unexpected oplog search hit in response: {err}"
+                                    ))
+                                })?;
+                            Ok(hits)
+                        }
+                        workerexecutor::v1::SearchComponentOplogResponse {
+                            result: Some(workerexecutor::v1::search_component_oplog_response::Result::Failure(err)),
+                        } => Err(err.into()),
+                        workerexecutor::v1::SearchComponentOplogResponse { .. } => Err("Empty response".into()),
+                    }
+                }).collect::<Result<Vec<_>, ResponseMapResult>>()
+            },
+            WorkerServiceError::InternalCallError,
+        )
+            .await?;
+
+        // Each executor returns its newest matches, merging them into a single page
+        let mut hits: Vec<OplogSearchHit> = result.into_iter().flatten().collect();
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.entry.timestamp()));
+        hits.truncate(count as usize);
+        Ok(hits)
+    }
 }
 
 fn is_filter_with_running_status(filter: &WorkerFilter) -> bool {
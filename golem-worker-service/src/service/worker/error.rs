@@ -15,11 +15,12 @@
 use crate::service::auth::AuthServiceError;
 use crate::service::component::ComponentServiceError;
 use crate::service::limit::LimitServiceError;
-use crate::service::worker::CallWorkerExecutorError;
+use crate::service::worker::{BulkOperationStoreError, CallWorkerExecutorError};
 use golem_common::SafeDisplay;
 use golem_common::model::WorkerId;
 use golem_common::model::account::AccountId;
 use golem_common::model::component::{ComponentFilePath, ComponentId};
+use golem_common::model::worker::BulkWorkerOperationId;
 use golem_service_base::clients::registry::RegistryServiceError;
 use golem_service_base::error::worker_executor::WorkerExecutorError;
 
@@ -49,6 +50,10 @@ pub enum WorkerServiceError {
     FileNotFound(ComponentFilePath),
     #[error("Bad file type: {0}")]
     BadFileType(ComponentFilePath),
+    #[error("Bulk operation not found: {0}")]
+    BulkOperationNotFound(BulkWorkerOperationId),
+    #[error("Invalid bulk operation: {0}")]
+    InvalidBulkOperation(String),
     #[error(transparent)]
     RegistryServiceError(#[from] RegistryServiceError),
 }
@@ -66,6 +71,8 @@ impl SafeDisplay for WorkerServiceError {
             Self::InternalCallError(inner) => inner.to_safe_string(),
             Self::FileNotFound(_) => self.to_string(),
             Self::BadFileType(_) => self.to_string(),
+            Self::BulkOperationNotFound(_) => self.to_string(),
+            Self::InvalidBulkOperation(_) => self.to_string(),
             Self::LimitError(inner) => inner.to_safe_string(),
             Self::AuthError(inner) => inner.to_safe_string(),
             Self::RegistryServiceError(inner) => inner.to_safe_string(),
@@ -73,6 +80,12 @@ impl SafeDisplay for WorkerServiceError {
     }
 }
 
+impl From<BulkOperationStoreError> for WorkerServiceError {
+    fn from(error: BulkOperationStoreError) -> Self {
+        Self::Internal(error.to_string())
+    }
+}
+
 impl From<WorkerServiceError> for golem_api_grpc::proto::golem::worker::v1::WorkerError {
     fn from(error: WorkerServiceError) -> Self {
         Self {
@@ -93,6 +106,7 @@ impl From<WorkerServiceError> for golem_api_grpc::proto::golem::worker::v1::work
             | WorkerServiceError::AccountIdNotFound(_)
             | WorkerServiceError::WorkerNotFound(_)
             | WorkerServiceError::FileNotFound(_)
+            | WorkerServiceError::BulkOperationNotFound(_)
             | WorkerServiceError::RegistryServiceError(RegistryServiceError::NotFound(_))
             | WorkerServiceError::Component(ComponentServiceError::ComponentNotFound)
             | WorkerServiceError::GolemError(WorkerExecutorError::WorkerNotFound { .. }) => {
@@ -103,6 +117,7 @@ impl From<WorkerServiceError> for golem_api_grpc::proto::golem::worker::v1::work
 
             WorkerServiceError::BadFileType(_)
             | WorkerServiceError::TypeChecker(_)
+            | WorkerServiceError::InvalidBulkOperation(_)
             | WorkerServiceError::RegistryServiceError(RegistryServiceError::BadRequest(_)) => {
                 Self::BadRequest(ErrorsBody {
                     errors: vec![error.to_safe_string()],
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 mod agents;
+mod bulk;
+mod bulk_store;
 mod client;
 mod connect;
 mod connect_proxy;
@@ -23,6 +25,8 @@ mod service;
 mod worker_stream;
 
 pub use agents::*;
+pub use bulk::*;
+pub use bulk_store::*;
 pub use client::*;
 pub use connect::*;
 pub use connect_proxy::*;
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::WorkerResult;
-use super::{ConnectWorkerStream, WorkerClient, WorkerServiceError};
+use super::{ConnectWorkerStream, InvocationParameters, WorkerClient, WorkerServiceError};
 use crate::service::auth::AuthService;
 use crate::service::component::ComponentService;
 use crate::service::limit::LimitService;
@@ -26,10 +26,13 @@ use golem_common::model::component::{
 };
 use golem_common::model::environment::EnvironmentId;
 use golem_common::model::oplog::OplogCursor;
-use golem_common::model::oplog::OplogIndex;
+use golem_common::model::oplog::{OplogIndex, OplogSearchHit};
 use golem_common::model::worker::WorkerUpdateMode;
-use golem_common::model::worker::{RevertWorkerTarget, WorkerMetadataDto};
-use golem_common::model::{IdempotencyKey, ScanCursor, WorkerFilter, WorkerId};
+use golem_common::model::worker::{
+    RecurringInvocation, RetentionCandidate, RevertWorkerTarget, ScheduleRecurrence,
+    WorkerMetadataDto,
+};
+use golem_common::model::{IdempotencyKey, ScanCursor, Timestamp, WorkerFilter, WorkerId};
 use golem_service_base::model::auth::{AuthCtx, EnvironmentAction};
 use golem_service_base::model::{ComponentFileSystemNode, GetOplogResponse};
 use golem_wasm::ValueAndType;
@@ -459,6 +462,7 @@ impl WorkerService {
         worker_id: &WorkerId,
         oplog_id: u64,
         data: Vec<u8>,
+        rejection: Option<String>,
         auth_ctx: AuthCtx,
     ) -> WorkerResult<bool> {
         let component = self
@@ -480,6 +484,7 @@ impl WorkerService {
                 worker_id,
                 oplog_id,
                 data,
+                rejection,
                 component.environment_id,
                 auth_ctx,
             )
@@ -714,6 +719,66 @@ impl WorkerService {
         Ok(result)
     }
 
+    pub async fn export_oplog(
+        &self,
+        worker_id: &WorkerId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Pin<Box<dyn Stream<Item = WorkerResult<Bytes>> + Send + 'static>>> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::ViewWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let archive = self
+            .worker_client
+            .export_oplog(worker_id, component.environment_id, auth_ctx)
+            .await?;
+
+        Ok(archive)
+    }
+
+    pub async fn import_oplog(
+        &self,
+        worker_id: &WorkerId,
+        archive: Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send + 'static>>,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<OplogIndex> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        let environment_auth_details = self
+            .auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::CreateWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let last_index = self
+            .worker_client
+            .import_oplog(
+                worker_id,
+                archive,
+                component.environment_id,
+                environment_auth_details.account_id_owning_environment,
+                auth_ctx,
+            )
+            .await?;
+
+        Ok(last_index)
+    }
+
     pub async fn get_file_system_node(
         &self,
         worker_id: &WorkerId,
@@ -844,6 +909,33 @@ impl WorkerService {
         Ok(())
     }
 
+    pub async fn change_labels(
+        &self,
+        worker_id: &WorkerId,
+        set: BTreeMap<String, String>,
+        removed: BTreeSet<String>,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<()> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::UpdateWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        self.worker_client
+            .change_labels(worker_id, set, removed, component.environment_id, auth_ctx)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn fork_worker(
         &self,
         source_worker_id: &WorkerId,
@@ -936,4 +1028,170 @@ impl WorkerService {
 
         Ok(canceled)
     }
+
+    pub async fn schedule_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: Option<IdempotencyKey>,
+        function_name: String,
+        params: InvocationParameters,
+        recurrence: ScheduleRecurrence,
+        start_at: Option<Timestamp>,
+        invocation_context: Option<InvocationContext>,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<RecurringInvocation> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        let environment_auth_details = self
+            .auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::UpdateWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let recurring_invocation = self
+            .worker_client
+            .schedule_recurring_invocation(
+                worker_id,
+                idempotency_key,
+                function_name,
+                params,
+                recurrence,
+                start_at,
+                invocation_context,
+                component.environment_id,
+                environment_auth_details.account_id_owning_environment,
+                auth_ctx,
+            )
+            .await?;
+
+        Ok(recurring_invocation)
+    }
+
+    pub async fn get_recurring_invocations(
+        &self,
+        worker_id: &WorkerId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RecurringInvocation>> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::ViewWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let invocations = self
+            .worker_client
+            .get_recurring_invocations(worker_id, component.environment_id, auth_ctx)
+            .await?;
+
+        Ok(invocations)
+    }
+
+    pub async fn cancel_recurring_invocation(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<bool> {
+        let component = self
+            .component_service
+            .get_latest_by_id(worker_id.component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::UpdateWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let canceled = self
+            .worker_client
+            .cancel_recurring_invocation(
+                worker_id,
+                idempotency_key,
+                component.environment_id,
+                auth_ctx,
+            )
+            .await?;
+
+        Ok(canceled)
+    }
+
+    /// Lists the agents of the component that are going to be deleted by the retention policies
+    /// of the worker executors, without deleting them
+    pub async fn get_retention_report(
+        &self,
+        component_id: &ComponentId,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<RetentionCandidate>> {
+        let component = self
+            .component_service
+            .get_latest_by_id(*component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::ViewWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let candidates = self
+            .worker_client
+            .get_retention_report(component_id, component.environment_id, auth_ctx)
+            .await?;
+
+        Ok(candidates)
+    }
+
+    /// Searches the oplogs of all the agents of the component, using the oplog search index of
+    /// the worker executors
+    pub async fn search_component_oplog(
+        &self,
+        component_id: &ComponentId,
+        query: String,
+        count: u64,
+        auth_ctx: AuthCtx,
+    ) -> WorkerResult<Vec<OplogSearchHit>> {
+        let component = self
+            .component_service
+            .get_latest_by_id(*component_id)
+            .await?;
+
+        self.auth_service
+            .authorize_environment_actions(
+                component.environment_id,
+                EnvironmentAction::ViewWorker,
+                &auth_ctx,
+            )
+            .await?;
+
+        let hits = self
+            .worker_client
+            .search_component_oplog(
+                component_id,
+                component.environment_id,
+                query,
+                count,
+                auth_ctx,
+            )
+            .await?;
+
+        Ok(hits)
+    }
 }
@@ -28,6 +28,7 @@ use golem_common::model::http_api_definition::{
 };
 use golem_common::model::http_api_deployment::{
     HttpApiDeploymentAgentOptions, HttpApiDeploymentCreation, HttpApiDeploymentUpdate,
+    HttpRateLimit, HttpRateLimitScope,
 };
 use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
 use golem_test_framework::dsl::{TestDsl, TestDslExtended};
@@ -114,6 +115,46 @@ async fn create_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::
     Ok(())
 }
 
+#[test]
+#[tracing::instrument]
+async fn create_http_api_deployment_with_invalid_rate_limit(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+    let domain = user.register_domain(&env.id).await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let http_api_deployment_creation = HttpApiDeploymentCreation {
+        domain,
+        agents: BTreeMap::from_iter([(
+            AgentTypeName("test-api".to_string()),
+            HttpApiDeploymentAgentOptions {
+                rate_limits: vec![HttpRateLimit {
+                    scope: HttpRateLimitScope::ClientIp,
+                    requests: 0,
+                    interval_seconds: 60,
+                    burst: None,
+                }],
+                ..Default::default()
+            },
+        )]),
+    };
+
+    let result = client
+        .create_http_api_deployment(&env.id.0, &http_api_deployment_creation)
+        .await;
+
+    assert!(
+        let Err(golem_client::Error::Item(
+            RegistryServiceCreateHttpApiDeploymentError::Error400(_)
+        )) = result
+    );
+
+    Ok(())
+}
+
 #[test]
 #[tracing::instrument]
 async fn update_http_api_deployment(deps: &EnvBasedTestDependencies) -> anyhow::Result<()> {
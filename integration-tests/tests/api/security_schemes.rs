@@ -14,11 +14,15 @@
 
 use assert2::assert;
 use golem_client::api::{
-    RegistryServiceClient, RegistryServiceCreateSecuritySchemeError,
-    RegistryServiceGetEnvironmentSecuritySchemesError, RegistryServiceGetSecuritySchemeError,
+    RegistryServiceClient, RegistryServiceCreateSecuritySchemeApiKeyError,
+    RegistryServiceCreateSecuritySchemeError, RegistryServiceGetApiKeySecuritySchemeError,
+    RegistryServiceGetEnvironmentSecuritySchemesError,
+    RegistryServiceGetSecuritySchemeApiKeysError, RegistryServiceGetSecuritySchemeError,
+    RegistryServiceRevokeSecuritySchemeApiKeyError,
 };
 use golem_common::model::security_scheme::{
-    Provider, SecuritySchemeCreation, SecuritySchemeName, SecuritySchemeUpdate,
+    ApiKeyCreation, ApiKeySecuritySchemeCreation, Provider, SecuritySchemeCreation,
+    SecuritySchemeMode, SecuritySchemeName, SecuritySchemeUpdate,
 };
 use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
 use golem_test_framework::dsl::TestDslExtended;
@@ -41,6 +45,9 @@ async fn create_and_fetch_security_scheme(deps: &EnvBasedTestDependencies) -> an
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let security_scheme = client
@@ -77,6 +84,9 @@ async fn delete_security_scheme(deps: &EnvBasedTestDependencies) -> anyhow::Resu
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let security_scheme = client
@@ -121,6 +131,9 @@ async fn invalid_redirect_url_fails_with_bad_request(
         client_secret: "client_secret".to_string(),
         redirect_url: "http//example.com".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let result = client
@@ -155,6 +168,9 @@ async fn other_users_cannot_see_security_scheme(
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let security_scheme = client_1
@@ -200,6 +216,9 @@ async fn creating_two_security_schemes_with_same_name_fails(
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     client
@@ -237,6 +256,9 @@ async fn security_scheme_name_can_be_reused_after_deletion(
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let security_scheme = client
@@ -257,6 +279,66 @@ async fn security_scheme_name_can_be_reused_after_deletion(
     Ok(())
 }
 
+#[test]
+#[tracing::instrument]
+async fn custom_provider_requires_issuer_url(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let mut security_scheme_creation = SecuritySchemeCreation {
+        name: SecuritySchemeName("test-scheme".to_string()),
+        provider_type: Provider::Custom,
+        client_id: "client_id".to_string(),
+        client_secret: "client_secret".to_string(),
+        redirect_url: "http://localhost:9006/auth/callback".to_string(),
+        scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
+    };
+
+    {
+        let result = client
+            .create_security_scheme(&env.id.0, &security_scheme_creation)
+            .await;
+
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceCreateSecuritySchemeError::Error400(_)
+            )) = result
+        );
+    }
+
+    security_scheme_creation.issuer_url = Some("http://idp.example.com/realms/golem".to_string());
+
+    {
+        let result = client
+            .create_security_scheme(&env.id.0, &security_scheme_creation)
+            .await;
+
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceCreateSecuritySchemeError::Error400(_)
+            )) = result
+        );
+    }
+
+    security_scheme_creation.issuer_url = Some("https://idp.example.com/realms/golem".to_string());
+
+    let security_scheme = client
+        .create_security_scheme(&env.id.0, &security_scheme_creation)
+        .await?;
+
+    assert!(security_scheme.provider_type == Provider::Custom);
+    assert!(security_scheme.issuer_url == security_scheme_creation.issuer_url);
+
+    Ok(())
+}
+
 #[test]
 #[tracing::instrument]
 async fn security_scheme_update(deps: &EnvBasedTestDependencies) -> anyhow::Result<()> {
@@ -273,6 +355,9 @@ async fn security_scheme_update(deps: &EnvBasedTestDependencies) -> anyhow::Resu
         client_secret: "client_secret".to_string(),
         redirect_url: "http://localhost:9006/auth/callback".to_string(),
         scopes: vec!["user".to_string(), "admin".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let security_scheme = client
@@ -286,6 +371,9 @@ async fn security_scheme_update(deps: &EnvBasedTestDependencies) -> anyhow::Resu
         client_secret: Some("client_secret_1".to_string()),
         redirect_url: Some("http://localhost:9006/auth/callback_1".to_string()),
         scopes: Some(vec!["user_1".to_string(), "admin_1".to_string()]),
+        issuer_url: None,
+        mode: None,
+        audience: None,
     };
 
     let updated_security_scheme = client
@@ -303,3 +391,336 @@ async fn security_scheme_update(deps: &EnvBasedTestDependencies) -> anyhow::Resu
 
     Ok(())
 }
+
+#[test]
+#[tracing::instrument]
+async fn bearer_token_security_scheme(deps: &EnvBasedTestDependencies) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let security_scheme_creation = SecuritySchemeCreation {
+        name: SecuritySchemeName("test-scheme".to_string()),
+        provider_type: Provider::Custom,
+        client_id: "client_id".to_string(),
+        client_secret: "client_secret".to_string(),
+        redirect_url: "http://localhost:9006/auth/callback".to_string(),
+        scopes: vec!["counters:write".to_string()],
+        issuer_url: Some("https://idp.example.com/realms/golem".to_string()),
+        mode: Some(SecuritySchemeMode::BearerToken),
+        audience: Some("counter-api".to_string()),
+    };
+
+    let security_scheme = client
+        .create_security_scheme(&env.id.0, &security_scheme_creation)
+        .await?;
+
+    assert!(security_scheme.mode == SecuritySchemeMode::BearerToken);
+    assert!(security_scheme.audience == security_scheme_creation.audience);
+
+    let security_scheme_update = SecuritySchemeUpdate {
+        current_revision: security_scheme.revision,
+        provider_type: None,
+        client_id: None,
+        client_secret: None,
+        redirect_url: None,
+        scopes: None,
+        issuer_url: None,
+        mode: Some(SecuritySchemeMode::AuthorizationCode),
+        audience: Some("".to_string()),
+    };
+
+    let updated_security_scheme = client
+        .update_security_scheme(&security_scheme.id.0, &security_scheme_update)
+        .await?;
+
+    assert!(updated_security_scheme.mode == SecuritySchemeMode::AuthorizationCode);
+    assert!(updated_security_scheme.audience.is_none());
+    assert!(updated_security_scheme.issuer_url == security_scheme_creation.issuer_url);
+
+    Ok(())
+}
+
+fn api_key_security_scheme_creation() -> ApiKeySecuritySchemeCreation {
+    ApiKeySecuritySchemeCreation {
+        name: SecuritySchemeName("partner-keys".to_string()),
+    }
+}
+
+fn oidc_security_scheme_creation() -> SecuritySchemeCreation {
+    SecuritySchemeCreation {
+        name: SecuritySchemeName("google".to_string()),
+        provider_type: Provider::Google,
+        client_id: "client_id".to_string(),
+        client_secret: "client_secret".to_string(),
+        redirect_url: "http://localhost:9006/auth/callback".to_string(),
+        scopes: vec!["openid".to_string()],
+        issuer_url: None,
+        mode: None,
+        audience: None,
+    }
+}
+
+#[test]
+#[tracing::instrument]
+async fn api_key_security_schemes_are_managed_separately(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let api_key_security_scheme = client
+        .create_api_key_security_scheme(&env.id.0, &api_key_security_scheme_creation())
+        .await?;
+    let oidc_security_scheme = client
+        .create_security_scheme(&env.id.0, &oidc_security_scheme_creation())
+        .await?;
+
+    assert!(api_key_security_scheme.name == api_key_security_scheme_creation().name);
+
+    {
+        let fetched = client
+            .get_api_key_security_scheme(&api_key_security_scheme.id.0)
+            .await?;
+        assert!(fetched == api_key_security_scheme);
+    }
+
+    {
+        let result = client
+            .get_environment_api_key_security_schemes(&env.id.0)
+            .await?;
+        assert!(result.values == vec![api_key_security_scheme.clone()]);
+    }
+
+    {
+        let result = client.get_environment_security_schemes(&env.id.0).await?;
+        assert!(result.values == vec![oidc_security_scheme.clone()]);
+    }
+
+    {
+        let result = client
+            .get_security_scheme(&api_key_security_scheme.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceGetSecuritySchemeError::Error404(_)
+            )) = result
+        );
+    }
+
+    {
+        let result = client
+            .get_api_key_security_scheme(&oidc_security_scheme.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceGetApiKeySecuritySchemeError::Error404(_)
+            )) = result
+        );
+    }
+
+    client
+        .delete_api_key_security_scheme(
+            &api_key_security_scheme.id.0,
+            api_key_security_scheme.revision.into(),
+        )
+        .await?;
+
+    {
+        let result = client
+            .get_api_key_security_scheme(&api_key_security_scheme.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceGetApiKeySecuritySchemeError::Error404(_)
+            )) = result
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn create_list_and_revoke_api_keys(deps: &EnvBasedTestDependencies) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let security_scheme = client
+        .create_api_key_security_scheme(&env.id.0, &api_key_security_scheme_creation())
+        .await?;
+
+    let api_key_creation = ApiKeyCreation {
+        name: "partner".to_string(),
+        scopes: vec!["counters:read".to_string()],
+        expires_at: Some(chrono::Utc::now() + chrono::TimeDelta::days(1)),
+    };
+
+    let api_key = client
+        .create_security_scheme_api_key(&security_scheme.id.0, &api_key_creation)
+        .await?;
+
+    assert!(api_key.name == api_key_creation.name);
+    assert!(api_key.scopes == api_key_creation.scopes);
+    assert!(api_key.security_scheme_id == security_scheme.id);
+    assert!(!api_key.secret.is_empty());
+
+    {
+        let result = client
+            .create_security_scheme_api_key(&security_scheme.id.0, &api_key_creation)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceCreateSecuritySchemeApiKeyError::Error409(_)
+            )) = result
+        );
+    }
+
+    let api_key = api_key.without_secret();
+
+    {
+        let result = client
+            .get_security_scheme_api_keys(&security_scheme.id.0)
+            .await?;
+        assert!(result.values == vec![api_key.clone()]);
+    }
+
+    {
+        let revoked = client
+            .revoke_security_scheme_api_key(&security_scheme.id.0, &api_key.id.0)
+            .await?;
+        assert!(revoked == api_key);
+    }
+
+    {
+        let result = client
+            .get_security_scheme_api_keys(&security_scheme.id.0)
+            .await?;
+        assert!(result.values.is_empty());
+    }
+
+    {
+        let result = client
+            .revoke_security_scheme_api_key(&security_scheme.id.0, &api_key.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceRevokeSecuritySchemeApiKeyError::Error404(_)
+            )) = result
+        );
+    }
+
+    // the name of a revoked key can be reused
+    let reissued_api_key = client
+        .create_security_scheme_api_key(&security_scheme.id.0, &api_key_creation)
+        .await?;
+    assert!(reissued_api_key.id != api_key.id);
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn api_keys_require_api_key_security_scheme(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?;
+    let (_, env) = user.app_and_env().await?;
+
+    let client = deps.registry_service().client(&user.token).await;
+
+    let security_scheme = client
+        .create_security_scheme(&env.id.0, &oidc_security_scheme_creation())
+        .await?;
+
+    let result = client
+        .create_security_scheme_api_key(
+            &security_scheme.id.0,
+            &ApiKeyCreation {
+                name: "partner".to_string(),
+                scopes: vec![],
+                expires_at: None,
+            },
+        )
+        .await;
+
+    assert!(
+        let Err(golem_client::Error::Item(
+            RegistryServiceCreateSecuritySchemeApiKeyError::Error404(_)
+        )) = result
+    );
+
+    Ok(())
+}
+
+#[test]
+#[tracing::instrument]
+async fn other_users_cannot_manage_api_keys(deps: &EnvBasedTestDependencies) -> anyhow::Result<()> {
+    let user_1 = deps.user().await?;
+    let user_2 = deps.user().await?;
+    let (_, env) = user_1.app_and_env().await?;
+
+    let client_1 = deps.registry_service().client(&user_1.token).await;
+    let client_2 = deps.registry_service().client(&user_2.token).await;
+
+    let security_scheme = client_1
+        .create_api_key_security_scheme(&env.id.0, &api_key_security_scheme_creation())
+        .await?;
+
+    let api_key_creation = ApiKeyCreation {
+        name: "partner".to_string(),
+        scopes: vec![],
+        expires_at: None,
+    };
+
+    let api_key = client_1
+        .create_security_scheme_api_key(&security_scheme.id.0, &api_key_creation)
+        .await?;
+
+    {
+        let result = client_2
+            .create_security_scheme_api_key(&security_scheme.id.0, &api_key_creation)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceCreateSecuritySchemeApiKeyError::Error404(_)
+            )) = result
+        );
+    }
+
+    {
+        let result = client_2
+            .get_security_scheme_api_keys(&security_scheme.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceGetSecuritySchemeApiKeysError::Error404(_)
+            )) = result
+        );
+    }
+
+    {
+        let result = client_2
+            .revoke_security_scheme_api_key(&security_scheme.id.0, &api_key.id.0)
+            .await;
+        assert!(
+            let Err(golem_client::Error::Item(
+                RegistryServiceRevokeSecuritySchemeApiKeyError::Error404(_)
+            )) = result
+        );
+    }
+
+    {
+        let result = client_1
+            .get_security_scheme_api_keys(&security_scheme.id.0)
+            .await?;
+        assert!(result.values == vec![api_key.without_secret()]);
+    }
+
+    Ok(())
+}
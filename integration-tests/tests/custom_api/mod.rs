@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod agent_http_routes_ts;
+mod rate_limits;
 // mod echo_agent;
 // mod shopping_cart;
 
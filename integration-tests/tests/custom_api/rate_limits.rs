@@ -0,0 +1,117 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::http::{HeaderMap, HeaderValue};
+use golem_client::api::RegistryServiceClient;
+use golem_common::model::agent::AgentTypeName;
+use golem_common::model::domain_registration::{Domain, DomainRegistrationCreation};
+use golem_common::model::http_api_deployment::{
+    HttpApiDeploymentAgentOptions, HttpApiDeploymentCreation, HttpRateLimit, HttpRateLimitScope,
+};
+use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
+use golem_test_framework::dsl::{TestDsl, TestDslExtended};
+use pretty_assertions::assert_eq;
+use reqwest::Url;
+use std::collections::BTreeMap;
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(EnvBasedTestDependencies);
+
+#[test]
+#[tracing::instrument]
+async fn exhausted_rate_limit_responds_with_retry_after(
+    deps: &EnvBasedTestDependencies,
+) -> anyhow::Result<()> {
+    let user = deps.user().await?.with_auto_deploy(false);
+    let client = deps.registry_service().client(&user.token).await;
+    let (_, env) = user.app_and_env().await?;
+
+    let domain = Domain(format!("{}.golem.cloud", env.id));
+
+    client
+        .create_domain_registration(
+            &env.id.0,
+            &DomainRegistrationCreation {
+                domain: domain.clone(),
+            },
+        )
+        .await?;
+
+    user.component(&env.id, "golem_it_agent_http_routes_ts")
+        .name("golem-it:agent-http-routes-ts")
+        .store()
+        .await?;
+
+    client
+        .create_http_api_deployment(
+            &env.id.0,
+            &HttpApiDeploymentCreation {
+                domain: domain.clone(),
+                agents: BTreeMap::from_iter([(
+                    AgentTypeName("http-agent".to_string()),
+                    HttpApiDeploymentAgentOptions {
+                        rate_limits: vec![HttpRateLimit {
+                            scope: HttpRateLimitScope::Route,
+                            requests: 2,
+                            interval_seconds: 3600,
+                            burst: None,
+                        }],
+                        ..Default::default()
+                    },
+                )]),
+            },
+        )
+        .await?;
+
+    user.deploy_environment(&env.id).await?;
+
+    let client = {
+        let mut headers = HeaderMap::new();
+        headers.insert("Host", HeaderValue::from_str(&domain.0)?);
+        reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?
+    };
+    let base_url = Url::parse(&format!("http://127.0.0.1:{}", user.custom_request_port()))?;
+    let limited_route = base_url.join("/http-agents/test-agent/string-path-var/foo")?;
+
+    for _ in 0..2 {
+        let response = client.get(limited_route.clone()).send().await?;
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+    }
+
+    let response = client.get(limited_route.clone()).send().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+
+    // two requests per hour refill a token every 30 minutes
+    let retry_after: u64 = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .expect("Retry-After header")
+        .to_str()?
+        .parse()?;
+    assert!(
+        (1..=1800).contains(&retry_after),
+        "unexpected Retry-After: {retry_after}"
+    );
+
+    // other routes have their own buckets
+    let response = client
+        .get(base_url.join("/http-agents/test-agent/multi-path-vars/foo/bar")?)
+        .send()
+        .await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    Ok(())
+}
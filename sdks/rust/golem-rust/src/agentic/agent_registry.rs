@@ -16,7 +16,7 @@ use crate::{
     agentic::{agent_initiator::AgentInitiator, ResolvedAgent},
     golem_agentic::{exports::golem::agent::guest::AgentType, golem::agent::common::ElementSchema},
 };
-use golem_wasm::golem_rpc_0_2_x::types::parse_uuid;
+use golem_wasm::golem_rpc_0_2_2::types::parse_uuid;
 use golem_wasm::{AgentId, ComponentId};
 use std::rc::Rc;
 use std::{cell::RefCell, future::Future};
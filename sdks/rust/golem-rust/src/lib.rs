@@ -31,7 +31,7 @@ pub mod bindings {
         generate_unused_types: true,
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
         }
@@ -49,7 +49,7 @@ pub mod load_snapshot {
         generate_unused_types: true,
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
 
@@ -85,7 +85,7 @@ pub mod save_snapshot {
         generate_unused_types: true,
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
 
@@ -121,7 +121,7 @@ pub mod golem_agentic {
         generate_unused_types: true,
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
 
@@ -157,7 +157,7 @@ pub mod golem_ai {
         additional_derives: [crate::Schema],
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:io/error@0.2.3": wstd::wasi::io::error,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
@@ -198,7 +198,7 @@ pub mod oplog_processor {
         generate_unused_types: true,
         pub_export_macro: true,
         with: {
-            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_x::types,
+            "golem:rpc/types@0.2.2": golem_wasm::golem_rpc_0_2_2::types,
             "wasi:io/poll@0.2.3": wstd::wasi::io::poll,
             "wasi:clocks/wall-clock@0.2.3": wstd::wasi::clocks::wall_clock,
 
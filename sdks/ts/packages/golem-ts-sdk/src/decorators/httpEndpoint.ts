@@ -36,6 +36,7 @@ export type EndpointDecoratorOptions = {
   };
   headers?: Record<string, string>;
   auth?: boolean;
+  scopes?: string[];
   cors?: string[];
 };
 
@@ -110,9 +111,10 @@ export type EndpointDecoratorOptions = {
  *
  * ### Authentication
  * - `auth: true` requires the request to be authenticated.
+ * - `scopes` lists the scopes bearer tokens must grant to call the endpoint.
  * - Example:
  * ```ts
- * @endpoint({ get: '/secure-data', auth: true })
+ * @endpoint({ get: '/secure-data', auth: true, scopes: ['data:read'] })
  * getSecureData(userId: string) { ... }
  * ```
  *
@@ -191,7 +193,10 @@ export function endpoint(opts: EndpointDecoratorOptions) {
       ([headerName, variableName]) => ({ headerName, variableName }),
     );
 
-    const authDetails: AuthDetails = { required: opts.auth ?? false };
+    const authDetails: AuthDetails = {
+      required: opts.auth ?? false,
+      scopes: opts.scopes ?? [],
+    };
 
     const corsOptions: CorsOptions = { allowedPatterns: opts.cors ?? [] };
 
@@ -29,7 +29,7 @@ export function getHttpMountDetails(
 
   return {
     pathPrefix,
-    authDetails: agentDecoratorOptions.auth ? { required: true } : { required: false },
+    authDetails: { required: agentDecoratorOptions.auth ?? false, scopes: [] },
     phantomAgent: false,
     corsOptions: {
       allowedPatterns: agentDecoratorOptions.cors ?? [],
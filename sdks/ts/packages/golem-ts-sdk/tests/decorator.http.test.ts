@@ -36,7 +36,7 @@ describe('Http Agent class', () => {
     expect(simpleHttpAgent.httpEndpoint).toEqual([
       {
         httpMethod: { tag: 'get' },
-        authDetails: { required: false },
+        authDetails: { required: false, scopes: [] },
         queryVars: [],
         corsOptions: {
           allowedPatterns: [],
@@ -114,7 +114,7 @@ describe('Http Agent class', () => {
     expect(simpleHttpAgent.httpMount).toBeDefined();
     expect(simpleHttpAgent.httpMount).toEqual({
       pathPrefix: expectedPathPrefix,
-      authDetails: { required: true },
+      authDetails: { required: true, scopes: [] },
       phantomAgent: false,
       corsOptions: {
         allowedPatterns: ['https://app.acme.com', 'https://staging.acme.com'],
@@ -136,7 +136,7 @@ describe('Http Agent class', () => {
     expect(complexHttpAgent.httpEndpoint).toEqual([
       {
         httpMethod: { tag: 'get' },
-        authDetails: { required: false },
+        authDetails: { required: false, scopes: [] },
         queryVars: [],
         corsOptions: {
           allowedPatterns: [],
@@ -164,7 +164,7 @@ describe('Http Agent class', () => {
     expect(complexHttpAgent.httpEndpoint).toEqual([
       {
         httpMethod: { tag: 'post' },
-        authDetails: { required: false },
+        authDetails: { required: false, scopes: [] },
         queryVars: [
           {
             queryParamName: 'l',
@@ -198,7 +198,7 @@ describe('Http Agent class', () => {
     expect(complexHttpAgentMetadata.httpEndpoint).toEqual([
       {
         httpMethod: { tag: 'custom', val: 'patch' },
-        authDetails: { required: false },
+        authDetails: { required: false, scopes: [] },
         queryVars: [
           {
             queryParamName: 'l',
@@ -222,7 +222,7 @@ describe('Http Agent class', () => {
       },
       {
         httpMethod: { tag: 'get' },
-        authDetails: { required: true },
+        authDetails: { required: true, scopes: ['greetings:read'] },
         queryVars: [
           {
             queryParamName: 'lx',
@@ -255,7 +255,7 @@ describe('Http Agent class', () => {
       },
       {
         httpMethod: { tag: 'get' },
-        authDetails: { required: false },
+        authDetails: { required: false, scopes: [] },
         queryVars: [
           {
             queryParamName: 'l',
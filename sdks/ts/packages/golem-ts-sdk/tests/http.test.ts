@@ -34,7 +34,7 @@ describe('getHttpMountDetails – basic behavior', () => {
 
     expect(result.pathPrefix).toEqual([{ tag: 'literal', val: 'chats' }]);
 
-    expect(result.authDetails).toEqual({ required: false });
+    expect(result.authDetails).toEqual({ required: false, scopes: [] });
     expect(result.phantomAgent).toBe(false);
     expect(result.corsOptions.allowedPatterns).toEqual([]);
     expect(result.webhookSuffix).toEqual([]);
@@ -80,7 +80,7 @@ describe('getHttpMountDetails – auth and cors', () => {
 
     const result = getHttpMountDetails(opts)!;
 
-    expect(result.authDetails).toEqual({ required: true });
+    expect(result.authDetails).toEqual({ required: true, scopes: [] });
     expect(result.corsOptions.allowedPatterns).toEqual(['https://app.acme.com']);
   });
 });
@@ -153,7 +153,7 @@ describe('validateHttpMountWithConstructor', () => {
         tag: 'path-variable',
         val: { variableName: v },
       })),
-      authDetails: { required: false },
+      authDetails: { required: false, scopes: [] },
       phantomAgent: false,
       corsOptions: { allowedPatterns: [] },
       webhookSuffix: [],
@@ -234,7 +234,7 @@ describe('validateHttpEndpoint', () => {
 
   const httpMountDetails: HttpMountDetails = {
     pathPrefix: [{ tag: 'literal', val: 'test' }],
-    authDetails: { required: false },
+    authDetails: { required: false, scopes: [] },
     phantomAgent: false,
     corsOptions: { allowedPatterns: [] },
     webhookSuffix: [],
@@ -533,6 +533,7 @@ class ComplexHttpAgent extends BaseAgent {
     get: '/greet?lx={location}&nm={name}',
     cors: ['*'],
     auth: true,
+    scopes: ['greetings:read'],
     headers: { 'X-Foo': 'location', 'X-Bar': 'name' },
   })
 
@@ -74,6 +74,7 @@ declare module 'golem:agent/common' {
   };
   export type AuthDetails = {
     required: boolean;
+    scopes: string[];
   };
   export type HttpMountDetails = {
     pathPrefix: PathSegment[];
@@ -108,6 +109,12 @@ declare module 'golem:agent/common' {
   export type GolemUserPrincipal = {
     accountId: AccountId;
   };
+  export type ApiKeyPrincipal = {
+    keyId: string;
+    name: string;
+    securityScheme: string;
+    scopes: string[];
+  };
   export type Principal = 
   {
     tag: 'oidc'
@@ -123,6 +130,10 @@ declare module 'golem:agent/common' {
   } |
   {
     tag: 'anonymous'
+  } |
+  {
+    tag: 'api-key'
+    val: ApiKeyPrincipal
   };
   export type TextType = {
     languageCode: string;
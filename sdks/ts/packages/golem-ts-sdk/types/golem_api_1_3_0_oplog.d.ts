@@ -275,6 +275,15 @@ declare module 'golem:api/oplog@1.3.0' {
     timestamp: Datetime;
     beginIndex: OplogIndex;
   };
+  export type SnapshotParameters = {
+    timestamp: Datetime;
+    data: Uint8Array;
+  };
+  export type ChangeLabelsParameters = {
+    timestamp: Datetime;
+    set: [string, string][];
+    removed: string[];
+  };
   export type Timestamp = {
     timestamp: Datetime;
   };
@@ -485,5 +494,15 @@ declare module 'golem:api/oplog@1.3.0' {
   {
     tag: 'rolled-back-remote-transaction'
     val: RemoteTransactionParameters
+  } |
+  /** A snapshot of the agent's state taken automatically by the executor */
+  {
+    tag: 'snapshot'
+    val: SnapshotParameters
+  } |
+  /** Sets and removes labels of the agent */
+  {
+    tag: 'change-labels'
+    val: ChangeLabelsParameters
   };
 }